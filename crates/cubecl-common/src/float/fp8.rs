@@ -0,0 +1,480 @@
+use bytemuck::{Pod, Zeroable};
+use core::cmp::Ordering;
+use core::fmt::{Debug, Display, Formatter};
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use num_traits::{NumCast, ToPrimitive};
+
+/// An 8-bit floating point type with 4 exponent bits and 3 mantissa bits, following the
+/// `E4M3` encoding of the [OCP 8-bit floating point specification].
+///
+/// This format has no infinities and a single NaN representation per sign. Its largest finite
+/// value is `448`. Conversions from [`f32`] saturate to that value instead of overflowing.
+///
+/// [OCP 8-bit floating point specification]: https://www.opencompute.org/documents/ocp-8-bit-floating-point-specification-ofp8-revision-1-0-2023-12-01-pdf-1
+#[allow(non_camel_case_types)]
+#[repr(transparent)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Default, Zeroable, Pod)]
+pub struct e4m3(u8);
+
+/// An 8-bit floating point type with 5 exponent bits and 2 mantissa bits, following the
+/// `E5M2` encoding of the [OCP 8-bit floating point specification].
+///
+/// This format follows the IEEE 754 conventions for infinities and NaN, it is the upper byte of
+/// an [`f16`](half::f16). Its largest finite value is `57344`.
+///
+/// [OCP 8-bit floating point specification]: https://www.opencompute.org/documents/ocp-8-bit-floating-point-specification-ofp8-revision-1-0-2023-12-01-pdf-1
+#[allow(non_camel_case_types)]
+#[repr(transparent)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Default, Zeroable, Pod)]
+pub struct e5m2(u8);
+
+impl e4m3 {
+    /// Largest finite [`e4m3`] value, `448`.
+    pub const MAX: Self = Self::from_bits(0x7E);
+    /// Smallest finite [`e4m3`] value, `-448`.
+    pub const MIN: Self = Self::from_bits(0xFE);
+    /// Smallest positive normal [`e4m3`] value, `2^-6`.
+    pub const MIN_POSITIVE: Self = Self::from_bits(0x08);
+    /// Difference between `1.0` and the next larger representable number, `2^-3`.
+    pub const EPSILON: Self = Self::from_bits(0x20);
+    /// [`e4m3`] Not a Number (NaN).
+    pub const NAN: Self = Self::from_bits(0x7F);
+    /// [`e4m3`] positive zero, `0`.
+    pub const ZERO: Self = Self::from_bits(0x00);
+    /// [`e4m3`] one, `1`.
+    pub const ONE: Self = Self::from_bits(0x38);
+
+    /// Constructs a [`e4m3`] value from the raw bits.
+    #[inline]
+    #[must_use]
+    pub const fn from_bits(bits: u8) -> e4m3 {
+        e4m3(bits)
+    }
+
+    /// Converts a [`e4m3`] into the underlying bit representation.
+    #[inline]
+    #[must_use]
+    pub const fn to_bits(self) -> u8 {
+        self.0
+    }
+
+    /// Constructs a [`e4m3`] value from a 32-bit floating point value.
+    ///
+    /// This operation is lossy. Values are rounded to the nearest representable value, with ties
+    /// to even. Values too large to fit, including ±∞, saturate to ±448. NaN values are preserved.
+    #[inline]
+    #[must_use]
+    pub fn from_f32(value: f32) -> e4m3 {
+        let bits = value.to_bits();
+        let sign = ((bits >> 24) & 0x80) as u8;
+
+        if value.is_nan() {
+            return e4m3(sign | 0x7F);
+        }
+
+        match encode(bits, 3, 7) {
+            Some(magnitude) if magnitude <= 0x7E => e4m3(sign | magnitude as u8),
+            _ => e4m3(sign | 0x7E),
+        }
+    }
+
+    /// Constructs a [`e4m3`] value from a 64-bit floating point value.
+    ///
+    /// The value is first converted to [`f32`], see [`e4m3::from_f32`].
+    #[inline]
+    #[must_use]
+    pub fn from_f64(value: f64) -> e4m3 {
+        Self::from_f32(value as f32)
+    }
+
+    /// Converts a [`e4m3`] value into an [`f32`] value.
+    ///
+    /// This conversion is lossless as all values can be represented exactly in [`f32`].
+    #[inline]
+    #[must_use]
+    pub fn to_f32(self) -> f32 {
+        if self.0 & 0x7F == 0x7F {
+            return f32::NAN;
+        }
+
+        decode(self.0, 3, 7)
+    }
+
+    /// Converts a [`e4m3`] value into an [`f64`] value.
+    ///
+    /// This conversion is lossless as all values can be represented exactly in [`f64`].
+    #[inline]
+    #[must_use]
+    pub fn to_f64(self) -> f64 {
+        self.to_f32() as f64
+    }
+
+    /// Whether this value is NaN.
+    #[inline]
+    #[must_use]
+    pub const fn is_nan(self) -> bool {
+        self.0 & 0x7F == 0x7F
+    }
+}
+
+impl e5m2 {
+    /// Largest finite [`e5m2`] value, `57344`.
+    pub const MAX: Self = Self::from_bits(0x7B);
+    /// Smallest finite [`e5m2`] value, `-57344`.
+    pub const MIN: Self = Self::from_bits(0xFB);
+    /// Smallest positive normal [`e5m2`] value, `2^-14`.
+    pub const MIN_POSITIVE: Self = Self::from_bits(0x04);
+    /// Difference between `1.0` and the next larger representable number, `2^-2`.
+    pub const EPSILON: Self = Self::from_bits(0x34);
+    /// [`e5m2`] positive infinity (+∞).
+    pub const INFINITY: Self = Self::from_bits(0x7C);
+    /// [`e5m2`] negative infinity (-∞).
+    pub const NEG_INFINITY: Self = Self::from_bits(0xFC);
+    /// [`e5m2`] Not a Number (NaN).
+    pub const NAN: Self = Self::from_bits(0x7E);
+    /// [`e5m2`] positive zero, `0`.
+    pub const ZERO: Self = Self::from_bits(0x00);
+    /// [`e5m2`] one, `1`.
+    pub const ONE: Self = Self::from_bits(0x3C);
+
+    /// Constructs a [`e5m2`] value from the raw bits.
+    #[inline]
+    #[must_use]
+    pub const fn from_bits(bits: u8) -> e5m2 {
+        e5m2(bits)
+    }
+
+    /// Converts a [`e5m2`] into the underlying bit representation.
+    #[inline]
+    #[must_use]
+    pub const fn to_bits(self) -> u8 {
+        self.0
+    }
+
+    /// Constructs a [`e5m2`] value from a 32-bit floating point value.
+    ///
+    /// This operation is lossy. Values are rounded to the nearest representable value, with ties
+    /// to even. If the value is too large to fit, ±∞ will result. NaN values are preserved.
+    #[inline]
+    #[must_use]
+    pub fn from_f32(value: f32) -> e5m2 {
+        let bits = value.to_bits();
+        let sign = ((bits >> 24) & 0x80) as u8;
+
+        if value.is_nan() {
+            return e5m2(sign | 0x7E);
+        }
+
+        match encode(bits, 2, 15) {
+            Some(magnitude) if magnitude < 0x7C => e5m2(sign | magnitude as u8),
+            _ => e5m2(sign | 0x7C),
+        }
+    }
+
+    /// Constructs a [`e5m2`] value from a 64-bit floating point value.
+    ///
+    /// The value is first converted to [`f32`], see [`e5m2::from_f32`].
+    #[inline]
+    #[must_use]
+    pub fn from_f64(value: f64) -> e5m2 {
+        Self::from_f32(value as f32)
+    }
+
+    /// Converts a [`e5m2`] value into an [`f32`] value.
+    ///
+    /// This conversion is lossless as all values can be represented exactly in [`f32`].
+    #[inline]
+    #[must_use]
+    pub fn to_f32(self) -> f32 {
+        half::f16::from_bits((self.0 as u16) << 8).to_f32()
+    }
+
+    /// Converts a [`e5m2`] value into an [`f64`] value.
+    ///
+    /// This conversion is lossless as all values can be represented exactly in [`f64`].
+    #[inline]
+    #[must_use]
+    pub fn to_f64(self) -> f64 {
+        self.to_f32() as f64
+    }
+
+    /// Whether this value is NaN.
+    #[inline]
+    #[must_use]
+    pub const fn is_nan(self) -> bool {
+        self.0 & 0x7F > 0x7C
+    }
+}
+
+/// Encode the magnitude of a finite [`f32`] into an 8-bit float with `mantissa_bits` bits of
+/// mantissa and the given exponent `bias`, rounding to nearest even.
+///
+/// Returns `None` for infinities. Finite values that are too large for the target format yield a
+/// magnitude past its largest finite value, which is handled by the caller.
+fn encode(bits: u32, mantissa_bits: u32, bias: i32) -> Option<u32> {
+    let exponent = ((bits >> 23) & 0xFF) as i32;
+    let mantissa = bits & 0x7F_FFFF;
+
+    if exponent == 0xFF {
+        return None;
+    }
+
+    let exponent = exponent - 127 + bias;
+    let (mantissa, shift) = match exponent > 0 {
+        true => (mantissa, 23 - mantissa_bits),
+        // Subnormal in the target format, the implicit bit becomes explicit.
+        false => (
+            mantissa | 0x80_0000,
+            23 - mantissa_bits + (1 - exponent) as u32,
+        ),
+    };
+
+    if shift > 24 {
+        return Some(0);
+    }
+
+    let truncated = mantissa >> shift;
+    let remainder = mantissa & ((1 << shift) - 1);
+    let half = 1 << (shift - 1);
+    let rounded = match remainder > half || (remainder == half && truncated & 1 == 1) {
+        true => truncated + 1,
+        false => truncated,
+    };
+
+    // A mantissa overflow from rounding carries into the exponent.
+    match exponent > 0 {
+        true => Some(((exponent as u32) << mantissa_bits) + rounded),
+        false => Some(rounded),
+    }
+}
+
+/// Decode a finite 8-bit float with `mantissa_bits` bits of mantissa and the given exponent
+/// `bias` into an [`f32`].
+fn decode(bits: u8, mantissa_bits: u32, bias: i32) -> f32 {
+    let sign = ((bits & 0x80) as u32) << 24;
+    let exponent = ((bits & 0x7F) >> mantissa_bits) as i32;
+    let mantissa = (bits as u32) & ((1 << mantissa_bits) - 1);
+
+    let magnitude = match exponent {
+        0 => {
+            // Subnormal: `mantissa * 2^(1 - bias - mantissa_bits)`, both factors are exact.
+            let scale = f32::from_bits(((127 + 1 - bias - mantissa_bits as i32) as u32) << 23);
+            mantissa as f32 * scale
+        }
+        _ => f32::from_bits(
+            (((exponent - bias + 127) as u32) << 23) | (mantissa << (23 - mantissa_bits)),
+        ),
+    };
+
+    f32::from_bits(sign | magnitude.to_bits())
+}
+
+macro_rules! impl_fp8 {
+    ($ty:ident) => {
+        impl PartialEq for $ty {
+            fn eq(&self, other: &Self) -> bool {
+                self.to_f32() == other.to_f32()
+            }
+        }
+
+        impl PartialOrd for $ty {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                self.to_f32().partial_cmp(&other.to_f32())
+            }
+        }
+
+        impl Debug for $ty {
+            fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                Debug::fmt(&self.to_f32(), f)
+            }
+        }
+
+        impl Display for $ty {
+            fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                Display::fmt(&self.to_f32(), f)
+            }
+        }
+
+        impl Neg for $ty {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                Self(self.0 ^ 0x80)
+            }
+        }
+
+        impl Mul for $ty {
+            type Output = Self;
+
+            fn mul(self, rhs: Self) -> Self::Output {
+                Self::from_f32(self.to_f32() * rhs.to_f32())
+            }
+        }
+
+        impl MulAssign for $ty {
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl Div for $ty {
+            type Output = Self;
+
+            fn div(self, rhs: Self) -> Self::Output {
+                Self::from_f32(self.to_f32() / rhs.to_f32())
+            }
+        }
+
+        impl DivAssign for $ty {
+            fn div_assign(&mut self, rhs: Self) {
+                *self = *self / rhs;
+            }
+        }
+
+        impl Add for $ty {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                Self::from_f32(self.to_f32() + rhs.to_f32())
+            }
+        }
+
+        impl AddAssign for $ty {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl Sub for $ty {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                Self::from_f32(self.to_f32() - rhs.to_f32())
+            }
+        }
+
+        impl SubAssign for $ty {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl From<f32> for $ty {
+            fn from(value: f32) -> Self {
+                Self::from_f32(value)
+            }
+        }
+
+        impl From<$ty> for f32 {
+            fn from(value: $ty) -> Self {
+                value.to_f32()
+            }
+        }
+
+        impl ToPrimitive for $ty {
+            fn to_i64(&self) -> Option<i64> {
+                Some($ty::to_f32(*self) as i64)
+            }
+
+            fn to_u64(&self) -> Option<u64> {
+                Some($ty::to_f32(*self) as u64)
+            }
+
+            fn to_f32(&self) -> Option<f32> {
+                Some($ty::to_f32(*self))
+            }
+
+            fn to_f64(&self) -> Option<f64> {
+                Some($ty::to_f64(*self))
+            }
+        }
+
+        impl NumCast for $ty {
+            fn from<T: num_traits::ToPrimitive>(n: T) -> Option<Self> {
+                Some(Self::from_f32(n.to_f32()?))
+            }
+        }
+    };
+}
+
+impl_fp8!(e4m3);
+impl_fp8!(e5m2);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn e4m3_roundtrip_all_values() {
+        for bits in 0..=u8::MAX {
+            let value = e4m3::from_bits(bits);
+            if value.is_nan() {
+                assert!(e4m3::from_f32(value.to_f32()).is_nan());
+            } else {
+                assert_eq!(e4m3::from_f32(value.to_f32()).to_bits(), bits);
+            }
+        }
+    }
+
+    #[test]
+    fn e5m2_roundtrip_all_values() {
+        for bits in 0..=u8::MAX {
+            let value = e5m2::from_bits(bits);
+            if value.is_nan() {
+                assert!(e5m2::from_f32(value.to_f32()).is_nan());
+            } else {
+                assert_eq!(e5m2::from_f32(value.to_f32()).to_bits(), bits);
+            }
+        }
+    }
+
+    #[test]
+    fn e4m3_known_values() {
+        assert_eq!(e4m3::MAX.to_f32(), 448.0);
+        assert_eq!(e4m3::MIN_POSITIVE.to_f32(), 0.015625);
+        assert_eq!(e4m3::EPSILON.to_f32(), 0.125);
+        assert_eq!(e4m3::from_bits(0x01).to_f32(), 0.001953125);
+        assert_eq!(e4m3::from_f32(1.0).to_bits(), 0x38);
+        assert_eq!(e4m3::ZERO.to_f32(), 0.0);
+        assert_eq!(e4m3::ONE.to_f32(), 1.0);
+        assert_eq!(e4m3::from_f32(-2.5).to_f32(), -2.5);
+    }
+
+    #[test]
+    fn e5m2_known_values() {
+        assert_eq!(e5m2::MAX.to_f32(), 57344.0);
+        assert_eq!(e5m2::MIN_POSITIVE.to_f32(), 0.00006103515625);
+        assert_eq!(e5m2::EPSILON.to_f32(), 0.25);
+        assert_eq!(e5m2::INFINITY.to_f32(), f32::INFINITY);
+        assert_eq!(e5m2::from_f32(1.0).to_bits(), 0x3C);
+        assert_eq!(e5m2::ZERO.to_f32(), 0.0);
+        assert_eq!(e5m2::ONE.to_f32(), 1.0);
+    }
+
+    #[test]
+    fn rounds_to_nearest_even() {
+        // Halfway between 1.0 and 1.125, 1.0 has the even mantissa.
+        assert_eq!(e4m3::from_f32(1.0625).to_f32(), 1.0);
+        // Halfway between 1.125 and 1.25, 1.25 has the even mantissa.
+        assert_eq!(e4m3::from_f32(1.1875).to_f32(), 1.25);
+        assert_eq!(e5m2::from_f32(1.125).to_f32(), 1.0);
+        assert_eq!(e5m2::from_f32(1.375).to_f32(), 1.5);
+    }
+
+    #[test]
+    fn overflow() {
+        assert_eq!(e4m3::from_f32(1000.0).to_f32(), 448.0);
+        assert_eq!(e4m3::from_f32(f32::NEG_INFINITY).to_f32(), -448.0);
+        assert_eq!(e5m2::from_f32(1e6).to_f32(), f32::INFINITY);
+        assert_eq!(e5m2::from_f32(-1e6).to_f32(), f32::NEG_INFINITY);
+    }
+
+    #[test]
+    fn underflow_to_zero() {
+        assert_eq!(e4m3::from_f32(1e-6).to_bits(), 0x00);
+        assert_eq!(e4m3::from_f32(-1e-6).to_bits(), 0x80);
+        assert_eq!(e5m2::from_f32(1e-9).to_bits(), 0x00);
+    }
+}
@@ -1,5 +1,7 @@
+mod fp8;
 mod relaxed;
 mod tensor_float;
 
+pub use fp8::*;
 pub use relaxed::*;
 pub use tensor_float::*;
@@ -3,6 +3,56 @@ use cubecl_common::ExecutionMode;
 use crate::{compute::KernelDefinition, ir::Elem};
 use std::fmt::Display;
 
+/// Error returned when a compiler can't compile a kernel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompilationError {
+    /// The kernel uses an element type the compiler doesn't support.
+    UnsupportedElem(Elem),
+    /// The shared memories of the kernel don't fit in the shared memory of a cube on the device.
+    SharedMemoryLimitExceeded {
+        shared_memory_bytes: usize,
+        max_bytes: usize,
+    },
+    /// The kernel declares a pipeline without any stage.
+    PipelineWithoutStages,
+    /// The buffers of all the stages of a pipeline don't fit in the shared memory of a cube.
+    SharedMemoryExceeded {
+        num_stages: usize,
+        shared_memory_bytes: usize,
+        max_bytes: usize,
+    },
+}
+
+impl Display for CompilationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompilationError::UnsupportedElem(elem) => {
+                write!(f, "The element type {elem} isn't supported by the compiler")
+            }
+            CompilationError::SharedMemoryLimitExceeded {
+                shared_memory_bytes,
+                max_bytes,
+            } => write!(
+                f,
+                "The kernel needs {shared_memory_bytes} bytes of shared memory, but only \
+                 {max_bytes} bytes are available per cube"
+            ),
+            CompilationError::PipelineWithoutStages => {
+                write!(f, "A pipeline must have at least one stage")
+            }
+            CompilationError::SharedMemoryExceeded {
+                num_stages,
+                shared_memory_bytes,
+                max_bytes,
+            } => write!(
+                f,
+                "A pipeline with {num_stages} stages needs {shared_memory_bytes} bytes of shared \
+                 memory for its buffers, but only {max_bytes} bytes are available per cube"
+            ),
+        }
+    }
+}
+
 /// Trait for compiled code representation
 pub trait CompilerRepresentation: Display {
     /// Computes and returns the shared memory size
@@ -21,6 +71,15 @@ pub trait Compiler: Sync + Send + 'static + Clone + Default + core::fmt::Debug {
         compilation_options: &Self::CompilationOptions,
         mode: ExecutionMode,
     ) -> Self::Representation;
+    /// Compiles the [kernel definition](KernelDefinition) into the compiler's representation, or
+    /// returns an error when the kernel uses something the compiler doesn't support.
+    fn try_compile(
+        kernel: KernelDefinition,
+        compilation_options: &Self::CompilationOptions,
+        mode: ExecutionMode,
+    ) -> Result<Self::Representation, CompilationError> {
+        Ok(Self::compile(kernel, compilation_options, mode))
+    }
     /// The size of the given element in bytes.
     fn elem_size(elem: Elem) -> usize;
     /// The maximal size of a shared memory, in bytes
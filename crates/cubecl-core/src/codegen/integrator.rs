@@ -66,6 +66,10 @@ pub struct KernelOptions {
     pub kernel_name: String,
     pub debug_symbols: bool,
     pub fp_math_mode: FastMath,
+    /// The shared memory available to a cube on the device the kernel is launched on, in bytes.
+    /// Kernels using more fail to compile with
+    /// [SharedMemoryLimitExceeded](crate::codegen::CompilationError::SharedMemoryLimitExceeded).
+    pub max_shared_memory_size: Option<usize>,
 }
 
 impl core::fmt::Display for KernelSettings {
@@ -223,6 +227,12 @@ impl KernelSettings {
         self.options.fp_math_mode = mode;
         self
     }
+
+    /// Set the shared memory available to a cube on the device, in bytes.
+    pub fn max_shared_memory_size(mut self, bytes: usize) -> Self {
+        self.options.max_shared_memory_size = Some(bytes);
+        self
+    }
 }
 
 #[allow(dead_code)]
@@ -1,4 +1,4 @@
-use cubecl_ir::{ExpandElement, Scope};
+use cubecl_ir::{BinaryOperator, ExpandElement, Instruction, Operator, Scope, Vectorization};
 use cubecl_runtime::debug::DebugLogger;
 
 use crate::ir::{Elem, Id, Item};
@@ -41,6 +41,37 @@ impl KernelBuilder {
         self.context.scalar(index, elem)
     }
 
+    /// Register a vectorized scalar and return the [element](ExpandElement) to be used for kernel
+    /// expansion.
+    ///
+    /// Each lane takes its own slot in the scalar buffer of the element type, and the lanes are
+    /// gathered into a local line at the start of the kernel. Without vectorization, this is the
+    /// same as [scalar](Self::scalar).
+    pub fn scalar_vectorized(&mut self, elem: Elem, vectorization: Vectorization) -> ExpandElement {
+        let line_size = vectorization.map(|factor| factor.get()).unwrap_or(1);
+
+        if line_size == 1 {
+            return self.scalar(elem);
+        }
+
+        let line = self
+            .context
+            .create_local_mut(Item::vectorized(elem, vectorization));
+
+        for i in 0..line_size {
+            let lane = self.scalar(elem);
+            self.context.register(Instruction::new(
+                Operator::IndexAssign(BinaryOperator {
+                    lhs: (i as u32).into(),
+                    rhs: *lane,
+                }),
+                *line,
+            ));
+        }
+
+        line
+    }
+
     /// Register an output array and return the [element](ExpandElement) to be used for kernel expansion.
     pub fn output_tensor(&mut self, item: Item) -> ExpandElement {
         self.outputs.push(OutputInfo::Array {
@@ -1,9 +1,12 @@
 use std::{fmt::Display, marker::PhantomData};
 
-use crate::{codegen::CompilerRepresentation, Compiler, Kernel, KernelId, KernelOptions};
+use crate::{
+    codegen::{CompilationError, CompilerRepresentation},
+    Compiler, Kernel, KernelId, KernelOptions,
+};
 use alloc::sync::Arc;
 use cubecl_common::{CubeDim, ExecutionMode};
-use cubecl_ir::{Item, Scope};
+use cubecl_ir::{Elem, Item, Scope};
 use serde::{Deserialize, Serialize};
 
 /// A kernel, compiled in the target language
@@ -208,6 +211,19 @@ pub struct KernelDefinition {
     pub options: KernelOptions,
 }
 
+impl KernelDefinition {
+    /// Find the element type of a binding or of a variable of the body that matches the predicate.
+    pub fn find_elem(&self, predicate: impl Fn(Elem) -> bool) -> Option<Elem> {
+        self.inputs
+            .iter()
+            .chain(self.outputs.iter())
+            .chain(self.named.iter().map(|(_, binding)| binding))
+            .map(|binding| binding.item.elem)
+            .find(|elem| predicate(*elem))
+            .or_else(|| self.body.find_elem(&predicate))
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 #[allow(missing_docs)]
 pub struct Binding {
@@ -243,6 +259,14 @@ pub trait CubeTask<C: Compiler>: Send + Sync {
         compilation_options: &C::CompilationOptions,
         mode: ExecutionMode,
     ) -> CompiledKernel<C>;
+    /// Compile the kernel into source, or return an error when the compiler doesn't support it.
+    fn try_compile(
+        &self,
+        compilation_options: &C::CompilationOptions,
+        mode: ExecutionMode,
+    ) -> Result<CompiledKernel<C>, CompilationError> {
+        Ok(self.compile(compilation_options, mode))
+    }
     fn name(&self) -> &'static str {
         core::any::type_name::<Self>()
     }
@@ -261,13 +285,34 @@ impl<C: Compiler, K: Kernel> CubeTask<C> for KernelTask<C, K> {
         compilation_options: &C::CompilationOptions,
         mode: ExecutionMode,
     ) -> CompiledKernel<C> {
+        match self.try_compile(compilation_options, mode) {
+            Ok(kernel) => kernel,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    fn try_compile(
+        &self,
+        compilation_options: &C::CompilationOptions,
+        mode: ExecutionMode,
+    ) -> Result<CompiledKernel<C>, CompilationError> {
         let gpu_ir = self.kernel_definition.define();
+        if let Some(max_bytes) = gpu_ir.options.max_shared_memory_size {
+            // Counted during the expansion, since not every compiler knows the size it emits.
+            let shared_memory_bytes = gpu_ir.body.allocator.shared_bytes();
+            if shared_memory_bytes > max_bytes {
+                return Err(CompilationError::SharedMemoryLimitExceeded {
+                    shared_memory_bytes,
+                    max_bytes,
+                });
+            }
+        }
         let entrypoint_name = gpu_ir.options.kernel_name.clone();
         let cube_dim = gpu_ir.cube_dim;
-        let lower_level_ir = C::compile(gpu_ir, compilation_options, mode);
+        let lower_level_ir = C::try_compile(gpu_ir, compilation_options, mode)?;
         let shared_mem_bytes = lower_level_ir.shared_memory_size();
 
-        CompiledKernel {
+        Ok(CompiledKernel {
             entrypoint_name,
             debug_name: Some(core::any::type_name::<K>()),
             source: lower_level_ir.to_string(),
@@ -275,7 +320,7 @@ impl<C: Compiler, K: Kernel> CubeTask<C> for KernelTask<C, K> {
             cube_dim,
             shared_mem_bytes,
             debug_info: None,
-        }
+        })
     }
 
     fn id(&self) -> KernelId {
@@ -296,6 +341,14 @@ impl<C: Compiler> CubeTask<C> for Arc<dyn CubeTask<C>> {
         self.as_ref().compile(compilation_options, mode)
     }
 
+    fn try_compile(
+        &self,
+        compilation_options: &C::CompilationOptions,
+        mode: ExecutionMode,
+    ) -> Result<CompiledKernel<C>, CompilationError> {
+        self.as_ref().try_compile(compilation_options, mode)
+    }
+
     fn id(&self) -> KernelId {
         self.as_ref().id()
     }
@@ -313,6 +366,14 @@ impl<C: Compiler> CubeTask<C> for Box<dyn CubeTask<C>> {
         self.as_ref().compile(compilation_options, mode)
     }
 
+    fn try_compile(
+        &self,
+        compilation_options: &C::CompilationOptions,
+        mode: ExecutionMode,
+    ) -> Result<CompiledKernel<C>, CompilationError> {
+        self.as_ref().try_compile(compilation_options, mode)
+    }
+
     fn id(&self) -> KernelId {
         self.as_ref().id()
     }
@@ -9,12 +9,15 @@ use crate::{
 };
 use crate::{Kernel, Runtime};
 use bytemuck::NoUninit;
+use cubecl_common::{e4m3, e5m2};
 use cubecl_runtime::client::ComputeClient;
 use cubecl_runtime::server::{Binding, CubeCount};
 
 /// Prepare a kernel for [launch](KernelLauncher::launch).
 pub struct KernelLauncher<R: Runtime> {
     tensors: TensorState<R>,
+    scalar_e4m3: ScalarState<e4m3>,
+    scalar_e5m2: ScalarState<e5m2>,
     scalar_bf16: ScalarState<half::bf16>,
     scalar_f16: ScalarState<half::f16>,
     scalar_f32: ScalarState<f32>,
@@ -91,6 +94,18 @@ impl<R: Runtime> KernelLauncher<R> {
         self.scalar_i64.push(scalar);
     }
 
+    /// Register a e4m3 scalar to be launched.
+    pub fn register_e4m3(&mut self, scalar: e4m3) {
+        self.register_scalar(Elem::Float(FloatKind::E4M3));
+        self.scalar_e4m3.push(scalar);
+    }
+
+    /// Register a e5m2 scalar to be launched.
+    pub fn register_e5m2(&mut self, scalar: e5m2) {
+        self.register_scalar(Elem::Float(FloatKind::E5M2));
+        self.scalar_e5m2.push(scalar);
+    }
+
     /// Register a bf16 scalar to be launched.
     pub fn register_bf16(&mut self, scalar: half::bf16) {
         self.register_scalar(Elem::Float(FloatKind::BF16));
@@ -115,6 +130,90 @@ impl<R: Runtime> KernelLauncher<R> {
         self.scalar_f64.push(scalar);
     }
 
+    /// Register a contiguous block of u8 scalars to be launched, in order.
+    pub fn register_u8_slice(&mut self, scalars: &[u8]) {
+        self.register_scalar(Elem::UInt(UIntKind::U8));
+        self.scalar_u8.extend(scalars);
+    }
+
+    /// Register a contiguous block of u16 scalars to be launched, in order.
+    pub fn register_u16_slice(&mut self, scalars: &[u16]) {
+        self.register_scalar(Elem::UInt(UIntKind::U16));
+        self.scalar_u16.extend(scalars);
+    }
+
+    /// Register a contiguous block of u32 scalars to be launched, in order.
+    pub fn register_u32_slice(&mut self, scalars: &[u32]) {
+        self.register_scalar(Elem::UInt(UIntKind::U32));
+        self.scalar_u32.extend(scalars);
+    }
+
+    /// Register a contiguous block of u64 scalars to be launched, in order.
+    pub fn register_u64_slice(&mut self, scalars: &[u64]) {
+        self.register_scalar(Elem::UInt(UIntKind::U64));
+        self.scalar_u64.extend(scalars);
+    }
+
+    /// Register a contiguous block of i8 scalars to be launched, in order.
+    pub fn register_i8_slice(&mut self, scalars: &[i8]) {
+        self.register_scalar(Elem::Int(IntKind::I8));
+        self.scalar_i8.extend(scalars);
+    }
+
+    /// Register a contiguous block of i16 scalars to be launched, in order.
+    pub fn register_i16_slice(&mut self, scalars: &[i16]) {
+        self.register_scalar(Elem::Int(IntKind::I16));
+        self.scalar_i16.extend(scalars);
+    }
+
+    /// Register a contiguous block of i32 scalars to be launched, in order.
+    pub fn register_i32_slice(&mut self, scalars: &[i32]) {
+        self.register_scalar(Elem::Int(IntKind::I32));
+        self.scalar_i32.extend(scalars);
+    }
+
+    /// Register a contiguous block of i64 scalars to be launched, in order.
+    pub fn register_i64_slice(&mut self, scalars: &[i64]) {
+        self.register_scalar(Elem::Int(IntKind::I64));
+        self.scalar_i64.extend(scalars);
+    }
+
+    /// Register a contiguous block of e4m3 scalars to be launched, in order.
+    pub fn register_e4m3_slice(&mut self, scalars: &[e4m3]) {
+        self.register_scalar(Elem::Float(FloatKind::E4M3));
+        self.scalar_e4m3.extend(scalars);
+    }
+
+    /// Register a contiguous block of e5m2 scalars to be launched, in order.
+    pub fn register_e5m2_slice(&mut self, scalars: &[e5m2]) {
+        self.register_scalar(Elem::Float(FloatKind::E5M2));
+        self.scalar_e5m2.extend(scalars);
+    }
+
+    /// Register a contiguous block of bf16 scalars to be launched, in order.
+    pub fn register_bf16_slice(&mut self, scalars: &[half::bf16]) {
+        self.register_scalar(Elem::Float(FloatKind::BF16));
+        self.scalar_bf16.extend(scalars);
+    }
+
+    /// Register a contiguous block of f16 scalars to be launched, in order.
+    pub fn register_f16_slice(&mut self, scalars: &[half::f16]) {
+        self.register_scalar(Elem::Float(FloatKind::F16));
+        self.scalar_f16.extend(scalars);
+    }
+
+    /// Register a contiguous block of f32 scalars to be launched, in order.
+    pub fn register_f32_slice(&mut self, scalars: &[f32]) {
+        self.register_scalar(Elem::Float(FloatKind::F32));
+        self.scalar_f32.extend(scalars);
+    }
+
+    /// Register a contiguous block of f64 scalars to be launched, in order.
+    pub fn register_f64_slice(&mut self, scalars: &[f64]) {
+        self.register_scalar(Elem::Float(FloatKind::F64));
+        self.scalar_f64.extend(scalars);
+    }
+
     /// Launch the kernel.
     pub fn launch<K: Kernel>(
         self,
@@ -164,6 +263,8 @@ impl<R: Runtime> KernelLauncher<R> {
         for elem in self.scalar_order.drain(..) {
             match elem {
                 Elem::Float(kind) | Elem::AtomicFloat(kind) => match kind {
+                    FloatKind::E4M3 => self.scalar_e4m3.register::<R>(client, &mut bindings),
+                    FloatKind::E5M2 => self.scalar_e5m2.register::<R>(client, &mut bindings),
                     FloatKind::F16 => self.scalar_f16.register::<R>(client, &mut bindings),
                     FloatKind::BF16 => self.scalar_bf16.register::<R>(client, &mut bindings),
                     FloatKind::TF32 => self.scalar_f32.register::<R>(client, &mut bindings),
@@ -325,6 +426,14 @@ impl<T: NoUninit> ScalarState<T> {
         }
     }
 
+    /// Add new scalar values to the state, after the ones already registered.
+    pub fn extend(&mut self, vals: &[T]) {
+        match self {
+            ScalarState::Empty => *self = Self::Some(vals.to_vec()),
+            ScalarState::Some(values) => values.extend_from_slice(vals),
+        }
+    }
+
     fn register<R: Runtime>(
         &self,
         client: &ComputeClient<R::Server, R::Channel>,
@@ -344,6 +453,8 @@ impl<R: Runtime> Default for KernelLauncher<R> {
     fn default() -> Self {
         Self {
             tensors: TensorState::Empty,
+            scalar_e4m3: ScalarState::Empty,
+            scalar_e5m2: ScalarState::Empty,
             scalar_bf16: ScalarState::Empty,
             scalar_f16: ScalarState::Empty,
             scalar_f32: ScalarState::Empty,
@@ -33,6 +33,8 @@ pub struct RangeExpand<I: Int> {
     pub start: ExpandElementTyped<I>,
     pub end: ExpandElementTyped<I>,
     pub inclusive: bool,
+    /// See [range_unrolled_by].
+    pub unroll_factor: Option<u32>,
 }
 
 impl<I: Int> RangeExpand<I> {
@@ -41,6 +43,7 @@ impl<I: Int> RangeExpand<I> {
             start,
             end,
             inclusive,
+            unroll_factor: None,
         }
     }
 
@@ -107,6 +110,7 @@ impl<I: Int> Iterable<I> for RangeExpand<I> {
             step: None,
             scope: child,
             inclusive: self.inclusive,
+            unroll_factor: self.unroll_factor,
         })));
     }
 }
@@ -137,6 +141,7 @@ impl<I: Int + Into<ExpandElement>> Iterable<I> for SteppedRangeExpand<I> {
             step: Some(*self.step.expand),
             scope: child,
             inclusive: self.inclusive,
+            unroll_factor: None,
         })));
     }
 
@@ -205,6 +210,42 @@ pub mod range {
             start,
             end,
             inclusive: false,
+            unroll_factor: None,
+        }
+    }
+}
+
+/// Integer range whose loop is partially unrolled by `factor`. Equivalent to:
+///
+/// ```ignore
+/// start..end
+/// ```
+///
+/// Unlike `#[unroll]`, the bounds don't need to be known at compile time and the loop stays a
+/// runtime loop: the factor is only a hint, emitted as `#pragma unroll factor` by the C++
+/// backends and ignored by the others.
+pub fn range_unrolled_by<I: Int>(start: I, end: I, _factor: u32) -> impl Iterator<Item = I> {
+    range(start, end)
+}
+
+pub mod range_unrolled_by {
+    use cubecl_ir::Scope;
+
+    use crate::prelude::{ExpandElementTyped, Int};
+
+    use super::RangeExpand;
+
+    pub fn expand<I: Int>(
+        _scope: &mut Scope,
+        start: ExpandElementTyped<I>,
+        end: ExpandElementTyped<I>,
+        factor: u32,
+    ) -> RangeExpand<I> {
+        RangeExpand {
+            start,
+            end,
+            inclusive: false,
+            unroll_factor: Some(factor),
         }
     }
 }
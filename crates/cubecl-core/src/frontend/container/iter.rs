@@ -42,6 +42,7 @@ impl<T: SizedContainer> Iterable<T::Item> for ExpandElementTyped<T> {
             step: None,
             inclusive: false,
             scope: child,
+            unroll_factor: None,
         })));
     }
 
@@ -11,7 +11,10 @@ use crate::{
 use cubecl_ir::{Comparison, ExpandElement};
 use derive_more::derive::Neg;
 /// A contiguous list of elements that supports auto-vectorized operations.
-
+///
+/// Lines of `f16` or `bf16` with an even size are packed in pairs by the CUDA compiler: a line of
+/// two is loaded and stored as a single 32-bit word, and its arithmetic maps to `__half2` (or
+/// `__nv_bfloat162`) operations. Reading such tensors with an even line size is enough to opt in.
 #[derive(Neg)]
 pub struct Line<P> {
     // Comptime lines only support 1 element.
@@ -170,6 +173,105 @@ mod size {
     }
 }
 
+/// Module that contains the implementation details of the horizontal reductions.
+mod reduce {
+    use cubecl_ir::{Operator, UIntKind, Variable};
+
+    use crate::frontend::operation::base::{binary_expand, binary_expand_no_vec};
+
+    use super::*;
+
+    impl<P: Numeric> Line<P> {
+        /// Sum of all the elements of the line.
+        ///
+        /// The line size must be a power of two.
+        pub fn reduce_sum(self) -> P {
+            unexpanded!()
+        }
+
+        /// Largest of the elements of the line.
+        ///
+        /// The line size must be a power of two.
+        pub fn reduce_max(self) -> P {
+            unexpanded!()
+        }
+
+        /// Expand function of [reduce_sum](Self::reduce_sum).
+        pub fn __expand_reduce_sum(
+            scope: &mut Scope,
+            line: ExpandElementTyped<Self>,
+        ) -> ExpandElementTyped<P> {
+            line.__expand_reduce_sum_method(scope)
+        }
+
+        /// Expand function of [reduce_max](Self::reduce_max).
+        pub fn __expand_reduce_max(
+            scope: &mut Scope,
+            line: ExpandElementTyped<Self>,
+        ) -> ExpandElementTyped<P> {
+            line.__expand_reduce_max_method(scope)
+        }
+    }
+
+    impl<P: Numeric> ExpandElementTyped<Line<P>> {
+        /// Expand method of [reduce_sum](Line::reduce_sum).
+        pub fn __expand_reduce_sum_method(self, scope: &mut Scope) -> ExpandElementTyped<P> {
+            reduce_lanes(scope, self, Arithmetic::Add)
+        }
+
+        /// Expand method of [reduce_max](Line::reduce_max).
+        pub fn __expand_reduce_max_method(self, scope: &mut Scope) -> ExpandElementTyped<P> {
+            reduce_lanes(scope, self, Arithmetic::Max)
+        }
+    }
+
+    /// Reduces the lanes of a line as a tree: the upper half of the lanes is folded onto the
+    /// lower half until a single lane is left.
+    ///
+    /// The tree is unrolled at comptime into `size - 1` scalar operations over `log2(size)`
+    /// levels, each level only depending on the previous one. Lanes are combined in the same
+    /// order on every runtime, so float sums don't depend on the backend.
+    fn reduce_lanes<P: Numeric>(
+        scope: &mut Scope,
+        line: ExpandElementTyped<Line<P>>,
+        op: fn(BinaryOperator) -> Arithmetic,
+    ) -> ExpandElementTyped<P> {
+        let size = line.size();
+        assert!(
+            size.is_power_of_two(),
+            "Can't reduce a line of {size} elements, the line size must be a power of two"
+        );
+
+        if size == 1 {
+            return line.expand.into();
+        }
+
+        let line: ExpandElement = line.into();
+        let mut lanes: Vec<ExpandElement> = (0..size)
+            .map(|i| {
+                let index = Variable::constant(ConstantScalarValue::UInt(i as u64, UIntKind::U32));
+                binary_expand_no_vec(
+                    scope,
+                    line.clone(),
+                    ExpandElement::Plain(index),
+                    Operator::Index,
+                )
+            })
+            .collect();
+
+        while lanes.len() > 1 {
+            let upper = lanes.split_off(lanes.len() / 2);
+            lanes = lanes
+                .into_iter()
+                .zip(upper)
+                .map(|(lower, upper)| binary_expand(scope, lower, upper, op))
+                .collect();
+        }
+
+        lanes.remove(0).into()
+    }
+}
+
 // Implement a comparison operator define in
 macro_rules! impl_line_comparison {
     ($name:ident, $operator:ident, $comment:literal) => {
@@ -0,0 +1,77 @@
+use std::num::NonZero;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    compute::{KernelBuilder, KernelLauncher},
+    ir::Vectorization,
+    prelude::{
+        ArgSettings, CompilationArg, ExpandElementTyped, LaunchArg, LaunchArgExpand, Numeric,
+        ScalarArgSettings,
+    },
+    Runtime,
+};
+
+use super::Line;
+
+/// A line passed by value to a kernel, like a small vector uniform.
+///
+/// The line size is the number of values.
+pub struct LineArg<T: Numeric> {
+    pub values: Vec<T>,
+}
+
+impl<T: Numeric> LineArg<T> {
+    /// Create a new line argument from its values.
+    pub fn new(values: Vec<T>) -> Self {
+        Self { values }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct LineCompilationArg {
+    pub vectorization: Vectorization,
+}
+
+impl CompilationArg for LineCompilationArg {}
+
+impl<T: Numeric, R: Runtime> ArgSettings<R> for LineArg<T> {
+    fn register(&self, launcher: &mut KernelLauncher<R>) {
+        for value in self.values.iter() {
+            ScalarArgSettings::register(value, launcher);
+        }
+    }
+}
+
+impl<T: Numeric> LaunchArgExpand for Line<T> {
+    type CompilationArg = LineCompilationArg;
+
+    fn expand(
+        arg: &Self::CompilationArg,
+        builder: &mut KernelBuilder,
+    ) -> ExpandElementTyped<Line<T>> {
+        builder
+            .scalar_vectorized(T::as_elem(&builder.context), arg.vectorization)
+            .into()
+    }
+}
+
+impl<T: Numeric> LaunchArg for Line<T> {
+    type RuntimeArg<'a, R: Runtime> = LineArg<T>;
+
+    fn compilation_arg<R: Runtime>(runtime_arg: &Self::RuntimeArg<'_, R>) -> Self::CompilationArg {
+        // Checked before narrowing, since a length above `u8::MAX` would wrap to another size.
+        let line_size = runtime_arg.values.len();
+        assert!(
+            R::supported_line_sizes()
+                .iter()
+                .any(|size| *size as usize == line_size),
+            "A line of {line_size} values isn't supported, the line sizes of the runtime are {:?}",
+            R::supported_line_sizes()
+        );
+
+        LineCompilationArg {
+            vectorization: NonZero::new(line_size as u8),
+        }
+    }
+}
@@ -1,4 +1,6 @@
 mod base;
+mod launch;
 mod ops;
 
 pub use base::*;
+pub use launch::*;
@@ -3,11 +3,12 @@ use num_traits::{NumCast, ToPrimitive};
 
 use crate::{
     frontend::{
-        Abs, Ceil, Clamp, Cos, CubeIndex, CubeIndexMut, CubePrimitive, Erf, Exp,
-        ExpandElementTyped, Floor, Log, Log1p, Max, Min, Powf, Recip, Remainder, Round, Sin, Sqrt,
-        Tanh,
+        Abs, Acos, Acosh, Asin, Asinh, Atan, Atan2, Atanh, Ceil, Clamp, Cos, Cosh, CubeIndex,
+        CubeIndexMut, CubePrimitive, Erf, Exp, Exp2, ExpandElementTyped, Floor, Fma, Fract, Gelu,
+        Log, Log1p, Log2, Max, Min, Powf, Recip, Remainder, Round, Signum, Sin, Sinh, Sqrt, Tan,
+        Tanh, Trunc,
     },
-    prelude::{BitwiseNot, CountOnes, FindFirstSet, LeadingZeros, ReverseBits},
+    prelude::{BitwiseNot, CountOnes, FindFirstSet, LeadingZeros, ReverseBits, TrailingZeros},
     unexpanded,
 };
 
@@ -232,22 +233,39 @@ where
 }
 
 impl<P: CubePrimitive + Abs> Abs for Line<P> {}
+impl<P: CubePrimitive + Signum> Signum for Line<P> {}
 impl<P: CubePrimitive + Max> Max for Line<P> {}
 impl<P: CubePrimitive + Min> Min for Line<P> {}
 impl<P: CubePrimitive + Clamp> Clamp for Line<P> {}
+impl<P: CubePrimitive + Fma> Fma for Line<P> {}
 impl<P: CubePrimitive + Log> Log for Line<P> {}
 impl<P: CubePrimitive + Log1p> Log1p for Line<P> {}
 impl<P: CubePrimitive + Erf> Erf for Line<P> {}
+impl<P: CubePrimitive + Gelu> Gelu for Line<P> {}
 impl<P: CubePrimitive + Exp> Exp for Line<P> {}
 impl<P: CubePrimitive + Powf> Powf for Line<P> {}
+impl<P: CubePrimitive + Atan2> Atan2 for Line<P> {}
 impl<P: CubePrimitive + Sqrt> Sqrt for Line<P> {}
 impl<P: CubePrimitive + Cos> Cos for Line<P> {}
 impl<P: CubePrimitive + Sin> Sin for Line<P> {}
 impl<P: CubePrimitive + Tanh> Tanh for Line<P> {}
+impl<P: CubePrimitive + Log2> Log2 for Line<P> {}
+impl<P: CubePrimitive + Exp2> Exp2 for Line<P> {}
+impl<P: CubePrimitive + Sinh> Sinh for Line<P> {}
+impl<P: CubePrimitive + Cosh> Cosh for Line<P> {}
+impl<P: CubePrimitive + Asinh> Asinh for Line<P> {}
+impl<P: CubePrimitive + Acosh> Acosh for Line<P> {}
+impl<P: CubePrimitive + Atanh> Atanh for Line<P> {}
+impl<P: CubePrimitive + Tan> Tan for Line<P> {}
+impl<P: CubePrimitive + Asin> Asin for Line<P> {}
+impl<P: CubePrimitive + Acos> Acos for Line<P> {}
+impl<P: CubePrimitive + Atan> Atan for Line<P> {}
 impl<P: CubePrimitive + Recip> Recip for Line<P> {}
 impl<P: CubePrimitive + Remainder> Remainder for Line<P> {}
 impl<P: CubePrimitive + Round> Round for Line<P> {}
 impl<P: CubePrimitive + Floor> Floor for Line<P> {}
+impl<P: CubePrimitive + Trunc> Trunc for Line<P> {}
+impl<P: CubePrimitive + Fract> Fract for Line<P> {}
 impl<P: CubePrimitive + Ceil> Ceil for Line<P> {}
 impl<P: CubePrimitive + ReverseBits> ReverseBits for Line<P> {}
 impl<P: CubePrimitive + BitwiseNot> BitwiseNot for Line<P> {}
@@ -308,6 +326,37 @@ impl<P: LeadingZeros> ExpandElementTyped<Line<P>> {
     }
 }
 
+impl<P: TrailingZeros> Line<P> {
+    pub fn trailing_zeros(self) -> Line<u32> {
+        unexpanded!()
+    }
+
+    pub fn __expand_trailing_zeros(
+        scope: &mut Scope,
+        value: ExpandElementTyped<Self>,
+    ) -> ExpandElementTyped<Line<u32>> {
+        value.__expand_trailing_zeros_method(scope)
+    }
+}
+
+impl<P: TrailingZeros> ExpandElementTyped<Line<P>> {
+    pub fn __expand_trailing_zeros_method(
+        self,
+        scope: &mut Scope,
+    ) -> ExpandElementTyped<Line<u32>> {
+        let mut out_item = self.expand.item;
+        out_item.elem = Elem::UInt(UIntKind::U32);
+        let out = scope.create_local(out_item);
+        scope.register(Instruction::new(
+            Bitwise::TrailingZeros(UnaryOperator {
+                input: *self.expand,
+            }),
+            *out,
+        ));
+        out.into()
+    }
+}
+
 impl<P: FindFirstSet> Line<P> {
     pub fn find_first_set(self) -> Line<u32> {
         unexpanded!()
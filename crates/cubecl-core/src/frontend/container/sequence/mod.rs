@@ -1,5 +1,7 @@
 mod base;
 mod launch;
+mod scalar_array;
 
 pub use base::*;
 pub use launch::*;
+pub use scalar_array::*;
@@ -0,0 +1,88 @@
+use std::{cell::RefCell, marker::PhantomData, rc::Rc};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    compute::{KernelBuilder, KernelLauncher},
+    frontend::{indexation::Index, CubeType, Numeric},
+    prelude::{ArgSettings, CompilationArg, LaunchArg, LaunchArgExpand},
+    unexpanded, Runtime,
+};
+
+use super::SequenceExpand;
+
+/// A fixed number of scalars launched as one contiguous block of uniforms.
+///
+/// Inside the kernel, it is a [sequence](super::Sequence) of scalars, so it can only be indexed
+/// with comptime indices, for instance in an unrolled loop. The number of scalars is part of the
+/// kernel definition, so launching with another length compiles another kernel.
+pub struct ScalarArray<T: Numeric> {
+    _type: PhantomData<T>,
+}
+
+impl<T: Numeric> ScalarArray<T> {
+    /// Obtain the number of scalars.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> u32 {
+        unexpanded!()
+    }
+
+    /// Get the scalar at the given position.
+    #[allow(unused_variables, clippy::should_implement_trait)]
+    pub fn index<I: Index>(&self, index: I) -> &T {
+        unexpanded!()
+    }
+}
+
+impl<T: Numeric> CubeType for ScalarArray<T> {
+    type ExpandType = SequenceExpand<T>;
+}
+
+/// The scalars of a [scalar array](ScalarArray) at launch.
+pub struct ScalarArrayArg<'a, T: Numeric> {
+    pub values: &'a [T],
+}
+
+impl<'a, T: Numeric> ScalarArrayArg<'a, T> {
+    pub fn new(values: &'a [T]) -> Self {
+        Self { values }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct ScalarArrayCompilationArg {
+    pub len: usize,
+}
+
+impl CompilationArg for ScalarArrayCompilationArg {}
+
+impl<T: Numeric> LaunchArg for ScalarArray<T> {
+    type RuntimeArg<'a, R: Runtime> = ScalarArrayArg<'a, T>;
+
+    fn compilation_arg<R: Runtime>(runtime_arg: &Self::RuntimeArg<'_, R>) -> Self::CompilationArg {
+        ScalarArrayCompilationArg {
+            len: runtime_arg.values.len(),
+        }
+    }
+}
+
+impl<R: Runtime, T: Numeric> ArgSettings<R> for ScalarArrayArg<'_, T> {
+    fn register(&self, launcher: &mut KernelLauncher<R>) {
+        T::register_slice(self.values, launcher);
+    }
+}
+
+impl<T: Numeric> LaunchArgExpand for ScalarArray<T> {
+    type CompilationArg = ScalarArrayCompilationArg;
+
+    fn expand(arg: &Self::CompilationArg, builder: &mut KernelBuilder) -> SequenceExpand<T> {
+        // Scalars of the same type get consecutive ids, matching the registered block.
+        let values = (0..arg.len)
+            .map(|_| builder.scalar(T::as_elem(&builder.context)).into())
+            .collect();
+
+        SequenceExpand {
+            values: Rc::new(RefCell::new(values)),
+        }
+    }
+}
@@ -253,6 +253,29 @@ mod indexation {
         {
             unexpanded!()
         }
+
+        /// Same as [index_unchecked](Tensor::index_unchecked), with a 64-bit index to address
+        /// buffers of more than `u32::MAX` elements.
+        ///
+        /// The index is given as is to the backend, which must support 64-bit indices, like
+        /// CUDA and HIP do. It requires the `u64` [type](crate::Feature::Type) to be supported.
+        ///
+        /// # Safety
+        /// Out of bounds indexing causes undefined behaviour and may segfault. Ensure index is
+        /// always in bounds
+        pub unsafe fn index_unchecked_wide(&self, _i: u64) -> &E {
+            unexpanded!()
+        }
+
+        /// Same as [index_assign_unchecked](Tensor::index_assign_unchecked), with a 64-bit index,
+        /// see [index_unchecked_wide](Tensor::index_unchecked_wide).
+        ///
+        /// # Safety
+        /// Out of bounds indexing causes undefined behaviour and may segfault. Ensure index is
+        /// always in bounds
+        pub unsafe fn index_assign_unchecked_wide(&mut self, _i: u64, _value: E) {
+            unexpanded!()
+        }
     }
 
     impl<E: CubePrimitive> ExpandElementTyped<Tensor<E>> {
@@ -286,6 +309,37 @@ mod indexation {
                 *self.expand,
             ));
         }
+
+        pub fn __expand_index_unchecked_wide_method(
+            self,
+            scope: &mut Scope,
+            i: ExpandElementTyped<u64>,
+        ) -> ExpandElementTyped<E> {
+            let out = scope.create_local(self.expand.item);
+            scope.register(Instruction::new(
+                Operator::UncheckedIndex(BinaryOperator {
+                    lhs: *self.expand,
+                    rhs: i.expand.consume(),
+                }),
+                *out,
+            ));
+            out.into()
+        }
+
+        pub fn __expand_index_assign_unchecked_wide_method(
+            self,
+            scope: &mut Scope,
+            i: ExpandElementTyped<u64>,
+            value: ExpandElementTyped<E>,
+        ) {
+            scope.register(Instruction::new(
+                Operator::UncheckedIndexAssign(BinaryOperator {
+                    lhs: i.expand.consume(),
+                    rhs: value.expand.consume(),
+                }),
+                *self.expand,
+            ));
+        }
     }
 }
 
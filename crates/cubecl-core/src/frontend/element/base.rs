@@ -4,7 +4,7 @@ use crate::{
     prelude::{init_expand, KernelBuilder, KernelLauncher},
     Runtime,
 };
-use cubecl_common::{flex32, tf32};
+use cubecl_common::{e4m3, e5m2, flex32, tf32};
 use cubecl_ir::ExpandElement;
 use half::{bf16, f16};
 use std::marker::PhantomData;
@@ -191,6 +191,8 @@ from_const!(i8);
 from_const!(i16);
 from_const!(i32);
 from_const!(f64);
+from_const!(e4m3);
+from_const!(e5m2);
 from_const!(f16);
 from_const!(bf16);
 from_const!(flex32);
@@ -316,6 +318,34 @@ impl<T: CubePrimitive> ExpandElementTyped<T> {
     }
 }
 
+impl<T: Numeric> ExpandElementTyped<T> {
+    /// Get the value of the variable on the host, when it is a constant known during expansion.
+    ///
+    /// Literals are constants, and so are the results of operations folded during expansion, like
+    /// a [fused multiply-add](crate::prelude::Fma) of constants. This allows generic code to
+    /// specialize at expansion time when an input happens to be constant.
+    ///
+    /// Returns [None] when the value is only known at runtime, or when the constant can't be
+    /// represented by `T`.
+    pub fn try_as_comptime(&self) -> Option<T> {
+        match self.constant()? {
+            ConstantScalarValue::Int(val, _) => <T as num_traits::NumCast>::from(val),
+            ConstantScalarValue::Float(val, _) => <T as num_traits::NumCast>::from(val),
+            ConstantScalarValue::UInt(val, _) => <T as num_traits::NumCast>::from(val),
+            ConstantScalarValue::Bool(_) => None,
+        }
+    }
+}
+
+impl ExpandElementTyped<bool> {
+    /// Get the value of the variable on the host, when it is a constant known during expansion.
+    ///
+    /// Returns [None] when the value is only known at runtime.
+    pub fn try_as_comptime(&self) -> Option<bool> {
+        self.constant()?.try_as_bool()
+    }
+}
+
 pub(crate) fn init_expand_element<E: Into<ExpandElement>>(
     scope: &mut Scope,
     element: E,
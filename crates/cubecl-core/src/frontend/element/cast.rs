@@ -1,7 +1,11 @@
 use cubecl_ir::{ExpandElement, Operator};
+use half::{bf16, f16};
 
 use crate::frontend::{cast, CubePrimitive, CubeType};
-use crate::ir::{Instruction, Item, Scope, UnaryOperator, Variable};
+use crate::ir::{
+    Arithmetic, BinaryOperator, ClampOperator, Comparison, ConstantScalarValue, Elem, FloatKind,
+    Instruction, IntKind, Item, Operation, Scope, Select, UnaryOperator, Variable,
+};
 use crate::unexpanded;
 
 use super::ExpandElementTyped;
@@ -24,12 +28,218 @@ pub trait Cast: CubePrimitive {
         cast::expand(scope, value, new_var.clone().into());
         new_var.into()
     }
+
+    /// Cast `value`, clamping it to the range of this type when it doesn't fit, like Rust's `as`
+    /// does from floats to integers.
+    ///
+    /// From floats to integers, the fraction is truncated and NaN becomes zero. Between integers,
+    /// values out of range become the closest bound, so `300` cast to `i8` is `127`. Other
+    /// conversions are the same as [cast_from](Cast::cast_from).
+    ///
+    /// This is expanded to a clamp followed by a regular cast, so it behaves the same on every
+    /// backend.
+    fn cast_saturating_from<From: CubePrimitive>(value: From) -> Self;
+
+    fn __expand_cast_saturating_from<From: CubePrimitive>(
+        scope: &mut Scope,
+        value: ExpandElementTyped<From>,
+    ) -> <Self as CubeType>::ExpandType {
+        let elem = <Self as CubePrimitive>::as_elem(scope);
+        saturating_cast(scope, value.expand, elem).into()
+    }
+
+    /// Cast `value`, keeping only its lowest bits when it doesn't fit, like Rust's `as` does
+    /// between integers, so `300` cast to `i8` is `44`.
+    ///
+    /// From floats to integers, the fraction is truncated first into an `i32`, or an `i64` for
+    /// 64-bit outputs, so the integer part must fit in it. Other conversions are the same as
+    /// [cast_from](Cast::cast_from).
+    fn cast_wrapping_from<From: CubePrimitive>(value: From) -> Self;
+
+    fn __expand_cast_wrapping_from<From: CubePrimitive>(
+        scope: &mut Scope,
+        value: ExpandElementTyped<From>,
+    ) -> <Self as CubeType>::ExpandType {
+        let elem = <Self as CubePrimitive>::as_elem(scope);
+        wrapping_cast(scope, value.expand, elem).into()
+    }
 }
 
 impl<P: CubePrimitive> Cast for P {
     fn cast_from<From: CubePrimitive>(_value: From) -> Self {
         unexpanded!()
     }
+
+    fn cast_saturating_from<From: CubePrimitive>(_value: From) -> Self {
+        unexpanded!()
+    }
+
+    fn cast_wrapping_from<From: CubePrimitive>(_value: From) -> Self {
+        unexpanded!()
+    }
+}
+
+fn saturating_cast(scope: &mut Scope, input: ExpandElement, elem: Elem) -> ExpandElement {
+    let vectorization = input.item.vectorization;
+    let source = input.item.elem;
+
+    match (source, elem) {
+        // Every fp8 value is exact in f32, whose bounds are easier to round.
+        (Elem::Float(FloatKind::E4M3 | FloatKind::E5M2), Elem::Int(_) | Elem::UInt(_)) => {
+            let item = Item::vectorized(Elem::Float(FloatKind::F32), vectorization);
+            let wide = register(scope, item, Operator::Cast(UnaryOperator { input: *input }));
+            saturating_cast(scope, wide, elem)
+        }
+        (Elem::Float(kind), Elem::Int(_) | Elem::UInt(_)) => {
+            let (min, max) = int_range(elem);
+            let float = |value: f64| Variable::constant(ConstantScalarValue::Float(value, kind));
+            let clamped = clamp(
+                scope,
+                &input,
+                float(float_bound(kind, min)),
+                float(float_bound(kind, max)),
+            );
+
+            let is_nan = register(
+                scope,
+                Item::vectorized(Elem::Bool, vectorization),
+                Comparison::NotEqual(BinaryOperator {
+                    lhs: *input,
+                    rhs: *input,
+                }),
+            );
+            let value = register(
+                scope,
+                input.item,
+                Operator::Select(Select {
+                    cond: *is_nan,
+                    then: float(0.0),
+                    or_else: *clamped,
+                }),
+            );
+
+            convert(scope, value, elem)
+        }
+        (Elem::Int(_) | Elem::UInt(_), Elem::Int(_) | Elem::UInt(_)) => {
+            let (source_min, source_max) = int_range(source);
+            let (min, max) = int_range(elem);
+
+            if min <= source_min && source_max <= max {
+                return convert(scope, input, elem);
+            }
+
+            let int = |value: i128| match source {
+                Elem::Int(kind) => Variable::constant(ConstantScalarValue::Int(value as i64, kind)),
+                Elem::UInt(kind) => {
+                    Variable::constant(ConstantScalarValue::UInt(value as u64, kind))
+                }
+                _ => unreachable!(),
+            };
+            let clamped = clamp(
+                scope,
+                &input,
+                int(min.max(source_min)),
+                int(max.min(source_max)),
+            );
+
+            convert(scope, clamped, elem)
+        }
+        _ => convert(scope, input, elem),
+    }
+}
+
+fn wrapping_cast(scope: &mut Scope, input: ExpandElement, elem: Elem) -> ExpandElement {
+    match (input.item.elem, elem) {
+        (Elem::Float(_), Elem::Int(_) | Elem::UInt(_)) => {
+            let wide = match elem.size() {
+                8 => Elem::Int(IntKind::I64),
+                _ => Elem::Int(IntKind::I32),
+            };
+            let truncated = convert(scope, input, wide);
+            convert(scope, truncated, elem)
+        }
+        _ => convert(scope, input, elem),
+    }
+}
+
+/// The smallest and largest values of an integer element.
+fn int_range(elem: Elem) -> (i128, i128) {
+    let bits = elem.size() as u32 * 8;
+    match elem {
+        Elem::Int(_) => (-(1 << (bits - 1)), (1 << (bits - 1)) - 1),
+        _ => (0, (1 << bits) - 1),
+    }
+}
+
+/// Returns the float of the given kind closest to the integer `bound`, rounded toward zero so
+/// that casting it back to the integer type doesn't overflow.
+///
+/// Bounds beyond the finite range of the float become its largest finite value.
+fn float_bound(kind: FloatKind, bound: i128) -> f64 {
+    let fits = |value: f64| (value as i128).unsigned_abs() <= bound.unsigned_abs();
+
+    match kind {
+        FloatKind::F16 => {
+            let mut value = f16::from_f64(bound as f64);
+            while !fits(value.to_f64()) {
+                value = f16::from_bits(value.to_bits() - 1);
+            }
+            value.to_f64()
+        }
+        FloatKind::BF16 => {
+            let mut value = bf16::from_f64(bound as f64);
+            while !fits(value.to_f64()) {
+                value = bf16::from_bits(value.to_bits() - 1);
+            }
+            value.to_f64()
+        }
+        FloatKind::F64 => {
+            let mut value = bound as f64;
+            while !fits(value) {
+                value = f64::from_bits(value.to_bits() - 1);
+            }
+            value
+        }
+        _ => {
+            let mut value = bound as f32;
+            while !fits(value as f64) {
+                value = f32::from_bits(value.to_bits() - 1);
+            }
+            value as f64
+        }
+    }
+}
+
+fn clamp(
+    scope: &mut Scope,
+    input: &ExpandElement,
+    min_value: Variable,
+    max_value: Variable,
+) -> ExpandElement {
+    register(
+        scope,
+        input.item,
+        Arithmetic::Clamp(ClampOperator {
+            input: **input,
+            min_value,
+            max_value,
+        }),
+    )
+}
+
+fn convert(scope: &mut Scope, input: ExpandElement, elem: Elem) -> ExpandElement {
+    if input.item.elem == elem {
+        return input;
+    }
+
+    let item = Item::vectorized(elem, input.item.vectorization);
+    register(scope, item, Operator::Cast(UnaryOperator { input: *input }))
+}
+
+fn register(scope: &mut Scope, item: Item, operation: impl Into<Operation>) -> ExpandElement {
+    let out = scope.create_local(item);
+    scope.register(Instruction::new(operation, *out));
+    out
 }
 
 /// Enables reinterpet-casting/bitcasting from any floating point value to any integer value and vice
@@ -8,6 +8,7 @@ use crate::{
 
 use super::Numeric;
 
+mod fp8;
 mod relaxed;
 mod tensor_float;
 mod typemap;
@@ -23,16 +24,35 @@ pub trait Float:
     + Cos
     + Sin
     + Tanh
+    + Log2
+    + Exp2
+    + Sinh
+    + Cosh
+    + Asinh
+    + Acosh
+    + Atanh
+    + Tan
+    + Asin
+    + Acos
+    + Atan
     + Powf
+    + Atan2
     + Sqrt
     + Round
     + Floor
+    + Trunc
+    + Fract
     + Ceil
     + Erf
+    + Gelu
     + Recip
     + Magnitude
     + Normalize
     + Dot
+    + Max
+    + Min
+    + Clamp
+    + Fma
     + Into<Self::ExpandType>
     + core::ops::Neg<Output = Self>
     + core::ops::Add<Output = Self>
@@ -67,12 +87,18 @@ pub trait Float:
 
 macro_rules! impl_float {
     (half $primitive:ident, $kind:ident) => {
-        impl_float!($primitive, $kind, |val| $primitive::from_f32(val));
+        impl_float!(
+            $primitive,
+            $kind,
+            |val| $primitive::from_f32(val),
+            $primitive::ZERO,
+            $primitive::ONE
+        );
     };
     ($primitive:ident, $kind:ident) => {
-        impl_float!($primitive, $kind, |val| val as $primitive);
+        impl_float!($primitive, $kind, |val| val as $primitive, 0.0, 1.0);
     };
-    ($primitive:ident, $kind:ident, $new:expr) => {
+    ($primitive:ident, $kind:ident, $new:expr, $zero:expr, $one:expr) => {
         impl CubeType for $primitive {
             type ExpandType = ExpandElementTyped<$primitive>;
         }
@@ -92,6 +118,11 @@ macro_rules! impl_float {
         }
 
         impl Numeric for $primitive {
+            const MIN: Self = $primitive::MIN;
+            const MAX: Self = $primitive::MAX;
+            const ZERO: Self = $zero;
+            const ONE: Self = $one;
+
             fn min_value() -> Self {
                 <Self as num_traits::Float>::min_value()
             }
@@ -147,22 +178,38 @@ impl ScalarArgSettings for f16 {
     fn register<R: Runtime>(&self, settings: &mut KernelLauncher<R>) {
         settings.register_f16(*self);
     }
+
+    fn register_slice<R: Runtime>(values: &[Self], settings: &mut KernelLauncher<R>) {
+        settings.register_f16_slice(values);
+    }
 }
 
 impl ScalarArgSettings for bf16 {
     fn register<R: Runtime>(&self, settings: &mut KernelLauncher<R>) {
         settings.register_bf16(*self);
     }
+
+    fn register_slice<R: Runtime>(values: &[Self], settings: &mut KernelLauncher<R>) {
+        settings.register_bf16_slice(values);
+    }
 }
 
 impl ScalarArgSettings for f32 {
     fn register<R: Runtime>(&self, settings: &mut KernelLauncher<R>) {
         settings.register_f32(*self);
     }
+
+    fn register_slice<R: Runtime>(values: &[Self], settings: &mut KernelLauncher<R>) {
+        settings.register_f32_slice(values);
+    }
 }
 
 impl ScalarArgSettings for f64 {
     fn register<R: Runtime>(&self, settings: &mut KernelLauncher<R>) {
         settings.register_f64(*self);
     }
+
+    fn register_slice<R: Runtime>(values: &[Self], settings: &mut KernelLauncher<R>) {
+        settings.register_f64_slice(values);
+    }
 }
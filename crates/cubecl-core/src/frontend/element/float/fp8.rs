@@ -0,0 +1,115 @@
+use cubecl_common::{e4m3, e5m2};
+use cubecl_ir::{Elem, ExpandElement, FloatKind, Scope};
+
+use crate::prelude::Numeric;
+
+use super::{
+    init_expand_element, CubePrimitive, CubeType, ExpandElementBaseInit, ExpandElementTyped, Float,
+    Init, IntoRuntime, KernelBuilder, KernelLauncher, LaunchArgExpand, Runtime, ScalarArgSettings,
+};
+
+macro_rules! impl_fp8 {
+    ($primitive:ident, $kind:ident, $register:ident, $register_slice:ident) => {
+        impl CubeType for $primitive {
+            type ExpandType = ExpandElementTyped<$primitive>;
+        }
+
+        impl CubePrimitive for $primitive {
+            /// Return the element type to use on GPU
+            fn as_elem_native() -> Option<Elem> {
+                Some(Elem::Float(FloatKind::$kind))
+            }
+        }
+
+        impl IntoRuntime for $primitive {
+            fn __expand_runtime_method(self, scope: &mut Scope) -> ExpandElementTyped<Self> {
+                let expand: ExpandElementTyped<Self> = self.into();
+                Init::init(expand, scope)
+            }
+        }
+
+        impl Numeric for $primitive {
+            const MIN: Self = $primitive::MIN;
+            const MAX: Self = $primitive::MAX;
+            const ZERO: Self = $primitive::ZERO;
+            const ONE: Self = $primitive::ONE;
+
+            fn min_value() -> Self {
+                $primitive::MIN
+            }
+            fn max_value() -> Self {
+                $primitive::MAX
+            }
+        }
+
+        impl ExpandElementBaseInit for $primitive {
+            fn init_elem(scope: &mut Scope, elem: ExpandElement) -> ExpandElement {
+                init_expand_element(scope, elem)
+            }
+        }
+
+        impl ScalarArgSettings for $primitive {
+            fn register<R: Runtime>(&self, settings: &mut KernelLauncher<R>) {
+                settings.$register(*self);
+            }
+
+            fn register_slice<R: Runtime>(values: &[Self], settings: &mut KernelLauncher<R>) {
+                settings.$register_slice(values);
+            }
+        }
+
+        impl LaunchArgExpand for $primitive {
+            type CompilationArg = ();
+
+            fn expand(
+                _: &Self::CompilationArg,
+                builder: &mut KernelBuilder,
+            ) -> ExpandElementTyped<Self> {
+                builder.scalar($primitive::as_elem(&builder.context)).into()
+            }
+        }
+    };
+}
+
+impl_fp8!(e4m3, E4M3, register_e4m3, register_e4m3_slice);
+impl_fp8!(e5m2, E5M2, register_e5m2, register_e5m2_slice);
+
+impl Float for e4m3 {
+    const DIGITS: u32 = 0;
+    const EPSILON: Self = e4m3::EPSILON;
+    /// `e4m3` has no infinity, the conversion saturates to the largest finite value.
+    const INFINITY: Self = e4m3::MAX;
+    const MANTISSA_DIGITS: u32 = 4;
+    const MAX_10_EXP: i32 = 2;
+    const MAX_EXP: i32 = 9;
+    const MIN_10_EXP: i32 = -1;
+    const MIN_EXP: i32 = -5;
+    const MIN_POSITIVE: Self = e4m3::MIN_POSITIVE;
+    const NAN: Self = e4m3::NAN;
+    /// `e4m3` has no infinity, the conversion saturates to the lowest finite value.
+    const NEG_INFINITY: Self = e4m3::MIN;
+    const RADIX: u32 = 2;
+
+    fn new(val: f32) -> Self {
+        e4m3::from_f32(val)
+    }
+}
+
+impl Float for e5m2 {
+    const DIGITS: u32 = 0;
+    const EPSILON: Self = e5m2::EPSILON;
+    const INFINITY: Self = e5m2::INFINITY;
+    const MANTISSA_DIGITS: u32 = 3;
+    const MAX_10_EXP: i32 = 4;
+    const MAX_EXP: i32 = 16;
+    const MIN_10_EXP: i32 = -4;
+    const MIN_EXP: i32 = -13;
+    const MIN_POSITIVE: Self = e5m2::MIN_POSITIVE;
+    const NAN: Self = e5m2::NAN;
+    const NEG_INFINITY: Self = e5m2::NEG_INFINITY;
+    const RADIX: u32 = 2;
+
+    fn new(val: f32) -> Self {
+        e5m2::from_f32(val)
+    }
+}
@@ -27,6 +27,11 @@ impl IntoRuntime for flex32 {
 }
 
 impl Numeric for flex32 {
+    const MIN: Self = flex32::from_f32(f32::MIN);
+    const MAX: Self = flex32::from_f32(f32::MAX);
+    const ZERO: Self = flex32::from_f32(0.0);
+    const ONE: Self = flex32::from_f32(1.0);
+
     fn min_value() -> Self {
         <Self as num_traits::Float>::min_value()
     }
@@ -28,6 +28,11 @@ impl IntoRuntime for tf32 {
 }
 
 impl Numeric for tf32 {
+    const MIN: Self = tf32::from_f32(f32::MIN);
+    const MAX: Self = tf32::from_f32(f32::MAX);
+    const ZERO: Self = tf32::from_f32(0.0);
+    const ONE: Self = tf32::from_f32(1.0);
+
     fn min_value() -> Self {
         Self::from_f32(f32::MIN)
     }
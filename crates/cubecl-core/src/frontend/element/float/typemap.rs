@@ -201,7 +201,14 @@ impl<const POS: u8> IntoRuntime for FloatExpand<POS> {
     }
 }
 
+/// The extremes are the ones of `f32`, use [min_value](Numeric::min_value) and
+/// [max_value](Numeric::max_value) for the ones of the element type the kernel is compiled for.
 impl<const POS: u8> Numeric for FloatExpand<POS> {
+    const MIN: Self = FloatExpand::from_f32(f32::MIN);
+    const MAX: Self = FloatExpand::from_f32(f32::MAX);
+    const ZERO: Self = FloatExpand::from_f32(0.0);
+    const ONE: Self = FloatExpand::from_f32(1.0);
+
     fn min_value() -> Self {
         panic!("Can't use min value in comptime with dynamic element type");
     }
@@ -221,21 +228,37 @@ impl<const POS: u8> Dot for FloatExpand<POS> {}
 impl<const POS: u8> Magnitude for FloatExpand<POS> {}
 impl<const POS: u8> Recip for FloatExpand<POS> {}
 impl<const POS: u8> Erf for FloatExpand<POS> {}
+impl<const POS: u8> Gelu for FloatExpand<POS> {}
 impl<const POS: u8> Exp for FloatExpand<POS> {}
 impl<const POS: u8> Remainder for FloatExpand<POS> {}
 impl<const POS: u8> Abs for FloatExpand<POS> {}
 impl<const POS: u8> Max for FloatExpand<POS> {}
 impl<const POS: u8> Min for FloatExpand<POS> {}
 impl<const POS: u8> Clamp for FloatExpand<POS> {}
+impl<const POS: u8> Fma for FloatExpand<POS> {}
 impl<const POS: u8> Log for FloatExpand<POS> {}
 impl<const POS: u8> Log1p for FloatExpand<POS> {}
 impl<const POS: u8> Cos for FloatExpand<POS> {}
 impl<const POS: u8> Sin for FloatExpand<POS> {}
 impl<const POS: u8> Tanh for FloatExpand<POS> {}
+impl<const POS: u8> Log2 for FloatExpand<POS> {}
+impl<const POS: u8> Exp2 for FloatExpand<POS> {}
+impl<const POS: u8> Sinh for FloatExpand<POS> {}
+impl<const POS: u8> Cosh for FloatExpand<POS> {}
+impl<const POS: u8> Asinh for FloatExpand<POS> {}
+impl<const POS: u8> Acosh for FloatExpand<POS> {}
+impl<const POS: u8> Atanh for FloatExpand<POS> {}
+impl<const POS: u8> Tan for FloatExpand<POS> {}
+impl<const POS: u8> Asin for FloatExpand<POS> {}
+impl<const POS: u8> Acos for FloatExpand<POS> {}
+impl<const POS: u8> Atan for FloatExpand<POS> {}
 impl<const POS: u8> Powf for FloatExpand<POS> {}
+impl<const POS: u8> Atan2 for FloatExpand<POS> {}
 impl<const POS: u8> Sqrt for FloatExpand<POS> {}
 impl<const POS: u8> Round for FloatExpand<POS> {}
 impl<const POS: u8> Floor for FloatExpand<POS> {}
+impl<const POS: u8> Trunc for FloatExpand<POS> {}
+impl<const POS: u8> Fract for FloatExpand<POS> {}
 impl<const POS: u8> Ceil for FloatExpand<POS> {}
 
 impl<T: Index, const POS: u8> CubeIndex<T> for FloatExpand<POS> {
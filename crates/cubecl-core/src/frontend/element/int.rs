@@ -2,8 +2,8 @@ use cubecl_ir::ExpandElement;
 
 use crate::frontend::{CubeType, Numeric};
 use crate::ir::{Elem, IntKind, Scope};
-use crate::prelude::BitwiseNot;
-use crate::prelude::{FindFirstSet, LeadingZeros};
+use crate::prelude::{Abs, BitwiseNot, Signum};
+use crate::prelude::{FindFirstSet, LeadingZeros, TrailingZeros};
 use crate::Runtime;
 use crate::{
     compute::{KernelBuilder, KernelLauncher},
@@ -15,16 +15,23 @@ use super::{
     IntoRuntime, LaunchArgExpand, ScalarArgSettings, __expand_new,
 };
 
+mod pow;
 mod typemap;
+
+pub use pow::*;
 pub use typemap::*;
 
 /// Signed or unsigned integer. Used as input in int kernels
 pub trait Int:
     Numeric
+    + Abs
+    + Signum
+    + IntPow
     + CountOnes
     + ReverseBits
     + BitwiseNot
     + LeadingZeros
+    + TrailingZeros
     + FindFirstSet
     + std::ops::Rem<Output = Self>
     + core::ops::Add<Output = Self>
@@ -78,6 +85,11 @@ macro_rules! impl_int {
         }
 
         impl Numeric for $type {
+            const MIN: Self = $type::MIN;
+            const MAX: Self = $type::MAX;
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
             fn min_value() -> Self {
                 $type::MIN
             }
@@ -122,22 +134,38 @@ impl ScalarArgSettings for i8 {
     fn register<R: Runtime>(&self, settings: &mut KernelLauncher<R>) {
         settings.register_i8(*self);
     }
+
+    fn register_slice<R: Runtime>(values: &[Self], settings: &mut KernelLauncher<R>) {
+        settings.register_i8_slice(values);
+    }
 }
 
 impl ScalarArgSettings for i16 {
     fn register<R: Runtime>(&self, settings: &mut KernelLauncher<R>) {
         settings.register_i16(*self);
     }
+
+    fn register_slice<R: Runtime>(values: &[Self], settings: &mut KernelLauncher<R>) {
+        settings.register_i16_slice(values);
+    }
 }
 
 impl ScalarArgSettings for i32 {
     fn register<R: Runtime>(&self, settings: &mut KernelLauncher<R>) {
         settings.register_i32(*self);
     }
+
+    fn register_slice<R: Runtime>(values: &[Self], settings: &mut KernelLauncher<R>) {
+        settings.register_i32_slice(values);
+    }
 }
 
 impl ScalarArgSettings for i64 {
     fn register<R: Runtime>(&self, settings: &mut KernelLauncher<R>) {
         settings.register_i64(*self);
     }
+
+    fn register_slice<R: Runtime>(values: &[Self], settings: &mut KernelLauncher<R>) {
+        settings.register_i64_slice(values);
+    }
 }
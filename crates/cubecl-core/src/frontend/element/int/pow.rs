@@ -0,0 +1,91 @@
+use crate::{self as cubecl, unexpanded};
+use cubecl::prelude::*;
+
+use crate::frontend::operation::mul;
+
+/// Raise an integer to an unsigned integer power.
+///
+/// When the exponent is known at compile time, the power is unrolled into a minimal chain of
+/// multiplications using exponentiation by squaring. Otherwise a loop doing the same thing is
+/// emitted. Overflow wraps like any other integer multiplication, and `pow(x, 0)` is always `1`.
+pub trait IntPow: Numeric {
+    #[allow(unused_variables)]
+    fn pow(self, exp: u32) -> Self {
+        unexpanded!()
+    }
+
+    fn __expand_pow(
+        scope: &mut Scope,
+        base: ExpandElementTyped<Self>,
+        exp: ExpandElementTyped<u32>,
+    ) -> ExpandElementTyped<Self> {
+        match exp.constant() {
+            Some(exp) => pow_unrolled(scope, base, exp.as_u32()),
+            None => pow_loop::expand::<Self>(scope, base, exp),
+        }
+    }
+}
+
+impl<I: IntPow> ExpandElementTyped<I> {
+    pub fn __expand_pow_method(
+        self,
+        scope: &mut Scope,
+        exp: ExpandElementTyped<u32>,
+    ) -> ExpandElementTyped<I> {
+        I::__expand_pow(scope, self, exp)
+    }
+}
+
+fn pow_unrolled<I: Numeric>(
+    scope: &mut Scope,
+    base: ExpandElementTyped<I>,
+    mut exp: u32,
+) -> ExpandElementTyped<I> {
+    let mut result: Option<ExpandElementTyped<I>> = None;
+    let mut square = base;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = Some(match result {
+                Some(result) => mul::expand(scope, result, square.clone()),
+                None => square.clone(),
+            });
+        }
+
+        exp >>= 1;
+        if exp > 0 {
+            square = mul::expand(scope, square.clone(), square);
+        }
+    }
+
+    result.unwrap_or_else(|| I::__expand_from_int(scope, 1i64.into()))
+}
+
+#[cube]
+fn pow_loop<I: Numeric>(base: I, exp: u32) -> I {
+    let mut result = I::from_int(1);
+    let mut square = base;
+    let mut exp = exp;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result *= square;
+        }
+        exp >>= 1;
+        if exp > 0 {
+            square *= square;
+        }
+    }
+
+    result
+}
+
+impl IntPow for i8 {}
+impl IntPow for i16 {}
+impl IntPow for i32 {}
+impl IntPow for i64 {}
+impl IntPow for u8 {}
+impl IntPow for u16 {}
+impl IntPow for u32 {}
+impl IntPow for u64 {}
+impl<const POS: u8> IntPow for IntExpand<POS> {}
@@ -171,7 +171,14 @@ impl<const POS: u8> IntoRuntime for IntExpand<POS> {
     }
 }
 
+/// The extremes are the ones of `i64`, use [min_value](Numeric::min_value) and
+/// [max_value](Numeric::max_value) for the ones of the element type the kernel is compiled for.
 impl<const POS: u8> Numeric for IntExpand<POS> {
+    const MIN: Self = IntExpand(i64::MIN);
+    const MAX: Self = IntExpand(i64::MAX);
+    const ZERO: Self = IntExpand(0);
+    const ONE: Self = IntExpand(1);
+
     fn min_value() -> Self {
         panic!("Can't use min value in comptime with dynamic element type");
     }
@@ -188,6 +195,7 @@ impl<const POS: u8> ExpandElementBaseInit for IntExpand<POS> {
 
 impl<const POS: u8> Remainder for IntExpand<POS> {}
 impl<const POS: u8> Abs for IntExpand<POS> {}
+impl<const POS: u8> Signum for IntExpand<POS> {}
 impl<const POS: u8> Max for IntExpand<POS> {}
 impl<const POS: u8> Min for IntExpand<POS> {}
 impl<const POS: u8> Clamp for IntExpand<POS> {}
@@ -197,6 +205,7 @@ impl<const POS: u8> ReverseBits for IntExpand<POS> {}
 impl<const POS: u8> CountOnes for IntExpand<POS> {}
 impl<const POS: u8> FindFirstSet for IntExpand<POS> {}
 impl<const POS: u8> LeadingZeros for IntExpand<POS> {}
+impl<const POS: u8> TrailingZeros for IntExpand<POS> {}
 
 impl<T: Index, const POS: u8> CubeIndex<T> for IntExpand<POS> {
     type Output = Self;
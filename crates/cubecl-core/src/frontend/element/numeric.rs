@@ -46,7 +46,22 @@ pub trait Numeric:
     + std::cmp::PartialOrd
     + std::cmp::PartialEq
 {
+    /// Smallest finite value of the type, the identity of a max reduction.
+    ///
+    /// The constant is kept as written, so kernels generic over the element type get the value of
+    /// the placeholder type they are expanded with. Use [min_value](Numeric::min_value) to get the
+    /// value of the element type they are compiled for.
+    const MIN: Self;
+    /// Largest finite value of the type, the identity of a min reduction.
+    const MAX: Self;
+    /// Zero, the identity of a sum.
+    const ZERO: Self;
+    /// One, the identity of a product.
+    const ONE: Self;
+
+    /// Smallest finite value of the element type the kernel is compiled for.
     fn min_value() -> Self;
+    /// Largest finite value of the element type the kernel is compiled for.
     fn max_value() -> Self;
 
     fn __expand_min_value(scope: &mut Scope) -> <Self as CubeType>::ExpandType {
@@ -120,6 +135,18 @@ pub trait Numeric:
 pub trait ScalarArgSettings: Send + Sync {
     /// Register the information to the [KernelLauncher].
     fn register<R: Runtime>(&self, launcher: &mut KernelLauncher<R>);
+
+    /// Register a contiguous block of scalars to the [KernelLauncher].
+    ///
+    /// Types that are sent as-is override it to register the whole block in one call.
+    fn register_slice<R: Runtime>(values: &[Self], launcher: &mut KernelLauncher<R>)
+    where
+        Self: Sized,
+    {
+        for value in values {
+            value.register(launcher);
+        }
+    }
 }
 
 #[derive(new)]
@@ -53,6 +53,11 @@ macro_rules! declare_uint {
         }
 
         impl Numeric for $primitive {
+            const MIN: Self = $primitive::MIN;
+            const MAX: Self = $primitive::MAX;
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
             fn min_value() -> Self {
                 $primitive::MIN
             }
@@ -80,22 +85,38 @@ impl ScalarArgSettings for u8 {
     fn register<R: Runtime>(&self, settings: &mut KernelLauncher<R>) {
         settings.register_u8(*self);
     }
+
+    fn register_slice<R: Runtime>(values: &[Self], settings: &mut KernelLauncher<R>) {
+        settings.register_u8_slice(values);
+    }
 }
 
 impl ScalarArgSettings for u16 {
     fn register<R: Runtime>(&self, settings: &mut KernelLauncher<R>) {
         settings.register_u16(*self);
     }
+
+    fn register_slice<R: Runtime>(values: &[Self], settings: &mut KernelLauncher<R>) {
+        settings.register_u16_slice(values);
+    }
 }
 
 impl ScalarArgSettings for u32 {
     fn register<R: Runtime>(&self, settings: &mut KernelLauncher<R>) {
         settings.register_u32(*self);
     }
+
+    fn register_slice<R: Runtime>(values: &[Self], settings: &mut KernelLauncher<R>) {
+        settings.register_u32_slice(values);
+    }
 }
 
 impl ScalarArgSettings for u64 {
     fn register<R: Runtime>(&self, settings: &mut KernelLauncher<R>) {
         settings.register_u64(*self);
     }
+
+    fn register_slice<R: Runtime>(values: &[Self], settings: &mut KernelLauncher<R>) {
+        settings.register_u64_slice(values);
+    }
 }
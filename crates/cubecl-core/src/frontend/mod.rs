@@ -12,11 +12,12 @@ mod element;
 mod indexation;
 mod operation;
 mod options;
+mod overflow;
 mod plane;
 mod polyfills;
 mod topology;
 
-pub use branch::{range, range_stepped, RangeExpand, SteppedRangeExpand};
+pub use branch::{range, range_stepped, range_unrolled_by, RangeExpand, SteppedRangeExpand};
 pub use comment::*;
 pub use const_expand::*;
 pub use container::*;
@@ -25,6 +26,7 @@ pub use element::*;
 pub use indexation::*;
 pub use operation::*;
 pub use options::*;
+pub use overflow::*;
 pub use plane::*;
 pub use polyfills::*;
 pub use topology::*;
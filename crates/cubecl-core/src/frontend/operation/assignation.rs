@@ -53,7 +53,7 @@ pub mod index_assign {
     use ir::{Instruction, UIntKind, VariableKind};
 
     use crate::{
-        flex32,
+        e4m3, e5m2, flex32,
         frontend::CubeType,
         prelude::{ExpandElementTyped, SliceMut},
         tf32,
@@ -103,7 +103,9 @@ pub mod index_assign {
     impl_index!(Array);
     impl_index!(Tensor);
     impl_index!(SharedMemory);
-    impl_index_vec!(i64, i32, i16, i8, f16, bf16, flex32, tf32, f32, f64, u64, u32, u16, u8);
+    impl_index_vec!(
+        i64, i32, i16, i8, e4m3, e5m2, f16, bf16, flex32, tf32, f32, f64, u64, u32, u16, u8
+    );
 
     impl<E: CubeType, I: Index> CubeIndexMut<I> for SliceMut<E> {}
 }
@@ -112,7 +114,7 @@ pub mod index {
     use ir::{UIntKind, VariableKind};
 
     use crate::{
-        flex32,
+        e4m3, e5m2, flex32,
         frontend::{
             operation::base::{binary_expand, binary_expand_no_vec},
             CubeType,
@@ -173,7 +175,9 @@ pub mod index {
     impl_index!(Array);
     impl_index!(Tensor);
     impl_index!(SharedMemory);
-    impl_index_vec!(i64, i32, i16, i8, f16, flex32, tf32, bf16, f32, f64, u64, u32, u16, u8);
+    impl_index_vec!(
+        i64, i32, i16, i8, e4m3, e5m2, f16, flex32, tf32, bf16, f32, f64, u64, u32, u16, u8
+    );
 
     impl<E: CubeType, I: Index> CubeIndex<I> for Slice<E> {
         type Output = E;
@@ -356,7 +360,7 @@ pub mod add_assign_op {
 
     use self::ir::Arithmetic;
     use crate::{
-        frontend::operation::base::assign_op_expand,
+        frontend::{check_overflow_expand, operation::base::assign_op_expand, CheckedOp},
         prelude::{CubeType, ExpandElementTyped},
     };
 
@@ -367,49 +371,80 @@ pub mod add_assign_op {
         lhs: ExpandElementTyped<C>,
         rhs: ExpandElementTyped<C>,
     ) -> ExpandElementTyped<C> {
-        assign_op_expand(scope, lhs.into(), rhs.into(), Arithmetic::Add).into()
+        let lhs: ExpandElement = lhs.into();
+        let rhs: ExpandElement = rhs.into();
+
+        check_overflow_expand(scope, &lhs, &rhs, CheckedOp::Add);
+        assign_op_expand(scope, lhs, rhs, Arithmetic::Add).into()
     }
 }
 
 pub mod sub_assign_op {
     use self::ir::Arithmetic;
     use super::*;
-    use crate::{frontend::operation::base::assign_op_expand, prelude::ExpandElementTyped};
+    use crate::{
+        frontend::{check_overflow_expand, operation::base::assign_op_expand, CheckedOp},
+        prelude::ExpandElementTyped,
+    };
 
     pub fn expand<C: CubeType>(
         scope: &mut Scope,
         lhs: ExpandElementTyped<C>,
         rhs: ExpandElementTyped<C>,
     ) -> ExpandElement {
-        assign_op_expand(scope, lhs.into(), rhs.into(), Arithmetic::Sub)
+        let lhs: ExpandElement = lhs.into();
+        let rhs: ExpandElement = rhs.into();
+
+        check_overflow_expand(scope, &lhs, &rhs, CheckedOp::Sub);
+        assign_op_expand(scope, lhs, rhs, Arithmetic::Sub)
     }
 }
 
 pub mod mul_assign_op {
     use self::ir::Arithmetic;
     use super::*;
-    use crate::{frontend::operation::base::assign_op_expand, prelude::ExpandElementTyped};
+    use crate::{
+        frontend::{check_overflow_expand, operation::base::assign_op_expand, CheckedOp},
+        prelude::ExpandElementTyped,
+    };
 
     pub fn expand<C: CubeType>(
         scope: &mut Scope,
         lhs: ExpandElementTyped<C>,
         rhs: ExpandElementTyped<C>,
     ) -> ExpandElement {
-        assign_op_expand(scope, lhs.into(), rhs.into(), Arithmetic::Mul)
+        let lhs: ExpandElement = lhs.into();
+        let rhs: ExpandElement = rhs.into();
+
+        check_overflow_expand(scope, &lhs, &rhs, CheckedOp::Mul);
+        assign_op_expand(scope, lhs, rhs, Arithmetic::Mul)
     }
 }
 
 pub mod div_assign_op {
-    use self::ir::Arithmetic;
+    use self::ir::{Arithmetic, Elem};
     use super::*;
-    use crate::{frontend::operation::base::assign_op_expand, prelude::ExpandElementTyped};
+    use crate::{
+        frontend::operation::base::{assign_op_expand, unary_expand},
+        prelude::ExpandElementTyped,
+    };
 
+    /// Same as [div::expand](crate::frontend::div::expand), including the lowering to a
+    /// reciprocal.
     pub fn expand<C: CubeType>(
         scope: &mut Scope,
         lhs: ExpandElementTyped<C>,
         rhs: ExpandElementTyped<C>,
     ) -> ExpandElement {
-        assign_op_expand(scope, lhs.into(), rhs.into(), Arithmetic::Div)
+        let lhs: ExpandElement = lhs.into();
+        let rhs: ExpandElement = rhs.into();
+
+        if scope.allow_reciprocal && matches!(lhs.item.elem, Elem::Float(_)) {
+            let recip = unary_expand(scope, rhs, Arithmetic::Recip);
+            return assign_op_expand(scope, lhs, recip, Arithmetic::Mul);
+        }
+
+        assign_op_expand(scope, lhs, rhs, Arithmetic::Div)
     }
 }
 
@@ -1,10 +1,10 @@
-use crate::ir::{Arithmetic, Bitwise, ExpandElement, Operator, Scope};
+use crate::ir::{Arithmetic, Bitwise, ConstantScalarValue, Elem, ExpandElement, Operator, Scope};
 use crate::{
-    flex32,
-    frontend::{CubePrimitive, ExpandElementTyped},
+    e4m3, e5m2, flex32,
+    frontend::{check_overflow_expand, CheckedOp, CubePrimitive, ExpandElementTyped},
 };
 use crate::{
-    frontend::operation::base::{binary_expand, binary_expand_fixed_output},
+    frontend::operation::base::{binary_expand, binary_expand_fixed_output, unary_expand},
     unexpanded,
 };
 use crate::{frontend::CubeType, tf32};
@@ -18,7 +18,11 @@ pub mod add {
         lhs: ExpandElementTyped<C>,
         rhs: ExpandElementTyped<C>,
     ) -> ExpandElementTyped<C> {
-        binary_expand(scope, lhs.into(), rhs.into(), Arithmetic::Add).into()
+        let lhs: ExpandElement = lhs.into();
+        let rhs: ExpandElement = rhs.into();
+
+        check_overflow_expand(scope, &lhs, &rhs, CheckedOp::Add);
+        binary_expand(scope, lhs, rhs, Arithmetic::Add).into()
     }
 }
 
@@ -30,7 +34,11 @@ pub mod sub {
         lhs: ExpandElementTyped<C>,
         rhs: ExpandElementTyped<C>,
     ) -> ExpandElementTyped<C> {
-        binary_expand(scope, lhs.into(), rhs.into(), Arithmetic::Sub).into()
+        let lhs: ExpandElement = lhs.into();
+        let rhs: ExpandElement = rhs.into();
+
+        check_overflow_expand(scope, &lhs, &rhs, CheckedOp::Sub);
+        binary_expand(scope, lhs, rhs, Arithmetic::Sub).into()
     }
 }
 
@@ -42,19 +50,38 @@ pub mod mul {
         lhs: ExpandElementTyped<C>,
         rhs: ExpandElementTyped<C>,
     ) -> ExpandElementTyped<C> {
-        binary_expand(scope, lhs.into(), rhs.into(), Arithmetic::Mul).into()
+        let lhs: ExpandElement = lhs.into();
+        let rhs: ExpandElement = rhs.into();
+
+        check_overflow_expand(scope, &lhs, &rhs, CheckedOp::Mul);
+        binary_expand(scope, lhs, rhs, Arithmetic::Mul).into()
     }
 }
 
 pub mod div {
     use super::*;
 
+    /// Divide `lhs` by `rhs`.
+    ///
+    /// When the kernel allows [FastMath::AllowReciprocal](crate::prelude::FastMath), float
+    /// divisions are lowered to `lhs * recip(rhs)`. A multiplication and a reciprocal are usually
+    /// cheaper than a division on GPUs, but the result is rounded twice, so it may be off by an
+    /// ulp or two from the correctly rounded quotient. Divisors close to the largest finite value
+    /// have a reciprocal that underflows, which gives `0` instead of a tiny quotient.
     pub fn expand<C: CubePrimitive>(
         scope: &mut Scope,
         lhs: ExpandElementTyped<C>,
         rhs: ExpandElementTyped<C>,
     ) -> ExpandElementTyped<C> {
-        binary_expand(scope, lhs.into(), rhs.into(), Arithmetic::Div).into()
+        let lhs: ExpandElement = lhs.into();
+        let rhs: ExpandElement = rhs.into();
+
+        if scope.allow_reciprocal && matches!(lhs.item.elem, Elem::Float(_)) {
+            let recip = unary_expand(scope, rhs, Arithmetic::Recip);
+            return binary_expand(scope, lhs, recip, Arithmetic::Mul).into();
+        }
+
+        binary_expand(scope, lhs, rhs, Arithmetic::Div).into()
     }
 }
 
@@ -211,12 +238,81 @@ macro_rules! impl_binary_func_fixed_output_vectorization {
     }
 }
 
+/// Raise a float to a float power.
+///
+/// When the exponent is a constant integer from 2 to [MAX_MUL_CHAIN_EXPONENT], the power is
+/// computed with a chain of multiplications instead of the `powf` of the target, e.g. `x * x`
+/// for `x.powf(2.0)`. Each multiplication is rounded, so a power computed this way may differ
+/// from `powf` by a few ulps. Other exponents, including runtime values, use `powf`.
+pub trait Powf: CubeType + Sized {
+    fn powf(self, _rhs: Self) -> Self {
+        unexpanded!()
+    }
+
+    fn __expand_powf(
+        scope: &mut Scope,
+        lhs: ExpandElementTyped<Self>,
+        rhs: ExpandElementTyped<Self>,
+    ) -> ExpandElementTyped<Self> {
+        powf_expand(scope, lhs.into(), rhs.into()).into()
+    }
+}
+
+/// The largest constant exponent of [Powf] lowered to multiplications.
+pub const MAX_MUL_CHAIN_EXPONENT: u32 = 8;
+
+fn powf_expand(scope: &mut Scope, lhs: ExpandElement, rhs: ExpandElement) -> ExpandElement {
+    let exponent = match rhs.as_const() {
+        Some(ConstantScalarValue::Float(value, _)) => value,
+        _ => return binary_expand(scope, lhs, rhs, Arithmetic::Powf),
+    };
+
+    if exponent.fract() != 0.0 || !(2.0..=MAX_MUL_CHAIN_EXPONENT as f64).contains(&exponent) {
+        return binary_expand(scope, lhs, rhs, Arithmetic::Powf);
+    }
+
+    // Exponentiation by squaring. Since the exponent is at least 2, the result is always a new
+    // variable, never `lhs` itself.
+    let mut exponent = exponent as u32;
+    let mut power = lhs;
+    let mut result: Option<ExpandElement> = None;
+    loop {
+        if exponent & 1 == 1 {
+            result = Some(match result {
+                Some(result) => binary_expand(scope, result, power.clone(), Arithmetic::Mul),
+                None => power.clone(),
+            });
+        }
+        exponent >>= 1;
+        if exponent == 0 {
+            break;
+        }
+        power = binary_expand(scope, power.clone(), power, Arithmetic::Mul);
+    }
+
+    result.expect("The exponent is at least 2")
+}
+
+macro_rules! impl_powf {
+    ($($type:ty),*) => {
+        $(impl Powf for $type {})*
+        $(impl ExpandElementTyped<$type> {
+            pub fn __expand_powf_method(self, scope: &mut Scope, rhs: ExpandElementTyped<$type>) -> ExpandElementTyped<$type> {
+                powf_expand(scope, self.into(), rhs.into()).into()
+            }
+        })*
+    }
+}
+
+impl_powf!(e4m3, e5m2, f16, bf16, flex32, tf32, f32, f64);
 impl_binary_func!(
-    Powf,
-    powf,
-    __expand_powf,
-    __expand_powf_method,
-    Arithmetic::Powf,
+    Atan2,
+    atan2,
+    __expand_atan2,
+    __expand_atan2_method,
+    Arithmetic::Atan2,
+    e4m3,
+    e5m2,
     f16,
     bf16,
     flex32,
@@ -230,6 +326,8 @@ impl_binary_func!(
     __expand_max,
     __expand_max_method,
     Arithmetic::Max,
+    e4m3,
+    e5m2,
     f16,
     bf16,
     flex32,
@@ -251,6 +349,8 @@ impl_binary_func!(
     __expand_min,
     __expand_min_method,
     Arithmetic::Min,
+    e4m3,
+    e5m2,
     f16,
     bf16,
     flex32,
@@ -272,6 +372,8 @@ impl_binary_func!(
     __expand_rem,
     __expand_rem_method,
     Arithmetic::Remainder,
+    e4m3,
+    e5m2,
     f16,
     bf16,
     flex32,
@@ -294,6 +396,8 @@ impl_binary_func_fixed_output_vectorization!(
     __expand_dot_method,
     Arithmetic::Dot,
     None,
+    e4m3,
+    e5m2,
     f16,
     bf16,
     flex32,
@@ -23,6 +23,11 @@ pub fn select<C: CubePrimitive>(condition: bool, then: C, or_else: C) -> C {
 }
 
 /// Same as [select()] but with lines instead.
+///
+/// Each lane of the output is selected by the matching lane of the condition, which is usually
+/// the mask returned by a line comparison such as [Line::less_than]. Lines of one element are
+/// broadcast to the other lanes, so a ReLU is `select_many(x.greater_than(zero), x, zero)` with
+/// `zero = Line::new(N::from_int(0))`, for any numeric `N`.
 #[allow(unused_variables)]
 pub fn select_many<C: CubePrimitive>(
     condition: Line<bool>,
@@ -1,7 +1,7 @@
 use half::{bf16, f16};
 
 use crate::{
-    flex32,
+    e4m3, e5m2, flex32,
     ir::{Arithmetic, ClampOperator, ExpandElement, Scope},
     prelude::CubePrimitive,
     tf32, unexpanded,
@@ -36,6 +36,8 @@ pub trait Clamp: CubePrimitive + Sized {
     }
 }
 
+impl Clamp for e4m3 {}
+impl Clamp for e5m2 {}
 impl Clamp for f16 {}
 impl Clamp for bf16 {}
 impl Clamp for flex32 {}
@@ -1,9 +1,17 @@
+use half::{bf16, f16};
+
 use crate::{
-    ir::{Arithmetic, ExpandElement, FmaOperator, Instruction, Scope},
+    e4m3, e5m2, flex32,
+    ir::{
+        Arithmetic, ConstantScalarValue, Elem, ExpandElement, FloatKind, FmaOperator, Instruction,
+        Scope, Variable,
+    },
     prelude::CubePrimitive,
-    unexpanded,
+    tf32, unexpanded,
 };
 
+use super::{add, mul};
+
 /// Fused multiply-add `A*B+C`.
 #[allow(unused_variables)]
 pub fn fma<C: CubePrimitive>(a: C, b: C, c: C) -> C {
@@ -32,3 +40,62 @@ pub fn fma_expand<C: CubePrimitive>(
 
     output
 }
+
+/// Fused multiply-add `a * b + c` on floats, computed with a single rounding.
+///
+/// Lines are computed lane-wise. When all three arguments are constants, the result is folded at
+/// compile time. Float types without a native fused multiply-add, like the 8-bit floats, are
+/// computed as a multiplication followed by an addition instead.
+pub trait Fma: CubePrimitive + Sized {
+    #[allow(unused_variables)]
+    fn fma(a: Self, b: Self, c: Self) -> Self {
+        unexpanded!()
+    }
+
+    fn __expand_fma(
+        scope: &mut Scope,
+        a: Self::ExpandType,
+        b: Self::ExpandType,
+        c: Self::ExpandType,
+    ) -> Self::ExpandType {
+        if let Some(folded) = fold_fma(&a.expand, &b.expand, &c.expand) {
+            return ExpandElement::Plain(Variable::constant(folded)).into();
+        }
+
+        match a.expand.item.elem() {
+            Elem::Float(FloatKind::E4M3 | FloatKind::E5M2) => {
+                let product = mul::expand(scope, a, b);
+                add::expand(scope, product, c)
+            }
+            _ => fma_expand::<Self>(scope, a.into(), b.into(), c.into()).into(),
+        }
+    }
+}
+
+fn fold_fma(
+    a: &ExpandElement,
+    b: &ExpandElement,
+    c: &ExpandElement,
+) -> Option<ConstantScalarValue> {
+    let a = a.as_const()?;
+    let b = b.as_const()?.cast_to(a.elem());
+    let c = c.as_const()?.cast_to(a.elem());
+
+    match (a, b, c) {
+        (
+            ConstantScalarValue::Float(a, kind),
+            ConstantScalarValue::Float(b, _),
+            ConstantScalarValue::Float(c, _),
+        ) => Some(ConstantScalarValue::Float(a.mul_add(b, c), kind)),
+        _ => None,
+    }
+}
+
+impl Fma for e4m3 {}
+impl Fma for e5m2 {}
+impl Fma for f16 {}
+impl Fma for bf16 {}
+impl Fma for flex32 {}
+impl Fma for tf32 {}
+impl Fma for f32 {}
+impl Fma for f64 {}
@@ -0,0 +1,85 @@
+use half::{bf16, f16};
+
+use crate::{
+    e4m3, e5m2, flex32,
+    ir::{ConstantScalarValue, Elem, ExpandElement, Scope, Variable},
+    prelude::{CubePrimitive, ExpandElementTyped},
+    tf32, unexpanded,
+};
+
+use super::{add, mul, Erf, Tanh};
+
+/// `sqrt(2 / pi)`, the slope of the tanh approximation at zero.
+const SQRT_2_OVER_PI: f64 = 0.797_884_560_802_865_4;
+/// Cubic coefficient of the tanh approximation.
+const GELU_TANH_COEF: f64 = 0.044_715;
+
+/// Gaussian error linear unit, `x * P(X <= x)` for `X` following the standard normal
+/// distribution.
+///
+/// Lines are computed lane-wise. Large negative inputs give zero, since the cumulative
+/// distribution reaches exactly `-1 + 1` there.
+pub trait Gelu: Erf + Tanh {
+    /// Exact GELU, `0.5 * x * (1 + erf(x / sqrt(2)))`.
+    #[allow(unused_variables)]
+    fn gelu(x: Self) -> Self {
+        unexpanded!()
+    }
+
+    /// Tanh approximation of GELU, `0.5 * x * (1 + tanh(sqrt(2 / pi) * (x + 0.044715 * x^3)))`,
+    /// as used by GPT-2 and BERT.
+    #[allow(unused_variables)]
+    fn gelu_tanh(x: Self) -> Self {
+        unexpanded!()
+    }
+
+    fn __expand_gelu(scope: &mut Scope, x: Self::ExpandType) -> ExpandElementTyped<Self> {
+        let scaled = mul::expand(
+            scope,
+            x.clone(),
+            constant_like(&x, core::f64::consts::FRAC_1_SQRT_2),
+        );
+        let erf = Self::__expand_erf(scope, scaled);
+        half_x_times_one_plus(scope, x, erf)
+    }
+
+    fn __expand_gelu_tanh(scope: &mut Scope, x: Self::ExpandType) -> ExpandElementTyped<Self> {
+        let square = mul::expand(scope, x.clone(), x.clone());
+        let cube = mul::expand(scope, square, x.clone());
+        let cube = mul::expand(scope, cube, constant_like(&x, GELU_TANH_COEF));
+        let inner = add::expand(scope, x.clone(), cube);
+        let inner = mul::expand(scope, inner, constant_like(&x, SQRT_2_OVER_PI));
+        let tanh = Self::__expand_tanh(scope, inner);
+        half_x_times_one_plus(scope, x, tanh)
+    }
+}
+
+/// `0.5 * x * (1 + cdf)`, shared by both forms of GELU.
+fn half_x_times_one_plus<F: CubePrimitive>(
+    scope: &mut Scope,
+    x: ExpandElementTyped<F>,
+    cdf: ExpandElementTyped<F>,
+) -> ExpandElementTyped<F> {
+    let one_plus = add::expand(scope, cdf, constant_like(&x, 1.0));
+    let half_x = mul::expand(scope, x.clone(), constant_like(&x, 0.5));
+    mul::expand(scope, half_x, one_plus)
+}
+
+/// Scalar float constant of the element type of `x`, which binary operations broadcast to the
+/// line size of `x`.
+fn constant_like<F: CubePrimitive>(x: &ExpandElementTyped<F>, value: f64) -> ExpandElementTyped<F> {
+    let kind = match x.expand.item.elem() {
+        Elem::Float(kind) => kind,
+        elem => panic!("GELU is only defined on floats, got {elem}"),
+    };
+    ExpandElement::Plain(Variable::constant(ConstantScalarValue::Float(value, kind))).into()
+}
+
+impl Gelu for e4m3 {}
+impl Gelu for e5m2 {}
+impl Gelu for f16 {}
+impl Gelu for bf16 {}
+impl Gelu for flex32 {}
+impl Gelu for tf32 {}
+impl Gelu for f32 {}
+impl Gelu for f64 {}
@@ -6,6 +6,7 @@ mod clamp;
 mod cmp;
 mod copy;
 mod fma;
+mod gelu;
 mod unary;
 
 pub use assignation::*;
@@ -16,4 +17,5 @@ pub use clamp::*;
 pub use cmp::*;
 pub use copy::*;
 pub use fma::*;
+pub use gelu::*;
 pub use unary::*;
@@ -1,14 +1,15 @@
-use cubecl_ir::{Bitwise, Operator};
+use cubecl_ir::{Bitwise, ConstantScalarValue, FloatKind, Operator, Variable, VariableKind};
 use half::{bf16, f16};
 
 use crate::{
-    flex32,
+    e4m3, e5m2, flex32,
     ir::{Arithmetic, ExpandElement, Scope},
     prelude::{CubePrimitive, ExpandElementTyped},
     tf32, unexpanded,
 };
 
 use super::base::{unary_expand, unary_expand_fixed_output};
+use super::sub;
 
 pub mod not {
     use super::*;
@@ -86,11 +87,69 @@ macro_rules! impl_unary_func_fixed_out_ty {
     }
 }
 
+/// Same as `impl_unary_func`, but a constant operand is evaluated on the host with `$fold` instead
+/// of emitting an instruction.
+macro_rules! impl_unary_func_const_fold {
+    ($(#[$meta:meta])* $trait_name:ident, $method_name:ident, $method_name_expand:ident, $operator:expr, $fold:expr, $($type:ty),*) => {
+        $(#[$meta])*
+        pub trait $trait_name: CubePrimitive + Sized {
+            #[allow(unused_variables)]
+            fn $method_name(x: Self) -> Self {
+                unexpanded!()
+            }
+
+            fn $method_name_expand(scope: &mut Scope, x: Self::ExpandType) -> ExpandElementTyped<Self> {
+                let x: ExpandElement = x.into();
+                match fold_float_constant(&x, $fold) {
+                    Some(folded) => folded.into(),
+                    None => unary_expand(scope, x, $operator).into(),
+                }
+            }
+        }
+
+        $(impl $trait_name for $type {})*
+    }
+}
+
+/// Evaluate `fold` on the host when `x` is a float constant.
+///
+/// The result is rounded to the precision of the constant's [FloatKind], so folding gives the
+/// same value as the kernel would compute in that precision.
+fn fold_float_constant(x: &ExpandElement, fold: fn(f64) -> f64) -> Option<ExpandElement> {
+    match x.kind {
+        VariableKind::ConstantScalar(ConstantScalarValue::Float(value, kind)) => {
+            let value = round_to_float_kind(fold(value), kind);
+            Some(ExpandElement::Plain(Variable::new(
+                VariableKind::ConstantScalar(ConstantScalarValue::Float(value, kind)),
+                x.item,
+            )))
+        }
+        _ => None,
+    }
+}
+
+fn round_to_float_kind(value: f64, kind: FloatKind) -> f64 {
+    match kind {
+        FloatKind::E4M3 => e4m3::from_f64(value).to_f64(),
+        FloatKind::E5M2 => e5m2::from_f64(value).to_f64(),
+        FloatKind::F16 => f16::from_f64(value).to_f64(),
+        FloatKind::BF16 => bf16::from_f64(value).to_f64(),
+        FloatKind::Flex32 => flex32::from_f64(value).to_f64(),
+        FloatKind::TF32 => tf32::from_f64(value).to_f64(),
+        FloatKind::F32 => value as f32 as f64,
+        FloatKind::F64 => value,
+    }
+}
+
+// On signed integers, `abs(MIN)` wraps around to `MIN` like `wrapping_abs`, since the
+// positive value isn't representable in two's complement.
 impl_unary_func!(
     Abs,
     abs,
     __expand_abs,
     Arithmetic::Abs,
+    e4m3,
+    e5m2,
     f16,
     bf16,
     flex32,
@@ -106,11 +165,29 @@ impl_unary_func!(
     u32,
     u64
 );
+// Returns `-1`, `0` or `1`. Unsigned integers are never negative, so only `0` or `1`.
 impl_unary_func!(
+    Signum,
+    signum,
+    __expand_signum,
+    Arithmetic::Signum,
+    i8,
+    i16,
+    i32,
+    i64,
+    u8,
+    u16,
+    u32,
+    u64
+);
+impl_unary_func_const_fold!(
     Exp,
     exp,
     __expand_exp,
     Arithmetic::Exp,
+    f64::exp,
+    e4m3,
+    e5m2,
     f16,
     bf16,
     flex32,
@@ -118,11 +195,14 @@ impl_unary_func!(
     f32,
     f64
 );
-impl_unary_func!(
+impl_unary_func_const_fold!(
     Log,
     log,
     __expand_log,
     Arithmetic::Log,
+    f64::ln,
+    e4m3,
+    e5m2,
     f16,
     bf16,
     flex32,
@@ -130,11 +210,14 @@ impl_unary_func!(
     f32,
     f64
 );
-impl_unary_func!(
+impl_unary_func_const_fold!(
     Log1p,
     log1p,
     __expand_log1p,
     Arithmetic::Log1p,
+    f64::ln_1p,
+    e4m3,
+    e5m2,
     f16,
     bf16,
     flex32,
@@ -142,11 +225,44 @@ impl_unary_func!(
     f32,
     f64
 );
-impl_unary_func!(
+impl_unary_func_const_fold!(
+    Log2,
+    log2,
+    __expand_log2,
+    Arithmetic::Log2,
+    f64::log2,
+    e4m3,
+    e5m2,
+    f16,
+    bf16,
+    flex32,
+    tf32,
+    f32,
+    f64
+);
+impl_unary_func_const_fold!(
+    Exp2,
+    exp2,
+    __expand_exp2,
+    Arithmetic::Exp2,
+    f64::exp2,
+    e4m3,
+    e5m2,
+    f16,
+    bf16,
+    flex32,
+    tf32,
+    f32,
+    f64
+);
+impl_unary_func_const_fold!(
     Cos,
     cos,
     __expand_cos,
     Arithmetic::Cos,
+    f64::cos,
+    e4m3,
+    e5m2,
     f16,
     bf16,
     flex32,
@@ -154,11 +270,14 @@ impl_unary_func!(
     f32,
     f64
 );
-impl_unary_func!(
+impl_unary_func_const_fold!(
     Sin,
     sin,
     __expand_sin,
     Arithmetic::Sin,
+    f64::sin,
+    e4m3,
+    e5m2,
     f16,
     bf16,
     flex32,
@@ -166,11 +285,14 @@ impl_unary_func!(
     f32,
     f64
 );
-impl_unary_func!(
+impl_unary_func_const_fold!(
     Tanh,
     tanh,
     __expand_tanh,
     Arithmetic::Tanh,
+    f64::tanh,
+    e4m3,
+    e5m2,
     f16,
     bf16,
     flex32,
@@ -178,11 +300,149 @@ impl_unary_func!(
     f32,
     f64
 );
-impl_unary_func!(
+impl_unary_func_const_fold!(
+    Sinh,
+    sinh,
+    __expand_sinh,
+    Arithmetic::Sinh,
+    f64::sinh,
+    e4m3,
+    e5m2,
+    f16,
+    bf16,
+    flex32,
+    tf32,
+    f32,
+    f64
+);
+impl_unary_func_const_fold!(
+    Cosh,
+    cosh,
+    __expand_cosh,
+    Arithmetic::Cosh,
+    f64::cosh,
+    e4m3,
+    e5m2,
+    f16,
+    bf16,
+    flex32,
+    tf32,
+    f32,
+    f64
+);
+impl_unary_func_const_fold!(
+    Asinh,
+    asinh,
+    __expand_asinh,
+    Arithmetic::Asinh,
+    f64::asinh,
+    e4m3,
+    e5m2,
+    f16,
+    bf16,
+    flex32,
+    tf32,
+    f32,
+    f64
+);
+impl_unary_func_const_fold!(
+    Acosh,
+    acosh,
+    __expand_acosh,
+    Arithmetic::Acosh,
+    f64::acosh,
+    e4m3,
+    e5m2,
+    f16,
+    bf16,
+    flex32,
+    tf32,
+    f32,
+    f64
+);
+impl_unary_func_const_fold!(
+    Atanh,
+    atanh,
+    __expand_atanh,
+    Arithmetic::Atanh,
+    f64::atanh,
+    e4m3,
+    e5m2,
+    f16,
+    bf16,
+    flex32,
+    tf32,
+    f32,
+    f64
+);
+impl_unary_func_const_fold!(
+    Tan,
+    tan,
+    __expand_tan,
+    Arithmetic::Tan,
+    f64::tan,
+    e4m3,
+    e5m2,
+    f16,
+    bf16,
+    flex32,
+    tf32,
+    f32,
+    f64
+);
+impl_unary_func_const_fold!(
+    Asin,
+    asin,
+    __expand_asin,
+    Arithmetic::Asin,
+    f64::asin,
+    e4m3,
+    e5m2,
+    f16,
+    bf16,
+    flex32,
+    tf32,
+    f32,
+    f64
+);
+impl_unary_func_const_fold!(
+    Acos,
+    acos,
+    __expand_acos,
+    Arithmetic::Acos,
+    f64::acos,
+    e4m3,
+    e5m2,
+    f16,
+    bf16,
+    flex32,
+    tf32,
+    f32,
+    f64
+);
+impl_unary_func_const_fold!(
+    Atan,
+    atan,
+    __expand_atan,
+    Arithmetic::Atan,
+    f64::atan,
+    e4m3,
+    e5m2,
+    f16,
+    bf16,
+    flex32,
+    tf32,
+    f32,
+    f64
+);
+impl_unary_func_const_fold!(
     Sqrt,
     sqrt,
     __expand_sqrt,
     Arithmetic::Sqrt,
+    f64::sqrt,
+    e4m3,
+    e5m2,
     f16,
     bf16,
     flex32,
@@ -190,11 +450,18 @@ impl_unary_func!(
     f32,
     f64
 );
-impl_unary_func!(
+impl_unary_func_const_fold!(
+    /// Round to the nearest integer, with ties rounded to the nearest even integer
+    /// (banker's rounding), so `0.5` and `-0.5` round to `0` and `1.5` and `2.5` round to `2`.
+    ///
+    /// Every backend, as well as constant folding, breaks ties the same way.
     Round,
     round,
     __expand_round,
     Arithmetic::Round,
+    f64::round_ties_even,
+    e4m3,
+    e5m2,
     f16,
     bf16,
     flex32,
@@ -202,11 +469,14 @@ impl_unary_func!(
     f32,
     f64
 );
-impl_unary_func!(
+impl_unary_func_const_fold!(
     Floor,
     floor,
     __expand_floor,
     Arithmetic::Floor,
+    f64::floor,
+    e4m3,
+    e5m2,
     f16,
     bf16,
     flex32,
@@ -214,11 +484,52 @@ impl_unary_func!(
     f32,
     f64
 );
-impl_unary_func!(
+impl_unary_func_const_fold!(
+    /// Round towards zero, dropping the fractional part.
+    Trunc,
+    trunc,
+    __expand_trunc,
+    Arithmetic::Trunc,
+    f64::trunc,
+    e4m3,
+    e5m2,
+    f16,
+    bf16,
+    flex32,
+    tf32,
+    f32,
+    f64
+);
+
+/// Fractional part `x - trunc(x)`, which has the sign of `x`, so `fract(-1.25)` is `-0.25`.
+pub trait Fract: Trunc {
+    #[allow(unused_variables)]
+    fn fract(x: Self) -> Self {
+        unexpanded!()
+    }
+
+    fn __expand_fract(scope: &mut Scope, x: Self::ExpandType) -> ExpandElementTyped<Self> {
+        let trunc = Self::__expand_trunc(scope, x.clone());
+        sub::expand(scope, x, trunc)
+    }
+}
+
+impl Fract for e4m3 {}
+impl Fract for e5m2 {}
+impl Fract for f16 {}
+impl Fract for bf16 {}
+impl Fract for flex32 {}
+impl Fract for tf32 {}
+impl Fract for f32 {}
+impl Fract for f64 {}
+impl_unary_func_const_fold!(
     Ceil,
     ceil,
     __expand_ceil,
     Arithmetic::Ceil,
+    f64::ceil,
+    e4m3,
+    e5m2,
     f16,
     bf16,
     flex32,
@@ -231,6 +542,8 @@ impl_unary_func!(
     erf,
     __expand_erf,
     Arithmetic::Erf,
+    e4m3,
+    e5m2,
     f16,
     bf16,
     flex32,
@@ -238,11 +551,14 @@ impl_unary_func!(
     f32,
     f64
 );
-impl_unary_func!(
+impl_unary_func_const_fold!(
     Recip,
     recip,
     __expand_recip,
     Arithmetic::Recip,
+    f64::recip,
+    e4m3,
+    e5m2,
     f16,
     bf16,
     flex32,
@@ -256,6 +572,8 @@ impl_unary_func_fixed_out_vectorization!(
     __expand_magnitude,
     Arithmetic::Magnitude,
     None,
+    e4m3,
+    e5m2,
     f16,
     bf16,
     flex32,
@@ -268,6 +586,8 @@ impl_unary_func!(
     normalize,
     __expand_normalize,
     Arithmetic::Normalize,
+    e4m3,
+    e5m2,
     f16,
     bf16,
     flex32,
@@ -334,6 +654,21 @@ impl_unary_func_fixed_out_ty!(
     u64,
     i64
 );
+impl_unary_func_fixed_out_ty!(
+    TrailingZeros,
+    trailing_zeros,
+    __expand_trailing_zeros,
+    u32,
+    Bitwise::TrailingZeros,
+    u8,
+    i8,
+    u16,
+    i16,
+    u32,
+    i32,
+    u64,
+    i64
+);
 impl_unary_func_fixed_out_ty!(
     FindFirstSet,
     find_first_set,
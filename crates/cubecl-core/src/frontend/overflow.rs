@@ -0,0 +1,235 @@
+use crate::ir::{
+    Arithmetic, AtomicOp, BinaryOperator, Bitwise, Branch, Comparison, ConstantScalarValue, Elem,
+    ExpandElement, If, Instruction, IntKind, Item, Operation, Operator, Scope, Select, UIntKind,
+    UnaryOperator, Variable,
+};
+use crate::{
+    frontend::{Array, Atomic, ExpandElementTyped},
+    unexpanded,
+};
+
+/// Check the signed integer additions, subtractions and multiplications of the kernel for
+/// overflows, setting `flag[0]` to one when one of them overflows, like Rust does in debug builds.
+///
+/// The checks are only emitted with the `overflow_checks` feature. Without it, this does nothing
+/// and integer operations wrap silently, which is what they always do on GPUs. The operation still
+/// wraps when the flag is set, so the kernel runs to completion and the flag can be read from the
+/// host after the launch.
+///
+/// Only operations expanded after the call are checked, so it should be called at the start of
+/// the kernel. Scalar `i32` and `i64` operations are checked, lines aren't.
+pub fn enable_overflow_checks(_flag: &Array<Atomic<u32>>) {
+    unexpanded!()
+}
+
+pub mod enable_overflow_checks {
+    use super::*;
+
+    pub fn expand(scope: &mut Scope, flag: ExpandElementTyped<Array<Atomic<u32>>>) {
+        if cfg!(feature = "overflow_checks") {
+            scope.overflow_flag = Some(*flag.expand);
+        }
+    }
+}
+
+/// Integer operation that can be checked for overflows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum CheckedOp {
+    Add,
+    Sub,
+    Mul,
+}
+
+/// Set the overflow flag of the scope when `lhs op rhs` overflows.
+///
+/// Does nothing when [enable_overflow_checks] wasn't called, or when the operands aren't scalar
+/// signed integers of the same kind. The operation itself isn't registered.
+pub(crate) fn check_overflow_expand(
+    scope: &mut Scope,
+    lhs: &ExpandElement,
+    rhs: &ExpandElement,
+    op: CheckedOp,
+) {
+    let flag = match scope.overflow_flag {
+        Some(flag) if cfg!(feature = "overflow_checks") => flag,
+        _ => return,
+    };
+    let kind = match (scalar_int(lhs.item), scalar_int(rhs.item)) {
+        (Some(lhs), Some(rhs)) if lhs == rhs => lhs,
+        _ => return,
+    };
+    let (lhs, rhs): (Variable, Variable) = (**lhs, **rhs);
+    let int = Item::new(Elem::Int(kind));
+    let int_const = |value: i64| Variable::constant(ConstantScalarValue::Int(value, kind));
+
+    let overflows = match op {
+        // The result has the opposite sign of both operands.
+        CheckedOp::Add => {
+            let result = register(scope, int, Arithmetic::Add(BinaryOperator { lhs, rhs }));
+            differs_in_sign(scope, kind, lhs, rhs, *result)
+        }
+        // The operands have different signs and the result doesn't have the sign of lhs.
+        CheckedOp::Sub => {
+            let result = register(scope, int, Arithmetic::Sub(BinaryOperator { lhs, rhs }));
+            differs_in_sign(scope, kind, rhs, *result, lhs)
+        }
+        // The product is divided back by lhs, since wider integers aren't available on every
+        // backend, e.g. WGSL has no i64. Dividing by zero or dividing the minimum by -1 isn't
+        // defined, so these lhs are handled separately.
+        CheckedOp::Mul => {
+            let boolean = Item::new(Elem::Bool);
+            let result = register(scope, int, Arithmetic::Mul(BinaryOperator { lhs, rhs }));
+
+            let is_zero = compare(scope, lhs, int_const(0), Comparison::Equal);
+            let is_minus_one = compare(scope, lhs, int_const(-1), Comparison::Equal);
+            let special = register(
+                scope,
+                boolean,
+                Operator::Or(BinaryOperator {
+                    lhs: *is_zero,
+                    rhs: *is_minus_one,
+                }),
+            );
+            let divisor = register(
+                scope,
+                int,
+                Operator::Select(Select {
+                    cond: *special,
+                    then: int_const(1),
+                    or_else: lhs,
+                }),
+            );
+            let quotient = register(
+                scope,
+                int,
+                Arithmetic::Div(BinaryOperator {
+                    lhs: *result,
+                    rhs: *divisor,
+                }),
+            );
+            let mismatch = compare(scope, *quotient, rhs, Comparison::NotEqual);
+            let is_general = register(
+                scope,
+                boolean,
+                Operator::Not(UnaryOperator { input: *special }),
+            );
+            let general = register(
+                scope,
+                boolean,
+                Operator::And(BinaryOperator {
+                    lhs: *is_general,
+                    rhs: *mismatch,
+                }),
+            );
+
+            let min = match kind {
+                IntKind::I32 => i32::MIN as i64,
+                _ => i64::MIN,
+            };
+            let rhs_is_min = compare(scope, rhs, int_const(min), Comparison::Equal);
+            let negates_min = register(
+                scope,
+                boolean,
+                Operator::And(BinaryOperator {
+                    lhs: *is_minus_one,
+                    rhs: *rhs_is_min,
+                }),
+            );
+
+            register(
+                scope,
+                boolean,
+                Operator::Or(BinaryOperator {
+                    lhs: *general,
+                    rhs: *negates_min,
+                }),
+            )
+        }
+    };
+
+    let mut child = scope.child();
+    let index = Variable::constant(ConstantScalarValue::UInt(0, UIntKind::U32));
+    let pointer = register(
+        &mut child,
+        flag.item,
+        Operator::Index(BinaryOperator {
+            lhs: flag,
+            rhs: index,
+        }),
+    );
+    register(
+        &mut child,
+        Item::new(Elem::UInt(UIntKind::U32)),
+        AtomicOp::Or(BinaryOperator {
+            lhs: *pointer,
+            rhs: Variable::constant(ConstantScalarValue::UInt(1, UIntKind::U32)),
+        }),
+    );
+
+    scope.register(Branch::If(Box::new(If {
+        cond: *overflows,
+        scope: child,
+    })));
+}
+
+fn scalar_int(item: Item) -> Option<IntKind> {
+    let scalar = item.vectorization.map(|factor| factor.get()).unwrap_or(1) == 1;
+    match item.elem {
+        Elem::Int(kind @ (IntKind::I32 | IntKind::I64)) if scalar => Some(kind),
+        _ => None,
+    }
+}
+
+fn register(scope: &mut Scope, item: Item, operation: impl Into<Operation>) -> ExpandElement {
+    let out = scope.create_local(item);
+    scope.register(Instruction::new(operation, *out));
+    out
+}
+
+fn compare(
+    scope: &mut Scope,
+    lhs: Variable,
+    rhs: Variable,
+    comparison: fn(BinaryOperator) -> Comparison,
+) -> ExpandElement {
+    register(
+        scope,
+        Item::new(Elem::Bool),
+        comparison(BinaryOperator { lhs, rhs }),
+    )
+}
+
+/// Whether `reference` has a different sign than both `a` and `b`, which is when
+/// `(a ^ reference) & (b ^ reference)` is negative.
+fn differs_in_sign(
+    scope: &mut Scope,
+    kind: IntKind,
+    a: Variable,
+    b: Variable,
+    reference: Variable,
+) -> ExpandElement {
+    let int = Item::new(Elem::Int(kind));
+    let xor = |scope: &mut Scope, lhs: Variable| {
+        register(
+            scope,
+            int,
+            Bitwise::BitwiseXor(BinaryOperator {
+                lhs,
+                rhs: reference,
+            }),
+        )
+    };
+    let a_flipped = xor(scope, a);
+    let b_flipped = xor(scope, b);
+    let both = register(
+        scope,
+        int,
+        Bitwise::BitwiseAnd(BinaryOperator {
+            lhs: *a_flipped,
+            rhs: *b_flipped,
+        }),
+    );
+    let zero = Variable::constant(ConstantScalarValue::Int(0, kind));
+
+    compare(scope, *both, zero, Comparison::Lower)
+}
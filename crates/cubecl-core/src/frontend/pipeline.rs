@@ -117,7 +117,10 @@ impl<C: CubePrimitive> Default for Pipeline<C> {
 }
 
 impl<C: CubePrimitive> Pipeline<C> {
-    /// Create a pipeline instance
+    /// Create a pipeline instance with between 1 and [u8::MAX] stages.
+    ///
+    /// The shared memories of the kernel hold the buffers of every stage, so they must fit in the
+    /// shared memory of a cube, which the CUDA and HIP compilers check.
     pub fn new(_num_stages: u32) -> Self {
         Self { _c: PhantomData }
     }
@@ -153,6 +156,11 @@ impl<C: CubePrimitive> Pipeline<C> {
     }
 
     pub fn __expand_new(scope: &mut Scope, num_stages: u32) -> PipelineExpand<C> {
+        assert!(
+            (1..=u8::MAX as u32).contains(&num_stages),
+            "A pipeline must have between 1 and {} stages, got {num_stages}",
+            u8::MAX
+        );
         let elem = C::as_elem(scope);
         let variable = scope.create_pipeline(Item::new(elem), num_stages as u8);
         PipelineExpand {
@@ -30,6 +30,8 @@ pub mod plane_elect {
 
 /// Broadcasts the value from the specified plane unit at the given index
 /// to all active units within that plane.
+///
+/// Like the other plane operations, this requires [Feature::Plane](crate::Feature::Plane).
 #[allow(unused_variables)]
 pub fn plane_broadcast<E: CubePrimitive>(value: E, index: u32) -> E {
     unexpanded!()
@@ -61,6 +63,11 @@ pub mod plane_broadcast {
 }
 
 /// Perform a reduce sum operation across all units in a plane.
+///
+/// Every active unit of the plane gets the sum, which makes it a building block for reductions
+/// within a cube, such as the normalization of a softmax or a layer norm. Lines are reduced
+/// element-wise. Like the other plane operations, this lowers to warp intrinsics and requires
+/// [Feature::Plane](crate::Feature::Plane).
 #[allow(unused_variables)]
 pub fn plane_sum<E: CubePrimitive>(value: E) -> E {
     unexpanded!()
@@ -159,6 +166,8 @@ pub mod plane_exclusive_sum {
 }
 
 /// Perform a reduce prod operation across all units in a plane.
+///
+/// Every active unit of the plane gets the product, see [plane_sum()].
 pub fn plane_prod<E: CubePrimitive>(_elem: E) -> E {
     unexpanded!()
 }
@@ -256,6 +265,8 @@ pub mod plane_exclusive_prod {
 }
 
 /// Perform a reduce max operation across all units in a plane.
+///
+/// Every active unit of the plane gets the maximum, see [plane_sum()].
 pub fn plane_max<E: CubePrimitive>(_elem: E) -> E {
     unexpanded!()
 }
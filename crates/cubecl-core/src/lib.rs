@@ -34,7 +34,7 @@ use crate::compute::KernelDefinition;
 use frontend::LaunchArg;
 
 pub use cubecl_common::ExecutionMode;
-pub use cubecl_common::{flex32, tf32};
+pub use cubecl_common::{e4m3, e5m2, flex32, tf32};
 
 pub use prelude::CubeCount;
 pub use prelude::CubeDim;
@@ -23,5 +23,5 @@ pub use cubecl_runtime::server::CubeCount;
 
 pub use crate::frontend::*;
 pub use crate::{comment, comptime, terminate};
-pub use cubecl_common::{flex32, tf32, CubeDim, ExecutionMode};
+pub use cubecl_common::{e4m3, e5m2, flex32, tf32, CubeDim, ExecutionMode};
 pub use cubecl_ir::Scope;
@@ -61,12 +61,20 @@ pub enum Feature {
         k: u8,
         n: u8,
     },
+    /// The cmma int feature enables cooperative matrix-multiply and accumulate operations on
+    /// integer inputs. The supported element types and shapes are still listed with
+    /// [Cmma](Feature::Cmma).
+    CmmaInt,
     CmmaWarpSize(i32),
     Type(Elem),
     /// Features supported for floating point atomics. For integers, all methods are supported as
     /// long as the type is.
     AtomicFloat(AtomicFeature),
-    /// The pipeline feature enables pipelined (async) operations
+    /// The pipeline feature enables pipelined (async) operations, like copies from global to
+    /// shared memory that overlap with computation.
+    ///
+    /// CUDA reports it from compute capability 7.0, which has `memcpy_async`. Kernels using
+    /// pipelines fail to compile on runtimes without it, so they should check it beforehand.
     Pipeline,
 }
 
@@ -128,6 +128,386 @@ test_binary_impl!(
     ]
 );
 
+test_binary_impl!(
+    test_max,
+    F,
+    F::max,
+    [
+        {
+            input_vectorization: 1,
+            out_vectorization: 1,
+            lhs: as_type![F: 1., -3.1, -2.4, 15.1],
+            rhs: as_type![F: -1., 23.1, -1.4, 5.1],
+            expected: as_type![F: 1., 23.1, -1.4, 15.1]
+        },
+        {
+            input_vectorization: 2,
+            out_vectorization: 2,
+            lhs: as_type![F: 1., -3.1, -2.4, 15.1],
+            rhs: as_type![F: -1., 23.1, -1.4, 5.1],
+            expected: as_type![F: 1., 23.1, -1.4, 15.1]
+        },
+        {
+            input_vectorization: 4,
+            out_vectorization: 4,
+            lhs: as_type![F: 1., -3.1, -2.4, 15.1],
+            rhs: as_type![F: -1., 23.1, -1.4, 5.1],
+            expected: as_type![F: 1., 23.1, -1.4, 15.1]
+        }
+    ]
+);
+
+test_binary_impl!(
+    test_min,
+    F,
+    F::min,
+    [
+        {
+            input_vectorization: 1,
+            out_vectorization: 1,
+            lhs: as_type![F: 1., -3.1, -2.4, 15.1],
+            rhs: as_type![F: -1., 23.1, -1.4, 5.1],
+            expected: as_type![F: -1., -3.1, -2.4, 5.1]
+        },
+        {
+            input_vectorization: 2,
+            out_vectorization: 2,
+            lhs: as_type![F: 1., -3.1, -2.4, 15.1],
+            rhs: as_type![F: -1., 23.1, -1.4, 5.1],
+            expected: as_type![F: -1., -3.1, -2.4, 5.1]
+        },
+        {
+            input_vectorization: 4,
+            out_vectorization: 4,
+            lhs: as_type![F: 1., -3.1, -2.4, 15.1],
+            rhs: as_type![F: -1., 23.1, -1.4, 5.1],
+            expected: as_type![F: -1., -3.1, -2.4, 5.1]
+        }
+    ]
+);
+
+#[cube]
+fn clamp_symmetric<F: Float>(value: F, bound: F) -> F {
+    F::clamp(value, -bound, bound)
+}
+
+test_binary_impl!(
+    test_clamp,
+    F,
+    clamp_symmetric::<F>,
+    [
+        {
+            input_vectorization: 1,
+            out_vectorization: 1,
+            lhs: as_type![F: -3.1, 0.5, 2.4, -0.25],
+            rhs: as_type![F: 1., 1., 2., 0.1],
+            expected: as_type![F: -1., 0.5, 2., -0.1]
+        },
+        {
+            input_vectorization: 4,
+            out_vectorization: 4,
+            lhs: as_type![F: -3.1, 0.5, 2.4, -0.25],
+            rhs: as_type![F: 1., 1., 2., 0.1],
+            expected: as_type![F: -1., 0.5, 2., -0.1]
+        }
+    ]
+);
+
+#[cube]
+fn fma_square<F: Float>(value: F, addend: F) -> F {
+    F::fma(value, value, addend)
+}
+
+test_binary_impl!(
+    test_fma,
+    F,
+    fma_square::<F>,
+    [
+        {
+            input_vectorization: 1,
+            out_vectorization: 1,
+            lhs: as_type![F: 1., -3., 0.5, 2.],
+            rhs: as_type![F: 1., 2., -0.25, -4.],
+            expected: as_type![F: 2., 11., 0., 0.]
+        },
+        {
+            input_vectorization: 4,
+            out_vectorization: 4,
+            lhs: as_type![F: 1., -3., 0.5, 2.],
+            rhs: as_type![F: 1., 2., -0.25, -4.],
+            expected: as_type![F: 2., 11., 0., 0.]
+        }
+    ]
+);
+
+#[cube]
+fn fma_constants<F: Float>(lhs: F, rhs: F) -> F {
+    // Folded to 7 at compile time.
+    lhs * F::fma(F::new(2.), F::new(3.), F::new(1.)) + rhs
+}
+
+test_binary_impl!(
+    test_fma_constants,
+    F,
+    fma_constants::<F>,
+    [
+        {
+            input_vectorization: 1,
+            out_vectorization: 1,
+            lhs: as_type![F: 1., -1., 0.5, 0.],
+            rhs: as_type![F: 0., 1., 0.5, 2.],
+            expected: as_type![F: 7., -6., 4., 2.]
+        }
+    ]
+);
+
+#[cube(launch_unchecked, create_dummy_kernel)]
+fn fused_multiply_add<F: Float>(lhs: &Array<F>, rhs: &Array<F>, output: &mut Array<F>) {
+    if ABSOLUTE_POS < rhs.len() {
+        output[ABSOLUTE_POS] = fma_square::<F>(lhs[ABSOLUTE_POS], rhs[ABSOLUTE_POS]);
+    }
+}
+
+#[cube(launch_unchecked, create_dummy_kernel)]
+fn folded_multiply_add<F: Float>(lhs: &Array<F>, rhs: &Array<F>, output: &mut Array<F>) {
+    if ABSOLUTE_POS < rhs.len() {
+        output[ABSOLUTE_POS] = fma_constants::<F>(lhs[ABSOLUTE_POS], rhs[ABSOLUTE_POS]);
+    }
+}
+
+pub fn test_fma_source<R: Runtime, F: Float + num_traits::Float + CubeElement + Display>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let lhs = as_type![F: 1., -1., 0.5, 0.];
+    let rhs = as_type![F: 0., 1., 0.5, 2.];
+    let num_elems = lhs.len();
+    let lhs_handle = client.create(F::as_bytes(lhs));
+    let rhs_handle = client.create(F::as_bytes(rhs));
+    let output_handle = client.empty(core::mem::size_of_val(lhs));
+    let cube_count = || CubeCount::Static(1, 1, 1);
+    let cube_dim = CubeDim::new(num_elems as u32, 1, 1);
+    let args = || unsafe {
+        (
+            ArrayArg::from_raw_parts::<F>(&lhs_handle, num_elems, 1),
+            ArrayArg::from_raw_parts::<F>(&rhs_handle, num_elems, 1),
+            ArrayArg::from_raw_parts::<F>(&output_handle, num_elems, 1),
+        )
+    };
+
+    let source = |kernel: Box<dyn CubeTask<R::Compiler>>| {
+        kernel
+            .compile(&Default::default(), ExecutionMode::Unchecked)
+            .source
+            .to_lowercase()
+    };
+    let (lhs_arg, rhs_arg, output_arg) = args();
+    let fused_source = source(Box::new(KernelTask::<R::Compiler, _>::new(
+        fused_multiply_add::create_dummy_kernel::<F, R>(
+            cube_count(),
+            cube_dim,
+            lhs_arg,
+            rhs_arg,
+            output_arg,
+        ),
+    )));
+    let (lhs_arg, rhs_arg, output_arg) = args();
+    let folded_source = source(Box::new(KernelTask::<R::Compiler, _>::new(
+        folded_multiply_add::create_dummy_kernel::<F, R>(
+            cube_count(),
+            cube_dim,
+            lhs_arg,
+            rhs_arg,
+            output_arg,
+        ),
+    )));
+
+    assert!(
+        fused_source.contains("fma"),
+        "Expected a fused multiply-add:\n{fused_source}"
+    );
+    assert!(
+        !folded_source.contains("fma"),
+        "Expected the constant multiply-add to be folded:\n{folded_source}"
+    );
+}
+
+test_binary_impl!(
+    test_atan2,
+    F,
+    F::atan2,
+    [
+        {
+            input_vectorization: 1,
+            out_vectorization: 1,
+            lhs: as_type![F: 1., -1., 1., 0.],
+            rhs: as_type![F: 1., 1., -1., 2.],
+            expected: as_type![F: 0.785, -0.785, 2.356, 0.]
+        },
+        {
+            input_vectorization: 2,
+            out_vectorization: 2,
+            lhs: as_type![F: 1., -1., 1., 0.],
+            rhs: as_type![F: 1., 1., -1., 2.],
+            expected: as_type![F: 0.785, -0.785, 2.356, 0.]
+        },
+        {
+            input_vectorization: 4,
+            out_vectorization: 4,
+            lhs: as_type![F: 1., -1., 1., 0.],
+            rhs: as_type![F: 1., 1., -1., 2.],
+            expected: as_type![F: 0.785, -0.785, 2.356, 0.]
+        }
+    ]
+);
+
+#[cube(launch_unchecked)]
+fn div_exact<F: Float>(lhs: &Array<F>, rhs: &Array<F>, output: &mut Array<F>) {
+    if ABSOLUTE_POS < rhs.len() {
+        output[ABSOLUTE_POS] = lhs[ABSOLUTE_POS] / rhs[ABSOLUTE_POS];
+    }
+}
+
+#[cube(launch_unchecked, fast_math = FastMath::AllowReciprocal)]
+fn div_reciprocal<F: Float>(lhs: &Array<F>, rhs: &Array<F>, output: &mut Array<F>) {
+    if ABSOLUTE_POS < rhs.len() {
+        output[ABSOLUTE_POS] = lhs[ABSOLUTE_POS] / rhs[ABSOLUTE_POS];
+    }
+}
+
+pub fn test_div_reciprocal<R: Runtime, F: Float + num_traits::Float + CubeElement + Display>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let lhs = as_type![F: 1., -3.1, 7., 15.1, 0., 2.5, -100., 1.];
+    let rhs = as_type![F: 3., 23.1, -1.4, 5.1, 2., 0.1, 7., 1.];
+    let expected = lhs
+        .iter()
+        .zip(rhs.iter())
+        .map(|(lhs, rhs)| *lhs / *rhs)
+        .collect::<Vec<F>>();
+
+    let lhs_handle = client.create(F::as_bytes(lhs));
+    let rhs_handle = client.create(F::as_bytes(rhs));
+    let exact_handle = client.empty(core::mem::size_of_val(lhs));
+    let reciprocal_handle = client.empty(core::mem::size_of_val(lhs));
+    let num_elems = lhs.len();
+
+    unsafe {
+        div_exact::launch_unchecked::<F, R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(num_elems as u32, 1, 1),
+            ArrayArg::from_raw_parts::<F>(&lhs_handle, num_elems, 1),
+            ArrayArg::from_raw_parts::<F>(&rhs_handle, num_elems, 1),
+            ArrayArg::from_raw_parts::<F>(&exact_handle, num_elems, 1),
+        );
+        div_reciprocal::launch_unchecked::<F, R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(num_elems as u32, 1, 1),
+            ArrayArg::from_raw_parts::<F>(&lhs_handle, num_elems, 1),
+            ArrayArg::from_raw_parts::<F>(&rhs_handle, num_elems, 1),
+            ArrayArg::from_raw_parts::<F>(&reciprocal_handle, num_elems, 1),
+        );
+    }
+
+    // Rounding twice only costs a few ulps, well within the tolerance.
+    assert_equals_approx::<R, F>(&client, exact_handle, &expected, 0.001);
+    assert_equals_approx::<R, F>(&client, reciprocal_handle, &expected, 0.001);
+}
+
+#[cube(launch_unchecked, create_dummy_kernel)]
+fn square<F: Float>(input: &Array<F>, output: &mut Array<F>) {
+    if ABSOLUTE_POS < input.len() {
+        output[ABSOLUTE_POS] = F::powf(input[ABSOLUTE_POS], F::new(2.0));
+    }
+}
+
+#[cube(launch_unchecked)]
+fn cube_constant<F: Float>(input: &Array<F>, output: &mut Array<F>) {
+    if ABSOLUTE_POS < input.len() {
+        output[ABSOLUTE_POS] = F::powf(input[ABSOLUTE_POS], F::new(3.0));
+    }
+}
+
+#[cube(launch_unchecked, create_dummy_kernel)]
+fn raise_fractional<F: Float>(input: &Array<F>, output: &mut Array<F>) {
+    if ABSOLUTE_POS < input.len() {
+        output[ABSOLUTE_POS] = F::powf(input[ABSOLUTE_POS], F::new(2.5));
+    }
+}
+
+pub fn test_powf_constant_exponent<
+    R: Runtime,
+    F: Float + num_traits::Float + CubeElement + Display,
+>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    // A fractional power of a negative number isn't a real number, so the inputs are positive.
+    let input = as_type![F: 1., 3.1, 2.5, 0.5, 7., 1.2, 0., 1.5];
+    let num_elems = input.len();
+    let input_handle = client.create(F::as_bytes(input));
+    let output_handles: [Handle; 3] =
+        core::array::from_fn(|_| client.empty(core::mem::size_of_val(input)));
+    let cube_count = || CubeCount::Static(1, 1, 1);
+    let cube_dim = CubeDim::new(num_elems as u32, 1, 1);
+    let args = |output: usize| unsafe {
+        (
+            ArrayArg::from_raw_parts::<F>(&input_handle, num_elems, 1),
+            ArrayArg::from_raw_parts::<F>(&output_handles[output], num_elems, 1),
+        )
+    };
+
+    unsafe {
+        let (input, output) = args(0);
+        square::launch_unchecked::<F, R>(&client, cube_count(), cube_dim, input, output);
+        let (input, output) = args(1);
+        raise_fractional::launch_unchecked::<F, R>(&client, cube_count(), cube_dim, input, output);
+        let (input, output) = args(2);
+        cube_constant::launch_unchecked::<F, R>(&client, cube_count(), cube_dim, input, output);
+    }
+
+    // The kernels are compiled again to look at the operations in their source.
+    let source = |kernel: Box<dyn CubeTask<R::Compiler>>| {
+        kernel
+            .compile(&Default::default(), ExecutionMode::Unchecked)
+            .source
+            .to_lowercase()
+    };
+    let (input_arg, output_arg) = args(0);
+    let square_source = source(Box::new(KernelTask::<R::Compiler, _>::new(
+        square::create_dummy_kernel::<F, R>(cube_count(), cube_dim, input_arg, output_arg),
+    )));
+    let (input_arg, output_arg) = args(1);
+    let fractional_source = source(Box::new(KernelTask::<R::Compiler, _>::new(
+        raise_fractional::create_dummy_kernel::<F, R>(
+            cube_count(),
+            cube_dim,
+            input_arg,
+            output_arg,
+        ),
+    )));
+
+    assert!(
+        !square_source.contains("pow"),
+        "Expected a multiplication instead of a power:\n{square_source}"
+    );
+    assert!(
+        fractional_source.contains("pow"),
+        "Expected a power for a fractional exponent:\n{fractional_source}"
+    );
+
+    let [square_handle, fractional_handle, cube_handle] = output_handles;
+    let expected = |exponent: F| {
+        input
+            .iter()
+            .map(|x| num_traits::Float::powf(*x, exponent))
+            .collect::<Vec<F>>()
+    };
+    assert_equals_approx::<R, F>(&client, square_handle, &expected(F::new(2.0)), 0.001);
+    assert_equals_approx::<R, F>(&client, fractional_handle, &expected(F::new(2.5)), 0.001);
+    assert_equals_approx::<R, F>(&client, cube_handle, &expected(F::new(3.0)), 0.001);
+}
+
 #[allow(missing_docs)]
 #[macro_export]
 macro_rules! testgen_binary {
@@ -148,6 +528,15 @@ macro_rules! testgen_binary {
             }
 
             add_test!(test_dot);
+            add_test!(test_max);
+            add_test!(test_min);
+            add_test!(test_clamp);
+            add_test!(test_fma);
+            add_test!(test_fma_constants);
+            add_test!(test_fma_source);
+            add_test!(test_atan2);
+            add_test!(test_div_reciprocal);
+            add_test!(test_powf_constant_exponent);
         }
     };
 }
@@ -50,6 +50,30 @@ pub fn kernel_select<F: Float>(output: &mut Array<F>, cond: u32) {
     }
 }
 
+#[cube(launch)]
+pub fn kernel_select_many<F: Float>(
+    lhs: &Array<Line<F>>,
+    rhs: &Array<Line<F>>,
+    output: &mut Array<Line<F>>,
+) {
+    if ABSOLUTE_POS < output.len() {
+        let lhs = lhs[ABSOLUTE_POS];
+        let rhs = rhs[ABSOLUTE_POS];
+        // Lane-wise minimum, from a mask with one bool per lane.
+        output[ABSOLUTE_POS] = select_many(lhs.less_than(rhs), lhs, rhs);
+    }
+}
+
+#[cube(launch)]
+pub fn kernel_select_relu<N: Numeric>(input: &Array<Line<N>>, output: &mut Array<Line<N>>) {
+    if ABSOLUTE_POS < output.len() {
+        let value = input[ABSOLUTE_POS];
+        // The scalar zero is broadcast to every lane.
+        let zero = Line::new(N::from_int(0));
+        output[ABSOLUTE_POS] = select_many(value.greater_than(zero), value, zero);
+    }
+}
+
 pub fn test_switch_statement<R: Runtime, F: Float + CubeElement>(
     client: ComputeClient<R::Server, R::Channel>,
 ) {
@@ -162,6 +186,55 @@ pub fn test_select<R: Runtime, F: Float + CubeElement>(
     }
 }
 
+pub fn test_select_many<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+    vectorization: u8,
+) {
+    let lhs = client.create(as_bytes![F: 1.0, -2.0, 3.0, 4.0, 0.5, 6.0, -7.0, 8.0]);
+    let rhs = client.create(as_bytes![F: 2.0, -3.0, 3.0, -4.0, 1.5, 5.0, -6.0, 9.0]);
+    let output = client.empty(8 * core::mem::size_of::<F>());
+    let num_lines = 8 / vectorization as usize;
+
+    kernel_select_many::launch::<F, R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(num_lines as u32, 1, 1),
+        unsafe { ArrayArg::from_raw_parts::<F>(&lhs, num_lines, vectorization) },
+        unsafe { ArrayArg::from_raw_parts::<F>(&rhs, num_lines, vectorization) },
+        unsafe { ArrayArg::from_raw_parts::<F>(&output, num_lines, vectorization) },
+    );
+
+    let actual = client.read_one(output.binding());
+    let actual = F::from_bytes(&actual);
+    let expected = [1.0, -3.0, 3.0, -4.0, 0.5, 5.0, -7.0, 8.0].map(F::new);
+
+    assert_eq!(actual, &expected);
+}
+
+pub fn test_select_relu<R: Runtime, N: Numeric + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+    vectorization: u8,
+) {
+    let input = [-3, 1, 0, 4, -1, 2, 5, -8].map(N::from_int);
+    let input = client.create(N::as_bytes(&input));
+    let output = client.empty(8 * core::mem::size_of::<N>());
+    let num_lines = 8 / vectorization as usize;
+
+    kernel_select_relu::launch::<N, R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(num_lines as u32, 1, 1),
+        unsafe { ArrayArg::from_raw_parts::<N>(&input, num_lines, vectorization) },
+        unsafe { ArrayArg::from_raw_parts::<N>(&output, num_lines, vectorization) },
+    );
+
+    let actual = client.read_one(output.binding());
+    let actual = N::from_bytes(&actual);
+    let expected = [0, 1, 0, 4, 0, 2, 5, 0].map(N::from_int);
+
+    assert_eq!(actual, &expected);
+}
+
 #[allow(missing_docs)]
 #[macro_export]
 macro_rules! testgen_branch {
@@ -213,5 +286,71 @@ macro_rules! testgen_branch {
                 client, false,
             );
         }
+
+        #[test]
+        fn test_select_many_vec2() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::branch::test_select_many::<TestRuntime, FloatType>(
+                client, 2,
+            );
+        }
+
+        #[test]
+        fn test_select_many_vec4() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::branch::test_select_many::<TestRuntime, FloatType>(
+                client, 4,
+            );
+        }
+
+        #[test]
+        fn test_select_relu_vec1() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::branch::test_select_relu::<TestRuntime, FloatType>(
+                client, 1,
+            );
+        }
+
+        #[test]
+        fn test_select_relu_vec4() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::branch::test_select_relu::<TestRuntime, FloatType>(
+                client, 4,
+            );
+        }
+    };
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_branch_int {
+    () => {
+        mod branch_int {
+            use super::*;
+
+            #[test]
+            fn test_select_relu_vec1() {
+                let client = TestRuntime::client(&Default::default());
+                cubecl_core::runtime_tests::branch::test_select_relu::<TestRuntime, IntType>(
+                    client, 1,
+                );
+            }
+
+            #[test]
+            fn test_select_relu_vec2() {
+                let client = TestRuntime::client(&Default::default());
+                cubecl_core::runtime_tests::branch::test_select_relu::<TestRuntime, IntType>(
+                    client, 2,
+                );
+            }
+
+            #[test]
+            fn test_select_relu_vec4() {
+                let client = TestRuntime::client(&Default::default());
+                cubecl_core::runtime_tests::branch::test_select_relu::<TestRuntime, IntType>(
+                    client, 4,
+                );
+            }
+        }
     };
 }
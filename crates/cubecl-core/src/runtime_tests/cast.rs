@@ -0,0 +1,102 @@
+use crate::{
+    self as cubecl,
+    ir::{Elem, IntKind},
+    Feature,
+};
+
+use cubecl::prelude::*;
+
+#[cube(launch)]
+pub fn kernel_cast_saturating(input: &Array<f32>, output: &mut Array<i32>) {
+    if ABSOLUTE_POS < input.len() {
+        output[ABSOLUTE_POS] = i32::cast_from(i8::cast_saturating_from(input[ABSOLUTE_POS]));
+    }
+}
+
+#[cube(launch)]
+pub fn kernel_cast_wrapping(input: &Array<f32>, output: &mut Array<i32>) {
+    if ABSOLUTE_POS < input.len() {
+        output[ABSOLUTE_POS] = i32::cast_from(i8::cast_wrapping_from(input[ABSOLUTE_POS]));
+    }
+}
+
+const INPUT: [f32; 6] = [300.0, -200.0, 127.9, -128.5, 1.5, 1000.0];
+
+fn supports_i8<R: Runtime>(client: &ComputeClient<R::Server, R::Channel>) -> bool {
+    let supported = client
+        .properties()
+        .feature_enabled(Feature::Type(Elem::Int(IntKind::I8)));
+
+    if !supported {
+        println!("i8 not supported - skipped");
+    }
+
+    supported
+}
+
+pub fn test_cast_saturating<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    if !supports_i8::<R>(&client) {
+        return;
+    }
+
+    let mut input = INPUT.to_vec();
+    input.push(f32::NAN);
+    let input_handle = client.create(f32::as_bytes(&input));
+    let output = client.empty(input.len() * core::mem::size_of::<i32>());
+
+    kernel_cast_saturating::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(input.len() as u32, 1, 1),
+        unsafe { ArrayArg::from_raw_parts::<f32>(&input_handle, input.len(), 1) },
+        unsafe { ArrayArg::from_raw_parts::<i32>(&output, input.len(), 1) },
+    );
+
+    let actual = client.read_one(output.binding());
+    let actual = i32::from_bytes(&actual);
+
+    assert_eq!(actual, [127, -128, 127, -128, 1, 127, 0]);
+}
+
+pub fn test_cast_wrapping<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    if !supports_i8::<R>(&client) {
+        return;
+    }
+
+    let input_handle = client.create(f32::as_bytes(&INPUT));
+    let output = client.empty(INPUT.len() * core::mem::size_of::<i32>());
+
+    kernel_cast_wrapping::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(INPUT.len() as u32, 1, 1),
+        unsafe { ArrayArg::from_raw_parts::<f32>(&input_handle, INPUT.len(), 1) },
+        unsafe { ArrayArg::from_raw_parts::<i32>(&output, INPUT.len(), 1) },
+    );
+
+    let actual = client.read_one(output.binding());
+    let actual = i32::from_bytes(&actual);
+
+    // 300 = 256 + 44, -200 = -256 + 56 and 1000 = 4 * 256 - 24.
+    assert_eq!(actual, [44, 56, 127, -128, 1, -24]);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_cast {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_cast_saturating() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::cast::test_cast_saturating::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_cast_wrapping() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::cast::test_cast_wrapping::<TestRuntime>(client);
+        }
+    };
+}
@@ -2,7 +2,7 @@ use crate as cubecl;
 
 use crate::Feature;
 use cubecl::{
-    ir::{Elem, FloatKind},
+    ir::{Elem, FloatKind, IntKind, UIntKind},
     prelude::*,
 };
 use half::{bf16, f16};
@@ -47,6 +47,46 @@ pub fn kernel_simple_1(lhs: &Array<f16>, rhs: &Array<f16>, out: &mut Array<f32>)
     );
 }
 
+#[cube(launch)]
+/// Executes Out = Lhs @ Rhs.T
+pub fn kernel_simple_u8(lhs: &Array<u8>, rhs: &Array<u8>, out: &mut Array<i32>) {
+    let a = cmma::Matrix::<u8>::from_slice(
+        cmma::MatrixIdent::A,
+        16,
+        16,
+        16,
+        cmma::MatrixLayout::RowMajor,
+        &lhs.to_slice(),
+        16,
+    );
+    let b = cmma::Matrix::<u8>::from_slice(
+        cmma::MatrixIdent::B,
+        16,
+        16,
+        16,
+        cmma::MatrixLayout::ColMajor,
+        &rhs.to_slice(),
+        16,
+    );
+    let c = cmma::Matrix::<i32>::from_value(
+        cmma::MatrixIdent::Accumulator,
+        16,
+        16,
+        16,
+        cmma::MatrixLayout::Undefined,
+        0,
+    );
+
+    cmma::execute::<u8, u8, i32, i32>(&a, &b, &c, &c);
+
+    cmma::store(
+        &mut out.to_slice_mut(),
+        &c,
+        16,
+        cmma::MatrixLayout::RowMajor,
+    );
+}
+
 #[cube(launch)]
 /// Executes Out = Lhs @ Rhs.T
 pub fn kernel_simple_tf32(lhs: &Array<tf32>, rhs: &Array<tf32>, out: &mut Array<f32>) {
@@ -332,6 +372,49 @@ pub fn test_simple_tf32<R: Runtime>(
     assert_eq!(expected, actual);
 }
 
+pub fn test_simple_u8<R: Runtime>(
+    client: ComputeClient<R::Server, R::Channel>,
+    cube_dimensions: CubeDim,
+) {
+    if !client.properties().feature_enabled(Feature::Cmma {
+        a: Elem::UInt(UIntKind::U8),
+        b: Elem::UInt(UIntKind::U8),
+        c: Elem::Int(IntKind::I32),
+        m: 16,
+        k: 16,
+        n: 16,
+    }) {
+        // We can't execute the test, skip.
+        return;
+    }
+
+    // Row `m` of lhs is filled with `m`, so every output row is `m * sum(rhs row)`.
+    let lhs: Vec<u8> = (0..256).map(|i| (i / 16) as u8).collect();
+    let rhs: Vec<u8> = (0..256).map(|i| (i % 8) as u8).collect();
+
+    let lhs = client.create(u8::as_bytes(&lhs));
+    let rhs = client.create(u8::as_bytes(&rhs));
+    let out = client.empty(core::mem::size_of::<i32>() * 256);
+
+    unsafe {
+        kernel_simple_u8::launch::<R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            cube_dimensions,
+            ArrayArg::from_raw_parts::<u8>(&lhs, 256, 1),
+            ArrayArg::from_raw_parts::<u8>(&rhs, 256, 1),
+            ArrayArg::from_raw_parts::<i32>(&out, 256, 1),
+        )
+    };
+
+    let actual = client.read_one(out.binding());
+    let actual = i32::from_bytes(&actual);
+
+    let expected: Vec<i32> = (0..256).map(|i| (i / 16) * 56).collect();
+
+    assert_eq!(expected, actual);
+}
+
 #[allow(missing_docs)]
 #[macro_export]
 macro_rules! testgen_cmma {
@@ -364,6 +447,20 @@ macro_rules! testgen_cmma {
             );
         }
 
+        #[test]
+        fn test_cmma_simple_u8() {
+            let client = TestRuntime::client(&Default::default());
+            // In HIP the thread block size must be 32
+            #[cfg(feature = "is_hip")]
+            let cube_dimensions = CubeDim::new(32, 1, 1);
+            #[cfg(not(feature = "is_hip"))]
+            let cube_dimensions = CubeDim::new(16, 16, 1);
+            cubecl_core::runtime_tests::cmma::test_simple_u8::<TestRuntime>(
+                client,
+                cube_dimensions,
+            );
+        }
+
         #[test]
         fn test_cmma_cast_f16() {
             let client = TestRuntime::client(&Default::default());
@@ -1,5 +1,7 @@
 use crate as cubecl;
+use crate::ir::{ConstantScalarValue, Elem, FloatKind, IntKind, Item, Scope, UIntKind, Variable};
 use crate::prelude::*;
+use crate::Feature;
 
 #[cube(launch)]
 fn constant_array_kernel<F: Float>(out: &mut Array<F>, #[comptime] data: Vec<u32>) {
@@ -29,6 +31,135 @@ pub fn test_constant_array<R: Runtime>(client: ComputeClient<R::Server, R::Chann
     assert_eq!(actual[0], 5.0);
 }
 
+pub fn test_try_as_comptime() {
+    let mut scope = Scope::root(false);
+
+    let two: ExpandElementTyped<f32> = 2.0f32.into();
+    let folded = f32::__expand_fma(&mut scope, two.clone(), 3.0f32.into(), 1.0f32.into());
+    assert_eq!(folded.try_as_comptime(), Some(7.0));
+
+    let index: ExpandElementTyped<u32> = 5u32.into();
+    assert_eq!(index.try_as_comptime(), Some(5));
+    let flag: ExpandElementTyped<bool> = true.into();
+    assert_eq!(flag.try_as_comptime(), Some(true));
+
+    let runtime: ExpandElementTyped<f32> =
+        scope.create_local(Item::new(f32::as_elem(&scope))).into();
+    assert_eq!(runtime.try_as_comptime(), None);
+    let product = f32::__expand_fma(&mut scope, two, runtime, 1.0f32.into());
+    assert_eq!(product.try_as_comptime(), None);
+}
+
+#[cube(launch)]
+fn max_reduce_kernel<N: Numeric>(input: &Array<N>, output: &mut Array<N>) {
+    let mut max = N::min_value();
+
+    for i in 0..input.len() {
+        max = N::max(max, input[i]);
+    }
+
+    output[0] = max;
+}
+
+fn test_max_reduce<R: Runtime, N: Numeric + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    values: &[N],
+    expected: N,
+) {
+    let input = client.create(N::as_bytes(values));
+    let output = client.empty(core::mem::size_of::<N>());
+
+    max_reduce_kernel::launch::<N, R>(
+        client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(1, 1, 1),
+        unsafe { ArrayArg::from_raw_parts::<N>(&input, values.len(), 1) },
+        unsafe { ArrayArg::from_raw_parts::<N>(&output, 1, 1) },
+    );
+
+    let actual = client.read_one(output.binding());
+    let actual = N::from_bytes(&actual);
+
+    assert_eq!(actual[0], expected);
+}
+
+pub fn test_max_reduce_from_min<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    // All the values are below zero, so starting from zero would give the wrong maximum.
+    test_max_reduce::<R, f32>(&client, &[-7.5, -3.25, -12.0, -1e30], -3.25);
+    test_max_reduce::<R, i32>(&client, &[-7, -3, -12, i32::MIN], -3);
+    test_max_reduce::<R, u32>(&client, &[7, 3, 12, 0], 12);
+
+    // The extremes of these types aren't the ones of `f32` and `i64` used during expansion.
+    if client
+        .properties()
+        .feature_enabled(Feature::Type(Elem::Float(FloatKind::F16)))
+    {
+        let values = [half::f16::MIN, half::f16::from_f32(-3.25)];
+        test_max_reduce::<R, half::f16>(&client, &values, half::f16::from_f32(-3.25));
+    }
+    if client
+        .properties()
+        .feature_enabled(Feature::Type(Elem::Int(IntKind::I64)))
+    {
+        test_max_reduce::<R, i64>(&client, &[i64::MIN, -7, i64::MIN + 1], -7);
+    }
+}
+
+pub fn test_numeric_constants_typed() {
+    let mut scope = Scope::root(false);
+    scope.register_elem::<NumericExpand<0>>(Elem::Int(IntKind::I32));
+    scope.register_elem::<NumericExpand<1>>(Elem::Float(FloatKind::F16));
+    scope.register_elem::<NumericExpand<2>>(Elem::Float(FloatKind::F64));
+    scope.register_elem::<IntExpand<0>>(Elem::UInt(UIntKind::U32));
+
+    fn typed<T: Numeric + Into<Variable>>(scope: &Scope, value: T) -> Option<ConstantScalarValue> {
+        ExpandElementTyped::<T>::from_lit(scope, value).constant()
+    }
+
+    // Literals are kept as written, even when they are the extremes of the placeholder types.
+    assert_eq!(
+        typed(&scope, NumericExpand::<2>::MAX),
+        Some(ConstantScalarValue::Float(f32::MAX as f64, FloatKind::F64))
+    );
+    assert_eq!(
+        typed(&scope, NumericExpand::<2>::ONE),
+        Some(ConstantScalarValue::Float(1.0, FloatKind::F64))
+    );
+    assert_eq!(
+        typed(&scope, IntExpand::<0>::ZERO),
+        Some(ConstantScalarValue::UInt(0, UIntKind::U32))
+    );
+
+    // The extremes of the element types are expanded explicitly.
+    assert_eq!(
+        NumericExpand::<0>::__expand_min_value(&mut scope).constant(),
+        Some(ConstantScalarValue::Int(i32::MIN as i64, IntKind::I32))
+    );
+    assert_eq!(
+        NumericExpand::<0>::__expand_max_value(&mut scope).constant(),
+        Some(ConstantScalarValue::Int(i32::MAX as i64, IntKind::I32))
+    );
+    assert_eq!(
+        NumericExpand::<1>::__expand_min_value(&mut scope).constant(),
+        Some(ConstantScalarValue::Float(
+            half::f16::MIN.to_f64(),
+            FloatKind::F16
+        ))
+    );
+    assert_eq!(
+        NumericExpand::<2>::__expand_max_value(&mut scope).constant(),
+        Some(ConstantScalarValue::Float(f64::MAX, FloatKind::F64))
+    );
+    assert_eq!(
+        IntExpand::<0>::__expand_min_value(&mut scope).constant(),
+        Some(ConstantScalarValue::UInt(0, UIntKind::U32))
+    );
+    assert_eq!(
+        IntExpand::<0>::__expand_max_value(&mut scope).constant(),
+        Some(ConstantScalarValue::UInt(u32::MAX as u64, UIntKind::U32))
+    );
+}
+
 #[allow(missing_docs)]
 #[macro_export]
 macro_rules! testgen_constants {
@@ -40,5 +171,21 @@ macro_rules! testgen_constants {
             let client = TestRuntime::client(&Default::default());
             cubecl_core::runtime_tests::constants::test_constant_array::<TestRuntime>(client);
         }
+
+        #[test]
+        fn test_try_as_comptime() {
+            cubecl_core::runtime_tests::constants::test_try_as_comptime();
+        }
+
+        #[test]
+        fn test_max_reduce_from_min() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::constants::test_max_reduce_from_min::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_numeric_constants_typed() {
+            cubecl_core::runtime_tests::constants::test_numeric_constants_typed();
+        }
     };
 }
@@ -1,4 +1,8 @@
-use crate::{self as cubecl, as_type};
+use crate::{
+    self as cubecl, as_type,
+    ir::{Elem, UIntKind},
+    Feature,
+};
 
 use cubecl::prelude::*;
 
@@ -41,6 +45,51 @@ pub fn test_kernel_index_scalar<R: Runtime, F: Float + CubeElement>(
     assert_eq!(actual[2], F::new(123.0));
 }
 
+#[cube(launch)]
+pub fn kernel_index_wide(buffer: &mut Array<u8>, output: &mut Array<u8>, wide: u64, narrow: u64) {
+    if UNIT_POS == 0 {
+        unsafe {
+            buffer.index_assign_unchecked_wide(wide, output[0]);
+            buffer.index_assign_unchecked_wide(narrow, output[1]);
+            output[2] = *buffer.index_unchecked_wide(wide);
+            output[3] = *buffer.index_unchecked_wide(narrow);
+        }
+    }
+}
+
+/// Writes and reads back an element past the first `u32::MAX` elements of a buffer, and the
+/// element that a 32-bit index would wrap it to, which must stay distinct.
+///
+/// This allocates a buffer of more than 4 GiB, so it only runs when the ignored tests are, and is
+/// skipped on runtimes without `u64`.
+pub fn test_kernel_index_wide<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    if !client
+        .properties()
+        .feature_enabled(Feature::Type(Elem::UInt(UIntKind::U64)))
+    {
+        return;
+    }
+
+    let narrow = 5u64;
+    let wide = narrow + (1u64 << 32);
+    let len = wide as usize + 1;
+    let buffer = client.empty(len);
+    let output = client.create(u8::as_bytes(&[7, 9, 0, 0]));
+
+    kernel_index_wide::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::default(),
+        unsafe { ArrayArg::from_raw_parts::<u8>(&buffer, len, 1) },
+        unsafe { ArrayArg::from_raw_parts::<u8>(&output, 4, 1) },
+        ScalarArg::new(wide),
+        ScalarArg::new(narrow),
+    );
+
+    let actual = client.read_one(output.binding());
+    assert_eq!(u8::from_bytes(&actual), &[7, 9, 7, 9]);
+}
+
 #[allow(missing_docs)]
 #[macro_export]
 macro_rules! testgen_index {
@@ -54,5 +103,12 @@ macro_rules! testgen_index {
                 client,
             );
         }
+
+        #[test]
+        #[ignore = "Allocates a buffer of more than 4 GiB"]
+        fn test_index_wide() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::index::test_kernel_index_wide::<TestRuntime>(client);
+        }
     };
 }
@@ -33,6 +33,23 @@ pub fn kernel_without_generics(output: &mut Array<f32>) {
     }
 }
 
+#[cube(launch)]
+pub fn kernel_with_line_scalar<F: Float>(output: &mut Array<Line<F>>, weights: Line<F>) {
+    if UNIT_POS == 0 {
+        output[0] = output[0] * weights;
+    }
+}
+
+#[cube(launch)]
+pub fn kernel_with_scalar_array(output: &mut Array<f32>, values: ScalarArray<f32>, offset: f32) {
+    if UNIT_POS == 0 {
+        #[unroll]
+        for i in 0..16 {
+            output[i] = *values.index(i) + offset;
+        }
+    }
+}
+
 pub fn test_kernel_with_comptime_tag<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
     let handle = client.create(f32::as_bytes(&[5.0]));
     let array_arg = unsafe { ArrayArg::from_raw_parts::<f32>(&handle, 1, 1) };
@@ -99,6 +116,60 @@ pub fn test_kernel_without_generics<R: Runtime>(client: ComputeClient<R::Server,
     assert_eq!(actual[0], 5.0);
 }
 
+pub fn test_kernel_with_line_scalar<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let handle = client.create(as_bytes![F: 1.0, 2.0, 3.0, 4.0]);
+
+    kernel_with_line_scalar::launch::<F, R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::default(),
+        unsafe { ArrayArg::from_raw_parts::<F>(&handle, 4, 4) },
+        LineArg::new(vec![F::new(0.5), F::new(1.0), F::new(2.0), F::new(0.0)]),
+    );
+
+    let actual = client.read_one(handle.binding());
+    let actual = F::from_bytes(&actual);
+
+    assert_eq!(actual, [F::new(0.5), F::new(2.0), F::new(6.0), F::new(0.0)]);
+}
+
+pub fn test_kernel_with_unsupported_line_scalar<R: Runtime>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let handle = client.create(f32::as_bytes(&[0.0; 4]));
+
+    // 256 values would wrap to an empty line if the length was narrowed to a `u8`.
+    kernel_with_line_scalar::launch::<f32, R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::default(),
+        unsafe { ArrayArg::from_raw_parts::<f32>(&handle, 4, 4) },
+        LineArg::new(vec![1.0; 256]),
+    );
+}
+
+pub fn test_kernel_with_scalar_array<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let values: Vec<f32> = (0..16).map(|i| i as f32).collect();
+    let handle = client.empty(values.len() * core::mem::size_of::<f32>());
+
+    kernel_with_scalar_array::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::default(),
+        unsafe { ArrayArg::from_raw_parts::<f32>(&handle, values.len(), 1) },
+        ScalarArrayArg::new(&values),
+        ScalarArg::new(0.5),
+    );
+
+    let actual = client.read_one(handle.binding());
+    let actual = f32::from_bytes(&actual);
+    let expected: Vec<f32> = values.iter().map(|value| value + 0.5).collect();
+
+    assert_eq!(actual, expected);
+}
+
 #[allow(missing_docs)]
 #[macro_export]
 macro_rules! testgen_launch {
@@ -119,6 +190,24 @@ macro_rules! testgen_launch {
             cubecl_core::runtime_tests::launch::test_kernel_without_generics::<TestRuntime>(client);
         }
 
+        #[test]
+        fn test_launch_with_line_scalar() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::launch::test_kernel_with_line_scalar::<
+                TestRuntime,
+                FloatType,
+            >(client);
+        }
+
+        #[test]
+        #[should_panic(expected = "A line of 256 values isn't supported")]
+        fn test_launch_with_unsupported_line_scalar() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::launch::test_kernel_with_unsupported_line_scalar::<
+                TestRuntime,
+            >(client);
+        }
+
         #[test]
         fn test_launch_with_comptime_tag() {
             let client = TestRuntime::client(&Default::default());
@@ -126,5 +215,13 @@ macro_rules! testgen_launch {
                 client,
             );
         }
+
+        #[test]
+        fn test_launch_with_scalar_array() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::launch::test_kernel_with_scalar_array::<TestRuntime>(
+                client,
+            );
+        }
     };
 }
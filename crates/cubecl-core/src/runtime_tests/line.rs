@@ -104,6 +104,93 @@ pub fn test_line_loop_unroll<R: Runtime, F: Float + CubeElement>(
     }
 }
 
+#[cube(launch_unchecked)]
+pub fn kernel_line_double<F: Float>(input: &Array<Line<F>>, output: &mut Array<Line<F>>) {
+    if ABSOLUTE_POS < input.len() {
+        let line = input[ABSOLUTE_POS];
+        output[ABSOLUTE_POS] = line + line;
+    }
+}
+
+/// Even lines of half precision floats are packed in pairs on some backends, which must not
+/// change what is read or written compared to loading the elements one by one.
+pub fn test_line_packed_load<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let num_elems = 64;
+    // Small integers, so doubling them is exact in any precision.
+    let values = (0..num_elems as i64)
+        .map(|i| F::from_int(i % 17 - 8))
+        .collect::<Vec<_>>();
+    let input = client.create(F::as_bytes(&values));
+
+    let run = |line_size: u8| {
+        let output = client.empty(num_elems * core::mem::size_of::<F>());
+        let num_lines = num_elems / line_size as usize;
+        unsafe {
+            kernel_line_double::launch_unchecked::<F, R>(
+                &client,
+                CubeCount::new_single(),
+                CubeDim::new(num_lines as u32, 1, 1),
+                ArrayArg::from_raw_parts::<F>(&input, num_lines, line_size),
+                ArrayArg::from_raw_parts::<F>(&output, num_lines, line_size),
+            );
+        }
+        F::from_bytes(&client.read_one(output.binding())).to_vec()
+    };
+
+    let unpacked = run(1);
+    let expected = values.iter().map(|v| *v + *v).collect::<Vec<_>>();
+    assert_eq!(unpacked, expected);
+
+    for line_size in R::line_size_elem(&F::as_elem_native_unchecked()) {
+        if line_size % 2 == 0 {
+            assert_eq!(run(line_size), unpacked, "Line size {line_size}");
+        }
+    }
+}
+
+#[cube(launch_unchecked)]
+pub fn kernel_line_reduce<F: Float>(input: &Array<Line<F>>, output: &mut Array<F>) {
+    if UNIT_POS == 0 {
+        output[0] = input[0].reduce_sum();
+        output[1] = input[0].reduce_max();
+    }
+}
+
+pub fn test_line_reduce<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    for line_size in R::line_size_elem(&F::as_elem_native_unchecked()) {
+        // Small integers, so the sum is exact in any precision.
+        let values: Vec<f32> = (0..line_size as i64)
+            .map(|i| (i * 7 % 5 - 2) as f32)
+            .collect();
+        let input = client.create(F::as_bytes(
+            &values.iter().map(|v| F::new(*v)).collect::<Vec<_>>(),
+        ));
+        let output = client.empty(2 * core::mem::size_of::<F>());
+
+        unsafe {
+            kernel_line_reduce::launch_unchecked::<F, R>(
+                &client,
+                CubeCount::new_single(),
+                CubeDim::new_single(),
+                ArrayArg::from_raw_parts::<F>(&input, 1, line_size),
+                ArrayArg::from_raw_parts::<F>(&output, 2, 1),
+            );
+        }
+
+        let actual = client.read_one(output.binding());
+        let actual = F::from_bytes(&actual);
+
+        let sum = values.iter().sum::<f32>();
+        let max = values.iter().copied().fold(f32::MIN, f32::max);
+
+        assert_eq!(actual, [F::new(sum), F::new(max)], "line size {line_size}");
+    }
+}
+
 macro_rules! impl_line_comparison {
     ($cmp:ident, $expected:expr) => {
         ::paste::paste! {
@@ -180,6 +267,20 @@ macro_rules! testgen_line {
             );
         }
 
+        #[test]
+        fn test_line_reduce() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::line::test_line_reduce::<TestRuntime, FloatType>(client);
+        }
+
+        #[test]
+        fn test_line_packed_load() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::line::test_line_packed_load::<TestRuntime, FloatType>(
+                client,
+            );
+        }
+
         #[test]
         fn test_line_equal() {
             let client = TestRuntime::client(&Default::default());
@@ -2,6 +2,7 @@ pub mod assign;
 pub mod atomic;
 pub mod binary;
 pub mod branch;
+pub mod cast;
 pub mod cmma;
 pub mod const_match;
 pub mod constants;
@@ -12,9 +13,11 @@ pub mod launch;
 pub mod line;
 pub mod memcpy_async;
 pub mod metadata;
+pub mod overflow;
 pub mod pipeline;
 pub mod plane;
 pub mod sequence;
+pub mod shared_memory;
 pub mod slice;
 pub mod tensor;
 pub mod topology;
@@ -97,6 +100,7 @@ macro_rules! testgen_float {
 #[macro_export]
 macro_rules! testgen_int {
     () => {
+        cubecl_core::testgen_branch_int!();
         cubecl_core::testgen_unary_int!();
         cubecl_core::testgen_atomic_int!();
     };
@@ -121,6 +125,9 @@ macro_rules! testgen_untyped {
         cubecl_core::testgen_constants!();
         cubecl_core::testgen_tensor_indexing!();
         cubecl_core::testgen_debug!();
+        cubecl_core::testgen_overflow!();
+        cubecl_core::testgen_cast!();
+        cubecl_core::testgen_shared_memory!();
     };
 }
 
@@ -0,0 +1,224 @@
+use crate::{
+    self as cubecl,
+    ir::{Elem, UIntKind},
+    Feature,
+};
+
+use cubecl::prelude::*;
+
+/// Signed integer operation whose overflow is checked.
+#[derive(CubeType, Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum CheckedOp {
+    Add,
+    Sub,
+    Mul,
+}
+
+#[cube(launch)]
+pub fn kernel_checked(
+    lhs: i32,
+    rhs: i32,
+    output: &mut Array<i32>,
+    flag: &mut Array<Atomic<u32>>,
+    #[comptime] op: CheckedOp,
+) {
+    enable_overflow_checks(flag);
+
+    if UNIT_POS == 0 {
+        output[0] = match op {
+            CheckedOp::Add => lhs + rhs,
+            CheckedOp::Sub => lhs - rhs,
+            CheckedOp::Mul => lhs * rhs,
+        };
+    }
+}
+
+fn launch_checked<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    op: CheckedOp,
+    lhs: i32,
+    rhs: i32,
+) -> (i32, u32) {
+    let output = client.empty(core::mem::size_of::<i32>());
+    let flag = client.create(u32::as_bytes(&[0]));
+
+    kernel_checked::launch::<R>(
+        client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::default(),
+        ScalarArg::new(lhs),
+        ScalarArg::new(rhs),
+        unsafe { ArrayArg::from_raw_parts::<i32>(&output, 1, 1) },
+        unsafe { ArrayArg::from_raw_parts::<u32>(&flag, 1, 1) },
+        op,
+    );
+
+    let output = i32::from_bytes(&client.read_one(output.binding()))[0];
+    let flag = u32::from_bytes(&client.read_one(flag.binding()))[0];
+
+    (output, flag)
+}
+
+/// Check that every operation wraps like [i32::wrapping_add] and its siblings, and that the flag
+/// is set exactly for the ones that overflow, when the checks are enabled.
+fn check_operations<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    op: CheckedOp,
+    operands: &[(i32, i32)],
+) {
+    for &(lhs, rhs) in operands {
+        let (expected, overflows) = match op {
+            CheckedOp::Add => lhs.overflowing_add(rhs),
+            CheckedOp::Sub => lhs.overflowing_sub(rhs),
+            CheckedOp::Mul => lhs.overflowing_mul(rhs),
+        };
+        let (output, flag) = launch_checked::<R>(client, op, lhs, rhs);
+
+        assert_eq!(output, expected, "{lhs} {op:?} {rhs} should wrap");
+        assert_eq!(
+            flag,
+            (overflows && cfg!(feature = "overflow_checks")) as u32,
+            "Wrong overflow flag for {lhs} {op:?} {rhs}"
+        );
+    }
+}
+
+fn supports_atomic_flag<R: Runtime>(client: &ComputeClient<R::Server, R::Channel>) -> bool {
+    let supported = client
+        .properties()
+        .feature_enabled(Feature::Type(Elem::AtomicUInt(UIntKind::U32)));
+
+    if !supported {
+        println!("Atomic u32 not supported - skipped");
+    }
+
+    supported
+}
+
+pub fn test_overflowing_add<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    if !supports_atomic_flag::<R>(&client) {
+        return;
+    }
+
+    check_operations::<R>(
+        &client,
+        CheckedOp::Add,
+        &[(i32::MAX, 1), (i32::MIN, -1), (i32::MIN, i32::MIN)],
+    );
+}
+
+pub fn test_non_overflowing_add<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    if !supports_atomic_flag::<R>(&client) {
+        return;
+    }
+
+    check_operations::<R>(
+        &client,
+        CheckedOp::Add,
+        &[(i32::MAX, -1), (i32::MIN, i32::MAX), (-5, 3)],
+    );
+}
+
+pub fn test_overflowing_sub<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    if !supports_atomic_flag::<R>(&client) {
+        return;
+    }
+
+    check_operations::<R>(
+        &client,
+        CheckedOp::Sub,
+        &[(i32::MIN, 1), (i32::MAX, -1), (0, i32::MIN)],
+    );
+}
+
+pub fn test_non_overflowing_sub<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    if !supports_atomic_flag::<R>(&client) {
+        return;
+    }
+
+    check_operations::<R>(
+        &client,
+        CheckedOp::Sub,
+        &[(i32::MIN, -1), (-1, i32::MIN), (i32::MAX, i32::MAX)],
+    );
+}
+
+pub fn test_overflowing_mul<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    if !supports_atomic_flag::<R>(&client) {
+        return;
+    }
+
+    check_operations::<R>(
+        &client,
+        CheckedOp::Mul,
+        &[
+            (i32::MAX, 2),
+            (46341, 46341),
+            (-1, i32::MIN),
+            (i32::MIN, -1),
+            (65536, 65536),
+        ],
+    );
+}
+
+pub fn test_non_overflowing_mul<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    if !supports_atomic_flag::<R>(&client) {
+        return;
+    }
+
+    check_operations::<R>(
+        &client,
+        CheckedOp::Mul,
+        &[
+            (46340, 46340),
+            (0, i32::MIN),
+            (-1, i32::MAX),
+            (i32::MIN, 1),
+            (-46340, 46341),
+        ],
+    );
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_overflow {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_overflowing_add() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::overflow::test_overflowing_add::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_non_overflowing_add() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::overflow::test_non_overflowing_add::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_overflowing_sub() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::overflow::test_overflowing_sub::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_non_overflowing_sub() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::overflow::test_non_overflowing_sub::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_overflowing_mul() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::overflow::test_overflowing_mul::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_non_overflowing_mul() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::overflow::test_non_overflowing_mul::<TestRuntime>(client);
+        }
+    };
+}
@@ -108,6 +108,16 @@ pub fn kernel_broadcast<F: Float>(output: &mut Tensor<F>) {
     }
 }
 
+#[cube(launch)]
+pub fn kernel_reduce_all_units<F: Float>(input: &Tensor<F>, output: &mut Tensor<F>) {
+    let val = input[UNIT_POS];
+
+    output[UNIT_POS] = plane_sum(val);
+    output[UNIT_POS + CUBE_DIM] = plane_max(val);
+    output[UNIT_POS + 2 * CUBE_DIM] = plane_prod(val);
+    output[UNIT_POS + 3 * CUBE_DIM] = plane_broadcast(val, 11);
+}
+
 #[cube(launch)]
 pub fn kernel_ballot(output: &mut Tensor<Line<u32>>) {
     let val2 = plane_ballot(UNIT_POS < 8);
@@ -625,6 +635,49 @@ pub fn test_plane_broadcast<
     );
 }
 
+/// Reduces a known pattern over a full plane, checking that every unit gets the result and not
+/// only the first one.
+pub fn test_plane_reduce_all_units<
+    TestRuntime: Runtime,
+    F: Float + num_traits::Float + CubeElement + Display,
+>(
+    client: ComputeClient<TestRuntime::Server, TestRuntime::Channel>,
+) {
+    if !client.properties().feature_enabled(Feature::Plane) {
+        // Can't execute the test.
+        return;
+    }
+
+    let plane_size = 32;
+    // Powers of two, so the products are exact in any precision.
+    let input: Vec<F> = (0..plane_size)
+        .map(|unit| match unit {
+            3 => F::new(2.0),
+            11 => F::new(4.0),
+            _ => F::new(1.0),
+        })
+        .collect();
+    let expected: Vec<F> = [36.0, 4.0, 8.0, 4.0]
+        .into_iter()
+        .flat_map(|value| vec![F::new(value); plane_size as usize])
+        .collect();
+
+    let input_handle = client.create(F::as_bytes(&input));
+    let output_handle = client.empty(expected.len() * core::mem::size_of::<F>());
+
+    unsafe {
+        kernel_reduce_all_units::launch::<F, TestRuntime>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(plane_size, 1, 1),
+            TensorArg::from_raw_parts::<F>(&input_handle, &[1], &[input.len()], 1),
+            TensorArg::from_raw_parts::<F>(&output_handle, &[1], &[expected.len()], 1),
+        );
+    }
+
+    assert_equals_approx::<TestRuntime, F>(&client, output_handle, &expected, 1e-5);
+}
+
 fn test_plane_operation<
     TestRuntime: Runtime,
     F: Float + num_traits::Float + CubeElement + Display,
@@ -905,6 +958,14 @@ macro_rules! testgen_plane {
             impl_test_plane_broadcast(4);
         }
 
+        #[test]
+        fn test_plane_reduce_all_units() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::plane::test_plane_reduce_all_units::<TestRuntime, FloatType>(
+                client,
+            );
+        }
+
         #[test]
         fn test_plane_ballot() {
             let client = TestRuntime::client(&Default::default());
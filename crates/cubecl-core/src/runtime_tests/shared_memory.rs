@@ -0,0 +1,113 @@
+use crate::codegen::CompilationError;
+use crate::ir::{Elem, FloatKind, Item, Scope};
+use crate::prelude::*;
+
+fn max_shared_memory_size<R: Runtime>(client: &ComputeClient<R::Server, R::Channel>) -> usize {
+    client
+        .properties()
+        .hardware_properties()
+        .max_shared_memory_size
+}
+
+pub fn test_shared_memory_within_limit<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let max_bytes = max_shared_memory_size::<R>(&client);
+    let scope = Scope::root(false);
+    let item = Item::vectorized(Elem::Float(FloatKind::F32), core::num::NonZero::new(4));
+
+    let lhs = scope.allocator.create_shared(item, 64);
+    let rhs = scope.allocator.create_shared(item, 64);
+
+    assert_ne!(lhs.index(), rhs.index());
+    assert_eq!(scope.allocator.shared_bytes(), 2 * 64 * 4 * 4);
+    assert!(scope.allocator.shared_bytes() <= max_bytes);
+}
+
+/// A kernel declaring a shared memory of `length` floats, for a device with
+/// `max_shared_memory_size` bytes of shared memory per cube.
+struct SharedMemoryKernel {
+    length: u32,
+    max_shared_memory_size: usize,
+}
+
+impl Kernel for SharedMemoryKernel {
+    fn define(&self) -> KernelDefinition {
+        let mut builder = KernelBuilder::default();
+        builder
+            .context
+            .create_shared(Elem::Float(FloatKind::F32), self.length);
+
+        let settings = KernelSettings::default()
+            .kernel_name("shared_memory")
+            .max_shared_memory_size(self.max_shared_memory_size);
+        builder.build(settings)
+    }
+}
+
+fn compile_shared_memory_kernel<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    length: u32,
+) -> Result<CompiledKernel<R::Compiler>, CompilationError> {
+    let kernel = SharedMemoryKernel {
+        length,
+        max_shared_memory_size: max_shared_memory_size::<R>(client),
+    };
+    KernelTask::<R::Compiler, _>::new(kernel)
+        .try_compile(&Default::default(), ExecutionMode::Checked)
+}
+
+pub fn test_kernel_within_shared_memory_limit<R: Runtime>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let length = (max_shared_memory_size::<R>(&client) / core::mem::size_of::<f32>()) as u32;
+
+    assert!(compile_shared_memory_kernel::<R>(&client, length).is_ok());
+}
+
+/// A kernel using more shared memory than the device has fails to compile, before it is
+/// launched.
+pub fn test_kernel_exceeding_shared_memory_limit<R: Runtime>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let max_bytes = max_shared_memory_size::<R>(&client);
+    let length = (max_bytes / core::mem::size_of::<f32>()) as u32 + 1;
+
+    assert_eq!(
+        compile_shared_memory_kernel::<R>(&client, length).err(),
+        Some(CompilationError::SharedMemoryLimitExceeded {
+            shared_memory_bytes: max_bytes + core::mem::size_of::<f32>(),
+            max_bytes,
+        })
+    );
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_shared_memory {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_shared_memory_within_limit() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::shared_memory::test_shared_memory_within_limit::<
+                TestRuntime,
+            >(client);
+        }
+
+        #[test]
+        fn test_kernel_within_shared_memory_limit() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::shared_memory::test_kernel_within_shared_memory_limit::<
+                TestRuntime,
+            >(client);
+        }
+
+        #[test]
+        fn test_kernel_exceeding_shared_memory_limit() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::shared_memory::test_kernel_exceeding_shared_memory_limit::<
+                TestRuntime,
+            >(client);
+        }
+    };
+}
@@ -238,6 +238,663 @@ test_unary_impl!(
     ]
 );
 
+test_unary_impl!(
+    test_tan,
+    F,
+    F::tan,
+    [
+        {
+            input_vectorization: 1,
+            out_vectorization: 1,
+            input: as_type![F: 0., 0.5, -1., 1.2],
+            expected: as_type![F: 0., 0.546, -1.557, 2.572]
+        },
+        {
+            input_vectorization: 2,
+            out_vectorization: 2,
+            input: as_type![F: 0., 0.5, -1., 1.2],
+            expected: as_type![F: 0., 0.546, -1.557, 2.572]
+        },
+        {
+            input_vectorization: 4,
+            out_vectorization: 4,
+            input: as_type![F: 0., 0.5, -1., 1.2],
+            expected: as_type![F: 0., 0.546, -1.557, 2.572]
+        }
+    ]
+);
+
+test_unary_impl!(
+    test_asin,
+    F,
+    F::asin,
+    [
+        {
+            input_vectorization: 1,
+            out_vectorization: 1,
+            input: as_type![F: 0., 0.5, -0.5, 1.],
+            expected: as_type![F: 0., 0.524, -0.524, 1.571]
+        },
+        {
+            input_vectorization: 2,
+            out_vectorization: 2,
+            input: as_type![F: 0., 0.5, -0.5, 1.],
+            expected: as_type![F: 0., 0.524, -0.524, 1.571]
+        },
+        {
+            input_vectorization: 4,
+            out_vectorization: 4,
+            input: as_type![F: 0., 0.5, -0.5, 1.],
+            expected: as_type![F: 0., 0.524, -0.524, 1.571]
+        }
+    ]
+);
+
+test_unary_impl!(
+    test_acos,
+    F,
+    F::acos,
+    [
+        {
+            input_vectorization: 1,
+            out_vectorization: 1,
+            input: as_type![F: 0., 0.5, -0.5, 1.],
+            expected: as_type![F: 1.571, 1.047, 2.094, 0.]
+        },
+        {
+            input_vectorization: 2,
+            out_vectorization: 2,
+            input: as_type![F: 0., 0.5, -0.5, 1.],
+            expected: as_type![F: 1.571, 1.047, 2.094, 0.]
+        },
+        {
+            input_vectorization: 4,
+            out_vectorization: 4,
+            input: as_type![F: 0., 0.5, -0.5, 1.],
+            expected: as_type![F: 1.571, 1.047, 2.094, 0.]
+        }
+    ]
+);
+
+test_unary_impl!(
+    test_atan,
+    F,
+    F::atan,
+    [
+        {
+            input_vectorization: 1,
+            out_vectorization: 1,
+            input: as_type![F: 0., 1., -1., 4.],
+            expected: as_type![F: 0., 0.785, -0.785, 1.326]
+        },
+        {
+            input_vectorization: 2,
+            out_vectorization: 2,
+            input: as_type![F: 0., 1., -1., 4.],
+            expected: as_type![F: 0., 0.785, -0.785, 1.326]
+        },
+        {
+            input_vectorization: 4,
+            out_vectorization: 4,
+            input: as_type![F: 0., 1., -1., 4.],
+            expected: as_type![F: 0., 0.785, -0.785, 1.326]
+        }
+    ]
+);
+
+test_unary_impl!(
+    test_sinh,
+    F,
+    F::sinh,
+    [
+        {
+            input_vectorization: 1,
+            out_vectorization: 1,
+            input: as_type![F: 0., 0.5, -1., 2.],
+            expected: as_type![F: 0., 0.521, -1.175, 3.627]
+        },
+        {
+            input_vectorization: 2,
+            out_vectorization: 2,
+            input: as_type![F: 0., 0.5, -1., 2.],
+            expected: as_type![F: 0., 0.521, -1.175, 3.627]
+        },
+        {
+            input_vectorization: 4,
+            out_vectorization: 4,
+            input: as_type![F: 0., 0.5, -1., 2.],
+            expected: as_type![F: 0., 0.521, -1.175, 3.627]
+        }
+    ]
+);
+
+test_unary_impl!(
+    test_cosh,
+    F,
+    F::cosh,
+    [
+        {
+            input_vectorization: 1,
+            out_vectorization: 1,
+            input: as_type![F: 0., 0.5, -1., 2.],
+            expected: as_type![F: 1., 1.128, 1.543, 3.762]
+        },
+        {
+            input_vectorization: 2,
+            out_vectorization: 2,
+            input: as_type![F: 0., 0.5, -1., 2.],
+            expected: as_type![F: 1., 1.128, 1.543, 3.762]
+        },
+        {
+            input_vectorization: 4,
+            out_vectorization: 4,
+            input: as_type![F: 0., 0.5, -1., 2.],
+            expected: as_type![F: 1., 1.128, 1.543, 3.762]
+        }
+    ]
+);
+
+test_unary_impl!(
+    test_asinh,
+    F,
+    F::asinh,
+    [
+        {
+            input_vectorization: 1,
+            out_vectorization: 1,
+            input: as_type![F: 0., 0.5, -1., 2.],
+            expected: as_type![F: 0., 0.481, -0.881, 1.444]
+        },
+        {
+            input_vectorization: 2,
+            out_vectorization: 2,
+            input: as_type![F: 0., 0.5, -1., 2.],
+            expected: as_type![F: 0., 0.481, -0.881, 1.444]
+        },
+        {
+            input_vectorization: 4,
+            out_vectorization: 4,
+            input: as_type![F: 0., 0.5, -1., 2.],
+            expected: as_type![F: 0., 0.481, -0.881, 1.444]
+        }
+    ]
+);
+
+test_unary_impl!(
+    test_acosh,
+    F,
+    F::acosh,
+    [
+        {
+            input_vectorization: 1,
+            out_vectorization: 1,
+            input: as_type![F: 1., 1.5, 2., 4.],
+            expected: as_type![F: 0., 0.962, 1.317, 2.063]
+        },
+        {
+            input_vectorization: 2,
+            out_vectorization: 2,
+            input: as_type![F: 1., 1.5, 2., 4.],
+            expected: as_type![F: 0., 0.962, 1.317, 2.063]
+        },
+        {
+            input_vectorization: 4,
+            out_vectorization: 4,
+            input: as_type![F: 1., 1.5, 2., 4.],
+            expected: as_type![F: 0., 0.962, 1.317, 2.063]
+        }
+    ]
+);
+
+test_unary_impl!(
+    test_atanh,
+    F,
+    F::atanh,
+    [
+        {
+            input_vectorization: 1,
+            out_vectorization: 1,
+            input: as_type![F: 0., 0.5, -0.5, 0.9],
+            expected: as_type![F: 0., 0.549, -0.549, 1.472]
+        },
+        {
+            input_vectorization: 2,
+            out_vectorization: 2,
+            input: as_type![F: 0., 0.5, -0.5, 0.9],
+            expected: as_type![F: 0., 0.549, -0.549, 1.472]
+        },
+        {
+            input_vectorization: 4,
+            out_vectorization: 4,
+            input: as_type![F: 0., 0.5, -0.5, 0.9],
+            expected: as_type![F: 0., 0.549, -0.549, 1.472]
+        }
+    ]
+);
+
+test_unary_impl!(
+    test_log2,
+    F,
+    F::log2,
+    [
+        {
+            input_vectorization: 1,
+            out_vectorization: 1,
+            input: as_type![F: 0.25, 1., 3., 10.],
+            expected: as_type![F: -2., 0., 1.585, 3.322]
+        },
+        {
+            input_vectorization: 2,
+            out_vectorization: 2,
+            input: as_type![F: 0.25, 1., 3., 10.],
+            expected: as_type![F: -2., 0., 1.585, 3.322]
+        },
+        {
+            input_vectorization: 4,
+            out_vectorization: 4,
+            input: as_type![F: 0.25, 1., 3., 10.],
+            expected: as_type![F: -2., 0., 1.585, 3.322]
+        }
+    ]
+);
+
+test_unary_impl!(
+    test_exp2,
+    F,
+    F::exp2,
+    [
+        {
+            input_vectorization: 1,
+            out_vectorization: 1,
+            input: as_type![F: -2., 0., 1.5, 3.],
+            expected: as_type![F: 0.25, 1., 2.828, 8.]
+        },
+        {
+            input_vectorization: 2,
+            out_vectorization: 2,
+            input: as_type![F: -2., 0., 1.5, 3.],
+            expected: as_type![F: 0.25, 1., 2.828, 8.]
+        },
+        {
+            input_vectorization: 4,
+            out_vectorization: 4,
+            input: as_type![F: -2., 0., 1.5, 3.],
+            expected: as_type![F: 0.25, 1., 2.828, 8.]
+        }
+    ]
+);
+
+#[cube]
+fn exp2_log2<F: Float>(x: F) -> F {
+    F::exp2(F::log2(x))
+}
+
+test_unary_impl!(
+    test_exp2_log2_round_trip,
+    F,
+    exp2_log2::<F>,
+    [
+        {
+            input_vectorization: 1,
+            out_vectorization: 1,
+            input: as_type![F: 0.25, 1., 3., 10.],
+            expected: as_type![F: 0.25, 1., 3., 10.]
+        },
+        {
+            input_vectorization: 2,
+            out_vectorization: 2,
+            input: as_type![F: 0.25, 1., 3., 10.],
+            expected: as_type![F: 0.25, 1., 3., 10.]
+        },
+        {
+            input_vectorization: 4,
+            out_vectorization: 4,
+            input: as_type![F: 0.25, 1., 3., 10.],
+            expected: as_type![F: 0.25, 1., 3., 10.]
+        }
+    ]
+);
+
+#[cube]
+fn const_folded<F: Float>(x: F) -> F {
+    // Both calls have constant operands, so they are folded while expanding.
+    x * F::sqrt(F::new(4.0)) + F::exp(F::new(0.0))
+}
+
+test_unary_impl!(
+    test_const_fold,
+    F,
+    const_folded::<F>,
+    [
+        {
+            input_vectorization: 1,
+            out_vectorization: 1,
+            input: as_type![F: 0., 0.5, -0.5, 2.],
+            expected: as_type![F: 1., 2., 0., 5.]
+        },
+        {
+            input_vectorization: 4,
+            out_vectorization: 4,
+            input: as_type![F: 0., 0.5, -0.5, 2.],
+            expected: as_type![F: 1., 2., 0., 5.]
+        }
+    ]
+);
+
+#[cube]
+fn const_folded_base_2<F: Float>(x: F) -> F {
+    x * F::exp2(F::new(3.0)) + F::log2(F::new(0.25))
+}
+
+test_unary_impl!(
+    test_const_fold_base_2,
+    F,
+    const_folded_base_2::<F>,
+    [
+        {
+            input_vectorization: 1,
+            out_vectorization: 1,
+            input: as_type![F: 0., 0.5, -0.5, 2.],
+            expected: as_type![F: -2., 2., -6., 14.]
+        },
+        {
+            input_vectorization: 4,
+            out_vectorization: 4,
+            input: as_type![F: 0., 0.5, -0.5, 2.],
+            expected: as_type![F: -2., 2., -6., 14.]
+        }
+    ]
+);
+
+#[cube(launch_unchecked, create_dummy_kernel)]
+fn const_fold_array<F: Float>(input: &Array<F>, output: &mut Array<F>) {
+    if ABSOLUTE_POS < input.len() {
+        output[ABSOLUTE_POS] = const_folded::<F>(input[ABSOLUTE_POS]);
+    }
+}
+
+#[cube(launch_unchecked, create_dummy_kernel)]
+fn runtime_sqrt_exp<F: Float>(input: &Array<F>, output: &mut Array<F>) {
+    if ABSOLUTE_POS < input.len() {
+        let x = input[ABSOLUTE_POS];
+        output[ABSOLUTE_POS] = F::sqrt(x) + F::exp(x);
+    }
+}
+
+pub fn test_const_fold_source<R: Runtime, F: Float + num_traits::Float + CubeElement + Display>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let input = as_type![F: 0., 0.5, -0.5, 2.];
+    let num_elems = input.len();
+    let input_handle = client.create(F::as_bytes(input));
+    let output_handle = client.empty(core::mem::size_of_val(input));
+    let cube_count = || CubeCount::Static(1, 1, 1);
+    let cube_dim = CubeDim::new(num_elems as u32, 1, 1);
+    let args = || unsafe {
+        (
+            ArrayArg::from_raw_parts::<F>(&input_handle, num_elems, 1),
+            ArrayArg::from_raw_parts::<F>(&output_handle, num_elems, 1),
+        )
+    };
+
+    let source = |kernel: Box<dyn CubeTask<R::Compiler>>| {
+        kernel
+            .compile(&Default::default(), ExecutionMode::Unchecked)
+            .source
+            .to_lowercase()
+    };
+    let (input_arg, output_arg) = args();
+    let folded_source = source(Box::new(KernelTask::<R::Compiler, _>::new(
+        const_fold_array::create_dummy_kernel::<F, R>(
+            cube_count(),
+            cube_dim,
+            input_arg,
+            output_arg,
+        ),
+    )));
+    let (input_arg, output_arg) = args();
+    let runtime_source = source(Box::new(KernelTask::<R::Compiler, _>::new(
+        runtime_sqrt_exp::create_dummy_kernel::<F, R>(
+            cube_count(),
+            cube_dim,
+            input_arg,
+            output_arg,
+        ),
+    )));
+
+    // The runtime kernel makes sure the names are the ones the backend uses.
+    for function in ["sqrt", "exp"] {
+        assert!(
+            runtime_source.contains(function),
+            "Expected {function} with a runtime operand:\n{runtime_source}"
+        );
+        assert!(
+            !folded_source.contains(function),
+            "Expected {function} to be folded:\n{folded_source}"
+        );
+    }
+}
+
+test_unary_impl_int!(test_abs_int, I, I::abs, [
+    {
+        input_vectorization: 1,
+        out_vectorization: 1,
+        input: as_type![I: -3, 0, 5, -1],
+        expected: as_type![I: 3, 0, 5, 1]
+    },
+    {
+        input_vectorization: 2,
+        out_vectorization: 2,
+        input: as_type![I: -3, 0, 5, -1],
+        expected: as_type![I: 3, 0, 5, 1]
+    },
+    {
+        input_vectorization: 4,
+        out_vectorization: 4,
+        input: as_type![I: -3, 0, 5, -1],
+        expected: as_type![I: 3, 0, 5, 1]
+    }
+]);
+
+test_unary_impl_int!(test_signum, I, I::signum, [
+    {
+        input_vectorization: 1,
+        out_vectorization: 1,
+        input: as_type![I: -3, 0, 5, -1],
+        expected: as_type![I: -1, 0, 1, -1]
+    },
+    {
+        input_vectorization: 2,
+        out_vectorization: 2,
+        input: as_type![I: -3, 0, 5, -1],
+        expected: as_type![I: -1, 0, 1, -1]
+    },
+    {
+        input_vectorization: 4,
+        out_vectorization: 4,
+        input: as_type![I: -3, 0, 5, -1],
+        expected: as_type![I: -1, 0, 1, -1]
+    }
+]);
+
+#[cube]
+fn pow_5<I: Int>(x: I) -> I {
+    I::pow(x, 5)
+}
+
+#[cube]
+fn pow_0<I: Int>(x: I) -> I {
+    I::pow(x, 0)
+}
+
+#[cube]
+fn pow_self<I: Int>(x: I) -> I {
+    I::pow(x, u32::cast_from(x))
+}
+
+test_unary_impl_int!(test_pow_comptime, I, pow_5::<I>, [
+    {
+        input_vectorization: 1,
+        out_vectorization: 1,
+        input: as_type![I: -2, 0, 1, 2],
+        expected: as_type![I: -32, 0, 1, 32]
+    },
+    {
+        input_vectorization: 4,
+        out_vectorization: 4,
+        input: as_type![I: -2, 0, 1, 2],
+        expected: as_type![I: -32, 0, 1, 32]
+    }
+]);
+
+test_unary_impl_int!(test_pow_zero, I, pow_0::<I>, [
+    {
+        input_vectorization: 1,
+        out_vectorization: 1,
+        input: as_type![I: -2, 0, 1, 2],
+        expected: as_type![I: 1, 1, 1, 1]
+    }
+]);
+
+test_unary_impl_int!(test_pow_runtime, I, pow_self::<I>, [
+    {
+        input_vectorization: 1,
+        out_vectorization: 1,
+        input: as_type![I: 0, 1, 2, 3],
+        expected: as_type![I: 1, 1, 4, 27]
+    }
+]);
+
+test_unary_impl!(
+    test_round,
+    F,
+    F::round,
+    [
+        {
+            input_vectorization: 1,
+            out_vectorization: 1,
+            input: as_type![F: 0.5, 1.5, 2.5, -2.5],
+            expected: as_type![F: 0., 2., 2., -2.]
+        },
+        {
+            input_vectorization: 2,
+            out_vectorization: 2,
+            input: as_type![F: 0.5, 1.5, 2.5, -2.5],
+            expected: as_type![F: 0., 2., 2., -2.]
+        },
+        {
+            input_vectorization: 4,
+            out_vectorization: 4,
+            input: as_type![F: 0.5, 1.5, 2.5, -2.5],
+            expected: as_type![F: 0., 2., 2., -2.]
+        }
+    ]
+);
+
+test_unary_impl!(
+    test_trunc,
+    F,
+    F::trunc,
+    [
+        {
+            input_vectorization: 1,
+            out_vectorization: 1,
+            input: as_type![F: 1.7, -1.7, 0.2, -3.5],
+            expected: as_type![F: 1., -1., 0., -3.]
+        },
+        {
+            input_vectorization: 2,
+            out_vectorization: 2,
+            input: as_type![F: 1.7, -1.7, 0.2, -3.5],
+            expected: as_type![F: 1., -1., 0., -3.]
+        },
+        {
+            input_vectorization: 4,
+            out_vectorization: 4,
+            input: as_type![F: 1.7, -1.7, 0.2, -3.5],
+            expected: as_type![F: 1., -1., 0., -3.]
+        }
+    ]
+);
+
+test_unary_impl!(
+    test_fract,
+    F,
+    F::fract,
+    [
+        {
+            input_vectorization: 1,
+            out_vectorization: 1,
+            input: as_type![F: 1.25, -1.25, 3.5, -0.75],
+            expected: as_type![F: 0.25, -0.25, 0.5, -0.75]
+        },
+        {
+            input_vectorization: 2,
+            out_vectorization: 2,
+            input: as_type![F: 1.25, -1.25, 3.5, -0.75],
+            expected: as_type![F: 0.25, -0.25, 0.5, -0.75]
+        },
+        {
+            input_vectorization: 4,
+            out_vectorization: 4,
+            input: as_type![F: 1.25, -1.25, 3.5, -0.75],
+            expected: as_type![F: 0.25, -0.25, 0.5, -0.75]
+        }
+    ]
+);
+
+test_unary_impl!(
+    test_gelu,
+    F,
+    F::gelu,
+    [
+        {
+            input_vectorization: 1,
+            out_vectorization: 1,
+            input: as_type![F: -100.0, -20.0, -3.0, -1.0, 0.0, 0.5, 1.0, 3.0],
+            expected: as_type![F: 0.0, 0.0, -0.004, -0.1587, 0.0, 0.3457, 0.8413, 2.996]
+        },
+        {
+            input_vectorization: 2,
+            out_vectorization: 2,
+            input: as_type![F: -100.0, -20.0, -3.0, -1.0, 0.0, 0.5, 1.0, 3.0],
+            expected: as_type![F: 0.0, 0.0, -0.004, -0.1587, 0.0, 0.3457, 0.8413, 2.996]
+        },
+        {
+            input_vectorization: 4,
+            out_vectorization: 4,
+            input: as_type![F: -100.0, -20.0, -3.0, -1.0, 0.0, 0.5, 1.0, 3.0],
+            expected: as_type![F: 0.0, 0.0, -0.004, -0.1587, 0.0, 0.3457, 0.8413, 2.996]
+        }
+    ]
+);
+
+test_unary_impl!(
+    test_gelu_tanh,
+    F,
+    F::gelu_tanh,
+    [
+        {
+            input_vectorization: 1,
+            out_vectorization: 1,
+            input: as_type![F: -100.0, -20.0, -3.0, -1.0, 0.0, 0.5, 1.0, 3.0],
+            expected: as_type![F: 0.0, 0.0, -0.0036, -0.1588, 0.0, 0.3457, 0.8412, 2.9964]
+        },
+        {
+            input_vectorization: 2,
+            out_vectorization: 2,
+            input: as_type![F: -100.0, -20.0, -3.0, -1.0, 0.0, 0.5, 1.0, 3.0],
+            expected: as_type![F: 0.0, 0.0, -0.0036, -0.1588, 0.0, 0.3457, 0.8412, 2.9964]
+        },
+        {
+            input_vectorization: 4,
+            out_vectorization: 4,
+            input: as_type![F: -100.0, -20.0, -3.0, -1.0, 0.0, 0.5, 1.0, 3.0],
+            expected: as_type![F: 0.0, 0.0, -0.0036, -0.1588, 0.0, 0.3457, 0.8412, 2.9964]
+        }
+    ]
+);
+
 test_unary_impl_int_fixed!(test_count_ones, I, u32, I::count_ones, [
     {
         input_vectorization: 1,
@@ -315,6 +972,33 @@ test_unary_impl_int_fixed!(test_leading_zeros, I, u32, I::leading_zeros, [
     }
 ]);
 
+macro_rules! width {
+    () => {
+        (size_of::<I>() * 8) as u32
+    };
+}
+
+test_unary_impl_int_fixed!(test_trailing_zeros, I, u32, I::trailing_zeros, [
+    {
+        input_vectorization: 1,
+        out_vectorization: 1,
+        input: as_type![I: 0b1110_0010, 0b0000_0000, 0b1111_1111],
+        expected: &[1, width!(), 0]
+    },
+    {
+        input_vectorization: 2,
+        out_vectorization: 2,
+        input: as_type![I: 0b1110_0010, 0b0000_0000, 0b1111_1111, 0b1000_0000],
+        expected: &[1, width!(), 0, 7]
+    },
+    {
+        input_vectorization: 4,
+        out_vectorization: 4,
+        input: as_type![I: 0b1110_0010, 0b0000_0000, 0b1111_1111, 0b1000_0000],
+        expected: &[1, width!(), 0, 7]
+    }
+]);
+
 test_unary_impl_int_fixed!(test_find_first_set, I, u32, I::find_first_set, [
     {
         input_vectorization: 1,
@@ -357,6 +1041,26 @@ macro_rules! testgen_unary {
 
             add_test!(test_normalize);
             add_test!(test_magnitude);
+            add_test!(test_tan);
+            add_test!(test_asin);
+            add_test!(test_acos);
+            add_test!(test_atan);
+            add_test!(test_sinh);
+            add_test!(test_cosh);
+            add_test!(test_asinh);
+            add_test!(test_acosh);
+            add_test!(test_atanh);
+            add_test!(test_log2);
+            add_test!(test_exp2);
+            add_test!(test_exp2_log2_round_trip);
+            add_test!(test_round);
+            add_test!(test_trunc);
+            add_test!(test_fract);
+            add_test!(test_gelu);
+            add_test!(test_gelu_tanh);
+            add_test!(test_const_fold);
+            add_test!(test_const_fold_base_2);
+            add_test!(test_const_fold_source);
         }
     };
 }
@@ -383,7 +1087,13 @@ macro_rules! testgen_unary_int {
             add_test!(test_count_ones);
             add_test!(test_reverse_bits);
             add_test!(test_leading_zeros);
+            add_test!(test_trailing_zeros);
             add_test!(test_find_first_set);
+            add_test!(test_abs_int);
+            add_test!(test_signum);
+            add_test!(test_pow_comptime);
+            add_test!(test_pow_zero);
+            add_test!(test_pow_runtime);
         }
     };
 }
@@ -1,6 +1,6 @@
 use std::marker::PhantomData;
 
-use crate::shared::{Dialect, WmmaCompiler};
+use crate::shared::{Dialect, Elem, WmmaCompiler};
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub struct CudaDialect<M> {
@@ -64,6 +64,9 @@ impl<M: WmmaCompiler<Self>> Dialect for CudaDialect<M> {
     fn include_bf16(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str("#include <cuda_bf16.h>\n")
     }
+    fn include_fp8(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("#include <cuda_fp8.h>\n")
+    }
     fn include_runtime(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str("#include <cuda_runtime.h>\n")
     }
@@ -74,6 +77,21 @@ impl<M: WmmaCompiler<Self>> Dialect for CudaDialect<M> {
     fn bfloat162_type_name(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str("__nv_bfloat162")
     }
+    fn fp8_e4m3_type_name(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("__nv_fp8_e4m3")
+    }
+    fn fp8_e5m2_type_name(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("__nv_fp8_e5m2")
+    }
+    fn fma(elem: Elem<Self>) -> &'static str {
+        // Rounded to nearest, so the compiler can't pick another rounding mode.
+        match elem {
+            Elem::F32 => "__fmaf_rn",
+            Elem::F64 => "__fma_rn",
+            Elem::F16 | Elem::BF16 => "__hfma",
+            _ => "fma",
+        }
+    }
     fn warp_shuffle(var: &str, source: &str) -> String {
         format!("__shfl_sync(-1, {var}, {source})")
     }
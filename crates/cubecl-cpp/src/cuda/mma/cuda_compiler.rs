@@ -9,6 +9,8 @@ use cubecl_core::ir::{self as gpu};
 
 const WMMA_NAMESPACE: &str = "nvcuda::wmma";
 const WMMA_MINIMUM_VERSION: u32 = 70;
+// Integer (u8/i8) fragments need sm_72 or newer.
+const WMMA_INTEGER_MINIMUM_VERSION: u32 = 72;
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub struct CudaWmmaCompiler {}
@@ -93,6 +95,15 @@ impl WmmaCompiler<CudaDialect<Self>> for CudaWmmaCompiler {
                 vec![(16, 16, 8)],
             ));
         }
+        if arch.version >= WMMA_INTEGER_MINIMUM_VERSION {
+            let tdims = vec![(16, 16, 16), (32, 8, 16), (8, 32, 16)];
+            for elem in [
+                gpu::Elem::UInt(gpu::UIntKind::U8),
+                gpu::Elem::Int(gpu::IntKind::I8),
+            ] {
+                result.push((elem, elem, gpu::Elem::Int(gpu::IntKind::I32), tdims.clone()));
+            }
+        }
         result
     }
 }
@@ -1,6 +1,6 @@
 use std::marker::PhantomData;
 
-use crate::shared::{Dialect, WmmaCompiler};
+use crate::shared::{Dialect, Elem, WmmaCompiler};
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub struct HipDialect<M> {
@@ -65,6 +65,9 @@ impl<M: WmmaCompiler<Self>> Dialect for HipDialect<M> {
         // "hip_bf16.h" triggers redefinition errors during compilation
         f.write_str("#include <hip/hip_bfloat16.h>\n")
     }
+    fn include_fp8(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("#include <hip/hip_fp8.h>\n")
+    }
     fn include_runtime(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str("#include <hip/hip_runtime.h>\n")
     }
@@ -75,6 +78,22 @@ impl<M: WmmaCompiler<Self>> Dialect for HipDialect<M> {
         // "hip_bfloat16.h" has no "hip_bfloat162" type
         f.write_str("hip_bfloat16")
     }
+    fn fp8_e4m3_type_name(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("__hip_fp8_e4m3")
+    }
+    fn fp8_e5m2_type_name(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("__hip_fp8_e5m2")
+    }
+    fn fma(elem: Elem<Self>) -> &'static str {
+        match elem {
+            Elem::F32 => "__fmaf_rn",
+            Elem::F64 => "__fma_rn",
+            Elem::F16 => "__hfma",
+            // "hip_bfloat16.h" has no "__hfma", the float fma is rounded back on assignment
+            Elem::BF16 => "fmaf",
+            _ => "fma",
+        }
+    }
     fn warp_shuffle(var: &str, source: &str) -> String {
         format!("__shfl({var}, {source})")
     }
@@ -0,0 +1,102 @@
+use std::fmt::Display;
+
+use super::{Component, Dialect, Variable};
+
+/// How the asynchronous copies of a kernel are lowered, with pipelines unless barriers are opted
+/// into through the [compilation options](super::CompilationOptions).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AsyncCopyLowering {
+    /// Use `cuda::pipeline`, see [PipelineOps](super::PipelineOps).
+    #[default]
+    Pipeline,
+    /// Use `cuda::barrier`, see [BarrierOps].
+    Barrier,
+}
+
+/// Asynchronous copies completed through a `cuda::barrier` instead of a `cuda::pipeline`.
+///
+/// Pipeline stages don't exist with a barrier, so the pipeline operations map as follows:
+///
+/// - `Init` declares the barrier, expecting a single arrival since the pipeline is per unit.
+/// - `MemCopyAsync` becomes [BarrierOps::MemCopyAsync], which attaches the copy to the current
+///   phase of the barrier.
+/// - `ProducerAcquire` and `ProducerCommit` emit nothing: copies are tracked as soon as they are
+///   issued.
+/// - `ConsumerWait` becomes [BarrierOps::Arrive] followed by [BarrierOps::Wait], completing the
+///   phase once every copy issued so far has landed.
+/// - `ConsumerRelease` emits nothing, since the next phase starts right after the wait.
+///
+/// Waiting completes all the copies in flight instead of only the oldest stage, so the result is
+/// the same as with a pipeline, with less overlap when more than one stage is in flight.
+#[derive(Debug, Clone)]
+pub enum BarrierOps<D: Dialect> {
+    Init {
+        barrier: Variable<D>,
+    },
+    MemCopyAsync {
+        barrier: Variable<D>,
+        source: Variable<D>,
+        destination: Variable<D>,
+    },
+    Arrive {
+        barrier: Variable<D>,
+    },
+    Wait {
+        barrier: Variable<D>,
+    },
+}
+
+impl<D: Dialect> BarrierOps<D> {
+    pub fn barrier_id(&self) -> u32 {
+        match self {
+            BarrierOps::Init { barrier } => barrier.id().unwrap(),
+            BarrierOps::MemCopyAsync { barrier, .. } => barrier.id().unwrap(),
+            BarrierOps::Arrive { barrier } => barrier.id().unwrap(),
+            BarrierOps::Wait { barrier } => barrier.id().unwrap(),
+        }
+    }
+}
+
+impl<D: Dialect> Display for BarrierOps<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BarrierOps::Init { barrier } => {
+                write!(
+                    f,
+                    "
+cuda::barrier<cuda::thread_scope::thread_scope_thread> {barrier};
+init(&{barrier}, 1);
+cuda::barrier<cuda::thread_scope::thread_scope_thread>::arrival_token {barrier}_token;
+                "
+                )
+            }
+            BarrierOps::MemCopyAsync {
+                barrier,
+                source,
+                destination,
+            } => {
+                let item = source.item();
+                let size = item.elem().size() * item.vectorization;
+                write!(f, "
+cuda::memcpy_async(cooperative_groups::this_thread(), {destination}, {source}, {source}_length * {size}, {barrier});
+                ")
+            }
+            BarrierOps::Arrive { barrier } => {
+                write!(
+                    f,
+                    "
+{barrier}_token = {barrier}.arrive();
+                "
+                )
+            }
+            BarrierOps::Wait { barrier } => {
+                write!(
+                    f,
+                    "
+{barrier}.wait(std::move({barrier}_token));
+            "
+                )
+            }
+        }
+    }
+}
@@ -1,19 +1,24 @@
 use std::hash::Hash;
-use std::{collections::HashSet, fmt::Debug, num::NonZero};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    num::NonZero,
+};
 
 use cubecl_common::ExecutionMode;
 use cubecl_core::prelude::{expand_checked_index_assign, FastMath, KernelDefinition};
 use cubecl_core::{
-    ir::{self as gpu},
-    Compiler, Feature,
+    ir::{self as gpu, OperationReflect},
+    CompilationError, Compiler, CompilerRepresentation, Feature,
 };
-use cubecl_runtime::DeviceProperties;
+use cubecl_runtime::{debug::DebugLogger, DeviceProperties};
 
-use super::pipeline::PipelineOps;
+use super::pipeline::{push_timed, shared_memory_bytes, PipelineOps};
 use super::{
-    AtomicKind, BinaryInstruction, Binding, Body, ComputeKernel, ConstArray, Elem, Fragment,
-    FragmentIdent, FragmentLayout, Instruction, Item, LocalArray, SharedMemory, UnaryInstruction,
-    Variable, VariableSettings, WarpInstruction, WmmaCompiler, WmmaInstruction,
+    AsyncCopyLowering, AtomicKind, BarrierOps, BinaryInstruction, Binding, Body, ComputeKernel,
+    ConstArray, Elem, Fragment, FragmentIdent, FragmentLayout, Instruction, Item, LocalArray,
+    SharedMemory, UnaryInstruction, Variable, VariableSettings, WarpInstruction, WmmaCompiler,
+    WmmaInstruction,
 };
 
 pub(super) static COUNTER_TMP_VAR: std::sync::atomic::AtomicU32 =
@@ -25,10 +30,15 @@ pub trait Dialect:
     // includes
     fn include_f16(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result;
     fn include_bf16(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result;
+    fn include_fp8(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result;
     fn include_runtime(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result;
     // types
     fn bfloat16_type_name(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result;
     fn bfloat162_type_name(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result;
+    fn fp8_e4m3_type_name(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result;
+    fn fp8_e5m2_type_name(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result;
+    // math
+    fn fma(elem: Elem<Self>) -> &'static str;
     // warp instructions (all threads participating)
     fn warp_shuffle(var: &str, source: &str) -> String;
     fn warp_shuffle_xor(var: &str, offset: &str) -> String;
@@ -42,11 +52,19 @@ pub trait Dialect:
 #[derive(Clone, Debug)]
 pub struct CompilationOptions {
     pub warp_size: u32,
+    /// How the asynchronous copies of pipelines are lowered, which depends on the architecture.
+    pub async_copy_lowering: AsyncCopyLowering,
+    /// The shared memory available to a cube on the device, in bytes.
+    pub max_shared_memory_size: usize,
 }
 
 impl Default for CompilationOptions {
     fn default() -> Self {
-        Self { warp_size: 32 }
+        Self {
+            warp_size: 32,
+            async_copy_lowering: AsyncCopyLowering::default(),
+            max_shared_memory_size: 49152,
+        }
     }
 }
 
@@ -55,14 +73,21 @@ impl Default for CompilationOptions {
 pub struct CppCompiler<D: Dialect> {
     shared_memories: Vec<SharedMemory<D>>,
     pipelines: Vec<PipelineOps<D>>,
+    /// The size in bytes of the largest buffer copied into by each pipeline.
+    pipeline_stage_bytes: HashMap<gpu::Id, usize>,
+    /// The size in bytes of the slices of shared memories with constant bounds.
+    shared_slice_bytes: HashMap<gpu::Id, usize>,
+    barriers: Vec<BarrierOps<D>>,
     const_arrays: Vec<ConstArray<D>>,
     local_arrays: Vec<LocalArray<D>>,
     metadata: cubecl_core::Metadata,
     warp_size_checked: bool,
     wmma: bool,
     pipeline: bool,
+    barrier: bool,
     bf16: bool,
     f16: bool,
+    fp8: bool,
     printf: bool,
     num_inputs: usize,
     num_outputs: usize,
@@ -71,6 +96,7 @@ pub struct CppCompiler<D: Dialect> {
     strategy: ExecutionMode,
     settings: VariableSettings,
     compilation_options: CompilationOptions,
+    debug_symbols: bool,
 }
 
 impl<D: Dialect> Compiler for CppCompiler<D> {
@@ -82,9 +108,21 @@ impl<D: Dialect> Compiler for CppCompiler<D> {
         compilation_options: &Self::CompilationOptions,
         strategy: ExecutionMode,
     ) -> Self::Representation {
+        match Self::try_compile(kernel, compilation_options, strategy) {
+            Ok(ir) => ir,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    fn try_compile(
+        kernel: KernelDefinition,
+        compilation_options: &Self::CompilationOptions,
+        strategy: ExecutionMode,
+    ) -> Result<Self::Representation, CompilationError> {
         let compiler = Self {
             compilation_options: compilation_options.clone(),
             strategy,
+            debug_symbols: DebugLogger::default().is_activated() || kernel.options.debug_symbols,
             ..Self::default()
         };
         let ir = compiler.compile_ir(kernel);
@@ -102,10 +140,14 @@ impl<D: Dialect> Compiler for CppCompiler<D> {
 }
 
 impl<D: Dialect> CppCompiler<D> {
-    fn compile_ir(mut self, mut value: KernelDefinition) -> ComputeKernel<D> {
+    fn compile_ir(
+        mut self,
+        mut value: KernelDefinition,
+    ) -> Result<ComputeKernel<D>, CompilationError> {
         self.build_metadata(&value);
 
         let instructions = self.compile_scope(&mut value.body);
+        self.check_pipelines()?;
         let inputs = value
             .inputs
             .into_iter()
@@ -126,6 +168,7 @@ impl<D: Dialect> CppCompiler<D> {
             instructions,
             shared_memories: self.shared_memories,
             pipelines: self.pipelines,
+            barriers: self.barriers,
             const_arrays: self.const_arrays,
             local_arrays: self.local_arrays,
             warp_size_checked: self.warp_size_checked,
@@ -136,7 +179,7 @@ impl<D: Dialect> CppCompiler<D> {
             .fp_math_mode
             .contains(FastMath::ReducedPrecision);
 
-        ComputeKernel {
+        let kernel = ComputeKernel {
             inputs,
             outputs,
             named,
@@ -144,11 +187,58 @@ impl<D: Dialect> CppCompiler<D> {
             body,
             wmma_activated: self.wmma,
             pipeline: self.pipeline,
+            barrier: self.barrier,
             bf16: self.bf16,
             f16: self.f16,
+            fp8: self.fp8,
             fast_math,
             items: self.items,
             kernel_name: value.options.kernel_name,
+        };
+
+        Ok(kernel)
+    }
+
+    /// Check that every pipeline has at least one stage, and that its stages fit in the shared
+    /// memory of a cube on the device, so that a kernel with too many or too large stages fails to
+    /// compile with a clear error instead of failing to launch.
+    ///
+    /// A stage is sized like the largest buffer its pipeline copies into. Slices of shared memory
+    /// with runtime bounds have no known size, so they aren't accounted for.
+    fn check_pipelines(&self) -> Result<(), CompilationError> {
+        let max_bytes = self.compilation_options.max_shared_memory_size;
+        for pipeline in self.pipelines.iter() {
+            if let PipelineOps::Init { num_stages, .. } = pipeline {
+                let num_stages = *num_stages as usize;
+                if num_stages == 0 {
+                    return Err(CompilationError::PipelineWithoutStages);
+                }
+
+                let stage_bytes = self
+                    .pipeline_stage_bytes
+                    .get(&pipeline.pipeline_id())
+                    .copied()
+                    .unwrap_or(0);
+                let shared_memory_bytes = num_stages * stage_bytes;
+                if shared_memory_bytes > max_bytes {
+                    return Err(CompilationError::SharedMemoryExceeded {
+                        num_stages,
+                        shared_memory_bytes,
+                        max_bytes,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The size in bytes of `buffer` when it is a shared memory or a slice of one with constant
+    /// bounds.
+    fn shared_buffer_bytes(&self, buffer: &Variable<D>) -> Option<usize> {
+        match buffer {
+            Variable::SharedMemory(..) => Some(shared_memory_bytes(buffer)),
+            Variable::Slice { id, .. } => self.shared_slice_bytes.get(id).copied(),
+            _ => None,
         }
     }
 
@@ -222,6 +312,19 @@ impl<D: Dialect> CppCompiler<D> {
         instruction: gpu::Instruction,
         scope: &mut gpu::Scope,
     ) {
+        if let gpu::Operation::Arithmetic(_) | gpu::Operation::Comparison(_) = instruction.operation
+        {
+            if let Some(args) = instruction.operation.args() {
+                if args
+                    .iter()
+                    .chain(instruction.out.iter())
+                    .any(|var| is_fp8(var.item.elem))
+                {
+                    return self.compile_fp8_promoted(instructions, instruction, args, scope);
+                }
+            }
+        }
+
         let out = instruction.out;
         match instruction.operation {
             gpu::Operation::Copy(variable) => {
@@ -350,44 +453,138 @@ impl<D: Dialect> CppCompiler<D> {
                 gpu::NonSemantic::Comment { content } => {
                     instructions.push(Instruction::Comment { content })
                 }
+                gpu::NonSemantic::DebugName { var, name } => {
+                    // Names are only a debugging aid, so the output is unchanged without symbols.
+                    if self.debug_symbols {
+                        instructions.push(Instruction::DebugName {
+                            var: self.compile_variable(var),
+                            name,
+                        })
+                    }
+                }
             },
-            gpu::Operation::Pipeline(pipeline_ops) => match pipeline_ops {
-                gpu::PipelineOps::MemCopyAsync {
-                    pipeline,
-                    source,
-                    destination,
-                } => {
-                    instructions.push(Instruction::Pipeline(
-                        super::pipeline::PipelineOps::MemCopyAsync {
-                            pipeline: self.compile_variable(pipeline),
+            gpu::Operation::Pipeline(pipeline_ops)
+                if self.compilation_options.async_copy_lowering == AsyncCopyLowering::Barrier =>
+            {
+                self.compile_pipeline_as_barrier(pipeline_ops, instructions)
+            }
+            gpu::Operation::Pipeline(pipeline_ops) => {
+                let pipeline_ops = match pipeline_ops {
+                    gpu::PipelineOps::MemCopyAsync {
+                        pipeline,
+                        source,
+                        destination,
+                    } => {
+                        let pipeline = self.compile_variable(pipeline);
+                        let destination = self.compile_variable(destination);
+                        if let Some(bytes) = self.shared_buffer_bytes(&destination) {
+                            let stage_bytes = self
+                                .pipeline_stage_bytes
+                                .entry(pipeline.id().unwrap())
+                                .or_default();
+                            *stage_bytes = usize::max(*stage_bytes, bytes);
+                        }
+                        PipelineOps::MemCopyAsync {
+                            pipeline,
                             source: self.compile_variable(source),
-                            destination: self.compile_variable(destination),
-                        },
-                    ));
-                }
-                gpu::PipelineOps::ProducerAcquire { pipeline } => instructions.push(
-                    Instruction::Pipeline(super::pipeline::PipelineOps::ProducerAcquire {
+                            destination,
+                        }
+                    }
+                    gpu::PipelineOps::ProducerAcquire { pipeline } => {
+                        PipelineOps::ProducerAcquire {
+                            pipeline: self.compile_variable(pipeline),
+                        }
+                    }
+                    gpu::PipelineOps::ProducerCommit { pipeline } => PipelineOps::ProducerCommit {
                         pipeline: self.compile_variable(pipeline),
-                    }),
-                ),
-                gpu::PipelineOps::ProducerCommit { pipeline } => instructions.push(
-                    Instruction::Pipeline(super::pipeline::PipelineOps::ProducerCommit {
+                    },
+                    gpu::PipelineOps::ConsumerWait { pipeline } => PipelineOps::ConsumerWait {
                         pipeline: self.compile_variable(pipeline),
-                    }),
-                ),
+                    },
+                    gpu::PipelineOps::ConsumerRelease { pipeline } => {
+                        PipelineOps::ConsumerRelease {
+                            pipeline: self.compile_variable(pipeline),
+                        }
+                    }
+                };
+                // Times the phases of the pipeline with the `pipeline_profiling` feature.
+                push_timed(instructions, pipeline_ops);
+            }
+        }
+    }
 
-                gpu::PipelineOps::ConsumerWait { pipeline } => instructions.push(
-                    Instruction::Pipeline(super::pipeline::PipelineOps::ConsumerWait {
-                        pipeline: self.compile_variable(pipeline),
-                    }),
-                ),
+    /// The fp8 types only have conversions, so math on fp8 values is done in `float`: the fp8
+    /// operands are converted before the operation, and the result is converted back after it.
+    fn compile_fp8_promoted(
+        &mut self,
+        instructions: &mut Vec<Instruction<D>>,
+        instruction: gpu::Instruction,
+        args: Vec<gpu::Variable>,
+        scope: &mut gpu::Scope,
+    ) {
+        let promoted_item = |item: gpu::Item| gpu::Item {
+            elem: gpu::Elem::Float(gpu::FloatKind::F32),
+            ..item
+        };
 
-                gpu::PipelineOps::ConsumerRelease { pipeline } => instructions.push(
-                    Instruction::Pipeline(super::pipeline::PipelineOps::ConsumerRelease {
-                        pipeline: self.compile_variable(pipeline),
-                    }),
-                ),
-            },
+        let mut promoted_args = Vec::with_capacity(args.len());
+        for arg in args {
+            if !is_fp8(arg.item.elem) {
+                promoted_args.push(arg);
+                continue;
+            }
+            let promoted = *scope.create_local(promoted_item(arg.item));
+            instructions.push(Instruction::Assign(UnaryInstruction {
+                input: self.compile_variable(arg),
+                out: self.compile_variable(promoted),
+            }));
+            promoted_args.push(promoted);
+        }
+
+        let operation =
+            gpu::Operation::from_code_and_args(instruction.operation.op_code(), &promoted_args)
+                .expect("Arithmetic and comparison operations only have variable arguments");
+        let out = instruction.out();
+        if is_fp8(out.item.elem) {
+            let promoted = *scope.create_local(promoted_item(out.item));
+            self.compile_operation(
+                instructions,
+                gpu::Instruction::new(operation, promoted),
+                scope,
+            );
+            instructions.push(Instruction::Assign(UnaryInstruction {
+                input: self.compile_variable(promoted),
+                out: self.compile_variable(out),
+            }));
+        } else {
+            self.compile_operation(instructions, gpu::Instruction::new(operation, out), scope);
+        }
+    }
+
+    fn compile_pipeline_as_barrier(
+        &mut self,
+        pipeline_ops: gpu::PipelineOps,
+        instructions: &mut Vec<Instruction<D>>,
+    ) {
+        match pipeline_ops {
+            gpu::PipelineOps::MemCopyAsync {
+                pipeline,
+                source,
+                destination,
+            } => instructions.push(Instruction::Barrier(BarrierOps::MemCopyAsync {
+                barrier: self.compile_variable(pipeline),
+                source: self.compile_variable(source),
+                destination: self.compile_variable(destination),
+            })),
+            gpu::PipelineOps::ConsumerWait { pipeline } => {
+                let barrier = self.compile_variable(pipeline);
+                instructions.push(Instruction::Barrier(BarrierOps::Arrive { barrier }));
+                instructions.push(Instruction::Barrier(BarrierOps::Wait { barrier }));
+            }
+            // Copies are tracked by the barrier phase, not by stages.
+            gpu::PipelineOps::ProducerAcquire { .. }
+            | gpu::PipelineOps::ProducerCommit { .. }
+            | gpu::PipelineOps::ConsumerRelease { .. } => {}
         }
     }
 
@@ -545,6 +742,7 @@ impl<D: Dialect> CppCompiler<D> {
                 end: self.compile_variable(range_loop.end),
                 step: range_loop.step.map(|it| self.compile_variable(it)),
                 inclusive: range_loop.inclusive,
+                unroll_factor: range_loop.unroll_factor,
                 instructions: self.compile_scope(&mut range_loop.scope),
             }),
             gpu::Branch::Loop(mut op) => instructions.push(Instruction::Loop {
@@ -626,6 +824,9 @@ impl<D: Dialect> CppCompiler<D> {
             gpu::Arithmetic::Abs(op) => {
                 instructions.push(Instruction::Abs(self.compile_unary(op, out)))
             }
+            gpu::Arithmetic::Signum(op) => {
+                instructions.push(Instruction::Signum(self.compile_unary(op, out)))
+            }
             gpu::Arithmetic::Exp(op) => {
                 instructions.push(Instruction::Exp(self.compile_unary(op, out)))
             }
@@ -644,9 +845,45 @@ impl<D: Dialect> CppCompiler<D> {
             gpu::Arithmetic::Tanh(op) => {
                 instructions.push(Instruction::Tanh(self.compile_unary(op, out)))
             }
+            gpu::Arithmetic::Log2(op) => {
+                instructions.push(Instruction::Log2(self.compile_unary(op, out)))
+            }
+            gpu::Arithmetic::Exp2(op) => {
+                instructions.push(Instruction::Exp2(self.compile_unary(op, out)))
+            }
+            gpu::Arithmetic::Sinh(op) => {
+                instructions.push(Instruction::Sinh(self.compile_unary(op, out)))
+            }
+            gpu::Arithmetic::Cosh(op) => {
+                instructions.push(Instruction::Cosh(self.compile_unary(op, out)))
+            }
+            gpu::Arithmetic::Asinh(op) => {
+                instructions.push(Instruction::Asinh(self.compile_unary(op, out)))
+            }
+            gpu::Arithmetic::Acosh(op) => {
+                instructions.push(Instruction::Acosh(self.compile_unary(op, out)))
+            }
+            gpu::Arithmetic::Atanh(op) => {
+                instructions.push(Instruction::Atanh(self.compile_unary(op, out)))
+            }
+            gpu::Arithmetic::Tan(op) => {
+                instructions.push(Instruction::Tan(self.compile_unary(op, out)))
+            }
+            gpu::Arithmetic::Asin(op) => {
+                instructions.push(Instruction::Asin(self.compile_unary(op, out)))
+            }
+            gpu::Arithmetic::Acos(op) => {
+                instructions.push(Instruction::Acos(self.compile_unary(op, out)))
+            }
+            gpu::Arithmetic::Atan(op) => {
+                instructions.push(Instruction::Atan(self.compile_unary(op, out)))
+            }
             gpu::Arithmetic::Powf(op) => {
                 instructions.push(Instruction::Powf(self.compile_binary(op, out)))
             }
+            gpu::Arithmetic::Atan2(op) => {
+                instructions.push(Instruction::Atan2(self.compile_binary(op, out)))
+            }
             gpu::Arithmetic::Sqrt(op) => {
                 instructions.push(Instruction::Sqrt(self.compile_unary(op, out)))
             }
@@ -691,6 +928,9 @@ impl<D: Dialect> CppCompiler<D> {
             gpu::Arithmetic::Floor(op) => {
                 instructions.push(Instruction::Floor(self.compile_unary(op, out)))
             }
+            gpu::Arithmetic::Trunc(op) => {
+                instructions.push(Instruction::Trunc(self.compile_unary(op, out)))
+            }
             gpu::Arithmetic::Ceil(op) => {
                 instructions.push(Instruction::Ceil(self.compile_unary(op, out)))
             }
@@ -782,12 +1022,31 @@ impl<D: Dialect> CppCompiler<D> {
             gpu::Bitwise::LeadingZeros(op) => {
                 instructions.push(Instruction::LeadingZeros(self.compile_unary(op, out)))
             }
+            gpu::Bitwise::TrailingZeros(op) => {
+                instructions.push(Instruction::TrailingZeros(self.compile_unary(op, out)))
+            }
             gpu::Bitwise::FindFirstSet(op) => {
                 instructions.push(Instruction::FindFirstSet(self.compile_unary(op, out)))
             }
         };
     }
 
+    /// Remember the size of the slice `out` of `op` when it covers a constant range of a shared
+    /// memory, so that the copies of pipelines into it can be sized.
+    fn record_shared_slice(&mut self, op: &gpu::SliceOperator, out: gpu::Variable) {
+        let gpu::VariableKind::Slice { id } = out.kind else {
+            return;
+        };
+        let Variable::SharedMemory(_, item, _) = self.compile_variable(op.input) else {
+            return;
+        };
+        let bound = |var: gpu::Variable| var.as_const().and_then(|value| value.try_as_usize());
+        if let (Some(start), Some(end)) = (bound(op.start), bound(op.end)) {
+            let bytes = end.saturating_sub(start) * item.elem.size() * item.vectorization;
+            self.shared_slice_bytes.insert(id, bytes);
+        }
+    }
+
     fn compile_operator(
         &mut self,
         value: gpu::Operator,
@@ -798,6 +1057,7 @@ impl<D: Dialect> CppCompiler<D> {
         let out = out.unwrap();
         match value {
             gpu::Operator::Slice(op) => {
+                self.record_shared_slice(&op, out);
                 if matches!(self.strategy, ExecutionMode::Checked) && op.input.has_length() {
                     let input = op.input;
                     let input_len = *scope
@@ -1048,11 +1308,18 @@ impl<D: Dialect> CppCompiler<D> {
                 item,
                 num_stages,
             } => {
-                self.pipeline = true;
                 let pipeline = Variable::Pipeline {
                     id,
                     item: self.compile_item(item),
                 };
+                if self.compilation_options.async_copy_lowering == AsyncCopyLowering::Barrier {
+                    self.barrier = true;
+                    if !self.barriers.iter().any(|s| s.barrier_id() == id) {
+                        self.barriers.push(BarrierOps::Init { barrier: pipeline });
+                    }
+                    return pipeline;
+                }
+                self.pipeline = true;
                 if !self.pipelines.iter().any(|s| s.pipeline_id() == id) {
                     self.pipelines.push(PipelineOps::Init {
                         pipeline,
@@ -1120,6 +1387,14 @@ impl<D: Dialect> CppCompiler<D> {
     fn compile_elem(&mut self, value: gpu::Elem) -> Elem<D> {
         match value {
             gpu::Elem::Float(kind) => match kind {
+                gpu::FloatKind::E4M3 => {
+                    self.fp8 = true;
+                    Elem::E4M3
+                }
+                gpu::FloatKind::E5M2 => {
+                    self.fp8 = true;
+                    Elem::E5M2
+                }
                 gpu::FloatKind::F16 => {
                     self.f16 = true;
                     Elem::F16
@@ -1167,6 +1442,13 @@ impl<D: Dialect> CppCompiler<D> {
     }
 }
 
+fn is_fp8(elem: gpu::Elem) -> bool {
+    matches!(
+        elem,
+        gpu::Elem::Float(gpu::FloatKind::E4M3 | gpu::FloatKind::E5M2)
+    )
+}
+
 pub fn register_supported_types(props: &mut DeviceProperties<Feature>) {
     let supported_types = [
         gpu::Elem::UInt(gpu::UIntKind::U8),
@@ -1195,3 +1477,315 @@ pub fn register_supported_types(props: &mut DeviceProperties<Feature>) {
         props.register_feature(Feature::Type(ty));
     }
 }
+
+#[cfg(all(test, feature = "cuda"))]
+mod tests {
+    use super::*;
+    use crate::cuda::{mma::CudaWmmaCompiler, CudaDialect};
+
+    type D = CudaDialect<CudaWmmaCompiler>;
+
+    /// Compile a single stage copied asynchronously from a slice to shared memory, along with the
+    /// declarations of the pipelines and barriers it uses.
+    fn compile_async_copy(async_copy_lowering: AsyncCopyLowering) -> String {
+        let mut compiler = CppCompiler::<D> {
+            compilation_options: CompilationOptions {
+                async_copy_lowering,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let item = gpu::Item::vectorized(gpu::Elem::Float(gpu::FloatKind::F32), NonZero::new(4));
+        let pipeline = gpu::Variable::new(
+            gpu::VariableKind::Pipeline {
+                id: 0,
+                item,
+                num_stages: 2,
+            },
+            item,
+        );
+        let source = gpu::Variable::new(gpu::VariableKind::Slice { id: 1 }, item);
+        let destination = gpu::Variable::new(gpu::VariableKind::Slice { id: 2 }, item);
+        let operations = [
+            gpu::PipelineOps::ProducerAcquire { pipeline },
+            gpu::PipelineOps::MemCopyAsync {
+                pipeline,
+                source,
+                destination,
+            },
+            gpu::PipelineOps::ProducerCommit { pipeline },
+            gpu::PipelineOps::ConsumerWait { pipeline },
+            gpu::PipelineOps::ConsumerRelease { pipeline },
+        ];
+
+        let mut scope = gpu::Scope::root(false);
+        let mut instructions = Vec::new();
+        for operation in operations {
+            let instruction = gpu::Instruction {
+                out: None,
+                operation: gpu::Operation::Pipeline(operation),
+            };
+            compiler.compile_operation(&mut instructions, instruction, &mut scope);
+        }
+
+        let declarations = compiler
+            .pipelines
+            .iter()
+            .map(|pipeline| pipeline.to_string())
+            .chain(compiler.barriers.iter().map(|barrier| barrier.to_string()));
+        declarations
+            .chain(
+                instructions
+                    .iter()
+                    .map(|instruction| instruction.to_string()),
+            )
+            .collect()
+    }
+
+    #[test]
+    fn async_copy_lowered_to_pipeline() {
+        let source = compile_async_copy(AsyncCopyLowering::Pipeline);
+
+        assert!(
+            source.contains("cuda::pipeline"),
+            "Missing pipeline in {source}"
+        );
+        assert!(source.contains(".producer_acquire();"), "{source}");
+        assert!(source.contains(".consumer_wait();"), "{source}");
+        assert!(
+            !source.contains("cuda::barrier"),
+            "Unexpected barrier in {source}"
+        );
+    }
+
+    #[test]
+    fn async_copy_lowered_to_barrier() {
+        let source = compile_async_copy(AsyncCopyLowering::Barrier);
+
+        assert!(
+            source.contains("cuda::barrier<cuda::thread_scope::thread_scope_thread> pipeline_0;"),
+            "Missing barrier in {source}"
+        );
+        assert!(source.contains("init(&pipeline_0, 1);"), "{source}");
+        assert!(
+            source.contains(
+                "cuda::memcpy_async(cooperative_groups::this_thread(), slice_2, slice_1, \
+                 slice_1_length * 16, pipeline_0);"
+            ),
+            "Missing copy in {source}"
+        );
+        assert!(
+            source.contains("pipeline_0_token = pipeline_0.arrive();"),
+            "{source}"
+        );
+        assert!(
+            source.contains("pipeline_0.wait(std::move(pipeline_0_token));"),
+            "{source}"
+        );
+        assert!(
+            !source.contains("cuda::pipeline"),
+            "Unexpected pipeline in {source}"
+        );
+        assert!(
+            !source.contains("producer_"),
+            "Unexpected stage in {source}"
+        );
+    }
+
+    #[test]
+    fn fp8_math_is_done_in_float() {
+        let mut compiler = CppCompiler::<D>::default();
+        let mut scope = gpu::Scope::root(false);
+        let item = gpu::Item::new(gpu::Elem::Float(gpu::FloatKind::E4M3));
+        let lhs = *scope.create_local(item);
+        let rhs = *scope.create_local(item);
+        let out = *scope.create_local(item);
+        let instruction =
+            gpu::Instruction::new(gpu::Arithmetic::Add(gpu::BinaryOperator { lhs, rhs }), out);
+
+        let mut instructions = Vec::new();
+        compiler.compile_operation(&mut instructions, instruction, &mut scope);
+        let source: String = instructions
+            .iter()
+            .map(|instruction| instruction.to_string())
+            .collect();
+
+        assert!(source.contains("const float l_3 = float(l_0);"), "{source}");
+        assert!(source.contains("const float l_4 = float(l_1);"), "{source}");
+        assert!(source.contains("const float l_5 = l_3 + l_4;"), "{source}");
+        assert!(
+            source.contains("const __nv_fp8_e4m3 l_2 = __nv_fp8_e4m3(l_5);"),
+            "{source}"
+        );
+    }
+
+    /// Compile a kernel assigning a constant to a local variable named `total`.
+    fn compile_named_local(debug_symbols: bool) -> String {
+        let mut scope = gpu::Scope::root(debug_symbols);
+        let item = gpu::Item::new(gpu::Elem::Float(gpu::FloatKind::F32));
+        let total = scope.create_local_named(item, "total");
+        let value =
+            gpu::Variable::constant(gpu::ConstantScalarValue::Float(1.0, gpu::FloatKind::F32));
+        scope.register(gpu::Instruction::new(gpu::Operation::Copy(value), *total));
+
+        let kernel = KernelDefinition {
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            named: Vec::new(),
+            cube_dim: cubecl_core::CubeDim::default(),
+            body: scope,
+            options: cubecl_core::KernelOptions {
+                debug_symbols,
+                ..Default::default()
+            },
+        };
+        CppCompiler::<D>::compile(kernel, &Default::default(), ExecutionMode::Checked).to_string()
+    }
+
+    #[test]
+    fn named_local_is_annotated_with_debug_symbols() {
+        let source = compile_named_local(true);
+
+        assert!(source.contains("/* l_0: total */"), "{source}");
+        assert!(source.contains("const float l_0 = float(1.0);"), "{source}");
+    }
+
+    #[test]
+    fn named_local_is_unchanged_without_debug_symbols() {
+        let source = compile_named_local(false);
+
+        assert!(!source.contains("total"), "{source}");
+        assert!(source.contains("const float l_0 = float(1.0);"), "{source}");
+    }
+
+    /// Compile a kernel copying asynchronously into the `num_stages` shared memories of
+    /// `tile_elems` lines of 16 bytes staged by a pipeline, on a device with
+    /// `max_shared_memory_size` bytes of shared memory per cube.
+    ///
+    /// At least one copy is emitted, since the pipeline is only declared once it is used.
+    fn compile_staged_kernel(
+        num_stages: u8,
+        tile_elems: u32,
+        max_shared_memory_size: usize,
+    ) -> Result<ComputeKernel<D>, CompilationError> {
+        let mut scope = gpu::Scope::root(false);
+        let item = gpu::Item::vectorized(gpu::Elem::Float(gpu::FloatKind::F32), NonZero::new(4));
+        let pipeline = scope.create_pipeline(item, num_stages);
+        let source = scope.create_slice(item);
+        for _ in 0..num_stages.max(1) {
+            let destination = scope.create_shared(item, tile_elems);
+            scope.register(gpu::Instruction {
+                out: None,
+                operation: gpu::Operation::Pipeline(gpu::PipelineOps::MemCopyAsync {
+                    pipeline: *pipeline,
+                    source: *source,
+                    destination: *destination,
+                }),
+            });
+        }
+
+        let kernel = KernelDefinition {
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            named: Vec::new(),
+            cube_dim: cubecl_core::CubeDim::default(),
+            body: scope,
+            options: Default::default(),
+        };
+        let options = CompilationOptions {
+            max_shared_memory_size,
+            ..Default::default()
+        };
+        CppCompiler::<D>::try_compile(kernel, &options, ExecutionMode::Checked)
+    }
+
+    #[cfg(feature = "pipeline_profiling")]
+    #[test]
+    fn pipeline_phases_are_accumulated_in_the_profile_buffer() {
+        let mut scope = gpu::Scope::root(false);
+        let item = gpu::Item::vectorized(gpu::Elem::Float(gpu::FloatKind::F32), NonZero::new(4));
+        let pipeline = *scope.create_pipeline(item, 2);
+        let source = *scope.create_slice(item);
+        let destination = *scope.create_shared(item, 256);
+        for operation in [
+            gpu::PipelineOps::ProducerAcquire { pipeline },
+            gpu::PipelineOps::MemCopyAsync {
+                pipeline,
+                source,
+                destination,
+            },
+            gpu::PipelineOps::ProducerCommit { pipeline },
+            gpu::PipelineOps::ConsumerWait { pipeline },
+            gpu::PipelineOps::ConsumerRelease { pipeline },
+        ] {
+            scope.register(gpu::Instruction {
+                out: None,
+                operation: gpu::Operation::Pipeline(operation),
+            });
+        }
+
+        let kernel = KernelDefinition {
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            named: Vec::new(),
+            cube_dim: cubecl_core::CubeDim::default(),
+            body: scope,
+            options: Default::default(),
+        };
+        let source = CppCompiler::<D>::compile(kernel, &Default::default(), ExecutionMode::Checked)
+            .to_string();
+
+        assert!(source.contains("__device__ unsigned long long pipeline_profile[3];"));
+        assert_eq!(source.matches("_produce_cycles = 0;").count(), 1);
+        assert_eq!(source.matches("_produce_cycles += clock64()").count(), 1);
+        assert_eq!(source.matches("_wait_cycles += clock64()").count(), 1);
+        assert_eq!(source.matches("_consume_cycles += clock64()").count(), 1);
+        assert_eq!(source.matches("atomicAdd(&pipeline_profile[").count(), 3);
+        assert!(source.contains("atomicAdd(&pipeline_profile[1], (unsigned long long) pipeline_"));
+    }
+
+    #[test]
+    fn pipeline_stages_fitting_in_shared_memory_compile() {
+        let kernel = compile_staged_kernel(4, 256, 49152).unwrap();
+
+        assert_eq!(kernel.shared_memory_size(), 4 * 256 * 16);
+    }
+
+    #[test]
+    fn pipeline_stages_exceeding_shared_memory_are_rejected() {
+        // Each stage takes 16 KiB, so four of them don't fit in 48 KiB of shared memory.
+        let error = compile_staged_kernel(4, 1024, 49152).unwrap_err();
+
+        assert_eq!(
+            error,
+            CompilationError::SharedMemoryExceeded {
+                num_stages: 4,
+                shared_memory_bytes: 65536,
+                max_bytes: 49152,
+            }
+        );
+    }
+
+    #[test]
+    fn pipeline_stages_are_checked_against_the_device_shared_memory() {
+        // Two stages of 16 KiB fit in the default limit, but not on a device with 16 KiB.
+        assert!(compile_staged_kernel(2, 1024, 49152).is_ok());
+        let error = compile_staged_kernel(2, 1024, 16384).unwrap_err();
+
+        assert_eq!(
+            error,
+            CompilationError::SharedMemoryExceeded {
+                num_stages: 2,
+                shared_memory_bytes: 32768,
+                max_bytes: 16384,
+            }
+        );
+    }
+
+    #[test]
+    fn pipeline_without_stages_is_rejected() {
+        let error = compile_staged_kernel(0, 256, 49152).unwrap_err();
+
+        assert_eq!(error, CompilationError::PipelineWithoutStages);
+    }
+}
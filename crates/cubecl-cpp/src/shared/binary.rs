@@ -164,6 +164,51 @@ impl<D: Dialect> Binary<D> for Powf {
     }
 }
 
+pub struct Atan2;
+
+impl<D: Dialect> Binary<D> for Atan2 {
+    // atan2 has no half equivalent
+    fn format_scalar<Lhs: Display, Rhs: Display>(
+        f: &mut std::fmt::Formatter<'_>,
+        lhs: Lhs,
+        rhs: Rhs,
+        item: Item<D>,
+    ) -> std::fmt::Result {
+        let elem = item.elem;
+        match elem {
+            Elem::F16 | Elem::F162 | Elem::BF16 | Elem::BF162 => {
+                write!(f, "{elem}(atan2(float({lhs}), float({rhs})))")
+            }
+            _ => write!(f, "atan2({lhs}, {rhs})"),
+        }
+    }
+
+    fn unroll_vec(
+        f: &mut Formatter<'_>,
+        lhs: &Variable<D>,
+        rhs: &Variable<D>,
+        out: &Variable<D>,
+    ) -> core::fmt::Result {
+        let item_out = out.item();
+        let index = out.item().vectorization;
+
+        let out = out.fmt_left();
+        writeln!(f, "{out} = {item_out}{{")?;
+        for i in 0..index {
+            let lhsi = lhs.index(i);
+            let rhsi = rhs.index(i);
+
+            Self::format_scalar(f, lhsi, rhsi, item_out)?;
+            f.write_str(", ")?;
+        }
+
+        f.write_str(
+            "};
+",
+        )
+    }
+}
+
 pub struct Max;
 
 impl<D: Dialect> Binary<D> for Max {
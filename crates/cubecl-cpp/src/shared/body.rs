@@ -1,4 +1,8 @@
-use super::{pipeline::PipelineOps, Dialect, Instruction, Variable};
+use super::{
+    barrier::BarrierOps,
+    pipeline::{PipelineOps, PipelineTimer},
+    Dialect, Instruction, Variable,
+};
 use std::fmt::Display;
 
 /// A body is composed of a list of [instructions](Instruction).
@@ -7,6 +11,7 @@ pub struct Body<D: Dialect> {
     pub instructions: Vec<Instruction<D>>,
     pub shared_memories: Vec<super::SharedMemory<D>>,
     pub pipelines: Vec<PipelineOps<D>>,
+    pub barriers: Vec<BarrierOps<D>>,
     pub const_arrays: Vec<super::ConstArray<D>>,
     pub local_arrays: Vec<super::LocalArray<D>>,
     pub warp_size_checked: bool,
@@ -99,6 +104,16 @@ impl<D: Dialect> Display for Body<D> {
 
         for pipeline in self.pipelines.iter() {
             writeln!(f, "{pipeline}")?;
+
+            if cfg!(feature = "pipeline_profiling") {
+                if let (_, Some(timer)) = PipelineTimer::around(pipeline) {
+                    write!(f, "{timer}")?;
+                }
+            }
+        }
+
+        for barrier in self.barriers.iter() {
+            writeln!(f, "{barrier}")?;
         }
 
         for const_array in self.const_arrays.iter() {
@@ -132,6 +147,17 @@ impl<D: Dialect> Display for Body<D> {
             write!(f, "{ops}")?;
         }
 
+        if cfg!(feature = "pipeline_profiling") {
+            for (slot, pipeline) in self.pipelines.iter().enumerate() {
+                if let PipelineOps::Init { pipeline, .. } | PipelineOps::InitThread { pipeline } =
+                    pipeline
+                {
+                    let pipeline = *pipeline;
+                    write!(f, "{}", PipelineTimer::Report { pipeline, slot })?;
+                }
+            }
+        }
+
         Ok(())
     }
 }
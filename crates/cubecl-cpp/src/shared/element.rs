@@ -1,4 +1,5 @@
 use cubecl_core::{
+    e4m3, e5m2,
     ir::{self as gpu, ConstantScalarValue, Id},
     tf32,
 };
@@ -9,6 +10,8 @@ use super::{Dialect, Fragment, FragmentIdent, COUNTER_TMP_VAR};
 
 #[derive(Debug, Clone, PartialEq, Eq, Copy, Hash)]
 pub enum Elem<D: Dialect> {
+    E4M3,
+    E5M2,
     TF32,
     F32,
     F64,
@@ -68,6 +71,8 @@ pub struct Item<D: Dialect> {
 impl<D: Dialect> Display for Elem<D> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Elem::E4M3 => D::fp8_e4m3_type_name(f),
+            Elem::E5M2 => D::fp8_e5m2_type_name(f),
             Elem::F16 => f.write_str("__half"),
             Elem::F162 => f.write_str("__half2"),
             Elem::F32 => f.write_str("float"),
@@ -238,6 +243,9 @@ impl<D: Dialect> Display for Variable<D> {
                     gpu::IntKind::I64 => write!(f, "{elem}({})", *val),
                 },
                 ConstantScalarValue::Float(val, kind) => match kind {
+                    gpu::FloatKind::E4M3 | gpu::FloatKind::E5M2 => {
+                        write!(f, "{elem}({:?})", *val as f32)
+                    }
                     gpu::FloatKind::F16 => {
                         write!(f, "{elem}({:?})", half::f16::from_f64(*val))
                     }
@@ -583,6 +591,8 @@ impl<D: Dialect> Item<D> {
 impl<D: Dialect> Elem<D> {
     pub const fn size(&self) -> usize {
         match self {
+            Elem::E4M3 => core::mem::size_of::<e4m3>(),
+            Elem::E5M2 => core::mem::size_of::<e5m2>(),
             Elem::F16 => core::mem::size_of::<f16>(),
             Elem::F162 => 2 * core::mem::size_of::<f16>(),
             Elem::BF162 => 2 * core::mem::size_of::<bf16>(),
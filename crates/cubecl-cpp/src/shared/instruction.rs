@@ -1,8 +1,11 @@
 use crate::shared::FmtLeft;
 
 use super::{
-    binary::*, pipeline::PipelineOps, unary::*, Component, Dialect, Elem, Item, Variable,
-    WarpInstruction, WmmaInstruction,
+    barrier::BarrierOps,
+    binary::*,
+    pipeline::{PipelineOps, PipelineTimer},
+    unary::*,
+    Component, Dialect, Elem, Item, Variable, WarpInstruction, WmmaInstruction,
 };
 use std::{fmt::Display, marker::PhantomData};
 
@@ -64,6 +67,7 @@ pub enum Instruction<D: Dialect> {
         end: Variable<D>,
         step: Option<Variable<D>>,
         inclusive: bool,
+        unroll_factor: Option<u32>,
         instructions: Vec<Self>,
     },
     VecInit {
@@ -124,15 +128,29 @@ pub enum Instruction<D: Dialect> {
     ShiftRight(BinaryInstruction<D>),
     BitwiseNot(UnaryInstruction<D>),
     LeadingZeros(UnaryInstruction<D>),
+    TrailingZeros(UnaryInstruction<D>),
     FindFirstSet(UnaryInstruction<D>),
     Abs(UnaryInstruction<D>),
+    Signum(UnaryInstruction<D>),
     Exp(UnaryInstruction<D>),
     Log(UnaryInstruction<D>),
     Log1p(UnaryInstruction<D>),
     Cos(UnaryInstruction<D>),
     Sin(UnaryInstruction<D>),
     Tanh(UnaryInstruction<D>),
+    Log2(UnaryInstruction<D>),
+    Exp2(UnaryInstruction<D>),
+    Sinh(UnaryInstruction<D>),
+    Cosh(UnaryInstruction<D>),
+    Asinh(UnaryInstruction<D>),
+    Acosh(UnaryInstruction<D>),
+    Atanh(UnaryInstruction<D>),
+    Tan(UnaryInstruction<D>),
+    Asin(UnaryInstruction<D>),
+    Acos(UnaryInstruction<D>),
+    Atan(UnaryInstruction<D>),
     Powf(BinaryInstruction<D>),
+    Atan2(BinaryInstruction<D>),
     Sqrt(UnaryInstruction<D>),
     Min(BinaryInstruction<D>),
     Max(BinaryInstruction<D>),
@@ -150,6 +168,7 @@ pub enum Instruction<D: Dialect> {
     Round(UnaryInstruction<D>),
     Ceil(UnaryInstruction<D>),
     Floor(UnaryInstruction<D>),
+    Trunc(UnaryInstruction<D>),
     Warp(WarpInstruction<D>),
     Wmma(WmmaInstruction<D>),
     Bitcast(UnaryInstruction<D>),
@@ -193,7 +212,13 @@ pub enum Instruction<D: Dialect> {
     Comment {
         content: String,
     },
+    DebugName {
+        var: Variable<D>,
+        name: String,
+    },
     Pipeline(PipelineOps<D>),
+    PipelineTimer(PipelineTimer<D>),
+    Barrier(BarrierOps<D>),
 }
 
 impl<D: Dialect> Display for Instruction<D> {
@@ -240,6 +265,7 @@ impl<D: Dialect> Display for Instruction<D> {
             Instruction::CountBits(it) => CountBits::format(f, &it.input, &it.out),
             Instruction::ReverseBits(it) => ReverseBits::format(f, &it.input, &it.out),
             Instruction::LeadingZeros(it) => LeadingZeros::format(f, &it.input, &it.out),
+            Instruction::TrailingZeros(it) => TrailingZeros::format(f, &it.input, &it.out),
             Instruction::FindFirstSet(it) => FindFirstSet::format(f, &it.input, &it.out),
             Instruction::ShiftLeft(it) => ShiftLeft::format(f, &it.lhs, &it.rhs, &it.out),
             Instruction::ShiftRight(it) => ShiftRight::format(f, &it.lhs, &it.rhs, &it.out),
@@ -287,8 +313,12 @@ impl<D: Dialect> Display for Instruction<D> {
                 end,
                 step,
                 inclusive,
+                unroll_factor,
                 instructions,
             } => {
+                if let Some(factor) = unroll_factor {
+                    write!(f, "\n#pragma unroll {factor}")?;
+                }
                 let increment = step
                     .map(|step| format!("{i} += {step}"))
                     .unwrap_or_else(|| format!("++{i}"));
@@ -425,13 +455,26 @@ for ({i_ty} {i} = {start}; {i} {cmp} {end}; {increment}) {{
             Instruction::GreaterEqual(it) => GreaterEqual::format(f, &it.lhs, &it.rhs, &it.out),
             Instruction::Erf(it) => Erf::format(f, &it.input, &it.out),
             Instruction::Abs(it) => Abs::format(f, &it.input, &it.out),
+            Instruction::Signum(it) => Signum::format(f, &it.input, &it.out),
             Instruction::Exp(it) => Exp::format(f, &it.input, &it.out),
             Instruction::Log(it) => Log::format(f, &it.input, &it.out),
             Instruction::Log1p(it) => Log1p::format(f, &it.input, &it.out),
             Instruction::Cos(it) => Cos::format(f, &it.input, &it.out),
             Instruction::Sin(it) => Sin::format(f, &it.input, &it.out),
             Instruction::Tanh(it) => Tanh::format(f, &it.input, &it.out),
+            Instruction::Log2(it) => Log2::format(f, &it.input, &it.out),
+            Instruction::Exp2(it) => Exp2::format(f, &it.input, &it.out),
+            Instruction::Sinh(it) => Sinh::format(f, &it.input, &it.out),
+            Instruction::Cosh(it) => Cosh::format(f, &it.input, &it.out),
+            Instruction::Asinh(it) => Asinh::format(f, &it.input, &it.out),
+            Instruction::Acosh(it) => Acosh::format(f, &it.input, &it.out),
+            Instruction::Atanh(it) => Atanh::format(f, &it.input, &it.out),
+            Instruction::Tan(it) => Tan::format(f, &it.input, &it.out),
+            Instruction::Asin(it) => Asin::format(f, &it.input, &it.out),
+            Instruction::Acos(it) => Acos::format(f, &it.input, &it.out),
+            Instruction::Atan(it) => Atan::format(f, &it.input, &it.out),
             Instruction::Powf(it) => Powf::format(f, &it.lhs, &it.rhs, &it.out),
+            Instruction::Atan2(it) => Atan2::format(f, &it.lhs, &it.rhs, &it.out),
             Instruction::Sqrt(it) => Sqrt::format(f, &it.input, &it.out),
             Instruction::Max(it) => Max::format(f, &it.lhs, &it.rhs, &it.out),
             Instruction::Min(it) => Min::format(f, &it.lhs, &it.rhs, &it.out),
@@ -450,6 +493,7 @@ for ({i_ty} {i} = {start}; {i} {cmp} {end}; {increment}) {{
             Instruction::Round(it) => Round::format(f, &it.input, &it.out),
             Instruction::Ceil(it) => Ceil::format(f, &it.input, &it.out),
             Instruction::Floor(it) => Floor::format(f, &it.input, &it.out),
+            Instruction::Trunc(it) => Trunc::format(f, &it.input, &it.out),
             Instruction::SliceLength { input, out } => {
                 let out = out.fmt_left();
                 writeln!(f, "{out} = {input}_length;")
@@ -613,7 +657,10 @@ for ({i_ty} {i} = {start}; {i} {cmp} {end}; {increment}) {{
                     writeln!(f, "// {content}")
                 }
             }
+            Instruction::DebugName { var, name } => writeln!(f, "/* {var}: {name} */"),
             Instruction::Pipeline(pipeline_ops) => write!(f, "{pipeline_ops}"),
+            Instruction::PipelineTimer(timer) => write!(f, "{timer}"),
+            Instruction::Barrier(barrier_ops) => write!(f, "{barrier_ops}"),
         }
     }
 }
@@ -639,10 +686,11 @@ impl<D: Dialect> Fma<D> {
     ) -> core::fmt::Result {
         let out_item = out.item();
         let num = out_item.vectorization;
+        let fma = D::fma(out_item.elem);
 
         let out = out.fmt_left();
         if num == 1 {
-            writeln!(f, "{out} = fma({a}, {b}, {c});")
+            writeln!(f, "{out} = {fma}({a}, {b}, {c});")
         } else {
             writeln!(f, "{out} = {out_item}{{")?;
 
@@ -651,7 +699,7 @@ impl<D: Dialect> Fma<D> {
                 let bi = b.index(i);
                 let ci = c.index(i);
 
-                writeln!(f, "fma({ai}, {bi}, {ci}),")?;
+                writeln!(f, "{fma}({ai}, {bi}, {ci}),")?;
             }
             f.write_str("};\n")
         }
@@ -887,3 +935,41 @@ impl<V: Display, D: Dialect> Display for EnsureBoolArg<'_, V, D> {
         }
     }
 }
+
+#[cfg(all(test, feature = "cuda"))]
+mod tests {
+    use super::*;
+    use crate::cuda::{mma::CudaWmmaCompiler, CudaDialect};
+
+    type D = CudaDialect<CudaWmmaCompiler>;
+
+    fn range_loop(unroll_factor: Option<u32>) -> Instruction<D> {
+        let item = Item::new(Elem::U32, 1);
+        Instruction::RangeLoop {
+            i: Variable::LocalMut { id: 0, item },
+            start: Variable::LocalConst { id: 1, item },
+            end: Variable::LocalConst { id: 2, item },
+            step: None,
+            inclusive: false,
+            unroll_factor,
+            instructions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn range_loop_with_unroll_factor_is_preceded_by_pragma() {
+        let source = range_loop(Some(4)).to_string();
+
+        assert!(
+            source.contains("#pragma unroll 4\nfor ("),
+            "Missing pragma in {source}"
+        );
+    }
+
+    #[test]
+    fn range_loop_without_unroll_factor_has_no_pragma() {
+        let source = range_loop(None).to_string();
+
+        assert!(!source.contains("#pragma"), "Unexpected pragma in {source}");
+    }
+}
@@ -1,4 +1,7 @@
-use super::{Body, Dialect, Item, Variable};
+use super::{
+    pipeline::{PIPELINE_PROFILE_BUFFER, PIPELINE_PROFILE_COUNTERS},
+    Body, Dialect, Item, Variable,
+};
 use cubecl_core::{compute::Visibility, ir::Id, CompilerRepresentation, CubeDim};
 use std::{collections::HashSet, fmt::Display};
 
@@ -52,8 +55,10 @@ pub struct ComputeKernel<D: Dialect> {
     pub body: Body<D>,
     pub wmma_activated: bool,
     pub pipeline: bool,
+    pub barrier: bool,
     pub bf16: bool,
     pub f16: bool,
+    pub fp8: bool,
     pub fast_math: bool,
     pub items: HashSet<super::Item<D>>,
     pub kernel_name: String,
@@ -83,6 +88,10 @@ impl<D: Dialect> Display for ComputeKernel<D> {
             D::include_f16(f)?;
         }
 
+        if self.fp8 {
+            D::include_fp8(f)?;
+        }
+
         if self.wmma_activated {
             D::wmma_includes(f)?;
         }
@@ -92,6 +101,11 @@ impl<D: Dialect> Display for ComputeKernel<D> {
             f.write_str("#include <cuda/pipeline>\n")?;
         }
 
+        if self.barrier {
+            f.write_str("#include <cooperative_groups/memcpy_async.h>\n")?;
+            f.write_str("#include <cuda/barrier>\n")?;
+        }
+
         f.write_str("typedef unsigned char uint8;\n")?;
         f.write_str("typedef unsigned short uint16;\n")?;
         f.write_str("typedef unsigned int uint;\n")?;
@@ -122,6 +136,16 @@ struct __align__({alignment}) {item} {{"
             }
         }
 
+        if cfg!(feature = "pipeline_profiling") && !self.body.pipelines.is_empty() {
+            let num_counters = self.body.pipelines.len() * PIPELINE_PROFILE_COUNTERS;
+            write!(
+                f,
+                "
+__device__ unsigned long long {PIPELINE_PROFILE_BUFFER}[{num_counters}];
+"
+            )?;
+        }
+
         write!(
             f,
             "
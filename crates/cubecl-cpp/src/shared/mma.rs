@@ -53,6 +53,9 @@ pub fn register_wmma_features(
     properties: &mut DeviceProperties<Feature>,
 ) {
     for (i, o, c, tdims) in supported_combinations {
+        if matches!(i, gpu::Elem::Int(_) | gpu::Elem::UInt(_)) && !tdims.is_empty() {
+            properties.register_feature(Feature::CmmaInt);
+        }
         for (m, n, k) in tdims {
             properties.register_feature(Feature::Cmma {
                 a: i,
@@ -189,6 +192,8 @@ pub mod wmma_api_base {
     ) -> std::fmt::Result {
         let elem = match fragment.elem {
             Elem::TF32 => format!("{namespace}::precision::tf32"),
+            // Fragments are only specialized for `signed char`, which is distinct from `char`.
+            Elem::I8 => "signed char".to_string(),
             Elem::BF16 => {
                 if fragment.ident == FragmentIdent::Accumulator {
                     format!("{}", Elem::<D>::F16) // Normally not supported except for cast.
@@ -1,6 +1,7 @@
 pub mod binary;
 pub mod unary;
 
+mod barrier;
 mod base;
 mod body;
 mod element;
@@ -10,10 +11,12 @@ mod mma;
 mod pipeline;
 mod warp;
 
+pub use barrier::*;
 pub use base::*;
 pub use body::*;
 pub use element::*;
 pub use instruction::*;
 pub use kernel::*;
 pub use mma::*;
+pub use pipeline::*;
 pub use warp::*;
@@ -1,6 +1,6 @@
 use std::fmt::Display;
 
-use super::{Component, Dialect, Variable};
+use super::{Component, Dialect, Instruction, Variable};
 
 #[derive(Debug, Clone)]
 pub enum PipelineOps<D: Dialect> {
@@ -8,11 +8,26 @@ pub enum PipelineOps<D: Dialect> {
         pipeline: Variable<D>,
         num_stages: u8,
     },
+    /// A pipeline private to the unit, created with `cuda::make_pipeline()`.
+    ///
+    /// It isn't limited to a fixed number of stages, and is required by
+    /// [PipelineOps::ConsumerWaitPrior].
+    InitThread {
+        pipeline: Variable<D>,
+    },
     MemCopyAsync {
         pipeline: Variable<D>,
         source: Variable<D>,
         destination: Variable<D>,
     },
+    /// Same as [PipelineOps::MemCopyAsync], but only copies the first `num_elements` elements of
+    /// the source instead of its whole length.
+    MemCopyAsyncCounted {
+        pipeline: Variable<D>,
+        source: Variable<D>,
+        destination: Variable<D>,
+        num_elements: Variable<D>,
+    },
     ProducerAcquire {
         pipeline: Variable<D>,
     },
@@ -22,6 +37,14 @@ pub enum PipelineOps<D: Dialect> {
     ConsumerWait {
         pipeline: Variable<D>,
     },
+    /// Wait until every committed stage except the `prior` most recent ones has landed, with
+    /// `cuda::pipeline_consumer_wait_prior<prior>`.
+    ///
+    /// The CUDA API only provides it for thread scoped pipelines, see [PipelineOps::InitThread].
+    ConsumerWaitPrior {
+        pipeline: Variable<D>,
+        prior: u8,
+    },
     ConsumerRelease {
         pipeline: Variable<D>,
     },
@@ -31,15 +54,243 @@ impl<D: Dialect> PipelineOps<D> {
     pub fn pipeline_id(&self) -> u32 {
         match self {
             PipelineOps::MemCopyAsync { pipeline, .. } => pipeline.id().unwrap(),
+            PipelineOps::MemCopyAsyncCounted { pipeline, .. } => pipeline.id().unwrap(),
             PipelineOps::Init { pipeline, .. } => pipeline.id().unwrap(),
+            PipelineOps::InitThread { pipeline } => pipeline.id().unwrap(),
             PipelineOps::ProducerAcquire { pipeline } => pipeline.id().unwrap(),
             PipelineOps::ProducerCommit { pipeline } => pipeline.id().unwrap(),
             PipelineOps::ConsumerWait { pipeline } => pipeline.id().unwrap(),
+            PipelineOps::ConsumerWaitPrior { pipeline, .. } => pipeline.id().unwrap(),
             PipelineOps::ConsumerRelease { pipeline } => pipeline.id().unwrap(),
         }
     }
 }
 
+/// Error returned when a [MultiStagePipeline] can't be built.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PipelineError {
+    /// A pipeline needs at least two stages to overlap copies with computation, and at most
+    /// [u8::MAX] stages.
+    InvalidNumStages(usize),
+    /// The buffers of all the stages don't fit in the shared memory available to a cube.
+    SharedMemoryExceeded {
+        num_stages: usize,
+        shared_memory_bytes: usize,
+        max_bytes: usize,
+    },
+}
+
+impl PipelineError {
+    /// Check that the `shared_memory_bytes` bytes of shared memory holding the buffers of the
+    /// `num_stages` stages of a pipeline fit in the `max_bytes` bytes available to a cube.
+    pub fn check_shared_memory(
+        num_stages: usize,
+        shared_memory_bytes: usize,
+        max_bytes: usize,
+    ) -> Result<(), Self> {
+        if shared_memory_bytes > max_bytes {
+            return Err(PipelineError::SharedMemoryExceeded {
+                num_stages,
+                shared_memory_bytes,
+                max_bytes,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Display for PipelineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PipelineError::InvalidNumStages(num_stages) => write!(
+                f,
+                "A pipeline must have between 2 and {} stages, got {num_stages}",
+                u8::MAX
+            ),
+            PipelineError::SharedMemoryExceeded {
+                num_stages,
+                shared_memory_bytes,
+                max_bytes,
+            } => write!(
+                f,
+                "A pipeline with {num_stages} stages needs {shared_memory_bytes} bytes of shared \
+                 memory for its buffers, but only {max_bytes} bytes are available per cube"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PipelineError {}
+
+/// A software pipeline overlapping asynchronous copies of tiles with their consumption.
+///
+/// Each stage owns a buffer, and tiles rotate through the stages: tile `i` is copied into the
+/// buffer of stage `i % num_stages`. The first `num_stages` tiles are copied upfront, then each
+/// tile is waited on, consumed and released, which frees its stage for the copy of tile
+/// `i + num_stages`.
+///
+/// By default, each wait is a `consumer_wait` on a pipeline with `num_stages` stages. With
+/// [wait_prior](MultiStagePipeline::wait_prior), the pipeline is thread scoped and each wait
+/// names how many of the later tiles may still be in flight, which is what
+/// `cuda::pipeline_consumer_wait_prior` expects.
+#[derive(Debug, Clone)]
+pub struct MultiStagePipeline<D: Dialect> {
+    pipeline: Variable<D>,
+    buffers: Vec<Variable<D>>,
+    wait_prior: bool,
+}
+
+impl<D: Dialect> MultiStagePipeline<D> {
+    /// Create a pipeline with one stage per buffer of `buffers`.
+    ///
+    /// Every stage is sized like the largest shared memory of `buffers`, so that a tile fits in
+    /// any of them, and the total must not exceed `max_shared_memory_size` bytes. Checking it
+    /// here gives a clear error instead of a kernel failing to launch.
+    pub fn new(
+        pipeline: Variable<D>,
+        buffers: Vec<Variable<D>>,
+        max_shared_memory_size: usize,
+    ) -> Result<Self, PipelineError> {
+        let num_stages = buffers.len();
+        if num_stages < 2 || num_stages > u8::MAX as usize {
+            return Err(PipelineError::InvalidNumStages(num_stages));
+        }
+
+        let tile_bytes = buffers.iter().map(shared_memory_bytes).max().unwrap_or(0);
+        PipelineError::check_shared_memory(
+            num_stages,
+            num_stages * tile_bytes,
+            max_shared_memory_size,
+        )?;
+
+        Ok(Self {
+            pipeline,
+            buffers,
+            wait_prior: false,
+        })
+    }
+
+    /// Wait on each tile with [PipelineOps::ConsumerWaitPrior], keeping the copies of the
+    /// following tiles in flight.
+    pub fn wait_prior(mut self) -> Self {
+        self.wait_prior = true;
+        self
+    }
+
+    pub fn num_stages(&self) -> u8 {
+        self.buffers.len() as u8
+    }
+
+    /// The declaration of the pipeline, along with its `cuda::pipeline_shared_state` unless it is
+    /// thread scoped.
+    pub fn init(&self) -> PipelineOps<D> {
+        if self.wait_prior {
+            PipelineOps::InitThread {
+                pipeline: self.pipeline,
+            }
+        } else {
+            PipelineOps::Init {
+                pipeline: self.pipeline,
+                num_stages: self.num_stages(),
+            }
+        }
+    }
+
+    /// Emit the copies of all `tiles` along with their consumption.
+    ///
+    /// `consume` is called once per tile, in order, with the index of the tile and the buffer
+    /// holding it, and pushes the instructions using the buffer. The buffer must not be used
+    /// outside of `consume`, since it is overwritten by a later tile.
+    pub fn build(
+        &self,
+        tiles: &[Variable<D>],
+        mut consume: impl FnMut(usize, Variable<D>, &mut Vec<Instruction<D>>),
+    ) -> Vec<Instruction<D>> {
+        let num_stages = self.buffers.len();
+        let mut instructions = Vec::new();
+
+        for (index, source) in tiles.iter().enumerate().take(num_stages) {
+            self.produce(&mut instructions, *source, index);
+        }
+
+        for index in 0..tiles.len() {
+            push_timed(&mut instructions, self.wait(index, tiles.len()));
+            consume(index, self.buffer(index), &mut instructions);
+            push_timed(
+                &mut instructions,
+                PipelineOps::ConsumerRelease {
+                    pipeline: self.pipeline,
+                },
+            );
+
+            if let Some(source) = tiles.get(index + num_stages) {
+                self.produce(&mut instructions, *source, index + num_stages);
+            }
+        }
+
+        instructions
+    }
+
+    fn wait(&self, tile: usize, num_tiles: usize) -> PipelineOps<D> {
+        let pipeline = self.pipeline;
+
+        if !self.wait_prior {
+            return PipelineOps::ConsumerWait { pipeline };
+        }
+
+        // Tiles up to `tile + num_stages - 1` are committed at this point, only the ones after
+        // `tile` may stay in flight.
+        let committed = usize::min(tile + self.buffers.len(), num_tiles);
+        PipelineOps::ConsumerWaitPrior {
+            pipeline,
+            prior: (committed - tile - 1) as u8,
+        }
+    }
+
+    fn buffer(&self, tile: usize) -> Variable<D> {
+        self.buffers[tile % self.buffers.len()]
+    }
+
+    fn produce(&self, instructions: &mut Vec<Instruction<D>>, source: Variable<D>, tile: usize) {
+        let pipeline = self.pipeline;
+        push_timed(instructions, PipelineOps::ProducerAcquire { pipeline });
+        push_timed(
+            instructions,
+            PipelineOps::MemCopyAsync {
+                pipeline,
+                source,
+                destination: self.buffer(tile),
+            },
+        );
+        push_timed(instructions, PipelineOps::ProducerCommit { pipeline });
+    }
+}
+
+/// Push a pipeline operation, along with the [timers](PipelineTimer) measuring the phase it
+/// starts or ends when the `pipeline_profiling` feature is enabled.
+pub fn push_timed<D: Dialect>(instructions: &mut Vec<Instruction<D>>, op: PipelineOps<D>) {
+    if !cfg!(feature = "pipeline_profiling") {
+        instructions.push(Instruction::Pipeline(op));
+        return;
+    }
+
+    let (before, after) = PipelineTimer::around(&op);
+    instructions.extend(before.map(Instruction::PipelineTimer));
+    instructions.push(Instruction::Pipeline(op));
+    instructions.extend(after.map(Instruction::PipelineTimer));
+}
+
+/// The size in bytes of a shared memory buffer, or zero for variables that aren't shared
+/// memories, since they don't take any space in it.
+pub(super) fn shared_memory_bytes<D: Dialect>(buffer: &Variable<D>) -> usize {
+    match buffer {
+        Variable::SharedMemory(_, item, length) => {
+            item.elem.size() * item.vectorization * *length as usize
+        }
+        _ => 0,
+    }
+}
+
 impl<D: Dialect> Display for PipelineOps<D> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -54,6 +305,18 @@ impl<D: Dialect> Display for PipelineOps<D> {
 cuda::memcpy_async(cooperative_groups::this_thread(), {destination}, {source}, {source}_length * {size}, {pipeline});
                 ")
             }
+            PipelineOps::MemCopyAsyncCounted {
+                pipeline,
+                source,
+                destination,
+                num_elements,
+            } => {
+                let item = source.item();
+                let size = item.elem().size() * item.vectorization;
+                write!(f, "
+cuda::memcpy_async(cooperative_groups::this_thread(), {destination}, {source}, {num_elements} * {size}, {pipeline});
+                ")
+            }
             PipelineOps::Init {
                 pipeline,
                 num_stages,
@@ -66,6 +329,14 @@ auto {pipeline} = cuda::make_pipeline(cooperative_groups::this_thread(), &{pipel
                 "
                 )
             }
+            PipelineOps::InitThread { pipeline } => {
+                write!(
+                    f,
+                    "
+auto {pipeline} = cuda::make_pipeline();
+                "
+                )
+            }
             PipelineOps::ProducerAcquire { pipeline } => {
                 write!(
                     f,
@@ -87,6 +358,14 @@ auto {pipeline} = cuda::make_pipeline(cooperative_groups::this_thread(), &{pipel
                     f,
                     "
 {pipeline}.consumer_wait();
+            "
+                )
+            }
+            PipelineOps::ConsumerWaitPrior { pipeline, prior } => {
+                write!(
+                    f,
+                    "
+cuda::pipeline_consumer_wait_prior<{prior}>({pipeline});
             "
                 )
             }
@@ -101,3 +380,357 @@ auto {pipeline} = cuda::make_pipeline(cooperative_groups::this_thread(), &{pipel
         }
     }
 }
+
+/// The name of the `__device__` buffer the phases of the pipelines are accumulated in with the
+/// `pipeline_profiling` feature.
+///
+/// It holds three counters per pipeline, in the order the kernel declares them: the cycles spent
+/// producing, waiting and consuming, summed over all the units. The runtime reads it back after
+/// each launch.
+pub const PIPELINE_PROFILE_BUFFER: &str = "pipeline_profile";
+
+/// The number of counters each pipeline has in [PIPELINE_PROFILE_BUFFER].
+pub const PIPELINE_PROFILE_COUNTERS: usize = 3;
+
+/// The `clock64()` timers measuring the phases of a pipeline with the `pipeline_profiling`
+/// feature.
+///
+/// Copies are produced from `producer_acquire` to `producer_commit`, then waited on until the end
+/// of `consumer_wait`, and consumed until `consumer_release`. A kernel spending most of its
+/// cycles waiting is load-bound.
+#[derive(Debug, Clone)]
+pub enum PipelineTimer<D: Dialect> {
+    /// Declare the counters of the unit, after the declaration of the pipeline.
+    Init { pipeline: Variable<D> },
+    /// Start timing a phase.
+    Start { pipeline: Variable<D> },
+    /// End the production of a stage.
+    Produced { pipeline: Variable<D> },
+    /// End the wait on a stage, which starts its consumption.
+    Waited { pipeline: Variable<D> },
+    /// End the consumption of a stage.
+    Consumed { pipeline: Variable<D> },
+    /// Add the counters of the unit to the ones of the pipeline at `slot` in
+    /// [PIPELINE_PROFILE_BUFFER], at the end of the kernel.
+    Report { pipeline: Variable<D>, slot: usize },
+}
+
+impl<D: Dialect> PipelineTimer<D> {
+    /// The timers going before and after `op`.
+    pub fn around(op: &PipelineOps<D>) -> (Option<Self>, Option<Self>) {
+        match *op {
+            PipelineOps::Init { pipeline, .. } | PipelineOps::InitThread { pipeline } => {
+                (None, Some(PipelineTimer::Init { pipeline }))
+            }
+            PipelineOps::ProducerAcquire { pipeline } => {
+                (Some(PipelineTimer::Start { pipeline }), None)
+            }
+            PipelineOps::ProducerCommit { pipeline } => {
+                (None, Some(PipelineTimer::Produced { pipeline }))
+            }
+            PipelineOps::ConsumerWait { pipeline }
+            | PipelineOps::ConsumerWaitPrior { pipeline, .. } => (
+                Some(PipelineTimer::Start { pipeline }),
+                Some(PipelineTimer::Waited { pipeline }),
+            ),
+            PipelineOps::ConsumerRelease { pipeline } => {
+                (Some(PipelineTimer::Consumed { pipeline }), None)
+            }
+            PipelineOps::MemCopyAsync { .. } | PipelineOps::MemCopyAsyncCounted { .. } => {
+                (None, None)
+            }
+        }
+    }
+}
+
+impl<D: Dialect> Display for PipelineTimer<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PipelineTimer::Init { pipeline } => write!(
+                f,
+                "
+long long {pipeline}_produce_cycles = 0;
+long long {pipeline}_wait_cycles = 0;
+long long {pipeline}_consume_cycles = 0;
+long long {pipeline}_phase_start = 0;
+"
+            ),
+            PipelineTimer::Start { pipeline } => write!(
+                f,
+                "
+{pipeline}_phase_start = clock64();
+"
+            ),
+            PipelineTimer::Produced { pipeline } => write!(
+                f,
+                "
+{pipeline}_produce_cycles += clock64() - {pipeline}_phase_start;
+"
+            ),
+            PipelineTimer::Waited { pipeline } => write!(
+                f,
+                "
+{pipeline}_wait_cycles += clock64() - {pipeline}_phase_start;
+{pipeline}_phase_start = clock64();
+"
+            ),
+            PipelineTimer::Consumed { pipeline } => write!(
+                f,
+                "
+{pipeline}_consume_cycles += clock64() - {pipeline}_phase_start;
+"
+            ),
+            PipelineTimer::Report { pipeline, slot } => {
+                let offset = slot * PIPELINE_PROFILE_COUNTERS;
+                write!(
+                    f,
+                    "
+atomicAdd(&{PIPELINE_PROFILE_BUFFER}[{}], (unsigned long long) {pipeline}_produce_cycles);
+atomicAdd(&{PIPELINE_PROFILE_BUFFER}[{}], (unsigned long long) {pipeline}_wait_cycles);
+atomicAdd(&{PIPELINE_PROFILE_BUFFER}[{}], (unsigned long long) {pipeline}_consume_cycles);
+",
+                    offset,
+                    offset + 1,
+                    offset + 2
+                )
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "cuda"))]
+mod tests {
+    use cubecl_core::Compiler;
+
+    use super::*;
+    use crate::{
+        cuda::{mma::CudaWmmaCompiler, CudaDialect},
+        shared::{Elem, Item},
+        CudaCompiler,
+    };
+
+    type D = CudaDialect<CudaWmmaCompiler>;
+
+    fn stages(num_stages: u32, tile_elems: u32) -> (Variable<D>, Vec<Variable<D>>) {
+        let item = Item::new(Elem::F32, 4);
+        let pipeline = Variable::Pipeline { id: 0, item };
+        let buffers = (1..=num_stages)
+            .map(|id| Variable::SharedMemory(id, item, tile_elems))
+            .collect();
+        (pipeline, buffers)
+    }
+
+    #[test]
+    fn pipeline_fitting_in_shared_memory_is_accepted() {
+        let (pipeline, buffers) = stages(4, 256);
+        let max_bytes = CudaCompiler::max_shared_memory_size();
+
+        let pipeline = MultiStagePipeline::new(pipeline, buffers, max_bytes).unwrap();
+
+        assert_eq!(pipeline.num_stages(), 4);
+    }
+
+    #[test]
+    fn pipeline_exceeding_shared_memory_is_rejected() {
+        // Each tile takes 16 KiB, so four of them don't fit in 48 KiB.
+        let (pipeline, buffers) = stages(4, 1024);
+        let max_bytes = CudaCompiler::max_shared_memory_size();
+
+        let error = MultiStagePipeline::new(pipeline, buffers, max_bytes).unwrap_err();
+
+        assert_eq!(
+            error,
+            PipelineError::SharedMemoryExceeded {
+                num_stages: 4,
+                shared_memory_bytes: 64 * 1024,
+                max_bytes,
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "A pipeline with 4 stages needs 65536 bytes of shared memory for its buffers, \
+                 but only {max_bytes} bytes are available per cube"
+            )
+        );
+    }
+
+    #[test]
+    fn pipeline_without_stages_is_rejected() {
+        let (pipeline, buffers) = stages(0, 256);
+
+        let error = MultiStagePipeline::new(pipeline, buffers, usize::MAX).unwrap_err();
+
+        assert_eq!(error, PipelineError::InvalidNumStages(0));
+    }
+
+    #[test]
+    fn pipeline_ops_match_the_cuda_api() {
+        let (pipeline, buffers) = stages(1, 256);
+        let item = Item::new(Elem::F32, 4);
+        let source = Variable::Slice { id: 10, item };
+        let num_elements = Variable::LocalConst {
+            id: 11,
+            item: Item::new(Elem::U32, 1),
+        };
+        let ops = [
+            PipelineOps::Init {
+                pipeline,
+                num_stages: 3,
+            },
+            PipelineOps::InitThread { pipeline },
+            PipelineOps::ProducerAcquire { pipeline },
+            PipelineOps::MemCopyAsync {
+                pipeline,
+                source,
+                destination: buffers[0],
+            },
+            PipelineOps::MemCopyAsyncCounted {
+                pipeline,
+                source,
+                destination: buffers[0],
+                num_elements,
+            },
+            PipelineOps::ProducerCommit { pipeline },
+            PipelineOps::ConsumerWait { pipeline },
+            PipelineOps::ConsumerWaitPrior { pipeline, prior: 2 },
+            PipelineOps::ConsumerRelease { pipeline },
+        ];
+
+        let source = ops.iter().map(|op| op.to_string()).collect::<String>();
+        let lines = source
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+
+        assert!(ops.iter().all(|op| op.pipeline_id() == 0));
+        assert_eq!(
+            lines,
+            [
+                "cuda::pipeline_shared_state<cuda::thread_scope::thread_scope_block, 3> pipeline_0_state;",
+                "auto pipeline_0 = cuda::make_pipeline(cooperative_groups::this_thread(), &pipeline_0_state);",
+                "auto pipeline_0 = cuda::make_pipeline();",
+                "pipeline_0.producer_acquire();",
+                "cuda::memcpy_async(cooperative_groups::this_thread(), shared_memory_1, slice_10, slice_10_length * 16, pipeline_0);",
+                "cuda::memcpy_async(cooperative_groups::this_thread(), shared_memory_1, slice_10, l_11 * 16, pipeline_0);",
+                "pipeline_0.producer_commit();",
+                "pipeline_0.consumer_wait();",
+                "cuda::pipeline_consumer_wait_prior<2>(pipeline_0);",
+                "pipeline_0.consumer_release();",
+            ]
+        );
+    }
+
+    /// The lines of the pipeline declaration followed by the copies and consumption of three
+    /// tiles rotating through two stages.
+    fn build_three_tiles(pipeline: MultiStagePipeline<D>) -> Vec<String> {
+        let item = Item::new(Elem::F32, 4);
+        let tiles = [10, 11, 12].map(|id| Variable::Slice { id, item });
+
+        let instructions = pipeline.build(&tiles, |index, buffer, instructions| {
+            instructions.push(Instruction::Comment {
+                content: format!("consume tile {index} from {buffer}"),
+            })
+        });
+
+        core::iter::once(pipeline.init().to_string())
+            .chain(
+                instructions
+                    .iter()
+                    .map(|instruction| instruction.to_string()),
+            )
+            .collect::<String>()
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[cfg(not(feature = "pipeline_profiling"))]
+    #[test]
+    fn pipeline_rotates_tiles_through_stages() {
+        let (pipeline, buffers) = stages(2, 256);
+        let pipeline = MultiStagePipeline::new(pipeline, buffers, usize::MAX).unwrap();
+
+        assert_eq!(
+            build_three_tiles(pipeline),
+            [
+                "cuda::pipeline_shared_state<cuda::thread_scope::thread_scope_block, 2> pipeline_0_state;",
+                "auto pipeline_0 = cuda::make_pipeline(cooperative_groups::this_thread(), &pipeline_0_state);",
+                "pipeline_0.producer_acquire();",
+                "cuda::memcpy_async(cooperative_groups::this_thread(), shared_memory_1, slice_10, slice_10_length * 16, pipeline_0);",
+                "pipeline_0.producer_commit();",
+                "pipeline_0.producer_acquire();",
+                "cuda::memcpy_async(cooperative_groups::this_thread(), shared_memory_2, slice_11, slice_11_length * 16, pipeline_0);",
+                "pipeline_0.producer_commit();",
+                "pipeline_0.consumer_wait();",
+                "// consume tile 0 from shared_memory_1",
+                "pipeline_0.consumer_release();",
+                "pipeline_0.producer_acquire();",
+                "cuda::memcpy_async(cooperative_groups::this_thread(), shared_memory_1, slice_12, slice_12_length * 16, pipeline_0);",
+                "pipeline_0.producer_commit();",
+                "pipeline_0.consumer_wait();",
+                "// consume tile 1 from shared_memory_2",
+                "pipeline_0.consumer_release();",
+                "pipeline_0.consumer_wait();",
+                "// consume tile 2 from shared_memory_1",
+                "pipeline_0.consumer_release();",
+            ]
+        );
+    }
+
+    #[cfg(not(feature = "pipeline_profiling"))]
+    #[test]
+    fn pipeline_waits_on_prior_stages() {
+        let (pipeline, buffers) = stages(2, 256);
+        let pipeline = MultiStagePipeline::new(pipeline, buffers, usize::MAX)
+            .unwrap()
+            .wait_prior();
+
+        assert_eq!(
+            build_three_tiles(pipeline),
+            [
+                "auto pipeline_0 = cuda::make_pipeline();",
+                "pipeline_0.producer_acquire();",
+                "cuda::memcpy_async(cooperative_groups::this_thread(), shared_memory_1, slice_10, slice_10_length * 16, pipeline_0);",
+                "pipeline_0.producer_commit();",
+                "pipeline_0.producer_acquire();",
+                "cuda::memcpy_async(cooperative_groups::this_thread(), shared_memory_2, slice_11, slice_11_length * 16, pipeline_0);",
+                "pipeline_0.producer_commit();",
+                "cuda::pipeline_consumer_wait_prior<1>(pipeline_0);",
+                "// consume tile 0 from shared_memory_1",
+                "pipeline_0.consumer_release();",
+                "pipeline_0.producer_acquire();",
+                "cuda::memcpy_async(cooperative_groups::this_thread(), shared_memory_1, slice_12, slice_12_length * 16, pipeline_0);",
+                "pipeline_0.producer_commit();",
+                "cuda::pipeline_consumer_wait_prior<1>(pipeline_0);",
+                "// consume tile 1 from shared_memory_2",
+                "pipeline_0.consumer_release();",
+                "cuda::pipeline_consumer_wait_prior<0>(pipeline_0);",
+                "// consume tile 2 from shared_memory_1",
+                "pipeline_0.consumer_release();",
+            ]
+        );
+    }
+
+    #[cfg(feature = "pipeline_profiling")]
+    #[test]
+    fn pipeline_phases_are_timed_when_profiling() {
+        let (pipeline, buffers) = stages(2, 256);
+        let tiles = [Variable::GlobalInputArray(0, Item::new(Elem::F32, 4)); 3];
+        let pipeline = MultiStagePipeline::new(pipeline, buffers, usize::MAX).unwrap();
+
+        let source = pipeline
+            .build(&tiles, |_, _, _| {})
+            .iter()
+            .map(|instruction| instruction.to_string())
+            .collect::<String>();
+
+        assert_eq!(source.matches("_phase_start = clock64()").count(), 9);
+        assert_eq!(source.matches("_produce_cycles += clock64()").count(), 3);
+        assert_eq!(source.matches("_wait_cycles += clock64()").count(), 3);
+        assert_eq!(source.matches("_consume_cycles += clock64()").count(), 3);
+    }
+}
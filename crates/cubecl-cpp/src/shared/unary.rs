@@ -154,11 +154,66 @@ function!(Sqrt, "sqrt");
 function!(Exp, "exp");
 function!(Ceil, "ceil");
 function!(Floor, "floor");
+function!(Trunc, "trunc");
 function!(Round, "rint");
 
 function!(Tanh, "tanh", false);
+function!(Log2, "log2");
+function!(Exp2, "exp2");
+function!(Sinh, "sinh", false);
+function!(Cosh, "cosh", false);
+function!(Asinh, "asinh", false);
+function!(Acosh, "acosh", false);
+function!(Atanh, "atanh", false);
+function!(Tan, "tan", false);
+function!(Asin, "asin", false);
+function!(Acos, "acos", false);
+function!(Atan, "atan", false);
 function!(Erf, "erf", false);
-function!(Abs, "abs", false);
+
+pub struct Abs;
+
+impl<D: Dialect> FunctionFmt<D> for Abs {
+    fn base_function_name() -> &'static str {
+        "abs"
+    }
+    fn half_support() -> bool {
+        false
+    }
+}
+
+impl<D: Dialect> Unary<D> for Abs {
+    fn format_scalar<Input: Component<D>>(
+        f: &mut std::fmt::Formatter<'_>,
+        input: Input,
+        elem: Elem<D>,
+    ) -> std::fmt::Result {
+        match elem {
+            // `abs` is ambiguous on unsigned integers, and a no-op anyway
+            Elem::U8 | Elem::U16 | Elem::U32 | Elem::U64 => write!(f, "{input}"),
+            _ => Self::format_unary(f, input, elem),
+        }
+    }
+
+    fn can_optimize() -> bool {
+        false
+    }
+}
+
+pub struct Signum;
+
+impl<D: Dialect> Unary<D> for Signum {
+    fn format_scalar<Input: Component<D>>(
+        f: &mut std::fmt::Formatter<'_>,
+        input: Input,
+        elem: Elem<D>,
+    ) -> std::fmt::Result {
+        match elem {
+            Elem::U8 | Elem::U16 | Elem::U32 | Elem::U64 => write!(f, "{elem}({input} != 0)"),
+            _ => write!(f, "{elem}(({input} > 0) - ({input} < 0))"),
+        }
+    }
+}
 
 fn zero_extend<D: Dialect>(input: impl Component<D>) -> String {
     match input.elem() {
@@ -228,6 +283,28 @@ impl<D: Dialect> Unary<D> for LeadingZeros {
     }
 }
 
+pub struct TrailingZeros;
+
+impl<D: Dialect> Unary<D> for TrailingZeros {
+    fn format_scalar<Input: Component<D>>(
+        f: &mut std::fmt::Formatter<'_>,
+        input: Input,
+        _elem: Elem<D>,
+    ) -> std::fmt::Result {
+        // Leading zeros of the reversed bits, which correctly yields the bit width for zero
+        match input.elem() {
+            Elem::I32 | Elem::U32 => write!(f, "__clz(__brev({input}))"),
+            Elem::I64 | Elem::U64 => write!(f, "__clzll(__brevll({input}))"),
+            elem => write!(
+                f,
+                "min(__clz(__brev({})), {})",
+                zero_extend(input),
+                elem.size() * 8
+            ),
+        }
+    }
+}
+
 pub struct FindFirstSet;
 
 impl<D: Dialect> Unary<D> for FindFirstSet {
@@ -1,5 +1,8 @@
 use cubecl_cpp::{
-    cuda::arch::CudaArchitecture, formatter::format_cpp, shared::CompilationOptions, CudaCompiler,
+    cuda::arch::CudaArchitecture,
+    formatter::format_cpp,
+    shared::{CompilationOptions, PIPELINE_PROFILE_BUFFER, PIPELINE_PROFILE_COUNTERS},
+    CudaCompiler,
 };
 
 use super::fence::{Fence, SyncStream};
@@ -18,10 +21,12 @@ use cubecl_runtime::{
 use cubecl_runtime::{TimestampsError, TimestampsResult};
 use cudarc::driver::sys::CUctx_st;
 use cudarc::driver::sys::CUfunc_st;
+use cudarc::driver::sys::{CUdeviceptr, CUmodule};
 use std::collections::HashMap;
 use std::ffi::CStr;
 use std::ffi::CString;
 use std::future::Future;
+use std::mem::MaybeUninit;
 use std::path::PathBuf;
 use std::time::Instant;
 
@@ -69,6 +74,9 @@ struct CompiledKernel {
     cube_dim: CubeDim,
     shared_mem_bytes: usize,
     func: *mut CUfunc_st,
+    /// The address and size in bytes of the buffer the phases of the pipelines are accumulated
+    /// in, with the `pipeline_profiling` feature.
+    pipeline_profile: Option<(CUdeviceptr, usize)>,
 }
 
 unsafe impl Send for CudaServer {}
@@ -379,10 +387,11 @@ impl CudaContext {
         };
 
         let func_name = CString::new(kernel_compiled.entrypoint_name).unwrap();
-        let func = unsafe {
+        let (func, pipeline_profile) = unsafe {
             let module =
                 cudarc::driver::result::module::load_data(ptx.as_ptr() as *const _).unwrap();
-            cudarc::driver::result::module::get_function(module, func_name).unwrap()
+            let func = cudarc::driver::result::module::get_function(module, func_name).unwrap();
+            (func, find_pipeline_profile(module))
         };
 
         self.module_names.insert(
@@ -391,6 +400,7 @@ impl CudaContext {
                 cube_dim,
                 shared_mem_bytes,
                 func,
+                pipeline_profile,
             },
         );
     }
@@ -419,6 +429,33 @@ impl CudaContext {
             )
             .unwrap();
         };
+
+        if let Some(profile) = kernel.pipeline_profile {
+            self.report_pipeline_profile(&kernel_id, profile);
+        }
+    }
+
+    /// Read back the cycles spent in each phase of the pipelines of the kernel that just ran,
+    /// summed over all its units, and reset them for the next launch.
+    fn report_pipeline_profile(&mut self, kernel_id: &KernelId, profile: (CUdeviceptr, usize)) {
+        let (ptr, size) = profile;
+        let mut counters = vec![0u64; size / std::mem::size_of::<u64>()];
+
+        unsafe {
+            cudarc::driver::result::memcpy_dtoh_async(&mut counters, ptr, self.stream).unwrap();
+            cudarc::driver::result::memset_d8_async(ptr, 0, size, self.stream).unwrap();
+        };
+        self.sync();
+
+        for (pipeline, phases) in counters.chunks(PIPELINE_PROFILE_COUNTERS).enumerate() {
+            log::info!(
+                "Pipeline {pipeline} of {kernel_id}: produce {} cycles, wait {} cycles, consume {} \
+                 cycles, summed over all units",
+                phases[0],
+                phases[1],
+                phases[2]
+            );
+        }
     }
 
     fn memory_usage(&self) -> MemoryUsage {
@@ -448,6 +485,31 @@ impl CudaServer {
     }
 }
 
+/// Find the buffer a kernel compiled with the `pipeline_profiling` feature accumulates the phases
+/// of its pipelines in, see [PIPELINE_PROFILE_BUFFER]. Kernels without pipelines don't have one.
+unsafe fn find_pipeline_profile(module: CUmodule) -> Option<(CUdeviceptr, usize)> {
+    if !cfg!(feature = "pipeline_profiling") {
+        return None;
+    }
+
+    let name = CString::new(PIPELINE_PROFILE_BUFFER).unwrap();
+    let mut ptr = MaybeUninit::uninit();
+    let mut size = MaybeUninit::uninit();
+    let result = cudarc::driver::sys::lib().cuModuleGetGlobal_v2(
+        ptr.as_mut_ptr(),
+        size.as_mut_ptr(),
+        module,
+        name.as_ptr(),
+    );
+
+    match result {
+        cudarc::driver::sys::CUresult::CUDA_SUCCESS => {
+            Some((ptr.assume_init(), size.assume_init()))
+        }
+        _ => None,
+    }
+}
+
 fn include_path() -> PathBuf {
     let mut path = cuda_path().expect("
         CUDA installation not found.
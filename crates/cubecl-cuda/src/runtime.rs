@@ -19,7 +19,7 @@ use crate::{
 use cubecl_cpp::{
     cuda::{arch::CudaArchitecture, mma::CudaWmmaCompiler},
     register_supported_types,
-    shared::register_wmma_features,
+    shared::{register_wmma_features, AsyncCopyLowering, CompilationOptions},
     CudaCompiler, WmmaCompiler,
 };
 
@@ -28,6 +28,9 @@ use cubecl_cpp::{
 pub struct RuntimeOptions {
     /// Configures the memory management.
     pub memory_config: MemoryConfiguration,
+    /// How the asynchronous copies of pipelines are lowered. Pipelines are used unless barriers
+    /// are opted into, which track the copies in hardware starting with Hopper.
+    pub async_copy_lowering: AsyncCopyLowering,
 }
 
 #[derive(Debug)]
@@ -78,17 +81,28 @@ fn create_client(device: &CudaDevice, options: RuntimeOptions) -> ComputeClient<
         alignment: CudaStorage::ALIGNMENT,
     };
 
-    let warp_size = unsafe {
-        cudarc::driver::result::device::get_attribute(
-            device_ptr,
-            cudarc::driver::sys::CUdevice_attribute::CU_DEVICE_ATTRIBUTE_WARP_SIZE,
-        )
-        .unwrap()
+    use cudarc::driver::sys::CUdevice_attribute::*;
+    let attribute = |attr| unsafe {
+        cudarc::driver::result::device::get_attribute(device_ptr, attr).unwrap() as u32
     };
+
+    let warp_size = attribute(CU_DEVICE_ATTRIBUTE_WARP_SIZE);
     let hardware_props = HardwareProperties {
-        plane_size_min: warp_size as u32,
-        plane_size_max: warp_size as u32,
+        plane_size_min: warp_size,
+        plane_size_max: warp_size,
         max_bindings: crate::device::CUDA_MAX_BINDINGS,
+        max_shared_memory_size: attribute(CU_DEVICE_ATTRIBUTE_MAX_SHARED_MEMORY_PER_BLOCK) as usize,
+        max_cube_count: (
+            attribute(CU_DEVICE_ATTRIBUTE_MAX_GRID_DIM_X),
+            attribute(CU_DEVICE_ATTRIBUTE_MAX_GRID_DIM_Y),
+            attribute(CU_DEVICE_ATTRIBUTE_MAX_GRID_DIM_Z),
+        ),
+        max_cube_dim: (
+            attribute(CU_DEVICE_ATTRIBUTE_MAX_BLOCK_DIM_X),
+            attribute(CU_DEVICE_ATTRIBUTE_MAX_BLOCK_DIM_Y),
+            attribute(CU_DEVICE_ATTRIBUTE_MAX_BLOCK_DIM_Z),
+        ),
+        max_units_per_cube: attribute(CU_DEVICE_ATTRIBUTE_MAX_THREADS_PER_BLOCK),
     };
 
     let memory_management =
@@ -104,6 +118,11 @@ fn create_client(device: &CudaDevice, options: RuntimeOptions) -> ComputeClient<
         device_props.register_feature(Feature::Type(Elem::AtomicFloat(FloatKind::F16)));
         device_props.register_feature(Feature::Pipeline);
     }
+    // FP8 conversions and tensor core instructions are native starting with Ada Lovelace.
+    if arch.version >= 89 {
+        device_props.register_feature(Feature::Type(Elem::Float(FloatKind::E4M3)));
+        device_props.register_feature(Feature::Type(Elem::Float(FloatKind::E5M2)));
+    }
     // NOTE: I commented that since I observed synchronisation issues with atomic add for bf16.
     // if arch.version >= 80 {
     //     device_props.register_feature(Feature::Type(Elem::AtomicFloat(FloatKind::BF16)));
@@ -114,7 +133,11 @@ fn create_client(device: &CudaDevice, options: RuntimeOptions) -> ComputeClient<
     device_props.register_feature(Feature::AtomicFloat(AtomicFeature::LoadStore));
     device_props.register_feature(Feature::AtomicFloat(AtomicFeature::Add));
 
-    let comp_opts = Default::default();
+    let comp_opts = CompilationOptions {
+        async_copy_lowering: options.async_copy_lowering,
+        max_shared_memory_size: device_props.hardware_properties().max_shared_memory_size,
+        ..Default::default()
+    };
     let cuda_ctx = CudaContext::new(memory_management, comp_opts, stream, ctx, arch);
     let server = CudaServer::new(cuda_ctx);
     ComputeClient::new(MutexComputeChannel::new(server), device_props)
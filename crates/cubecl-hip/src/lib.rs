@@ -30,5 +30,8 @@ mod tests {
     cubecl_linalg::testgen_matmul_plane!([f32]);
     cubecl_linalg::testgen_matmul_accelerated!([f32]);
     cubecl_linalg::testgen_tensor_identity!([f32, u32]);
+    cubecl_linalg::testgen_tensor_transpose!([f32, u32]);
+    cubecl_linalg::testgen_tensor_handle!([f32, u32]);
+    cubecl_linalg::testgen_tensor_layout!([f32, u32]);
     cubecl_reduce::testgen_reduce!([f16, bf16, f32, f64]);
 }
@@ -51,6 +51,14 @@ fn create_client<M: WmmaCompiler<HipDialect<M>>>(
     let mut prop_warp_size = 0;
     #[allow(unused_assignments)]
     let mut prop_arch_name = "";
+    #[allow(unused_assignments)]
+    let mut prop_max_shared_memory_size = 0;
+    #[allow(unused_assignments)]
+    let mut prop_max_grid_size = [0; 3];
+    #[allow(unused_assignments)]
+    let mut prop_max_threads_dim = [0; 3];
+    #[allow(unused_assignments)]
+    let mut prop_max_threads_per_block = 0;
     unsafe {
         let mut ll_device_props = MaybeUninit::uninit();
         let status = cubecl_hip_sys::hipGetDevicePropertiesR0600(
@@ -60,6 +68,10 @@ fn create_client<M: WmmaCompiler<HipDialect<M>>>(
         assert_eq!(status, HIP_SUCCESS, "Should get device properties");
         let ll_device_props = ll_device_props.assume_init();
         prop_warp_size = ll_device_props.warpSize;
+        prop_max_shared_memory_size = ll_device_props.sharedMemPerBlock;
+        prop_max_grid_size = ll_device_props.maxGridSize;
+        prop_max_threads_dim = ll_device_props.maxThreadsDim;
+        prop_max_threads_per_block = ll_device_props.maxThreadsPerBlock;
         prop_arch_name = CStr::from_ptr(ll_device_props.gcnArchName.as_ptr())
             .to_str()
             .unwrap();
@@ -107,6 +119,18 @@ fn create_client<M: WmmaCompiler<HipDialect<M>>>(
         // This is a guess - not clear if ROCM has a limit on the number of bindings,
         // but it's dubious it's more than this.
         max_bindings: 1024,
+        max_shared_memory_size: prop_max_shared_memory_size,
+        max_cube_count: (
+            prop_max_grid_size[0] as u32,
+            prop_max_grid_size[1] as u32,
+            prop_max_grid_size[2] as u32,
+        ),
+        max_cube_dim: (
+            prop_max_threads_dim[0] as u32,
+            prop_max_threads_dim[1] as u32,
+            prop_max_threads_dim[2] as u32,
+        ),
+        max_units_per_cube: prop_max_threads_per_block as u32,
     };
     let memory_management =
         MemoryManagement::from_configuration(storage, &mem_properties, options.memory_config);
@@ -125,6 +149,8 @@ fn create_client<M: WmmaCompiler<HipDialect<M>>>(
 
     let comp_opts = CompilationOptions {
         warp_size: arch.warp_size(),
+        max_shared_memory_size: prop_max_shared_memory_size,
+        ..Default::default()
     };
     let hip_ctx = HipContext::new(memory_management, comp_opts, stream);
     let server = HipServer::new(hip_ctx);
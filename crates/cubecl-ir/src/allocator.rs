@@ -1,10 +1,28 @@
 use alloc::{rc::Rc, vec::Vec};
-use core::cell::RefCell;
+use core::{cell::RefCell, fmt::Display};
 
 use hashbrown::HashMap;
-use portable_atomic::{AtomicU32, Ordering};
+use portable_atomic::{AtomicU32, AtomicUsize, Ordering};
 
-use super::{Item, Matrix, Variable, VariableKind};
+use super::{Id, Item, Matrix, Variable, VariableKind};
+
+/// The length given to [Allocator::create_local_array_dynamic] is only known at runtime.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuntimeArrayLength {
+    /// The variable holding the length.
+    pub length: Variable,
+}
+
+impl Display for RuntimeArrayLength {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Local arrays need a length known when compiling the kernel, got the runtime value {}. \
+             Use a comptime upper bound instead.",
+            self.length
+        )
+    }
+}
 
 /// An allocator for local variables of a kernel.
 ///
@@ -24,12 +42,100 @@ use super::{Item, Matrix, Variable, VariableKind};
 /// That is, each variable must be declared and used exactly once.
 ///
 /// [static single-assignment](https://en.wikipedia.org/wiki/Static_single-assignment_form)
+///
+/// # Debug names
+///
+/// Local variables can be given a name with the `*_named` variants of the scope, e.g.
+/// [Scope::create_local_named](crate::Scope::create_local_named). Names are only a debugging aid:
+/// they are registered as a [NonSemantic::DebugName](crate::NonSemantic::DebugName) instruction,
+/// which the compilers only emit when debug symbols are enabled.
+///
+/// # Bounded reuse
+///
+/// By default, a mutable variable can be reused anywhere in the kernel once it isn't referenced
+/// anymore. Reuse can be restricted to a block with [Allocator::scope]: mutable variables created
+/// while the returned guard is alive are never handed out again once it is dropped.
+///
+/// # Pool cap
+///
+/// The pool of an item grows with the number of mutable variables alive at the same time. On
+/// register-starved kernels, [Allocator::with_max_pool] sets a soft cap on the size of each
+/// pool, trading the reuse bounds for fewer declared variables once it is reached.
+///
+/// # Shared memory
+///
+/// Shared memories created with [Allocator::create_shared] are counted, so that the
+/// [total size](Allocator::shared_bytes) of a kernel can be compared with the limit of the device
+/// once its expansion is done, instead of failing when it's compiled or launched.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, Default, TypeHash)]
 pub struct Allocator {
     #[cfg_attr(feature = "serde", serde(skip))]
     local_mut_pool: Rc<RefCell<HashMap<Item, Vec<ExpandElement>>>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    local_mut_retired: Rc<RefCell<Vec<ExpandElement>>>,
     next_id: Rc<AtomicU32>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    stats: Rc<RefCell<AllocatorStats>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    max_pool: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    shared_bytes: Rc<AtomicUsize>,
+}
+
+/// Allocation counters of an [Allocator], useful to diagnose register pressure.
+///
+/// The counters are shared by every scope using the same allocator, so they describe the whole
+/// kernel once its expansion is done.
+#[derive(Clone, Debug, Default, PartialEq, Eq, TypeHash)]
+pub struct AllocatorStats {
+    /// Number of immutable local variables created.
+    pub locals: u32,
+    /// Number of mutable local variables that reused a free variable of the pool.
+    pub locals_mut_reused: u32,
+    /// Number of mutable local variables that were newly added to the pool.
+    pub locals_mut_allocated: u32,
+    /// Number of restricted mutable local variables created, which are never reused.
+    pub locals_restricted: u32,
+    /// The largest number of mutable variables held by the pool for each item.
+    pub peak_pool_size: HashMap<Item, usize>,
+    /// Variables added to a pool that was already at the [cap](Allocator::with_max_pool).
+    pub spills: Vec<SpillEvent>,
+}
+
+/// A mutable variable that had to be added to the pool of its item past the
+/// [cap](Allocator::with_max_pool), because all the variables of the pool were in use.
+#[derive(Clone, Debug, PartialEq, Eq, TypeHash)]
+pub struct SpillEvent {
+    /// The item of the variable.
+    pub item: Item,
+    /// The size of the pool once the variable was added.
+    pub pool_size: usize,
+}
+
+/// Guard returned by [Allocator::scope].
+///
+/// On drop, the mutable variables created since the guard was made are removed from the reuse
+/// pool. They are still declared by the kernel, but their live range stays within the block.
+#[must_use = "reuse is only bounded until the guard is dropped"]
+pub struct AllocatorScope {
+    allocator: Allocator,
+    first_id: Id,
+}
+
+impl Drop for AllocatorScope {
+    fn drop(&mut self) {
+        let mut pool = self.allocator.local_mut_pool.borrow_mut();
+        let mut retired = self.allocator.local_mut_retired.borrow_mut();
+
+        for variables in pool.values_mut() {
+            let (scoped, kept): (Vec<_>, Vec<_>) = core::mem::take(variables)
+                .into_iter()
+                .partition(|var| var.index().is_some_and(|id| id >= self.first_id));
+            *variables = kept;
+            retired.extend(scoped);
+        }
+    }
 }
 
 impl PartialEq for Allocator {
@@ -41,8 +147,24 @@ impl PartialEq for Allocator {
 impl Eq for Allocator {}
 
 impl Allocator {
+    /// Cap the pool of mutable variables of each item to `limit` variables.
+    ///
+    /// Once the pool of an item is full, [Allocator::create_local_mut] also reuses the free
+    /// variables retired by an [AllocatorScope], so their live range isn't bounded to their block
+    /// anymore. When none is free either, a new variable is declared anyway, since all the
+    /// variables of the pool are alive, and a [SpillEvent] is recorded in the
+    /// [stats](Allocator::stats). Variables are never reused across items, even of the same size,
+    /// since a variable keeps its declared type in the generated code.
+    ///
+    /// Without a cap, the pools are unbounded.
+    pub fn with_max_pool(mut self, limit: usize) -> Self {
+        self.max_pool = Some(limit);
+        self
+    }
+
     /// Create a new immutable local variable of type specified by `item`.
     pub fn create_local(&self, item: Item) -> ExpandElement {
+        self.stats.borrow_mut().locals += 1;
         let id = self.new_local_index();
         let local = VariableKind::LocalConst { id };
         ExpandElement::Plain(Variable::new(local, item))
@@ -56,12 +178,24 @@ impl Allocator {
             self.create_local_restricted(item)
         } else {
             self.reuse_local_mut(item)
+                .or_else(|| self.reuse_retired_local_mut(item))
                 .unwrap_or_else(|| ExpandElement::Managed(self.add_local_mut(item)))
         }
     }
 
+    /// Create a new mutable local variable of type specified by `item`, without reusing a
+    /// variable of the pool. It is still added to the pool and can be reused once dropped.
+    pub fn create_local_mut_unpooled(&self, item: Item) -> ExpandElement {
+        if item.elem.is_atomic() {
+            self.create_local_restricted(item)
+        } else {
+            ExpandElement::Managed(self.add_local_mut(item))
+        }
+    }
+
     /// Create a new mutable restricted local variable of type specified by `item`.
     pub fn create_local_restricted(&self, item: Item) -> ExpandElement {
+        self.stats.borrow_mut().locals_restricted += 1;
         let id = self.new_local_index();
         let local = VariableKind::LocalMut { id };
         ExpandElement::Plain(Variable::new(local, item))
@@ -79,6 +213,41 @@ impl Allocator {
         ExpandElement::Plain(local_array)
     }
 
+    /// Create a local array of type specified by `item` whose length is given by a variable.
+    ///
+    /// No runtime supports local arrays sized at launch time: CUDA and HIP don't allow variable
+    /// length arrays in device code, and WGSL and SPIR-V need array lengths that are known when
+    /// compiling the kernel. A `length` folded to a constant is therefore lowered exactly like
+    /// [Allocator::create_local_array], on every runtime.
+    ///
+    /// A `length` that isn't a constant is rejected with a [RuntimeArrayLength] error, and no
+    /// array is created. Kernels that need a scratch array bounded by a runtime value should
+    /// allocate it with a comptime upper bound and only use the first `length` elements.
+    pub fn create_local_array_dynamic(
+        &self,
+        item: Item,
+        length: Variable,
+    ) -> Result<ExpandElement, RuntimeArrayLength> {
+        match length.as_const().and_then(|length| length.try_as_u32()) {
+            Some(length) => Ok(self.create_local_array(item, length)),
+            None => Err(RuntimeArrayLength { length }),
+        }
+    }
+
+    /// Create a shared memory of `size` elements of type specified by `item`.
+    ///
+    /// The shared memory isn't declared by the kernel on its own: it must also be registered in
+    /// the scope, which [Scope::create_shared](crate::Scope::create_shared) does.
+    pub fn create_shared(&self, item: Item, size: u32) -> ExpandElement {
+        let id = self.new_local_index();
+        let bytes = item.elem.size() * item.vectorization.map_or(1, |v| v.get() as usize);
+        self.shared_bytes
+            .fetch_add(bytes * size as usize, Ordering::AcqRel);
+
+        let variable = Variable::new(VariableKind::SharedMemory { id, length: size }, item);
+        ExpandElement::Plain(variable)
+    }
+
     /// Create a slice variable
     pub fn create_slice(&self, item: Item) -> ExpandElement {
         let id = self.new_local_index();
@@ -113,12 +282,32 @@ impl Allocator {
     pub fn reuse_local_mut(&self, item: Item) -> Option<ExpandElement> {
         // Among the candidates, take a variable if it's only referenced by the pool.
         // Arbitrarily takes the first it finds in reversed order.
-        self.local_mut_pool.borrow().get(&item).and_then(|vars| {
+        let reused = self.local_mut_pool.borrow().get(&item).and_then(|vars| {
             vars.iter()
                 .rev()
                 .find(|var| matches!(var, ExpandElement::Managed(v) if Rc::strong_count(v) == 1))
                 .cloned()
-        })
+        });
+        if reused.is_some() {
+            self.stats.borrow_mut().locals_mut_reused += 1;
+        }
+        reused
+    }
+
+    // Try to return a free retired variable for the given `item` when its pool is at the cap, or
+    // `None` otherwise. The variable stays retired, so it's only reused past the cap.
+    fn reuse_retired_local_mut(&self, item: Item) -> Option<ExpandElement> {
+        let limit = self.max_pool?;
+        let pool_size = self.local_mut_pool.borrow().get(&item).map_or(0, Vec::len);
+        if pool_size < limit {
+            return None;
+        }
+
+        let reused = self.local_mut_retired.borrow().iter().find(|var| {
+            matches!(var, ExpandElement::Managed(v) if v.item == item && Rc::strong_count(v) == 1)
+        })?.clone();
+        self.stats.borrow_mut().locals_mut_reused += 1;
+        Some(reused)
     }
 
     /// Add a new variable to the pool with type specified by `item` for the given `scope`.
@@ -130,18 +319,65 @@ impl Allocator {
         let mut pool = self.local_mut_pool.borrow_mut();
         let variables = pool.entry(item).or_default();
         variables.push(expand);
+
+        let mut stats = self.stats.borrow_mut();
+        stats.locals_mut_allocated += 1;
+        let peak = stats.peak_pool_size.entry(item).or_default();
+        *peak = (*peak).max(variables.len());
+        if self.max_pool.is_some_and(|limit| variables.len() > limit) {
+            stats.spills.push(SpillEvent {
+                item,
+                pool_size: variables.len(),
+            });
+        }
+
         var
     }
 
+    /// A snapshot of the allocation counters, see [AllocatorStats].
+    pub fn stats(&self) -> AllocatorStats {
+        self.stats.borrow().clone()
+    }
+
+    /// The variables added past the [cap](Allocator::with_max_pool) of their pool, in the order
+    /// they were declared. Always empty without a cap.
+    pub fn spills(&self) -> Vec<SpillEvent> {
+        self.stats.borrow().spills.clone()
+    }
+
+    /// The total size in bytes of the shared memories created with [Allocator::create_shared].
+    ///
+    /// A kernel is only valid if this doesn't exceed the maximal shared memory size of the
+    /// device it is launched on, as reported by the hardware properties of its client.
+    pub fn shared_bytes(&self) -> usize {
+        self.shared_bytes.load(Ordering::Acquire)
+    }
+
+    /// Bound the reuse of the mutable variables created until the returned guard is dropped.
+    ///
+    /// Variables from before the guard can still be reused inside of it.
+    pub fn scope(&self) -> AllocatorScope {
+        AllocatorScope {
+            allocator: self.clone(),
+            first_id: self.next_id.load(Ordering::Acquire),
+        }
+    }
+
     pub fn new_local_index(&self) -> u32 {
         self.next_id.fetch_add(1, Ordering::Release)
     }
 
     pub fn take_variables(&self) -> Vec<Variable> {
+        let retired = self
+            .local_mut_retired
+            .borrow_mut()
+            .drain(..)
+            .collect::<Vec<_>>();
         self.local_mut_pool
             .borrow_mut()
             .drain()
             .flat_map(|it| it.1)
+            .chain(retired)
             .map(|it| *it)
             .collect()
     }
@@ -151,7 +387,7 @@ use cubecl_macros_internal::TypeHash;
 pub use expand_element::*;
 
 mod expand_element {
-    use cubecl_common::{flex32, tf32};
+    use cubecl_common::{e4m3, e5m2, flex32, tf32};
     use half::{bf16, f16};
 
     use super::*;
@@ -222,6 +458,8 @@ mod expand_element {
     impl_into_expand_element!(u64);
     impl_into_expand_element!(usize);
     impl_into_expand_element!(bool);
+    impl_into_expand_element!(e4m3);
+    impl_into_expand_element!(e5m2);
     impl_into_expand_element!(flex32);
     impl_into_expand_element!(f16);
     impl_into_expand_element!(bf16);
@@ -232,3 +470,57 @@ mod expand_element {
     impl_into_expand_element!(i32);
     impl_into_expand_element!(i64);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Elem, FloatKind};
+
+    fn item() -> Item {
+        Item::new(Elem::Float(FloatKind::F32))
+    }
+
+    /// Creates a variable in a block bounded by [Allocator::scope] while `kept` is alive, then
+    /// returns the ids of the scoped variable and of the one created after the block.
+    fn ids_around_scope(allocator: &Allocator) -> (Option<Id>, Option<Id>) {
+        let _kept = allocator.create_local_mut(item());
+        let scoped = {
+            let _guard = allocator.scope();
+            allocator.create_local_mut(item()).index()
+        };
+        (scoped, allocator.create_local_mut(item()).index())
+    }
+
+    #[test]
+    fn without_cap_scoped_variables_are_not_reused() {
+        let allocator = Allocator::default();
+        let (scoped, after) = ids_around_scope(&allocator);
+
+        assert_ne!(scoped, after);
+        assert_eq!(allocator.stats().locals_mut_allocated, 3);
+        assert!(allocator.spills().is_empty());
+    }
+
+    #[test]
+    fn past_the_cap_retired_variables_are_reused() {
+        let allocator = Allocator::default().with_max_pool(1);
+        let (scoped, after) = ids_around_scope(&allocator);
+
+        assert_eq!(scoped, after);
+        assert_eq!(allocator.stats().locals_mut_allocated, 2);
+        assert_eq!(allocator.stats().locals_mut_reused, 1);
+    }
+
+    #[test]
+    fn variables_past_the_cap_are_recorded_as_spills() {
+        let allocator = Allocator::default().with_max_pool(2);
+        let _alive: Vec<_> = (0..4).map(|_| allocator.create_local_mut(item())).collect();
+
+        let spill = |pool_size| SpillEvent {
+            item: item(),
+            pool_size,
+        };
+        assert_eq!(allocator.spills(), vec![spill(3), spill(4)]);
+        assert_eq!(allocator.stats().peak_pool_size[&item()], 4);
+    }
+}
@@ -17,16 +17,30 @@ pub enum Arithmetic {
     Mul(BinaryOperator),
     Div(BinaryOperator),
     Abs(UnaryOperator),
+    Signum(UnaryOperator),
     Exp(UnaryOperator),
     Log(UnaryOperator),
     Log1p(UnaryOperator),
     Cos(UnaryOperator),
     Sin(UnaryOperator),
     Tanh(UnaryOperator),
+    Log2(UnaryOperator),
+    Exp2(UnaryOperator),
+    Sinh(UnaryOperator),
+    Cosh(UnaryOperator),
+    Asinh(UnaryOperator),
+    Acosh(UnaryOperator),
+    Atanh(UnaryOperator),
+    Tan(UnaryOperator),
+    Asin(UnaryOperator),
+    Acos(UnaryOperator),
+    Atan(UnaryOperator),
     Powf(BinaryOperator),
+    Atan2(BinaryOperator),
     Sqrt(UnaryOperator),
     Round(UnaryOperator),
     Floor(UnaryOperator),
+    Trunc(UnaryOperator),
     Ceil(UnaryOperator),
     Erf(UnaryOperator),
     Recip(UnaryOperator),
@@ -53,16 +67,30 @@ impl Display for Arithmetic {
             Arithmetic::Mul(op) => write!(f, "{} * {}", op.lhs, op.rhs),
             Arithmetic::Div(op) => write!(f, "{} / {}", op.lhs, op.rhs),
             Arithmetic::Abs(op) => write!(f, "{}.abs()", op.input),
+            Arithmetic::Signum(op) => write!(f, "{}.signum()", op.input),
             Arithmetic::Exp(op) => write!(f, "{}.exp()", op.input),
             Arithmetic::Log(op) => write!(f, "{}.log()", op.input),
             Arithmetic::Log1p(op) => write!(f, "{}.log_1p()", op.input),
             Arithmetic::Cos(op) => write!(f, "{}.cos()", op.input),
             Arithmetic::Sin(op) => write!(f, "{}.sin()", op.input),
             Arithmetic::Tanh(op) => write!(f, "{}.tanh()", op.input),
+            Arithmetic::Log2(op) => write!(f, "{}.log2()", op.input),
+            Arithmetic::Exp2(op) => write!(f, "{}.exp2()", op.input),
+            Arithmetic::Sinh(op) => write!(f, "{}.sinh()", op.input),
+            Arithmetic::Cosh(op) => write!(f, "{}.cosh()", op.input),
+            Arithmetic::Asinh(op) => write!(f, "{}.asinh()", op.input),
+            Arithmetic::Acosh(op) => write!(f, "{}.acosh()", op.input),
+            Arithmetic::Atanh(op) => write!(f, "{}.atanh()", op.input),
+            Arithmetic::Tan(op) => write!(f, "{}.tan()", op.input),
+            Arithmetic::Asin(op) => write!(f, "{}.asin()", op.input),
+            Arithmetic::Acos(op) => write!(f, "{}.acos()", op.input),
+            Arithmetic::Atan(op) => write!(f, "{}.atan()", op.input),
             Arithmetic::Powf(op) => write!(f, "{}.pow({})", op.lhs, op.rhs),
+            Arithmetic::Atan2(op) => write!(f, "{}.atan2({})", op.lhs, op.rhs),
             Arithmetic::Sqrt(op) => write!(f, "{}.sqrt()", op.input),
             Arithmetic::Round(op) => write!(f, "{}.round()", op.input),
             Arithmetic::Floor(op) => write!(f, "{}.floor()", op.input),
+            Arithmetic::Trunc(op) => write!(f, "{}.trunc()", op.input),
             Arithmetic::Ceil(op) => write!(f, "{}.ceil()", op.input),
             Arithmetic::Erf(op) => write!(f, "{}.erf()", op.input),
             Arithmetic::Recip(op) => write!(f, "{}.recip()", op.input),
@@ -102,6 +102,9 @@ pub struct RangeLoop {
     pub step: Option<Variable>,
     pub inclusive: bool,
     pub scope: Scope,
+    /// Hint for the backend to unroll the loop by this factor, which is ignored by backends that
+    /// can't express it. `None` lets the backend decide.
+    pub unroll_factor: Option<u32>,
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -171,6 +174,7 @@ impl RangeLoop {
             step,
             scope,
             inclusive,
+            unroll_factor: None,
         })));
     }
 }
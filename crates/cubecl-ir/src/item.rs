@@ -2,7 +2,7 @@ use super::{ConstantScalarValue, Variable, VariableKind};
 use crate::TypeHash;
 use core::fmt::Display;
 use core::num::NonZero;
-use cubecl_common::{flex32, tf32};
+use cubecl_common::{e4m3, e5m2, flex32, tf32};
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, Copy, TypeHash, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -14,6 +14,8 @@ pub enum FloatKind {
     F32,
     TF32,
     F64,
+    E4M3,
+    E5M2,
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -125,6 +127,8 @@ impl Elem {
     pub const fn size(&self) -> usize {
         match self {
             Elem::Float(kind) | Elem::AtomicFloat(kind) => match kind {
+                FloatKind::E4M3 => core::mem::size_of::<e4m3>(),
+                FloatKind::E5M2 => core::mem::size_of::<e5m2>(),
                 FloatKind::F16 => core::mem::size_of::<half::f16>(),
                 FloatKind::BF16 => core::mem::size_of::<half::bf16>(),
                 FloatKind::F32 => core::mem::size_of::<f32>(),
@@ -165,6 +169,8 @@ impl Elem {
     pub fn max_variable(&self) -> Variable {
         let value = match self {
             Elem::Float(kind) | Elem::AtomicFloat(kind) => match kind {
+                FloatKind::E4M3 => ConstantScalarValue::Float(e4m3::MAX.to_f64(), FloatKind::E4M3),
+                FloatKind::E5M2 => ConstantScalarValue::Float(e5m2::MAX.to_f64(), FloatKind::E5M2),
                 FloatKind::F16 => {
                     ConstantScalarValue::Float(half::f16::MAX.to_f64(), FloatKind::F16)
                 }
@@ -197,6 +203,8 @@ impl Elem {
     pub fn min_variable(&self) -> Variable {
         let value = match self {
             Elem::Float(kind) | Elem::AtomicFloat(kind) => match kind {
+                FloatKind::E4M3 => ConstantScalarValue::Float(e4m3::MIN.to_f64(), FloatKind::E4M3),
+                FloatKind::E5M2 => ConstantScalarValue::Float(e5m2::MIN.to_f64(), FloatKind::E5M2),
                 FloatKind::F16 => {
                     ConstantScalarValue::Float(half::f16::MIN.to_f64(), FloatKind::F16)
                 }
@@ -237,6 +245,8 @@ impl Display for Elem {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::Float(kind) => match kind {
+                FloatKind::E4M3 => f.write_str("e4m3"),
+                FloatKind::E5M2 => f.write_str("e5m2"),
                 FloatKind::F16 => f.write_str("f16"),
                 FloatKind::BF16 => f.write_str("bf16"),
                 FloatKind::Flex32 => f.write_str("flex32"),
@@ -344,6 +354,18 @@ impl From<i64> for Variable {
     }
 }
 
+impl From<e4m3> for Variable {
+    fn from(value: e4m3) -> Self {
+        Variable::constant(ConstantScalarValue::Float(value.to_f64(), FloatKind::E4M3))
+    }
+}
+
+impl From<e5m2> for Variable {
+    fn from(value: e5m2) -> Self {
+        Variable::constant(ConstantScalarValue::Float(value.to_f64(), FloatKind::E5M2))
+    }
+}
+
 impl From<half::f16> for Variable {
     fn from(value: half::f16) -> Self {
         Variable::constant(ConstantScalarValue::Float(value.to_f64(), FloatKind::F16))
@@ -39,6 +39,11 @@ pub enum NonSemantic {
     Comment {
         content: String,
     },
+    /// Name a local variable in the generated code.
+    DebugName {
+        var: Variable,
+        name: String,
+    },
 }
 
 impl OperationReflect for NonSemantic {
@@ -1,12 +1,12 @@
-use alloc::{rc::Rc, vec::Vec};
+use alloc::{rc::Rc, string::ToString, vec::Vec};
 use core::{any::TypeId, cell::RefCell};
 use hashbrown::HashMap;
 
 use crate::{ExpandElement, Matrix, TypeHash};
 
 use super::{
-    processing::ScopeProcessing, Allocator, Elem, Id, Instruction, Item, Operation, UIntKind,
-    Variable, VariableKind,
+    processing::ScopeProcessing, Allocator, Branch, Elem, Id, Instruction, Item, NonSemantic,
+    Operation, OperationReflect, RuntimeArrayLength, UIntKind, Variable, VariableKind,
 };
 
 /// The scope is the main [operation](Operation) and [variable](Variable) container that simplify
@@ -36,6 +36,11 @@ pub struct Scope {
     pub layout_ref: Option<Variable>,
     pub allocator: Allocator,
     pub debug_enabled: bool,
+    /// Lower float divisions to a multiplication by the reciprocal of the divisor.
+    pub allow_reciprocal: bool,
+    /// Array of atomic flags whose first element is set to one when a checked integer operation
+    /// overflows.
+    pub overflow_flag: Option<Variable>,
     #[type_hash(skip)]
     #[cfg_attr(feature = "serde", serde(skip))]
     pub typemap: Rc<RefCell<HashMap<TypeId, Elem>>>,
@@ -94,6 +99,8 @@ impl Scope {
             layout_ref: None,
             allocator: Allocator::default(),
             debug_enabled,
+            allow_reciprocal: false,
+            overflow_flag: None,
             typemap: Default::default(),
         }
     }
@@ -154,6 +161,37 @@ impl Scope {
         self.allocator.create_local(item)
     }
 
+    /// Create a new immutable variable, annotated with `name` in the generated code.
+    pub fn create_local_named(&mut self, item: Item, name: &str) -> ExpandElement {
+        let local = self.allocator.create_local(item);
+        self.register_debug_name(*local, name);
+        local
+    }
+
+    /// Create a new mutable variable, annotated with `name` in the generated code.
+    ///
+    /// A new variable is always defined so that the name isn't attached to a variable declared
+    /// for another purpose.
+    pub fn create_local_mut_named<I: Into<Item>>(&mut self, item: I, name: &str) -> ExpandElement {
+        let local = self.allocator.create_local_mut_unpooled(item.into());
+        self.register_debug_name(*local, name);
+        local
+    }
+
+    /// Create a new restricted variable, annotated with `name` in the generated code.
+    pub fn create_local_restricted_named(&mut self, item: Item, name: &str) -> ExpandElement {
+        let local = self.allocator.create_local_restricted(item);
+        self.register_debug_name(*local, name);
+        local
+    }
+
+    fn register_debug_name(&mut self, var: Variable, name: &str) {
+        self.register(NonSemantic::DebugName {
+            var,
+            name: name.to_string(),
+        });
+    }
+
     /// Reads an input array to a local variable.
     ///
     /// The index refers to the argument position of the array in the compute shader.
@@ -274,6 +312,8 @@ impl Scope {
             layout_ref: self.layout_ref,
             allocator: self.allocator.clone(),
             debug_enabled: self.debug_enabled,
+            allow_reciprocal: self.allow_reciprocal,
+            overflow_flag: self.overflow_flag,
             typemap: self.typemap.clone(),
         }
     }
@@ -314,6 +354,43 @@ impl Scope {
         .optimize()
     }
 
+    /// Find the element type of a variable of the scope or of its nested scopes that matches the
+    /// predicate.
+    pub fn find_elem(&self, predicate: &impl Fn(Elem) -> bool) -> Option<Elem> {
+        let found = |var: &Variable| predicate(var.item.elem).then_some(var.item.elem);
+        let variables = self
+            .locals
+            .iter()
+            .chain(self.const_arrays.iter().map(|(var, _)| var))
+            .chain(self.shared_memories.iter())
+            .chain(self.local_arrays.iter());
+        if let Some(elem) = variables.filter_map(found).next() {
+            return Some(elem);
+        }
+
+        self.operations.iter().find_map(|instruction| {
+            let args = instruction.operation.args().unwrap_or_default();
+            if let Some(elem) = instruction.out.iter().chain(args.iter()).find_map(found) {
+                return Some(elem);
+            }
+            match &instruction.operation {
+                Operation::Branch(Branch::If(op)) => op.scope.find_elem(predicate),
+                Operation::Branch(Branch::IfElse(op)) => op
+                    .scope_if
+                    .find_elem(predicate)
+                    .or_else(|| op.scope_else.find_elem(predicate)),
+                Operation::Branch(Branch::Switch(op)) => op
+                    .cases
+                    .iter()
+                    .find_map(|(_, scope)| scope.find_elem(predicate))
+                    .or_else(|| op.scope_default.find_elem(predicate)),
+                Operation::Branch(Branch::RangeLoop(op)) => op.scope.find_elem(predicate),
+                Operation::Branch(Branch::Loop(op)) => op.scope.find_elem(predicate),
+                _ => None,
+            }
+        })
+    }
+
     pub fn new_local_index(&self) -> u32 {
         self.allocator.new_local_index()
     }
@@ -346,17 +423,11 @@ impl Scope {
         item: I,
         shared_memory_size: u32,
     ) -> ExpandElement {
-        let item = item.into();
-        let index = self.new_local_index();
-        let shared_memory = Variable::new(
-            VariableKind::SharedMemory {
-                id: index,
-                length: shared_memory_size,
-            },
-            item,
-        );
-        self.shared_memories.push(shared_memory);
-        ExpandElement::Plain(shared_memory)
+        let shared_memory = self
+            .allocator
+            .create_shared(item.into(), shared_memory_size);
+        self.shared_memories.push(*shared_memory);
+        shared_memory
     }
 
     /// Create a shared variable of the given [item type](Item).
@@ -408,6 +479,22 @@ impl Scope {
         local_array
     }
 
+    /// Create a local array of the given [item type](Item) whose length is given by a variable,
+    /// see [Allocator::create_local_array_dynamic].
+    ///
+    /// Nothing is added to the scope when the length is only known at runtime.
+    pub fn create_local_array_dynamic<I: Into<Item>>(
+        &mut self,
+        item: I,
+        length: Variable,
+    ) -> Result<ExpandElement, RuntimeArrayLength> {
+        let local_array = self
+            .allocator
+            .create_local_array_dynamic(item.into(), length)?;
+        self.add_local_array(*local_array);
+        Ok(local_array)
+    }
+
     pub fn add_local_array(&mut self, var: Variable) {
         self.local_arrays.push(var);
     }
@@ -386,6 +386,8 @@ impl Display for ConstantScalarValue {
             ConstantScalarValue::Int(val, IntKind::I16) => write!(f, "{val}i16"),
             ConstantScalarValue::Int(val, IntKind::I32) => write!(f, "{val}i32"),
             ConstantScalarValue::Int(val, IntKind::I64) => write!(f, "{val}i64"),
+            ConstantScalarValue::Float(val, FloatKind::E4M3) => write!(f, "{val}e4m3"),
+            ConstantScalarValue::Float(val, FloatKind::E5M2) => write!(f, "{val}e5m2"),
             ConstantScalarValue::Float(val, FloatKind::BF16) => write!(f, "{val}bf16"),
             ConstantScalarValue::Float(val, FloatKind::F16) => write!(f, "{val}f16"),
             ConstantScalarValue::Float(val, FloatKind::TF32) => write!(f, "{val}tf32"),
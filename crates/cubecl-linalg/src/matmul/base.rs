@@ -1,35 +1,111 @@
+use core::any::TypeId;
 use cubecl_core::{
     client::ComputeClient,
-    prelude::{Float, Numeric, TensorHandleRef},
-    Runtime,
+    prelude::{CubeCount, CubeDim, Float, Numeric, TensorHandleRef},
+    Feature, Runtime,
 };
+use cubecl_runtime::DeviceProperties;
 
-use crate::tensor::TensorHandle;
+use crate::tensor::{matrix_layout, MatrixLayout, TensorHandle};
 
 use super::{
-    components::tile::accelerated::Accelerated,
+    components::{
+        self, global::EpilogueOp, tile::accelerated::Accelerated, MatmulProblem, MatmulSelection,
+    },
     kernels::{
-        matmul::{self, PipelinedSelector, SpecializedSelector, StandardSelector},
-        simple,
+        matmul::{
+            self, accelerated_supported, stage_elem, PipelinedSelector, SelectedKernel,
+            SpecializedDoubleBufferedSelector, SpecializedSelector, StandardPrefetchingSelector,
+            StandardSelector, StandardSwizzledSelector,
+        },
+        simple, split_k,
         tiling2d::{self, Tiling2dConfig},
-        MatmulLaunchError,
+        MatmulAvailabilityError, MatmulContext, MatmulLaunchError,
     },
+    tune,
 };
 
 #[derive(Debug, Clone, Default)]
 pub enum Strategy {
     Standard,
+    /// Standard matmul with the stages of the output assigned to the cubes in a swizzled order,
+    /// so that cubes running together share more of their inputs in the L2 cache.
+    StandardSwizzled,
+    /// Standard matmul where the next `depth - 1` stages along k are copied asynchronously while
+    /// the current one is computed, through a pipeline of 2 to 4 stages.
+    ///
+    /// Falls back on [Strategy::Standard] when the device can't copy asynchronously.
+    StandardPrefetching {
+        depth: u32,
+    },
     Pipelined,
     Specialized,
+    /// Specialized matmul where the loads of the producers overlap with the computation
+    /// through a 2-stage pipeline.
+    ///
+    /// Falls back on [Strategy::Specialized] when the device can't copy asynchronously.
+    SpecializedDoubleBuffered,
+    /// Standard matmul with the k dimension split into `splits` partial matmuls.
+    SplitK {
+        splits: u32,
+    },
     #[cfg(any(test, feature = "export_tests"))]
     // Very slow, only use for testing.
     PlaneMma,
     Simple,
+    /// Simple matmul computing `out = alpha * lhs @ rhs + beta * out`.
+    SimpleScaled {
+        alpha: f32,
+        beta: f32,
+    },
     Tiling2D(Tiling2dConfig),
+    /// Benchmark the other strategies the first time a problem is seen and reuse the fastest.
+    ///
+    /// See [clear_autotune_cache](super::clear_autotune_cache) to reset the cached choices.
+    Autotune,
+    /// [Strategy::Standard] with cmma, or [Strategy::Tiling2D] when the device has no cmma
+    /// instruction for the element types or the accelerated kernel is unavailable.
     #[default]
     Auto,
 }
 
+/// How a matmul was configured, as returned by [launch_ref_with_report].
+#[derive(Debug, Clone)]
+pub struct MatmulReport {
+    /// The strategy that launched the kernel.
+    ///
+    /// For [Strategy::Auto], this is the strategy it settled on.
+    pub strategy: Strategy,
+    /// Tile and stage sizes picked by the selector, along with the plane dimension.
+    ///
+    /// Only the strategies built on a [MatmulSelector](matmul::MatmulSelector) have one.
+    pub selection: Option<MatmulSelection>,
+    /// Cube dimension of the kernel, when it has a selection.
+    pub cube_dim: Option<CubeDim>,
+    /// Cube count of the kernel, when it has a selection.
+    pub cube_count: Option<CubeCount>,
+}
+
+impl MatmulReport {
+    fn new(strategy: Strategy) -> Self {
+        Self {
+            strategy,
+            selection: None,
+            cube_dim: None,
+            cube_count: None,
+        }
+    }
+
+    fn selected(strategy: Strategy, kernel: SelectedKernel) -> Self {
+        Self {
+            strategy,
+            selection: Some(kernel.selection),
+            cube_dim: Some(kernel.cube_dim),
+            cube_count: Some(kernel.cube_count),
+        }
+    }
+}
+
 pub fn launch<R: Runtime, EG: Float>(
     strategy: &Strategy,
     client: &ComputeClient<R::Server, R::Channel>,
@@ -53,49 +129,236 @@ pub fn launch_ref<R: Runtime, EG: Numeric>(
     rhs: &TensorHandleRef<R>,
     out: &TensorHandleRef<R>,
 ) -> Result<(), MatmulLaunchError> {
+    launch_ref_with_context::<R, EG>(strategy, client, &mut MatmulContext::new(), lhs, rhs, out)
+}
+
+/// Same as [launch_ref], with the intermediate buffers of the strategy taken from `context`, so
+/// that launches of the same problem reuse them.
+///
+/// Only [Strategy::SplitK] needs intermediate buffers, the other strategies ignore the context.
+pub fn launch_ref_with_context<R: Runtime, EG: Numeric>(
+    strategy: &Strategy,
+    client: &ComputeClient<R::Server, R::Channel>,
+    context: &mut MatmulContext<R>,
+    lhs: &TensorHandleRef<R>,
+    rhs: &TensorHandleRef<R>,
+    out: &TensorHandleRef<R>,
+) -> Result<(), MatmulLaunchError> {
+    launch_reported::<R, EG, f32>(strategy, client, context, lhs, rhs, out).map(|_| ())
+}
+
+/// Same as [launch_ref], also returning how the matmul was configured.
+///
+/// This is useful to log which kernel [Strategy::Auto] picked, or the tile sizes chosen by a
+/// selector for a given shape.
+pub fn launch_ref_with_report<R: Runtime, EG: Numeric>(
+    strategy: &Strategy,
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<R>,
+    rhs: &TensorHandleRef<R>,
+    out: &TensorHandleRef<R>,
+) -> Result<MatmulReport, MatmulLaunchError> {
+    launch_reported::<R, EG, f32>(strategy, client, &mut MatmulContext::new(), lhs, rhs, out)
+}
+
+/// Same as [launch_ref_with_report], but the tiles accumulate in `EA` instead of `f32`.
+///
+/// See [matmul::launch_ref_with_accumulator]. Only the strategies built on a
+/// [MatmulSelector](matmul::MatmulSelector) have a configurable accumulator: the other ones fail
+/// with [AccumulatorNotConfigurable](MatmulAvailabilityError::AccumulatorNotConfigurable) unless
+/// `EA` is `f32`. [Strategy::Auto] doesn't fall back on Tiling2D either when `EA` isn't `f32`.
+pub fn launch_ref_with_accumulator<R: Runtime, EG: Numeric, EA: Numeric>(
+    strategy: &Strategy,
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<R>,
+    rhs: &TensorHandleRef<R>,
+    out: &TensorHandleRef<R>,
+) -> Result<MatmulReport, MatmulLaunchError> {
+    launch_reported::<R, EG, EA>(strategy, client, &mut MatmulContext::new(), lhs, rhs, out)
+}
+
+fn launch_reported<R: Runtime, EG: Numeric, EA: Numeric>(
+    strategy: &Strategy,
+    client: &ComputeClient<R::Server, R::Channel>,
+    context: &mut MatmulContext<R>,
+    lhs: &TensorHandleRef<R>,
+    rhs: &TensorHandleRef<R>,
+    out: &TensorHandleRef<R>,
+) -> Result<MatmulReport, MatmulLaunchError> {
+    let selected = |kernel| MatmulReport::selected(strategy.clone(), kernel);
+    let default_accumulator = TypeId::of::<EA>() == TypeId::of::<f32>();
+    let not_configurable = || {
+        MatmulLaunchError::Unavailable(MatmulAvailabilityError::AccumulatorNotConfigurable {
+            accumulator: EA::as_elem_native_unchecked(),
+        })
+    };
+
     match strategy {
+        Strategy::SplitK { .. }
+        | Strategy::Tiling2D(_)
+        | Strategy::Simple
+        | Strategy::SimpleScaled { .. }
+        | Strategy::Autotune
+            if !default_accumulator =>
+        {
+            Err(not_configurable())
+        }
         Strategy::Standard => {
-            matmul::launch_ref::<R, EG, StandardSelector<Accelerated>>(client, lhs, rhs, out)
+            matmul::launch_ref_with_accumulator::<R, EG, EA, StandardSelector<Accelerated>>(
+                client, lhs, rhs, out,
+            )
+            .map(selected)
+        }
+        Strategy::StandardSwizzled => {
+            matmul::launch_ref_with_accumulator::<R, EG, EA, StandardSwizzledSelector<Accelerated>>(
+                client, lhs, rhs, out,
+            )
+            .map(selected)
         }
+        Strategy::StandardPrefetching { depth } => match depth {
+            2 => matmul::launch_ref_with_accumulator::<
+                R,
+                EG,
+                EA,
+                StandardPrefetchingSelector<Accelerated, 2>,
+            >(client, lhs, rhs, out),
+            3 => matmul::launch_ref_with_accumulator::<
+                R,
+                EG,
+                EA,
+                StandardPrefetchingSelector<Accelerated, 3>,
+            >(client, lhs, rhs, out),
+            4 => matmul::launch_ref_with_accumulator::<
+                R,
+                EG,
+                EA,
+                StandardPrefetchingSelector<Accelerated, 4>,
+            >(client, lhs, rhs, out),
+            _ => Err(MatmulLaunchError::InvalidConfig(Box::new(format!(
+                "Prefetching needs a pipeline of 2 to 4 stages, got {depth}"
+            )))),
+        }
+        .map(selected),
         Strategy::Pipelined => {
-            matmul::launch_ref::<R, EG, PipelinedSelector<Accelerated>>(client, lhs, rhs, out)
+            matmul::launch_ref_with_accumulator::<R, EG, EA, PipelinedSelector<Accelerated>>(
+                client, lhs, rhs, out,
+            )
+            .map(selected)
         }
         Strategy::Specialized => {
-            matmul::launch_ref::<R, EG, SpecializedSelector<Accelerated>>(client, lhs, rhs, out)
-        }
-        #[cfg(any(test, feature = "export_tests"))]
-        Strategy::PlaneMma => {
-            matmul::launch_ref::<R, EG, StandardSelector<super::components::tile::plane::PlaneMma>>(
+            matmul::launch_ref_with_accumulator::<R, EG, EA, SpecializedSelector<Accelerated>>(
                 client, lhs, rhs, out,
             )
+            .map(selected)
+        }
+        Strategy::SpecializedDoubleBuffered => matmul::launch_ref_with_accumulator::<
+            R,
+            EG,
+            EA,
+            SpecializedDoubleBufferedSelector<Accelerated>,
+        >(client, lhs, rhs, out)
+        .map(selected),
+        Strategy::SplitK { splits } => {
+            split_k::launch_ref_with_context::<R, EG, StandardSelector<Accelerated>>(
+                client, context, lhs, rhs, out, *splits,
+            )?;
+            Ok(MatmulReport::new(strategy.clone()))
         }
+        #[cfg(any(test, feature = "export_tests"))]
+        Strategy::PlaneMma => matmul::launch_ref_with_accumulator::<
+            R,
+            EG,
+            EA,
+            StandardSelector<super::components::tile::plane::PlaneMma>,
+        >(client, lhs, rhs, out)
+        .map(selected),
         Strategy::Tiling2D(config) => {
             tiling2d::launch_ref::<R, EG>(client, lhs, rhs, out, config.clone());
-            Ok(())
+            Ok(MatmulReport::new(strategy.clone()))
         }
         Strategy::Simple => {
             simple::launch_ref::<R, EG>(client, lhs, rhs, out)?;
-            Ok(())
+            Ok(MatmulReport::new(strategy.clone()))
+        }
+        Strategy::SimpleScaled { alpha, beta } => {
+            simple::launch_ref_scaled::<R, EG>(client, lhs, rhs, out, *alpha, *beta)?;
+            Ok(MatmulReport::new(strategy.clone()))
+        }
+        Strategy::Autotune => {
+            tune::launch_autotune::<R, EG>(client, lhs, rhs, out)?;
+            Ok(MatmulReport::new(strategy.clone()))
         }
         Strategy::Auto => {
-            if let Err(err) =
-                matmul::launch_ref::<R, EG, StandardSelector<Accelerated>>(client, lhs, rhs, out)
-            {
-                match err {
-                    super::kernels::MatmulLaunchError::Unavailable(_) => {
-                        tiling2d::launch_ref::<R, EG>(
-                            client,
-                            lhs,
-                            rhs,
-                            out,
-                            Tiling2dConfig::default(),
-                        )
-                    }
-                    _ => panic!("{err:?}"),
-                }
+            let launch_tiling2d = || {
+                let problem = tiling2d_problem(lhs, rhs, out);
+                let config = Tiling2dConfig::from_problem::<R, EG>(client, &problem);
+                tiling2d::launch_ref::<R, EG>(client, lhs, rhs, out, config.clone());
+                Ok(MatmulReport::new(Strategy::Tiling2D(config)))
+            };
+
+            if auto_skips_accelerated::<EG, EA>(client.properties(), default_accumulator) {
+                return launch_tiling2d();
             }
 
-            Ok(())
+            match matmul::launch_ref_with_accumulator::<R, EG, EA, StandardSelector<Accelerated>>(
+                client, lhs, rhs, out,
+            ) {
+                Ok(kernel) => Ok(MatmulReport::selected(Strategy::Standard, kernel)),
+                Err(MatmulLaunchError::Unavailable(_)) if default_accumulator => launch_tiling2d(),
+                Err(err @ MatmulLaunchError::Unavailable(_)) => Err(err),
+                Err(err) => panic!("{err:?}"),
+            }
         }
     }
 }
+
+/// Whether [Strategy::Auto] launches Tiling2D without trying the accelerated kernel first.
+///
+/// Without a cmma instruction for the element types, e.g. for f64 on most devices, the
+/// accelerated kernel can't be launched, so it isn't configured to find out. Tiling2D can't be
+/// configured with another accumulator, so those still try the accelerated kernel.
+pub(crate) fn auto_skips_accelerated<EG: Numeric, EA: Numeric>(
+    properties: &DeviceProperties<Feature>,
+    default_accumulator: bool,
+) -> bool {
+    default_accumulator
+        && !accelerated_supported(
+            properties,
+            stage_elem::<EG, StandardSelector<Accelerated>>(),
+            EA::as_elem_native_unchecked(),
+        )
+}
+
+/// Describe the problem solved by Tiling2D, which picks its own line sizes.
+fn tiling2d_problem<R: Runtime>(
+    lhs: &TensorHandleRef<R>,
+    rhs: &TensorHandleRef<R>,
+    out: &TensorHandleRef<R>,
+) -> MatmulProblem {
+    let (lhs_rank, rhs_rank) = (lhs.shape.len(), rhs.shape.len());
+    let layout = |strides: &[usize]| match matrix_layout(strides) {
+        MatrixLayout::MildlyPermuted {
+            transposed: true, ..
+        } => components::MatrixLayout::ColMajor,
+        _ => components::MatrixLayout::RowMajor,
+    };
+
+    MatmulProblem {
+        m: lhs.shape[lhs_rank - 2],
+        n: rhs.shape[rhs_rank - 1],
+        k: lhs.shape[lhs_rank - 1],
+        batches: (
+            lhs.shape[..lhs_rank - 2].to_vec(),
+            rhs.shape[..rhs_rank - 2].to_vec(),
+        ),
+        lhs_layout: layout(lhs.strides),
+        rhs_layout: layout(rhs.strides),
+        out_layout: layout(out.strides),
+        lhs_line_size: 1,
+        rhs_line_size: 1,
+        out_line_size: 1,
+        beta: 0.0,
+        has_bias: false,
+        epilogue: EpilogueOp::None,
+    }
+}
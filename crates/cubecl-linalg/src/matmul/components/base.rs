@@ -10,6 +10,7 @@ pub struct MatmulSize {
     pub k: u32,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct MatmulSelection {
     pub tile: MatmulSize,
     pub num_stagess: MatmulSize,
@@ -20,6 +21,9 @@ pub struct MatmulSelection {
 pub trait MatmulLaunch: MatmulConfigFactory {
     /// Entry point
     ///
+    /// `beta` scales the existing output when the config [has a beta](super::global::HasBeta), and is
+    /// ignored otherwise.
+    ///
     /// # Safety
     ///
     /// Out-of-bounds can happen
@@ -29,6 +33,7 @@ pub trait MatmulLaunch: MatmulConfigFactory {
         cube_count: CubeCount,
         input: InputRuntimeArg<'a, MS, R>,
         output: OutputRuntimeArg<'a, MS, R>,
+        beta: f32,
         config: <Self as MatmulConfigFactory>::Config,
     );
 }
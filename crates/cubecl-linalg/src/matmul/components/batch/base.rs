@@ -1,9 +1,12 @@
 use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
 
-use crate::matmul::components::global::args::{self, MatmulArgs, TensorInput, TensorOutput};
-use crate::matmul::components::MatmulPrecision;
+use crate::matmul::components::global::args::{
+    MatmulArgs, TensorBias, TensorInputIdent, TensorLhs, TensorOutput, TensorRhs,
+};
+use crate::matmul::components::global::EpilogueArgs;
 use crate::matmul::components::{config::MatmulConfig, global, Ident, MatmulLaunch, StageDim};
+use crate::matmul::components::{InputRuntimeArg, MatmulPrecision, MatmulSpec, OutputRuntimeArg};
 use crate::tensor::{ReadWrite, VirtualTensor};
 
 /// A family of [matmuls](BatchMatmul) working with any [precision](MatmulPrecision).
@@ -34,9 +37,10 @@ pub trait BatchMatmul<MP: MatmulPrecision>: 'static + Send + Sync {
 
     /// Performs batchwise matrix multiplication over tensors.
     fn execute(
-        lhs: VirtualTensor<MP::EG>,
-        rhs: VirtualTensor<MP::EG>,
+        lhs: VirtualTensor<MP::EL>,
+        rhs: VirtualTensor<MP::ER>,
         out: VirtualTensor<MP::EG, ReadWrite>,
+        epilogue_args: EpilogueArgs<MP::EG>,
         #[comptime] config: Self::Config,
     );
 }
@@ -62,30 +66,63 @@ pub trait BatchConfig: MatmulConfig {
     fn max_batches(&self) -> u32;
 }
 
-type Input<Args, EG> = <Args as MatmulArgs>::Input<EG>;
+type Input<Args, EL, ER> = <Args as MatmulArgs>::Input<EL, ER>;
 type Output<Args, EG> = <Args as MatmulArgs>::Output<EG>;
 
-#[cube(launch_unchecked)]
+#[cube(launch_unchecked, create_dummy_kernel)]
 pub(crate) fn matmul<
+    EL: Numeric,
+    ER: Numeric,
     EG: Numeric,
     ES: Numeric,
     EA: Numeric,
     Args: MatmulArgs,
     BMM: BatchMatmulFamily,
 >(
-    inputs: &Input<Args, EG>,
+    inputs: &Input<Args, EL, ER>,
     output: &mut Output<Args, EG>,
+    beta: f32,
     #[comptime] config: BMM::Config,
 ) {
     let mut state = Args::init_state(inputs, output);
 
-    let lhs = TensorInput::<EG, Args>::new(&state, args::TensorInputIdent::Lhs);
-    let rhs = TensorInput::<EG, Args>::new(&state, args::TensorInputIdent::Rhs);
-    let mut out = TensorOutput::<EG, Args>::new(&mut state);
+    let lhs = TensorLhs::<EL, ER, EG, Args>::new(&state, TensorInputIdent::Lhs);
+    let rhs = TensorRhs::<EL, ER, EG, Args>::new(&state, TensorInputIdent::Rhs);
+    // Only read when the config has a bias, so args without one are never asked for it.
+    let bias = TensorBias::<EL, ER, EG, Args>::new(&state);
+    let mut out = TensorOutput::<EL, ER, EG, Args>::new(&mut state);
 
-    let lhs = VirtualTensor::<EG>::new::<TensorInput<EG, Args>>(&lhs);
-    let rhs = VirtualTensor::<EG>::new::<TensorInput<EG, Args>>(&rhs);
-    let out = VirtualTensor::<EG, ReadWrite>::new::<TensorOutput<EG, Args>>(&mut out);
+    let lhs = VirtualTensor::<EL>::new::<TensorLhs<EL, ER, EG, Args>>(&lhs);
+    let rhs = VirtualTensor::<ER>::new::<TensorRhs<EL, ER, EG, Args>>(&rhs);
+    let bias = VirtualTensor::<EG>::new::<TensorBias<EL, ER, EG, Args>>(&bias);
+    let out = VirtualTensor::<EG, ReadWrite>::new::<TensorOutput<EL, ER, EG, Args>>(&mut out);
 
-    BMM::Matmul::<(EG, ES, EA)>::execute(lhs, rhs, out, config);
+    let epilogue_args = EpilogueArgs::new(bias, beta);
+
+    BMM::Matmul::<(EL, ER, EG, ES, EA)>::execute(lhs, rhs, out, epilogue_args, config);
+}
+
+/// Expand the kernel that [launch_unchecked](MatmulLaunch::launch_unchecked) would launch with
+/// the same arguments, and compile it into the source of the runtime without launching it.
+pub(crate) fn compile<'a, MS: MatmulSpec, R: Runtime, BMM: BatchMatmulFamily>(
+    cube_dim: CubeDim,
+    cube_count: CubeCount,
+    input: InputRuntimeArg<'a, MS, R>,
+    output: OutputRuntimeArg<'a, MS, R>,
+    beta: f32,
+    config: BMM::Config,
+) -> String {
+    let kernel =
+        matmul::create_dummy_kernel::<MS::EL, MS::ER, MS::EG, MS::ES, MS::EA, MS::Args, BMM, R>(
+            cube_count,
+            cube_dim,
+            input,
+            output,
+            ScalarArg::new(beta),
+            config,
+        );
+
+    KernelTask::<R::Compiler, _>::new(kernel)
+        .compile(&Default::default(), ExecutionMode::Unchecked)
+        .source
 }
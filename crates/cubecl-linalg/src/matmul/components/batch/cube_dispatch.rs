@@ -8,8 +8,8 @@ use crate::matmul::components::batch::shared::swizzle;
 #[cube]
 /// Distributes cube instances across the tensor, assigning each to compute data in distinct regions.
 pub trait CubeDispatch: Clone + Copy + 'static + Send + Sync + Debug + Hash + Eq {
-    fn x_y_indices() -> (u32, u32);
-    fn batch_index() -> u32;
+    fn x_y_indices(#[comptime] cube_count: (u32, u32, u32)) -> (u32, u32);
+    fn batch_index(#[comptime] cube_count: (u32, u32, u32)) -> u32;
     fn max_x(#[comptime] cube_count: (u32, u32, u32)) -> u32;
     fn max_y(#[comptime] cube_count: (u32, u32, u32)) -> u32;
     fn max_batches(#[comptime] cube_count: (u32, u32, u32)) -> u32;
@@ -17,6 +17,21 @@ pub trait CubeDispatch: Clone + Copy + 'static + Send + Sync + Debug + Hash + Eq
 
 pub trait CubeCountDispatch {
     fn cube_count(cubes_for_m: u32, cubes_for_n: u32, cubes_for_batches: u32) -> CubeCount;
+
+    /// The cube count given to [CubeDispatch], for a grid launched with `cube_count`.
+    ///
+    /// It is the launched cube count itself, unless the dispatch changes the shape of the grid.
+    fn dispatched_count(
+        cube_count: &CubeCount,
+        _cubes_for_m: u32,
+        _cubes_for_n: u32,
+        _cubes_for_batches: u32,
+    ) -> (u32, u32, u32) {
+        match cube_count {
+            CubeCount::Static(x, y, z) => (*x, *y, *z),
+            CubeCount::Dynamic(_) => panic!("Dynamic cube count unsupported"),
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
@@ -29,6 +44,22 @@ pub struct NaturalDispatch;
 /// and further along the n dimension as `cube_pos_y` increases.
 pub struct TransposedDispatch;
 
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
+/// Numbers the cubes along a single dimension, for problems needing more cubes than the y and z
+/// dimensions of the grid allow.
+///
+/// The cubes are folded into the x and y dimensions of the grid, with at most
+/// [FLATTENED_MAX_X](FlattenedDispatch::FLATTENED_MAX_X) cubes along x. The cube number is split
+/// into (m, n, batch) indices inside the kernel, with consecutive cubes operating on data further
+/// along the n dimension first, as with [TransposedDispatch]. The last row of the grid may hold
+/// cubes past the last batch, which don't compute anything.
+pub struct FlattenedDispatch;
+
+impl FlattenedDispatch {
+    /// Maximum number of cubes along x, which every runtime can launch.
+    pub const FLATTENED_MAX_X: u32 = u16::MAX as u32;
+}
+
 #[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
 /// Processes data in a swizzled pattern, prioritizing cubes along the x-axis first.
 ///
@@ -45,11 +76,11 @@ pub struct SwizzleTransposedDispatch<const W: u32>;
 
 #[cube]
 impl CubeDispatch for NaturalDispatch {
-    fn x_y_indices() -> (u32, u32) {
+    fn x_y_indices(#[comptime] _cube_count: (u32, u32, u32)) -> (u32, u32) {
         (CUBE_POS_X, CUBE_POS_Y)
     }
 
-    fn batch_index() -> u32 {
+    fn batch_index(#[comptime] _cube_count: (u32, u32, u32)) -> u32 {
         CUBE_POS_Z
     }
 
@@ -74,11 +105,11 @@ impl CubeCountDispatch for NaturalDispatch {
 
 #[cube]
 impl CubeDispatch for TransposedDispatch {
-    fn x_y_indices() -> (u32, u32) {
+    fn x_y_indices(#[comptime] _cube_count: (u32, u32, u32)) -> (u32, u32) {
         (CUBE_POS_Y, CUBE_POS_X)
     }
 
-    fn batch_index() -> u32 {
+    fn batch_index(#[comptime] _cube_count: (u32, u32, u32)) -> u32 {
         CUBE_POS_Z
     }
 
@@ -103,13 +134,13 @@ impl CubeCountDispatch for TransposedDispatch {
 
 #[cube]
 impl<const W: u32> CubeDispatch for SwizzleNaturalDispatch<W> {
-    fn x_y_indices() -> (u32, u32) {
+    fn x_y_indices(#[comptime] _cube_count: (u32, u32, u32)) -> (u32, u32) {
         let height = CUBE_COUNT_X;
         let nth_cube = CUBE_POS_Y * height + CUBE_POS_X;
         swizzle(nth_cube, height, W)
     }
 
-    fn batch_index() -> u32 {
+    fn batch_index(#[comptime] _cube_count: (u32, u32, u32)) -> u32 {
         CUBE_POS_Z
     }
 
@@ -134,13 +165,13 @@ impl<const W: u32> CubeCountDispatch for SwizzleNaturalDispatch<W> {
 
 #[cube]
 impl<const W: u32> CubeDispatch for SwizzleTransposedDispatch<W> {
-    fn x_y_indices() -> (u32, u32) {
+    fn x_y_indices(#[comptime] _cube_count: (u32, u32, u32)) -> (u32, u32) {
         let height = CUBE_COUNT_Y;
         let nth_cube = CUBE_POS_X * height + CUBE_POS_Y;
         swizzle(nth_cube, height, W)
     }
 
-    fn batch_index() -> u32 {
+    fn batch_index(#[comptime] _cube_count: (u32, u32, u32)) -> u32 {
         CUBE_POS_Z
     }
 
@@ -162,3 +193,48 @@ impl<const W: u32> CubeCountDispatch for SwizzleTransposedDispatch<W> {
         CubeCount::Static(cubes_for_n, cubes_for_m, cubes_for_batches)
     }
 }
+
+#[cube]
+impl CubeDispatch for FlattenedDispatch {
+    fn x_y_indices(#[comptime] cube_count: (u32, u32, u32)) -> (u32, u32) {
+        let cubes_per_batch = comptime!(cube_count.0 * cube_count.1);
+        let nth_cube = (CUBE_POS_Y * CUBE_COUNT_X + CUBE_POS_X) % cubes_per_batch;
+        (nth_cube / cube_count.1, nth_cube % cube_count.1)
+    }
+
+    fn batch_index(#[comptime] cube_count: (u32, u32, u32)) -> u32 {
+        (CUBE_POS_Y * CUBE_COUNT_X + CUBE_POS_X) / comptime!(cube_count.0 * cube_count.1)
+    }
+
+    fn max_x(#[comptime] cube_count: (u32, u32, u32)) -> u32 {
+        cube_count.0
+    }
+
+    fn max_y(#[comptime] cube_count: (u32, u32, u32)) -> u32 {
+        cube_count.1
+    }
+
+    fn max_batches(#[comptime] cube_count: (u32, u32, u32)) -> u32 {
+        cube_count.2
+    }
+}
+
+impl CubeCountDispatch for FlattenedDispatch {
+    fn cube_count(cubes_for_m: u32, cubes_for_n: u32, cubes_for_batches: u32) -> CubeCount {
+        let num_cubes = cubes_for_m as u64 * cubes_for_n as u64 * cubes_for_batches as u64;
+        let cubes_x = num_cubes.min(Self::FLATTENED_MAX_X as u64);
+        // Too many cubes overflow y, which is then rejected when checking the cube count.
+        let cubes_y = num_cubes.div_ceil(cubes_x.max(1)).min(u32::MAX as u64);
+
+        CubeCount::Static(cubes_x as u32, cubes_y as u32, 1)
+    }
+
+    fn dispatched_count(
+        _cube_count: &CubeCount,
+        cubes_for_m: u32,
+        cubes_for_n: u32,
+        cubes_for_batches: u32,
+    ) -> (u32, u32, u32) {
+        (cubes_for_m, cubes_for_n, cubes_for_batches)
+    }
+}
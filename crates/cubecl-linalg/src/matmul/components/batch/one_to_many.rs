@@ -1,7 +1,7 @@
 use std::marker::PhantomData;
 
 use crate::matmul::components::batch::span::{Span, SpanDim, SpanMatmul};
-use crate::matmul::components::global::GlobalMatmulFamily;
+use crate::matmul::components::global::{EpilogueArgs, GlobalMatmulFamily};
 use crate::matmul::components::{
     batch, config::MatmulConfig, global, Ident, MatmulConfigFactory, MatmulLaunch, StageDim,
 };
@@ -73,10 +73,17 @@ impl<GMM: GlobalMatmulFamily, S: SpanMatmul, C: CubeDispatch> MatmulLaunch
         cube_count: CubeCount,
         input: InputRuntimeArg<'a, MS, R>,
         output: OutputRuntimeArg<'a, MS, R>,
+        beta: f32,
         config: Self::Config,
     ) {
-        super::matmul::launch_unchecked::<MS::EG, MS::ES, MS::EA, MS::Args, Self, R>(
-            client, cube_count, cube_dim, input, output, config,
+        super::matmul::launch_unchecked::<MS::EL, MS::ER, MS::EG, MS::ES, MS::EA, MS::Args, Self, R>(
+            client,
+            cube_count,
+            cube_dim,
+            input,
+            output,
+            ScalarArg::new(beta),
+            config,
         );
     }
 }
@@ -105,9 +112,10 @@ impl<MP: MatmulPrecision, GMM: global::GlobalMatmul<MP>, S: SpanMatmul, C: CubeD
     type Config = Config<GMM::Config, C>;
 
     fn execute(
-        lhs: VirtualTensor<MP::EG>,
-        rhs: VirtualTensor<MP::EG>,
+        lhs: VirtualTensor<MP::EL>,
+        rhs: VirtualTensor<MP::ER>,
         out: VirtualTensor<MP::EG, ReadWrite>,
+        epilogue_args: EpilogueArgs<MP::EG>,
         #[comptime] config: Self::Config,
     ) {
         let rank = out.rank();
@@ -127,8 +135,8 @@ impl<MP: MatmulPrecision, GMM: global::GlobalMatmul<MP>, S: SpanMatmul, C: CubeD
         let stage_y = config.stage_dim(Ident::Out).num_elements_y_dim();
         let stage_z = 1;
 
-        let (x_index, y_index) = C::x_y_indices();
-        let batch_index = C::batch_index();
+        let (x_index, y_index) = C::x_y_indices(config.cube_count());
+        let batch_index = C::batch_index(config.cube_count());
 
         let span = Span::new(
             SpanDim::new(shape_x, stage_x, x_index, cubes_x),
@@ -136,11 +144,11 @@ impl<MP: MatmulPrecision, GMM: global::GlobalMatmul<MP>, S: SpanMatmul, C: CubeD
             SpanDim::new(shape_z, stage_z, batch_index, cubes_z),
         );
 
-        let k_range = (0, lhs.shape(rank - 1));
+        let k_range = (0, lhs.shape(lhs.rank() - 1));
 
         let gmm_config = config.to_gmm_config();
         let acc = GMM::init_accumulator(gmm_config);
-        S::execute::<MP, GMM>(lhs, rhs, out, span, acc, k_range, gmm_config);
+        S::execute::<MP, GMM>(lhs, rhs, out, epilogue_args, span, acc, k_range, gmm_config);
     }
 }
 
@@ -187,6 +195,10 @@ impl<G: global::GlobalConfig, C: CubeDispatch> Config<G, C> {
         }
     }
 
+    fn cube_count(&self) -> (u32, u32, u32) {
+        self.cube_count
+    }
+
     fn cube_count_x(&self) -> u32 {
         C::max_x(self.cube_count)
     }
@@ -1,7 +1,9 @@
 use std::marker::PhantomData;
 
 use crate::matmul::components::batch::shared::gmm_execute;
-use crate::matmul::components::global::{GlobalMatmul, GlobalMatmulFamily};
+use crate::matmul::components::global::{
+    EpilogueArgs, GlobalConfig as _, GlobalMatmul, GlobalMatmulFamily,
+};
 use crate::matmul::components::{
     batch, config::MatmulConfig, global, Ident, MatmulConfigFactory, MatmulLaunch, StageDim,
 };
@@ -16,18 +18,20 @@ use batch::{BatchMatmul, BatchMatmulFamily};
 use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
 
-use super::{BatchConfig as _, CubeDispatch};
+use super::{BatchConfig as _, CubeCountDispatch, CubeDispatch};
 
 pub struct OneToOneMatmulFamily<GMM: GlobalMatmulFamily, C: CubeDispatch> {
     _gmm: PhantomData<GMM>,
     _c: PhantomData<C>,
 }
 
-impl<GMM: GlobalMatmulFamily, C: CubeDispatch> BatchMatmulFamily for OneToOneMatmulFamily<GMM, C> {
+impl<GMM: GlobalMatmulFamily, C: CubeDispatch + CubeCountDispatch> BatchMatmulFamily
+    for OneToOneMatmulFamily<GMM, C>
+{
     type Matmul<MP: MatmulPrecision> = OneToOneMatmul<MP, GMM::Matmul<MP>, C>;
 }
 
-impl<GMM: GlobalMatmulFamily, C: CubeDispatch> MatmulConfigFactory
+impl<GMM: GlobalMatmulFamily, C: CubeDispatch + CubeCountDispatch> MatmulConfigFactory
     for OneToOneMatmulFamily<GMM, C>
 {
     type Input = GMM::Input;
@@ -52,27 +56,39 @@ impl<GMM: GlobalMatmulFamily, C: CubeDispatch> MatmulConfigFactory
         advanced_config: &AdvancedConfig,
     ) -> Self::Config {
         let gmm_config = GMM::make_config(input, problem, cube_dim, cube_count, advanced_config);
-        let cube_count = if let CubeCount::Static(x, y, z) = cube_count {
-            (*x, *y, *z)
-        } else {
-            panic!("Dynamic cube count unsupported")
-        };
+        let stage_m = gmm_config.stage_dim(Ident::Out).num_elements_x_dim();
+        let stage_n = gmm_config.stage_dim(Ident::Out).num_elements_y_dim();
+        let cube_count = C::dispatched_count(
+            cube_count,
+            (problem.m as u32 + stage_m - 1) / stage_m,
+            (problem.n as u32 + stage_n - 1) / stage_n,
+            problem.num_batches() as u32,
+        );
 
         Config::<GMM::Config, C>::new(gmm_config, cube_count)
     }
 }
 
-impl<GMM: GlobalMatmulFamily, C: CubeDispatch> MatmulLaunch for OneToOneMatmulFamily<GMM, C> {
+impl<GMM: GlobalMatmulFamily, C: CubeDispatch + CubeCountDispatch> MatmulLaunch
+    for OneToOneMatmulFamily<GMM, C>
+{
     unsafe fn launch_unchecked<'a, MS: MatmulSpec, R: Runtime>(
         client: &ComputeClient<<R as Runtime>::Server, <R as Runtime>::Channel>,
         cube_dim: CubeDim,
         cube_count: CubeCount,
         input: InputRuntimeArg<'a, MS, R>,
         output: OutputRuntimeArg<'a, MS, R>,
+        beta: f32,
         config: Self::Config,
     ) {
-        super::matmul::launch_unchecked::<MS::EG, MS::ES, MS::EA, MS::Args, Self, R>(
-            client, cube_count, cube_dim, input, output, config,
+        super::matmul::launch_unchecked::<MS::EL, MS::ER, MS::EG, MS::ES, MS::EA, MS::Args, Self, R>(
+            client,
+            cube_count,
+            cube_dim,
+            input,
+            output,
+            ScalarArg::new(beta),
+            config,
         );
     }
 }
@@ -95,31 +111,36 @@ impl<MP: MatmulPrecision, GMM: GlobalMatmul<MP>, C: CubeDispatch> BatchMatmul<MP
     type Config = Config<GMM::Config, C>;
 
     fn execute(
-        lhs: VirtualTensor<MP::EG>,
-        rhs: VirtualTensor<MP::EG>,
+        lhs: VirtualTensor<MP::EL>,
+        rhs: VirtualTensor<MP::ER>,
         out: VirtualTensor<MP::EG, ReadWrite>,
+        epilogue_args: EpilogueArgs<MP::EG>,
         #[comptime] config: Self::Config,
     ) {
-        let (x_index, y_index) = C::x_y_indices();
+        let (x_index, y_index) = C::x_y_indices(config.cube_count());
         let x_offset = x_index * config.stage_dim(Ident::Lhs).num_elements_x_dim();
         let y_offset = y_index * config.stage_dim(Ident::Rhs).num_elements_y_dim();
-        let nth_batch = C::batch_index();
+        let nth_batch = C::batch_index(config.cube_count());
         let rank = lhs.rank();
         let k_range = (0, lhs.shape(rank - 1));
 
         let gmm_config = config.to_gmm_config();
 
-        gmm_execute::<MP, GMM>(
-            lhs,
-            rhs,
-            out,
-            x_offset,
-            y_offset,
-            nth_batch,
-            &mut GMM::init_accumulator(gmm_config),
-            k_range,
-            gmm_config,
-        );
+        // Dispatches folding the cubes into a grid may launch a few past the last batch.
+        if nth_batch < config.max_batches() {
+            gmm_execute::<MP, GMM>(
+                lhs,
+                rhs,
+                out,
+                epilogue_args,
+                x_offset,
+                y_offset,
+                nth_batch,
+                &mut GMM::init_accumulator(gmm_config),
+                k_range,
+                gmm_config,
+            );
+        }
     }
 }
 
@@ -165,4 +186,9 @@ impl<G: global::GlobalConfig, C: CubeDispatch> Config<G, C> {
             _c: PhantomData,
         }
     }
+
+    /// The cube count given to the dispatch.
+    pub fn cube_count(&self) -> (u32, u32, u32) {
+        self.cube_count
+    }
 }
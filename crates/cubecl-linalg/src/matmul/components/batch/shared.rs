@@ -1,16 +1,22 @@
 use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
 
-use crate::matmul::components::{global, MatmulPrecision};
+use crate::matmul::components::global::{self, EpilogueArgs};
+use crate::matmul::components::MatmulPrecision;
 use crate::tensor::{ReadWrite, VirtualTensor};
 
 #[cube]
 /// Execute global matmul on lhs, rhs, writing in out.
 /// x and y offsets are absolute rows and columns
+///
+/// The batch offsets of all tensors follow their strides, so the output may be over-strided,
+/// for instance when it is a slice of an interleaved buffer. The inputs may have fewer batch
+/// dimensions than the output, in which case they are broadcasted over the leading ones.
 pub(crate) fn gmm_execute<MP: MatmulPrecision, GMM: global::GlobalMatmul<MP>>(
-    lhs: VirtualTensor<MP::EG>,
-    rhs: VirtualTensor<MP::EG>,
+    lhs: VirtualTensor<MP::EL>,
+    rhs: VirtualTensor<MP::ER>,
     out: VirtualTensor<MP::EG, ReadWrite>,
+    epilogue_args: EpilogueArgs<MP::EG>,
     x_offset: u32,
     y_offset: u32,
     nth_batch: u32,
@@ -19,19 +25,35 @@ pub(crate) fn gmm_execute<MP: MatmulPrecision, GMM: global::GlobalMatmul<MP>>(
     #[comptime] config: GMM::Config,
 ) {
     let rank = out.rank();
-    let batch_out = nth_batch * out.shape(rank - 2) * out.shape(rank - 1);
+    let lhs_rank = lhs.rank();
+    let rhs_rank = rhs.rank();
+    let mut remaining = nth_batch;
+    let mut batch_out = 0u32.runtime();
     let mut batch_lhs = 0u32.runtime();
     let mut batch_rhs = 0u32.runtime();
-    for b in 0..rank - 2 {
-        let tmp = batch_out / out.stride(b);
-        batch_lhs += tmp % lhs.shape(b) * lhs.stride(b);
-        batch_rhs += tmp % rhs.shape(b) * rhs.stride(b);
+    // The innermost batch dimension varies fastest, like in a contiguous output.
+    for i in 0..rank - 2 {
+        let b = rank - 3 - i;
+        let index = remaining % out.shape(b);
+        remaining /= out.shape(b);
+
+        batch_out += index * out.stride(b);
+
+        // Batch dimensions are aligned on the last one.
+        if b + lhs_rank >= rank {
+            let b = b + lhs_rank - rank;
+            batch_lhs += index % lhs.shape(b) * lhs.stride(b);
+        }
+        if b + rhs_rank >= rank {
+            let b = b + rhs_rank - rank;
+            batch_rhs += index % rhs.shape(b) * rhs.stride(b);
+        }
     }
 
     GMM::execute(
         GMM::init_lhs_loader(lhs, x_offset, k_range.0, batch_lhs, config),
         GMM::init_rhs_loader(rhs, k_range.0, y_offset, batch_rhs, config),
-        GMM::init_unloader(out, x_offset, y_offset, batch_out),
+        GMM::init_unloader(out, epilogue_args, x_offset, y_offset, batch_out, config),
         acc,
         k_range,
         config,
@@ -4,7 +4,7 @@ use cubecl_core::prelude::*;
 use crate::{
     matmul::components::{
         batch::shared::swizzle,
-        global::{self},
+        global::{self, EpilogueArgs},
         MatmulPrecision,
     },
     tensor::{ReadWrite, VirtualTensor},
@@ -33,9 +33,10 @@ pub struct SpanDim {
 /// Iterates on several global matmul across a span
 pub trait SpanMatmul: 'static + Send + Sync {
     fn execute<MP: MatmulPrecision, GMM: global::GlobalMatmul<MP>>(
-        lhs: VirtualTensor<MP::EG>,
-        rhs: VirtualTensor<MP::EG>,
+        lhs: VirtualTensor<MP::EL>,
+        rhs: VirtualTensor<MP::ER>,
         out: VirtualTensor<MP::EG, ReadWrite>,
+        epilogue_args: EpilogueArgs<MP::EG>,
         span: Span,
         acc: GMM::Accumulator,
         k_range: (u32, u32),
@@ -90,9 +91,10 @@ impl SpanDim {
 #[cube]
 impl SpanMatmul for RowMajorSpanMatmul {
     fn execute<MP: MatmulPrecision, GMM: global::GlobalMatmul<MP>>(
-        lhs: VirtualTensor<MP::EG>,
-        rhs: VirtualTensor<MP::EG>,
+        lhs: VirtualTensor<MP::EL>,
+        rhs: VirtualTensor<MP::ER>,
         out: VirtualTensor<MP::EG, ReadWrite>,
+        epilogue_args: EpilogueArgs<MP::EG>,
         span: Span,
         mut acc: GMM::Accumulator,
         k_range: (u32, u32),
@@ -103,7 +105,16 @@ impl SpanMatmul for RowMajorSpanMatmul {
                 for col_iter in range_stepped(span.col.start, span.col.end, span.col.step) {
                     GMM::zero_accumulator(&mut acc, config);
                     gmm_execute::<MP, GMM>(
-                        lhs, rhs, out, row_iter, col_iter, batch_iter, &mut acc, k_range, config,
+                        lhs,
+                        rhs,
+                        out,
+                        epilogue_args,
+                        row_iter,
+                        col_iter,
+                        batch_iter,
+                        &mut acc,
+                        k_range,
+                        config,
                     );
                 }
             }
@@ -114,9 +125,10 @@ impl SpanMatmul for RowMajorSpanMatmul {
 #[cube]
 impl SpanMatmul for ColMajorSpanMatmul {
     fn execute<MP: MatmulPrecision, GMM: global::GlobalMatmul<MP>>(
-        lhs: VirtualTensor<MP::EG>,
-        rhs: VirtualTensor<MP::EG>,
+        lhs: VirtualTensor<MP::EL>,
+        rhs: VirtualTensor<MP::ER>,
         out: VirtualTensor<MP::EG, ReadWrite>,
+        epilogue_args: EpilogueArgs<MP::EG>,
         span: Span,
         mut acc: GMM::Accumulator,
         k_range: (u32, u32),
@@ -127,7 +139,16 @@ impl SpanMatmul for ColMajorSpanMatmul {
                 for row_iter in range_stepped(span.row.start, span.row.end, span.row.step) {
                     GMM::zero_accumulator(&mut acc, config);
                     gmm_execute::<MP, GMM>(
-                        lhs, rhs, out, row_iter, col_iter, batch_iter, &mut acc, k_range, config,
+                        lhs,
+                        rhs,
+                        out,
+                        epilogue_args,
+                        row_iter,
+                        col_iter,
+                        batch_iter,
+                        &mut acc,
+                        k_range,
+                        config,
                     );
                 }
             }
@@ -138,9 +159,10 @@ impl SpanMatmul for ColMajorSpanMatmul {
 #[cube]
 impl<const W: u32> SpanMatmul for SwizzleSpanMatmul<W> {
     fn execute<MP: MatmulPrecision, GMM: global::GlobalMatmul<MP>>(
-        lhs: VirtualTensor<MP::EG>,
-        rhs: VirtualTensor<MP::EG>,
+        lhs: VirtualTensor<MP::EL>,
+        rhs: VirtualTensor<MP::ER>,
         out: VirtualTensor<MP::EG, ReadWrite>,
+        epilogue_args: EpilogueArgs<MP::EG>,
         span: Span,
         mut acc: GMM::Accumulator,
         k_range: (u32, u32),
@@ -156,7 +178,16 @@ impl<const W: u32> SpanMatmul for SwizzleSpanMatmul<W> {
                 let row_iter = span.row.start + row * span.row.step;
                 let col_iter = span.col.start + col * span.col.step;
                 gmm_execute::<MP, GMM>(
-                    lhs, rhs, out, row_iter, col_iter, batch_iter, &mut acc, k_range, config,
+                    lhs,
+                    rhs,
+                    out,
+                    epilogue_args,
+                    row_iter,
+                    col_iter,
+                    batch_iter,
+                    &mut acc,
+                    k_range,
+                    config,
                 );
             }
         }
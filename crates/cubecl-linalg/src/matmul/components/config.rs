@@ -63,13 +63,16 @@ pub trait MatmulConfig:
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
-/// Identifier for all three tensors in a matmul
+/// Identifier for all the tensors in a matmul
 ///
 /// Useful to specialize some functions depending on the tensor
 pub enum Ident {
     Lhs,
     Rhs,
     Out,
+    /// Optional per-column bias of shape `[n]`, broadcasted over the rows and batches of the
+    /// output. It is read with the line size of the output and is never staged.
+    Bias,
 }
 
 impl Ident {
@@ -78,6 +81,7 @@ impl Ident {
             Ident::Lhs => InputIdent::Lhs,
             Ident::Rhs => InputIdent::Rhs,
             Ident::Out => panic!("Out is not an input."),
+            Ident::Bias => panic!("Bias is not loaded into a stage."),
         }
     }
 }
@@ -93,6 +97,89 @@ pub enum InputIdent {
 pub enum MatrixLayout {
     RowMajor,
     ColMajor,
+    /// General 2D strides, in elements, of a tensor in global memory.
+    ///
+    /// Only problems may be strided: kernel configs work with the [major](MatrixLayout::major)
+    /// layout, and the loaders read through the runtime strides of the tensor.
+    Strided {
+        row_stride: u32,
+        col_stride: u32,
+    },
+}
+
+impl MatrixLayout {
+    /// Returns the contiguous layout along whose minor dimension the strides are the smallest.
+    pub fn major(self) -> MatrixLayout {
+        match self {
+            MatrixLayout::Strided {
+                row_stride,
+                col_stride,
+            } => match col_stride <= row_stride {
+                true => MatrixLayout::RowMajor,
+                false => MatrixLayout::ColMajor,
+            },
+            layout => layout,
+        }
+    }
+
+    /// Returns the layout of the transposed matrix stored in the same memory.
+    ///
+    /// Row-major and column-major are swapped, and strided layouts swap their strides.
+    pub fn transposed(self) -> MatrixLayout {
+        match self {
+            MatrixLayout::RowMajor => MatrixLayout::ColMajor,
+            MatrixLayout::ColMajor => MatrixLayout::RowMajor,
+            MatrixLayout::Strided {
+                row_stride,
+                col_stride,
+            } => MatrixLayout::Strided {
+                row_stride: col_stride,
+                col_stride: row_stride,
+            },
+        }
+    }
+
+    /// Returns the row and column strides of a `rows` x `cols` matrix with this layout.
+    pub fn strides(self, rows: u32, cols: u32) -> (u32, u32) {
+        match self {
+            MatrixLayout::RowMajor => (cols, 1),
+            MatrixLayout::ColMajor => (1, rows),
+            MatrixLayout::Strided {
+                row_stride,
+                col_stride,
+            } => (row_stride, col_stride),
+        }
+    }
+
+    /// Whether consecutive elements along the minor dimension are adjacent in memory,
+    /// which is required to read them in lines of more than one element.
+    pub fn is_minor_contiguous(self) -> bool {
+        match self {
+            MatrixLayout::Strided {
+                row_stride,
+                col_stride,
+            } => u32::min(row_stride, col_stride) == 1,
+            _ => true,
+        }
+    }
+
+    /// Whether two different elements of a `rows` x `cols` matrix may share the same address.
+    ///
+    /// This is conservative: the minor stride must step over whole elements, and the major
+    /// stride over a whole row (or column) of minor strides.
+    pub fn aliases(self, rows: u32, cols: u32) -> bool {
+        let (row_stride, col_stride) = self.strides(rows, cols);
+        let (minor_stride, minor_len, major_stride, major_len) = match col_stride <= row_stride {
+            true => (col_stride, cols, row_stride, rows),
+            false => (row_stride, rows, col_stride, cols),
+        };
+
+        let minor_valid = minor_len <= 1 || minor_stride >= 1;
+        let major_valid =
+            major_len <= 1 || major_stride >= u32::max(minor_stride.saturating_mul(minor_len), 1);
+
+        !(minor_valid && major_valid)
+    }
 }
 
 #[cube]
@@ -101,6 +188,7 @@ pub fn as_cmma_layout(#[comptime] layout: MatrixLayout) -> cmma::MatrixLayout {
     match layout {
         MatrixLayout::RowMajor => cmma::MatrixLayout::RowMajor,
         MatrixLayout::ColMajor => cmma::MatrixLayout::ColMajor,
+        MatrixLayout::Strided { .. } => unreachable!("Configs only hold contiguous layouts"),
     }
 }
 
@@ -1,52 +1,122 @@
 use crate::tensor::{VirtualTensorOperations, VirtualTensorOperationsExpand};
 use cubecl::prelude::*;
-use cubecl_core::{self as cubecl};
+use cubecl_core::{self as cubecl, unexpanded};
+use std::any::TypeId;
+use std::marker::PhantomData;
 
 #[cube]
 /// Arguments for the matrix multiplication algorithm.
 pub trait MatmulArgs: Send + Sync + 'static + Clone {
     /// Type used for the input.
-    type Input<EG: Numeric>: LaunchArg + CubeType;
+    type Input<EL: Numeric, ER: Numeric>: LaunchArg + CubeType;
     /// Type used for the output.
     type Output<EG: Numeric>: LaunchArg + CubeType;
     /// Inner state that is used to create [tensor inputs](TensorInput) and
     /// [tensor outputs](TensorOutput) .
-    type State<EG: Numeric>: CubeType;
+    type State<EL: Numeric, ER: Numeric, EG: Numeric>: CubeType;
 
     /// Init the state.
-    fn init_state<EG: Numeric>(
-        input: &Self::Input<EG>,
+    fn init_state<EL: Numeric, ER: Numeric, EG: Numeric>(
+        input: &Self::Input<EL, ER>,
         output: &mut Self::Output<EG>,
-    ) -> Self::State<EG>;
+    ) -> Self::State<EL, ER, EG>;
 
     /// Read the line of the lhs tensor using the state at the given coordinate.
-    fn read_lhs<EG: Numeric>(state: &Self::State<EG>, coordinate: u32) -> Line<EG>;
+    fn read_lhs<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        coordinate: u32,
+    ) -> Line<EL>;
     /// Read the line of the rhs tensor using the state at the given coordinate.
-    fn read_rhs<EG: Numeric>(state: &Self::State<EG>, coordinate: u32) -> Line<EG>;
+    fn read_rhs<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        coordinate: u32,
+    ) -> Line<ER>;
+
+    /// Same as [read_lhs](MatmulArgs::read_lhs), with a 64-bit coordinate to address tensors of
+    /// more than `u32::MAX` lines.
+    fn read_lhs_wide<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        coordinate: u64,
+    ) -> Line<EL>;
+    /// Same as [read_rhs](MatmulArgs::read_rhs), with a 64-bit coordinate to address tensors of
+    /// more than `u32::MAX` lines.
+    fn read_rhs_wide<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        coordinate: u64,
+    ) -> Line<ER>;
+
+    /// Read the slice of the lhs tensor using the state between the given coordinates.
+    fn read_window_lhs<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        start: u32,
+        end: u32,
+    ) -> Slice<Line<EL>>;
+    /// Read the slice of the rhs tensor using the state between the given coordinates.
+    fn read_window_rhs<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        start: u32,
+        end: u32,
+    ) -> Slice<Line<ER>>;
+
+    /// Read the line of the output at the given coordinate using the state, which is only done
+    /// when accumulating into the existing output.
+    fn read_out<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        coordinate: u32,
+    ) -> Line<EG>;
+
+    /// Read the line of the bias at the given coordinate using the state, which is only done
+    /// when the matmul adds a bias to its output.
+    fn read_bias<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        coordinate: u32,
+    ) -> Line<EG>;
 
     /// Write the line to the output at the given coordinate using the state.
-    fn write_out<EG: Numeric>(state: &mut Self::State<EG>, coordinate: u32, value: Line<EG>);
+    fn write_out<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &mut Self::State<EL, ER, EG>,
+        coordinate: u32,
+        value: Line<EG>,
+    );
 
     /// Get the rank of the lhs tensor using the state.
-    fn rank_lhs<EG: Numeric>(state: &Self::State<EG>) -> u32;
+    fn rank_lhs<EL: Numeric, ER: Numeric, EG: Numeric>(state: &Self::State<EL, ER, EG>) -> u32;
     /// Get the rank of the rhs tensor using the state.
-    fn rank_rhs<EG: Numeric>(state: &Self::State<EG>) -> u32;
+    fn rank_rhs<EL: Numeric, ER: Numeric, EG: Numeric>(state: &Self::State<EL, ER, EG>) -> u32;
     /// Get the rank of the out tensor using the state.
-    fn rank_out<EG: Numeric>(state: &Self::State<EG>) -> u32;
+    fn rank_out<EL: Numeric, ER: Numeric, EG: Numeric>(state: &Self::State<EL, ER, EG>) -> u32;
 
     /// Get the shape of the lhs tensor using the state.
-    fn shape_lhs<EG: Numeric>(state: &Self::State<EG>, axis: u32) -> u32;
+    fn shape_lhs<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        axis: u32,
+    ) -> u32;
     /// Get the shape of the rhs tensor using the state.
-    fn shape_rhs<EG: Numeric>(state: &Self::State<EG>, axis: u32) -> u32;
+    fn shape_rhs<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        axis: u32,
+    ) -> u32;
     /// Get the shape of the out tensor using the state.
-    fn shape_out<EG: Numeric>(state: &Self::State<EG>, axis: u32) -> u32;
+    fn shape_out<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        axis: u32,
+    ) -> u32;
 
     /// Get the stride of the lhs tensor using the state.
-    fn stride_lhs<EG: Numeric>(state: &Self::State<EG>, axis: u32) -> u32;
+    fn stride_lhs<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        axis: u32,
+    ) -> u32;
     /// Get the stride of the rhs tensor using the state.
-    fn stride_rhs<EG: Numeric>(state: &Self::State<EG>, axis: u32) -> u32;
+    fn stride_rhs<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        axis: u32,
+    ) -> u32;
     /// Get the stride of the out tensor using the state.
-    fn stride_out<EG: Numeric>(state: &Self::State<EG>, axis: u32) -> u32;
+    fn stride_out<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        axis: u32,
+    ) -> u32;
 }
 
 #[derive(Clone, Copy)]
@@ -56,23 +126,97 @@ pub enum TensorInputIdent {
     Rhs,
 }
 
-/// Tensor input representation.
+/// Tensor input representation, reading either the lhs or the rhs depending on its
+/// [ident](TensorInputIdent).
 ///
 /// You can use the tensor input as if it was a pointer to the actually tensor.
-pub struct TensorInput<EG: Numeric, GA: MatmulArgs> {
-    state: *const GA::State<EG>,
+///
+/// `EI` is the element type of the side that is read, which must be `EL` for the lhs and `ER`
+/// for the rhs.
+pub struct TensorInput<EI: Numeric, EL: Numeric, ER: Numeric, EG: Numeric, GA: MatmulArgs> {
+    state: *const GA::State<EL, ER, EG>,
     ident: TensorInputIdent,
+    _input: PhantomData<EI>,
+}
+
+/// [Tensor input](TensorInput) reading the lhs.
+pub type TensorLhs<EL, ER, EG, GA> = TensorInput<EL, EL, ER, EG, GA>;
+/// [Tensor input](TensorInput) reading the rhs.
+pub type TensorRhs<EL, ER, EG, GA> = TensorInput<ER, EL, ER, EG, GA>;
+
+/// Tensor output representation.
+///
+/// You can use the tensor output as if it was a pointer to the actually tensor.
+///
+/// # Warning
+///
+/// There is no mutability guarantee.
+pub struct TensorOutput<EL: Numeric, ER: Numeric, EG: Numeric, GA: MatmulArgs> {
+    state: *mut GA::State<EL, ER, EG>,
 }
 
-impl<EG: Numeric, MA: MatmulArgs> VirtualTensorOperations<EG> for TensorInput<EG, MA> {}
-impl<EG: Numeric, MA: MatmulArgs> VirtualTensorOperations<EG> for TensorOutput<EG, MA> {}
+/// Bias representation, which is only ever read.
+///
+/// You can use the bias as if it was a pointer to the actually tensor.
+pub struct TensorBias<EL: Numeric, ER: Numeric, EG: Numeric, GA: MatmulArgs> {
+    state: *const GA::State<EL, ER, EG>,
+}
 
-impl<EG: Numeric, MA: MatmulArgs> VirtualTensorOperationsExpand<EG> for TensorOutputExpand<EG, MA> {
+/// Expand type for [tensor input](TensorInput).
+pub struct TensorInputExpand<EI: Numeric, EL: Numeric, ER: Numeric, EG: Numeric, GA: MatmulArgs> {
+    state: <GA::State<EL, ER, EG> as CubeType>::ExpandType,
+    ident: TensorInputIdent,
+    _input: PhantomData<EI>,
+}
+
+/// Expand type for [bias](TensorBias).
+pub struct TensorBiasExpand<EL: Numeric, ER: Numeric, EG: Numeric, GA: MatmulArgs> {
+    state: <GA::State<EL, ER, EG> as CubeType>::ExpandType,
+}
+
+/// Expand type for [tensor output](TensorOutput).
+pub struct TensorOutputExpand<EL: Numeric, ER: Numeric, EG: Numeric, GA: MatmulArgs> {
+    state: <GA::State<EL, ER, EG> as CubeType>::ExpandType,
+}
+
+impl<EI: Numeric, EL: Numeric, ER: Numeric, EG: Numeric, MA: MatmulArgs> VirtualTensorOperations<EI>
+    for TensorInput<EI, EL, ER, EG, MA>
+{
+}
+impl<EL: Numeric, ER: Numeric, EG: Numeric, MA: MatmulArgs> VirtualTensorOperations<EG>
+    for TensorOutput<EL, ER, EG, MA>
+{
+}
+impl<EL: Numeric, ER: Numeric, EG: Numeric, MA: MatmulArgs> VirtualTensorOperations<EG>
+    for TensorBias<EL, ER, EG, MA>
+{
+}
+
+impl<EL: Numeric, ER: Numeric, EG: Numeric, MA: MatmulArgs> VirtualTensorOperationsExpand<EG>
+    for TensorOutputExpand<EL, ER, EG, MA>
+{
     fn __expand_read_method(
+        &self,
+        scope: &mut Scope,
+        index: ExpandElementTyped<u32>,
+    ) -> ExpandElementTyped<Line<EG>> {
+        TensorOutputExpand::__expand_read_method(self.clone(), scope, index)
+    }
+
+    fn __expand_read_wide_method(
         &self,
         _scope: &mut Scope,
-        _index: ExpandElementTyped<u32>,
+        _index: ExpandElementTyped<u64>,
     ) -> ExpandElementTyped<Line<EG>> {
+        panic!("The output is only read with 32-bit offsets");
+    }
+
+    fn __expand_read_window_method(
+        &self,
+        _scope: &mut Scope,
+        _start: ExpandElementTyped<u32>,
+        _end: ExpandElementTyped<u32>,
+    ) -> ExpandElementTyped<Slice<Line<EG>>> {
         panic!("Can't read output tensor");
     }
 
@@ -106,13 +250,32 @@ impl<EG: Numeric, MA: MatmulArgs> VirtualTensorOperationsExpand<EG> for TensorOu
     }
 }
 
-impl<EG: Numeric, MA: MatmulArgs> VirtualTensorOperationsExpand<EG> for TensorInputExpand<EG, MA> {
+impl<EL: Numeric, ER: Numeric, EG: Numeric, MA: MatmulArgs> VirtualTensorOperationsExpand<EG>
+    for TensorBiasExpand<EL, ER, EG, MA>
+{
     fn __expand_read_method(
         &self,
         scope: &mut Scope,
         index: ExpandElementTyped<u32>,
     ) -> ExpandElementTyped<Line<EG>> {
-        TensorInputExpand::__expand_read_method(self.clone(), scope, index)
+        TensorBiasExpand::__expand_read_method(self.clone(), scope, index)
+    }
+
+    fn __expand_read_wide_method(
+        &self,
+        _scope: &mut Scope,
+        _index: ExpandElementTyped<u64>,
+    ) -> ExpandElementTyped<Line<EG>> {
+        panic!("The bias is only read with 32-bit offsets");
+    }
+
+    fn __expand_read_window_method(
+        &self,
+        _scope: &mut Scope,
+        _start: ExpandElementTyped<u32>,
+        _end: ExpandElementTyped<u32>,
+    ) -> ExpandElementTyped<Slice<Line<EG>>> {
+        panic!("The bias is only read line by line");
     }
 
     fn __expand_write_method(
@@ -120,6 +283,64 @@ impl<EG: Numeric, MA: MatmulArgs> VirtualTensorOperationsExpand<EG> for TensorIn
         _scope: &mut Scope,
         _index: ExpandElementTyped<u32>,
         _value: ExpandElementTyped<Line<EG>>,
+    ) {
+        panic!("Can't write to bias tensor");
+    }
+
+    fn __expand_shape_method(
+        &self,
+        _scope: &mut Scope,
+        _axis: ExpandElementTyped<u32>,
+    ) -> ExpandElementTyped<u32> {
+        panic!("The bias is only read line by line");
+    }
+
+    fn __expand_stride_method(
+        &self,
+        _scope: &mut Scope,
+        _axis: ExpandElementTyped<u32>,
+    ) -> ExpandElementTyped<u32> {
+        panic!("The bias is only read line by line");
+    }
+
+    fn __expand_rank_method(&self, _scope: &mut Scope) -> ExpandElementTyped<u32> {
+        panic!("The bias is only read line by line");
+    }
+}
+
+impl<EI: Numeric, EL: Numeric, ER: Numeric, EG: Numeric, MA: MatmulArgs>
+    VirtualTensorOperationsExpand<EI> for TensorInputExpand<EI, EL, ER, EG, MA>
+{
+    fn __expand_read_method(
+        &self,
+        scope: &mut Scope,
+        index: ExpandElementTyped<u32>,
+    ) -> ExpandElementTyped<Line<EI>> {
+        TensorInputExpand::__expand_read_method(self.clone(), scope, index)
+    }
+
+    fn __expand_read_wide_method(
+        &self,
+        scope: &mut Scope,
+        index: ExpandElementTyped<u64>,
+    ) -> ExpandElementTyped<Line<EI>> {
+        TensorInputExpand::__expand_read_wide_method(self.clone(), scope, index)
+    }
+
+    fn __expand_read_window_method(
+        &self,
+        scope: &mut Scope,
+        start: ExpandElementTyped<u32>,
+        end: ExpandElementTyped<u32>,
+    ) -> ExpandElementTyped<Slice<Line<EI>>> {
+        TensorInputExpand::__expand_read_window_method(self.clone(), scope, start, end)
+    }
+
+    fn __expand_write_method(
+        &self,
+        _scope: &mut Scope,
+        _index: ExpandElementTyped<u32>,
+        _value: ExpandElementTyped<Line<EI>>,
     ) {
         panic!("Can't write to input tensor");
     }
@@ -145,41 +366,66 @@ impl<EG: Numeric, MA: MatmulArgs> VirtualTensorOperationsExpand<EG> for TensorIn
     }
 }
 
-/// Tensor output representation.
-///
-/// You can use the tensor output as if it was a pointer to the actually tensor.
-///
-/// # Warning
-///
-/// There is no mutability guarantee.
-pub struct TensorOutput<EG: Numeric, GA: MatmulArgs> {
-    state: *mut GA::State<EG>,
-}
-
-/// Expand type for [tensor input](TensorInput).
-pub struct TensorInputExpand<EG: Numeric, GA: MatmulArgs> {
-    state: <GA::State<EG> as CubeType>::ExpandType,
-    ident: TensorInputIdent,
-}
+impl<EI: Numeric, EL: Numeric, ER: Numeric, EG: Numeric, MA: MatmulArgs>
+    TensorInput<EI, EL, ER, EG, MA>
+{
+    /// Create a [tensor input](TensorInput) from the state and the [ident](TensorInputIdent).
+    #[allow(unused_variables)]
+    pub fn new(state: &MA::State<EL, ER, EG>, ident: TensorInputIdent) -> Self {
+        unexpanded!()
+    }
 
-/// Expand type for [tensor output](TensorOutput).
-pub struct TensorOutputExpand<EG: Numeric, GA: MatmulArgs> {
-    state: <GA::State<EG> as CubeType>::ExpandType,
+    pub fn __expand_new(
+        _scope: &mut Scope,
+        state: <MA::State<EL, ER, EG> as CubeType>::ExpandType,
+        ident: TensorInputIdent,
+    ) -> TensorInputExpand<EI, EL, ER, EG, MA> {
+        TensorInputExpand {
+            state,
+            ident,
+            _input: PhantomData,
+        }
+    }
 }
 
 #[cube]
-impl<EG: Numeric, MA: MatmulArgs> TensorInput<EG, MA> {
-    /// Create a [tensor input](TensorInput) from the state and the [ident](TensorInputIdent).
-    pub fn new(state: &MA::State<EG>, #[comptime] ident: TensorInputIdent) -> TensorInput<EG, MA> {
-        TensorInput::<EG, MA> { state, ident }
+impl<EI: Numeric, EL: Numeric, ER: Numeric, EG: Numeric, MA: MatmulArgs>
+    TensorInput<EI, EL, ER, EG, MA>
+{
+    /// Read the tensor at the given coordinate.
+    pub fn read(&self, coordinate: u32) -> Line<EI> {
+        unsafe {
+            match comptime![&self.ident] {
+                TensorInputIdent::Lhs => Line::cast_from(MA::read_lhs(&(*self.state), coordinate)),
+                TensorInputIdent::Rhs => Line::cast_from(MA::read_rhs(&(*self.state), coordinate)),
+            }
+        }
     }
 
-    /// Read the tensor at the given coordinate.
-    pub fn read(&self, coordinate: u32) -> Line<EG> {
+    /// Read the tensor at the given 64-bit coordinate.
+    pub fn read_wide(&self, coordinate: u64) -> Line<EI> {
         unsafe {
             match comptime![&self.ident] {
-                TensorInputIdent::Lhs => MA::read_lhs(&(*self.state), coordinate),
-                TensorInputIdent::Rhs => MA::read_rhs(&(*self.state), coordinate),
+                TensorInputIdent::Lhs => {
+                    Line::cast_from(MA::read_lhs_wide(&(*self.state), coordinate))
+                }
+                TensorInputIdent::Rhs => {
+                    Line::cast_from(MA::read_rhs_wide(&(*self.state), coordinate))
+                }
+            }
+        }
+    }
+
+    /// Read the tensor between the given coordinates.
+    pub fn read_window(&self, start: u32, end: u32) -> Slice<Line<EI>> {
+        unsafe {
+            match comptime![&self.ident] {
+                TensorInputIdent::Lhs => {
+                    input_slice::<EL, EI>(MA::read_window_lhs(&(*self.state), start, end))
+                }
+                TensorInputIdent::Rhs => {
+                    input_slice::<ER, EI>(MA::read_window_rhs(&(*self.state), start, end))
+                }
             }
         }
     }
@@ -215,11 +461,39 @@ impl<EG: Numeric, MA: MatmulArgs> TensorInput<EG, MA> {
     }
 }
 
+/// Views the slice of one side as a slice of the [tensor input](TensorInput) element, which is
+/// the same type.
+#[allow(unused_variables)]
+fn input_slice<E: Numeric, EI: Numeric>(slice: Slice<Line<E>>) -> Slice<Line<EI>> {
+    unexpanded!()
+}
+
+mod input_slice {
+    use super::*;
+
+    pub fn expand<E: Numeric, EI: Numeric>(
+        _scope: &mut Scope,
+        slice: ExpandElementTyped<Slice<Line<E>>>,
+    ) -> ExpandElementTyped<Slice<Line<EI>>> {
+        assert_eq!(
+            TypeId::of::<E>(),
+            TypeId::of::<EI>(),
+            "The element type of a tensor input must be the one of its side"
+        );
+        slice.expand.into()
+    }
+}
+
 #[cube]
-impl<EG: Numeric, GA: MatmulArgs> TensorOutput<EG, GA> {
+impl<EL: Numeric, ER: Numeric, EG: Numeric, GA: MatmulArgs> TensorOutput<EL, ER, EG, GA> {
     /// Create a [tensor output](TensorOutput) from the state.
-    pub fn new(state: &mut GA::State<EG>) -> TensorOutput<EG, GA> {
-        TensorOutput::<EG, GA> { state }
+    pub fn new(state: &mut GA::State<EL, ER, EG>) -> TensorOutput<EL, ER, EG, GA> {
+        TensorOutput::<EL, ER, EG, GA> { state }
+    }
+
+    /// Read the tensor at the given coordinate.
+    pub fn read(&self, coordinate: u32) -> Line<EG> {
+        unsafe { GA::read_out(&(*self.state), coordinate) }
     }
 
     /// Write the value to tensor at the given coordinate.
@@ -243,6 +517,19 @@ impl<EG: Numeric, GA: MatmulArgs> TensorOutput<EG, GA> {
     }
 }
 
+#[cube]
+impl<EL: Numeric, ER: Numeric, EG: Numeric, GA: MatmulArgs> TensorBias<EL, ER, EG, GA> {
+    /// Create a [bias](TensorBias) from the state.
+    pub fn new(state: &GA::State<EL, ER, EG>) -> TensorBias<EL, ER, EG, GA> {
+        TensorBias::<EL, ER, EG, GA> { state }
+    }
+
+    /// Read the bias at the given coordinate.
+    pub fn read(&self, coordinate: u32) -> Line<EG> {
+        unsafe { GA::read_bias(&(*self.state), coordinate) }
+    }
+}
+
 #[derive(Clone)]
 /// Type implementing [MatmulArgs] where all inputs and the output are materialized tensors.
 ///
@@ -251,109 +538,366 @@ pub struct TensorArgs;
 
 #[derive(CubeLaunch)]
 /// Input representation for [TensorArgs] implementing [MatmulArgs].
-pub struct TensorInputs<EG: Numeric> {
+pub struct TensorInputs<EL: Numeric, ER: Numeric> {
     /// The lhs tensor.
-    pub lhs: Tensor<Line<EG>>,
+    pub lhs: Tensor<Line<EL>>,
     /// The rhs tensor.
-    pub rhs: Tensor<Line<EG>>,
+    pub rhs: Tensor<Line<ER>>,
 }
 
 #[cube]
 impl MatmulArgs for TensorArgs {
     type Output<EG: Numeric> = Tensor<Line<EG>>;
-    type Input<EG: Numeric> = TensorInputs<EG>;
-    type State<EG: Numeric> = (
-        *const Tensor<Line<EG>>,
-        *const Tensor<Line<EG>>,
+    type Input<EL: Numeric, ER: Numeric> = TensorInputs<EL, ER>;
+    type State<EL: Numeric, ER: Numeric, EG: Numeric> = (
+        *const Tensor<Line<EL>>,
+        *const Tensor<Line<ER>>,
         *mut Tensor<Line<EG>>,
     );
 
-    fn init_state<EG: Numeric>(
-        input: &Self::Input<EG>,
+    fn init_state<EL: Numeric, ER: Numeric, EG: Numeric>(
+        input: &Self::Input<EL, ER>,
         output: &mut Self::Output<EG>,
-    ) -> Self::State<EG> {
+    ) -> Self::State<EL, ER, EG> {
         (&input.lhs, &input.rhs, output)
     }
 
-    fn read_lhs<EG: Numeric>(state: &Self::State<EG>, coordinate: u32) -> Line<EG> {
+    fn read_lhs<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        coordinate: u32,
+    ) -> Line<EL> {
         unsafe { (*state.0)[coordinate] }
     }
 
-    fn read_rhs<EG: Numeric>(state: &Self::State<EG>, coordinate: u32) -> Line<EG> {
+    fn read_rhs<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        coordinate: u32,
+    ) -> Line<ER> {
         unsafe { (*state.1)[coordinate] }
     }
 
-    fn shape_lhs<EG: Numeric>(state: &Self::State<EG>, dim: u32) -> u32 {
+    fn read_lhs_wide<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        coordinate: u64,
+    ) -> Line<EL> {
+        unsafe { *(*state.0).index_unchecked_wide(coordinate) }
+    }
+
+    fn read_rhs_wide<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        coordinate: u64,
+    ) -> Line<ER> {
+        unsafe { *(*state.1).index_unchecked_wide(coordinate) }
+    }
+
+    fn read_window_lhs<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        start: u32,
+        end: u32,
+    ) -> Slice<Line<EL>> {
+        unsafe { (*state.0).slice(start, end) }
+    }
+
+    fn read_window_rhs<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        start: u32,
+        end: u32,
+    ) -> Slice<Line<ER>> {
+        unsafe { (*state.1).slice(start, end) }
+    }
+
+    fn shape_lhs<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        dim: u32,
+    ) -> u32 {
         unsafe { (*state.0).shape(dim) }
     }
 
-    fn shape_rhs<EG: Numeric>(state: &Self::State<EG>, dim: u32) -> u32 {
+    fn shape_rhs<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        dim: u32,
+    ) -> u32 {
         unsafe { (*state.1).shape(dim) }
     }
 
-    fn shape_out<EG: Numeric>(state: &Self::State<EG>, dim: u32) -> u32 {
+    fn shape_out<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        dim: u32,
+    ) -> u32 {
         unsafe { (*state.2).shape(dim) }
     }
 
-    fn stride_lhs<EG: Numeric>(state: &Self::State<EG>, dim: u32) -> u32 {
+    fn stride_lhs<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        dim: u32,
+    ) -> u32 {
         unsafe { (*state.0).stride(dim) }
     }
 
-    fn stride_rhs<EG: Numeric>(state: &Self::State<EG>, dim: u32) -> u32 {
+    fn stride_rhs<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        dim: u32,
+    ) -> u32 {
         unsafe { (*state.1).stride(dim) }
     }
 
-    fn stride_out<EG: Numeric>(state: &Self::State<EG>, dim: u32) -> u32 {
+    fn stride_out<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        dim: u32,
+    ) -> u32 {
         unsafe { (*state.2).stride(dim) }
     }
 
-    fn write_out<EG: Numeric>(state: &mut Self::State<EG>, coordinate: u32, value: Line<EG>) {
+    fn read_out<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        coordinate: u32,
+    ) -> Line<EG> {
+        unsafe { (*state.2)[coordinate] }
+    }
+
+    fn read_bias<EL: Numeric, ER: Numeric, EG: Numeric>(
+        _state: &Self::State<EL, ER, EG>,
+        _coordinate: u32,
+    ) -> Line<EG> {
+        no_bias::<EG>()
+    }
+
+    fn write_out<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &mut Self::State<EL, ER, EG>,
+        coordinate: u32,
+        value: Line<EG>,
+    ) {
         unsafe { (*state.2)[coordinate] = value }
     }
 
-    fn rank_lhs<EG: Numeric>(state: &Self::State<EG>) -> u32 {
+    fn rank_lhs<EL: Numeric, ER: Numeric, EG: Numeric>(state: &Self::State<EL, ER, EG>) -> u32 {
         unsafe { (*state.0).rank() }
     }
 
-    fn rank_rhs<EG: Numeric>(state: &Self::State<EG>) -> u32 {
+    fn rank_rhs<EL: Numeric, ER: Numeric, EG: Numeric>(state: &Self::State<EL, ER, EG>) -> u32 {
         unsafe { (*state.1).rank() }
     }
 
-    fn rank_out<EG: Numeric>(state: &Self::State<EG>) -> u32 {
+    fn rank_out<EL: Numeric, ER: Numeric, EG: Numeric>(state: &Self::State<EL, ER, EG>) -> u32 {
         unsafe { (*state.2).rank() }
     }
 }
 
+/// Fails to expand a read of the bias of [TensorArgs], which don't have one.
+fn no_bias<EG: Numeric>() -> Line<EG> {
+    unexpanded!()
+}
+
+mod no_bias {
+    use super::*;
+
+    pub fn expand<EG: Numeric>(_scope: &mut Scope) -> ExpandElementTyped<Line<EG>> {
+        panic!(
+            "TensorArgs have no bias, the matmul must be launched with TensorBiasArgs to add one"
+        )
+    }
+}
+
+#[derive(Clone)]
+/// Type implementing [MatmulArgs] like [TensorArgs], with a materialized bias tensor of shape `[n]`
+/// launched along with the output.
+pub struct TensorBiasArgs;
+
+#[derive(CubeLaunch)]
+/// Output representation for [TensorBiasArgs] implementing [MatmulArgs].
+pub struct TensorOutputWithBias<EG: Numeric> {
+    /// The output tensor.
+    pub out: Tensor<Line<EG>>,
+    /// The bias tensor, read with the line size of the output.
+    pub bias: Tensor<Line<EG>>,
+}
+
+#[cube]
+impl MatmulArgs for TensorBiasArgs {
+    type Output<EG: Numeric> = TensorOutputWithBias<EG>;
+    type Input<EL: Numeric, ER: Numeric> = TensorInputs<EL, ER>;
+    type State<EL: Numeric, ER: Numeric, EG: Numeric> = (
+        <TensorArgs as MatmulArgs>::State<EL, ER, EG>,
+        *const Tensor<Line<EG>>,
+    );
+
+    fn init_state<EL: Numeric, ER: Numeric, EG: Numeric>(
+        input: &Self::Input<EL, ER>,
+        output: &mut Self::Output<EG>,
+    ) -> Self::State<EL, ER, EG> {
+        (
+            TensorArgs::init_state::<EL, ER, EG>(input, &mut output.out),
+            &output.bias,
+        )
+    }
+
+    fn read_lhs<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        coordinate: u32,
+    ) -> Line<EL> {
+        TensorArgs::read_lhs::<EL, ER, EG>(&state.0, coordinate)
+    }
+
+    fn read_rhs<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        coordinate: u32,
+    ) -> Line<ER> {
+        TensorArgs::read_rhs::<EL, ER, EG>(&state.0, coordinate)
+    }
+
+    fn read_lhs_wide<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        coordinate: u64,
+    ) -> Line<EL> {
+        TensorArgs::read_lhs_wide::<EL, ER, EG>(&state.0, coordinate)
+    }
+
+    fn read_rhs_wide<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        coordinate: u64,
+    ) -> Line<ER> {
+        TensorArgs::read_rhs_wide::<EL, ER, EG>(&state.0, coordinate)
+    }
+
+    fn read_window_lhs<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        start: u32,
+        end: u32,
+    ) -> Slice<Line<EL>> {
+        TensorArgs::read_window_lhs::<EL, ER, EG>(&state.0, start, end)
+    }
+
+    fn read_window_rhs<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        start: u32,
+        end: u32,
+    ) -> Slice<Line<ER>> {
+        TensorArgs::read_window_rhs::<EL, ER, EG>(&state.0, start, end)
+    }
+
+    fn shape_lhs<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        dim: u32,
+    ) -> u32 {
+        TensorArgs::shape_lhs::<EL, ER, EG>(&state.0, dim)
+    }
+
+    fn shape_rhs<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        dim: u32,
+    ) -> u32 {
+        TensorArgs::shape_rhs::<EL, ER, EG>(&state.0, dim)
+    }
+
+    fn shape_out<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        dim: u32,
+    ) -> u32 {
+        TensorArgs::shape_out::<EL, ER, EG>(&state.0, dim)
+    }
+
+    fn stride_lhs<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        dim: u32,
+    ) -> u32 {
+        TensorArgs::stride_lhs::<EL, ER, EG>(&state.0, dim)
+    }
+
+    fn stride_rhs<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        dim: u32,
+    ) -> u32 {
+        TensorArgs::stride_rhs::<EL, ER, EG>(&state.0, dim)
+    }
+
+    fn stride_out<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        dim: u32,
+    ) -> u32 {
+        TensorArgs::stride_out::<EL, ER, EG>(&state.0, dim)
+    }
+
+    fn read_out<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        coordinate: u32,
+    ) -> Line<EG> {
+        TensorArgs::read_out::<EL, ER, EG>(&state.0, coordinate)
+    }
+
+    fn read_bias<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &Self::State<EL, ER, EG>,
+        coordinate: u32,
+    ) -> Line<EG> {
+        unsafe { (*state.1)[coordinate] }
+    }
+
+    fn write_out<EL: Numeric, ER: Numeric, EG: Numeric>(
+        state: &mut Self::State<EL, ER, EG>,
+        coordinate: u32,
+        value: Line<EG>,
+    ) {
+        TensorArgs::write_out::<EL, ER, EG>(&mut state.0, coordinate, value)
+    }
+
+    fn rank_lhs<EL: Numeric, ER: Numeric, EG: Numeric>(state: &Self::State<EL, ER, EG>) -> u32 {
+        TensorArgs::rank_lhs::<EL, ER, EG>(&state.0)
+    }
+
+    fn rank_rhs<EL: Numeric, ER: Numeric, EG: Numeric>(state: &Self::State<EL, ER, EG>) -> u32 {
+        TensorArgs::rank_rhs::<EL, ER, EG>(&state.0)
+    }
+
+    fn rank_out<EL: Numeric, ER: Numeric, EG: Numeric>(state: &Self::State<EL, ER, EG>) -> u32 {
+        TensorArgs::rank_out::<EL, ER, EG>(&state.0)
+    }
+}
+
 mod __input {
     use super::*;
 
-    impl<EG: Numeric, GA: MatmulArgs> CubeType for TensorInput<EG, GA> {
-        type ExpandType = TensorInputExpand<EG, GA>;
+    impl<EI: Numeric, EL: Numeric, ER: Numeric, EG: Numeric, GA: MatmulArgs> CubeType
+        for TensorInput<EI, EL, ER, EG, GA>
+    {
+        type ExpandType = TensorInputExpand<EI, EL, ER, EG, GA>;
     }
 
-    impl<EG: Numeric, GA: MatmulArgs> Clone for TensorInputExpand<EG, GA> {
+    impl<EI: Numeric, EL: Numeric, ER: Numeric, EG: Numeric, GA: MatmulArgs> Clone
+        for TensorInputExpand<EI, EL, ER, EG, GA>
+    {
         fn clone(&self) -> Self {
             Self {
                 state: self.state.clone(),
                 ident: self.ident,
+                _input: PhantomData,
             }
         }
     }
 
-    impl<EG: Numeric, GA: MatmulArgs> Init for TensorInputExpand<EG, GA> {
+    impl<EI: Numeric, EL: Numeric, ER: Numeric, EG: Numeric, GA: MatmulArgs> Init
+        for TensorInputExpand<EI, EL, ER, EG, GA>
+    {
         fn init(mut self, scope: &mut Scope) -> Self {
             self.state = self.state.init(scope);
             self
         }
     }
-    impl<EG: Numeric, GA: MatmulArgs> Clone for TensorInput<EG, GA> {
+
+    impl<EI: Numeric, EL: Numeric, ER: Numeric, EG: Numeric, GA: MatmulArgs> Clone
+        for TensorInput<EI, EL, ER, EG, GA>
+    {
         fn clone(&self) -> Self {
             *self
         }
     }
-    impl<EG: Numeric, GA: MatmulArgs> Copy for TensorInput<EG, GA> {}
 
-    impl<EG: Numeric, GA: MatmulArgs> IntoRuntime for TensorInput<EG, GA> {
+    impl<EI: Numeric, EL: Numeric, ER: Numeric, EG: Numeric, GA: MatmulArgs> Copy
+        for TensorInput<EI, EL, ER, EG, GA>
+    {
+    }
+
+    impl<EI: Numeric, EL: Numeric, ER: Numeric, EG: Numeric, GA: MatmulArgs> IntoRuntime
+        for TensorInput<EI, EL, ER, EG, GA>
+    {
         fn __expand_runtime_method(self, _scope: &mut Scope) -> Self::ExpandType {
             panic!("Can't exist at compile time");
         }
@@ -363,17 +907,66 @@ mod __input {
 mod __output {
     use super::*;
 
-    impl<EG: Numeric, GA: MatmulArgs> CubeType for TensorOutput<EG, GA> {
-        type ExpandType = TensorOutputExpand<EG, GA>;
+    impl<EL: Numeric, ER: Numeric, EG: Numeric, GA: MatmulArgs> CubeType
+        for TensorOutput<EL, ER, EG, GA>
+    {
+        type ExpandType = TensorOutputExpand<EL, ER, EG, GA>;
+    }
+
+    impl<EL: Numeric, ER: Numeric, EG: Numeric, GA: MatmulArgs> Clone for TensorOutput<EL, ER, EG, GA> {
+        fn clone(&self) -> Self {
+            *self
+        }
+    }
+
+    impl<EL: Numeric, ER: Numeric, EG: Numeric, GA: MatmulArgs> Clone
+        for TensorOutputExpand<EL, ER, EG, GA>
+    {
+        fn clone(&self) -> Self {
+            Self {
+                state: self.state.clone(),
+            }
+        }
+    }
+
+    impl<EL: Numeric, ER: Numeric, EG: Numeric, GA: MatmulArgs> Init
+        for TensorOutputExpand<EL, ER, EG, GA>
+    {
+        fn init(mut self, scope: &mut Scope) -> Self {
+            self.state = self.state.init(scope);
+            self
+        }
+    }
+
+    impl<EL: Numeric, ER: Numeric, EG: Numeric, GA: MatmulArgs> Copy for TensorOutput<EL, ER, EG, GA> {}
+
+    impl<EL: Numeric, ER: Numeric, EG: Numeric, GA: MatmulArgs> IntoRuntime
+        for TensorOutput<EL, ER, EG, GA>
+    {
+        fn __expand_runtime_method(self, _scope: &mut Scope) -> Self::ExpandType {
+            panic!("Can't exist at compile time");
+        }
+    }
+}
+
+mod __bias {
+    use super::*;
+
+    impl<EL: Numeric, ER: Numeric, EG: Numeric, GA: MatmulArgs> CubeType
+        for TensorBias<EL, ER, EG, GA>
+    {
+        type ExpandType = TensorBiasExpand<EL, ER, EG, GA>;
     }
 
-    impl<EG: Numeric, GA: MatmulArgs> Clone for TensorOutput<EG, GA> {
+    impl<EL: Numeric, ER: Numeric, EG: Numeric, GA: MatmulArgs> Clone for TensorBias<EL, ER, EG, GA> {
         fn clone(&self) -> Self {
             *self
         }
     }
 
-    impl<EG: Numeric, GA: MatmulArgs> Clone for TensorOutputExpand<EG, GA> {
+    impl<EL: Numeric, ER: Numeric, EG: Numeric, GA: MatmulArgs> Clone
+        for TensorBiasExpand<EL, ER, EG, GA>
+    {
         fn clone(&self) -> Self {
             Self {
                 state: self.state.clone(),
@@ -381,16 +974,20 @@ mod __output {
         }
     }
 
-    impl<EG: Numeric, GA: MatmulArgs> Init for TensorOutputExpand<EG, GA> {
+    impl<EL: Numeric, ER: Numeric, EG: Numeric, GA: MatmulArgs> Init
+        for TensorBiasExpand<EL, ER, EG, GA>
+    {
         fn init(mut self, scope: &mut Scope) -> Self {
             self.state = self.state.init(scope);
             self
         }
     }
 
-    impl<EG: Numeric, GA: MatmulArgs> Copy for TensorOutput<EG, GA> {}
+    impl<EL: Numeric, ER: Numeric, EG: Numeric, GA: MatmulArgs> Copy for TensorBias<EL, ER, EG, GA> {}
 
-    impl<EG: Numeric, GA: MatmulArgs> IntoRuntime for TensorOutput<EG, GA> {
+    impl<EL: Numeric, ER: Numeric, EG: Numeric, GA: MatmulArgs> IntoRuntime
+        for TensorBias<EL, ER, EG, GA>
+    {
         fn __expand_runtime_method(self, _scope: &mut Scope) -> Self::ExpandType {
             panic!("Can't exist at compile time");
         }
@@ -1,6 +1,7 @@
 use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
 
+use crate::matmul::components::global::{EpilogueArgs, EpilogueOp, HasBeta};
 use crate::matmul::components::stage::{self, StageWriter, TilingOrderConfig};
 use crate::matmul::components::{config::MatmulConfig, tile};
 use crate::matmul::components::{Ident, MatrixLayout};
@@ -36,8 +37,8 @@ pub trait GlobalMatmulFamily:
 /// before loading data.
 pub trait GlobalMatmul<MP: MatmulPrecision>: 'static + Send + Sync {
     type Config: GlobalConfig;
-    type LhsLoader: InputLoader<MP::EG, MP::ES, Self::Config>;
-    type RhsLoader: InputLoader<MP::EG, MP::ES, Self::Config>;
+    type LhsLoader: InputLoader<MP::EL, MP::ES, Self::Config>;
+    type RhsLoader: InputLoader<MP::ER, MP::ES, Self::Config>;
     type AccumulatorLoader: CubeType;
     type Out: OutputLoader<MP::EG>;
     type Accumulator: CubeType;
@@ -59,7 +60,7 @@ pub trait GlobalMatmul<MP: MatmulPrecision>: 'static + Send + Sync {
 
     /// Initialize the loader for Lhs, starting at row m and column k
     fn init_lhs_loader(
-        lhs: VirtualTensor<MP::EG>,
+        lhs: VirtualTensor<MP::EL>,
         m_offset: u32,
         k_offset: u32,
         batch_offset: u32,
@@ -68,7 +69,7 @@ pub trait GlobalMatmul<MP: MatmulPrecision>: 'static + Send + Sync {
 
     /// Initialize the loader for Rhs, starting at row k and column n
     fn init_rhs_loader(
-        rhs: VirtualTensor<MP::EG>,
+        rhs: VirtualTensor<MP::ER>,
         k_offset: u32,
         n_offset: u32,
         batch_offset: u32,
@@ -78,9 +79,11 @@ pub trait GlobalMatmul<MP: MatmulPrecision>: 'static + Send + Sync {
     /// Initialize the unloader at row m and column n
     fn init_unloader(
         out: VirtualTensor<MP::EG, ReadWrite>,
+        epilogue_args: EpilogueArgs<MP::EG>,
         m_offset: u32,
         n_offset: u32,
         batch_offset: u32,
+        #[comptime] config: Self::Config,
     ) -> Self::Out;
 
     /// Initialize the accumulator without data
@@ -184,4 +187,17 @@ pub trait GlobalConfig: MatmulConfig {
 
     /// Whether we transpose data when loading to the stage
     fn transpose_load(&self, ident: Ident) -> bool;
+
+    /// Whether the output is accumulated into the values already in it, see [HasBeta]
+    fn beta(&self) -> HasBeta;
+
+    /// Whether a bias of shape `[n]` is added to every row of the output
+    fn has_bias(&self) -> bool;
+
+    /// The activation applied to the output right before it is written, see [EpilogueOp]
+    fn epilogue(&self) -> EpilogueOp;
+
+    /// Whether the inputs are read with 64-bit offsets, because they have too many elements to
+    /// be addressed with 32-bit ones
+    fn wide_offsets(&self) -> bool;
 }
@@ -3,6 +3,7 @@ use crate::matmul::components::global::tensor_view::TensorReader;
 use crate::matmul::components::global::{self, GlobalConfig, LoadingValidation};
 use crate::matmul::components::{Ident, InvalidConfigError};
 use cubecl_core as cubecl;
+use cubecl_core::prelude::pipeline::Pipeline;
 use cubecl_core::prelude::*;
 
 #[derive(CubeType, Clone, Copy)]
@@ -79,6 +80,57 @@ impl BufferLoading {
             buffer_slice[unit_position / line_size] = Line::cast_from(line_read);
         }
     }
+
+    /// Same as [load_to_slice](BufferLoading::load_to_slice), but the lines are copied
+    /// asynchronously through the pipeline, within the stage the caller acquired.
+    pub fn load_to_slice_async<EG: Numeric, ES: Numeric, G: global::GlobalConfig>(
+        read_view: &TensorReader<EG>,
+        buffer_slice: &mut SliceMut<Line<ES>>,
+        pipeline: &Pipeline<ES>,
+        #[comptime] num_producer_planes: u32,
+        #[comptime] producer_plane_offset: u32,
+        #[comptime] ident: Ident,
+        #[comptime] config: G,
+    ) {
+        let stage_dim = config.stage_dim(ident);
+        let line_size = config.global_line_size(ident);
+
+        let num_buffer_elements = stage_dim.buffer_num_elements();
+
+        let total_units = comptime!(num_producer_planes * config.plane_dim());
+        let jump_length = comptime!(total_units * line_size);
+        let num_loads_per_unit = num_buffer_elements / jump_length;
+
+        let plane_id = if comptime!(producer_plane_offset > 0) {
+            UNIT_POS_Y - producer_plane_offset
+        } else {
+            UNIT_POS_Y
+        };
+
+        let unit_id = plane_id * config.plane_dim() + UNIT_POS_X;
+        let unit_position_base = unit_id * line_size;
+
+        for i in 0..num_loads_per_unit {
+            let unit_position = unit_position_base + i * jump_length;
+
+            let tile_num_elements = stage_dim.tile_num_elements();
+            let nth_buffer_tile = unit_position / tile_num_elements;
+            let pos_within_tile = unit_position % tile_num_elements;
+
+            let (tile_x, tile_y) = get_tiles_x_y(nth_buffer_tile, ident);
+
+            let line_index = unit_position / line_size;
+            read_view.memcpy_coalesced_async::<ES, G>(
+                pipeline,
+                &mut buffer_slice.slice_mut(line_index, line_index + 1),
+                tile_x,
+                tile_y,
+                pos_within_tile,
+                ident,
+                config,
+            );
+        }
+    }
 }
 
 #[cube]
@@ -1,5 +1,5 @@
 use crate::matmul::components::global::output_loader::Unloader;
-use crate::matmul::components::global::{self, CommonGlobalConfig, InputLoader};
+use crate::matmul::components::global::{self, CommonGlobalConfig, EpilogueArgs, InputLoader};
 use crate::matmul::components::global::{GlobalConfig, ZeroAccumulatorLoader};
 use crate::matmul::components::stage::single_buffer::{LhsBufferReader, RhsBufferReader};
 use crate::matmul::components::Ident;
@@ -32,8 +32,8 @@ where
     >,
 {
     type Config = CommonGlobalConfig<SMM::Config>;
-    type LhsLoader = LhsBufferLoader<MP::EG, MP::ES, SMM::Config>;
-    type RhsLoader = RhsBufferLoader<MP::EG, MP::ES, SMM::Config>;
+    type LhsLoader = LhsBufferLoader<MP::EL, MP::ES, SMM::Config>;
+    type RhsLoader = RhsBufferLoader<MP::ER, MP::ES, SMM::Config>;
     type AccumulatorLoader = ZeroAccumulatorLoader;
     type Out = Unloader<MP::EG>;
     type Accumulator = SMM::Accumulator;
@@ -130,7 +130,7 @@ where
     }
 
     fn init_lhs_loader(
-        lhs: VirtualTensor<MP::EG>,
+        lhs: VirtualTensor<MP::EL>,
         x_offset: u32,
         y_offset: u32,
         batch_offset: u32,
@@ -140,7 +140,7 @@ where
     }
 
     fn init_rhs_loader(
-        rhs: VirtualTensor<MP::EG>,
+        rhs: VirtualTensor<MP::ER>,
         x_offset: u32,
         y_offset: u32,
         batch_offset: u32,
@@ -151,11 +151,22 @@ where
 
     fn init_unloader(
         out: VirtualTensor<MP::EG, ReadWrite>,
+        epilogue_args: EpilogueArgs<MP::EG>,
         x_offset: u32,
         y_offset: u32,
         batch_offset: u32,
+        #[comptime] config: Self::Config,
     ) -> Self::Out {
-        Self::Out::new(out, x_offset, y_offset, batch_offset)
+        Self::Out::new(
+            out,
+            epilogue_args,
+            x_offset,
+            y_offset,
+            batch_offset,
+            config.layout(Ident::Out),
+            config.epilogue(),
+            config.beta(),
+        )
     }
 
     fn init_accumulator(#[comptime] config: Self::Config) -> Self::Accumulator {
@@ -1,6 +1,6 @@
 use crate::matmul::components::global::buffered::buffer_loading::BufferLoading;
 use crate::matmul::components::global::{
-    CommonGlobalConfig, GlobalConfig, GlobalMatmulFamily, LoadingValidation,
+    CommonGlobalConfig, GlobalConfig, GlobalMatmulFamily, HasBeta, LoadingValidation,
 };
 use crate::matmul::components::stage::single_buffer::{
     LhsBufferReaderFamily, RhsBufferReaderFamily,
@@ -83,12 +83,17 @@ where
             problem.m as u32 % size.m != 0,
             problem.n as u32 % size.n != 0,
             problem.k as u32 % size.k != 0,
-            problem.lhs_layout,
-            problem.rhs_layout,
+            problem.lhs_layout.major(),
+            problem.rhs_layout.major(),
+            problem.out_layout.major(),
             problem.lhs_line_size as u32,
             problem.rhs_line_size as u32,
             problem.out_line_size as u32,
             cube_dim.y,
+            HasBeta::new(problem.beta),
+            problem.has_bias,
+            problem.epilogue,
+            problem.wide_offsets(),
         )
     }
 }
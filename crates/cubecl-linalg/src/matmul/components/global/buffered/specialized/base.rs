@@ -1,6 +1,7 @@
 use crate::matmul::components::global::output_loader::Unloader;
 use crate::matmul::components::global::{
-    GlobalConfig as _, GlobalMatmul, GlobalMatmulFamily, InputLoader,
+    EpilogueArgs, EpilogueOp, GlobalConfig as _, GlobalMatmul, GlobalMatmulFamily, HasBeta,
+    InputLoader,
 };
 use crate::matmul::components::stage::single_buffer::{
     LhsBufferReader, LhsBufferReaderFamily, RhsBufferReader, RhsBufferReaderFamily,
@@ -75,12 +76,17 @@ where
             problem.m as u32 % size.m != 0,
             problem.n as u32 % size.n != 0,
             problem.k as u32 % size.k != 0,
-            problem.lhs_layout,
-            problem.rhs_layout,
+            problem.lhs_layout.major(),
+            problem.rhs_layout.major(),
+            problem.out_layout.major(),
             problem.lhs_line_size as u32,
             problem.rhs_line_size as u32,
             problem.out_line_size as u32,
             cube_dim.y,
+            HasBeta::new(problem.beta),
+            problem.has_bias,
+            problem.epilogue,
+            problem.wide_offsets(),
         )
     }
 }
@@ -107,8 +113,8 @@ where
     >,
 {
     type Config = Config<SMM::Config>;
-    type LhsLoader = LhsBufferLoader<MP::EG, MP::ES, SMM::Config>;
-    type RhsLoader = RhsBufferLoader<MP::EG, MP::ES, SMM::Config>;
+    type LhsLoader = LhsBufferLoader<MP::EL, MP::ES, SMM::Config>;
+    type RhsLoader = RhsBufferLoader<MP::ER, MP::ES, SMM::Config>;
     type AccumulatorLoader = ZeroAccumulatorLoader;
     type Out = Unloader<MP::EG>;
     type Accumulator = SMM::Accumulator;
@@ -170,7 +176,7 @@ where
     }
 
     fn init_lhs_loader(
-        lhs: VirtualTensor<MP::EG>,
+        lhs: VirtualTensor<MP::EL>,
         x_offset: u32,
         y_offset: u32,
         batch_offset: u32,
@@ -187,7 +193,7 @@ where
     }
 
     fn init_rhs_loader(
-        rhs: VirtualTensor<MP::EG>,
+        rhs: VirtualTensor<MP::ER>,
         x_offset: u32,
         y_offset: u32,
         batch_offset: u32,
@@ -205,11 +211,22 @@ where
 
     fn init_unloader(
         out: VirtualTensor<MP::EG, ReadWrite>,
+        epilogue_args: EpilogueArgs<MP::EG>,
         x_offset: u32,
         y_offset: u32,
         batch_offset: u32,
+        #[comptime] config: Self::Config,
     ) -> Self::Out {
-        Self::Out::new(out, x_offset, y_offset, batch_offset)
+        Self::Out::new(
+            out,
+            epilogue_args,
+            x_offset,
+            y_offset,
+            batch_offset,
+            config.layout(Ident::Out),
+            config.epilogue(),
+            config.beta(),
+        )
     }
 
     fn init_accumulator(#[comptime] config: Self::Config) -> Self::Accumulator {
@@ -247,10 +264,15 @@ pub struct Config<S: stage::StageConfig> {
     check_k_bounds: bool,
     lhs_layout: MatrixLayout,
     rhs_layout: MatrixLayout,
+    out_layout: MatrixLayout,
     lhs_line_size: u32,
     rhs_line_size: u32,
     out_line_size: u32,
     num_planes: u32,
+    beta: HasBeta,
+    has_bias: bool,
+    epilogue: EpilogueOp,
+    wide_offsets: bool,
 }
 
 impl<S: stage::StageConfig> global::GlobalConfig for Config<S> {
@@ -264,7 +286,7 @@ impl<S: stage::StageConfig> global::GlobalConfig for Config<S> {
         match ident {
             Ident::Lhs => self.lhs_line_size,
             Ident::Rhs => self.rhs_line_size,
-            Ident::Out => self.out_line_size,
+            Ident::Out | Ident::Bias => self.out_line_size,
         }
     }
 
@@ -280,7 +302,8 @@ impl<S: stage::StageConfig> global::GlobalConfig for Config<S> {
         match ident {
             Ident::Lhs => self.lhs_layout,
             Ident::Rhs => self.rhs_layout,
-            Ident::Out => self.smm_config.layout(Ident::Out),
+            Ident::Out => self.out_layout,
+            Ident::Bias => MatrixLayout::RowMajor,
         }
     }
 
@@ -311,6 +334,22 @@ impl<S: stage::StageConfig> global::GlobalConfig for Config<S> {
     fn transpose_load(&self, ident: Ident) -> bool {
         self.layout(ident) != self.smm_config.layout(ident)
     }
+
+    fn beta(&self) -> HasBeta {
+        self.beta
+    }
+
+    fn has_bias(&self) -> bool {
+        self.has_bias
+    }
+
+    fn epilogue(&self) -> EpilogueOp {
+        self.epilogue
+    }
+
+    fn wide_offsets(&self) -> bool {
+        self.wide_offsets
+    }
 }
 
 impl<S: stage::StageConfig> MatmulConfig for Config<S> {}
@@ -324,10 +363,15 @@ impl<S: stage::StageConfig> Config<S> {
         check_k_bounds: bool,
         lhs_layout: MatrixLayout,
         rhs_layout: MatrixLayout,
+        out_layout: MatrixLayout,
         lhs_line_size: u32,
         rhs_line_size: u32,
         out_line_size: u32,
         num_planes: u32,
+        beta: HasBeta,
+        has_bias: bool,
+        epilogue: EpilogueOp,
+        wide_offsets: bool,
     ) -> Self {
         Self {
             smm_config,
@@ -336,10 +380,15 @@ impl<S: stage::StageConfig> Config<S> {
             check_k_bounds,
             lhs_layout,
             rhs_layout,
+            out_layout,
             lhs_line_size,
             rhs_line_size,
             out_line_size,
             num_planes,
+            beta,
+            has_bias,
+            epilogue,
+            wide_offsets,
         }
     }
 
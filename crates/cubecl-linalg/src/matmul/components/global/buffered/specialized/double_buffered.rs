@@ -0,0 +1,298 @@
+use crate::matmul::components::global::output_loader::Unloader;
+use crate::matmul::components::global::{
+    EpilogueArgs, GlobalConfig as _, GlobalMatmul, GlobalMatmulFamily, InputLoader,
+};
+use crate::matmul::components::stage::single_buffer::{
+    LhsBufferReader, LhsBufferReaderFamily, RhsBufferReader, RhsBufferReaderFamily,
+};
+use crate::matmul::components::stage::StageMatmul;
+use crate::matmul::components::Ident;
+use crate::matmul::components::{global, MatmulProblem};
+use crate::matmul::components::{global::ZeroAccumulatorLoader, StageDim as _};
+use crate::matmul::components::{stage, MatmulPrecision};
+use crate::matmul::components::{InvalidConfigError, MatmulConfigFactory};
+use crate::matmul::kernels::matmul::AdvancedConfig;
+use crate::matmul::kernels::MatmulAvailabilityError;
+use crate::tensor::{ReadWrite, VirtualTensor};
+
+use super::loader::{LhsBufferLoader, RhsBufferLoader};
+use super::{Config, SpecializedMatmulFamily};
+use cubecl_core as cubecl;
+use cubecl_core::prelude::pipeline::Pipeline;
+use cubecl_core::prelude::*;
+use cubecl_core::Feature;
+use std::marker::PhantomData;
+
+pub struct SpecializedDoubleBufferedMatmulFamily<SMM: stage::StageMatmulFamily> {
+    _stage_matmul: PhantomData<SMM>,
+}
+
+impl<SMM> GlobalMatmulFamily for SpecializedDoubleBufferedMatmulFamily<SMM>
+where
+    SMM: stage::StageMatmulFamily<
+        LhsReader = LhsBufferReaderFamily,
+        RhsReader = RhsBufferReaderFamily,
+    >,
+{
+    type Matmul<MP: MatmulPrecision> =
+        SpecializedDoubleBufferedMatmul<MP, SMM::Matmul<MP::ES, MP::EG, MP::EA>>;
+}
+
+impl<SMM> MatmulConfigFactory for SpecializedDoubleBufferedMatmulFamily<SMM>
+where
+    SMM: stage::StageMatmulFamily,
+{
+    type Input = SMM::Input;
+    type Config = Config<SMM::Config>;
+
+    fn check_config(config: &Self::Config) -> Result<(), InvalidConfigError> {
+        if config.stage_dim(Ident::Lhs).num_tiles_y_dim() != 2 {
+            return Err(Box::new(
+                "Double buffering needs exactly 2 buffers, one per pipeline stage.",
+            ));
+        }
+
+        SpecializedMatmulFamily::<SMM>::check_config(config)
+    }
+
+    fn check_availability<R: Runtime, MP: MatmulPrecision>(
+        client: &ComputeClient<R::Server, R::Channel>,
+        config: &Self::Config,
+    ) -> Result<(), MatmulAvailabilityError> {
+        if !client.properties().feature_enabled(Feature::Pipeline) {
+            return Err(MatmulAvailabilityError::PipelineUnavailable);
+        }
+
+        SpecializedMatmulFamily::<SMM>::check_availability::<R, MP>(client, config)
+    }
+
+    fn make_config(
+        input: Self::Input,
+        problem: &MatmulProblem,
+        cube_dim: &CubeDim,
+        cube_count: &CubeCount,
+        advanced_config: &AdvancedConfig,
+    ) -> Self::Config {
+        SpecializedMatmulFamily::<SMM>::make_config(
+            input,
+            problem,
+            cube_dim,
+            cube_count,
+            advanced_config,
+        )
+    }
+}
+
+/// Same as [SpecializedMatmul](super::SpecializedMatmul), but the producers copy the buffers
+/// asynchronously through a 2-stage pipeline.
+///
+/// While the consumers compute on one buffer, the copy of the other one is already in flight,
+/// so the loads of the producer planes overlap with the computation of the consumer planes.
+pub struct SpecializedDoubleBufferedMatmul<
+    MP: MatmulPrecision,
+    SMM: StageMatmul<MP::ES, MP::EG, MP::EA>,
+> {
+    _ms: PhantomData<MP>,
+    _stage_matmul: PhantomData<SMM>,
+}
+
+#[cube]
+impl<MP: MatmulPrecision, SMM> global::GlobalMatmul<MP> for SpecializedDoubleBufferedMatmul<MP, SMM>
+where
+    SMM: StageMatmul<
+        MP::ES,
+        MP::EG,
+        MP::EA,
+        LhsReader = LhsBufferReader<MP::ES>,
+        RhsReader = RhsBufferReader<MP::ES>,
+    >,
+{
+    type Config = Config<SMM::Config>;
+    type LhsLoader = LhsBufferLoader<MP::EL, MP::ES, SMM::Config>;
+    type RhsLoader = RhsBufferLoader<MP::ER, MP::ES, SMM::Config>;
+    type AccumulatorLoader = ZeroAccumulatorLoader;
+    type Out = Unloader<MP::EG>;
+    type Accumulator = SMM::Accumulator;
+
+    fn execute(
+        mut lhs_loader: Self::LhsLoader,
+        mut rhs_loader: Self::RhsLoader,
+        mut out_unloader: Self::Out,
+        acc: &mut Self::Accumulator,
+        k_range: (u32, u32),
+        #[comptime] config: Self::Config,
+    ) {
+        let is_consumer = Self::is_consumer(config);
+
+        let buffer_step = config.stage_dim(Ident::Lhs).tile_size_y_dim();
+        let k_step = 2 * buffer_step; // equal to SMM::K
+
+        let range = k_range.1 - k_range.0;
+        let num_loops = (range + k_step - 1) / k_step;
+
+        SMM::zero_accumulator(acc, config.to_smm_config());
+
+        let (mut lhs_tile, mut rhs_tile) = SMM::init_tile_inputs(config.to_smm_config());
+
+        let pipeline = Pipeline::<MP::ES>::new(2u32);
+
+        pipeline.producer_acquire();
+        Self::LhsLoader::fill_stage_async(&mut lhs_loader, &pipeline, config);
+        Self::RhsLoader::fill_stage_async(&mut rhs_loader, &pipeline, config);
+        pipeline.producer_commit();
+
+        let lhs_reader_a = &Self::LhsLoader::as_stage_reader(&lhs_loader);
+        let rhs_reader_a = &Self::RhsLoader::as_stage_reader(&rhs_loader);
+
+        Self::LhsLoader::advance_view(&mut lhs_loader, buffer_step);
+        Self::RhsLoader::advance_view(&mut rhs_loader, buffer_step);
+
+        let lhs_reader_b = &Self::LhsLoader::as_stage_reader(&lhs_loader);
+        let rhs_reader_b = &Self::RhsLoader::as_stage_reader(&rhs_loader);
+
+        for i in 0..num_loops {
+            // Buffer B is copied while buffer A is computed.
+            pipeline.producer_acquire();
+            Self::LhsLoader::fill_stage_async(&mut lhs_loader, &pipeline, config);
+            Self::RhsLoader::fill_stage_async(&mut rhs_loader, &pipeline, config);
+            pipeline.producer_commit();
+
+            Self::LhsLoader::advance_view(&mut lhs_loader, buffer_step);
+            Self::RhsLoader::advance_view(&mut rhs_loader, buffer_step);
+
+            pipeline.consumer_wait();
+            sync_units();
+
+            if is_consumer {
+                SMM::execute(
+                    lhs_reader_a,
+                    rhs_reader_a,
+                    &mut lhs_tile,
+                    &mut rhs_tile,
+                    acc,
+                    config.to_smm_config(),
+                );
+            }
+
+            pipeline.consumer_release();
+            sync_units();
+
+            // Buffer A is copied for the next iteration while buffer B is computed.
+            // The stage is committed even when empty, to keep waiting on the right one.
+            pipeline.producer_acquire();
+            if i + 1 < num_loops {
+                Self::LhsLoader::fill_stage_async(&mut lhs_loader, &pipeline, config);
+                Self::RhsLoader::fill_stage_async(&mut rhs_loader, &pipeline, config);
+            }
+            pipeline.producer_commit();
+
+            Self::LhsLoader::advance_view(&mut lhs_loader, buffer_step);
+            Self::RhsLoader::advance_view(&mut rhs_loader, buffer_step);
+
+            pipeline.consumer_wait();
+            sync_units();
+
+            if is_consumer {
+                SMM::execute(
+                    lhs_reader_b,
+                    rhs_reader_b,
+                    &mut lhs_tile,
+                    &mut rhs_tile,
+                    acc,
+                    config.to_smm_config(),
+                );
+            }
+
+            pipeline.consumer_release();
+            sync_units();
+        }
+
+        if is_consumer {
+            SMM::read_accumulator::<Self::Out, Self::Config>(
+                acc,
+                &mut out_unloader,
+                config.to_smm_config(),
+                config,
+            );
+        }
+    }
+
+    fn init_lhs_loader(
+        lhs: VirtualTensor<MP::EL>,
+        x_offset: u32,
+        y_offset: u32,
+        batch_offset: u32,
+        #[comptime] config: Self::Config,
+    ) -> Self::LhsLoader {
+        Self::LhsLoader::new(
+            lhs,
+            x_offset,
+            y_offset,
+            batch_offset,
+            !Self::is_consumer(config),
+            config,
+        )
+    }
+
+    fn init_rhs_loader(
+        rhs: VirtualTensor<MP::ER>,
+        x_offset: u32,
+        y_offset: u32,
+        batch_offset: u32,
+        #[comptime] config: Self::Config,
+    ) -> Self::RhsLoader {
+        Self::RhsLoader::new(
+            rhs,
+            x_offset,
+            y_offset,
+            batch_offset,
+            !Self::is_consumer(config),
+            config,
+        )
+    }
+
+    fn init_unloader(
+        out: VirtualTensor<MP::EG, ReadWrite>,
+        epilogue_args: EpilogueArgs<MP::EG>,
+        x_offset: u32,
+        y_offset: u32,
+        batch_offset: u32,
+        #[comptime] config: Self::Config,
+    ) -> Self::Out {
+        Self::Out::new(
+            out,
+            epilogue_args,
+            x_offset,
+            y_offset,
+            batch_offset,
+            config.layout(Ident::Out),
+            config.epilogue(),
+            config.beta(),
+        )
+    }
+
+    fn init_accumulator(#[comptime] config: Self::Config) -> Self::Accumulator {
+        SMM::init_accumulator(config.to_smm_config())
+    }
+
+    fn zero_accumulator(acc: &mut Self::Accumulator, #[comptime] config: Self::Config) {
+        SMM::zero_accumulator(acc, config.to_smm_config());
+    }
+}
+
+#[cube]
+impl<
+        MP: MatmulPrecision,
+        SMM: StageMatmul<
+            MP::ES,
+            MP::EG,
+            MP::EA,
+            LhsReader = LhsBufferReader<MP::ES>,
+            RhsReader = RhsBufferReader<MP::ES>,
+        >,
+    > SpecializedDoubleBufferedMatmul<MP, SMM>
+{
+    fn is_consumer(#[comptime] config: <Self as GlobalMatmul<MP>>::Config) -> bool {
+        UNIT_POS_Y < config.num_consumers()
+    }
+}
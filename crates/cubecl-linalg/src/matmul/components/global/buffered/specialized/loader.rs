@@ -12,6 +12,7 @@ use crate::matmul::components::stage::{self, Stage};
 use crate::matmul::components::{global, Ident};
 use crate::tensor::VirtualTensor;
 use cubecl_core as cubecl;
+use cubecl_core::prelude::pipeline::Pipeline;
 use cubecl_core::prelude::*;
 
 #[derive(CubeType)]
@@ -87,6 +88,25 @@ impl<EG: Numeric, ES: Numeric, S: stage::StageConfig> LhsBufferLoader<EG, ES, S>
             _config: PhantomData::<S>.runtime(),
         }
     }
+
+    /// Same as [fill_stage](InputLoader::fill_stage), but the producers copy the buffer
+    /// asynchronously through the pipeline.
+    pub fn fill_stage_async(
+        this: &mut Self,
+        pipeline: &Pipeline<ES>,
+        #[comptime] config: specialized::Config<S>,
+    ) {
+        if this.is_producer {
+            load_buffer_async::<EG, ES, S>(
+                this.buffer_iter,
+                &this.tensor_view,
+                &mut this.stage,
+                pipeline,
+                Ident::Lhs,
+                config,
+            );
+        }
+    }
 }
 
 #[cube]
@@ -142,6 +162,25 @@ impl<EG: Numeric, ES: Numeric, S: stage::StageConfig> RhsBufferLoader<EG, ES, S>
             _config: PhantomData::<S>.runtime(),
         }
     }
+
+    /// Same as [fill_stage](InputLoader::fill_stage), but the producers copy the buffer
+    /// asynchronously through the pipeline.
+    pub fn fill_stage_async(
+        this: &mut Self,
+        pipeline: &Pipeline<ES>,
+        #[comptime] config: specialized::Config<S>,
+    ) {
+        if this.is_producer {
+            load_buffer_async::<EG, ES, S>(
+                this.buffer_iter,
+                &this.tensor_view,
+                &mut this.stage,
+                pipeline,
+                Ident::Rhs,
+                config,
+            );
+        }
+    }
 }
 
 #[cube]
@@ -173,6 +212,37 @@ fn load_buffer<EG: Numeric, ES: Numeric, S: stage::StageConfig>(
     );
 }
 
+#[cube]
+fn load_buffer_async<EG: Numeric, ES: Numeric, S: stage::StageConfig>(
+    buffer_iter: u32,
+    tensor_view: &TensorReader<EG>,
+    stage: &mut Stage<ES>,
+    pipeline: &Pipeline<ES>,
+    #[comptime] ident: Ident,
+    #[comptime] config: specialized::Config<S>,
+) {
+    let buffer_num_elements = config.stage_dim(ident).buffer_num_elements();
+    let line_size = config.stage_line_size(ident);
+    let buffer_num_lines = buffer_num_elements / line_size;
+
+    #[allow(clippy::all)]
+    let _ = comptime!(check_buffers_contiguous(ident, config));
+
+    let start = buffer_iter * buffer_num_lines;
+    let end = start + buffer_num_lines;
+    let buffer_slice = &mut stage.as_slice_mut().slice_mut(start, end);
+
+    BufferLoading::load_to_slice_async::<EG, ES, specialized::Config<S>>(
+        tensor_view,
+        buffer_slice,
+        pipeline,
+        config.num_producers(),
+        config.num_consumers(),
+        ident,
+        config,
+    );
+}
+
 fn check_buffers_contiguous<G: global::GlobalConfig>(ident: Ident, config: G) {
     match ident.as_input() {
         InputIdent::Lhs => {
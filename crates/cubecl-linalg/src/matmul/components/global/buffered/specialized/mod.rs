@@ -1,4 +1,6 @@
 mod base;
+mod double_buffered;
 mod loader;
 
 pub use base::*;
+pub use double_buffered::*;
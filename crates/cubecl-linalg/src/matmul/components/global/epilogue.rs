@@ -0,0 +1,163 @@
+use cubecl_core::prelude::*;
+use cubecl_core::{self as cubecl, unexpanded};
+
+use crate::tensor::VirtualTensor;
+
+#[derive(CubeType, Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+/// Elementwise activation fused into the output write of a matmul.
+///
+/// The activation is applied on the global element right before it is written, which saves
+/// launching a separate activation kernel on the output.
+pub enum EpilogueOp {
+    /// Write the accumulator as is.
+    #[default]
+    None,
+    /// `max(x, 0)`
+    Relu,
+    /// Exact GELU, `x * 0.5 * (1 + erf(x / sqrt(2)))`. Does not rely on the tanh approximation.
+    Gelu,
+    /// Tanh approximation of GELU, `x * 0.5 * (1 + tanh(sqrt(2 / pi) * (x + 0.044715 * x^3)))`.
+    GeluTanh,
+    /// `1 / (1 + exp(-x))`
+    Sigmoid,
+}
+
+impl EpilogueOp {
+    /// Whether the operation relies on transcendental functions, which are only defined on
+    /// floats.
+    pub fn is_float_only(&self) -> bool {
+        matches!(
+            self,
+            EpilogueOp::Gelu | EpilogueOp::GeluTanh | EpilogueOp::Sigmoid
+        )
+    }
+}
+
+#[cube]
+/// Applies the epilogue operation to a line of the global element.
+///
+/// ReLU is defined on every [numeric](Numeric) element. GELU and sigmoid are computed in the
+/// element itself, which must be a float, so they keep the precision of the output.
+pub fn apply_epilogue<E: Numeric>(value: Line<E>, #[comptime] op: EpilogueOp) -> Line<E> {
+    match op {
+        EpilogueOp::None => value,
+        EpilogueOp::Relu => Max::max(value, Line::<E>::empty(value.size()).fill(E::from_int(0))),
+        _ => float_epilogue::<E>(value, op),
+    }
+}
+
+/// Applies an epilogue operation relying on transcendental functions, which [Numeric] doesn't
+/// provide, in the float element of `value`.
+#[allow(unused_variables)]
+fn float_epilogue<E: Numeric>(value: Line<E>, op: EpilogueOp) -> Line<E> {
+    unexpanded!()
+}
+
+mod float_epilogue {
+    use super::*;
+    use cubecl_core::frontend::{add, div, mul, unary_expand};
+    use cubecl_core::ir::{Arithmetic, ConstantScalarValue, Elem, UnaryOperator, Variable};
+
+    /// `sqrt(2 / pi)`, the slope of the tanh approximation of GELU at zero.
+    const SQRT_2_OVER_PI: f64 = 0.797_884_560_802_865_4;
+    /// Cubic coefficient of the tanh approximation of GELU.
+    const GELU_TANH_COEF: f64 = 0.044_715;
+
+    pub fn expand<E: Numeric>(
+        scope: &mut Scope,
+        x: ExpandElementTyped<Line<E>>,
+        op: EpilogueOp,
+    ) -> ExpandElementTyped<Line<E>> {
+        let kind = match x.expand.item.elem() {
+            Elem::Float(kind) => kind,
+            elem => panic!("The {op:?} epilogue is only defined on floats, got {elem}"),
+        };
+        // Scalar constants are broadcast to the line size of `x` by binary operations.
+        let constant = |value: f64| -> ExpandElementTyped<Line<E>> {
+            ExpandElement::Plain(Variable::constant(ConstantScalarValue::Float(value, kind))).into()
+        };
+        let unary = |scope: &mut Scope,
+                     x: ExpandElementTyped<Line<E>>,
+                     func: fn(UnaryOperator) -> Arithmetic|
+         -> ExpandElementTyped<Line<E>> {
+            unary_expand(scope, x.into(), func).into()
+        };
+
+        match op {
+            EpilogueOp::Gelu => {
+                let scaled =
+                    mul::expand(scope, x.clone(), constant(core::f64::consts::FRAC_1_SQRT_2));
+                let erf = unary(scope, scaled, Arithmetic::Erf);
+                let one_plus = add::expand(scope, erf, constant(1.0));
+                let half_x = mul::expand(scope, x, constant(0.5));
+                mul::expand(scope, half_x, one_plus)
+            }
+            EpilogueOp::GeluTanh => {
+                let square = mul::expand(scope, x.clone(), x.clone());
+                let cube = mul::expand(scope, square, x.clone());
+                let cube = mul::expand(scope, cube, constant(GELU_TANH_COEF));
+                let inner = add::expand(scope, x.clone(), cube);
+                let inner = mul::expand(scope, inner, constant(SQRT_2_OVER_PI));
+                let tanh = unary(scope, inner, Arithmetic::Tanh);
+                let one_plus = add::expand(scope, tanh, constant(1.0));
+                let half_x = mul::expand(scope, x, constant(0.5));
+                mul::expand(scope, half_x, one_plus)
+            }
+            EpilogueOp::Sigmoid => {
+                let negated = unary(scope, x, Arithmetic::Neg);
+                let exp = unary(scope, negated, Arithmetic::Exp);
+                let one_plus = add::expand(scope, exp, constant(1.0));
+                div::expand(scope, constant(1.0), one_plus)
+            }
+            EpilogueOp::None | EpilogueOp::Relu => {
+                unreachable!("{op:?} is computed on every numeric element")
+            }
+        }
+    }
+}
+
+#[derive(CubeType, Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+/// Whether the output of a matmul is accumulated into the values already in it.
+///
+/// With a beta, the unloader reads the existing output and writes
+/// `out = accumulator + beta * out`, before the bias and the epilogue are applied.
+/// Otherwise the existing output is never read, so it may be uninitialized.
+///
+/// Only whether the output is read is known at compile time, the value of beta is a runtime
+/// argument of the kernel, see [EpilogueArgs], so changing it doesn't compile a new kernel.
+pub enum HasBeta {
+    /// Overwrite the output.
+    #[default]
+    No,
+    /// Accumulate into the output, scaled by the beta of the [epilogue arguments](EpilogueArgs).
+    Yes,
+}
+
+impl HasBeta {
+    /// Whether the output is accumulated into with the given `beta`.
+    ///
+    /// A beta of zero doesn't read the output at all, as with [HasBeta::No].
+    pub fn new(beta: f32) -> Self {
+        match beta == 0.0 {
+            true => HasBeta::No,
+            false => HasBeta::Yes,
+        }
+    }
+}
+
+#[derive(CubeType)]
+/// Runtime arguments of the epilogue applied by the unloader.
+pub struct EpilogueArgs<EG: Numeric> {
+    /// Bias of shape `[n]` added to every row of the output, only read when the
+    /// [config](super::GlobalConfig::has_bias) has one.
+    pub bias: VirtualTensor<EG>,
+    /// Scale of the existing output, only read with [HasBeta::Yes].
+    pub beta: f32,
+}
+
+#[cube]
+impl<EG: Numeric> EpilogueArgs<EG> {
+    pub fn new(bias: VirtualTensor<EG>, beta: f32) -> Self {
+        EpilogueArgs::<EG> { bias, beta }
+    }
+}
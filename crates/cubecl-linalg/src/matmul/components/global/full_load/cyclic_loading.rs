@@ -5,9 +5,10 @@ use crate::matmul::components::stage::{
 };
 use crate::matmul::components::{Ident, InvalidConfigError, MatrixLayout};
 use cubecl_core as cubecl;
+use cubecl_core::prelude::pipeline::Pipeline;
 use cubecl_core::prelude::*;
 
-use super::loader::LoadingStrategy;
+use super::loader::{AsyncLoadingStrategy, LoadingStrategy};
 
 #[derive(CubeType, Clone, Copy)]
 /// Loads the content of all tiles in the tensor view using all planes,
@@ -30,7 +31,8 @@ impl LoadingValidation for CyclicLoading {
             ));
         }
 
-        if config.transpose_load(ident) && config.global_line_size(ident) != 1 {
+        // Lines read from global memory are split into elements when transposing.
+        if config.transpose_load(ident) && config.stage_line_size(ident) != 1 {
             return Err(Box::new(
                 "Line size for stage is not supported when transposing",
             ));
@@ -40,6 +42,19 @@ impl LoadingValidation for CyclicLoading {
     }
 }
 
+#[derive(CubeType, Clone, Copy)]
+/// Same as [CyclicLoading], but lines outside of the tensor are never read,
+/// so the tensor doesn't need to be padded to a multiple of the stage size.
+///
+/// Zeros are written to the stage in their place.
+pub struct MaskedCyclicLoading {}
+
+impl LoadingValidation for MaskedCyclicLoading {
+    fn check<C: GlobalConfig>(config: &C, ident: Ident) -> Result<(), InvalidConfigError> {
+        CyclicLoading::check(config, ident)
+    }
+}
+
 #[cube]
 impl LoadingStrategy for CyclicLoading {
     fn load_to_slice<EG: Numeric, ES: Numeric, G: GlobalConfig>(
@@ -48,69 +63,220 @@ impl LoadingStrategy for CyclicLoading {
         #[comptime] ident: Ident,
         #[comptime] config: G,
     ) {
-        let stage_dim = config.stage_dim(ident);
-        let line_size = config.global_line_size(ident);
-        let num_stage_lines = stage_dim.total_elements() / line_size;
-        let tile_num_lines = stage_dim.tile_num_elements() / line_size;
-        let jump_length = comptime!(config.num_planes() * config.plane_dim());
-        let num_loads_per_unit = comptime!(num_stage_lines / jump_length);
+        load_cyclic::<EG, ES, G>(read_view, slice, ident, config, false);
+    }
+}
+
+#[cube]
+impl LoadingStrategy for MaskedCyclicLoading {
+    fn load_to_slice<EG: Numeric, ES: Numeric, G: GlobalConfig>(
+        read_view: &TensorReader<EG>,
+        slice: &mut SliceMut<Line<ES>>,
+        #[comptime] ident: Ident,
+        #[comptime] config: G,
+    ) {
+        load_cyclic::<EG, ES, G>(read_view, slice, ident, config, true);
+    }
+}
+
+/// Positions that are out of bounds are never read, with or without masking.
+#[cube]
+impl AsyncLoadingStrategy for CyclicLoading {
+    fn load_to_slice_async<EG: Numeric, ES: Numeric, G: GlobalConfig>(
+        read_view: &TensorReader<EG>,
+        slice: &mut SliceMut<Line<ES>>,
+        pipeline: &Pipeline<ES>,
+        #[comptime] ident: Ident,
+        #[comptime] config: G,
+    ) {
+        load_cyclic_async::<EG, ES, G>(read_view, slice, pipeline, ident, config);
+    }
+}
+
+#[cube]
+impl AsyncLoadingStrategy for MaskedCyclicLoading {
+    fn load_to_slice_async<EG: Numeric, ES: Numeric, G: GlobalConfig>(
+        read_view: &TensorReader<EG>,
+        slice: &mut SliceMut<Line<ES>>,
+        pipeline: &Pipeline<ES>,
+        #[comptime] ident: Ident,
+        #[comptime] config: G,
+    ) {
+        load_cyclic_async::<EG, ES, G>(read_view, slice, pipeline, ident, config);
+    }
+}
+
+#[cube]
+fn load_cyclic<EG: Numeric, ES: Numeric, G: GlobalConfig>(
+    read_view: &TensorReader<EG>,
+    slice: &mut SliceMut<Line<ES>>,
+    #[comptime] ident: Ident,
+    #[comptime] config: G,
+    #[comptime] masked: bool,
+) {
+    let stage_dim = config.stage_dim(ident);
+    let line_size = config.global_line_size(ident);
+    let num_stage_lines = stage_dim.total_elements() / line_size;
+    let tile_num_lines = stage_dim.tile_num_elements() / line_size;
+    let jump_length = comptime!(config.num_planes() * config.plane_dim());
+    let num_loads_per_unit = comptime!(num_stage_lines / jump_length);
+
+    let unit_id = UNIT_POS_Y * config.plane_dim() + UNIT_POS_X;
 
-        let unit_id = UNIT_POS_Y * config.plane_dim() + UNIT_POS_X;
+    for i in 0..num_loads_per_unit {
+        let unit_position = unit_id + i * jump_length;
 
-        for i in 0..num_loads_per_unit {
-            let unit_position = unit_id + i * jump_length;
+        let nth_tile = (unit_position) / tile_num_lines;
+        let (tile_x, tile_y) = tile_position::<G>(nth_tile, ident, config);
 
-            let nth_tile = (unit_position) / tile_num_lines;
-            let (tile_x, tile_y) = match config.tiling_order(ident) {
-                TilingOrderConfig::RowMajor => RowMajorTiling::to_x_y(
+        let pos_within_tile = (unit_position % tile_num_lines) * line_size;
+
+        let line_read = match comptime!(masked) {
+            true => {
+                read_view.load_coalesced_masked::<G>(tile_x, tile_y, pos_within_tile, ident, config)
+            }
+            false => read_view.load_coalesced::<G>(tile_x, tile_y, pos_within_tile, ident, config),
+        };
+
+        match config.transpose_load(ident) {
+            false => {
+                slice[unit_position] = Line::cast_from(line_read);
+            }
+            true => {
+                store_transposed::<EG, ES, G>(
+                    line_read,
+                    slice,
                     nth_tile,
-                    stage_dim.num_tiles_x_dim(),
-                    stage_dim.num_tiles_y_dim(),
-                ),
-                TilingOrderConfig::ColMajor => ColMajorTiling::to_x_y(
+                    pos_within_tile,
+                    ident,
+                    config,
+                );
+            }
+        }
+    }
+}
+
+/// Same as [load_cyclic], but the lines are copied asynchronously through the pipeline.
+///
+/// Transposed lines are split into elements, so they are read and written right away instead.
+#[cube]
+fn load_cyclic_async<EG: Numeric, ES: Numeric, G: GlobalConfig>(
+    read_view: &TensorReader<EG>,
+    slice: &mut SliceMut<Line<ES>>,
+    pipeline: &Pipeline<ES>,
+    #[comptime] ident: Ident,
+    #[comptime] config: G,
+) {
+    let stage_dim = config.stage_dim(ident);
+    let line_size = config.global_line_size(ident);
+    let num_stage_lines = stage_dim.total_elements() / line_size;
+    let tile_num_lines = stage_dim.tile_num_elements() / line_size;
+    let jump_length = comptime!(config.num_planes() * config.plane_dim());
+    let num_loads_per_unit = comptime!(num_stage_lines / jump_length);
+
+    let unit_id = UNIT_POS_Y * config.plane_dim() + UNIT_POS_X;
+
+    for i in 0..num_loads_per_unit {
+        let unit_position = unit_id + i * jump_length;
+
+        let nth_tile = (unit_position) / tile_num_lines;
+        let (tile_x, tile_y) = tile_position::<G>(nth_tile, ident, config);
+
+        let pos_within_tile = (unit_position % tile_num_lines) * line_size;
+
+        match config.transpose_load(ident) {
+            false => {
+                read_view.memcpy_coalesced_async::<ES, G>(
+                    pipeline,
+                    &mut slice.slice_mut(unit_position, unit_position + 1),
+                    tile_x,
+                    tile_y,
+                    pos_within_tile,
+                    ident,
+                    config,
+                );
+            }
+            true => {
+                let line_read = read_view.load_coalesced_masked::<G>(
+                    tile_x,
+                    tile_y,
+                    pos_within_tile,
+                    ident,
+                    config,
+                );
+                store_transposed::<EG, ES, G>(
+                    line_read,
+                    slice,
                     nth_tile,
-                    stage_dim.num_tiles_x_dim(),
-                    stage_dim.num_tiles_y_dim(),
-                ),
-            };
-
-            let pos_within_tile = (unit_position % tile_num_lines) * line_size;
-
-            let line_read =
-                read_view.load_coalesced::<G>(tile_x, tile_y, pos_within_tile, ident, config);
-
-            match config.transpose_load(ident) {
-                false => {
-                    slice[unit_position] = Line::cast_from(line_read);
-                }
-                true => {
-                    let tile_offset = nth_tile * tile_num_lines * line_size;
-
-                    let tile_size_x = config.stage_dim(ident).tile_size_x_dim();
-                    let tile_size_y = config.stage_dim(ident).tile_size_y_dim();
-
-                    let (height, width) = match config.layout(ident) {
-                        MatrixLayout::RowMajor => (tile_size_x, tile_size_y),
-                        MatrixLayout::ColMajor => (tile_size_y, tile_size_x),
-                    };
-
-                    let global_strided_idx = pos_within_tile / width;
-                    let global_contiguous_idx = pos_within_tile % width;
-
-                    let slice_strided_root = global_contiguous_idx;
-                    let slice_contiguous_idx = global_strided_idx;
-                    let slice_stride = height;
-
-                    #[unroll]
-                    for iter in 0..config.global_line_size(ident) {
-                        let slice_strided_idx = slice_strided_root + iter;
-                        let elem = line_read[iter];
-                        slice[tile_offset
-                            + slice_strided_idx * slice_stride
-                            + slice_contiguous_idx] = Line::cast_from(elem);
-                    }
-                }
+                    pos_within_tile,
+                    ident,
+                    config,
+                );
             }
         }
     }
 }
+
+/// Returns the coordinates of the nth tile of the stage, in its tiling order.
+#[cube]
+fn tile_position<G: GlobalConfig>(
+    nth_tile: u32,
+    #[comptime] ident: Ident,
+    #[comptime] config: G,
+) -> (u32, u32) {
+    let stage_dim = config.stage_dim(ident);
+
+    match config.tiling_order(ident) {
+        TilingOrderConfig::RowMajor => RowMajorTiling::to_x_y(
+            nth_tile,
+            stage_dim.num_tiles_x_dim(),
+            stage_dim.num_tiles_y_dim(),
+        ),
+        TilingOrderConfig::ColMajor => ColMajorTiling::to_x_y(
+            nth_tile,
+            stage_dim.num_tiles_x_dim(),
+            stage_dim.num_tiles_y_dim(),
+        ),
+    }
+}
+
+/// Writes the elements of a line read at `pos_within_tile` of the nth tile to their
+/// transposed positions in the stage.
+#[cube]
+fn store_transposed<EG: Numeric, ES: Numeric, G: GlobalConfig>(
+    line_read: Line<EG>,
+    slice: &mut SliceMut<Line<ES>>,
+    nth_tile: u32,
+    pos_within_tile: u32,
+    #[comptime] ident: Ident,
+    #[comptime] config: G,
+) {
+    let stage_dim = config.stage_dim(ident);
+    let tile_offset = nth_tile * stage_dim.tile_num_elements();
+
+    let tile_size_x = stage_dim.tile_size_x_dim();
+    let tile_size_y = stage_dim.tile_size_y_dim();
+
+    let (height, width) = match config.layout(ident) {
+        MatrixLayout::RowMajor => (tile_size_x, tile_size_y),
+        MatrixLayout::ColMajor => (tile_size_y, tile_size_x),
+        MatrixLayout::Strided { .. } => {
+            unreachable!("Configs only hold contiguous layouts")
+        }
+    };
+
+    let global_strided_idx = pos_within_tile / width;
+    let global_contiguous_idx = pos_within_tile % width;
+
+    let slice_strided_root = global_contiguous_idx;
+    let slice_contiguous_idx = global_strided_idx;
+    let slice_stride = height;
+
+    #[unroll]
+    for iter in 0..config.global_line_size(ident) {
+        let slice_strided_idx = slice_strided_root + iter;
+        let elem = line_read[iter];
+        slice[tile_offset + slice_strided_idx * slice_stride + slice_contiguous_idx] =
+            Line::cast_from(elem);
+    }
+}
@@ -8,6 +8,7 @@ use crate::matmul::components::stage::{self, Stage};
 use crate::matmul::components::{global, Ident};
 use crate::tensor::VirtualTensor;
 use cubecl_core as cubecl;
+use cubecl_core::prelude::pipeline::Pipeline;
 use cubecl_core::prelude::*;
 
 #[derive(CubeType)]
@@ -71,6 +72,27 @@ impl<EG: Numeric, ES: Numeric, S: stage::StageConfig, L: LoadingStrategy> LhsLoa
     }
 }
 
+#[cube]
+impl<EG: Numeric, ES: Numeric, S: stage::StageConfig, L: AsyncLoadingStrategy>
+    LhsLoader<EG, ES, S, L>
+{
+    /// Same as [fill_stage](InputLoader::fill_stage), but the stage is copied asynchronously
+    /// through the pipeline, within the stage the caller acquired.
+    pub fn fill_stage_async(
+        this: &mut Self,
+        pipeline: &Pipeline<ES>,
+        #[comptime] config: full_load::Config<S>,
+    ) {
+        L::load_to_slice_async::<EG, ES, full_load::Config<S>>(
+            &this.tensor_view,
+            &mut this.stage.as_slice_mut(),
+            pipeline,
+            Ident::Lhs,
+            config,
+        );
+    }
+}
+
 #[cube]
 impl<EG: Numeric, ES: Numeric, S: stage::StageConfig, L: LoadingStrategy>
     InputLoader<EG, ES, full_load::Config<S>> for RhsLoader<EG, ES, S, L>
@@ -116,6 +138,27 @@ impl<EG: Numeric, ES: Numeric, S: stage::StageConfig, L: LoadingStrategy> RhsLoa
     }
 }
 
+#[cube]
+impl<EG: Numeric, ES: Numeric, S: stage::StageConfig, L: AsyncLoadingStrategy>
+    RhsLoader<EG, ES, S, L>
+{
+    /// Same as [fill_stage](InputLoader::fill_stage), but the stage is copied asynchronously
+    /// through the pipeline, within the stage the caller acquired.
+    pub fn fill_stage_async(
+        this: &mut Self,
+        pipeline: &Pipeline<ES>,
+        #[comptime] config: full_load::Config<S>,
+    ) {
+        L::load_to_slice_async::<EG, ES, full_load::Config<S>>(
+            &this.tensor_view,
+            &mut this.stage.as_slice_mut(),
+            pipeline,
+            Ident::Rhs,
+            config,
+        );
+    }
+}
+
 #[cube]
 pub trait LoadingStrategy: 'static + Send + Sync + Clone + LoadingValidation {
     fn load_to_slice<EG: Numeric, ES: Numeric, G: global::GlobalConfig>(
@@ -125,3 +168,17 @@ pub trait LoadingStrategy: 'static + Send + Sync + Clone + LoadingValidation {
         #[comptime] config: G,
     );
 }
+
+#[cube]
+/// A [LoadingStrategy] that can also copy the stage asynchronously through a pipeline.
+pub trait AsyncLoadingStrategy: LoadingStrategy {
+    /// Same as [load_to_slice](LoadingStrategy::load_to_slice), but the lines are copied
+    /// asynchronously through the pipeline, within the stage the caller acquired.
+    fn load_to_slice_async<EG: Numeric, ES: Numeric, G: global::GlobalConfig>(
+        read_view: &TensorReader<EG>,
+        slice: &mut SliceMut<Line<ES>>,
+        pipeline: &Pipeline<ES>,
+        #[comptime] ident: Ident,
+        #[comptime] config: G,
+    );
+}
@@ -1,9 +1,11 @@
 mod base;
 mod cyclic_loading;
 mod loader;
+mod prefetching;
 mod tilewise_loading;
 
 pub use base::*;
 pub use cyclic_loading::*;
 pub use loader::*;
+pub use prefetching::*;
 pub use tilewise_loading::*;
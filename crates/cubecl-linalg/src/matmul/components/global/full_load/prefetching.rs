@@ -0,0 +1,332 @@
+use crate::matmul::components::global::output_loader::Unloader;
+use crate::matmul::components::global::{
+    EpilogueArgs, GlobalConfig, GlobalMatmul, GlobalMatmulFamily, InputLoader,
+};
+use crate::matmul::components::stage::multi_buffer::{
+    LhsReader, LhsReaderFamily, RhsReader, RhsReaderFamily,
+};
+use crate::matmul::components::stage::StageMatmul;
+use crate::matmul::components::{global::ZeroAccumulatorLoader, MatmulProblem};
+use crate::matmul::components::{stage, Ident, InvalidConfigError};
+use crate::matmul::components::{MatmulConfigFactory, MatmulPrecision};
+use crate::matmul::kernels::matmul::AdvancedConfig;
+use crate::matmul::kernels::MatmulAvailabilityError;
+use crate::tensor::{ReadWrite, VirtualTensor};
+
+use cubecl_core as cubecl;
+use cubecl_core::prelude::pipeline::Pipeline;
+use cubecl_core::prelude::*;
+use cubecl_core::Feature;
+use std::marker::PhantomData;
+
+use super::loader::{AsyncLoadingStrategy, LhsLoader, RhsLoader};
+use super::{Config, FullLoadMatmulFamily};
+
+pub struct PrefetchingFullLoadMatmulFamily<
+    SMM: stage::StageMatmulFamily,
+    LL: AsyncLoadingStrategy,
+    RL: AsyncLoadingStrategy,
+    const DEPTH: u32 = 2,
+> {
+    _stage_matmul: PhantomData<SMM>,
+    _lhs_loading: PhantomData<LL>,
+    _rhs_loading: PhantomData<RL>,
+}
+
+impl<SMM, LL, RL, const DEPTH: u32> GlobalMatmulFamily
+    for PrefetchingFullLoadMatmulFamily<SMM, LL, RL, DEPTH>
+where
+    SMM: stage::StageMatmulFamily<LhsReader = LhsReaderFamily, RhsReader = RhsReaderFamily>,
+    LL: AsyncLoadingStrategy,
+    RL: AsyncLoadingStrategy,
+{
+    type Matmul<MP: MatmulPrecision> =
+        PrefetchingFullLoadMatmul<MP, SMM::Matmul<MP::ES, MP::EG, MP::EA>, LL, RL, DEPTH>;
+}
+
+impl<SMM, LL, RL, const DEPTH: u32> MatmulConfigFactory
+    for PrefetchingFullLoadMatmulFamily<SMM, LL, RL, DEPTH>
+where
+    SMM: stage::StageMatmulFamily,
+    LL: AsyncLoadingStrategy,
+    RL: AsyncLoadingStrategy,
+{
+    type Input = SMM::Input;
+    type Config = Config<SMM::Config>;
+
+    fn check_config(config: &Self::Config) -> Result<(), InvalidConfigError> {
+        if DEPTH < 2 {
+            return Err(Box::new(
+                "Prefetching needs a pipeline of at least 2 stages, one computed and one in flight.",
+            ));
+        }
+
+        FullLoadMatmulFamily::<SMM, LL, RL>::check_config(config)
+    }
+
+    fn check_availability<R: Runtime, MP: MatmulPrecision>(
+        client: &ComputeClient<R::Server, R::Channel>,
+        config: &Self::Config,
+    ) -> Result<(), MatmulAvailabilityError> {
+        if !client.properties().feature_enabled(Feature::Pipeline) {
+            return Err(MatmulAvailabilityError::PipelineUnavailable);
+        }
+
+        FullLoadMatmulFamily::<SMM, LL, RL>::check_availability::<R, MP>(client, config)
+    }
+
+    fn make_config(
+        input: Self::Input,
+        problem: &MatmulProblem,
+        cube_dim: &CubeDim,
+        cube_count: &CubeCount,
+        advanced_config: &AdvancedConfig,
+    ) -> Self::Config {
+        FullLoadMatmulFamily::<SMM, LL, RL>::make_config(
+            input,
+            problem,
+            cube_dim,
+            cube_count,
+            advanced_config,
+        )
+    }
+}
+
+/// Same as [FullLoadMatmul](super::FullLoadMatmul), but the stages are copied asynchronously
+/// through a pipeline of `DEPTH` stages, each with its own loaders and shared memory.
+///
+/// While the stage at k is computed, the copies of the stages at k + 1 up to k + DEPTH - 1 are
+/// already in flight, so the loads of the next iterations overlap with the computation.
+pub struct PrefetchingFullLoadMatmul<
+    MP: MatmulPrecision,
+    SMM: StageMatmul<MP::ES, MP::EG, MP::EA>,
+    LL: AsyncLoadingStrategy,
+    RL: AsyncLoadingStrategy,
+    const DEPTH: u32,
+> {
+    _ms: PhantomData<MP>,
+    _stage_matmul: PhantomData<SMM>,
+    _lhs_loading: PhantomData<LL>,
+    _rhs_loading: PhantomData<RL>,
+}
+
+#[cube]
+impl<MP: MatmulPrecision, SMM, LL, RL, const DEPTH: u32> GlobalMatmul<MP>
+    for PrefetchingFullLoadMatmul<MP, SMM, LL, RL, DEPTH>
+where
+    SMM: StageMatmul<
+        MP::ES,
+        MP::EG,
+        MP::EA,
+        LhsReader = LhsReader<MP::ES>,
+        RhsReader = RhsReader<MP::ES>,
+    >,
+    LL: AsyncLoadingStrategy,
+    RL: AsyncLoadingStrategy,
+{
+    type Config = Config<SMM::Config>;
+    type LhsLoader = PrefetchingLoader<LhsLoader<MP::EL, MP::ES, SMM::Config, LL>>;
+    type RhsLoader = PrefetchingLoader<RhsLoader<MP::ER, MP::ES, SMM::Config, RL>>;
+    type AccumulatorLoader = ZeroAccumulatorLoader;
+    type Out = Unloader<MP::EG>;
+    type Accumulator = SMM::Accumulator;
+
+    fn execute(
+        mut lhs_loader: Self::LhsLoader,
+        mut rhs_loader: Self::RhsLoader,
+        mut out_unloader: Self::Out,
+        acc: &mut Self::Accumulator,
+        k_range: (u32, u32),
+        #[comptime] config: Self::Config,
+    ) {
+        let k_step = config.k_step;
+        let range = k_range.1 - k_range.0;
+        let num_loops = (range + k_step - 1) / k_step;
+        let num_rounds = (num_loops + DEPTH - 1) / DEPTH;
+        let pipeline_step = comptime!(DEPTH * k_step);
+
+        let (mut lhs_tile, mut rhs_tile) = SMM::init_tile_inputs(config.to_smm_config());
+        SMM::zero_accumulator(acc, config.to_smm_config());
+
+        let pipeline = Pipeline::<MP::ES>::new(DEPTH);
+
+        // The first DEPTH stages are put in flight before computing anything.
+        // Stages are committed even when empty, to keep waiting on the right one.
+        #[unroll]
+        for slot in 0..DEPTH {
+            let lhs_slot = lhs_loader.loaders.index_mut(slot);
+            let rhs_slot = rhs_loader.loaders.index_mut(slot);
+
+            pipeline.producer_acquire();
+            if slot < num_loops {
+                LhsLoader::<MP::EL, MP::ES, SMM::Config, LL>::fill_stage_async(
+                    lhs_slot, &pipeline, config,
+                );
+                RhsLoader::<MP::ER, MP::ES, SMM::Config, RL>::fill_stage_async(
+                    rhs_slot, &pipeline, config,
+                );
+            }
+            pipeline.producer_commit();
+
+            LhsLoader::<MP::EL, MP::ES, SMM::Config, LL>::advance_view(lhs_slot, pipeline_step);
+            RhsLoader::<MP::ER, MP::ES, SMM::Config, RL>::advance_view(rhs_slot, pipeline_step);
+        }
+
+        for round in 0..num_rounds {
+            #[unroll]
+            for slot in 0..DEPTH {
+                let k_iter = round * DEPTH + slot;
+                let lhs_slot = lhs_loader.loaders.index_mut(slot);
+                let rhs_slot = rhs_loader.loaders.index_mut(slot);
+
+                pipeline.consumer_wait();
+                sync_units();
+
+                if k_iter < num_loops {
+                    SMM::execute(
+                        &LhsLoader::<MP::EL, MP::ES, SMM::Config, LL>::as_stage_reader(lhs_slot),
+                        &RhsLoader::<MP::ER, MP::ES, SMM::Config, RL>::as_stage_reader(rhs_slot),
+                        &mut lhs_tile,
+                        &mut rhs_tile,
+                        acc,
+                        config.to_smm_config(),
+                    );
+                }
+
+                pipeline.consumer_release();
+                sync_units();
+
+                // The stage that was just computed is refilled DEPTH iterations ahead.
+                pipeline.producer_acquire();
+                if k_iter + DEPTH < num_loops {
+                    LhsLoader::<MP::EL, MP::ES, SMM::Config, LL>::fill_stage_async(
+                        lhs_slot, &pipeline, config,
+                    );
+                    RhsLoader::<MP::ER, MP::ES, SMM::Config, RL>::fill_stage_async(
+                        rhs_slot, &pipeline, config,
+                    );
+                }
+                pipeline.producer_commit();
+
+                LhsLoader::<MP::EL, MP::ES, SMM::Config, LL>::advance_view(lhs_slot, pipeline_step);
+                RhsLoader::<MP::ER, MP::ES, SMM::Config, RL>::advance_view(rhs_slot, pipeline_step);
+            }
+        }
+
+        sync_units();
+
+        SMM::read_accumulator::<Self::Out, Self::Config>(
+            acc,
+            &mut out_unloader,
+            config.to_smm_config(),
+            config,
+        );
+    }
+
+    fn init_lhs_loader(
+        lhs: VirtualTensor<MP::EL>,
+        x_offset: u32,
+        y_offset: u32,
+        batch_offset: u32,
+        #[comptime] config: Self::Config,
+    ) -> Self::LhsLoader {
+        let mut loaders = Sequence::new();
+
+        #[unroll]
+        for slot in 0..DEPTH {
+            loaders.push(LhsLoader::new::<Self::Config>(
+                lhs,
+                x_offset,
+                y_offset + slot * config.k_step,
+                batch_offset,
+                config,
+            ));
+        }
+
+        PrefetchingLoader::<LhsLoader<MP::EL, MP::ES, SMM::Config, LL>> { loaders }
+    }
+
+    fn init_rhs_loader(
+        rhs: VirtualTensor<MP::ER>,
+        x_offset: u32,
+        y_offset: u32,
+        batch_offset: u32,
+        #[comptime] config: Self::Config,
+    ) -> Self::RhsLoader {
+        let mut loaders = Sequence::new();
+
+        #[unroll]
+        for slot in 0..DEPTH {
+            loaders.push(RhsLoader::new::<Self::Config>(
+                rhs,
+                x_offset + slot * config.k_step,
+                y_offset,
+                batch_offset,
+                config,
+            ));
+        }
+
+        PrefetchingLoader::<RhsLoader<MP::ER, MP::ES, SMM::Config, RL>> { loaders }
+    }
+
+    fn init_unloader(
+        out: VirtualTensor<MP::EG, ReadWrite>,
+        epilogue_args: EpilogueArgs<MP::EG>,
+        x_offset: u32,
+        y_offset: u32,
+        batch_offset: u32,
+        #[comptime] config: Self::Config,
+    ) -> Self::Out {
+        Self::Out::new(
+            out,
+            epilogue_args,
+            x_offset,
+            y_offset,
+            batch_offset,
+            config.layout(Ident::Out),
+            config.epilogue(),
+            config.beta(),
+        )
+    }
+
+    fn init_accumulator(#[comptime] config: Self::Config) -> Self::Accumulator {
+        SMM::init_accumulator(config.to_smm_config())
+    }
+
+    fn zero_accumulator(acc: &mut Self::Accumulator, #[comptime] config: Self::Config) {
+        SMM::zero_accumulator(acc, config.to_smm_config());
+    }
+}
+
+#[derive(CubeType)]
+/// One loader per pipeline stage, each starting one stage further along k.
+pub struct PrefetchingLoader<L: CubeType> {
+    pub loaders: Sequence<L>,
+}
+
+#[cube]
+impl<EG: Numeric, ES: Numeric, G: GlobalConfig, L: InputLoader<EG, ES, G>> InputLoader<EG, ES, G>
+    for PrefetchingLoader<L>
+{
+    type StageReader = L::StageReader;
+
+    /// Fills the stage of every loader, each at its own k offset.
+    fn fill_stage(this: &mut Self, #[comptime] config: G) {
+        #[unroll]
+        for slot in 0..this.loaders.len() {
+            L::fill_stage(this.loaders.index_mut(slot), config);
+        }
+    }
+
+    /// Returns a reader for the stage of the first loader.
+    fn as_stage_reader(this: &Self) -> Self::StageReader {
+        L::as_stage_reader(this.loaders.index(0))
+    }
+
+    fn advance_view(this: &mut Self, k_offset: u32) {
+        #[unroll]
+        for slot in 0..this.loaders.len() {
+            L::advance_view(this.loaders.index_mut(slot), k_offset);
+        }
+    }
+}
@@ -5,6 +5,7 @@ pub mod tensor_view;
 
 mod accumulator_loader;
 mod base;
+mod epilogue;
 mod shared;
 mod tilewise_unloading;
 
@@ -12,4 +13,6 @@ pub mod output_loader;
 
 pub use accumulator_loader::*;
 pub use base::*;
+pub use epilogue::*;
 pub use shared::*;
+pub use tilewise_unloading::UnloadOrder;
@@ -1,15 +1,24 @@
 use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
 
+use crate::matmul::components::global::epilogue::{EpilogueArgs, EpilogueOp, HasBeta};
 use crate::matmul::components::global::tensor_view::TensorWriter;
-use crate::matmul::components::global::tilewise_unloading::TilewiseUnloading;
+use crate::matmul::components::global::tilewise_unloading::{TilewiseUnloading, UnloadOrder};
 use crate::matmul::components::stage::StageWriter;
+use crate::matmul::components::MatrixLayout;
 use crate::tensor::ReadWrite;
 use crate::{matmul::components::global, tensor::VirtualTensor};
 
 #[derive(CubeType)]
 pub struct Unloader<EG: Numeric> {
     pub tensor_view: TensorWriter<EG>,
+    pub epilogue_args: EpilogueArgs<EG>,
+    #[cube(comptime)]
+    pub order: UnloadOrder,
+    #[cube(comptime)]
+    pub epilogue: EpilogueOp,
+    #[cube(comptime)]
+    pub has_beta: HasBeta,
 }
 
 #[cube]
@@ -25,12 +34,48 @@ impl<EG: Numeric> global::OutputLoader<EG> for Unloader<EG> {
 impl<EG: Numeric> Unloader<EG> {
     pub fn new(
         tensor: VirtualTensor<EG, ReadWrite>,
+        epilogue_args: EpilogueArgs<EG>,
         x_offset: u32,
         y_offset: u32,
         batch_offset: u32,
+        #[comptime] layout: MatrixLayout,
+        #[comptime] epilogue: EpilogueOp,
+        #[comptime] has_beta: HasBeta,
+    ) -> Self {
+        Self::new_with_order(
+            tensor,
+            epilogue_args,
+            x_offset,
+            y_offset,
+            batch_offset,
+            layout,
+            comptime!(UnloadOrder::of_layout(layout)),
+            epilogue,
+            has_beta,
+        )
+    }
+
+    /// Same as [new](Unloader::new), but the tiles are written in the given order instead of the
+    /// one following the layout.
+    ///
+    /// The output is the same, only the scheduling of the writes changes.
+    pub fn new_with_order(
+        tensor: VirtualTensor<EG, ReadWrite>,
+        epilogue_args: EpilogueArgs<EG>,
+        x_offset: u32,
+        y_offset: u32,
+        batch_offset: u32,
+        #[comptime] layout: MatrixLayout,
+        #[comptime] order: UnloadOrder,
+        #[comptime] epilogue: EpilogueOp,
+        #[comptime] has_beta: HasBeta,
     ) -> Self {
         Unloader::<EG> {
-            tensor_view: TensorWriter::new(tensor, x_offset, y_offset, batch_offset),
+            tensor_view: TensorWriter::new(tensor, x_offset, y_offset, batch_offset, layout),
+            epilogue_args,
+            order,
+            epilogue,
+            has_beta,
         }
     }
 }
@@ -46,9 +91,35 @@ impl<EG: Numeric> StageWriter<EG> for Unloader<EG> {
     ) {
         TilewiseUnloading::unload_from_slice::<EG, ES, G>(
             &mut this.tensor_view,
+            &this.epilogue_args,
+            slice,
+            compute_plane_offset,
+            accumulator_offset,
+            comptime!(this.order),
+            comptime!(this.epilogue),
+            comptime!(this.has_beta),
+            false,
+            config,
+        );
+    }
+
+    fn write_masked<ES: Numeric, G: global::GlobalConfig>(
+        this: &mut Self,
+        slice: Slice<Line<ES>>,
+        compute_plane_offset: u32,
+        accumulator_offset: u32,
+        #[comptime] config: G,
+    ) {
+        TilewiseUnloading::unload_from_slice::<EG, ES, G>(
+            &mut this.tensor_view,
+            &this.epilogue_args,
             slice,
             compute_plane_offset,
             accumulator_offset,
+            comptime!(this.order),
+            comptime!(this.epilogue),
+            comptime!(this.has_beta),
+            true,
             config,
         );
     }
@@ -2,6 +2,7 @@ use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
 
 use crate::matmul::components::{
+    global::{EpilogueOp, HasBeta},
     stage::{self, TilingOrderConfig},
     Ident, MatmulConfig, MatrixLayout, StageDim,
 };
@@ -15,10 +16,15 @@ pub struct CommonGlobalConfig<S: stage::StageConfig> {
     pub check_k_bounds: bool,
     pub lhs_layout: MatrixLayout,
     pub rhs_layout: MatrixLayout,
+    pub out_layout: MatrixLayout,
     pub lhs_line_size: u32,
     pub rhs_line_size: u32,
     pub out_line_size: u32,
     pub num_planes: u32,
+    pub beta: HasBeta,
+    pub has_bias: bool,
+    pub epilogue: EpilogueOp,
+    pub wide_offsets: bool,
 }
 
 impl<S: stage::StageConfig> super::GlobalConfig for CommonGlobalConfig<S> {
@@ -32,7 +38,7 @@ impl<S: stage::StageConfig> super::GlobalConfig for CommonGlobalConfig<S> {
         match ident {
             Ident::Lhs => self.lhs_line_size,
             Ident::Rhs => self.rhs_line_size,
-            Ident::Out => self.out_line_size,
+            Ident::Out | Ident::Bias => self.out_line_size,
         }
     }
 
@@ -48,7 +54,8 @@ impl<S: stage::StageConfig> super::GlobalConfig for CommonGlobalConfig<S> {
         match ident {
             Ident::Lhs => self.lhs_layout,
             Ident::Rhs => self.rhs_layout,
-            Ident::Out => self.smm_config.layout(Ident::Out),
+            Ident::Out => self.out_layout,
+            Ident::Bias => MatrixLayout::RowMajor,
         }
     }
 
@@ -79,6 +86,22 @@ impl<S: stage::StageConfig> super::GlobalConfig for CommonGlobalConfig<S> {
     fn transpose_load(&self, ident: Ident) -> bool {
         self.layout(ident) != self.smm_config.layout(ident)
     }
+
+    fn beta(&self) -> HasBeta {
+        self.beta
+    }
+
+    fn has_bias(&self) -> bool {
+        self.has_bias
+    }
+
+    fn epilogue(&self) -> EpilogueOp {
+        self.epilogue
+    }
+
+    fn wide_offsets(&self) -> bool {
+        self.wide_offsets
+    }
 }
 
 impl<S: stage::StageConfig> MatmulConfig for CommonGlobalConfig<S> {}
@@ -92,10 +115,15 @@ impl<S: stage::StageConfig> CommonGlobalConfig<S> {
         check_k_bounds: bool,
         lhs_layout: MatrixLayout,
         rhs_layout: MatrixLayout,
+        out_layout: MatrixLayout,
         lhs_line_size: u32,
         rhs_line_size: u32,
         out_line_size: u32,
         num_planes: u32,
+        beta: HasBeta,
+        has_bias: bool,
+        epilogue: EpilogueOp,
+        wide_offsets: bool,
     ) -> Self {
         Self {
             smm_config,
@@ -104,10 +132,15 @@ impl<S: stage::StageConfig> CommonGlobalConfig<S> {
             check_k_bounds,
             lhs_layout,
             rhs_layout,
+            out_layout,
             lhs_line_size,
             rhs_line_size,
             out_line_size,
             num_planes,
+            beta,
+            has_bias,
+            epilogue,
+            wide_offsets,
         }
     }
 }
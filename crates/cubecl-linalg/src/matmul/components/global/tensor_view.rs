@@ -3,7 +3,9 @@ use crate::matmul::components::global;
 use crate::matmul::components::{Ident, MatrixLayout};
 use crate::tensor::{ReadWrite, VirtualTensor};
 use cubecl_core as cubecl;
+use cubecl_core::prelude::pipeline::{Pipeline, PipelineExpand};
 use cubecl_core::prelude::*;
+use cubecl_core::unexpanded;
 
 #[derive(CubeType)]
 /// A view of a tensor that starts reading data from a specified offset.
@@ -33,6 +35,9 @@ pub struct TensorWriter<E: Numeric> {
     pub shape_x: u32,
     pub shape_y: u32,
     pub batch_offset: u32,
+    /// Layout of the output tensor, which decides the order in which units write a tile.
+    #[cube(comptime)]
+    pub layout: MatrixLayout,
 }
 
 unsafe impl<E: Numeric> Sync for TensorReader<E> {}
@@ -92,22 +97,7 @@ impl<EG: Numeric> TensorReader<EG> {
         #[comptime] config: G,
     ) -> Line<EG> {
         let line_size = config.global_line_size(ident);
-        let tile_size_x = config.stage_dim(ident).tile_size_x_dim();
-        let tile_size_y = config.stage_dim(ident).tile_size_y_dim();
-
-        let view_tile_x = tile_x * tile_size_x + self.x_offset;
-        let view_tile_y = tile_y * tile_size_y + self.y_offset;
-
-        let (load_x, load_y) = match config.layout(ident) {
-            MatrixLayout::RowMajor => (unit_id / tile_size_y, unit_id % tile_size_y),
-            MatrixLayout::ColMajor => (unit_id % tile_size_x, unit_id / tile_size_x),
-        };
-
-        let view_x = view_tile_x + load_x;
-        let view_y = view_tile_y + load_y;
-
-        let read_pos =
-            (view_x * self.stride_x + view_y * self.stride_y + self.batch_offset) / line_size;
+        let (view_x, view_y) = self.coalesced_position::<G>(tile_x, tile_y, unit_id, ident, config);
 
         let (check_x_bounds, check_y_bounds) = match ident.as_input() {
             InputIdent::Lhs => (config.check_m_bounds(), config.check_k_bounds()),
@@ -117,36 +107,177 @@ impl<EG: Numeric> TensorReader<EG> {
         match comptime!((check_x_bounds, check_y_bounds)) {
             (true, true) => select(
                 view_x < self.shape_x && view_y < self.shape_y,
-                self.read(read_pos),
+                self.read_at::<G>(view_x, view_y, ident, config),
                 Line::empty(line_size).fill(EG::from_int(0)),
             ),
             (true, false) => select(
                 view_x < self.shape_x,
-                self.read(read_pos),
+                self.read_at::<G>(view_x, view_y, ident, config),
                 Line::empty(line_size).fill(EG::from_int(0)),
             ),
             (false, true) => select(
                 view_y < self.shape_y,
-                self.read(read_pos),
+                self.read_at::<G>(view_x, view_y, ident, config),
                 Line::empty(line_size).fill(EG::from_int(0)),
             ),
-            (false, false) => self.read(read_pos),
+            (false, false) => self.read_at::<G>(view_x, view_y, ident, config),
+        }
+    }
+
+    /// Same as [load_coalesced](TensorReader::load_coalesced), but out-of-bounds positions are
+    /// never read, instead of being read and replaced by zeros.
+    ///
+    /// This is slower because of the branch, but doesn't rely on the memory past the end of the
+    /// tensor being readable.
+    pub fn load_coalesced_masked<G: global::GlobalConfig>(
+        &self,
+        tile_x: u32,
+        tile_y: u32,
+        unit_id: u32,
+        #[comptime] ident: Ident,
+        #[comptime] config: G,
+    ) -> Line<EG> {
+        let line_size = config.global_line_size(ident);
+        let (view_x, view_y) = self.coalesced_position::<G>(tile_x, tile_y, unit_id, ident, config);
+
+        let mut line = Line::empty(line_size).fill(EG::from_int(0));
+        if self.in_bounds::<G>(view_x, view_y, ident, config) {
+            line = self.read_at::<G>(view_x, view_y, ident, config);
+        }
+        line
+    }
+
+    /// Copies the line [load_coalesced](TensorReader::load_coalesced) would read into the
+    /// first line of `destination`, asynchronously through the pipeline.
+    ///
+    /// Out-of-bounds positions are never read, zeros are written right away instead.
+    /// The copy is only asynchronous when the global and stage lines hold the same element
+    /// type with the same line size, and when the offsets fit in 32 bits, otherwise the line is
+    /// read, cast and written right away.
+    pub fn memcpy_coalesced_async<ES: Numeric, G: global::GlobalConfig>(
+        &self,
+        pipeline: &Pipeline<ES>,
+        destination: &mut SliceMut<Line<ES>>,
+        tile_x: u32,
+        tile_y: u32,
+        unit_id: u32,
+        #[comptime] ident: Ident,
+        #[comptime] config: G,
+    ) {
+        let line_size = config.global_line_size(ident);
+        let (view_x, view_y) = self.coalesced_position::<G>(tile_x, tile_y, unit_id, ident, config);
+
+        if self.in_bounds::<G>(view_x, view_y, ident, config) {
+            // Slices are addressed with 32-bit offsets.
+            if comptime!(config.wide_offsets()) {
+                destination[0] = Line::cast_from(self.read_at::<G>(view_x, view_y, ident, config));
+            } else {
+                let read_pos = self.read_position(view_x, view_y, line_size);
+                memcpy_line_async::<EG, ES>(
+                    pipeline,
+                    self.tensor.read_window(read_pos, read_pos + 1),
+                    destination,
+                );
+            }
+        } else {
+            destination[0] = Line::empty(line_size).fill(ES::from_int(0));
         }
     }
 
-    fn read(&self, position: u32) -> Line<EG> {
-        self.tensor.read(position)
+    /// Whether the given view coordinates are within the tensor, only checking the dimensions
+    /// that the config requires to be checked.
+    fn in_bounds<G: global::GlobalConfig>(
+        &self,
+        view_x: u32,
+        view_y: u32,
+        #[comptime] ident: Ident,
+        #[comptime] config: G,
+    ) -> bool {
+        let (check_x_bounds, check_y_bounds) = match ident.as_input() {
+            InputIdent::Lhs => (config.check_m_bounds(), config.check_k_bounds()),
+            InputIdent::Rhs => (config.check_k_bounds(), config.check_n_bounds()),
+        };
+
+        match comptime!((check_x_bounds, check_y_bounds)) {
+            (true, true) => view_x < self.shape_x && view_y < self.shape_y,
+            (true, false) => view_x < self.shape_x,
+            (false, true) => view_y < self.shape_y,
+            (false, false) => true,
+        }
+    }
+
+    /// Returns the coordinates in the view of the line loaded by the given unit id.
+    fn coalesced_position<G: global::GlobalConfig>(
+        &self,
+        tile_x: u32,
+        tile_y: u32,
+        unit_id: u32,
+        #[comptime] ident: Ident,
+        #[comptime] config: G,
+    ) -> (u32, u32) {
+        let tile_size_x = config.stage_dim(ident).tile_size_x_dim();
+        let tile_size_y = config.stage_dim(ident).tile_size_y_dim();
+
+        let view_tile_x = tile_x * tile_size_x + self.x_offset;
+        let view_tile_y = tile_y * tile_size_y + self.y_offset;
+
+        let (load_x, load_y) = match config.layout(ident) {
+            MatrixLayout::RowMajor => (unit_id / tile_size_y, unit_id % tile_size_y),
+            MatrixLayout::ColMajor => (unit_id % tile_size_x, unit_id / tile_size_x),
+            MatrixLayout::Strided { .. } => unreachable!("Configs only hold contiguous layouts"),
+        };
+
+        let view_x = view_tile_x + load_x;
+        let view_y = view_tile_y + load_y;
+
+        (view_x, view_y)
+    }
+
+    /// Returns the position in the tensor of the line at the given view coordinates.
+    fn read_position(&self, view_x: u32, view_y: u32, #[comptime] line_size: u32) -> u32 {
+        (view_x * self.stride_x + view_y * self.stride_y + self.batch_offset) / line_size
+    }
+
+    /// Reads the line at the given view coordinates, with a 64-bit offset when the config
+    /// requires [wide offsets](global::GlobalConfig::wide_offsets).
+    ///
+    /// The strides and the batch offset still fit in 32 bits, only the offset they add up to
+    /// may not.
+    fn read_at<G: global::GlobalConfig>(
+        &self,
+        view_x: u32,
+        view_y: u32,
+        #[comptime] ident: Ident,
+        #[comptime] config: G,
+    ) -> Line<EG> {
+        let line_size = config.global_line_size(ident);
+
+        if comptime!(config.wide_offsets()) {
+            let position = u64::cast_from(view_x) * u64::cast_from(self.stride_x)
+                + u64::cast_from(view_y) * u64::cast_from(self.stride_y)
+                + u64::cast_from(self.batch_offset);
+            self.tensor
+                .read_wide(position / comptime!(line_size as u64))
+        } else {
+            self.tensor
+                .read(self.read_position(view_x, view_y, line_size))
+        }
     }
 }
 
 #[cube]
 impl<EG: Numeric> TensorWriter<EG> {
     /// Instantiate a write view over the given tensor, pre-fetching needed strides and shapes
+    ///
+    /// Elements are addressed with the strides of the tensor, and `batch_offset` is an offset in
+    /// elements, so the rows and batches of the output don't need to be packed. The layout must
+    /// match the strides of the tensor for writes to be coalesced.
     pub fn new(
         tensor: VirtualTensor<EG, ReadWrite>,
         x_offset: u32,
         y_offset: u32,
         batch_offset: u32,
+        #[comptime] layout: MatrixLayout,
     ) -> Self {
         let rank = tensor.rank();
         let stride_x = tensor.stride(rank - 2);
@@ -163,12 +294,34 @@ impl<EG: Numeric> TensorWriter<EG> {
             shape_x,
             shape_y,
             batch_offset,
+            layout,
+        }
+    }
+
+    /// Returns the position within a tile written by the given unit id.
+    ///
+    /// For row-major tensors, subsequent units write lines horizontally within the tile,
+    /// while for column-major tensors, they write lines vertically.
+    pub fn unit_position<G: global::GlobalConfig>(
+        &self,
+        unit_id: u32,
+        #[comptime] config: G,
+    ) -> (u32, u32) {
+        let stage_dim = config.stage_dim(Ident::Out);
+        let tile_size_x = stage_dim.tile_size_x_dim();
+        let tile_size_y = stage_dim.tile_size_y_dim();
+
+        match comptime!(self.layout) {
+            MatrixLayout::RowMajor => (unit_id / tile_size_y, unit_id % tile_size_y),
+            MatrixLayout::ColMajor => (unit_id % tile_size_x, unit_id / tile_size_x),
+            MatrixLayout::Strided { .. } => unreachable!("Configs only hold contiguous layouts"),
         }
     }
 
     /// Writes data into the tensor view at the specified coordinates (write_x, write_y).
     ///
-    /// Each unit writes one line in a coalesced manner for improved efficiency, assuming row-major layout.
+    /// Each unit writes one line in a coalesced manner for improved efficiency,
+    /// following the layout of the tensor, see [unit_position](TensorWriter::unit_position).
     pub fn write_coalesced<ES: Numeric, G: global::GlobalConfig>(
         &mut self,
         tile_x: u32,
@@ -177,19 +330,50 @@ impl<EG: Numeric> TensorWriter<EG> {
         value: Line<ES>,
         #[comptime] config: G,
     ) {
-        let stage_dim = config.stage_dim(Ident::Out);
+        let (unit_x, unit_y) = self.unit_position::<G>(unit_id, config);
+        self.write_at::<ES, G>(tile_x, tile_y, unit_x, unit_y, value, config);
+    }
 
-        let view_x = tile_x * stage_dim.tile_size_x_dim()
-            + unit_id / stage_dim.tile_size_y_dim()
-            + self.x_offset;
-        let view_y = tile_y * stage_dim.tile_size_y_dim()
-            + unit_id % stage_dim.tile_size_y_dim()
-            + self.y_offset;
+    /// Writes a line at the position `(unit_x, unit_y)` within the tile `(tile_x, tile_y)`.
+    ///
+    /// Positions out of the bounds of the view are skipped, along the dimensions the config
+    /// checks the bounds of.
+    pub fn write_at<ES: Numeric, G: global::GlobalConfig>(
+        &mut self,
+        tile_x: u32,
+        tile_y: u32,
+        unit_x: u32,
+        unit_y: u32,
+        value: Line<ES>,
+        #[comptime] config: G,
+    ) {
+        self.write_at_checked::<ES, G>(
+            tile_x,
+            tile_y,
+            unit_x,
+            unit_y,
+            value,
+            comptime!((config.check_m_bounds(), config.check_n_bounds())),
+            config,
+        );
+    }
 
-        let write_position = (view_x * self.stride_x + view_y * self.stride_y + self.batch_offset)
-            / config.global_line_size(Ident::Out);
+    /// Same as [write_at](TensorWriter::write_at), with the bounds checked along the rows and
+    /// the columns given by `check_bounds` instead of the ones of the config.
+    pub fn write_at_checked<ES: Numeric, G: global::GlobalConfig>(
+        &mut self,
+        tile_x: u32,
+        tile_y: u32,
+        unit_x: u32,
+        unit_y: u32,
+        value: Line<ES>,
+        #[comptime] check_bounds: (bool, bool),
+        #[comptime] config: G,
+    ) {
+        let (view_x, view_y, write_position) =
+            self.position_at::<G>(tile_x, tile_y, unit_x, unit_y, config);
 
-        match comptime!((config.check_m_bounds(), config.check_n_bounds())) {
+        match comptime!(check_bounds) {
             (true, true) => {
                 if view_x < self.shape_x && view_y < self.shape_y {
                     self.write(write_position, Line::cast_from(value));
@@ -211,7 +395,116 @@ impl<EG: Numeric> TensorWriter<EG> {
         }
     }
 
+    /// Reads the line that [write_at](TensorWriter::write_at) would write at the same position.
+    ///
+    /// Positions out of the bounds of the view are never read, zeros are returned instead.
+    pub fn read_at<G: global::GlobalConfig>(
+        &self,
+        tile_x: u32,
+        tile_y: u32,
+        unit_x: u32,
+        unit_y: u32,
+        #[comptime] config: G,
+    ) -> Line<EG> {
+        self.read_at_checked::<G>(
+            tile_x,
+            tile_y,
+            unit_x,
+            unit_y,
+            comptime!((config.check_m_bounds(), config.check_n_bounds())),
+            config,
+        )
+    }
+
+    /// Reads the line that [write_at_checked](TensorWriter::write_at_checked) would write at the
+    /// same position with the same `check_bounds`.
+    pub fn read_at_checked<G: global::GlobalConfig>(
+        &self,
+        tile_x: u32,
+        tile_y: u32,
+        unit_x: u32,
+        unit_y: u32,
+        #[comptime] check_bounds: (bool, bool),
+        #[comptime] config: G,
+    ) -> Line<EG> {
+        let (view_x, view_y, read_position) =
+            self.position_at::<G>(tile_x, tile_y, unit_x, unit_y, config);
+
+        let in_bounds = match comptime!(check_bounds) {
+            (true, true) => view_x < self.shape_x && view_y < self.shape_y,
+            (true, false) => view_x < self.shape_x,
+            (false, true) => view_y < self.shape_y,
+            (false, false) => true,
+        };
+
+        let mut line = Line::empty(config.global_line_size(Ident::Out)).fill(EG::from_int(0));
+        if in_bounds {
+            line = self.tensor.read(read_position);
+        }
+        line
+    }
+
+    /// Returns the coordinates in the view of the position `(unit_x, unit_y)` within the tile
+    /// `(tile_x, tile_y)`, followed by the position of its line in the tensor.
+    fn position_at<G: global::GlobalConfig>(
+        &self,
+        tile_x: u32,
+        tile_y: u32,
+        unit_x: u32,
+        unit_y: u32,
+        #[comptime] config: G,
+    ) -> (u32, u32, u32) {
+        let stage_dim = config.stage_dim(Ident::Out);
+
+        let view_x = tile_x * stage_dim.tile_size_x_dim() + unit_x + self.x_offset;
+        let view_y = tile_y * stage_dim.tile_size_y_dim() + unit_y + self.y_offset;
+
+        let position = (view_x * self.stride_x + view_y * self.stride_y + self.batch_offset)
+            / config.global_line_size(Ident::Out);
+
+        (view_x, view_y, position)
+    }
+
     fn write(&mut self, position: u32, value: Line<EG>) {
         self.tensor.write(position, value)
     }
 }
+
+/// Asynchronously copies `source` into `destination` through the pipeline.
+///
+/// The pipeline copies raw memory, so lines that hold different element types or line sizes
+/// are read, cast and written right away instead.
+#[allow(unused_variables, dead_code)]
+fn memcpy_line_async<EG: Numeric, ES: Numeric>(
+    pipeline: &Pipeline<ES>,
+    source: Slice<Line<EG>>,
+    destination: &mut SliceMut<Line<ES>>,
+) {
+    unexpanded!()
+}
+
+mod memcpy_line_async {
+    use super::*;
+
+    pub fn expand<EG: Numeric, ES: Numeric>(
+        scope: &mut Scope,
+        pipeline: PipelineExpand<ES>,
+        source: ExpandElementTyped<Slice<Line<EG>>>,
+        destination: ExpandElementTyped<SliceMut<Line<ES>>>,
+    ) {
+        if source.expand.item == destination.expand.item {
+            let source = ExpandElementTyped::new(source.expand);
+            pipeline.__expand_memcpy_async_method(scope, source, destination);
+        } else {
+            cast_line::expand::<EG, ES>(scope, source, destination);
+        }
+    }
+}
+
+#[cube]
+fn cast_line<EG: Numeric, ES: Numeric>(
+    source: Slice<Line<EG>>,
+    destination: &mut SliceMut<Line<ES>>,
+) {
+    destination[0] = Line::cast_from(source[0]);
+}
@@ -1,9 +1,37 @@
+use crate::matmul::components::global::epilogue::{
+    apply_epilogue, EpilogueArgs, EpilogueOp, HasBeta,
+};
 use crate::matmul::components::global::tensor_view::TensorWriter;
 use crate::matmul::components::global::GlobalConfig;
-use crate::matmul::components::Ident;
+use crate::matmul::components::{Ident, MatrixLayout};
+use crate::tensor::VirtualTensor;
 use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
 
+/// Order in which the units of a plane walk through a tile when writing it to the output.
+///
+/// Both orders write the same values to the same positions, they only change which line every
+/// unit writes at each step. Orders following the layout of the output are coalesced.
+#[derive(CubeType, Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub enum UnloadOrder {
+    /// Subsequent units write subsequent lines of a row.
+    #[default]
+    RowMajor,
+    /// Subsequent units write subsequent lines of a column, moving to the next column of lines
+    /// once the tile height is covered.
+    ColMajor,
+}
+
+impl UnloadOrder {
+    /// The order that follows the given output layout.
+    pub fn of_layout(layout: MatrixLayout) -> Self {
+        match layout {
+            MatrixLayout::ColMajor => UnloadOrder::ColMajor,
+            _ => UnloadOrder::RowMajor,
+        }
+    }
+}
+
 #[derive(CubeType)]
 /// Writes the contents of a tile to the tensor view using a single plane,
 /// iterating with steps determined by the plane's dimension.
@@ -11,14 +39,26 @@ pub struct TilewiseUnloading {}
 
 #[cube]
 impl TilewiseUnloading {
+    /// Writes the tile held in `slice` to the tile `(tile_x, tile_y)` of the view.
+    ///
+    /// Lines out of the view are skipped along the dimensions the config checks the bounds of,
+    /// or along both dimensions when `masked` is set.
     pub fn unload_from_slice<EG: Numeric, ES: Numeric, G: GlobalConfig>(
         write_view: &mut TensorWriter<EG>,
+        epilogue_args: &EpilogueArgs<EG>,
         slice: Slice<Line<ES>>,
         tile_x: u32,
         tile_y: u32,
+        #[comptime] order: UnloadOrder,
+        #[comptime] epilogue: EpilogueOp,
+        #[comptime] has_beta: HasBeta,
+        #[comptime] masked: bool,
         #[comptime] config: G,
     ) {
+        let check_bounds = comptime!(bounds_to_check(&config, masked));
         let stage_dim = config.stage_dim(Ident::Out);
+        let tile_size_x = stage_dim.tile_size_x_dim();
+        let tile_size_y = stage_dim.tile_size_y_dim();
         let slice_line_size = config.stage_line_size(Ident::Out);
         let out_line_size = config.global_line_size(Ident::Out);
 
@@ -31,10 +71,84 @@ impl TilewiseUnloading {
         for i in 0..num_unit_writes {
             let unit_write = UNIT_POS_X * out_line_size + i * unit_step;
 
-            let value = slice[unit_write / out_line_size];
-            write_view.write_coalesced::<ES, G>(tile_x, tile_y, unit_write, value, config);
+            // The slice is always row-major, and so are the lines in it, whatever the order.
+            let (unit_x, unit_y) = match comptime!(order) {
+                UnloadOrder::RowMajor => (unit_write / tile_size_y, unit_write % tile_size_y),
+                UnloadOrder::ColMajor => {
+                    let line = unit_write / out_line_size;
+                    (line % tile_size_x, line / tile_size_x * out_line_size)
+                }
+            };
+            let mut value = slice[(unit_x * tile_size_y + unit_y) / out_line_size];
+
+            if comptime!(has_beta == HasBeta::Yes) {
+                let scale =
+                    Line::<ES>::empty(out_line_size).fill(ES::cast_from(epilogue_args.beta));
+                let existing = write_view.read_at_checked::<G>(
+                    tile_x,
+                    tile_y,
+                    unit_x,
+                    unit_y,
+                    check_bounds,
+                    config,
+                );
+
+                value += Line::<ES>::cast_from(existing) * scale;
+            }
+
+            if comptime!(config.has_bias()) {
+                let column = tile_y * tile_size_y + unit_y + write_view.y_offset;
+
+                value = Self::add_bias::<EG, ES, G>(
+                    value,
+                    &epilogue_args.bias,
+                    column,
+                    write_view.shape_y,
+                    comptime!(check_bounds.1),
+                    config,
+                );
+            }
+
+            let value = apply_epilogue::<EG>(Line::cast_from(value), epilogue);
+            write_view.write_at_checked::<EG, G>(
+                tile_x,
+                tile_y,
+                unit_x,
+                unit_y,
+                value,
+                check_bounds,
+                config,
+            );
         }
     }
+
+    /// Adds the bias at the given global column, in the stage element precision.
+    fn add_bias<EG: Numeric, ES: Numeric, G: GlobalConfig>(
+        value: Line<ES>,
+        bias: &VirtualTensor<EG>,
+        column: u32,
+        shape_y: u32,
+        #[comptime] check_n_bounds: bool,
+        #[comptime] config: G,
+    ) -> Line<ES> {
+        let out_line_size = config.global_line_size(Ident::Out);
+
+        // Columns out of bounds are never written, but we still avoid reading past the bias.
+        let column = match comptime!(check_n_bounds) {
+            true => select(column < shape_y, column, 0),
+            false => column,
+        };
+
+        value + Line::cast_from(bias.read(column / out_line_size))
+    }
+}
+
+/// The bounds checked along the rows and the columns, which are all of them when `masked`.
+fn bounds_to_check<G: GlobalConfig>(config: &G, masked: bool) -> (bool, bool) {
+    match masked {
+        true => (true, true),
+        false => (config.check_m_bounds(), config.check_n_bounds()),
+    }
 }
 
 fn check_line_size(out_line_size: u32, slice_line_size: u32) {
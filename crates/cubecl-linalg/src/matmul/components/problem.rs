@@ -1,9 +1,19 @@
 use crate::matmul::kernels::MatmulInvalidProblem;
 
-use super::{batch, MatrixLayout};
+use super::{
+    batch::BatchConfig,
+    global::{EpilogueOp, GlobalConfig},
+    stage::StageConfig,
+    tile::TileConfig,
+    Ident, MatrixLayout,
+};
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 /// Description of a matmul problem to solve, regardless of actual data
+///
+/// The [default](MatmulProblem::default) problem is empty and unbatched, with row-major tensors
+/// read with a line size of one, a beta of zero, no bias and no epilogue, so that problems can be
+/// written with only the fields that differ from it.
 pub struct MatmulProblem {
     pub m: usize,
     pub n: usize,
@@ -11,19 +21,102 @@ pub struct MatmulProblem {
     pub batches: (Vec<usize>, Vec<usize>),
     pub lhs_layout: MatrixLayout,
     pub rhs_layout: MatrixLayout,
+    pub out_layout: MatrixLayout,
     pub lhs_line_size: u8,
     pub rhs_line_size: u8,
     pub out_line_size: u8,
+    /// Scale of the values already in the output, which are accumulated into when it is not
+    /// zero, see [HasBeta](super::global::HasBeta).
+    pub beta: f32,
+    /// Whether a bias of shape `[n]` is added to every row of the output, see
+    /// [check_bias](MatmulProblem::check_bias).
+    pub has_bias: bool,
+    /// Activation applied to the output right before it is written.
+    pub epilogue: EpilogueOp,
+}
+
+impl Default for MatmulProblem {
+    fn default() -> Self {
+        Self {
+            m: 0,
+            n: 0,
+            k: 0,
+            batches: (vec![], vec![]),
+            lhs_layout: MatrixLayout::RowMajor,
+            rhs_layout: MatrixLayout::RowMajor,
+            out_layout: MatrixLayout::RowMajor,
+            lhs_line_size: 1,
+            rhs_line_size: 1,
+            out_line_size: 1,
+            beta: 0.0,
+            has_bias: false,
+            epilogue: EpilogueOp::None,
+        }
+    }
 }
 
 impl MatmulProblem {
+    /// Returns the problem computing the transposed output from the same buffers, using
+    /// `(lhs * rhs)^T = rhs^T * lhs^T`.
+    ///
+    /// The rhs becomes the lhs and the other way around, with their layouts
+    /// [transposed](MatrixLayout::transposed), so they still describe the same memory. m and n
+    /// are swapped, and so are the batches and the line sizes of the inputs. The output is
+    /// transposed in place, which makes a row-major output column-major: it must then be written
+    /// with a line size of one for the problem to be [valid](MatmulProblem::validate).
+    ///
+    /// A bias is added along the rows of the output, so it would be added along the columns of
+    /// the transposed output: the transposed problem only computes the same values without one.
+    ///
+    /// Transposing twice gives back the original problem.
+    pub fn transposed(&self) -> Self {
+        MatmulProblem {
+            m: self.n,
+            n: self.m,
+            k: self.k,
+            batches: (self.batches.1.clone(), self.batches.0.clone()),
+            lhs_layout: self.rhs_layout.transposed(),
+            rhs_layout: self.lhs_layout.transposed(),
+            out_layout: self.out_layout.transposed(),
+            lhs_line_size: self.rhs_line_size,
+            rhs_line_size: self.lhs_line_size,
+            out_line_size: self.out_line_size,
+            beta: self.beta,
+            has_bias: self.has_bias,
+            epilogue: self.epilogue,
+        }
+    }
+
+    /// Returns the same problem with the identified tensor laid out with `layout`.
+    ///
+    /// # Panics
+    ///
+    /// If the tensor is the bias, which is one-dimensional and has no layout.
+    pub fn with_layout(mut self, ident: Ident, layout: MatrixLayout) -> Self {
+        match ident {
+            Ident::Lhs => self.lhs_layout = layout,
+            Ident::Rhs => self.rhs_layout = layout,
+            Ident::Out => self.out_layout = layout,
+            Ident::Bias => panic!("The bias is one-dimensional and has no layout."),
+        }
+        self
+    }
+
+    /// Returns the batch dimensions of the output, broadcasting lhs and rhs batches NumPy-style.
+    ///
+    /// Batches are aligned on their last dimension, and missing leading dimensions count as one.
     pub(crate) fn batch_dims(&self) -> Vec<usize> {
-        self.batches
-            .0
-            .iter()
-            .rev()
-            .zip(self.batches.1.iter().rev())
-            .map(|(&dim_lhs, &dim_rhs)| std::cmp::max(dim_lhs, dim_rhs))
+        let (lhs, rhs) = &self.batches;
+        let rank = usize::max(lhs.len(), rhs.len());
+        let dim = |batches: &[usize], i: usize| {
+            (i + batches.len())
+                .checked_sub(rank)
+                .map(|i| batches[i])
+                .unwrap_or(1)
+        };
+
+        (0..rank)
+            .map(|i| std::cmp::max(dim(lhs, i), dim(rhs, i)))
             .collect()
     }
 
@@ -32,16 +125,54 @@ impl MatmulProblem {
         self.batch_dims().iter().product()
     }
 
+    /// Returns the number of floating point operations of the matmul, `2 * m * n * k` per batch
+    ///
+    /// A multiply-add counts as two operations, a multiplication and an addition, which is the
+    /// usual convention for reporting throughput. The scaling by beta isn't counted.
+    pub fn flops(&self) -> u64 {
+        2 * self.num_batches() as u64 * self.m as u64 * self.n as u64 * self.k as u64
+    }
+
+    /// Returns whether an input has more than `u32::MAX` elements, in which case the global
+    /// loaders compute their offsets with 64-bit integers, see
+    /// [wide_offsets](GlobalConfig::wide_offsets).
+    pub(crate) fn wide_offsets(&self) -> bool {
+        let (lhs_batches, rhs_batches) = &self.batches;
+        let lhs = lhs_batches.iter().product::<usize>() as u64 * self.m as u64 * self.k as u64;
+        let rhs = rhs_batches.iter().product::<usize>() as u64 * self.k as u64 * self.n as u64;
+
+        u64::max(lhs, rhs) > u32::MAX as u64
+    }
+
+    /// Returns the minimal number of bytes read from and written to global memory, for elements
+    /// of `elem_size` bytes
+    ///
+    /// Each input is read once, including when its batches are broadcasted, and the output is
+    /// written once, and also read once when accumulating into it with beta. Dividing
+    /// [flops](MatmulProblem::flops) by this gives the arithmetic intensity of the problem.
+    pub fn bytes_moved(&self, elem_size: usize) -> u64 {
+        let (lhs_batches, rhs_batches) = &self.batches;
+        let lhs = lhs_batches.iter().product::<usize>() * self.m * self.k;
+        let rhs = rhs_batches.iter().product::<usize>() * self.k * self.n;
+        let out = self.num_batches() * self.m * self.n;
+        let out_accesses = match self.beta == 0.0 {
+            true => 1,
+            false => 2,
+        };
+
+        (lhs + rhs + out_accesses * out) as u64 * elem_size as u64
+    }
+
     /// Asserts that the problem can be solved with the given batch matmul configs
     ///
     /// # Panics:
     ///
     ///  - If dimensions of the problem are larger than allowed by the config
-    ///  - If line sizes do not divide well the dimension in which they are aligned
-    pub fn check_config<B: batch::BatchConfig>(
-        &self,
-        config: &B,
-    ) -> Result<(), MatmulInvalidProblem> {
+    ///  - If a strided layout maps two elements of a tile to the same address
+    ///
+    /// The problem itself is expected to be [valid](MatmulProblem::validate), which is checked
+    /// once when it is created from the tensors.
+    pub fn check_config<B: BatchConfig>(&self, config: &B) -> Result<(), MatmulInvalidProblem> {
         if self.m > config.max_m() as usize {
             return Err(MatmulInvalidProblem::ExceededMSize {
                 m: self.m as u32,
@@ -63,51 +194,119 @@ impl MatmulProblem {
             });
         }
 
-        match self.lhs_layout {
-            MatrixLayout::RowMajor => {
-                if self.k % self.lhs_line_size as usize != 0 {
-                    return Err(MatmulInvalidProblem::InvalidLineSizeLhs {
-                        size: self.k as u32,
-                        line_size: self.lhs_line_size,
-                    });
-                }
-            }
-            MatrixLayout::ColMajor => {
-                if self.m % self.lhs_line_size as usize != 0 {
-                    return Err(MatmulInvalidProblem::InvalidLineSizeLhs {
-                        size: self.m as u32,
-                        line_size: self.lhs_line_size,
-                    });
-                }
+        let tile = *config
+            .to_gmm_config()
+            .to_smm_config()
+            .to_tmm_config()
+            .size();
+        for (ident, layout, rows, cols) in [
+            (Ident::Lhs, self.lhs_layout, tile.m, tile.k),
+            (Ident::Rhs, self.rhs_layout, tile.k, tile.n),
+            (Ident::Out, self.out_layout, tile.m, tile.n),
+        ] {
+            if layout.aliases(rows, cols) {
+                return Err(MatmulInvalidProblem::AliasedStrides { ident, rows, cols });
             }
         }
 
-        match self.rhs_layout {
-            MatrixLayout::RowMajor => {
-                if self.n % self.rhs_line_size as usize != 0 {
-                    return Err(MatmulInvalidProblem::InvalidLineSizeRhs {
-                        size: self.n as u32,
-                        line_size: self.rhs_line_size,
-                    });
-                }
-            }
-            MatrixLayout::ColMajor => {
-                if self.k % self.rhs_line_size as usize != 0 {
-                    return Err(MatmulInvalidProblem::InvalidLineSizeRhs {
-                        size: self.k as u32,
-                        line_size: self.lhs_line_size,
-                    });
-                }
-            }
+        Ok(())
+    }
+
+    /// Checks that the problem is well defined, independently of the kernel solving it
+    ///
+    /// # Errors:
+    ///
+    ///  - If m, n or k is zero
+    ///  - If the batch dimensions of lhs and rhs are empty or can't be broadcasted together. They
+    ///    are aligned on their last dimension, and missing leading dimensions count as one, so
+    ///    lhs and rhs may have a different number of them
+    ///  - If line sizes do not divide well the dimension in which they are aligned, or if a
+    ///    column-major output is not written with a line size of one
+    ///  - If a strided layout whose minor stride isn't one is read or written with lines of more
+    ///    than one element
+    pub fn validate(&self) -> Result<(), MatmulInvalidProblem> {
+        let (lhs_batches, rhs_batches) = &self.batches;
+
+        let broadcastable = !lhs_batches.contains(&0)
+            && !rhs_batches.contains(&0)
+            && lhs_batches
+                .iter()
+                .rev()
+                .zip(rhs_batches.iter().rev())
+                .all(|(&lhs, &rhs)| lhs == rhs || lhs == 1 || rhs == 1);
+        if !broadcastable {
+            return Err(MatmulInvalidProblem::IncompatibleBatches {
+                lhs: lhs_batches.clone(),
+                rhs: rhs_batches.clone(),
+            });
         }
 
-        if self.n % self.out_line_size as usize != 0 {
+        if self.m == 0 || self.n == 0 || self.k == 0 {
+            return Err(MatmulInvalidProblem::EmptyProblem {
+                m: self.m as u32,
+                n: self.n as u32,
+                k: self.k as u32,
+            });
+        }
+
+        let divides = |size: usize, line_size: u8, layout: MatrixLayout| {
+            line_size != 0
+                && size % line_size as usize == 0
+                && (line_size == 1 || layout.is_minor_contiguous())
+        };
+
+        let lhs_size = match self.lhs_layout.major() {
+            MatrixLayout::ColMajor => self.m,
+            _ => self.k,
+        };
+        if !divides(lhs_size, self.lhs_line_size, self.lhs_layout) {
+            return Err(MatmulInvalidProblem::InvalidLineSizeLhs {
+                size: lhs_size as u32,
+                line_size: self.lhs_line_size,
+            });
+        }
+
+        let rhs_size = match self.rhs_layout.major() {
+            MatrixLayout::ColMajor => self.k,
+            _ => self.n,
+        };
+        if !divides(rhs_size, self.rhs_line_size, self.rhs_layout) {
+            return Err(MatmulInvalidProblem::InvalidLineSizeRhs {
+                size: rhs_size as u32,
+                line_size: self.rhs_line_size,
+            });
+        }
+
+        // The stage is always row-major, so a column-major output is written element by element.
+        let out_valid = match self.out_layout.major() {
+            MatrixLayout::ColMajor => self.out_line_size == 1,
+            _ => divides(self.n, self.out_line_size, self.out_layout),
+        };
+        if !out_valid {
+            let out_size = match self.out_layout.major() {
+                MatrixLayout::ColMajor => self.m,
+                _ => self.n,
+            };
             return Err(MatmulInvalidProblem::InvalidLineSizeOut {
-                size: self.n as u32,
+                size: out_size as u32,
                 line_size: self.out_line_size,
             });
         }
 
         Ok(())
     }
+
+    /// Asserts that a bias of the given shape can be added to the output of the problem
+    ///
+    /// The bias is broadcasted over rows and batches, so it must be one-dimensional
+    /// and hold at least n elements.
+    pub fn check_bias(&self, bias_shape: &[usize]) -> Result<(), MatmulInvalidProblem> {
+        match bias_shape {
+            [len] if *len >= self.n => Ok(()),
+            _ => Err(MatmulInvalidProblem::InvalidBiasShape {
+                shape: bias_shape.to_vec(),
+                n: self.n as u32,
+            }),
+        }
+    }
 }
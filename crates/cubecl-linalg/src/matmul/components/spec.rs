@@ -7,7 +7,11 @@ use super::global::args::{MatmulArgs, TensorArgs};
 /// Matrix multiplication spec definiting each element types used in the computation as well as
 /// how the arguments are passed to the kernel.
 pub trait MatmulSpec: Send + Sync + Clone + 'static {
-    /// Element type of each input and output tensor of the kernel.
+    /// Element type of the lhs tensor of the kernel.
+    type EL: Numeric;
+    /// Element type of the rhs tensor of the kernel.
+    type ER: Numeric;
+    /// Element type of the output tensor of the kernel.
     type EG: Numeric;
     /// Element type of the intermediate representation of the inputs.
     type ES: Numeric;
@@ -19,7 +23,11 @@ pub trait MatmulSpec: Send + Sync + Clone + 'static {
 
 /// Matrix multiplication precisions.
 pub trait MatmulPrecision: Send + Sync + Clone + 'static {
-    /// Element type of each input and output tensor of the kernel.
+    /// Element type of the lhs tensor of the kernel.
+    type EL: Numeric;
+    /// Element type of the rhs tensor of the kernel.
+    type ER: Numeric;
+    /// Element type of the output tensor of the kernel.
     type EG: Numeric;
     /// Element type of the intermediate representation of the inputs.
     type ES: Numeric;
@@ -28,13 +36,25 @@ pub trait MatmulPrecision: Send + Sync + Clone + 'static {
 }
 
 impl<EG: Numeric, ES: Numeric, EA: Numeric> MatmulPrecision for (EG, ES, EA) {
+    type EL = EG;
+    type ER = EG;
+    type EG = EG;
+    type ES = ES;
+    type EA = EA;
+}
+
+impl<EL: Numeric, ER: Numeric, EG: Numeric, ES: Numeric, EA: Numeric> MatmulPrecision
+    for (EL, ER, EG, ES, EA)
+{
+    type EL = EL;
+    type ER = ER;
     type EG = EG;
     type ES = ES;
     type EA = EA;
 }
 
 /// Input argument
-pub type InputArg<MS> = <Args<MS> as MatmulArgs>::Input<EG<MS>>;
+pub type InputArg<MS> = <Args<MS> as MatmulArgs>::Input<EL<MS>, ER<MS>>;
 
 /// Output argument
 pub type OutputArg<MS> = <Args<MS> as MatmulArgs>::Output<EG<MS>>;
@@ -45,6 +65,8 @@ pub type InputRuntimeArg<'a, MS, R> = <InputArg<MS> as LaunchArg>::RuntimeArg<'a
 /// Output runtime argument
 pub type OutputRuntimeArg<'a, MS, R> = <OutputArg<MS> as LaunchArg>::RuntimeArg<'a, R>;
 
+type EL<MS> = <MS as MatmulSpec>::EL;
+type ER<MS> = <MS as MatmulSpec>::ER;
 type EG<MS> = <MS as MatmulSpec>::EG;
 type Args<MS> = <MS as MatmulSpec>::Args;
 
@@ -60,8 +82,53 @@ pub struct SingleMatmulSpec<EG, ES, EA, Args = TensorArgs> {
 impl<Args: MatmulArgs, EG: Numeric, ES: Numeric, EA: Numeric> MatmulSpec
     for SingleMatmulSpec<EG, ES, EA, Args>
 {
+    type EL = EG;
+    type ER = EG;
     type EG = EG;
     type ES = ES;
     type EA = EA;
     type Args = Args;
 }
+
+/// Specification for a matmul whose lhs and rhs tensors have different element types.
+///
+/// Both inputs are cast to `ES` when they are loaded into shared memory, so the tile matmul
+/// always works on a single input type.
+#[derive(Clone)]
+pub struct MixedMatmulSpec<EL, ER, EG, ES, EA, Args = TensorArgs> {
+    _el: PhantomData<EL>,
+    _er: PhantomData<ER>,
+    _eg: PhantomData<EG>,
+    _es: PhantomData<ES>,
+    _ea: PhantomData<EA>,
+    _args: PhantomData<Args>,
+}
+
+impl<Args: MatmulArgs, EL: Numeric, ER: Numeric, EG: Numeric, ES: Numeric, EA: Numeric> MatmulSpec
+    for MixedMatmulSpec<EL, ER, EG, ES, EA, Args>
+{
+    type EL = EL;
+    type ER = ER;
+    type EG = EG;
+    type ES = ES;
+    type EA = EA;
+    type Args = Args;
+}
+
+/// Specification for an integer matmul, with `i8` inputs multiplied and accumulated in `i32`.
+///
+/// The inputs stay `i8` in shared memory and are only widened by the tile matmul, which must
+/// support integer cmma instructions.
+#[derive(Clone)]
+pub struct IntegerMatmulSpec<Args = TensorArgs> {
+    _args: PhantomData<Args>,
+}
+
+impl<Args: MatmulArgs> MatmulSpec for IntegerMatmulSpec<Args> {
+    type EL = i8;
+    type ER = i8;
+    type EG = i32;
+    type ES = i8;
+    type EA = i32;
+    type Args = Args;
+}
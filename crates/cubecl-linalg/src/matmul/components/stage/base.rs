@@ -123,6 +123,20 @@ pub trait StageWriter<EG: Numeric>: CubeType + 'static + Send + Sync {
         accumulator_offset: u32,
         #[comptime] config: G,
     );
+
+    /// Same as [write](StageWriter::write), but lines falling out of the rows or columns of the
+    /// output are skipped, whatever the bounds checked by the config.
+    ///
+    /// The stage matmuls write with it whenever the config checks any bound, i.e. for problems
+    /// that aren't a multiple of the stage size, and keep [write](StageWriter::write) as the
+    /// full-write fast path otherwise.
+    fn write_masked<ES: Numeric, G: global::GlobalConfig>(
+        this: &mut Self,
+        slice: Slice<Line<ES>>,
+        compute_plane_offset: u32,
+        accumulator_offset: u32,
+        #[comptime] config: G,
+    );
 }
 
 /// Configuration for the Stage matmul (SMM) level
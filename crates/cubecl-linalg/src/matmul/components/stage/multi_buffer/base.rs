@@ -5,7 +5,7 @@ use cubecl_core::prelude::*;
 
 use crate::matmul::components::global::AccumulatorLoader;
 use crate::matmul::components::stage::shared::{
-    stage_matmul_size, CommonStageConfig, CommonStageInput,
+    check_unroll, stage_matmul_size, CommonStageConfig, CommonStageInput,
 };
 use crate::matmul::components::stage::{StageMatmul, StageMatmulFamily};
 use crate::matmul::components::tile::TileMatmulFamily;
@@ -54,6 +54,7 @@ impl<TMM: TileMatmulFamily> MatmulConfigFactory for MultiBufferMatmulFamily<TMM>
             config.stage_dim(Ident::Lhs).num_tiles_x_dim(),
             config.num_planes(),
         )?;
+        check_unroll(config.unroll)?;
         TMM::check_config(&config.to_tmm_config())
     }
 
@@ -95,6 +96,7 @@ impl<TMM: TileMatmulFamily> MatmulConfigFactory for MultiBufferMatmulFamily<TMM>
             cube_dim.y,
             advanced_config.lhs_tiling_order,
             advanced_config.rhs_tiling_order,
+            advanced_config.stage_unroll,
         )
     }
 }
@@ -136,24 +138,30 @@ where
         acc: &mut Self::Accumulator,
         #[comptime] config: Self::Config,
     ) {
-        #[unroll]
-        for buffer_iter in 0..config.num_stage.k {
-            let tile_lhs =
-                LhsReader::read_tile::<TMM::Config>(lhs_reader, UNIT_POS_Y, buffer_iter, config);
-            TMM::fill_lhs(&tile_lhs, lhs_tile, config.to_tmm_config());
-
+        if comptime!(config.unroll >= config.num_stage.k) {
             #[unroll]
-            for accumulator_iter in 0..acc.len() {
-                let tile_rhs = RhsReader::read_tile::<TMM::Config>(
+            for buffer_iter in 0..config.num_stage.k {
+                Self::execute_k_tile(
+                    lhs_reader,
                     rhs_reader,
+                    lhs_tile,
+                    rhs_tile,
+                    acc,
+                    buffer_iter,
+                    config,
+                );
+            }
+        } else {
+            for buffer_iter in range_unrolled_by(0, config.num_stage.k, config.unroll) {
+                Self::execute_k_tile(
+                    lhs_reader,
+                    rhs_reader,
+                    lhs_tile,
+                    rhs_tile,
+                    acc,
                     buffer_iter,
-                    accumulator_iter,
                     config,
                 );
-                TMM::fill_rhs(&tile_rhs, rhs_tile, config.to_tmm_config());
-
-                let accumulator = acc.index_mut(accumulator_iter);
-                TMM::execute(lhs_tile, rhs_tile, accumulator, config.to_tmm_config());
             }
         }
     }
@@ -186,13 +194,25 @@ where
             let accumulator = acc.index(accumulator_iter);
             let mut smem_slice = out_smem.slice_mut(start, start + num_tile_lines);
             TMM::read_accumulator(accumulator, &mut smem_slice, stage_config.to_tmm_config());
-            SW::write::<O, G>(
-                out,
-                smem_slice.to_slice(),
-                UNIT_POS_Y,
-                accumulator_iter,
-                global_config,
-            );
+
+            // Tiles are only out of the output when the config checks its bounds.
+            if comptime!(global_config.check_m_bounds() || global_config.check_n_bounds()) {
+                SW::write_masked::<O, G>(
+                    out,
+                    smem_slice.to_slice(),
+                    UNIT_POS_Y,
+                    accumulator_iter,
+                    global_config,
+                );
+            } else {
+                SW::write::<O, G>(
+                    out,
+                    smem_slice.to_slice(),
+                    UNIT_POS_Y,
+                    accumulator_iter,
+                    global_config,
+                );
+            }
         }
     }
 
@@ -227,6 +247,44 @@ where
     }
 }
 
+#[cube]
+impl<I, O, EA, TMM> MultiBufferMatmul<I, O, EA, TMM>
+where
+    I: Numeric,
+    O: Numeric,
+    EA: Numeric,
+    TMM: tile::TileMatmul<I, EA>,
+{
+    /// Accumulate the products of the lhs tile at `buffer_iter` along k with every rhs tile.
+    fn execute_k_tile(
+        lhs_reader: &LhsReader<I>,
+        rhs_reader: &RhsReader<I>,
+        lhs_tile: &mut TMM::Lhs,
+        rhs_tile: &mut TMM::Rhs,
+        acc: &mut Sequence<TMM::Accumulator>,
+        buffer_iter: u32,
+        #[comptime] config: CommonStageConfig<TMM::Config>,
+    ) {
+        let tile_lhs =
+            LhsReader::read_tile::<TMM::Config>(lhs_reader, UNIT_POS_Y, buffer_iter, config);
+        TMM::fill_lhs(&tile_lhs, lhs_tile, config.to_tmm_config());
+
+        #[unroll]
+        for accumulator_iter in 0..acc.len() {
+            let tile_rhs = RhsReader::read_tile::<TMM::Config>(
+                rhs_reader,
+                buffer_iter,
+                accumulator_iter,
+                config,
+            );
+            TMM::fill_rhs(&tile_rhs, rhs_tile, config.to_tmm_config());
+
+            let accumulator = acc.index_mut(accumulator_iter);
+            TMM::execute(lhs_tile, rhs_tile, accumulator, config.to_tmm_config());
+        }
+    }
+}
+
 fn check_num_planes(
     expected_num_planes: u32,
     actual_num_planes: u32,
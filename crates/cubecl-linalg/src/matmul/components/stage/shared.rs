@@ -3,8 +3,8 @@ use cubecl_core as cubecl;
 
 use crate::matmul::components::{
     tile::{TileConfig, TileMatmulFamily},
-    Ident, InputIdent, LhsStageDim, MatmulConfig, MatmulSize, MatrixLayout, OutStageDim,
-    RhsStageDim, StageDim,
+    Ident, InputIdent, InvalidConfigError, LhsStageDim, MatmulConfig, MatmulSize, MatrixLayout,
+    OutStageDim, RhsStageDim, StageDim,
 };
 
 use super::{StageConfig, TilingOrderConfig};
@@ -14,6 +14,17 @@ pub struct CommonStageInput<TMM: TileMatmulFamily> {
     pub num_stages: MatmulSize,
 }
 
+/// Check that the loop over the k tiles of a stage unrolls at least one iteration at a time.
+pub(super) fn check_unroll(unroll: u32) -> Result<(), InvalidConfigError> {
+    if unroll == 0 {
+        return Err(Box::new(
+            "Error: Expected the stage to be unrolled by at least 1, but found 0.",
+        ));
+    }
+
+    Ok(())
+}
+
 pub(super) fn stage_matmul_size<TMM: TileMatmulFamily>(
     config: &TMM::Config,
     num_stage: &MatmulSize,
@@ -38,6 +49,8 @@ pub struct CommonStageConfig<T: TileConfig> {
     pub num_planes: u32,
     pub lhs_tiling_order: TilingOrderConfig,
     pub rhs_tiling_order: TilingOrderConfig,
+    /// See [AdvancedConfig::stage_unroll](crate::matmul::kernels::matmul::AdvancedConfig::stage_unroll).
+    pub unroll: u32,
 }
 
 impl<T: TileConfig> StageConfig for CommonStageConfig<T> {
@@ -56,6 +69,7 @@ impl<T: TileConfig> StageConfig for CommonStageConfig<T> {
             Ident::Lhs => Box::new(self.lhs_stage_dim),
             Ident::Rhs => Box::new(self.rhs_stage_dim),
             Ident::Out => Box::new(self.out_stage_dim),
+            Ident::Bias => panic!("Bias is not loaded into a stage."),
         }
     }
 
@@ -96,6 +110,7 @@ impl<T: TileConfig> CommonStageConfig<T> {
         num_planes: u32,
         lhs_tiling_order: TilingOrderConfig,
         rhs_tiling_order: TilingOrderConfig,
+        unroll: u32,
     ) -> Self {
         Self {
             num_stage,
@@ -106,6 +121,7 @@ impl<T: TileConfig> CommonStageConfig<T> {
             num_planes,
             lhs_tiling_order,
             rhs_tiling_order,
+            unroll,
         }
     }
 }
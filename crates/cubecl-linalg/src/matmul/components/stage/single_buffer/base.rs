@@ -4,7 +4,7 @@ use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
 
 use crate::matmul::components::stage::shared::{
-    stage_matmul_size, CommonStageConfig, CommonStageInput,
+    check_unroll, stage_matmul_size, CommonStageConfig, CommonStageInput,
 };
 use crate::matmul::components::stage::StageMatmulFamily;
 use crate::matmul::components::tile::{TileMatmul, TileMatmulFamily};
@@ -49,6 +49,7 @@ where
     type Config = CommonStageConfig<TMM::Config>;
 
     fn check_config(config: &Self::Config) -> Result<(), InvalidConfigError> {
+        check_unroll(config.unroll)?;
         TMM::check_config(&config.to_tmm_config())
     }
 
@@ -91,6 +92,7 @@ where
             lhs_stage_dim.num_tiles_x_dim(),
             advanced_config.lhs_tiling_order,
             advanced_config.rhs_tiling_order,
+            advanced_config.stage_unroll,
         )
     }
 }
@@ -205,13 +207,25 @@ where
             let accumulator = acc.index(accumulator_iter);
             let mut smem_slice = out_smem.slice_mut(start, start + num_tile_lines);
             TMM::read_accumulator(accumulator, &mut smem_slice, stage_config.to_tmm_config());
-            SW::write::<O, G>(
-                out,
-                smem_slice.to_slice(),
-                UNIT_POS_Y,
-                accumulator_iter,
-                global_config,
-            );
+
+            // Tiles are only out of the output when the config checks its bounds.
+            if comptime!(global_config.check_m_bounds() || global_config.check_n_bounds()) {
+                SW::write_masked::<O, G>(
+                    out,
+                    smem_slice.to_slice(),
+                    UNIT_POS_Y,
+                    accumulator_iter,
+                    global_config,
+                );
+            } else {
+                SW::write::<O, G>(
+                    out,
+                    smem_slice.to_slice(),
+                    UNIT_POS_Y,
+                    accumulator_iter,
+                    global_config,
+                );
+            }
         }
     }
 }
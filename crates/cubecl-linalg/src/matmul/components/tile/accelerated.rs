@@ -1,17 +1,75 @@
 use crate::matmul::components::config::MatmulConfig;
-use crate::matmul::components::tile::{TileConfig, TileMatmul, TileMatmulFamily};
+use crate::matmul::components::tile::{
+    check_global_types, TileConfig, TileMatmul, TileMatmulFamily,
+};
 use crate::matmul::components::{
-    as_cmma_layout, Ident, InvalidConfigError, MatmulConfigFactory, MatmulPrecision, MatmulProblem,
-    MatmulSize, MatrixLayout,
+    as_cmma_layout, FormattedConfigError, Ident, InvalidConfigError, MatmulConfigFactory,
+    MatmulPrecision, MatmulProblem, MatmulSize, MatrixLayout,
 };
 use crate::matmul::kernels::matmul::AdvancedConfig;
 use crate::matmul::kernels::MatmulAvailabilityError;
 use cubecl_core::ir::{Elem, FloatKind};
 use cubecl_core::{self as cubecl, Feature};
 use cubecl_core::{cmma, prelude::*};
+use cubecl_runtime::DeviceProperties;
 
+/// Tile matmul on cmma instructions, which map to the tensor cores of the device.
+///
+/// Integer inputs, such as `i8` accumulated in `i32`, are supported on devices with
+/// [integer cmma](Feature::CmmaInt).
 pub struct Accelerated;
 
+impl Accelerated {
+    /// Tile sizes of the cmma instructions found on tensor cores.
+    ///
+    /// Devices only support some of them, depending on the element types, see
+    /// [supported_tile_sizes](Accelerated::supported_tile_sizes).
+    pub const TILE_SIZES: [MatmulSize; 4] = [
+        MatmulSize {
+            m: 16,
+            n: 16,
+            k: 16,
+        },
+        MatmulSize { m: 32, n: 8, k: 16 },
+        MatmulSize { m: 8, n: 32, k: 16 },
+        MatmulSize { m: 16, n: 16, k: 8 },
+    ];
+
+    /// Tile sizes among [TILE_SIZES](Accelerated::TILE_SIZES) for which the device has a cmma
+    /// instruction multiplying `input` elements into an `accumulator`.
+    pub fn supported_tile_sizes(
+        properties: &DeviceProperties<Feature>,
+        input: Elem,
+        accumulator: Elem,
+    ) -> Vec<MatmulSize> {
+        Self::TILE_SIZES
+            .into_iter()
+            .filter(|size| {
+                properties.feature_enabled(Feature::Cmma {
+                    a: input,
+                    b: input,
+                    c: accumulator,
+                    m: size.m as u8,
+                    k: size.k as u8,
+                    n: size.n as u8,
+                })
+            })
+            .collect()
+    }
+
+    /// Whether the client supports the cmma instruction of the given tile size for the
+    /// precision, without attempting a launch.
+    ///
+    /// Unlike [PlaneMma](super::plane::PlaneMma), the answer depends on the element types and
+    /// the tile size, since tensor cores only support a few combinations of them.
+    pub fn is_available<R: Runtime, MP: MatmulPrecision>(
+        client: &ComputeClient<R::Server, R::Channel>,
+        tile: MatmulSize,
+    ) -> bool {
+        check_cmma::<R, MP>(client, tile).is_ok()
+    }
+}
+
 impl TileMatmulFamily for Accelerated {
     type Matmul<I: Numeric, O: Numeric> = Accelerated;
 
@@ -79,6 +137,9 @@ impl<I: Numeric, O: Numeric> TileMatmul<I, O> for Accelerated {
             match config.layout(Ident::Lhs) {
                 MatrixLayout::RowMajor => config.size.k,
                 MatrixLayout::ColMajor => config.size.m,
+                MatrixLayout::Strided { .. } => {
+                    unreachable!("Configs only hold contiguous layouts")
+                }
             },
         );
     }
@@ -90,6 +151,9 @@ impl<I: Numeric, O: Numeric> TileMatmul<I, O> for Accelerated {
             match config.layout(Ident::Rhs) {
                 MatrixLayout::RowMajor => config.size.n,
                 MatrixLayout::ColMajor => config.size.k,
+                MatrixLayout::Strided { .. } => {
+                    unreachable!("Configs only hold contiguous layouts")
+                }
             },
         );
     }
@@ -139,6 +203,23 @@ impl MatmulConfigFactory for Accelerated {
         if config.plane_dim != 32 {
             return Err(Box::new("Error: Expected plane dimension to be 32, but found {}. Please ensure that cube dimension x is set correctly."));
         }
+
+        let size = config.size;
+        if !Self::TILE_SIZES.contains(&size) {
+            return Err(FormattedConfigError::new(move || {
+                format!(
+                    "Tile size {}x{}x{} is not the shape of a cmma instruction, expected one of {:?}.",
+                    size.m,
+                    size.n,
+                    size.k,
+                    Self::TILE_SIZES
+                        .iter()
+                        .map(|size| format!("{}x{}x{}", size.m, size.n, size.k))
+                        .collect::<Vec<_>>(),
+                )
+            }));
+        }
+
         Ok(())
     }
 
@@ -146,47 +227,7 @@ impl MatmulConfigFactory for Accelerated {
         client: &ComputeClient<R::Server, R::Channel>,
         config: &Self::Config,
     ) -> Result<(), MatmulAvailabilityError> {
-        let i_elem = MP::ES::as_elem_native().expect("to be a native type");
-        let o_elem = MP::EG::as_elem_native().expect("to be a native type");
-
-        let i_elem = match i_elem {
-            Elem::Float(FloatKind::Flex32) => Elem::Float(FloatKind::F32),
-            _ => i_elem,
-        };
-
-        let o_elem = match o_elem {
-            Elem::Float(FloatKind::Flex32) => Elem::Float(FloatKind::F32),
-            _ => o_elem,
-        };
-
-        let size = config.size;
-        if !client.properties().feature_enabled(Feature::Cmma {
-            a: i_elem,
-            b: i_elem,
-            c: o_elem,
-            m: size.m as u8,
-            k: size.k as u8,
-            n: size.n as u8,
-        }) {
-            return Err(MatmulAvailabilityError::CmmaInstructionUnavailable {
-                input: i_elem,
-                output: o_elem,
-                m: size.m,
-                n: size.n,
-                k: size.k,
-            });
-        }
-
-        if !(client.properties().feature_enabled(Feature::Type(i_elem))
-            && client.properties().feature_enabled(Feature::Type(o_elem)))
-        {
-            return Err(MatmulAvailabilityError::TypesUnavailable {
-                input: i_elem,
-                output: o_elem,
-            });
-        }
-
-        Ok(())
+        check_cmma::<R, MP>(client, config.size)
     }
 
     fn make_config(
@@ -197,13 +238,17 @@ impl MatmulConfigFactory for Accelerated {
         advanced_config: &AdvancedConfig,
     ) -> Self::Config {
         let (lhs_tile_layout, lhs_tile_line_size) = match advanced_config.enforced_tile_layout.0 {
-            Some(enforced_layout) if enforced_layout != problem.lhs_layout => (enforced_layout, 1),
-            _ => (problem.lhs_layout, problem.lhs_line_size),
+            Some(enforced_layout) if enforced_layout != problem.lhs_layout.major() => {
+                (enforced_layout, 1)
+            }
+            _ => (problem.lhs_layout.major(), problem.lhs_line_size),
         };
 
         let (rhs_tile_layout, rhs_tile_line_size) = match advanced_config.enforced_tile_layout.1 {
-            Some(enforced_layout) if enforced_layout != problem.rhs_layout => (enforced_layout, 1),
-            _ => (problem.rhs_layout, problem.rhs_line_size),
+            Some(enforced_layout) if enforced_layout != problem.rhs_layout.major() => {
+                (enforced_layout, 1)
+            }
+            _ => (problem.rhs_layout.major(), problem.rhs_line_size),
         };
 
         Config::new(
@@ -239,7 +284,7 @@ impl TileConfig for Config {
         match ident {
             Ident::Lhs => self.lhs_layout,
             Ident::Rhs => self.rhs_layout,
-            Ident::Out => MatrixLayout::RowMajor,
+            Ident::Out | Ident::Bias => MatrixLayout::RowMajor,
         }
     }
 
@@ -247,7 +292,7 @@ impl TileConfig for Config {
         match ident {
             Ident::Lhs => self.lhs_line_size,
             Ident::Rhs => self.rhs_line_size,
-            Ident::Out => self.out_line_size,
+            Ident::Out | Ident::Bias => self.out_line_size,
         }
     }
 
@@ -279,3 +324,55 @@ impl Config {
         }
     }
 }
+
+/// Check that the cmma instruction of the given tile size is supported for the precision,
+/// along with the element types.
+fn check_cmma<R: Runtime, MP: MatmulPrecision>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    size: MatmulSize,
+) -> Result<(), MatmulAvailabilityError> {
+    let native = |elem: Elem| match elem {
+        Elem::Float(FloatKind::Flex32) => Elem::Float(FloatKind::F32),
+        _ => elem,
+    };
+
+    // Both inputs are cast to the stage element before reaching the tile matmul,
+    // which accumulates in the accumulator element.
+    let i_elem = native(MP::ES::as_elem_native().expect("to be a native type"));
+    let acc_elem = native(MP::EA::as_elem_native().expect("to be a native type"));
+    let o_elem = native(MP::EG::as_elem_native().expect("to be a native type"));
+
+    if matches!(i_elem, Elem::Int(_) | Elem::UInt(_))
+        && !client.properties().feature_enabled(Feature::CmmaInt)
+    {
+        return Err(MatmulAvailabilityError::IntegerCmmaUnavailable);
+    }
+
+    if !client.properties().feature_enabled(Feature::Cmma {
+        a: i_elem,
+        b: i_elem,
+        c: acc_elem,
+        m: size.m as u8,
+        k: size.k as u8,
+        n: size.n as u8,
+    }) {
+        return Err(MatmulAvailabilityError::CmmaInstructionUnavailable {
+            input: i_elem,
+            output: acc_elem,
+            m: size.m,
+            n: size.n,
+            k: size.k,
+        });
+    }
+
+    if !(client.properties().feature_enabled(Feature::Type(i_elem))
+        && client.properties().feature_enabled(Feature::Type(o_elem)))
+    {
+        return Err(MatmulAvailabilityError::TypesUnavailable {
+            input: i_elem,
+            output: o_elem,
+        });
+    }
+
+    check_global_types::<R, MP>(client)
+}
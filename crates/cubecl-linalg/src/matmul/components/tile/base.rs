@@ -1,9 +1,12 @@
 use cubecl_core as cubecl;
+use cubecl_core::ir::{Elem, FloatKind};
 use cubecl_core::prelude::*;
+use cubecl_core::Feature;
 
 use crate::matmul::components::{
-    config::MatmulConfig, Ident, MatmulConfigFactory, MatmulSize, MatrixLayout,
+    config::MatmulConfig, Ident, MatmulConfigFactory, MatmulPrecision, MatmulSize, MatrixLayout,
 };
+use crate::matmul::kernels::MatmulAvailabilityError;
 
 pub trait TileMatmulFamily: MatmulConfigFactory<Config: TileConfig> {
     fn size(config: &Self::Config) -> MatmulSize;
@@ -108,3 +111,42 @@ pub trait TileConfig: MatmulConfig {
     /// Returns the line size for the given ident
     fn size(&self) -> &MatmulSize;
 }
+
+/// Checks that the element types of both inputs and of the output in global memory are
+/// supported by the device.
+pub(crate) fn check_global_types<R: Runtime, MP: MatmulPrecision>(
+    client: &ComputeClient<R::Server, R::Channel>,
+) -> Result<(), MatmulAvailabilityError> {
+    let native = |elem: Elem| match elem {
+        Elem::Float(FloatKind::Flex32) => Elem::Float(FloatKind::F32),
+        _ => elem,
+    };
+    let output = native(MP::EG::as_elem_native_unchecked());
+
+    for input in [
+        MP::EL::as_elem_native_unchecked(),
+        MP::ER::as_elem_native_unchecked(),
+    ] {
+        let input = native(input);
+
+        if !(client.properties().feature_enabled(Feature::Type(input))
+            && client.properties().feature_enabled(Feature::Type(output)))
+        {
+            return Err(MatmulAvailabilityError::TypesUnavailable { input, output });
+        }
+    }
+
+    // The accumulator is configurable, so it may be a type the inputs and output don't use.
+    let accumulator = native(MP::EA::as_elem_native_unchecked());
+    if !client
+        .properties()
+        .feature_enabled(Feature::Type(accumulator))
+    {
+        return Err(MatmulAvailabilityError::TypesUnavailable {
+            input: native(MP::ES::as_elem_native_unchecked()),
+            output: accumulator,
+        });
+    }
+
+    Ok(())
+}
@@ -8,7 +8,7 @@ use crate::matmul::kernels::MatmulAvailabilityError;
 
 use cubecl_core::prelude::*;
 use cubecl_core::{self as cubecl, Feature};
-use tile::{TileConfig, TileMatmul, TileMatmulFamily};
+use tile::{check_global_types, TileConfig, TileMatmul, TileMatmulFamily};
 
 /// PlaneMMA instruction uses plane cooperation but does not rely on tensor cores
 ///
@@ -21,6 +21,27 @@ use tile::{TileConfig, TileMatmul, TileMatmulFamily};
 ///  - If not vec4, there are patches in read_output that may harm performance
 pub struct PlaneMma;
 
+impl PlaneMma {
+    /// Whether the client supports the plane operations needed by PlaneMma,
+    /// without attempting a launch.
+    ///
+    /// The element types still need to be supported, see
+    /// [check_availability](MatmulConfigFactory::check_availability).
+    pub fn is_available<R: Runtime>(client: &ComputeClient<R::Server, R::Channel>) -> bool {
+        check_plane_operations::<R>(client).is_ok()
+    }
+}
+
+fn check_plane_operations<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+) -> Result<(), MatmulAvailabilityError> {
+    if !client.properties().feature_enabled(Feature::Plane) {
+        return Err(MatmulAvailabilityError::PlaneOperationsUnavailable);
+    }
+
+    Ok(())
+}
+
 impl TileMatmulFamily for PlaneMma {
     type Matmul<I: Numeric, O: Numeric> = Self;
 
@@ -104,6 +125,7 @@ impl<I: Numeric, O: Numeric> TileMatmul<I, O> for PlaneMma {
                 config.line_size(Ident::Lhs),
                 config.plane_dim(),
             ),
+            MatrixLayout::Strided { .. } => unreachable!("Configs only hold contiguous layouts"),
         }
     }
 
@@ -127,6 +149,7 @@ impl<I: Numeric, O: Numeric> TileMatmul<I, O> for PlaneMma {
                 config.line_size(Ident::Rhs),
                 config.plane_dim(),
             ),
+            MatrixLayout::Strided { .. } => unreachable!("Configs only hold contiguous layouts"),
         }
     }
 
@@ -376,13 +399,17 @@ impl MatmulConfigFactory for PlaneMma {
         advanced_config: &AdvancedConfig,
     ) -> Self::Config {
         let (lhs_tile_layout, lhs_tile_line_size) = match advanced_config.enforced_tile_layout.0 {
-            Some(enforced_layout) if enforced_layout != problem.lhs_layout => (enforced_layout, 1),
-            _ => (problem.lhs_layout, problem.lhs_line_size),
+            Some(enforced_layout) if enforced_layout != problem.lhs_layout.major() => {
+                (enforced_layout, 1)
+            }
+            _ => (problem.lhs_layout.major(), problem.lhs_line_size),
         };
 
         let (rhs_tile_layout, rhs_tile_line_size) = match advanced_config.enforced_tile_layout.1 {
-            Some(enforced_layout) if enforced_layout != problem.rhs_layout => (enforced_layout, 1),
-            _ => (problem.rhs_layout, problem.rhs_line_size),
+            Some(enforced_layout) if enforced_layout != problem.rhs_layout.major() => {
+                (enforced_layout, 1)
+            }
+            _ => (problem.rhs_layout.major(), problem.rhs_line_size),
         };
 
         Config::new(
@@ -400,23 +427,8 @@ impl MatmulConfigFactory for PlaneMma {
         client: &ComputeClient<R::Server, R::Channel>,
         _config: &Self::Config,
     ) -> Result<(), MatmulAvailabilityError> {
-        let i_elem = MP::EG::as_elem_native_unchecked();
-        let o_elem = MP::EG::as_elem_native_unchecked();
-
-        if !client.properties().feature_enabled(Feature::Plane) {
-            return Err(MatmulAvailabilityError::PlaneOperationsUnavailable);
-        }
-
-        if !(client.properties().feature_enabled(Feature::Type(i_elem))
-            && client.properties().feature_enabled(Feature::Type(o_elem)))
-        {
-            return Err(MatmulAvailabilityError::TypesUnavailable {
-                input: i_elem,
-                output: o_elem,
-            });
-        }
-
-        Ok(())
+        check_plane_operations::<R>(client)?;
+        check_global_types::<R, MP>(client)
     }
 }
 
@@ -441,7 +453,7 @@ impl TileConfig for Config {
         match ident {
             Ident::Lhs => self.lhs_layout,
             Ident::Rhs => self.rhs_layout,
-            Ident::Out => MatrixLayout::RowMajor,
+            Ident::Out | Ident::Bias => MatrixLayout::RowMajor,
         }
     }
 
@@ -449,7 +461,7 @@ impl TileConfig for Config {
         match ident {
             Ident::Lhs => self.lhs_line_size,
             Ident::Rhs => self.rhs_line_size,
-            Ident::Out => self.out_line_size,
+            Ident::Out | Ident::Bias => self.out_line_size,
         }
     }
 
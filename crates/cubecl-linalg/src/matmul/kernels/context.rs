@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use cubecl_core::ir::Elem;
+use cubecl_core::prelude::*;
+use cubecl_core::server::Handle;
+
+use crate::tensor::TensorHandle;
+
+/// Intermediate buffers of matmul launches, kept from one launch to the next.
+///
+/// Launches given the same context reuse the buffers of previous launches with the same shape
+/// and element type instead of allocating new ones, which helps loops launching the same problem
+/// many times. The buffers stay allocated until the context is [cleared](MatmulContext::clear)
+/// or dropped.
+///
+/// Launches on the same client run in order, so consecutive launches can share a buffer. A
+/// context must not be used by several clients at once.
+pub struct MatmulContext<R: Runtime> {
+    buffers: HashMap<(Elem, Vec<usize>), Handle>,
+    num_allocations: usize,
+    _runtime: PhantomData<R>,
+}
+
+impl<R: Runtime> Default for MatmulContext<R> {
+    fn default() -> Self {
+        Self {
+            buffers: HashMap::new(),
+            num_allocations: 0,
+            _runtime: PhantomData,
+        }
+    }
+}
+
+impl<R: Runtime> MatmulContext<R> {
+    /// Create an empty context.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a contiguous intermediate tensor of the given shape, whose content is undefined.
+    ///
+    /// The buffer is only allocated the first time a shape is asked for with this element type.
+    pub fn intermediate<E: Numeric>(
+        &mut self,
+        client: &ComputeClient<R::Server, R::Channel>,
+        shape: Vec<usize>,
+    ) -> TensorHandle<R, E> {
+        let key = (E::as_elem_native_unchecked(), shape.clone());
+        let handle = self
+            .buffers
+            .entry(key)
+            .or_insert_with(|| {
+                self.num_allocations += 1;
+                TensorHandle::<R, E>::empty(client, shape.clone()).handle
+            })
+            .clone();
+
+        TensorHandle::new_contiguous(shape, handle)
+    }
+
+    /// Number of buffers held by the context.
+    pub fn num_buffers(&self) -> usize {
+        self.buffers.len()
+    }
+
+    /// Number of buffers the context allocated since it was created.
+    pub fn num_allocations(&self) -> usize {
+        self.num_allocations
+    }
+
+    /// Free the buffers held by the context.
+    pub fn clear(&mut self) {
+        self.buffers.clear();
+    }
+}
@@ -1,12 +1,41 @@
 use cubecl_core::{ir::Elem, CubeCount};
 use std::fmt::Debug;
 
-use crate::matmul::components::InvalidConfigError;
+use crate::matmul::components::{global::EpilogueOp, Ident, InvalidConfigError};
 
 pub enum MatmulLaunchError {
     Unavailable(MatmulAvailabilityError),
     InvalidProblem(MatmulInvalidProblem),
     InvalidConfig(InvalidConfigError),
+    /// The identified tensor can't be accessed with lines of `line_size` elements along its
+    /// dimension of size `dim`, because the size isn't divisible by the line size or the
+    /// dimension isn't contiguous.
+    ///
+    /// This is what the line size checks of the [problem](MatmulInvalidProblem) become once
+    /// converted into a launch error.
+    IncompatibleLineSize {
+        ident: Ident,
+        dim: u32,
+        line_size: u8,
+    },
+    /// The grid needed by the problem is larger than the
+    /// [maximum cube count](cubecl_runtime::memory_management::HardwareProperties::max_cube_count)
+    /// of the device on some axis.
+    ///
+    /// When the y or z axis is exceeded, launching with the
+    /// [FlattenedDispatch](crate::matmul::components::batch::FlattenedDispatch) folds all the
+    /// cubes into the x and y axes instead.
+    ExceedsDeviceLimits {
+        cube_count: (u32, u32, u32),
+        max: (u32, u32, u32),
+    },
+    /// The cube dim of the selected algorithm is larger than the maximum cube dim of the device
+    /// on some axis, or has more units than a cube of the device can hold.
+    CubeDimExceedsDeviceLimits {
+        cube_dim: (u32, u32, u32),
+        max: (u32, u32, u32),
+        max_units: u32,
+    },
 }
 
 pub enum MatmulAvailabilityError {
@@ -15,7 +44,13 @@ pub enum MatmulAvailabilityError {
     PlaneDimUnsupported {
         plane_dim: u32,
     },
+    PlaneDimMismatch {
+        selected: u32,
+        device: u32,
+    },
     PlaneOperationsUnavailable,
+    PipelineUnavailable,
+    IntegerCmmaUnavailable,
     TypesUnavailable {
         input: Elem,
         output: Elem,
@@ -27,6 +62,9 @@ pub enum MatmulAvailabilityError {
         n: u32,
         k: u32,
     },
+    AccumulatorNotConfigurable {
+        accumulator: Elem,
+    },
 }
 
 pub enum MatmulInvalidProblem {
@@ -36,11 +74,42 @@ pub enum MatmulInvalidProblem {
     InvalidLineSizeLhs { size: u32, line_size: u8 },
     InvalidLineSizeRhs { size: u32, line_size: u8 },
     InvalidLineSizeOut { size: u32, line_size: u8 },
+    InvalidBiasShape { shape: Vec<usize>, n: u32 },
+    InvalidSplitK { k: u32, splits: u32 },
+    EmptyProblem { m: u32, n: u32, k: u32 },
+    MismatchedBatchRank { lhs: u32, rhs: u32 },
+    IncompatibleBatches { lhs: Vec<usize>, rhs: Vec<usize> },
+    InvalidPartials { shape: Vec<usize>, out: Vec<usize> },
+    UnalignedStrides { strides: Vec<usize>, line_size: u8 },
+    UnsupportedLineSize { ident: Ident, line_size: u8 },
+    InvalidRank { ident: Ident, rank: u32 },
+    MismatchedK { lhs: u32, rhs: u32 },
+    InvalidOutShape { out: Vec<usize>, dims: Vec<usize> },
+    OutOfBounds { ident: Ident, bytes: u64, size: u64 },
+    AliasedStrides { ident: Ident, rows: u32, cols: u32 },
+    FloatEpilogue { epilogue: EpilogueOp, elem: Elem },
 }
 
 impl From<MatmulInvalidProblem> for MatmulLaunchError {
     fn from(value: MatmulInvalidProblem) -> Self {
-        Self::InvalidProblem(value)
+        let incompatible_line_size = |ident, size, line_size| Self::IncompatibleLineSize {
+            ident,
+            dim: size,
+            line_size,
+        };
+
+        match value {
+            MatmulInvalidProblem::InvalidLineSizeLhs { size, line_size } => {
+                incompatible_line_size(Ident::Lhs, size, line_size)
+            }
+            MatmulInvalidProblem::InvalidLineSizeRhs { size, line_size } => {
+                incompatible_line_size(Ident::Rhs, size, line_size)
+            }
+            MatmulInvalidProblem::InvalidLineSizeOut { size, line_size } => {
+                incompatible_line_size(Ident::Out, size, line_size)
+            }
+            value => Self::InvalidProblem(value),
+        }
     }
 }
 
@@ -80,6 +149,32 @@ impl Debug for MatmulLaunchError {
                     err.to_string()
                 )
             }
+            MatmulLaunchError::IncompatibleLineSize {
+                ident,
+                dim,
+                line_size,
+            } => {
+                writeln!(
+                    f,
+                    "Unable to launch matmul because the {ident:?} tensor can't be accessed with lines of {line_size} elements along its dimension of size {dim}: the size must be divisible by the line size, and the dimension contiguous"
+                )
+            }
+            MatmulLaunchError::ExceedsDeviceLimits { cube_count, max } => {
+                writeln!(
+                    f,
+                    "Unable to launch matmul because it needs a cube count of {cube_count:?} but the device can launch at most {max:?}: consider the FlattenedDispatch, which folds the cubes into the x and y axes"
+                )
+            }
+            MatmulLaunchError::CubeDimExceedsDeviceLimits {
+                cube_dim,
+                max,
+                max_units,
+            } => {
+                writeln!(
+                    f,
+                    "Unable to launch matmul because it needs a cube dim of {cube_dim:?} but the device allows at most {max:?}, with at most {max_units} units per cube"
+                )
+            }
         }
     }
 }
@@ -114,6 +209,62 @@ impl Debug for MatmulInvalidProblem {
                 f,
                 "The out tensor can't be written with line size={line_size} and dimension={size}"
             ),
+            MatmulInvalidProblem::InvalidBiasShape { shape, n } => write!(
+                f,
+                "The bias tensor must have shape [n] with n={n}, but its shape is {shape:?}"
+            ),
+            MatmulInvalidProblem::InvalidSplitK { k, splits } => write!(
+                f,
+                "Problem has k={k} which can't be split into {splits} splits"
+            ),
+            MatmulInvalidProblem::EmptyProblem { m, n, k } => write!(
+                f,
+                "Problem has m={m}, n={n} and k={k}, but none can be zero"
+            ),
+            MatmulInvalidProblem::MismatchedBatchRank { lhs, rhs } => write!(
+                f,
+                "The lhs tensor has {lhs} batch dimensions but the rhs tensor has {rhs}"
+            ),
+            MatmulInvalidProblem::IncompatibleBatches { lhs, rhs } => write!(
+                f,
+                "The lhs batches {lhs:?} can't be broadcasted with the rhs batches {rhs:?}, or are empty"
+            ),
+            MatmulInvalidProblem::InvalidPartials { shape, out } => write!(
+                f,
+                "Partials of shape {shape:?} can't be summed into an output of shape {out:?}"
+            ),
+            MatmulInvalidProblem::UnalignedStrides { strides, line_size } => write!(
+                f,
+                "A tensor with strides {strides:?} can't be read with lines of {line_size} elements"
+            ),
+            MatmulInvalidProblem::UnsupportedLineSize { ident, line_size } => write!(
+                f,
+                "The {ident:?} tensor can't be accessed with lines of {line_size} elements, which the runtime doesn't support for its element type"
+            ),
+            MatmulInvalidProblem::InvalidRank { ident, rank } => write!(
+                f,
+                "The {ident:?} tensor has rank {rank}, but it needs at least 2 dims and one stride per dim"
+            ),
+            MatmulInvalidProblem::MismatchedK { lhs, rhs } => write!(
+                f,
+                "The lhs tensor has k={lhs} but the rhs tensor has k={rhs}"
+            ),
+            MatmulInvalidProblem::InvalidOutShape { out, dims } => write!(
+                f,
+                "The out tensor has shape {out:?} but the problem expects {dims:?}"
+            ),
+            MatmulInvalidProblem::OutOfBounds { ident, bytes, size } => write!(
+                f,
+                "The {ident:?} tensor reaches {bytes} bytes but its handle only holds {size} bytes"
+            ),
+            MatmulInvalidProblem::AliasedStrides { ident, rows, cols } => write!(
+                f,
+                "The strides of the {ident:?} tensor map different elements of a {rows}x{cols} tile to the same address"
+            ),
+            MatmulInvalidProblem::FloatEpilogue { epilogue, elem } => write!(
+                f,
+                "The {epilogue:?} epilogue is only defined on floats, but the output is {elem}"
+            ),
         }
     }
 }
@@ -124,6 +275,12 @@ impl Debug for MatmulAvailabilityError {
             MatmulAvailabilityError::PlaneOperationsUnavailable => {
                 writeln!(f, "Plane operations not supported.")
             }
+            MatmulAvailabilityError::PipelineUnavailable => {
+                writeln!(f, "Asynchronous copies through a pipeline not supported.")
+            }
+            MatmulAvailabilityError::IntegerCmmaUnavailable => {
+                writeln!(f, "Cmma on integer inputs not supported.")
+            }
             MatmulAvailabilityError::CubeCountTooBig(count) => {
                 writeln!(f, "Cube count too big {count:?}")
             }
@@ -133,6 +290,9 @@ impl Debug for MatmulAvailabilityError {
             MatmulAvailabilityError::PlaneDimUnsupported{plane_dim} => {
                 writeln!(f, "Plane dimension unsupported: {plane_dim}. Only 32 & 64 are supported.")
             },
+            MatmulAvailabilityError::PlaneDimMismatch { selected, device } => {
+                writeln!(f, "Selected plane dimension {selected} doesn't match the plane dimension of the device ({device}).")
+            },
             MatmulAvailabilityError::TypesUnavailable { input, output } => {
                 writeln!(
                     f,
@@ -152,6 +312,9 @@ impl Debug for MatmulAvailabilityError {
                 input,
                 output, m, n, k
             ),
+            MatmulAvailabilityError::AccumulatorNotConfigurable { accumulator } => {
+                writeln!(f, "The strategy can't accumulate in {accumulator:?}, only in f32.")
+            }
         }
     }
 }
@@ -5,27 +5,35 @@ use cubecl_runtime::DeviceProperties;
 
 use crate::matmul::{
     components::{
-        batch::TransposedDispatch, stage::*, tile::TileMatmulFamily, InputRuntimeArg,
-        MatmulProblem, MatmulSelection, MatmulSize, MatmulSpec, OutputRuntimeArg,
+        batch::{CubeCountDispatch, CubeDispatch, SwizzleTransposedDispatch, TransposedDispatch},
+        stage::*,
+        tile::{accelerated::Accelerated, TileMatmulFamily},
+        InputRuntimeArg, MatmulProblem, MatmulSelection, MatmulSize, MatmulSpec, OutputRuntimeArg,
+    },
+    kernels::{
+        matmul::base::{matmul_cube_preparation, SelectedKernel},
+        MatmulLaunchError,
     },
-    kernels::{matmul::base::matmul_cube_preparation, MatmulLaunchError},
 };
 
 use super::{
-    pipelined::PipelinedAlgorithm, specialized::SpecializedAlgorithm, standard::StandardAlgorithm,
+    pipelined::PipelinedAlgorithm,
+    specialized::{SpecializedAlgorithm, SpecializedDoubleBufferedAlgorithm},
+    standard::{StandardAlgorithm, StandardPrefetchingAlgorithm},
 };
 
 const NUM_SM_APPROX: usize = 50;
 const NUM_TENSOR_CORES_APPROX: usize = 8;
 
 pub trait MatmulSelector {
+    /// Launches the kernel chosen for the problem, and returns how it was configured.
     fn select_kernel<'a, MS: MatmulSpec, R: Runtime>(
         client: &ComputeClient<R::Server, R::Channel>,
         input: InputRuntimeArg<'a, MS, R>,
         output: OutputRuntimeArg<'a, MS, R>,
         problem: MatmulProblem,
         plane_dim: u32,
-    ) -> Result<(), MatmulLaunchError>;
+    ) -> Result<SelectedKernel, MatmulLaunchError>;
     fn stage_tf32_supported() -> bool;
 }
 
@@ -34,6 +42,22 @@ pub struct StandardSelector<TMM: TileMatmulFamily, D = TransposedDispatch> {
     _dispatch: PhantomData<D>,
 }
 
+/// Same as [StandardSelector], with the stages of the output assigned to the cubes in a swizzled
+/// order, see [SwizzleTransposedDispatch].
+///
+/// Cubes launched together then compute stages in `W` neighbouring columns, which read the same
+/// rows of lhs and columns of rhs, so more of their reads hit the L2 cache.
+pub type StandardSwizzledSelector<TMM, const W: u32 = 4> =
+    StandardSelector<TMM, SwizzleTransposedDispatch<W>>;
+
+/// Selects the standard algorithm prefetching the next `DEPTH - 1` stages, or the standard one
+/// when the device can't copy asynchronously.
+///
+/// The selection is rejected if the `DEPTH` stages don't fit in the shared memory of the device.
+pub struct StandardPrefetchingSelector<TMM: TileMatmulFamily, const DEPTH: u32 = 2> {
+    _tmm: PhantomData<TMM>,
+}
+
 pub struct PipelinedSelector<TMM: TileMatmulFamily> {
     _tmm: PhantomData<TMM>,
 }
@@ -42,21 +66,74 @@ pub struct SpecializedSelector<TMM: TileMatmulFamily> {
     _tmm: PhantomData<TMM>,
 }
 
-impl<TMM: TileMatmulFamily> MatmulSelector for StandardSelector<TMM> {
+/// Selects the double-buffered specialized algorithm, or the single-buffered one when the
+/// device can't copy asynchronously.
+pub struct SpecializedDoubleBufferedSelector<TMM: TileMatmulFamily> {
+    _tmm: PhantomData<TMM>,
+}
+
+impl<TMM: TileMatmulFamily, D: CubeDispatch + CubeCountDispatch> MatmulSelector
+    for StandardSelector<TMM, D>
+{
     fn select_kernel<'a, MS: MatmulSpec, R: Runtime>(
         client: &ComputeClient<R::Server, R::Channel>,
         input: InputRuntimeArg<'a, MS, R>,
         output: OutputRuntimeArg<'a, MS, R>,
         problem: MatmulProblem,
         plane_dim: u32,
-    ) -> Result<(), MatmulLaunchError> {
+    ) -> Result<SelectedKernel, MatmulLaunchError> {
         let selection = matmul_selection::<TMM, MS, R>(client, &problem, plane_dim);
         let config_input = CommonStageInput {
             tile: TMM::input(selection.tile),
             num_stages: selection.num_stagess,
         };
 
-        matmul_cube_preparation::<MS, R, StandardAlgorithm<TMM>>(
+        matmul_cube_preparation::<MS, R, StandardAlgorithm<TMM, D>>(
+            client,
+            input,
+            output,
+            problem,
+            config_input,
+            selection,
+        )
+    }
+
+    fn stage_tf32_supported() -> bool {
+        TMM::requires_tensor_cores()
+    }
+}
+
+impl<TMM: TileMatmulFamily, const DEPTH: u32> MatmulSelector
+    for StandardPrefetchingSelector<TMM, DEPTH>
+{
+    fn select_kernel<'a, MS: MatmulSpec, R: Runtime>(
+        client: &ComputeClient<R::Server, R::Channel>,
+        input: InputRuntimeArg<'a, MS, R>,
+        output: OutputRuntimeArg<'a, MS, R>,
+        problem: MatmulProblem,
+        plane_dim: u32,
+    ) -> Result<SelectedKernel, MatmulLaunchError> {
+        if !client.properties().feature_enabled(Feature::Pipeline) {
+            return StandardSelector::<TMM, TransposedDispatch>::select_kernel::<MS, R>(
+                client, input, output, problem, plane_dim,
+            );
+        }
+
+        let selection = matmul_selection::<TMM, MS, R>(client, &problem, plane_dim);
+        StandardPrefetchingAlgorithm::<TMM, DEPTH>::check_shared_memory(
+            &selection,
+            MS::ES::as_elem_native_unchecked().size(),
+            client
+                .properties()
+                .hardware_properties()
+                .max_shared_memory_size,
+        )?;
+        let config_input = CommonStageInput {
+            tile: TMM::input(selection.tile),
+            num_stages: selection.num_stagess,
+        };
+
+        matmul_cube_preparation::<MS, R, StandardPrefetchingAlgorithm<TMM, DEPTH>>(
             client,
             input,
             output,
@@ -78,7 +155,7 @@ impl<TMM: TileMatmulFamily> MatmulSelector for PipelinedSelector<TMM> {
         output: OutputRuntimeArg<'a, MS, R>,
         problem: MatmulProblem,
         plane_dim: u32,
-    ) -> Result<(), MatmulLaunchError> {
+    ) -> Result<SelectedKernel, MatmulLaunchError> {
         let selection = matmul_selection::<TMM, MS, R>(client, &problem, plane_dim);
         let config_input = CommonStageInput {
             tile: TMM::input(selection.tile),
@@ -107,7 +184,7 @@ impl<TMM: TileMatmulFamily> MatmulSelector for SpecializedSelector<TMM> {
         output: OutputRuntimeArg<'a, MS, R>,
         problem: MatmulProblem,
         plane_dim: u32,
-    ) -> Result<(), MatmulLaunchError> {
+    ) -> Result<SelectedKernel, MatmulLaunchError> {
         let selection = matmul_selection::<TMM, MS, R>(client, &problem, plane_dim);
         let config_input = CommonStageInput {
             tile: TMM::input(selection.tile),
@@ -129,29 +206,88 @@ impl<TMM: TileMatmulFamily> MatmulSelector for SpecializedSelector<TMM> {
     }
 }
 
+impl<TMM: TileMatmulFamily> MatmulSelector for SpecializedDoubleBufferedSelector<TMM> {
+    fn select_kernel<'a, MS: MatmulSpec, R: Runtime>(
+        client: &ComputeClient<R::Server, R::Channel>,
+        input: InputRuntimeArg<'a, MS, R>,
+        output: OutputRuntimeArg<'a, MS, R>,
+        problem: MatmulProblem,
+        plane_dim: u32,
+    ) -> Result<SelectedKernel, MatmulLaunchError> {
+        if !client.properties().feature_enabled(Feature::Pipeline) {
+            return SpecializedSelector::<TMM>::select_kernel::<MS, R>(
+                client, input, output, problem, plane_dim,
+            );
+        }
+
+        let selection = matmul_selection::<TMM, MS, R>(client, &problem, plane_dim);
+        let config_input = CommonStageInput {
+            tile: TMM::input(selection.tile),
+            num_stages: selection.num_stagess,
+        };
+
+        matmul_cube_preparation::<MS, R, SpecializedDoubleBufferedAlgorithm<TMM>>(
+            client,
+            input,
+            output,
+            problem,
+            config_input,
+            selection,
+        )
+    }
+
+    fn stage_tf32_supported() -> bool {
+        TMM::requires_tensor_cores()
+    }
+}
+
+/// Whether the device has a cmma instruction multiplying `stage` elements into an `accumulator`,
+/// for at least one of the tile sizes of [Accelerated].
+///
+/// This tells up front if an accelerated kernel can be launched at all, without making its config.
+pub(crate) fn accelerated_supported(
+    properties: &DeviceProperties<Feature>,
+    stage: Elem,
+    accumulator: Elem,
+) -> bool {
+    if matches!(stage, Elem::Int(_) | Elem::UInt(_))
+        && !properties.feature_enabled(Feature::CmmaInt)
+    {
+        return false;
+    }
+
+    !Accelerated::supported_tile_sizes(properties, stage, accumulator).is_empty()
+}
+
 /// A heuristic to choose the instruction to use, based on input shape
 ///
-/// Will use 16x16 for balanced matrices, and 32x8 or 8x32 for degenerated ones.
-#[allow(clippy::type_complexity)]
+/// Prefers 16x16 for balanced matrices, and 32x8 or 8x32 for degenerated ones, then picks the
+/// first of the tile sizes that the device supports in that order of preference. Without device
+/// properties, every tile size is deemed supported. When none of them is, the preferred one is
+/// kept, and the launch will report the cmma instruction unavailable.
 fn find_instruction_shape(
     properties: Option<(&DeviceProperties<Feature>, (Elem, Elem, Elem))>,
     m: usize,
     n: usize,
-) -> (usize, usize, usize) {
-    let supported = |m: u8, n: u8, k: u8| {
-        properties
-            .map(|(p, (a, b, c))| p.feature_enabled(Feature::Cmma { a, b, c, m, n, k }))
-            .unwrap_or(true)
+) -> MatmulSize {
+    let [balanced, tall, wide, short_k] = Accelerated::TILE_SIZES;
+    let preferences = if m >= 4 * n {
+        [tall, balanced, short_k, wide]
+    } else if n >= 4 * m {
+        [wide, balanced, short_k, tall]
+    } else {
+        [balanced, short_k, tall, wide]
     };
 
-    if m >= 4 * n && supported(32, 8, 16) {
-        (32, 8, 16)
-    } else if n >= 4 * n && supported(8, 32, 16) {
-        (8, 32, 16)
-    } else if supported(16, 16, 16) {
-        (16, 16, 16)
-    } else {
-        (16, 16, 8)
+    match properties {
+        Some((properties, (input, _, accumulator))) => {
+            let supported = Accelerated::supported_tile_sizes(properties, input, accumulator);
+            preferences
+                .into_iter()
+                .find(|size| supported.contains(size))
+                .unwrap_or(preferences[0])
+        }
+        None => preferences[0],
     }
 }
 
@@ -206,12 +342,12 @@ fn find_stage_size_m_n(
     }
 }
 
-fn matmul_selection<TMM: TileMatmulFamily, MS: MatmulSpec, R: Runtime>(
+pub(crate) fn matmul_selection<TMM: TileMatmulFamily, MS: MatmulSpec, R: Runtime>(
     client: &ComputeClient<R::Server, R::Channel>,
     problem: &MatmulProblem,
     plane_dim: u32,
 ) -> MatmulSelection {
-    let (instruction_m, instruction_n, instruction_k) = find_instruction_shape(
+    let tile = find_instruction_shape(
         if TMM::requires_tensor_cores() {
             Some((
                 client.properties(),
@@ -234,16 +370,12 @@ fn matmul_selection<TMM: TileMatmulFamily, MS: MatmulSpec, R: Runtime>(
         problem.num_batches(),
         NUM_SM_APPROX,
         NUM_TENSOR_CORES_APPROX,
-        instruction_m,
-        instruction_n,
+        tile.m as usize,
+        tile.n as usize,
     );
 
     MatmulSelection {
-        tile: MatmulSize {
-            m: instruction_m as u32,
-            n: instruction_n as u32,
-            k: instruction_k as u32,
-        },
+        tile,
         num_stagess: MatmulSize {
             m: stage_size_m_n as u32,
             n: stage_size_m_n as u32,
@@ -252,3 +384,127 @@ fn matmul_selection<TMM: TileMatmulFamily, MS: MatmulSpec, R: Runtime>(
         plane_dim,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matmul::base::auto_skips_accelerated;
+    use crate::matmul::kernels::matmul::base::stage_elem;
+    use cubecl_core::ir::FloatKind;
+    use cubecl_runtime::memory_management::{HardwareProperties, MemoryDeviceProperties};
+
+    const F16: Elem = Elem::Float(FloatKind::F16);
+    const F32: Elem = Elem::Float(FloatKind::F32);
+
+    /// Properties of a device with cmma instructions of the given sizes, on f16 inputs
+    /// accumulated in f32.
+    fn properties(sizes: &[MatmulSize]) -> DeviceProperties<Feature> {
+        let features: Vec<_> = sizes
+            .iter()
+            .map(|size| Feature::Cmma {
+                a: F16,
+                b: F16,
+                c: F32,
+                m: size.m as u8,
+                k: size.k as u8,
+                n: size.n as u8,
+            })
+            .collect();
+
+        DeviceProperties::new(
+            &features,
+            MemoryDeviceProperties {
+                max_page_size: 1 << 30,
+                alignment: 32,
+            },
+            HardwareProperties {
+                plane_size_min: 32,
+                plane_size_max: 32,
+                max_bindings: 8,
+                max_shared_memory_size: 49152,
+                max_cube_count: (i32::MAX as u32, u16::MAX as u32, u16::MAX as u32),
+                max_cube_dim: (1024, 1024, 64),
+                max_units_per_cube: 1024,
+            },
+        )
+    }
+
+    #[test]
+    fn instruction_shape_matches_problem_skew() {
+        let properties = properties(&Accelerated::TILE_SIZES);
+        let shape = |m, n| find_instruction_shape(Some((&properties, (F16, F16, F32))), m, n);
+
+        assert_eq!(shape(128, 128), Accelerated::TILE_SIZES[0]);
+        assert_eq!(shape(512, 64), MatmulSize { m: 32, n: 8, k: 16 });
+        assert_eq!(shape(64, 512), MatmulSize { m: 8, n: 32, k: 16 });
+    }
+
+    #[test]
+    fn instruction_shape_falls_back_on_supported_size() {
+        let wide = MatmulSize { m: 8, n: 32, k: 16 };
+        let properties = properties(&[wide]);
+        let shape = |m, n| find_instruction_shape(Some((&properties, (F16, F16, F32))), m, n);
+
+        assert_eq!(shape(128, 128), wide);
+        assert_eq!(shape(512, 64), wide);
+    }
+
+    #[test]
+    fn f64_is_not_accelerated_on_device_with_only_f16_cmma() {
+        type S = StandardSelector<Accelerated>;
+        let properties = properties(&Accelerated::TILE_SIZES);
+
+        let f64_stage = stage_elem::<f64, S>();
+        assert_eq!(f64_stage, Elem::Float(FloatKind::TF32));
+        assert!(!accelerated_supported(&properties, f64_stage, F32));
+
+        let f16_stage = stage_elem::<half::f16, S>();
+        assert!(accelerated_supported(&properties, f16_stage, F32));
+        assert!(!accelerated_supported(&properties, f16_stage, F16));
+    }
+
+    #[test]
+    fn auto_skips_accelerated_for_f64_on_device_with_only_f16_cmma() {
+        let properties = properties(&Accelerated::TILE_SIZES);
+
+        assert!(auto_skips_accelerated::<f64, f32>(&properties, true));
+        assert!(!auto_skips_accelerated::<half::f16, f32>(&properties, true));
+        // Tiling2D can't accumulate in f16, so the accelerated kernel is still tried.
+        assert!(!auto_skips_accelerated::<f64, half::f16>(
+            &properties,
+            false
+        ));
+    }
+
+    #[test]
+    fn prefetching_depth_is_limited_by_shared_memory() {
+        let selection = MatmulSelection {
+            tile: MatmulSize {
+                m: 16,
+                n: 16,
+                k: 16,
+            },
+            num_stagess: MatmulSize { m: 8, n: 8, k: 2 },
+            plane_dim: 32,
+        };
+        let max_bytes = 49152;
+
+        // Each stage of f16 lhs and rhs takes 2 * 128 * 32 * 2 = 16384 bytes.
+        assert_eq!(
+            StandardPrefetchingAlgorithm::<Accelerated, 2>::shared_memory_size(&selection, 2),
+            32768
+        );
+        assert!(
+            StandardPrefetchingAlgorithm::<Accelerated, 3>::check_shared_memory(
+                &selection, 2, max_bytes
+            )
+            .is_ok()
+        );
+        assert!(
+            StandardPrefetchingAlgorithm::<Accelerated, 4>::check_shared_memory(
+                &selection, 2, max_bytes
+            )
+            .is_err()
+        );
+    }
+}
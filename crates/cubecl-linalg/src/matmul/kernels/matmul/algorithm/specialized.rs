@@ -48,6 +48,40 @@ where
             lhs_tiling_order: stage::TilingOrderConfig::ColMajor,
             rhs_tiling_order: stage::TilingOrderConfig::RowMajor,
             enforced_tile_layout: (None, None),
+            stage_unroll: u32::MAX,
         }
     }
 }
+
+/// Same as [SpecializedAlgorithm], with the producers copying the buffers through a 2-stage
+/// pipeline so that their loads overlap with the computation.
+pub struct SpecializedDoubleBufferedAlgorithm<TMM, Dispatch = batch::TransposedDispatch> {
+    pub _tmm: PhantomData<TMM>,
+    pub _dispatch: PhantomData<Dispatch>,
+}
+
+impl<TMM, Dispatch> base::Algorithm for SpecializedDoubleBufferedAlgorithm<TMM, Dispatch>
+where
+    TMM: tile::TileMatmulFamily,
+    Dispatch: CubeDispatch + CubeCountDispatch,
+{
+    type TileMatmul = TMM;
+    type StageMatmul = stage::single_buffer::SingleBufferMatmulFamily<Self::TileMatmul>;
+    type GlobalMatmul =
+        global::buffered::specialized::SpecializedDoubleBufferedMatmulFamily<Self::StageMatmul>;
+
+    type BatchMatmul = batch::one_to_one::OneToOneMatmulFamily<Self::GlobalMatmul, Dispatch>;
+    type Selection = MatmulSelection;
+
+    fn cube_dim(selection: &MatmulSelection) -> CubeDim {
+        <SpecializedAlgorithm<TMM, Dispatch> as base::Algorithm>::cube_dim(selection)
+    }
+
+    fn cube_count(selection: &MatmulSelection, problem: &MatmulProblem) -> CubeCount {
+        <SpecializedAlgorithm<TMM, Dispatch> as base::Algorithm>::cube_count(selection, problem)
+    }
+
+    fn advanced_config() -> crate::matmul::kernels::matmul::AdvancedConfig {
+        <SpecializedAlgorithm<TMM, Dispatch> as base::Algorithm>::advanced_config()
+    }
+}
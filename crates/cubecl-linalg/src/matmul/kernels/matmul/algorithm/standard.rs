@@ -3,26 +3,34 @@ use cubecl_core::prelude::*;
 use std::marker::PhantomData;
 
 use crate::matmul::components::batch::{CubeCountDispatch, CubeDispatch};
-use crate::matmul::components::global::full_load::CyclicLoading;
+use crate::matmul::components::global::full_load::{
+    AsyncLoadingStrategy, CyclicLoading, LoadingStrategy,
+};
 use crate::matmul::components::stage::{self};
-use crate::matmul::components::MatmulProblem;
 use crate::matmul::components::{batch, global};
-use crate::matmul::components::{tile, MatmulSelection};
+use crate::matmul::components::{tile, InvalidConfigError, MatmulSelection};
+use crate::matmul::components::{MatmulProblem, MatrixLayout};
 
-pub struct StandardAlgorithm<TMM, Dispatch = batch::TransposedDispatch> {
+/// Loads the whole stage at once, using the `Loading` strategy for both inputs.
+///
+/// With [MaskedCyclicLoading](global::full_load::MaskedCyclicLoading), the inputs don't need to
+/// be readable past their end when the problem isn't a multiple of the stage size.
+pub struct StandardAlgorithm<TMM, Dispatch = batch::TransposedDispatch, Loading = CyclicLoading> {
     pub _tmm: PhantomData<TMM>,
     pub _dispatch: PhantomData<Dispatch>,
+    pub _loading: PhantomData<Loading>,
 }
 
-impl<TMM, Dispatch> base::Algorithm for StandardAlgorithm<TMM, Dispatch>
+impl<TMM, Dispatch, Loading> base::Algorithm for StandardAlgorithm<TMM, Dispatch, Loading>
 where
     TMM: tile::TileMatmulFamily,
     Dispatch: CubeDispatch + CubeCountDispatch,
+    Loading: LoadingStrategy,
 {
     type TileMatmul = TMM;
     type StageMatmul = stage::multi_buffer::MultiBufferMatmulFamily<Self::TileMatmul>;
     type GlobalMatmul =
-        global::full_load::FullLoadMatmulFamily<Self::StageMatmul, CyclicLoading, CyclicLoading>;
+        global::full_load::FullLoadMatmulFamily<Self::StageMatmul, Loading, Loading>;
 
     type BatchMatmul = batch::one_to_one::OneToOneMatmulFamily<Self::GlobalMatmul, Dispatch>;
     type Selection = MatmulSelection;
@@ -45,6 +53,187 @@ where
             lhs_tiling_order: stage::TilingOrderConfig::ColMajor,
             rhs_tiling_order: stage::TilingOrderConfig::RowMajor,
             enforced_tile_layout: (None, None),
+            stage_unroll: u32::MAX,
         }
     }
 }
+
+/// Same as [StandardAlgorithm], with the tiles of both stages always row-major.
+///
+/// Column-major inputs are read as they are in global memory, and transposed while being written
+/// to the stage. The stage isn't vectorized then, but the inputs don't need to be transposed
+/// beforehand, and tile matmuls only ever see row-major tiles.
+pub struct StandardTransposedLoadAlgorithm<
+    TMM,
+    Dispatch = batch::TransposedDispatch,
+    Loading = CyclicLoading,
+> {
+    pub _tmm: PhantomData<TMM>,
+    pub _dispatch: PhantomData<Dispatch>,
+    pub _loading: PhantomData<Loading>,
+}
+
+impl<TMM, Dispatch, Loading> base::Algorithm
+    for StandardTransposedLoadAlgorithm<TMM, Dispatch, Loading>
+where
+    TMM: tile::TileMatmulFamily,
+    Dispatch: CubeDispatch + CubeCountDispatch,
+    Loading: LoadingStrategy,
+{
+    type TileMatmul = TMM;
+    type StageMatmul = stage::multi_buffer::MultiBufferMatmulFamily<Self::TileMatmul>;
+    type GlobalMatmul =
+        global::full_load::FullLoadMatmulFamily<Self::StageMatmul, Loading, Loading>;
+
+    type BatchMatmul = batch::one_to_one::OneToOneMatmulFamily<Self::GlobalMatmul, Dispatch>;
+    type Selection = MatmulSelection;
+
+    fn cube_dim(selection: &MatmulSelection) -> CubeDim {
+        <StandardAlgorithm<TMM, Dispatch, Loading> as base::Algorithm>::cube_dim(selection)
+    }
+
+    fn cube_count(selection: &MatmulSelection, problem: &MatmulProblem) -> CubeCount {
+        <StandardAlgorithm<TMM, Dispatch, Loading> as base::Algorithm>::cube_count(
+            selection, problem,
+        )
+    }
+
+    fn advanced_config() -> crate::matmul::kernels::matmul::AdvancedConfig {
+        crate::matmul::kernels::matmul::AdvancedConfig {
+            enforced_tile_layout: (Some(MatrixLayout::RowMajor), Some(MatrixLayout::RowMajor)),
+            ..<StandardAlgorithm<TMM, Dispatch, Loading> as base::Algorithm>::advanced_config()
+        }
+    }
+}
+
+/// Same as [StandardAlgorithm], with the loop over the k tiles of a stage unrolled `UNROLL`
+/// iterations at a time instead of fully, see
+/// [stage_unroll](crate::matmul::kernels::matmul::AdvancedConfig::stage_unroll).
+///
+/// This trades instruction-level parallelism for code size and register pressure, which
+/// favors different factors on different architectures.
+pub struct StandardUnrolledAlgorithm<
+    TMM,
+    const UNROLL: u32,
+    Dispatch = batch::TransposedDispatch,
+    Loading = CyclicLoading,
+> {
+    pub _tmm: PhantomData<TMM>,
+    pub _dispatch: PhantomData<Dispatch>,
+    pub _loading: PhantomData<Loading>,
+}
+
+impl<TMM, const UNROLL: u32, Dispatch, Loading> base::Algorithm
+    for StandardUnrolledAlgorithm<TMM, UNROLL, Dispatch, Loading>
+where
+    TMM: tile::TileMatmulFamily,
+    Dispatch: CubeDispatch + CubeCountDispatch,
+    Loading: LoadingStrategy,
+{
+    type TileMatmul = TMM;
+    type StageMatmul = stage::multi_buffer::MultiBufferMatmulFamily<Self::TileMatmul>;
+    type GlobalMatmul =
+        global::full_load::FullLoadMatmulFamily<Self::StageMatmul, Loading, Loading>;
+
+    type BatchMatmul = batch::one_to_one::OneToOneMatmulFamily<Self::GlobalMatmul, Dispatch>;
+    type Selection = MatmulSelection;
+
+    fn cube_dim(selection: &MatmulSelection) -> CubeDim {
+        <StandardAlgorithm<TMM, Dispatch, Loading> as base::Algorithm>::cube_dim(selection)
+    }
+
+    fn cube_count(selection: &MatmulSelection, problem: &MatmulProblem) -> CubeCount {
+        <StandardAlgorithm<TMM, Dispatch, Loading> as base::Algorithm>::cube_count(
+            selection, problem,
+        )
+    }
+
+    fn advanced_config() -> crate::matmul::kernels::matmul::AdvancedConfig {
+        crate::matmul::kernels::matmul::AdvancedConfig {
+            stage_unroll: UNROLL,
+            ..<StandardAlgorithm<TMM, Dispatch, Loading> as base::Algorithm>::advanced_config()
+        }
+    }
+}
+
+/// Same as [StandardAlgorithm], with the stages copied asynchronously through a pipeline of
+/// `DEPTH` stages, so the next `DEPTH - 1` stages along k are loaded while one is computed, see
+/// [PrefetchingFullLoadMatmul](global::full_load::PrefetchingFullLoadMatmul).
+///
+/// Each pipeline stage has its own shared memory, so this takes `DEPTH` times as much of it.
+pub struct StandardPrefetchingAlgorithm<
+    TMM,
+    const DEPTH: u32 = 2,
+    Dispatch = batch::TransposedDispatch,
+    Loading = CyclicLoading,
+> {
+    pub _tmm: PhantomData<TMM>,
+    pub _dispatch: PhantomData<Dispatch>,
+    pub _loading: PhantomData<Loading>,
+}
+
+impl<TMM, const DEPTH: u32, Dispatch, Loading> base::Algorithm
+    for StandardPrefetchingAlgorithm<TMM, DEPTH, Dispatch, Loading>
+where
+    TMM: tile::TileMatmulFamily,
+    Dispatch: CubeDispatch + CubeCountDispatch,
+    Loading: AsyncLoadingStrategy,
+{
+    type TileMatmul = TMM;
+    type StageMatmul = stage::multi_buffer::MultiBufferMatmulFamily<Self::TileMatmul>;
+    type GlobalMatmul = global::full_load::PrefetchingFullLoadMatmulFamily<
+        Self::StageMatmul,
+        Loading,
+        Loading,
+        DEPTH,
+    >;
+
+    type BatchMatmul = batch::one_to_one::OneToOneMatmulFamily<Self::GlobalMatmul, Dispatch>;
+    type Selection = MatmulSelection;
+
+    fn cube_dim(selection: &MatmulSelection) -> CubeDim {
+        <StandardAlgorithm<TMM, Dispatch, Loading> as base::Algorithm>::cube_dim(selection)
+    }
+
+    fn cube_count(selection: &MatmulSelection, problem: &MatmulProblem) -> CubeCount {
+        <StandardAlgorithm<TMM, Dispatch, Loading> as base::Algorithm>::cube_count(
+            selection, problem,
+        )
+    }
+
+    fn advanced_config() -> crate::matmul::kernels::matmul::AdvancedConfig {
+        <StandardAlgorithm<TMM, Dispatch, Loading> as base::Algorithm>::advanced_config()
+    }
+}
+
+impl<TMM, const DEPTH: u32, Dispatch, Loading>
+    StandardPrefetchingAlgorithm<TMM, DEPTH, Dispatch, Loading>
+{
+    /// The shared memory taken by the `DEPTH` stages of lhs and rhs, with elements of `elem_size`
+    /// bytes.
+    pub fn shared_memory_size(selection: &MatmulSelection, elem_size: usize) -> usize {
+        let stage_size = |tile: u32, num_stages: u32| (tile * num_stages) as usize;
+        let m = stage_size(selection.tile.m, selection.num_stagess.m);
+        let n = stage_size(selection.tile.n, selection.num_stagess.n);
+        let k = stage_size(selection.tile.k, selection.num_stagess.k);
+
+        DEPTH as usize * (m * k + k * n) * elem_size
+    }
+
+    /// Check that the `DEPTH` stages fit in `max_shared_memory_size` bytes of shared memory.
+    pub fn check_shared_memory(
+        selection: &MatmulSelection,
+        elem_size: usize,
+        max_shared_memory_size: usize,
+    ) -> Result<(), InvalidConfigError> {
+        let size = Self::shared_memory_size(selection, elem_size);
+
+        if size > max_shared_memory_size {
+            return Err(Box::new(format!(
+                "The {DEPTH} stages of the pipeline take {size} bytes of shared memory, but the device only has {max_shared_memory_size} bytes"
+            )));
+        }
+
+        Ok(())
+    }
+}
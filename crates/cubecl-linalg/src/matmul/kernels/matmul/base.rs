@@ -1,18 +1,25 @@
 use core::any::TypeId;
+use cubecl_core::ir::Elem;
 use cubecl_core::prelude::*;
 
 use cubecl_core::{
     client::ComputeClient, frontend::TensorHandleRef, tensor_line_size_parallel, Runtime,
 };
+use cubecl_runtime::server::Handle;
 
 use crate::matmul;
-use crate::matmul::components::global::args::TensorInputsLaunch;
+use crate::matmul::components::global::args::{
+    TensorBiasArgs, TensorInputsLaunch, TensorOutputWithBiasLaunch,
+};
+use crate::matmul::components::global::EpilogueOp;
 use crate::matmul::components::{
-    InputRuntimeArg, MatmulConfigFactory, MatmulLaunch, MatmulProblem, MatmulSpec,
-    OutputRuntimeArg, SingleMatmulSpec,
+    batch, Ident, InputRuntimeArg, MatmulConfigFactory, MatmulLaunch, MatmulProblem,
+    MatmulSelection, MatmulSpec, OutputRuntimeArg, SingleMatmulSpec,
+};
+use crate::matmul::kernels::{MatmulAvailabilityError, MatmulInvalidProblem, MatmulLaunchError};
+use crate::tensor::{
+    into_contiguous, matrix_layout, tensor_line_size_strided, MatrixLayout, TensorHandle,
 };
-use crate::matmul::kernels::{MatmulAvailabilityError, MatmulLaunchError};
-use crate::tensor::{into_contiguous, matrix_layout, MatrixLayout, TensorHandle};
 
 use super::algorithm::MatmulSelector;
 use super::config::AdvancedConfig;
@@ -36,16 +43,148 @@ pub fn launch<R: Runtime, EG: Numeric, S: MatmulSelector>(
     }
 }
 
+/// How the kernel launched for a matmul was configured by its [MatmulSelector].
+#[derive(Clone, Debug)]
+pub struct SelectedKernel {
+    /// Tile and stage sizes, along with the plane dimension.
+    pub selection: MatmulSelection,
+    pub cube_dim: CubeDim,
+    pub cube_count: CubeCount,
+}
+
 /// Launch a matrix multiplication kernel.
 ///
 /// Cmma will be used if available and enabled,
 /// otherwise it will fall back on a non-cmma implementation
+///
+/// The inputs are read through their strides, so views with padded rows or batches don't need
+/// to be made contiguous first. Only highly permuted inputs are copied.
 pub fn launch_ref<R: Runtime, EG: Numeric, S: MatmulSelector>(
     client: &ComputeClient<R::Server, R::Channel>,
     lhs: &TensorHandleRef<'_, R>,
     rhs: &TensorHandleRef<'_, R>,
     out: &TensorHandleRef<'_, R>,
 ) -> Result<(), MatmulLaunchError> {
+    launch_ref_selected::<R, EG, S>(client, lhs, rhs, out).map(|_| ())
+}
+
+/// Same as [launch_ref], returning how the selector configured the launched kernel.
+pub fn launch_ref_selected<R: Runtime, EG: Numeric, S: MatmulSelector>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+) -> Result<SelectedKernel, MatmulLaunchError> {
+    launch_ref_with_accumulator::<R, EG, f32, S>(client, lhs, rhs, out)
+}
+
+/// Same as [launch_ref_selected], but the tiles accumulate in `EA` instead of `f32`.
+///
+/// A lower precision accumulator, such as `f16`, may be faster but loses precision as k grows,
+/// while `f64` is more accurate on large problems. The inputs are still multiplied in the stage
+/// element picked for `EG`, so the tile matmul of the selector must support multiplying them into
+/// `EA`, which is checked along with the rest of the availability of the kernel.
+pub fn launch_ref_with_accumulator<R: Runtime, EG: Numeric, EA: Numeric, S: MatmulSelector>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+) -> Result<SelectedKernel, MatmulLaunchError> {
+    launch_ref_accumulating::<R, EG, EA, S>(
+        client,
+        lhs,
+        rhs,
+        out,
+        0.0,
+        None,
+        EpilogueOp::None,
+        None,
+    )
+}
+
+/// Same as [launch_ref_selected], but accumulates into the existing output, computing
+/// `out = lhs @ rhs + beta * out` in the unloader of the kernel.
+///
+/// The existing output is only read when `beta` is not zero, so `out` may be uninitialized
+/// otherwise, and the kernel is then the same as the one of [launch_ref_selected]. Beta is a
+/// runtime argument of the kernel, so all non-zero betas share the same kernel.
+pub fn launch_ref_with_beta<R: Runtime, EG: Numeric, S: MatmulSelector>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+    beta: f32,
+) -> Result<SelectedKernel, MatmulLaunchError> {
+    launch_ref_accumulating::<R, EG, f32, S>(
+        client,
+        lhs,
+        rhs,
+        out,
+        beta,
+        None,
+        EpilogueOp::None,
+        None,
+    )
+}
+
+/// Same as [launch_ref_selected], but adds the `bias` of shape `[n]` to every row of the output,
+/// computing `out = lhs @ rhs + bias` in the unloader of the kernel.
+///
+/// The bias is read with the line size of the output, and is copied into a contiguous tensor
+/// first if it isn't one. It is indexed by the column of the output, so it is added the same way
+/// to column-major outputs.
+pub fn launch_ref_with_bias<R: Runtime, EG: Numeric, S: MatmulSelector>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    bias: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+) -> Result<SelectedKernel, MatmulLaunchError> {
+    launch_ref_accumulating::<R, EG, f32, S>(
+        client,
+        lhs,
+        rhs,
+        out,
+        0.0,
+        Some(bias),
+        EpilogueOp::None,
+        None,
+    )
+}
+
+/// Same as [launch_ref_selected], but applies the `epilogue` activation to the output right
+/// before it is written, computing `out = epilogue(lhs @ rhs)` in the unloader of the kernel.
+///
+/// The activation is computed in `EG`. GELU and sigmoid are only defined on floats, so they
+/// can't be applied to integer outputs.
+pub fn launch_ref_with_epilogue<R: Runtime, EG: Numeric, S: MatmulSelector>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+    epilogue: EpilogueOp,
+) -> Result<SelectedKernel, MatmulLaunchError> {
+    launch_ref_accumulating::<R, EG, f32, S>(client, lhs, rhs, out, 0.0, None, epilogue, None)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn launch_ref_accumulating<R: Runtime, EG: Numeric, EA: Numeric, S: MatmulSelector>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+    beta: f32,
+    bias: Option<&TensorHandleRef<'_, R>>,
+    epilogue: EpilogueOp,
+    line_sizes: Option<(u8, u8, u8)>,
+) -> Result<SelectedKernel, MatmulLaunchError> {
+    // The bias is read by index, so it must be contiguous.
+    let contiguous_bias = bias
+        .filter(|bias| bias.shape.len() == 1 && *bias.strides != [1])
+        .map(|bias| into_contiguous::<R, EG>(client, bias));
+    let contiguous_bias = contiguous_bias.as_ref().map(TensorHandle::as_ref);
+    let bias = contiguous_bias.as_ref().or(bias);
+
     let check_layout = |tensor: &TensorHandleRef<'_, R>| match matrix_layout(tensor.strides) {
         MatrixLayout::Contiguous => (false, false),
         MatrixLayout::MildlyPermuted {
@@ -59,77 +198,312 @@ pub fn launch_ref<R: Runtime, EG: Numeric, S: MatmulSelector>(
     let (rhs_make_contiguous, rhs_transposed) = check_layout(rhs);
 
     match (lhs_make_contiguous, rhs_make_contiguous) {
-        (false, false) => matmul_cmma_ref_no_check::<R, EG, S>(
+        (false, false) => matmul_cmma_ref_no_check::<R, EG, EA, S>(
             client,
             lhs,
             rhs,
             out,
             (lhs_transposed, rhs_transposed),
+            beta,
+            bias,
+            epilogue,
+            line_sizes,
         ),
-        (false, true) => matmul_cmma_ref_no_check::<R, EG, S>(
+        (false, true) => matmul_cmma_ref_no_check::<R, EG, EA, S>(
             client,
             lhs,
             &into_contiguous::<R, EG>(client, rhs).as_ref(),
             out,
             (lhs_transposed, rhs_transposed),
+            beta,
+            bias,
+            epilogue,
+            line_sizes,
         ),
-        (true, false) => matmul_cmma_ref_no_check::<R, EG, S>(
+        (true, false) => matmul_cmma_ref_no_check::<R, EG, EA, S>(
             client,
             &into_contiguous::<R, EG>(client, lhs).as_ref(),
             rhs,
             out,
             (lhs_transposed, rhs_transposed),
+            beta,
+            bias,
+            epilogue,
+            line_sizes,
         ),
-        (true, true) => matmul_cmma_ref_no_check::<R, EG, S>(
+        (true, true) => matmul_cmma_ref_no_check::<R, EG, EA, S>(
             client,
             &into_contiguous::<R, EG>(client, lhs).as_ref(),
             &into_contiguous::<R, EG>(client, rhs).as_ref(),
             out,
             (lhs_transposed, rhs_transposed),
+            beta,
+            bias,
+            epilogue,
+            line_sizes,
         ),
     }
 }
 
-fn matmul_cmma_ref_no_check<R: Runtime, EG: Numeric, S: MatmulSelector>(
+/// Same as [launch_ref], but the handles are validated first.
+///
+/// The shapes must describe a valid matmul, with the output of the broadcasted batches, and
+/// every element reachable through the strides must lie within the handle. Line sizes are then
+/// checked against the shapes along with the rest of the problem, as in [launch_ref].
+///
+/// This guards against writing out of bounds of a mismatched output buffer, at the cost of a
+/// few host-side checks per call; use [launch_ref] in hot loops where the handles are known
+/// to be valid.
+pub fn launch_ref_checked<R: Runtime, EG: Numeric, S: MatmulSelector>(
     client: &ComputeClient<R::Server, R::Channel>,
     lhs: &TensorHandleRef<'_, R>,
     rhs: &TensorHandleRef<'_, R>,
     out: &TensorHandleRef<'_, R>,
-    transposed: (bool, bool),
 ) -> Result<(), MatmulLaunchError> {
-    let rank = lhs.strides.len();
+    check_handles::<R>(lhs, rhs, out)?;
+    launch_ref::<R, EG, S>(client, lhs, rhs, out)
+}
+
+/// A tensor described by raw pointers to its parts, for callers that don't hold [TensorHandle]s,
+/// such as bindings to other languages.
+///
+/// The runtimes can't adopt memory they didn't allocate, so the buffer stays a [Handle] of the
+/// client, which bindings keep alive and pass around as an opaque pointer.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct RawTensor {
+    /// The buffer holding the elements of the tensor on the device.
+    pub handle: *const Handle,
+    /// Size of each dimension, `rank` values.
+    pub shape: *const usize,
+    /// Strides of each dimension in elements, `rank` values.
+    pub strides: *const usize,
+    pub rank: usize,
+    /// Number of elements read or written at once along the last dimension, or along the
+    /// second-to-last one for a transposed tensor. Highly permuted inputs are copied into
+    /// contiguous tensors first, which are then read with this line size.
+    pub line_size: u8,
+}
+
+/// Same as [launch_ref_selected], but the tensors are given by raw pointers to their parts, and
+/// are read and written with the given line sizes instead of the ones picked from their shapes and
+/// strides.
+///
+/// The tensors are validated as in [launch_ref_checked]. The line sizes must be supported by the
+/// runtime for `EG`, divide the dimension they are aligned with, and divide every stride but the
+/// contiguous one. Invalid shapes, strides or line sizes are reported as errors instead of being
+/// launched.
+///
+/// # Safety
+///
+/// - The handle pointers must point to live handles allocated with the client, holding elements
+///   of type `EG` and aligned on the line sizes.
+/// - The shape and stride pointers must each point to `rank` initialized values, and stay valid
+///   for the duration of the call.
+/// - The output must not overlap the inputs, and its strides must not map two of its elements
+///   to the same address.
+pub unsafe fn launch_from_raw_parts<R: Runtime, EG: Numeric, S: MatmulSelector>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: RawTensor,
+    rhs: RawTensor,
+    out: RawTensor,
+) -> Result<SelectedKernel, MatmulLaunchError> {
+    let elem_size = EG::size().expect("Should be a native type");
+    let [lhs_ref, rhs_ref, out_ref] = [lhs, rhs, out].map(|tensor| unsafe {
+        TensorHandleRef::<R>::from_raw_parts(
+            &*tensor.handle,
+            core::slice::from_raw_parts(tensor.strides, tensor.rank),
+            core::slice::from_raw_parts(tensor.shape, tensor.rank),
+            elem_size,
+        )
+    });
+
+    check_handles::<R>(&lhs_ref, &rhs_ref, &out_ref)?;
+
+    let eg_elem = EG::as_elem_native_unchecked();
+    let tensors = [
+        (Ident::Lhs, &lhs_ref, lhs.line_size),
+        (Ident::Rhs, &rhs_ref, rhs.line_size),
+        (Ident::Out, &out_ref, out.line_size),
+    ];
+    for (ident, tensor, line_size) in tensors {
+        if !R::line_size_elem(&eg_elem).any(|supported| supported == line_size) {
+            return Err(MatmulInvalidProblem::UnsupportedLineSize { ident, line_size }.into());
+        }
+        check_line_alignment(tensor.strides, line_size)?;
+    }
+
+    launch_ref_accumulating::<R, EG, f32, S>(
+        client,
+        &lhs_ref,
+        &rhs_ref,
+        &out_ref,
+        0.0,
+        None,
+        EpilogueOp::None,
+        Some((lhs.line_size, rhs.line_size, out.line_size)),
+    )
+}
+
+/// Check that a matrix read with lines of `line_size` elements has a contiguous dimension among
+/// its last two, and that its other strides are multiples of the line size, so every line starts
+/// on an aligned address.
+fn check_line_alignment(strides: &[usize], line_size: u8) -> Result<(), MatmulInvalidProblem> {
+    if line_size == 1 {
+        return Ok(());
+    }
+
+    let rank = strides.len();
+    let contiguous = (rank - 2..rank).rev().find(|&dim| strides[dim] == 1);
+    let aligned = contiguous.is_some_and(|contiguous| {
+        strides
+            .iter()
+            .enumerate()
+            .all(|(dim, stride)| dim == contiguous || stride % line_size as usize == 0)
+    });
+
+    match aligned {
+        true => Ok(()),
+        false => Err(MatmulInvalidProblem::UnalignedStrides {
+            strides: strides.to_vec(),
+            line_size,
+        }),
+    }
+}
+
+fn check_handles<R: Runtime>(
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+) -> Result<(), MatmulInvalidProblem> {
+    let tensors = [(Ident::Lhs, lhs), (Ident::Rhs, rhs), (Ident::Out, out)];
+
+    for (ident, tensor) in tensors {
+        if tensor.shape.len() < 2 || tensor.shape.len() != tensor.strides.len() {
+            return Err(MatmulInvalidProblem::InvalidRank {
+                ident,
+                rank: tensor.shape.len() as u32,
+            });
+        }
+    }
+
+    let (lhs_rank, rhs_rank) = (lhs.shape.len(), rhs.shape.len());
+    if lhs.shape[lhs_rank - 1] != rhs.shape[rhs_rank - 2] {
+        return Err(MatmulInvalidProblem::MismatchedK {
+            lhs: lhs.shape[lhs_rank - 1] as u32,
+            rhs: rhs.shape[rhs_rank - 2] as u32,
+        });
+    }
+
+    // Batches are aligned on the last one, and missing leading batches count as one.
+    let (lhs_batches, rhs_batches) = (&lhs.shape[..lhs_rank - 2], &rhs.shape[..rhs_rank - 2]);
+    let num_batches = usize::max(lhs_batches.len(), rhs_batches.len());
+    let batch = |batches: &[usize], i: usize| {
+        (i + batches.len())
+            .checked_sub(num_batches)
+            .map(|i| batches[i])
+            .unwrap_or(1)
+    };
+
+    let mut dims = Vec::with_capacity(num_batches + 2);
+    for i in 0..num_batches {
+        let (lhs_batch, rhs_batch) = (batch(lhs_batches, i), batch(rhs_batches, i));
+        let batch = match (lhs_batch, rhs_batch) {
+            _ if lhs_batch == rhs_batch || rhs_batch == 1 => lhs_batch,
+            (1, _) => rhs_batch,
+            _ => {
+                return Err(MatmulInvalidProblem::IncompatibleBatches {
+                    lhs: lhs_batches.to_vec(),
+                    rhs: rhs_batches.to_vec(),
+                })
+            }
+        };
+        dims.push(batch);
+    }
+    dims.push(lhs.shape[lhs_rank - 2]);
+    dims.push(rhs.shape[rhs_rank - 1]);
+
+    if out.shape != dims {
+        return Err(MatmulInvalidProblem::InvalidOutShape {
+            out: out.shape.to_vec(),
+            dims,
+        });
+    }
+
+    for (ident, tensor) in tensors {
+        // Nothing is read nor written in an empty tensor.
+        if tensor.shape.contains(&0) {
+            continue;
+        }
+
+        let last_offset: usize = tensor
+            .shape
+            .iter()
+            .zip(tensor.strides)
+            .map(|(shape, stride)| (shape - 1) * stride)
+            .sum();
+        let bytes = ((last_offset + 1) * tensor.elem_size) as u64;
+        let size = tensor.handle.size();
+
+        if bytes > size {
+            return Err(MatmulInvalidProblem::OutOfBounds { ident, bytes, size });
+        }
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn matmul_cmma_ref_no_check<R: Runtime, EG: Numeric, EA: Numeric, S: MatmulSelector>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+    transposed: (bool, bool),
+    beta: f32,
+    bias: Option<&TensorHandleRef<'_, R>>,
+    epilogue: EpilogueOp,
+    line_sizes: Option<(u8, u8, u8)>,
+) -> Result<SelectedKernel, MatmulLaunchError> {
+    // The inputs may have fewer batch dimensions than each other, see [MatmulProblem::validate].
+    let (lhs_rank, rhs_rank, out_rank) = (lhs.shape.len(), rhs.shape.len(), out.shape.len());
     let eg_elem = EG::as_elem_native().expect("To be a native type");
 
-    let m = lhs.shape[rank - 2] as u32;
-    let k = lhs.shape[rank - 1] as u32;
-    let n = rhs.shape[rank - 1] as u32;
+    let m = lhs.shape[lhs_rank - 2] as u32;
+    let k = lhs.shape[lhs_rank - 1] as u32;
+    let n = rhs.shape[rhs_rank - 1] as u32;
 
-    let lhs_line_size = tensor_line_size_parallel(
-        R::line_size_elem(&eg_elem),
-        lhs.shape,
-        lhs.strides,
-        rank - 1,
-    );
-    let rhs_line_size = tensor_line_size_parallel(
-        R::line_size_elem(&eg_elem),
-        rhs.shape,
-        rhs.strides,
-        rank - 1,
-    );
-    let out_line_size = tensor_line_size_parallel(
-        R::line_size_elem(&eg_elem),
-        out.shape,
-        out.strides,
-        rank - 1,
-    );
+    // The loaders read through the strides of the inputs, which may be padded.
+    let (lhs_line_size, rhs_line_size, out_line_size) = line_sizes.unwrap_or_else(|| {
+        (
+            tensor_line_size_strided(
+                R::line_size_elem(&eg_elem),
+                lhs.shape,
+                lhs.strides,
+                lhs_rank - 1,
+            ),
+            tensor_line_size_strided(
+                R::line_size_elem(&eg_elem),
+                rhs.shape,
+                rhs.strides,
+                rhs_rank - 1,
+            ),
+            tensor_line_size_parallel(
+                R::line_size_elem(&eg_elem),
+                out.shape,
+                out.strides,
+                out_rank - 1,
+            ),
+        )
+    });
 
     let problem = MatmulProblem {
         m: m as usize,
         n: n as usize,
         k: k as usize,
         batches: (
-            lhs.shape[..lhs.shape.len() - 2].to_vec(),
-            rhs.shape[..rhs.shape.len() - 2].to_vec(),
+            lhs.shape[..lhs_rank - 2].to_vec(),
+            rhs.shape[..rhs_rank - 2].to_vec(),
         ),
         lhs_layout: match transposed.0 {
             true => matmul::components::MatrixLayout::ColMajor,
@@ -139,34 +513,38 @@ fn matmul_cmma_ref_no_check<R: Runtime, EG: Numeric, S: MatmulSelector>(
             true => matmul::components::MatrixLayout::ColMajor,
             false => matmul::components::MatrixLayout::RowMajor,
         },
+        out_layout: match matrix_layout(out.strides) {
+            MatrixLayout::MildlyPermuted {
+                transposed: true, ..
+            } => matmul::components::MatrixLayout::ColMajor,
+            _ => matmul::components::MatrixLayout::RowMajor,
+        },
         lhs_line_size,
         rhs_line_size,
         out_line_size,
+        beta,
+        has_bias: bias.is_some(),
+        epilogue,
     };
-
-    let plane_size = client
-        .properties()
-        .hardware_properties()
-        .defined_plane_size();
-
-    let plane_dim = match plane_size {
-        Some(32) | Some(64) => plane_size.expect("32 or 64"),
-        Some(plane_dim) => {
-            return Err(MatmulLaunchError::Unavailable(
-                MatmulAvailabilityError::PlaneDimUnsupported { plane_dim },
-            ))
-        }
-        None => {
-            return Err(MatmulLaunchError::Unavailable(
-                MatmulAvailabilityError::PlaneDimUnknown,
-            ))
+    problem.validate()?;
+    if epilogue.is_float_only() && !matches!(eg_elem, Elem::Float(_)) {
+        return Err(MatmulInvalidProblem::FloatEpilogue {
+            epilogue,
+            elem: eg_elem,
         }
-    };
+        .into());
+    }
+    if let Some(bias) = bias {
+        problem.check_bias(bias.shape)?;
+    }
+
+    let plane_dim = device_plane_dim::<R>(client)?;
 
-    matmul_launch_kernel::<R, EG, S>(
+    matmul_launch_kernel::<R, EG, EA, S>(
         client,
         lhs,
         rhs,
+        bias,
         out,
         (lhs_line_size, rhs_line_size, out_line_size),
         problem,
@@ -174,74 +552,222 @@ fn matmul_cmma_ref_no_check<R: Runtime, EG: Numeric, S: MatmulSelector>(
     )
 }
 
-fn matmul_launch_kernel<R: Runtime, EG: Numeric, S: MatmulSelector>(
+/// The precision of the stages when `EG` matmuls are launched with a selector, which is what the
+/// tile matmul multiplies.
+///
+/// Half precision inputs stay in half precision, other inputs are cast to `tf32` when the selector
+/// supports it.
+enum StagePrecision {
+    F16,
+    BF16,
+    TF32,
+    Global,
+}
+
+impl StagePrecision {
+    fn of<EG: Numeric, S: MatmulSelector>() -> Self {
+        if TypeId::of::<EG>() == TypeId::of::<half::f16>()
+            || TypeId::of::<EG>() == TypeId::of::<flex32>()
+        {
+            Self::F16
+        } else if TypeId::of::<EG>() == TypeId::of::<half::bf16>() {
+            Self::BF16
+        } else if S::stage_tf32_supported() {
+            Self::TF32
+        } else {
+            Self::Global
+        }
+    }
+}
+
+/// The element type of the stages when `EG` matmuls are launched with `S`, see [StagePrecision].
+pub(crate) fn stage_elem<EG: Numeric, S: MatmulSelector>() -> Elem {
+    match StagePrecision::of::<EG, S>() {
+        StagePrecision::F16 => half::f16::as_elem_native_unchecked(),
+        StagePrecision::BF16 => half::bf16::as_elem_native_unchecked(),
+        StagePrecision::TF32 => tf32::as_elem_native_unchecked(),
+        StagePrecision::Global => EG::as_elem_native_unchecked(),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn matmul_launch_kernel<R: Runtime, EG: Numeric, EA: Numeric, S: MatmulSelector>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    bias: Option<&TensorHandleRef<'_, R>>,
+    out: &TensorHandleRef<'_, R>,
+    line_sizes: (u8, u8, u8),
+    problem: MatmulProblem,
+    plane_dim: u32,
+) -> Result<SelectedKernel, MatmulLaunchError> {
+    match StagePrecision::of::<EG, S>() {
+        StagePrecision::F16 => matmul_launch_kernel_staged::<R, EG, half::f16, EA, S>(
+            client, lhs, rhs, bias, out, line_sizes, problem, plane_dim,
+        ),
+        StagePrecision::BF16 => matmul_launch_kernel_staged::<R, EG, half::bf16, EA, S>(
+            client, lhs, rhs, bias, out, line_sizes, problem, plane_dim,
+        ),
+        StagePrecision::TF32 => matmul_launch_kernel_staged::<R, EG, tf32, EA, S>(
+            client, lhs, rhs, bias, out, line_sizes, problem, plane_dim,
+        ),
+        StagePrecision::Global => matmul_launch_kernel_staged::<R, EG, EG, EA, S>(
+            client, lhs, rhs, bias, out, line_sizes, problem, plane_dim,
+        ),
+    }
+}
+
+/// Launch the matmul with stages of `ES`, reading the bias along with the output when there is
+/// one.
+#[allow(clippy::too_many_arguments)]
+fn matmul_launch_kernel_staged<
+    R: Runtime,
+    EG: Numeric,
+    ES: Numeric,
+    EA: Numeric,
+    S: MatmulSelector,
+>(
     client: &ComputeClient<R::Server, R::Channel>,
     lhs: &TensorHandleRef<'_, R>,
     rhs: &TensorHandleRef<'_, R>,
+    bias: Option<&TensorHandleRef<'_, R>>,
     out: &TensorHandleRef<'_, R>,
     (lhs_line_size, rhs_line_size, out_line_size): (u8, u8, u8),
     problem: MatmulProblem,
     plane_dim: u32,
-) -> Result<(), MatmulLaunchError> {
-    if TypeId::of::<EG>() == TypeId::of::<half::f16>()
-        || TypeId::of::<EG>() == TypeId::of::<flex32>()
-    {
-        S::select_kernel::<SingleMatmulSpec<EG, half::f16, f32>, R>(
-            client,
-            TensorInputsLaunch::new(
-                lhs.as_tensor_arg(lhs_line_size),
-                rhs.as_tensor_arg(rhs_line_size),
-            ),
-            out.as_tensor_arg(out_line_size),
-            problem,
-            plane_dim,
-        )
-    } else if TypeId::of::<EG>() == TypeId::of::<half::bf16>() {
-        S::select_kernel::<SingleMatmulSpec<EG, half::bf16, f32>, R>(
-            client,
-            TensorInputsLaunch::new(
-                lhs.as_tensor_arg(lhs_line_size),
-                rhs.as_tensor_arg(rhs_line_size),
-            ),
-            out.as_tensor_arg(out_line_size),
-            problem,
-            plane_dim,
-        )
-    } else if S::stage_tf32_supported() {
-        S::select_kernel::<SingleMatmulSpec<EG, tf32, f32>, R>(
+) -> Result<SelectedKernel, MatmulLaunchError> {
+    let inputs = TensorInputsLaunch::new(
+        lhs.as_tensor_arg(lhs_line_size),
+        rhs.as_tensor_arg(rhs_line_size),
+    );
+
+    match bias {
+        None => S::select_kernel::<SingleMatmulSpec<EG, ES, EA>, R>(
             client,
-            TensorInputsLaunch::new(
-                lhs.as_tensor_arg(lhs_line_size),
-                rhs.as_tensor_arg(rhs_line_size),
-            ),
+            inputs,
             out.as_tensor_arg(out_line_size),
             problem,
             plane_dim,
-        )
-    } else {
-        S::select_kernel::<SingleMatmulSpec<EG, EG, f32>, R>(
+        ),
+        Some(bias) => S::select_kernel::<SingleMatmulSpec<EG, ES, EA, TensorBiasArgs>, R>(
             client,
-            TensorInputsLaunch::new(
-                lhs.as_tensor_arg(lhs_line_size),
-                rhs.as_tensor_arg(rhs_line_size),
+            inputs,
+            TensorOutputWithBiasLaunch::new(
+                out.as_tensor_arg(out_line_size),
+                bias.as_tensor_arg(out_line_size),
             ),
-            out.as_tensor_arg(out_line_size),
             problem,
             plane_dim,
-        )
+        ),
+    }
+}
+
+/// Query the plane dimension of the device, which selectors should use by default.
+///
+/// Fails if the device doesn't have a fixed plane size, or if it is neither 32 nor 64.
+pub fn device_plane_dim<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+) -> Result<u32, MatmulAvailabilityError> {
+    match client
+        .properties()
+        .hardware_properties()
+        .defined_plane_size()
+    {
+        Some(plane_dim @ (32 | 64)) => Ok(plane_dim),
+        Some(plane_dim) => Err(MatmulAvailabilityError::PlaneDimUnsupported { plane_dim }),
+        None => Err(MatmulAvailabilityError::PlaneDimUnknown),
+    }
+}
+
+/// Check that a selected plane dimension matches the plane size of the device.
+///
+/// Kernels assume every plane has exactly `plane_dim` units, so a mismatch would not fail
+/// to launch but would silently compute wrong results.
+/// Devices without a fixed plane size can't be checked and are accepted.
+pub fn check_plane_dim<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    plane_dim: u32,
+) -> Result<(), MatmulAvailabilityError> {
+    match client
+        .properties()
+        .hardware_properties()
+        .defined_plane_size()
+    {
+        Some(device) if device != plane_dim => Err(MatmulAvailabilityError::PlaneDimMismatch {
+            selected: plane_dim,
+            device,
+        }),
+        _ => Ok(()),
     }
 }
 
-pub(crate) fn matmul_cube_preparation<'a, MS: MatmulSpec, R: Runtime, D: Algorithm>(
+/// Check that a cube count can be launched on the device, i.e. that it doesn't exceed the
+/// maximum cube count of its [hardware properties](cubecl_runtime::memory_management::HardwareProperties)
+/// on any axis.
+///
+/// Launching a larger grid doesn't fail loudly on every backend, and can leave the output
+/// unwritten. Dynamic cube counts are only known on the device and are accepted.
+pub fn check_cube_count<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    cube_count: &CubeCount,
+) -> Result<(), MatmulLaunchError> {
+    let max = client.properties().hardware_properties().max_cube_count;
+
+    match cube_count {
+        CubeCount::Static(x, y, z) if *x > max.0 || *y > max.1 || *z > max.2 => {
+            Err(MatmulLaunchError::ExceedsDeviceLimits {
+                cube_count: (*x, *y, *z),
+                max,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Check that a cube dim can be launched on the device, i.e. that it doesn't exceed the maximum
+/// cube dim on any axis nor the maximum number of units per cube.
+pub fn check_cube_dim<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    cube_dim: &CubeDim,
+) -> Result<(), MatmulLaunchError> {
+    let properties = client.properties().hardware_properties();
+    let max = properties.max_cube_dim;
+    let max_units = properties.max_units_per_cube;
+
+    if cube_dim.x > max.0
+        || cube_dim.y > max.1
+        || cube_dim.z > max.2
+        || cube_dim.num_elems() > max_units
+    {
+        return Err(MatmulLaunchError::CubeDimExceedsDeviceLimits {
+            cube_dim: (cube_dim.x, cube_dim.y, cube_dim.z),
+            max,
+            max_units,
+        });
+    }
+
+    Ok(())
+}
+
+pub(crate) fn matmul_cube_preparation<
+    'a,
+    MS: MatmulSpec,
+    R: Runtime,
+    D: Algorithm<Selection = MatmulSelection>,
+>(
     client: &ComputeClient<R::Server, R::Channel>,
     input: InputRuntimeArg<'a, MS, R>,
     output: OutputRuntimeArg<'a, MS, R>,
     problem: MatmulProblem,
     config_input: <D::BatchMatmul as MatmulConfigFactory>::Input,
     selection: D::Selection,
-) -> Result<(), MatmulLaunchError> {
+) -> Result<SelectedKernel, MatmulLaunchError> {
     let cube_dim = D::cube_dim(&selection);
+    // Every algorithm lays its planes along the y axis, so x is the plane dimension.
+    check_plane_dim::<R>(client, cube_dim.x)?;
+    check_cube_dim::<R>(client, &cube_dim)?;
     let cube_count = D::cube_count(&selection, &problem);
+    check_cube_count::<R>(client, &cube_count)?;
     let advanced_config = D::advanced_config();
 
     launch_matmul::<MS, R, D>(
@@ -250,10 +776,16 @@ pub(crate) fn matmul_cube_preparation<'a, MS: MatmulSpec, R: Runtime, D: Algorit
         output,
         problem,
         cube_dim,
-        cube_count,
+        cube_count.clone(),
         advanced_config,
         config_input,
-    )
+    )?;
+
+    Ok(SelectedKernel {
+        selection,
+        cube_dim,
+        cube_count,
+    })
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -274,13 +806,58 @@ fn launch_matmul<'a, MS: MatmulSpec, R: Runtime, D: Algorithm>(
         &cube_count,
         &advanced_config,
     )?;
-    D::check_availability::<R, (MS::EG, MS::ES, MS::EA)>(client, &config)?;
+    D::check_availability::<R, (MS::EL, MS::ER, MS::EG, MS::ES, MS::EA)>(client, &config)?;
 
     unsafe {
         D::BatchMatmul::launch_unchecked::<MS, R>(
-            client, cube_dim, cube_count, input, output, config,
+            client,
+            cube_dim,
+            cube_count,
+            input,
+            output,
+            problem.beta,
+            config,
         );
     };
 
     Ok(())
 }
+
+/// Expand and compile the kernel that the algorithm would launch for the problem with the given
+/// selection, and return its source without launching it.
+///
+/// The config is made and checked like for a launch, so the same errors are returned. The inputs
+/// and output are only used for their line sizes, and are never read nor written. This is meant
+/// for debugging the generated code, e.g. the CUDA C++ on the CUDA runtime.
+pub fn compile_only<'a, MS: MatmulSpec, R: Runtime, D: Algorithm<Selection = MatmulSelection>>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: InputRuntimeArg<'a, MS, R>,
+    output: OutputRuntimeArg<'a, MS, R>,
+    problem: MatmulProblem,
+    config_input: <D::BatchMatmul as MatmulConfigFactory>::Input,
+    selection: D::Selection,
+) -> Result<String, MatmulLaunchError> {
+    let cube_dim = D::cube_dim(&selection);
+    check_plane_dim::<R>(client, cube_dim.x)?;
+    check_cube_dim::<R>(client, &cube_dim)?;
+    let cube_count = D::cube_count(&selection, &problem);
+    check_cube_count::<R>(client, &cube_count)?;
+
+    let config = D::make_config(
+        config_input,
+        &problem,
+        &cube_dim,
+        &cube_count,
+        &D::advanced_config(),
+    )?;
+    D::check_availability::<R, (MS::EL, MS::ER, MS::EG, MS::ES, MS::EA)>(client, &config)?;
+
+    Ok(batch::compile::<MS, R, D::BatchMatmul>(
+        cube_dim,
+        cube_count,
+        input,
+        output,
+        problem.beta,
+        config,
+    ))
+}
@@ -20,6 +20,14 @@ pub struct AdvancedConfig {
     /// transpose will be done at loading from global memory to stage,
     /// and stage will not be vectorized.
     pub enforced_tile_layout: (Option<MatrixLayout>, Option<MatrixLayout>),
+    /// Number of iterations of the loop over the k tiles of a stage that are unrolled together
+    ///
+    /// # Notes
+    ///
+    /// The loop is fully unrolled when it is at least the number of k tiles, which is the case
+    /// by default. Otherwise it is a runtime loop, hinted to be unrolled by this factor, which
+    /// must be at least 1.
+    pub stage_unroll: u32,
 }
 
 impl Default for AdvancedConfig {
@@ -28,6 +36,7 @@ impl Default for AdvancedConfig {
             lhs_tiling_order: stage::TilingOrderConfig::RowMajor,
             rhs_tiling_order: stage::TilingOrderConfig::RowMajor,
             enforced_tile_layout: (None, None),
+            stage_unroll: u32::MAX,
         }
     }
 }
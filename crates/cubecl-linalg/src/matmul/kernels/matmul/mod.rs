@@ -4,5 +4,10 @@ mod config;
 mod algorithm;
 
 pub use algorithm::*;
-pub use base::{launch, launch_ref};
+pub(crate) use base::stage_elem;
+pub use base::{
+    check_plane_dim, compile_only, device_plane_dim, launch, launch_from_raw_parts, launch_ref,
+    launch_ref_checked, launch_ref_selected, launch_ref_with_accumulator, launch_ref_with_beta,
+    launch_ref_with_bias, launch_ref_with_epilogue, RawTensor, SelectedKernel,
+};
 pub use config::{create_stage_dim, AdvancedConfig};
@@ -1,10 +1,16 @@
 /// Matmul using Accelerator or PlaneMma
 pub mod matmul;
+/// Sum of partial results along a leading axis.
+pub mod reduce;
 /// Simple non-cooperative matmul that can be very fast on small matrices.
 pub mod simple;
+/// Matmul splitting the k dimension into partial matmuls that are summed afterwards.
+pub mod split_k;
 /// Non-cooperative Matmul
 pub mod tiling2d;
 
+mod context;
 mod error;
 
+pub use context::*;
 pub use error::*;
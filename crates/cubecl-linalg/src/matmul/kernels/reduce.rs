@@ -0,0 +1,126 @@
+//! Sum reduction of partial results
+//!
+//! The partials have one more dimension than the output, in front of all the others.
+//! Each unit sums the partials of a single output line, and both tensors may have any strides, as
+//! long as they stay aligned with the line size picked for the launch.
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise, tensor_line_size_parallel};
+
+use super::{MatmulInvalidProblem, MatmulLaunchError};
+
+#[cube(launch_unchecked)]
+fn reduce_sum_kernel<N: Numeric>(partials: &Tensor<Line<N>>, out: &mut Tensor<Line<N>>) {
+    let rank = out.rank();
+    let mut remainder = ABSOLUTE_POS * out.line_size();
+    let mut out_offset = 0;
+    let mut offset = 0;
+
+    // The position walks the output in row-major order, whatever its strides are in memory.
+    // Axis 0 of the partials is the one being reduced, the others follow the output.
+    for i in 0..rank {
+        let axis = rank - 1 - i;
+        let coordinate = remainder % out.shape(axis);
+        remainder /= out.shape(axis);
+
+        out_offset += coordinate * out.stride(axis);
+        offset += coordinate * partials.stride(axis + 1);
+    }
+
+    if remainder > 0 {
+        terminate!();
+    }
+
+    let first = offset / partials.line_size();
+    let partial_stride = partials.stride(0) / partials.line_size();
+
+    let mut sum = partials[first];
+
+    for partial in 1..partials.shape(0) {
+        sum += partials[first + partial * partial_stride];
+    }
+
+    out[out_offset / out.line_size()] = sum;
+}
+
+/// Sum `partials` of shape `[num_partials, ..out.shape]` along its first axis into `out`.
+///
+/// The partials may have any strides, which allows reducing an axis that isn't the first in
+/// memory through a permuted view. The output may have any strides as well.
+///
+/// When both tensors are read with lines, their innermost dimension must be contiguous and every
+/// other stride must be a multiple of the line size, otherwise
+/// [UnalignedStrides](MatmulInvalidProblem::UnalignedStrides) is returned.
+pub fn launch_ref<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    partials: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+) -> Result<(), MatmulLaunchError> {
+    let rank = out.shape.len();
+
+    if rank == 0
+        || partials.shape.len() != rank + 1
+        || partials.shape[0] == 0
+        || partials.shape[1..] != *out.shape
+        || partials.strides.len() != rank + 1
+        || out.strides.len() != rank
+    {
+        return Err(MatmulInvalidProblem::InvalidPartials {
+            shape: partials.shape.to_vec(),
+            out: out.shape.to_vec(),
+        }
+        .into());
+    }
+
+    let num_elems: usize = out.shape.iter().product();
+
+    let line_size = tensor_line_size_parallel(
+        R::supported_line_sizes().iter().cloned(),
+        out.shape,
+        out.strides,
+        rank - 1,
+    )
+    .min(tensor_line_size_parallel(
+        R::supported_line_sizes().iter().cloned(),
+        partials.shape,
+        partials.strides,
+        rank,
+    ));
+
+    check_line_alignment(partials.strides, line_size)?;
+    check_line_alignment(out.strides, line_size)?;
+
+    let cube_dim = CubeDim::default();
+    let cube_count = calculate_cube_count_elemwise(num_elems / line_size as usize, cube_dim);
+
+    unsafe {
+        reduce_sum_kernel::launch_unchecked::<E, R>(
+            client,
+            cube_count,
+            cube_dim,
+            partials.as_tensor_arg(line_size),
+            out.as_tensor_arg(line_size),
+        );
+    }
+
+    Ok(())
+}
+
+/// The kernel indexes lines by dividing element offsets by the line size, which is only exact when
+/// the innermost dimension is contiguous and all the other strides are multiples of the line size.
+fn check_line_alignment(strides: &[usize], line_size: u8) -> Result<(), MatmulInvalidProblem> {
+    if line_size == 1 {
+        return Ok(());
+    }
+
+    let (inner, outer) = strides.split_last().expect("Tensors have at least one dim");
+    let line_size_usize = line_size as usize;
+
+    if *inner != 1 || outer.iter().any(|stride| stride % line_size_usize != 0) {
+        return Err(MatmulInvalidProblem::UnalignedStrides {
+            strides: strides.to_vec(),
+            line_size,
+        });
+    }
+
+    Ok(())
+}
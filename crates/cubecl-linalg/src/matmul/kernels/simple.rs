@@ -1,24 +1,27 @@
 //! Naive matmul kernel implementation
 //!
-//! Each local unit will compute a single element of the output matrix.
+//! Each local unit will compute a single element of the output matrix, except when the output
+//! is a single row or column. A whole cube then computes each element, see [gemv_kernel].
 use cubecl::prelude::*;
 use cubecl_core as cubecl;
 
 use crate::tensor::{into_contiguous, matrix_layout, MatrixLayout, TensorHandle};
 
-use super::MatmulLaunchError;
+use super::{MatmulInvalidProblem, MatmulLaunchError};
 
 #[cube(launch_unchecked)]
 fn matmul_kernel<N: Numeric>(
     lhs: &Tensor<Line<N>>,
     rhs: &Tensor<Line<N>>,
     out: &mut Tensor<N>,
+    alpha: f32,
+    beta: f32,
     // number of dimensions not involved in the matmul
     #[comptime] num_batches: Option<u32>,
+    // whether the current output is read back, only needed when beta is not zero
+    #[comptime] accumulate: bool,
 ) {
     let rank = out.rank();
-    let end = num_batches.unwrap_or_else(|| rank - 2);
-    let unroll = num_batches.is_some();
 
     let n_rows = lhs.shape(rank - 2);
     let n_cols = rhs.shape(rank - 1);
@@ -34,20 +37,9 @@ fn matmul_kernel<N: Numeric>(
 
     let line_size = lhs.line_size();
 
-    let mut offset_lhs = 0;
-    let mut offset_rhs = 0;
     let offset_out = n_rows * n_cols * batch_pos;
-
-    #[unroll(unroll)]
-    for i in 0..end {
-        let ogwl = offset_out / out.stride(i);
-
-        offset_lhs += ogwl % lhs.shape(i) * lhs.stride(i);
-        offset_rhs += ogwl % rhs.shape(i) * rhs.stride(i);
-    }
-
-    offset_lhs /= line_size.runtime();
-    offset_rhs /= line_size.runtime();
+    let offset_lhs = batch_offset(lhs, out, offset_out, num_batches);
+    let offset_rhs = batch_offset(rhs, out, offset_out, num_batches);
 
     let mut sum = Line::empty(line_size).fill(N::from_int(0));
 
@@ -64,34 +56,148 @@ fn matmul_kernel<N: Numeric>(
     out_index += offset_out;
 
     let unroll_sum = line_size != 1;
+    let mut accum = N::from_int(0);
     if unroll_sum {
-        let mut accum = N::from_int(0);
         // we unroll the loop to sum `vectorization_factor` elements at once, which lets us
         // use SIMD instructions to speed up the computation
         #[unroll]
         for v in 0..line_size {
             accum += sum[v];
         }
+    } else {
+        accum = sum[0];
+    }
+
+    accum *= N::cast_from(alpha);
+
+    if accumulate {
+        accum += N::cast_from(beta) * out[out_index];
+    }
+
+    out[out_index] = accum;
+}
+
+/// Same as [matmul_kernel], but each cube computes a single element of the output.
+///
+/// The units of the cube stride over k, then sum their partial dot products in shared memory,
+/// so that a single row or column of output still keeps every unit busy, where [matmul_kernel]
+/// would leave most of them idle. The cube must be made of `cube_size` units along x, a power of
+/// two.
+#[cube(launch_unchecked)]
+fn gemv_kernel<N: Numeric>(
+    lhs: &Tensor<Line<N>>,
+    rhs: &Tensor<Line<N>>,
+    out: &mut Tensor<N>,
+    alpha: f32,
+    beta: f32,
+    #[comptime] num_batches: Option<u32>,
+    #[comptime] accumulate: bool,
+    #[comptime] cube_size: u32,
+) {
+    let rank = out.rank();
+
+    let n_rows = lhs.shape(rank - 2);
+    let n_cols = rhs.shape(rank - 1);
+    let line_size = lhs.line_size();
+    let k = rhs.shape(rank - 2) / line_size.runtime();
+
+    let row = CUBE_POS_X / n_cols;
+    let col = CUBE_POS_X % n_cols;
+
+    let offset_out = n_rows * n_cols * CUBE_POS_Z;
+    let offset_lhs = batch_offset(lhs, out, offset_out, num_batches) + row * k;
+    let offset_rhs = batch_offset(rhs, out, offset_out, num_batches) + col * k;
+
+    let mut sum = Line::empty(line_size).fill(N::from_int(0));
+
+    for i in range_stepped(UNIT_POS_X, k, CUBE_DIM_X) {
+        sum += lhs[offset_lhs + i] * rhs[offset_rhs + i];
+    }
+
+    let mut partial = N::from_int(0);
+    #[unroll]
+    for v in 0..line_size {
+        partial += sum[v];
+    }
+
+    let mut partials = SharedMemory::<N>::new(cube_size);
+    partials[UNIT_POS_X] = partial;
+    sync_units();
+
+    let mut stride = CUBE_DIM_X / 2;
+    while stride > 0 {
+        if UNIT_POS_X < stride {
+            partials[UNIT_POS_X] = partials[UNIT_POS_X] + partials[UNIT_POS_X + stride];
+        }
+        sync_units();
+        stride /= 2;
+    }
+
+    if UNIT_POS_X == 0 {
+        let out_index = CUBE_POS_X + offset_out;
+        let mut accum = partials[0] * N::cast_from(alpha);
+
+        if accumulate {
+            accum += N::cast_from(beta) * out[out_index];
+        }
 
         out[out_index] = accum;
-    } else {
-        out[out_index] = sum[0];
     }
 }
 
+/// Returns the offset, in lines, of the matrix of `input` multiplied into the output matrix
+/// starting at `offset_out`, its batch dimensions of size one being broadcasted.
+#[cube]
+fn batch_offset<N: Numeric>(
+    input: &Tensor<Line<N>>,
+    out: &Tensor<N>,
+    offset_out: u32,
+    #[comptime] num_batches: Option<u32>,
+) -> u32 {
+    let rank = out.rank();
+    let end = num_batches.unwrap_or_else(|| rank - 2);
+    let unroll = num_batches.is_some();
+
+    let mut offset = 0;
+
+    #[unroll(unroll)]
+    for i in 0..end {
+        let ogwl = offset_out / out.stride(i);
+
+        offset += ogwl % input.shape(i) * input.stride(i);
+    }
+
+    offset / input.line_size().runtime()
+}
+
 /// Matrix multiplication using memory coalescing algorithm with custom cube dimensions
 pub fn launch_ref<R: Runtime, E: Numeric>(
     client: &ComputeClient<R::Server, R::Channel>,
     lhs: &TensorHandleRef<'_, R>,
     rhs: &TensorHandleRef<'_, R>,
     out: &TensorHandleRef<'_, R>,
+) -> Result<(), MatmulLaunchError> {
+    launch_ref_scaled::<R, E>(client, lhs, rhs, out, 1.0, 0.0)
+}
+
+/// Same as [launch_ref], but computes `out = alpha * lhs @ rhs + beta * out`.
+///
+/// The current output is only read when `beta` is not zero, so `out` may be uninitialized
+/// in that case.
+pub fn launch_ref_scaled<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+    alpha: f32,
+    beta: f32,
 ) -> Result<(), MatmulLaunchError> {
     let lhs =
         TensorHandle::<R, E>::new(lhs.shape.to_vec(), lhs.strides.to_vec(), lhs.handle.clone());
     let rhs =
         TensorHandle::<R, E>::new(rhs.shape.to_vec(), rhs.strides.to_vec(), rhs.handle.clone());
 
-    launch(client, lhs, rhs, out)
+    launch_scaled(client, lhs, rhs, out, alpha, beta)
 }
 
 pub fn launch<R: Runtime, E: Numeric>(
@@ -99,9 +205,37 @@ pub fn launch<R: Runtime, E: Numeric>(
     lhs: TensorHandle<R, E>,
     rhs: TensorHandle<R, E>,
     out: &TensorHandleRef<'_, R>,
+) -> Result<(), MatmulLaunchError> {
+    launch_scaled(client, lhs, rhs, out, 1.0, 0.0)
+}
+
+/// Same as [launch], but computes `out = alpha * lhs @ rhs + beta * out`.
+///
+/// Every dimension before the last two is a batch dimension, of which there can be any number.
+/// Batch dimensions of size one in `lhs` or `rhs` are broadcasted to the size of the output, but
+/// both inputs must have the same rank.
+///
+/// Vector-matrix and matrix-vector products, where m or n is one, are dispatched to a kernel
+/// splitting each dot product over a cube, unless there are too many output elements per batch
+/// for the cube count.
+pub fn launch_scaled<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandle<R, E>,
+    rhs: TensorHandle<R, E>,
+    out: &TensorHandleRef<'_, R>,
+    alpha: f32,
+    beta: f32,
 ) -> Result<(), MatmulLaunchError> {
     let (cube_dim_x, cube_dim_y) = (32, 8);
     let ndims = lhs.shape.len();
+
+    if rhs.shape.len() != ndims {
+        return Err(MatmulInvalidProblem::MismatchedBatchRank {
+            lhs: ndims as u32 - 2,
+            rhs: rhs.shape.len() as u32 - 2,
+        }
+        .into());
+    }
     let dim1 = ndims - 1;
     let dim2 = ndims - 2;
 
@@ -143,6 +277,42 @@ pub fn launch<R: Runtime, E: Numeric>(
         MatrixLayout::HighlyPermuted => correct_rhs_layout(rhs),
     };
 
+    let vectorization_factor = match lhs.shape[ndims - 1] % 4 == 0 {
+        true => 4,
+        false => 1,
+    };
+
+    let (num_rows, num_cols) = (lhs.shape[dim2], rhs_original_shape[dim1]);
+    let gemv_count = match num_rows == 1 || num_cols == 1 {
+        true => gemv_cube_count(out.shape, num_rows * num_cols),
+        false => None,
+    };
+
+    if let Some(cube_count) = gemv_count {
+        unsafe {
+            gemv_kernel::launch_unchecked::<E, R>(
+                client,
+                cube_count,
+                CubeDim::new(GEMV_CUBE_SIZE, 1, 1),
+                lhs.as_arg(vectorization_factor),
+                TensorArg::from_raw_parts::<E>(
+                    &rhs.handle,
+                    &rhs.strides,
+                    &rhs_original_shape,
+                    vectorization_factor,
+                ),
+                out.as_tensor_arg(1),
+                ScalarArg::new(alpha),
+                ScalarArg::new(beta),
+                Some(ndims as u32 - 2),
+                beta != 0.0,
+                GEMV_CUBE_SIZE,
+            );
+        };
+
+        return Ok(());
+    }
+
     let cube_count = simple_cube_count(
         &lhs.shape,
         &rhs_original_shape,
@@ -151,11 +321,6 @@ pub fn launch<R: Runtime, E: Numeric>(
         cube_dim_y,
     )?;
 
-    let vectorization_factor = match lhs.shape[ndims - 1] % 4 == 0 {
-        true => 4,
-        false => 1,
-    };
-
     unsafe {
         matmul_kernel::launch_unchecked::<E, R>(
             client,
@@ -169,13 +334,29 @@ pub fn launch<R: Runtime, E: Numeric>(
                 vectorization_factor,
             ),
             out.as_tensor_arg(1),
+            ScalarArg::new(alpha),
+            ScalarArg::new(beta),
             Some(ndims as u32 - 2),
+            beta != 0.0,
         );
     };
 
     Ok(())
 }
 
+/// Number of units computing each output element of [gemv_kernel].
+const GEMV_CUBE_SIZE: u32 = 64;
+
+/// One cube per output element along x and one per batch along z, if they fit in the cube count.
+fn gemv_cube_count(output_shape: &[usize], num_outputs: usize) -> Option<CubeCount> {
+    let ndims = output_shape.len();
+    let num_batches: usize = output_shape[..ndims - 2].iter().product();
+    let max_cube_count = u16::MAX as usize;
+
+    (num_outputs <= max_cube_count && num_batches <= max_cube_count)
+        .then(|| CubeCount::Static(num_outputs as u32, 1, num_batches as u32))
+}
+
 fn simple_cube_count(
     lhs_shape: &[usize],
     rhs_shape: &[usize],
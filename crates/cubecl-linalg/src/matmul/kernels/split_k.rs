@@ -0,0 +1,183 @@
+//! Split-K matmul
+//!
+//! The contraction dimension is partitioned into `splits` chunks that are solved as independent
+//! matmuls writing partial products into a temporary buffer, which is then summed into the output.
+//! This helps utilization when m and n are small but k is large.
+//!
+//! When k isn't divisible by the number of splits, the remaining columns of lhs and rows of rhs are
+//! solved as one more partial matmul.
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+use crate::tensor::TensorHandle;
+
+use super::matmul::{self, MatmulSelector};
+use super::reduce;
+use super::{MatmulContext, MatmulInvalidProblem, MatmulLaunchError};
+
+/// Launch a matrix multiplication where the k dimension is split into `splits` partial matmuls,
+/// each solved with the kernel chosen by the selector, followed by a reduction into `out`.
+///
+/// With `splits == 1`, this is exactly [matmul::launch_ref].
+/// The output may have any strides, and `splits` must be between 1 and `k`.
+pub fn launch_ref<R: Runtime, EG: Numeric, S: MatmulSelector>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+    splits: u32,
+) -> Result<(), MatmulLaunchError> {
+    launch_ref_with_context::<R, EG, S>(client, &mut MatmulContext::new(), lhs, rhs, out, splits)
+}
+
+/// Same as [launch_ref], with the buffer of the partial products taken from `context`, so that
+/// launches of the same problem reuse it.
+pub fn launch_ref_with_context<R: Runtime, EG: Numeric, S: MatmulSelector>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    context: &mut MatmulContext<R>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+    splits: u32,
+) -> Result<(), MatmulLaunchError> {
+    if splits == 1 {
+        return matmul::launch_ref::<R, EG, S>(client, lhs, rhs, out);
+    }
+
+    let rank = lhs.shape.len();
+    let k = lhs.shape[rank - 1];
+
+    if splits == 0 || splits as usize > k {
+        return Err(MatmulInvalidProblem::InvalidSplitK {
+            k: k as u32,
+            splits,
+        }
+        .into());
+    }
+
+    let splits_usize = splits as usize;
+    let k_split = k / splits_usize;
+    let k_remainder = k % splits_usize;
+    let num_partials = splits_usize + (k_remainder > 0) as usize;
+
+    // Each input gets a new batch axis, just before the matrix dims, that walks along k.
+    let with_split_axis = |values: &[usize], split_value: usize| {
+        let mut values = values.to_vec();
+        values.insert(rank - 2, split_value);
+        values
+    };
+
+    let mut lhs_shape = with_split_axis(lhs.shape, splits_usize);
+    lhs_shape[rank] = k_split;
+    let lhs_strides = with_split_axis(lhs.strides, k_split * lhs.strides[rank - 1]);
+
+    let mut rhs_shape = with_split_axis(rhs.shape, splits_usize);
+    rhs_shape[rank - 1] = k_split;
+    let rhs_strides = with_split_axis(rhs.strides, k_split * rhs.strides[rank - 2]);
+
+    let partials = context.intermediate::<EG>(client, with_split_axis(out.shape, num_partials));
+    let partials_shape = with_split_axis(out.shape, splits_usize);
+
+    let (lhs_split, rhs_split, partials_split) = unsafe {
+        (
+            TensorHandleRef::<R>::from_raw_parts(
+                lhs.handle,
+                &lhs_strides,
+                &lhs_shape,
+                lhs.elem_size,
+            ),
+            TensorHandleRef::<R>::from_raw_parts(
+                rhs.handle,
+                &rhs_strides,
+                &rhs_shape,
+                rhs.elem_size,
+            ),
+            TensorHandleRef::<R>::from_raw_parts(
+                &partials.handle,
+                &partials.strides,
+                &partials_shape,
+                out.elem_size,
+            ),
+        )
+    };
+
+    matmul::launch_ref::<R, EG, S>(client, &lhs_split, &rhs_split, &partials_split)?;
+
+    if k_remainder > 0 {
+        let k_start = k - k_remainder;
+
+        let mut lhs_shape = lhs.shape.to_vec();
+        lhs_shape[rank - 1] = k_remainder;
+        let lhs_handle = lhs
+            .handle
+            .clone()
+            .offset_start((k_start * lhs.strides[rank - 1] * lhs.elem_size) as u64);
+
+        let mut rhs_shape = rhs.shape.to_vec();
+        rhs_shape[rank - 2] = k_remainder;
+        let rhs_handle = rhs
+            .handle
+            .clone()
+            .offset_start((k_start * rhs.strides[rank - 2] * rhs.elem_size) as u64);
+
+        // The last partial, left out of the view of the other ones.
+        let mut last_strides = partials.strides.clone();
+        let split_stride = last_strides.remove(rank - 2);
+        let last_handle = partials
+            .handle
+            .clone()
+            .offset_start((splits_usize * split_stride * out.elem_size) as u64);
+
+        let (lhs_last, rhs_last, partials_last) = unsafe {
+            (
+                TensorHandleRef::<R>::from_raw_parts(
+                    &lhs_handle,
+                    lhs.strides,
+                    &lhs_shape,
+                    lhs.elem_size,
+                ),
+                TensorHandleRef::<R>::from_raw_parts(
+                    &rhs_handle,
+                    rhs.strides,
+                    &rhs_shape,
+                    rhs.elem_size,
+                ),
+                TensorHandleRef::<R>::from_raw_parts(
+                    &last_handle,
+                    &last_strides,
+                    out.shape,
+                    out.elem_size,
+                ),
+            )
+        };
+
+        matmul::launch_ref::<R, EG, S>(client, &lhs_last, &rhs_last, &partials_last)?;
+    }
+
+    sum_partials::<R, EG>(client, &partials, out)
+}
+
+fn sum_partials<R: Runtime, EG: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    partials: &TensorHandle<R, EG>,
+    out: &TensorHandleRef<'_, R>,
+) -> Result<(), MatmulLaunchError> {
+    let split_axis = out.shape.len() - 2;
+
+    // The reduction is over the first axis, so the split axis is moved in front in a view.
+    let mut shape = partials.shape.clone();
+    let mut strides = partials.strides.clone();
+    shape.insert(0, shape.remove(split_axis));
+    strides.insert(0, strides.remove(split_axis));
+
+    let partials = unsafe {
+        TensorHandleRef::<R>::from_raw_parts(
+            &partials.handle,
+            &strides,
+            &shape,
+            EG::size().expect("Should be a native type"),
+        )
+    };
+
+    reduce::launch_ref::<R, EG>(client, &partials, out)
+}
@@ -137,7 +137,9 @@ fn calculate_batch_offsets<N: Numeric>(
 }
 
 #[cube]
-fn make_shared_memories<N: Numeric>(#[comptime] config: CubeTiling2dConfig) -> SharedMemories<N> {
+pub(crate) fn make_shared_memories<N: Numeric>(
+    #[comptime] config: CubeTiling2dConfig,
+) -> SharedMemories<N> {
     let tile_size = config.tile_size;
     let block_size_m = config.block_size_m;
     let block_size_k = config.block_size_k;
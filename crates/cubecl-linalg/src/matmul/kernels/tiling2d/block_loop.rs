@@ -2,7 +2,7 @@ use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
 
 use super::{
-    base::{BatchOffsets, Coordinates, Dimensions, SharedMemories},
+    base::{make_shared_memories, BatchOffsets, Coordinates, Dimensions, SharedMemories},
     compute_loop::compute_loop,
     config::CubeTiling2dConfig,
     load_shared_memory::load_to_shared_memories,
@@ -20,6 +20,24 @@ pub(crate) fn block_loop<N: Numeric>(
     shared: SharedMemories<N>,
     #[comptime] config: CubeTiling2dConfig,
     dims: Dimensions,
+) {
+    if comptime!(config.double_buffered) {
+        double_buffered_block_loop::<N>(lhs, rhs, out, coordinates, offsets, shared, config, dims);
+    } else {
+        single_buffered_block_loop::<N>(lhs, rhs, out, coordinates, offsets, shared, config, dims);
+    }
+}
+
+#[cube]
+fn single_buffered_block_loop<N: Numeric>(
+    lhs: &Tensor<Line<N>>,
+    rhs: &Tensor<Line<N>>,
+    out: &mut Tensor<Line<N>>,
+    coordinates: Coordinates,
+    offsets: BatchOffsets,
+    shared: SharedMemories<N>,
+    #[comptime] config: CubeTiling2dConfig,
+    dims: Dimensions,
 ) {
     let mut results = init_results::<N>(config);
     let block_size_k = config.block_size_k;
@@ -49,6 +67,80 @@ pub(crate) fn block_loop<N: Numeric>(
     write_to_output::<N, TileWriter<N>>(out, &results, coordinates, offsets.out, dims, config);
 }
 
+/// Computes each block from one pair of shared memories while the next block is loaded into the
+/// other one, the roles being swapped at every block.
+///
+/// A block is only overwritten after the synchronization that follows its computation, so a
+/// single synchronization per block is needed.
+#[cube]
+fn double_buffered_block_loop<N: Numeric>(
+    lhs: &Tensor<Line<N>>,
+    rhs: &Tensor<Line<N>>,
+    out: &mut Tensor<Line<N>>,
+    coordinates: Coordinates,
+    offsets: BatchOffsets,
+    shared: SharedMemories<N>,
+    #[comptime] config: CubeTiling2dConfig,
+    dims: Dimensions,
+) {
+    let mut results = init_results::<N>(config);
+    let block_size_k = config.block_size_k;
+    let n_loops = (dims.k + block_size_k - 1) / block_size_k;
+    let other = make_shared_memories::<N>(config);
+
+    load_to_shared_memories::<N, TileLoader<N>>(
+        lhs,
+        rhs,
+        coordinates,
+        0,
+        offsets,
+        shared,
+        config,
+        dims,
+    );
+    sync_units();
+
+    // The buffers can't be selected at runtime, so the loop goes over pairs of blocks.
+    let n_pairs = (n_loops + 1) / 2;
+    for pair in 0..n_pairs {
+        let block = pair * 2;
+
+        if block + 1 < n_loops {
+            load_to_shared_memories::<N, TileLoader<N>>(
+                lhs,
+                rhs,
+                coordinates,
+                (block + 1) * block_size_k,
+                offsets,
+                other,
+                config,
+                dims,
+            );
+        }
+        compute_loop::<N>(coordinates, shared.lhs, shared.rhs, &mut results, config);
+        sync_units();
+
+        if block + 1 < n_loops {
+            if block + 2 < n_loops {
+                load_to_shared_memories::<N, TileLoader<N>>(
+                    lhs,
+                    rhs,
+                    coordinates,
+                    (block + 2) * block_size_k,
+                    offsets,
+                    shared,
+                    config,
+                    dims,
+                );
+            }
+            compute_loop::<N>(coordinates, other.lhs, other.rhs, &mut results, config);
+            sync_units();
+        }
+    }
+
+    write_to_output::<N, TileWriter<N>>(out, &results, coordinates, offsets.out, dims, config);
+}
+
 #[cube]
 fn init_results<N: Numeric>(#[comptime] config: CubeTiling2dConfig) -> Array<N> {
     let tile_size = config.tile_size;
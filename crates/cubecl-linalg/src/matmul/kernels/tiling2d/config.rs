@@ -1,12 +1,19 @@
 use cubecl_core::{
     self as cubecl,
-    prelude::{Init, Scope},
-    CubeDim,
+    prelude::{ComputeClient, Init, Numeric, Scope},
+    CubeDim, Runtime,
 };
 use cubecl_core::{CubeCount, CubeType};
 
+use crate::matmul::components::MatmulProblem;
+
 use super::base::TILE_SIZE;
 
+/// Smallest block size along m and n picked for small problems, below which cubes get too few units.
+const MIN_BLOCK_SIZE_MN: usize = 16;
+/// Largest block size along m and n, which reaches 256 units per cube.
+const MAX_BLOCK_SIZE_MN: usize = 64;
+
 #[derive(Debug, Clone)]
 /// Tiling 2D parameters
 pub struct Tiling2dConfig {
@@ -20,6 +27,9 @@ pub struct Tiling2dConfig {
     pub tile_size: usize,
     /// Loop unrolling
     pub unroll: bool,
+    /// Load the next block into a second pair of shared memories while the current one is
+    /// computed, which doubles the shared memory used but halves the synchronizations.
+    pub double_buffered: bool,
 }
 
 impl Default for Tiling2dConfig {
@@ -30,10 +40,91 @@ impl Default for Tiling2dConfig {
             block_size_n: 64,
             tile_size: TILE_SIZE,
             unroll: false,
+            double_buffered: false,
         }
     }
 }
 
+impl Tiling2dConfig {
+    /// Pick the block sizes for the given problem, fitting the shared memory of the device.
+    pub fn from_problem<R: Runtime, N: Numeric>(
+        client: &ComputeClient<R::Server, R::Channel>,
+        problem: &MatmulProblem,
+    ) -> Self {
+        Self::from_problem_with_limit(
+            problem,
+            N::size().expect("Should be a native type"),
+            client
+                .properties()
+                .hardware_properties()
+                .max_shared_memory_size,
+        )
+    }
+
+    /// Pick the block sizes for the given problem, such that the shared memory used by elements
+    /// of `elem_size` bytes doesn't exceed `max_shared_memory_size` bytes.
+    ///
+    /// Blocks are as small as possible for small problems, so that no unit is idle, and the full
+    /// `64x64` otherwise, with a block in k as large as the problem allows.
+    /// The blocks are then [shrunk](Self::shrink_to_fit) to fit the limit.
+    pub fn from_problem_with_limit(
+        problem: &MatmulProblem,
+        elem_size: usize,
+        max_shared_memory_size: usize,
+    ) -> Self {
+        let tile_size = TILE_SIZE;
+        let round_up = |size: usize| size.max(1).next_multiple_of(tile_size);
+
+        // The units are shared between m and n when loading, so both block sizes are equal.
+        let block_size_mn = round_up(usize::max(problem.m, problem.n))
+            .next_power_of_two()
+            .clamp(MIN_BLOCK_SIZE_MN, MAX_BLOCK_SIZE_MN);
+        let block_size_k = round_up(problem.k).min(block_size_mn);
+
+        Self {
+            block_size_m: block_size_mn,
+            block_size_k,
+            block_size_n: block_size_mn,
+            tile_size,
+            unroll: false,
+            double_buffered: false,
+        }
+        .shrink_to_fit(elem_size, max_shared_memory_size)
+    }
+
+    /// The shared memory used by the lhs and rhs blocks of elements of `elem_size` bytes.
+    pub fn shared_memory_size(&self, elem_size: usize) -> usize {
+        let num_buffers = if self.double_buffered { 2 } else { 1 };
+
+        num_buffers * (self.block_size_m + self.block_size_n) * self.block_size_k * elem_size
+    }
+
+    /// Shrink the config until its shared memory fits in `max_shared_memory_size` bytes.
+    ///
+    /// Double buffering is dropped first, then the block in k is halved and finally the blocks
+    /// in m and n, never going below the tile size. The config is returned unchanged if it
+    /// already fits, and may still exceed the limit once nothing can be shrunk.
+    pub fn shrink_to_fit(mut self, elem_size: usize, max_shared_memory_size: usize) -> Self {
+        let tile_size = self.tile_size;
+        let halve = |size: usize| (size / 2).max(1).next_multiple_of(tile_size);
+
+        while self.shared_memory_size(elem_size) > max_shared_memory_size {
+            if self.double_buffered {
+                self.double_buffered = false;
+            } else if self.block_size_k > tile_size {
+                self.block_size_k = halve(self.block_size_k);
+            } else if self.block_size_m > tile_size || self.block_size_n > tile_size {
+                self.block_size_m = halve(self.block_size_m);
+                self.block_size_n = halve(self.block_size_n);
+            } else {
+                break;
+            }
+        }
+
+        self
+    }
+}
+
 #[derive(Clone, Copy, Hash, PartialEq, Eq, Debug, CubeType)]
 /// Tiling 2D parameters
 pub struct CubeTiling2dConfig {
@@ -59,6 +150,8 @@ pub struct CubeTiling2dConfig {
     pub lhs_transposed: bool,
     /// Rhs is transposed in global memory
     pub rhs_transposed: bool,
+    /// Shared memories are doubled to load the next block during the computation of the current one
+    pub double_buffered: bool,
 }
 
 impl Init for CubeTiling2dConfig {
@@ -100,6 +193,7 @@ impl CubeTiling2dConfig {
             tile_size: config.tile_size as u32,
             lhs_transposed,
             rhs_transposed,
+            double_buffered: config.double_buffered,
         }
     }
 }
@@ -126,3 +220,94 @@ pub fn tiling2d_cube_dim(config: &Tiling2dConfig) -> CubeDim {
         1,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem(m: usize, n: usize, k: usize) -> MatmulProblem {
+        MatmulProblem {
+            m,
+            n,
+            k,
+            batches: (vec![1], vec![1]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn config_never_exceeds_shared_memory() {
+        let sizes = [1, 7, 16, 60, 64, 100, 256, 1024, 4096];
+
+        for max_shared_memory_size in [1024, 8192, 16384, 32768, 49152] {
+            for elem_size in [2, 4, 8] {
+                for (m, n, k) in sizes.iter().flat_map(|m| {
+                    sizes
+                        .iter()
+                        .flat_map(move |n| sizes.iter().map(move |k| (*m, *n, *k)))
+                }) {
+                    let config = Tiling2dConfig::from_problem_with_limit(
+                        &problem(m, n, k),
+                        elem_size,
+                        max_shared_memory_size,
+                    );
+
+                    assert!(
+                        config.shared_memory_size(elem_size) <= max_shared_memory_size,
+                        "{config:?} exceeds {max_shared_memory_size} bytes for m={m}, n={n}, k={k}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn config_is_valid_for_the_kernel() {
+        for (m, n, k) in [(1, 1, 1), (7, 300, 5), (64, 64, 64), (4096, 30, 4096)] {
+            let config = Tiling2dConfig::from_problem_with_limit(&problem(m, n, k), 4, 16384);
+
+            assert_eq!(config.block_size_m, config.block_size_n);
+            assert!(config.block_size_k <= config.block_size_m);
+            assert_eq!(config.block_size_m % config.tile_size, 0);
+            assert_eq!(config.block_size_k % config.tile_size, 0);
+        }
+    }
+
+    #[test]
+    fn large_problem_uses_largest_blocks() {
+        let config = Tiling2dConfig::from_problem_with_limit(&problem(4096, 4096, 4096), 4, 49152);
+
+        assert_eq!(config.block_size_m, MAX_BLOCK_SIZE_MN);
+        assert_eq!(config.block_size_k, MAX_BLOCK_SIZE_MN);
+    }
+
+    #[test]
+    fn small_problem_uses_smaller_blocks() {
+        let config = Tiling2dConfig::from_problem_with_limit(&problem(20, 10, 8), 4, 49152);
+
+        assert_eq!(config.block_size_m, 32);
+        assert_eq!(config.block_size_k, 8);
+    }
+
+    #[test]
+    fn shrink_drops_double_buffering_first() {
+        let config = Tiling2dConfig {
+            double_buffered: true,
+            ..Default::default()
+        }
+        .shrink_to_fit(4, 32768);
+
+        assert!(!config.double_buffered);
+        assert_eq!(config.block_size_m, 64);
+        assert_eq!(config.block_size_k, 32);
+    }
+
+    #[test]
+    fn shrink_keeps_a_config_that_fits() {
+        let config = Tiling2dConfig::default().shrink_to_fit(4, 49152);
+
+        assert_eq!(config.block_size_m, 64);
+        assert_eq!(config.block_size_k, 32);
+        assert_eq!(config.block_size_n, 64);
+    }
+}
@@ -6,5 +6,7 @@ pub mod kernels;
 /// Tests for matmul kernels
 #[cfg(feature = "export_tests")]
 pub mod tests;
+mod tune;
 
 pub use base::*;
+pub use tune::{clear_autotune_cache, MatmulAutotuneKey};
@@ -0,0 +1,183 @@
+//! Throughput of the matmul strategies, to compare them and catch performance regressions.
+use std::marker::PhantomData;
+
+use cubecl_core::benchmark::{Benchmark, BenchmarkComputations, TimestampsResult, TimingMethod};
+use cubecl_core::prelude::*;
+use cubecl_core::{future, CubeElement};
+use serde::{Deserialize, Serialize};
+
+use crate::matmul::components::{Ident, MatmulProblem};
+use crate::matmul::tests::cmma_matmul::matmul_test_launcher::{tensor_raw_parts, TestData};
+use crate::matmul::{self, Strategy};
+use crate::tensor::TensorHandle;
+
+/// Number of batches and matrix sizes of a benchmarked matmul.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatmulBenchShape {
+    pub b: usize,
+    pub m: usize,
+    pub n: usize,
+    pub k: usize,
+}
+
+impl MatmulBenchShape {
+    /// The number of floating point operations of the matmul, see [MatmulProblem::flops].
+    pub fn flops(&self) -> u64 {
+        2 * (self.b * self.m * self.n * self.k) as u64
+    }
+}
+
+/// Timings of a strategy on a shape, as measured by [bench_matmul].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MatmulBenchResult {
+    /// The [Debug] representation of the strategy.
+    pub strategy: String,
+    pub shape: MatmulBenchShape,
+    /// How durations were measured, either `full` or `device_only`.
+    pub timing_method: String,
+    pub num_samples: usize,
+    pub median_secs: f64,
+    pub mean_secs: f64,
+    pub min_secs: f64,
+    pub max_secs: f64,
+    /// The throughput over the median duration.
+    pub gflops: f64,
+    /// The minimal global memory traffic over the median duration, in GB/s.
+    pub bandwidth_gbps: f64,
+}
+
+/// Time every strategy on every shape, with row-major random inputs of type `EG`.
+///
+/// Each pair is launched once before being timed, which also fills the cache of
+/// [Strategy::Autotune]. Pairs that fail to launch, because the strategy is unavailable on the
+/// device or can't solve the shape, are left out of the results. The number of samples can be
+/// set with the `BENCH_NUM_SAMPLES` environment variable.
+pub fn bench_matmul<R: Runtime, EG: Float + CubeElement>(
+    device: &R::Device,
+    strategies: &[Strategy],
+    shapes: &[MatmulBenchShape],
+    timing_method: TimingMethod,
+) -> Vec<MatmulBenchResult> {
+    let client = R::client(device);
+    if let TimingMethod::DeviceOnly = timing_method {
+        client.enable_timestamps();
+    }
+
+    let mut results = Vec::new();
+
+    for shape in shapes {
+        for strategy in strategies {
+            let bench = MatmulBench::<R, EG> {
+                shape: *shape,
+                strategy: strategy.clone(),
+                client: client.clone(),
+                _eg: PhantomData,
+            };
+
+            if let Err(err) = bench.launch(bench.prepare()) {
+                println!("Skipping {} on {shape:?}: {err:?}", bench.name());
+                continue;
+            }
+
+            let problem = bench.problem();
+            let durations = bench.run(timing_method);
+            let computed = BenchmarkComputations::new(&durations);
+            let median_secs = computed.median.as_secs_f64();
+
+            results.push(MatmulBenchResult {
+                strategy: format!("{strategy:?}"),
+                shape: *shape,
+                timing_method: timing_method.to_string(),
+                num_samples: durations.durations.len(),
+                median_secs,
+                mean_secs: computed.mean.as_secs_f64(),
+                min_secs: computed.min.as_secs_f64(),
+                max_secs: computed.max.as_secs_f64(),
+                gflops: problem.flops() as f64 / median_secs / 1e9,
+                bandwidth_gbps: problem.bytes_moved(core::mem::size_of::<EG>()) as f64
+                    / median_secs
+                    / 1e9,
+            });
+        }
+    }
+
+    results
+}
+
+struct MatmulBench<R: Runtime, EG> {
+    shape: MatmulBenchShape,
+    strategy: Strategy,
+    client: ComputeClient<R::Server, R::Channel>,
+    _eg: PhantomData<EG>,
+}
+
+impl<R: Runtime, EG: Float + CubeElement> MatmulBench<R, EG> {
+    fn problem(&self) -> MatmulProblem {
+        MatmulProblem {
+            m: self.shape.m,
+            n: self.shape.n,
+            k: self.shape.k,
+            batches: (vec![self.shape.b], vec![self.shape.b]),
+            ..Default::default()
+        }
+    }
+
+    fn launch(
+        &self,
+        (lhs, rhs, out): <Self as Benchmark>::Args,
+    ) -> Result<(), matmul::kernels::MatmulLaunchError> {
+        matmul::launch_ref::<R, EG>(
+            &self.strategy,
+            &self.client,
+            &lhs.as_ref(),
+            &rhs.as_ref(),
+            &out.as_ref(),
+        )
+    }
+}
+
+impl<R: Runtime, EG: Float + CubeElement> Benchmark for MatmulBench<R, EG> {
+    type Args = (
+        TensorHandle<R, EG>,
+        TensorHandle<R, EG>,
+        TensorHandle<R, EG>,
+    );
+
+    fn prepare(&self) -> Self::Args {
+        let problem = self.problem();
+        let tensor = |ident| {
+            let parts =
+                tensor_raw_parts::<EG, R>(&self.client, &problem, ident, 0, TestData::Random);
+            TensorHandle::new(parts.shape, parts.strides, parts.handle)
+        };
+
+        (tensor(Ident::Lhs), tensor(Ident::Rhs), tensor(Ident::Out))
+    }
+
+    fn execute(&self, args: Self::Args) {
+        self.launch(args).unwrap();
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "matmul-{}-{}-{:?}",
+            R::name(),
+            EG::as_elem_native_unchecked(),
+            self.strategy
+        )
+        .to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        let MatmulBenchShape { b, m, n, k } = self.shape;
+        vec![vec![b, m, k], vec![b, k, n]]
+    }
+
+    fn sync(&self) {
+        future::block_on(self.client.sync())
+    }
+
+    fn sync_elapsed(&self) -> TimestampsResult {
+        future::block_on(self.client.sync_elapsed())
+    }
+}
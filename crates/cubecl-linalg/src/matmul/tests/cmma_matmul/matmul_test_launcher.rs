@@ -1,74 +1,161 @@
 use std::fmt::Display;
 
+use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
 use cubecl_core::server::Handle;
 use cubecl_core::tensor_line_size_parallel;
 use cubecl_core::CubeElement;
 use cubecl_core::Feature;
 
+use crate::matmul::components::batch::{BatchConfig, BatchMatmul, BatchMatmulFamily};
 use crate::matmul::components::global::args::TensorInputsLaunch;
+use crate::matmul::components::global::output_loader::Unloader;
+use crate::matmul::components::global::{
+    EpilogueArgs, EpilogueOp, GlobalConfig, HasBeta, UnloadOrder,
+};
+use crate::matmul::components::stage::{CommonStageInput, StageConfig, StageWriter};
 use crate::matmul::components::tile::accelerated::Accelerated;
 use crate::matmul::components::tile::plane::PlaneMma;
+use crate::matmul::components::tile::{TileConfig, TileMatmulFamily};
 use crate::matmul::components::Ident;
+use crate::matmul::components::IntegerMatmulSpec;
 use crate::matmul::components::MatmulConfigFactory;
 use crate::matmul::components::MatmulLaunch;
 use crate::matmul::components::MatmulProblem;
+use crate::matmul::components::MatmulSelection;
+use crate::matmul::components::MatmulSize;
 use crate::matmul::components::MatrixLayout;
-use crate::matmul::components::SingleMatmulSpec;
+use crate::matmul::components::MixedMatmulSpec;
 use crate::matmul::kernels::matmul;
 use crate::matmul::kernels::matmul::Algorithm;
 use crate::matmul::kernels::matmul::StandardSelector;
+use crate::matmul::kernels::MatmulLaunchError;
 use crate::matmul::tests::test_utils::CastInto;
-use crate::tensor::TensorHandle;
+use crate::tensor::{tensor_line_size_strided, ReadWrite, TensorHandle, VirtualTensor};
 
-use crate::matmul::tests::test_utils::assert_equals_approx;
+use crate::matmul::tests::test_utils::assert_equals_exact;
+use crate::matmul::tests::test_utils::generate_identity_data;
 use crate::matmul::tests::test_utils::generate_random_data;
-use crate::matmul::tests::test_utils::matmul_cpu_reference;
+use crate::matmul::tests::test_utils::generate_random_data_ranged;
+use crate::matmul::tests::test_utils::generate_random_int_data;
+use crate::matmul::tests::test_utils::generate_sequential_data;
+use crate::matmul::tests::test_utils::matmul_cpu_reference_cached;
+use crate::matmul::tests::test_utils::Distribution;
+use crate::matmul::tests::test_utils::RoundingMode;
+use crate::matmul::tests::test_utils::{assert_equals_approx, EpsilonPolicy, MatmulAccuracy};
+use crate::matmul::tests::test_utils::{shape, transpose};
 
-struct TensorRawParts<F: Float + CubeElement> {
-    handle: Handle,
-    shape: Vec<usize>,
-    strides: Vec<usize>,
-    original_data: Option<Vec<F>>,
+pub(crate) struct TensorRawParts<F: Float + CubeElement> {
+    pub(crate) handle: Handle,
+    pub(crate) shape: Vec<usize>,
+    pub(crate) strides: Vec<usize>,
+    pub(crate) original_data: Option<Vec<F>>,
 }
 
-type Spec<EG, ES> = SingleMatmulSpec<EG, ES, f32>;
+type Spec<EL, ER, EG, ES> = MixedMatmulSpec<EL, ER, EG, ES, f32>;
+
+/// Data used to fill the inputs, selected with the `MATMUL_TEST_DATA` environment variable.
+///
+/// - `random` (default): pseudo-random values with a fixed seed.
+/// - `identity`: identity matrices, so the output is the other input.
+/// - `sequential`: the ramp `0, 1, 2, ...`, so every value points to its position.
+///
+/// Tests stressing specific magnitudes use [TestData::Ranged] instead, which isn't selectable
+/// from the environment.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TestData {
+    Random,
+    Identity,
+    Sequential,
+    /// Pseudo-random values in `[min, max)` with a fixed seed, rounded to the input type with
+    /// `rounding`.
+    Ranged {
+        min: f32,
+        max: f32,
+        dist: Distribution,
+        rounding: RoundingMode,
+    },
+}
+
+impl TestData {
+    pub fn from_env() -> Self {
+        match std::env::var("MATMUL_TEST_DATA") {
+            Ok(val) => match val.as_str() {
+                "identity" => TestData::Identity,
+                "sequential" => TestData::Sequential,
+                _ => TestData::Random,
+            },
+            Err(_) => TestData::Random,
+        }
+    }
+
+    fn generate<EG: Float + CubeElement>(
+        self,
+        num_batches: usize,
+        rows: usize,
+        cols: usize,
+        seed: u64,
+    ) -> Vec<EG> {
+        match self {
+            TestData::Random => generate_random_data(num_batches * rows * cols, seed),
+            TestData::Identity => generate_identity_data(num_batches, rows, cols),
+            TestData::Sequential => generate_sequential_data(num_batches * rows * cols),
+            TestData::Ranged {
+                min,
+                max,
+                dist,
+                rounding,
+            } => generate_random_data_ranged(
+                num_batches * rows * cols,
+                seed,
+                min,
+                max,
+                dist,
+                rounding,
+            ),
+        }
+    }
+}
 
 /// Test the correctness of the specified Matmul on the given device,
 /// against a naive CPU implementation over the given problem
-pub fn test_matmul_algorithm<A, EG, ES, R>(
+///
+/// The lhs and rhs are generated from `data` with their own element types, which may differ.
+/// Their rows (or columns when column-major) are spaced by `padding` extra elements, which are
+/// filled with garbage that must not be read.
+pub fn test_matmul_algorithm<A, EL, ER, EG, ES, R>(
     client: ComputeClient<R::Server, R::Channel>,
     mut problem: MatmulProblem,
     input: <A::BatchMatmul as MatmulConfigFactory>::Input,
     selection: A::Selection,
+    padding: usize,
+    data: TestData,
 ) where
     A: Algorithm,
-    EG: Float + CubeElement + Display + CastInto<ES>,
+    EL: Float + CubeElement + Display + CastInto<ES>,
+    ER: Float + CubeElement + Display + CastInto<ES>,
+    EG: Float + CubeElement + Display,
     ES: Float + CubeElement + Display + CastInto<EG>,
     R: Runtime,
 {
-    let env = std::env::var("MATMUL_TEST_MODE");
+    let panic_on_launch_err = panic_on_launch_err();
 
-    let panic_on_launch_err = match env {
-        Ok(val) => match val.as_str() {
-            "panic" => true,
-            "skip" => false,
-            _ => false,
-        },
-        Err(_) => false,
-    };
-    let lhs = tensor_raw_parts::<EG, R>(&client, &problem, Ident::Lhs);
-    let rhs = tensor_raw_parts::<EG, R>(&client, &problem, Ident::Rhs);
-    let out = tensor_raw_parts::<EG, R>(&client, &problem, Ident::Out);
+    if let Err(err) = problem.validate() {
+        panic!("Invalid test problem: {err:?}");
+    }
 
-    problem.lhs_line_size = tensor_line_size_parallel(
-        R::line_size_elem(&EG::as_elem_native_unchecked()),
+    let lhs = tensor_raw_parts::<EL, R>(&client, &problem, Ident::Lhs, padding, data);
+    let rhs = tensor_raw_parts::<ER, R>(&client, &problem, Ident::Rhs, padding, data);
+    let out = tensor_raw_parts::<EG, R>(&client, &problem, Ident::Out, 0, data);
+
+    problem.lhs_line_size = tensor_line_size_strided(
+        R::line_size_elem(&EL::as_elem_native_unchecked()),
         &lhs.shape,
         &lhs.strides,
         lhs.strides.len() - 1,
     );
-    problem.rhs_line_size = tensor_line_size_parallel(
-        R::line_size_elem(&EG::as_elem_native_unchecked()),
+    problem.rhs_line_size = tensor_line_size_strided(
+        R::line_size_elem(&ER::as_elem_native_unchecked()),
         &rhs.shape,
         &rhs.strides,
         rhs.strides.len() - 1,
@@ -81,6 +168,9 @@ pub fn test_matmul_algorithm<A, EG, ES, R>(
     );
 
     let cube_dim = A::cube_dim(&selection);
+    if let Err(err) = matmul::check_plane_dim::<R>(&client, cube_dim.x) {
+        panic!("Invalid test selection: {err:?}");
+    }
     let cube_count = A::cube_count(&selection, &problem);
     let config = match A::make_config(
         input,
@@ -101,26 +191,38 @@ pub fn test_matmul_algorithm<A, EG, ES, R>(
         }
     };
 
-    if A::check_availability::<R, (EG, ES, f32)>(&client, &config).is_err() {
+    if A::check_availability::<R, (EL, ER, EG, ES, f32)>(&client, &config).is_err() {
         // Can't execute the test.
         println!("Skipped - not supported!");
         client.flush();
         return;
     }
 
+    let tile = *config
+        .to_gmm_config()
+        .to_smm_config()
+        .to_tmm_config()
+        .size();
+    let accuracy = MatmulAccuracy::new::<ES, f32, R>(
+        &client,
+        Some(tile),
+        A::TileMatmul::requires_tensor_cores(),
+        problem.k,
+    );
+
     unsafe {
-        A::BatchMatmul::launch_unchecked::<Spec<EG, ES>, R>(
+        A::BatchMatmul::launch_unchecked::<Spec<EL, ER, EG, ES>, R>(
             &client,
             cube_dim,
             cube_count,
             TensorInputsLaunch::new(
-                TensorArg::<R>::from_raw_parts::<EG>(
+                TensorArg::<R>::from_raw_parts::<EL>(
                     &lhs.handle,
                     &lhs.strides,
                     &lhs.shape,
                     problem.lhs_line_size,
                 ),
-                TensorArg::<R>::from_raw_parts::<EG>(
+                TensorArg::<R>::from_raw_parts::<ER>(
                     &rhs.handle,
                     &rhs.strides,
                     &rhs.shape,
@@ -133,18 +235,598 @@ pub fn test_matmul_algorithm<A, EG, ES, R>(
                 &out.shape,
                 problem.out_line_size,
             ),
+            problem.beta,
             config,
         );
     }
 
-    assert_result::<EG, ES, R>(
+    assert_result::<EL, ER, EG, ES, R>(
         &lhs.original_data.unwrap(),
         &rhs.original_data.unwrap(),
         &problem,
         &client,
         out.handle,
-        None,
+        accuracy,
+    );
+}
+
+/// Same as [test_matmul_algorithm], with `i8` inputs multiplied and accumulated in `i32`.
+///
+/// Integer products are exact, so the output must be exactly the one of the CPU reference.
+pub fn test_matmul_algorithm_integer<A, R>(
+    client: ComputeClient<R::Server, R::Channel>,
+    mut problem: MatmulProblem,
+    input: <A::BatchMatmul as MatmulConfigFactory>::Input,
+    selection: A::Selection,
+) where
+    A: Algorithm,
+    R: Runtime,
+{
+    if let Err(err) = problem.validate() {
+        panic!("Invalid test problem: {err:?}");
+    }
+
+    let lhs_data = generate_random_int_data(tensor_size(&problem, Ident::Lhs), 1234);
+    let rhs_data = generate_random_int_data(tensor_size(&problem, Ident::Rhs), 5678);
+
+    let lhs_shape = shape(&problem, Ident::Lhs);
+    let lhs_strides = strides(&problem, Ident::Lhs);
+    let lhs = client.create(i8::as_bytes(&with_strides(
+        &lhs_data,
+        problem.m,
+        problem.k,
+        &lhs_strides,
+    )));
+    let rhs_shape = shape(&problem, Ident::Rhs);
+    let rhs_strides = strides(&problem, Ident::Rhs);
+    let rhs = client.create(i8::as_bytes(&with_strides(
+        &rhs_data,
+        problem.k,
+        problem.n,
+        &rhs_strides,
+    )));
+    let out_shape = shape(&problem, Ident::Out);
+    let out_strides = strides(&problem, Ident::Out);
+    let out =
+        client.empty(tensor_size(&problem, Ident::Out) * i32::as_elem_native_unchecked().size());
+
+    problem.lhs_line_size = tensor_line_size_strided(
+        R::line_size_elem(&i8::as_elem_native_unchecked()),
+        &lhs_shape,
+        &lhs_strides,
+        lhs_strides.len() - 1,
+    );
+    problem.rhs_line_size = tensor_line_size_strided(
+        R::line_size_elem(&i8::as_elem_native_unchecked()),
+        &rhs_shape,
+        &rhs_strides,
+        rhs_strides.len() - 1,
+    );
+    problem.out_line_size = tensor_line_size_parallel(
+        R::line_size_elem(&i32::as_elem_native_unchecked()),
+        &out_shape,
+        &out_strides,
+        out_strides.len() - 1,
+    );
+
+    let cube_dim = A::cube_dim(&selection);
+    if let Err(err) = matmul::check_plane_dim::<R>(&client, cube_dim.x) {
+        panic!("Invalid test selection: {err:?}");
+    }
+    let cube_count = A::cube_count(&selection, &problem);
+    let config = match A::make_config(
+        input,
+        &problem,
+        &cube_dim,
+        &cube_count,
+        &A::advanced_config(),
+    ) {
+        Ok(config) => config,
+        Err(err) => {
+            let msg = format!("Can't launch the test: {:?}", err);
+            if panic_on_launch_err() {
+                panic!("{msg}");
+            } else {
+                println!("{msg}");
+                return;
+            }
+        }
+    };
+
+    if A::check_availability::<R, (i8, i8, i32, i8, i32)>(&client, &config).is_err() {
+        // Can't execute the test.
+        println!("Skipped - not supported!");
+        client.flush();
+        return;
+    }
+
+    unsafe {
+        A::BatchMatmul::launch_unchecked::<IntegerMatmulSpec, R>(
+            &client,
+            cube_dim,
+            cube_count,
+            TensorInputsLaunch::new(
+                TensorArg::<R>::from_raw_parts::<i8>(
+                    &lhs,
+                    &lhs_strides,
+                    &lhs_shape,
+                    problem.lhs_line_size,
+                ),
+                TensorArg::<R>::from_raw_parts::<i8>(
+                    &rhs,
+                    &rhs_strides,
+                    &rhs_shape,
+                    problem.rhs_line_size,
+                ),
+            ),
+            TensorArg::<R>::from_raw_parts::<i32>(
+                &out,
+                &out_strides,
+                &out_shape,
+                problem.out_line_size,
+            ),
+            problem.beta,
+            config,
+        );
+    }
+
+    let expected = matmul_cpu_reference_cached::<i8, i8, i32, i32>(&lhs_data, &rhs_data, &problem);
+    if let Err(e) = assert_equals_exact::<R, i32>(&client, out, &expected, problem.m, problem.n) {
+        panic!("{}", e);
+    }
+}
+
+/// Test the correctness of the specified Matmul on [sliced](VirtualTensor::slice_2d) blocks of
+/// larger row-major tensors, against the CPU reference on the extracted blocks.
+///
+/// The lhs block starts at `(m_start, k_start)`, the rhs block at `(k_start, n_start)` and the
+/// out block at `(m_start, n_start)`, with the sizes of the `problem`. The column starts must be
+/// multiples of the line sizes.
+pub fn test_matmul_algorithm_sliced<A, EG, ES, R>(
+    client: ComputeClient<R::Server, R::Channel>,
+    mut problem: MatmulProblem,
+    (m_start, n_start, k_start): (usize, usize, usize),
+    input: <A::BatchMatmul as MatmulConfigFactory>::Input,
+    selection: A::Selection,
+) where
+    A: Algorithm,
+    EG: Float + CubeElement + Display + CastInto<ES>,
+    ES: Float + CubeElement + Display + CastInto<EG>,
+    R: Runtime,
+{
+    if let Err(err) = problem.validate() {
+        panic!("Invalid test problem: {err:?}");
+    }
+
+    // The blocks are surrounded by other values on every side.
+    let margin = 16;
+    let full = MatmulProblem {
+        m: m_start + problem.m + margin,
+        n: n_start + problem.n + margin,
+        k: k_start + problem.k + margin,
+        ..problem.clone()
+    };
+
+    let lhs_full: Vec<EG> = generate_random_data(tensor_size(&full, Ident::Lhs), 1234);
+    let rhs_full: Vec<EG> = generate_random_data(tensor_size(&full, Ident::Rhs), 5678);
+    let lhs_data = extract_block(
+        &lhs_full,
+        (full.m, full.k),
+        (m_start, k_start),
+        (problem.m, problem.k),
+    );
+    let rhs_data = extract_block(
+        &rhs_full,
+        (full.k, full.n),
+        (k_start, n_start),
+        (problem.k, problem.n),
+    );
+
+    let lhs_shape = shape(&full, Ident::Lhs);
+    let lhs_strides = strides(&full, Ident::Lhs);
+    let lhs = client.create(EG::as_bytes(&lhs_full));
+    let rhs_shape = shape(&full, Ident::Rhs);
+    let rhs_strides = strides(&full, Ident::Rhs);
+    let rhs = client.create(EG::as_bytes(&rhs_full));
+    let out_shape = shape(&full, Ident::Out);
+    let out_strides = strides(&full, Ident::Out);
+    let out = client.empty(tensor_size(&full, Ident::Out) * EG::as_elem_native_unchecked().size());
+
+    // Lines must divide the blocks, which are read with the strides of the full tensors.
+    let line_size_elem = R::line_size_elem(&EG::as_elem_native_unchecked());
+    problem.lhs_line_size = tensor_line_size_strided(
+        line_size_elem.clone(),
+        &shape(&problem, Ident::Lhs),
+        &lhs_strides,
+        lhs_strides.len() - 1,
+    );
+    problem.rhs_line_size = tensor_line_size_strided(
+        line_size_elem.clone(),
+        &shape(&problem, Ident::Rhs),
+        &rhs_strides,
+        rhs_strides.len() - 1,
+    );
+    problem.out_line_size = tensor_line_size_strided(
+        line_size_elem,
+        &shape(&problem, Ident::Out),
+        &out_strides,
+        out_strides.len() - 1,
+    );
+    for (start, line_size) in [
+        (k_start, problem.lhs_line_size),
+        (n_start, problem.rhs_line_size),
+        (n_start, problem.out_line_size),
+    ] {
+        assert_eq!(start % line_size as usize, 0, "Blocks must start on a line");
+    }
+
+    let cube_dim = A::cube_dim(&selection);
+    if let Err(err) = matmul::check_plane_dim::<R>(&client, cube_dim.x) {
+        panic!("Invalid test selection: {err:?}");
+    }
+    let cube_count = A::cube_count(&selection, &problem);
+    let config = match A::make_config(
+        input,
+        &problem,
+        &cube_dim,
+        &cube_count,
+        &A::advanced_config(),
+    ) {
+        Ok(config) => config,
+        Err(err) => {
+            let msg = format!("Can't launch the test: {:?}", err);
+            if panic_on_launch_err() {
+                panic!("{msg}");
+            } else {
+                println!("{msg}");
+                return;
+            }
+        }
+    };
+
+    if A::check_availability::<R, (EG, EG, EG, ES, f32)>(&client, &config).is_err() {
+        // Can't execute the test.
+        println!("Skipped - not supported!");
+        client.flush();
+        return;
+    }
+
+    let tile = *config
+        .to_gmm_config()
+        .to_smm_config()
+        .to_tmm_config()
+        .size();
+    let accuracy = MatmulAccuracy::new::<ES, f32, R>(
+        &client,
+        Some(tile),
+        A::TileMatmul::requires_tensor_cores(),
+        problem.k,
+    );
+
+    unsafe {
+        sliced_matmul::launch_unchecked::<EG, EG, EG, ES, f32, A::BatchMatmul, R>(
+            &client,
+            cube_count,
+            cube_dim,
+            TensorArg::<R>::from_raw_parts::<EG>(
+                &lhs,
+                &lhs_strides,
+                &lhs_shape,
+                problem.lhs_line_size,
+            ),
+            TensorArg::<R>::from_raw_parts::<EG>(
+                &rhs,
+                &rhs_strides,
+                &rhs_shape,
+                problem.rhs_line_size,
+            ),
+            TensorArg::<R>::from_raw_parts::<EG>(
+                &out,
+                &out_strides,
+                &out_shape,
+                problem.out_line_size,
+            ),
+            ScalarArg::new(m_start as u32),
+            ScalarArg::new(n_start as u32),
+            ScalarArg::new(k_start as u32),
+            ScalarArg::new(problem.m as u32),
+            ScalarArg::new(problem.n as u32),
+            ScalarArg::new(problem.k as u32),
+            ScalarArg::new(problem.beta),
+            config,
+        );
+    }
+
+    let out_full = EG::from_bytes(&client.read_one(out.binding())).to_vec();
+    let out_data = extract_block(
+        &out_full,
+        (full.m, full.n),
+        (m_start, n_start),
+        (problem.m, problem.n),
+    );
+    assert_result::<EG, EG, EG, ES, R>(
+        &lhs_data,
+        &rhs_data,
+        &problem,
+        &client,
+        client.create(EG::as_bytes(&out_data)),
+        accuracy,
+    );
+}
+
+/// Runs the batch matmul on the blocks of the inputs and output starting at the given rows and
+/// columns.
+#[cube(launch_unchecked)]
+#[allow(clippy::too_many_arguments)]
+fn sliced_matmul<
+    EL: Numeric,
+    ER: Numeric,
+    EG: Numeric,
+    ES: Numeric,
+    EA: Numeric,
+    BMM: BatchMatmulFamily,
+>(
+    lhs: &Tensor<Line<EL>>,
+    rhs: &Tensor<Line<ER>>,
+    out: &mut Tensor<Line<EG>>,
+    m_start: u32,
+    n_start: u32,
+    k_start: u32,
+    m: u32,
+    n: u32,
+    k: u32,
+    beta: f32,
+    #[comptime] config: BMM::Config,
+) {
+    let lhs = VirtualTensor::<EL>::new::<Tensor<Line<EL>>>(lhs).slice_2d(
+        m_start,
+        m_start + m,
+        k_start,
+        k_start + k,
+        config.to_gmm_config().global_line_size(Ident::Lhs),
+    );
+    let rhs = VirtualTensor::<ER>::new::<Tensor<Line<ER>>>(rhs).slice_2d(
+        k_start,
+        k_start + k,
+        n_start,
+        n_start + n,
+        config.to_gmm_config().global_line_size(Ident::Rhs),
+    );
+    // The problem has no bias, so the output only stands in for it and is never read as one.
+    let bias = VirtualTensor::<EG>::new::<Tensor<Line<EG>>>(out);
+    let out = VirtualTensor::<EG, ReadWrite>::new::<Tensor<Line<EG>>>(out).slice_2d(
+        m_start,
+        m_start + m,
+        n_start,
+        n_start + n,
+        config.to_gmm_config().global_line_size(Ident::Out),
+    );
+
+    let epilogue_args = EpilogueArgs::new(bias, beta);
+
+    BMM::Matmul::<(EL, ER, EG, ES, EA)>::execute(lhs, rhs, out, epilogue_args, config);
+}
+
+/// Writes the row-major `tile` through an [Unloader] walking it in the given `order`, and
+/// returns the row-major output of the tile size.
+///
+/// The configs are the ones of a single-tile matmul made by `A` for the tile and line size.
+pub(crate) fn unload_tile<A, R>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    selection: MatmulSelection,
+    line_size: u8,
+    order: UnloadOrder,
+    tile: &[f32],
+) -> Vec<f32>
+where
+    A: Algorithm<Selection = MatmulSelection>,
+    R: Runtime,
+{
+    let shape = (selection.tile.m as usize, selection.tile.n as usize);
+    unload_tile_into::<A, R>(client, selection, line_size, order, tile, shape, false)
+}
+
+/// Same as [unload_tile], with the tile [masked](StageWriter::write_masked) to an output of
+/// `shape`, which lies in the top-left corner of the tile.
+///
+/// The configs don't check bounds, since they are made for a problem of the tile size. The
+/// returned buffer has the size of the tile, with `-1` wherever the output doesn't reach.
+pub(crate) fn unload_tile_masked<A, R>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    selection: MatmulSelection,
+    line_size: u8,
+    tile: &[f32],
+    shape: (usize, usize),
+) -> Vec<f32>
+where
+    A: Algorithm<Selection = MatmulSelection>,
+    R: Runtime,
+{
+    let order = UnloadOrder::of_layout(MatrixLayout::RowMajor);
+    unload_tile_into::<A, R>(client, selection, line_size, order, tile, shape, true)
+}
+
+fn unload_tile_into<A, R>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    selection: MatmulSelection,
+    line_size: u8,
+    order: UnloadOrder,
+    tile: &[f32],
+    shape: (usize, usize),
+    masked: bool,
+) -> Vec<f32>
+where
+    A: Algorithm<Selection = MatmulSelection>,
+    R: Runtime,
+{
+    let problem = MatmulProblem {
+        m: selection.tile.m as usize,
+        n: selection.tile.n as usize,
+        k: selection.tile.k as usize,
+        batches: (vec![], vec![]),
+        out_line_size: line_size,
+        ..Default::default()
+    };
+    let cube_dim = A::cube_dim(&selection);
+    let config = make_config::<A>(&selection, &problem)
+        .unwrap()
+        .to_gmm_config();
+
+    let tile_handle = client.create(f32::as_bytes(tile));
+    let out = client.create(f32::as_bytes(&vec![-1.0; tile.len()]));
+    let shape = [shape.0, shape.1];
+    let strides = [problem.n, 1];
+
+    unsafe {
+        unload_tile_kernel::launch_unchecked::<f32, _, R>(
+            client,
+            CubeCount::new_single(),
+            cube_dim,
+            ArrayArg::from_raw_parts::<f32>(
+                &tile_handle,
+                tile.len() / line_size as usize,
+                line_size,
+            ),
+            TensorArg::<R>::from_raw_parts::<f32>(&out, &strides, &shape, line_size),
+            order,
+            masked,
+            config,
+        );
+    }
+
+    f32::from_bytes(&client.read_one(out.binding())).to_vec()
+}
+
+#[cube(launch_unchecked)]
+fn unload_tile_kernel<EG: Numeric, G: GlobalConfig>(
+    tile: &Array<Line<EG>>,
+    out: &mut Tensor<Line<EG>>,
+    #[comptime] order: UnloadOrder,
+    #[comptime] masked: bool,
+    #[comptime] config: G,
+) {
+    // The config has no bias, so the output only stands in for it and is never read as one.
+    let bias = VirtualTensor::<EG>::new::<Tensor<Line<EG>>>(out);
+    let out = VirtualTensor::<EG, ReadWrite>::new::<Tensor<Line<EG>>>(out);
+    let mut unloader = Unloader::<EG>::new_with_order(
+        out,
+        EpilogueArgs::new(bias, 0.0),
+        0,
+        0,
+        0,
+        MatrixLayout::RowMajor,
+        order,
+        EpilogueOp::None,
+        HasBeta::No,
     );
+
+    if comptime!(masked) {
+        Unloader::<EG>::write_masked::<EG, G>(&mut unloader, tile.to_slice(), 0, 0, config);
+    } else {
+        Unloader::<EG>::write::<EG, G>(&mut unloader, tile.to_slice(), 0, 0, config);
+    }
+}
+
+/// Copy the `size` block starting at `start` out of every `shape` row-major matrix of `data`.
+fn extract_block<E: Copy>(
+    data: &[E],
+    shape: (usize, usize),
+    start: (usize, usize),
+    size: (usize, usize),
+) -> Vec<E> {
+    let batches = data.len() / (shape.0 * shape.1);
+    let mut result = Vec::with_capacity(batches * size.0 * size.1);
+
+    for b in 0..batches {
+        for i in start.0..start.0 + size.0 {
+            let row = (b * shape.0 + i) * shape.1;
+            result.extend_from_slice(&data[row + start.1..row + start.1 + size.1]);
+        }
+    }
+    result
+}
+
+/// The plane dimension of the device, or `None` when it doesn't have a fixed plane size and the
+/// test must be skipped.
+pub(crate) fn device_plane_dim<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+) -> Option<u32> {
+    match matmul::device_plane_dim::<R>(client) {
+        Ok(plane_dim) => Some(plane_dim),
+        Err(_) => {
+            println!("Can't run test without a fixed plane size.");
+            None
+        }
+    }
+}
+
+/// A selection of 16x16x16 tiles in stages of 2x2x2 tiles, for tests that only need a valid one.
+pub(crate) fn test_selection(plane_dim: u32) -> MatmulSelection {
+    MatmulSelection {
+        tile: MatmulSize {
+            m: 16,
+            n: 16,
+            k: 16,
+        },
+        num_stagess: MatmulSize { m: 2, n: 2, k: 2 },
+        plane_dim,
+    }
+}
+
+/// The stage input of `A` for the tile and stage sizes of the selection.
+pub(crate) fn stage_input<A: Algorithm>(
+    selection: &MatmulSelection,
+) -> CommonStageInput<A::TileMatmul> {
+    CommonStageInput {
+        tile: A::TileMatmul::input(selection.tile),
+        num_stages: selection.num_stagess,
+    }
+}
+
+/// Makes the config of `A` for the selection and problem, with the cube dim and count `A`
+/// computes for them.
+#[allow(clippy::type_complexity)]
+pub(crate) fn make_config<A: Algorithm<Selection = MatmulSelection>>(
+    selection: &MatmulSelection,
+    problem: &MatmulProblem,
+) -> Result<<A::BatchMatmul as MatmulConfigFactory>::Config, MatmulLaunchError> {
+    A::make_config(
+        stage_input::<A>(selection),
+        problem,
+        &A::cube_dim(selection),
+        &A::cube_count(selection, problem),
+        &A::advanced_config(),
+    )
+}
+
+/// Random data in `-range..range` with the shape of the given tensor of the problem.
+pub(crate) fn random_data(
+    problem: &MatmulProblem,
+    ident: Ident,
+    seed: u64,
+    range: f32,
+) -> Vec<f32> {
+    generate_random_data_ranged::<f32>(
+        shape(problem, ident).iter().product(),
+        seed,
+        -range,
+        range,
+        Distribution::Uniform,
+        RoundingMode::NearestEven,
+    )
+}
+
+/// Whether a test that can't be launched should panic instead of being skipped, selected with
+/// the `MATMUL_TEST_MODE` environment variable (`panic` or `skip`, the default).
+fn panic_on_launch_err() -> bool {
+    match std::env::var("MATMUL_TEST_MODE") {
+        Ok(val) => match val.as_str() {
+            "panic" => true,
+            "skip" => false,
+            _ => false,
+        },
+        Err(_) => false,
+    }
 }
 
 /// Test the correctness of the high-level Matmul on the given device,
@@ -154,85 +836,98 @@ pub fn test_matmul_launch<EG: Float + CubeElement + Display + CastInto<EG>, R: R
     device: &R::Device,
 ) {
     let client: ComputeClient<<R as Runtime>::Server, <R as Runtime>::Channel> = R::client(device);
+    let elem = EG::as_elem_native().expect("To be a native type");
 
-    if !(client.properties().feature_enabled(Feature::Plane)
-        && client.properties().feature_enabled(Feature::Type(
-            EG::as_elem_native().expect("To be a native type"),
-        )))
-    {
-        // Can't execute the test.
+    if !PlaneMma::is_available::<R>(&client) {
+        println!("Skipped - plane operations are not supported!");
+        return;
+    }
+    if !client.properties().feature_enabled(Feature::Type(elem)) {
+        println!("Skipped - {elem} is not supported!");
         return;
     }
 
-    let lhs = tensor_raw_parts::<EG, R>(&client, &problem, Ident::Lhs);
-    let rhs = tensor_raw_parts::<EG, R>(&client, &problem, Ident::Rhs);
-    let out = tensor_raw_parts::<EG, R>(&client, &problem, Ident::Out);
+    let data = TestData::from_env();
+    let lhs = tensor_raw_parts::<EG, R>(&client, &problem, Ident::Lhs, 0, data);
+    let rhs = tensor_raw_parts::<EG, R>(&client, &problem, Ident::Rhs, 0, data);
+    let out = tensor_raw_parts::<EG, R>(&client, &problem, Ident::Out, 0, data);
 
     let lhs_handle = TensorHandle::new(lhs.shape, lhs.strides, lhs.handle);
     let rhs_handle = TensorHandle::new(rhs.shape, rhs.strides, rhs.handle);
     let out_handle = TensorHandle::new(out.shape, out.strides, out.handle);
 
-    let out = matmul::launch::<R, EG, StandardSelector<Accelerated>>(
+    let launched = matmul::launch_ref_selected::<R, EG, StandardSelector<Accelerated>>(
         &client,
-        lhs_handle.clone(),
-        rhs_handle.clone(),
-        out_handle.clone(),
-    )
-    .unwrap_or_else(|_| {
-        matmul::launch::<R, EG, StandardSelector<PlaneMma>>(
-            &client, lhs_handle, rhs_handle, out_handle,
-        )
-        .unwrap()
-    });
+        &lhs_handle.as_ref(),
+        &rhs_handle.as_ref(),
+        &out_handle.as_ref(),
+    );
+    let (out, accuracy) = match launched {
+        // Same stage precision and tile shape as picked by the launch.
+        Ok(kernel) => {
+            let tile = Some(kernel.selection.tile);
+            let accuracy = if elem == half::f16::as_elem_native_unchecked()
+                || elem == flex32::as_elem_native_unchecked()
+            {
+                MatmulAccuracy::new::<half::f16, f32, R>(&client, tile, true, problem.k)
+            } else if elem == half::bf16::as_elem_native_unchecked() {
+                MatmulAccuracy::new::<half::bf16, f32, R>(&client, tile, true, problem.k)
+            } else {
+                MatmulAccuracy::new::<tf32, f32, R>(&client, tile, true, problem.k)
+            };
+            (out_handle, accuracy)
+        }
+        Err(_) => {
+            println!("No cmma available for {elem}, falling back to plane mma.");
+            let out = matmul::launch::<R, EG, StandardSelector<PlaneMma>>(
+                &client, lhs_handle, rhs_handle, out_handle,
+            )
+            .unwrap();
+            (out, MatmulAccuracy::scalar::<f32>(problem.k))
+        }
+    };
 
-    assert_result::<EG, EG, R>(
+    assert_result::<EG, EG, EG, EG, R>(
         &lhs.original_data.unwrap(),
         &rhs.original_data.unwrap(),
         &problem,
         &client,
         out.handle,
-        // We cannot assume the inner precision of the matmul, therefore we need a permissive epsilon
-        Some(10e-2),
+        accuracy,
     );
 }
 
-fn tensor_raw_parts<EG: Float + CubeElement, R: Runtime>(
+pub(crate) fn tensor_raw_parts<EG: Float + CubeElement, R: Runtime>(
     client: &ComputeClient<R::Server, R::Channel>,
     problem: &MatmulProblem,
     ident: Ident,
+    padding: usize,
+    data: TestData,
 ) -> TensorRawParts<EG> {
     match ident {
         Ident::Lhs => {
             let original_data: Vec<EG> =
-                generate_random_data(tensor_size(problem, Ident::Lhs), 1234);
-            let data = match problem.lhs_layout {
-                MatrixLayout::RowMajor => original_data.clone(),
-                MatrixLayout::ColMajor => {
-                    transpose::<EG>(&original_data, problem.num_batches(), problem.m, problem.k)
-                }
-            };
+                data.generate(num_batches(problem, ident), problem.m, problem.k, 1234);
+            let strides = padded_strides(problem, Ident::Lhs, padding);
+            let data = with_strides(&original_data, problem.m, problem.k, &strides);
 
             TensorRawParts {
                 handle: client.create(EG::as_bytes(&data)),
                 shape: shape(problem, Ident::Lhs),
-                strides: strides(problem, Ident::Lhs),
+                strides,
                 original_data: Some(original_data),
             }
         }
         Ident::Rhs => {
             let original_data: Vec<EG> =
-                generate_random_data(tensor_size(problem, Ident::Rhs), 5678);
-            let data = match problem.rhs_layout {
-                MatrixLayout::RowMajor => original_data.clone(),
-                MatrixLayout::ColMajor => {
-                    transpose::<EG>(&original_data, problem.num_batches(), problem.k, problem.n)
-                }
-            };
+                data.generate(num_batches(problem, ident), problem.k, problem.n, 5678);
+            let strides = padded_strides(problem, Ident::Rhs, padding);
+            let data = with_strides(&original_data, problem.k, problem.n, &strides);
 
             TensorRawParts {
                 handle: client.create(EG::as_bytes(&data)),
                 shape: shape(problem, Ident::Rhs),
-                strides: strides(problem, Ident::Rhs),
+                strides,
                 original_data: Some(original_data),
             }
         }
@@ -251,15 +946,39 @@ fn tensor_raw_parts<EG: Float + CubeElement, R: Runtime>(
                 original_data: None,
             }
         }
+        Ident::Bias => {
+            let original_data: Vec<EG> = data.generate(1, 1, problem.n, 9012);
+
+            TensorRawParts {
+                handle: client.create(EG::as_bytes(&original_data)),
+                shape: shape(problem, Ident::Bias),
+                strides: strides(problem, Ident::Bias),
+                original_data: Some(original_data),
+            }
+        }
     }
 }
 
-fn transpose<E: Copy>(array: &[E], batches: usize, rows: usize, cols: usize) -> Vec<E> {
-    let mut result = vec![array[0]; array.len()];
+/// Lay out `data`, made of contiguous row-major `rows` x `cols` matrices, in a buffer with the
+/// given strides, the last three being the batch, row and column strides.
+///
+/// Elements the strides skip over are filled with garbage that must not be read.
+fn with_strides<E: Numeric>(data: &[E], rows: usize, cols: usize, strides: &[usize]) -> Vec<E> {
+    let rank = strides.len();
+    let (row_stride, col_stride) = (strides[rank - 2], strides[rank - 1]);
+    let batch_stride = if rank > 2 { strides[rank - 3] } else { 0 };
+    let batches = data.len() / (rows * cols);
+
+    // Large enough to show up in the output if it was ever read.
+    let garbage = E::from_int(100);
+    let len = (batches - 1) * batch_stride + (rows - 1) * row_stride + (cols - 1) * col_stride + 1;
+    let mut result = vec![garbage; len];
+
     for b in 0..batches {
         for i in 0..rows {
             for j in 0..cols {
-                result[(b * rows * cols) + j * rows + i] = array[(b * rows * cols) + i * cols + j];
+                result[b * batch_stride + i * row_stride + j * col_stride] =
+                    data[(b * rows + i) * cols + j];
             }
         }
     }
@@ -267,106 +986,157 @@ fn transpose<E: Copy>(array: &[E], batches: usize, rows: usize, cols: usize) ->
 }
 
 fn assert_result<
-    EG: Float + CubeElement + Display + CastInto<ES>,
+    EL: Float + CubeElement + CastInto<ES>,
+    ER: Float + CubeElement + CastInto<ES>,
+    EG: Float + CubeElement + Display,
     ES: Float + CubeElement + CastInto<EG>,
     R: Runtime,
 >(
-    lhs: &[EG],
-    rhs: &[EG],
+    lhs: &[EL],
+    rhs: &[ER],
     problem: &MatmulProblem,
     client: &ComputeClient<R::Server, R::Channel>,
     out: Handle,
-    epsilon: Option<f32>,
+    accuracy: MatmulAccuracy,
 ) {
-    let epsilon = match epsilon {
-        Some(epsilon) => epsilon,
-        None => {
-            let maybe_cmma = client.properties().feature_enabled(Feature::Cmma {
-                a: ES::as_elem_native().expect("To be a native type"),
-                b: ES::as_elem_native().expect("To be a native type"),
-                c: EG::as_elem_native().expect("To be a native type"),
-                m: 16,
-                k: 16,
-                n: 16,
-            });
-
-            // Need to compensate for the temporary conversion to f16/tf32
-            match maybe_cmma {
-                true => 10e-5 / EG::EPSILON.to_f32().unwrap() * half::f16::EPSILON.to_f32(),
-                false => 10e-5,
-            }
-        }
+    let expected = matmul_cpu_reference_cached::<EL, ER, EG, ES>(lhs, rhs, problem);
+    let (expected, rows, cols) = match problem.out_layout {
+        MatrixLayout::RowMajor => (expected, problem.m, problem.n),
+        MatrixLayout::ColMajor => (
+            transpose(
+                &expected,
+                num_batches(problem, Ident::Out),
+                problem.m,
+                problem.n,
+            ),
+            problem.n,
+            problem.m,
+        ),
+        MatrixLayout::Strided { .. } => panic!("Strided outputs can't be compared"),
     };
 
-    let expected = matmul_cpu_reference(lhs, rhs, problem);
-    if let Err(e) = assert_equals_approx::<R, EG>(client, out, &expected, epsilon) {
+    if let Err(e) = assert_equals_approx::<R, EG>(
+        client,
+        out,
+        &expected,
+        EpsilonPolicy::Auto(accuracy),
+        rows,
+        cols,
+    ) {
         panic!("{}", e);
     }
 }
 
 /// Returns the total number of elements for the identified tensor, inferred by the problem definition
 fn tensor_size(problem: &MatmulProblem, ident: Ident) -> usize {
+    let num_batches = num_batches(problem, ident);
     match ident {
-        Ident::Lhs => problem.num_batches() * problem.m * problem.k,
-        Ident::Rhs => problem.num_batches() * problem.k * problem.n,
-        Ident::Out => problem.num_batches() * problem.m * problem.n,
+        Ident::Lhs => num_batches * problem.m * problem.k,
+        Ident::Rhs => num_batches * problem.k * problem.n,
+        Ident::Out => num_batches * problem.m * problem.n,
+        Ident::Bias => problem.n,
     }
 }
 
-/// Returns the shape of the identified tensor, inferred by the problem definition
-fn shape(problem: &MatmulProblem, ident: Ident) -> Vec<usize> {
+/// Returns the number of batches stored by the identified tensor, before broadcasting
+fn num_batches(problem: &MatmulProblem, ident: Ident) -> usize {
     match ident {
-        Ident::Lhs => problem
-            .batches
-            .0
-            .iter()
-            .cloned()
-            .chain(vec![problem.m, problem.k])
-            .collect(),
-        Ident::Rhs => problem
-            .batches
-            .1
-            .iter()
-            .cloned()
-            .chain(vec![problem.k, problem.n])
-            .collect(),
-        Ident::Out => problem
-            .batch_dims()
-            .iter()
-            .cloned()
-            .chain(vec![problem.m, problem.n])
-            .collect(),
+        Ident::Lhs => problem.batches.0.iter().product(),
+        Ident::Rhs => problem.batches.1.iter().product(),
+        Ident::Out => problem.num_batches(),
+        Ident::Bias => 1,
     }
 }
 
 /// Returns the stride of the identified tensor, inferred by the problem definition
 pub(crate) fn strides(problem: &MatmulProblem, ident: Ident) -> Vec<usize> {
+    padded_strides(problem, ident, 0)
+}
+
+/// Returns the stride of the identified tensor when every contiguous row (or column when
+/// column-major) is followed by `padding` unused elements
+///
+/// Strided layouts use their own strides and ignore the padding, and so does the bias, which is
+/// always contiguous.
+fn padded_strides(problem: &MatmulProblem, ident: Ident, padding: usize) -> Vec<usize> {
+    if ident == Ident::Bias {
+        return vec![1];
+    }
+
     let shape = shape(problem, ident);
     let rank = shape.len();
     let mut strides = Vec::with_capacity(rank);
 
-    let (last_batch, x, y) = match ident {
-        Ident::Lhs => match problem.lhs_layout {
-            MatrixLayout::RowMajor => (problem.m * problem.k, problem.k, 1),
-            MatrixLayout::ColMajor => (problem.m * problem.k, 1, problem.m),
-        },
-        Ident::Rhs => match problem.rhs_layout {
-            MatrixLayout::RowMajor => (problem.k * problem.n, problem.n, 1),
-            MatrixLayout::ColMajor => (problem.k * problem.n, 1, problem.k),
-        },
-        Ident::Out => (problem.m * problem.n, problem.n, 1),
+    let (rows, cols, layout) = match ident {
+        Ident::Lhs => (problem.m, problem.k, problem.lhs_layout),
+        Ident::Rhs => (problem.k, problem.n, problem.rhs_layout),
+        Ident::Out => (problem.m, problem.n, problem.out_layout),
+        Ident::Bias => unreachable!("The bias is one-dimensional"),
+    };
+    let (last_batch, x, y) = match layout {
+        MatrixLayout::RowMajor => (rows * (cols + padding), cols + padding, 1),
+        MatrixLayout::ColMajor => (cols * (rows + padding), 1, rows + padding),
+        MatrixLayout::Strided {
+            row_stride,
+            col_stride,
+        } => {
+            let (row_stride, col_stride) = (row_stride as usize, col_stride as usize);
+            let batch_stride = (rows - 1) * row_stride + (cols - 1) * col_stride + 1;
+            (batch_stride, row_stride, col_stride)
+        }
     };
 
     strides.push(y);
     strides.push(x);
 
     if rank > 2 {
-        strides.push(last_batch);
+        let mut batch_stride = last_batch;
+        strides.push(batch_stride);
 
-        for b in shape.iter().rev().take(rank - 3) {
-            strides.push(last_batch * b)
+        for b in shape[1..rank - 2].iter().rev() {
+            batch_stride *= b;
+            strides.push(batch_stride)
         }
     }
 
     strides.into_iter().rev().collect()
 }
+
+/// Returns the batch strides of the identified tensor, aligned on the output batch dimensions.
+///
+/// Batch dimensions that are broadcasted, either because they are missing or of size one,
+/// get a stride of zero so that every output batch reads the same data.
+///
+/// # Panics
+///
+/// If the batch dimensions of the tensor can't be broadcasted to the output.
+pub(crate) fn broadcast_batch_strides(problem: &MatmulProblem, ident: Ident) -> Vec<usize> {
+    let out_batches = problem.batch_dims();
+    let no_batches = Vec::new();
+    let batches = match ident {
+        Ident::Lhs => &problem.batches.0,
+        Ident::Rhs => &problem.batches.1,
+        Ident::Out => &out_batches,
+        Ident::Bias => &no_batches,
+    };
+    let strides = strides(problem, ident);
+    let missing = out_batches.len() - batches.len();
+
+    out_batches
+        .iter()
+        .enumerate()
+        .map(|(i, &out_dim)| {
+            let Some(b) = i.checked_sub(missing) else {
+                return 0;
+            };
+            match batches[b] {
+                dim if dim == out_dim => strides[b],
+                1 => 0,
+                dim => panic!(
+                    "Incompatible batch dimensions {:?} and {:?}: {:?} has {dim} at index {b}, which can't be broadcasted to {out_dim}",
+                    problem.batches.0, problem.batches.1, ident
+                ),
+            }
+        })
+        .collect()
+}
@@ -1,9 +1,13 @@
 #![allow(missing_docs)]
 
+pub mod bench;
 pub mod cmma_matmul;
+pub mod reduce;
 pub mod simple;
+pub mod split_k;
 mod test_macros;
-mod test_utils;
+pub(crate) mod test_utils;
 pub mod tiling2d;
 
 pub use test_macros::cmma::suite::*;
+pub use test_utils::{Distribution, FromF32Rounded, RoundingMode};
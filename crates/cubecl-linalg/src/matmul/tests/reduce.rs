@@ -0,0 +1,133 @@
+use std::fmt::Display;
+
+use cubecl_core::{prelude::Float, CubeElement, Runtime};
+
+use crate::{
+    matmul::kernels::{reduce, MatmulInvalidProblem, MatmulLaunchError},
+    tensor::TensorHandle,
+};
+
+use super::test_utils::{
+    assert_equals_approx, generate_random_data, EpsilonPolicy, MatmulAccuracy,
+};
+
+pub fn test_vectorized<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    test_reduce::<R, F>(4, &[2, 16, 32], device);
+}
+
+pub fn test_unaligned<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    test_reduce::<R, F>(3, &[3, 7, 5], device);
+}
+
+pub fn test_single_partial<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    test_reduce::<R, F>(1, &[8, 8], device);
+}
+
+/// Partials stored with their reduced axis in the middle, reduced through a permuted view.
+pub fn test_permuted_partials<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    let client = R::client(device);
+    let (batches, num_partials, rows, cols) = (2, 3, 8, 16);
+
+    // Stored as [batches, num_partials, rows, cols].
+    let data = generate_random_data::<F>(batches * num_partials * rows * cols, 999);
+    let handle = client.create(F::as_bytes(&data));
+
+    let matrix = rows * cols;
+    let mut expected = vec![F::from_int(0); batches * matrix];
+    for b in 0..batches {
+        for p in 0..num_partials {
+            for i in 0..matrix {
+                expected[b * matrix + i] += data[(b * num_partials + p) * matrix + i];
+            }
+        }
+    }
+
+    let partials = TensorHandle::<R, F>::new(
+        vec![num_partials, batches, rows, cols],
+        vec![matrix, num_partials * matrix, cols, 1],
+        handle,
+    );
+    let out = TensorHandle::<R, F>::empty(&client, vec![batches, rows, cols]);
+
+    reduce::launch_ref::<R, F>(&client, &partials.as_ref(), &out.as_ref()).unwrap();
+
+    if let Err(e) = assert_equals_approx::<R, F>(
+        &client,
+        out.handle,
+        &expected,
+        EpsilonPolicy::Auto(MatmulAccuracy::scalar::<F>(num_partials)),
+        rows,
+        cols,
+    ) {
+        panic!("{}", e);
+    }
+}
+
+/// Partials whose reduced axis has a stride that isn't a multiple of the line size picked for the
+/// launch are rejected instead of being read at the wrong offsets.
+pub fn test_unaligned_partial_stride<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let (num_partials, rows, cols) = (2, 4, 16);
+
+    if R::supported_line_sizes()
+        .iter()
+        .all(|line_size| *line_size == 1)
+    {
+        return;
+    }
+
+    // The partials are padded by one element, which leaves the reduced axis with an odd stride.
+    let partial_stride = rows * cols + 1;
+    let data = generate_random_data::<F>(num_partials * partial_stride, 999);
+    let partials = TensorHandle::<R, F>::new(
+        vec![num_partials, rows, cols],
+        vec![partial_stride, cols, 1],
+        client.create(F::as_bytes(&data)),
+    );
+    let out = TensorHandle::<R, F>::empty(&client, vec![rows, cols]);
+
+    let result = reduce::launch_ref::<R, F>(&client, &partials.as_ref(), &out.as_ref());
+
+    assert!(matches!(
+        result,
+        Err(MatmulLaunchError::InvalidProblem(
+            MatmulInvalidProblem::UnalignedStrides { .. }
+        ))
+    ));
+}
+
+fn test_reduce<R: Runtime, F: Float + CubeElement + Display>(
+    num_partials: usize,
+    out_shape: &[usize],
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let num_elems: usize = out_shape.iter().product();
+
+    let data = generate_random_data::<F>(num_partials * num_elems, 999);
+    let mut expected = vec![F::from_int(0); num_elems];
+    for (i, value) in data.iter().enumerate() {
+        expected[i % num_elems] += *value;
+    }
+
+    let mut partials_shape = vec![num_partials];
+    partials_shape.extend_from_slice(out_shape);
+    let partials = TensorHandle::<R, F>::from_data(&client, &data, partials_shape);
+    let out = TensorHandle::<R, F>::empty(&client, out_shape.to_vec());
+
+    reduce::launch_ref::<R, F>(&client, &partials.as_ref(), &out.as_ref()).unwrap();
+
+    let rank = out_shape.len();
+    if let Err(e) = assert_equals_approx::<R, F>(
+        &client,
+        out.handle,
+        &expected,
+        EpsilonPolicy::Auto(MatmulAccuracy::scalar::<F>(num_partials)),
+        out_shape[rank - 2],
+        out_shape[rank - 1],
+    ) {
+        panic!("{}", e);
+    }
+}
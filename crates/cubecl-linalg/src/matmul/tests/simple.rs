@@ -2,9 +2,18 @@ use std::fmt::Display;
 
 use cubecl_core::{prelude::Float, CubeElement, Runtime};
 
-use crate::{matmul::kernels::simple, tensor::TensorHandle};
+use crate::{
+    matmul::{
+        components::{Ident, MatmulProblem},
+        kernels::simple,
+    },
+    tensor::TensorHandle,
+};
 
-use super::test_utils::{assert_equals_approx, MatmulTestCase};
+use super::test_utils::{
+    assert_equals_approx, generate_random_data, matmul_cpu_reference, shape, EpsilonPolicy,
+    MatmulAccuracy, MatmulTestCase,
+};
 
 pub fn test_small<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
     let case = MatmulTestCase {
@@ -50,6 +59,97 @@ pub fn test_with_batches<R: Runtime, F: Float + CubeElement + Display>(device: &
     test_simple::<R, F>(case, device);
 }
 
+pub fn test_with_4d_batches<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    test_simple_batched::<R, F>(vec![2, 3], vec![2, 3], device);
+}
+
+pub fn test_with_broadcasted_batches<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    test_simple_batched::<R, F>(vec![2, 1], vec![1, 3], device);
+}
+
+pub fn test_scaled<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    let case = MatmulTestCase {
+        m: 60,
+        k: 64,
+        n: 60,
+        batch: 2,
+    };
+
+    test_simple_scaled::<R, F>(case, 0.5, 2.0, device);
+}
+
+pub fn test_scaled_without_beta<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    let case = MatmulTestCase {
+        m: 64,
+        k: 64,
+        n: 64,
+        batch: 1,
+    };
+
+    test_simple_scaled::<R, F>(case, 2.0, 0.0, device);
+}
+
+pub fn test_vector_matrix<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    let case = MatmulTestCase {
+        m: 1,
+        k: 256,
+        n: 60,
+        batch: 1,
+    };
+
+    test_simple::<R, F>(case, device);
+}
+
+pub fn test_matrix_vector<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    let case = MatmulTestCase {
+        m: 60,
+        k: 250,
+        n: 1,
+        batch: 1,
+    };
+
+    test_simple::<R, F>(case, device);
+}
+
+pub fn test_vector_matrix_with_batches<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 1,
+        k: 100,
+        n: 64,
+        batch: 3,
+    };
+
+    test_simple::<R, F>(case, device);
+}
+
+pub fn test_matrix_vector_with_batches<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let case = MatmulTestCase {
+        m: 64,
+        k: 128,
+        n: 1,
+        batch: 3,
+    };
+
+    test_simple::<R, F>(case, device);
+}
+
+pub fn test_scaled_vector_matrix<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    let case = MatmulTestCase {
+        m: 1,
+        k: 64,
+        n: 60,
+        batch: 2,
+    };
+
+    test_simple_scaled::<R, F>(case, 0.5, 2.0, device);
+}
+
 fn test_simple<R: Runtime, F: Float + CubeElement + Display>(
     case: MatmulTestCase,
     device: &R::Device,
@@ -63,7 +163,105 @@ fn test_simple<R: Runtime, F: Float + CubeElement + Display>(
     let out: TensorHandle<R, F> = case.empty_out(&client);
     simple::launch::<R, F>(&client, lhs, rhs, &out.as_ref()).unwrap();
 
-    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 10e-4) {
+    if let Err(e) = assert_equals_approx::<R, F>(
+        &client,
+        out.handle,
+        &expected,
+        EpsilonPolicy::Auto(MatmulAccuracy::scalar::<F>(case.k)),
+        case.m,
+        case.n,
+    ) {
+        panic!("{}", e);
+    }
+}
+
+/// Compares the simple matmul of inputs with any number of batch dimensions against the CPU
+/// reference, which broadcasts them the same way.
+fn test_simple_batched<R: Runtime, F: Float + CubeElement + Display>(
+    lhs_batches: Vec<usize>,
+    rhs_batches: Vec<usize>,
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let problem = MatmulProblem {
+        m: 40,
+        n: 24,
+        k: 32,
+        batches: (lhs_batches, rhs_batches),
+        ..Default::default()
+    };
+
+    let input = |ident: Ident, seed: u64| {
+        let shape = shape(&problem, ident);
+        let data = generate_random_data::<F>(shape.iter().product(), seed);
+        let handle = TensorHandle::<R, F>::from_data(&client, &data, shape);
+        (data, handle)
+    };
+    let (lhs_data, lhs) = input(Ident::Lhs, 1234);
+    let (rhs_data, rhs) = input(Ident::Rhs, 5678);
+
+    let expected = matmul_cpu_reference::<F, F>(&lhs_data, &rhs_data, &problem);
+
+    let out_shape = shape(&problem, Ident::Out);
+    let out_size = out_shape.iter().product::<usize>() * core::mem::size_of::<F>();
+    let out: TensorHandle<R, F> = TensorHandle::new_contiguous(out_shape, client.empty(out_size));
+    simple::launch::<R, F>(&client, lhs, rhs, &out.as_ref()).unwrap();
+
+    if let Err(e) = assert_equals_approx::<R, F>(
+        &client,
+        out.handle,
+        &expected,
+        EpsilonPolicy::Auto(MatmulAccuracy::scalar::<F>(problem.k)),
+        problem.m,
+        problem.n,
+    ) {
+        panic!("{}", e);
+    }
+}
+
+fn test_simple_scaled<R: Runtime, F: Float + CubeElement + Display>(
+    case: MatmulTestCase,
+    alpha: f32,
+    beta: f32,
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let lhs = case.random_lhs::<R, F>(&client);
+    let rhs = case.random_rhs::<R, F>(&client);
+
+    let product = case.matmul_cpu::<R, F>(&lhs, &rhs, &client);
+
+    // With beta at zero, the output is left uninitialized since it must not be read.
+    let (out, expected): (TensorHandle<R, F>, Vec<F>) = match beta == 0.0 {
+        true => (
+            case.empty_out(&client),
+            product.iter().map(|p| F::new(alpha) * *p).collect(),
+        ),
+        false => {
+            let init = generate_random_data::<F>(case.batch * case.m * case.n, 1234);
+            let expected = product
+                .iter()
+                .zip(init.iter())
+                .map(|(p, c)| F::new(alpha) * *p + F::new(beta) * *c)
+                .collect();
+            let out = TensorHandle::new_contiguous(
+                vec![case.batch, case.m, case.n],
+                client.create(F::as_bytes(&init)),
+            );
+            (out, expected)
+        }
+    };
+
+    simple::launch_scaled::<R, F>(&client, lhs, rhs, &out.as_ref(), alpha, beta).unwrap();
+
+    if let Err(e) = assert_equals_approx::<R, F>(
+        &client,
+        out.handle,
+        &expected,
+        EpsilonPolicy::Auto(MatmulAccuracy::scalar::<F>(case.k)),
+        case.m,
+        case.n,
+    ) {
         panic!("{}", e);
     }
 }
@@ -0,0 +1,154 @@
+use std::fmt::Display;
+
+use cubecl_core::{prelude::Float, CubeElement, Runtime};
+
+use crate::matmul::components::tile::{plane::PlaneMma, TileMatmulFamily};
+use crate::matmul::components::MatrixLayout;
+use crate::matmul::kernels::{
+    matmul::StandardSelector, split_k, MatmulContext, MatmulInvalidProblem, MatmulLaunchError,
+};
+use crate::tensor::TensorHandle;
+
+use super::test_utils::{
+    assert_equals_approx, transpose, EpsilonPolicy, MatmulAccuracy, MatmulTestCase,
+};
+
+/// Launch a split-k problem into an output of `out_layout` and compare it to the reference.
+///
+/// `k` doesn't have to be divisible by `splits`.
+pub fn test_split_k<R: Runtime, F: Float + CubeElement + Display>(
+    device: &R::Device,
+    k: usize,
+    splits: u32,
+    out_layout: MatrixLayout,
+) {
+    let client = R::client(device);
+    if !PlaneMma::is_available::<R>(&client) {
+        println!("Skipped - plane operations are not supported!");
+        return;
+    }
+
+    let case = MatmulTestCase {
+        m: 32,
+        k,
+        n: 48,
+        batch: 2,
+    };
+    let lhs = case.random_lhs::<R, F>(&client);
+    let rhs = case.random_rhs::<R, F>(&client);
+    let mut expected = case.matmul_cpu::<R, F>(&lhs, &rhs, &client);
+
+    let out: TensorHandle<R, F> = match out_layout {
+        MatrixLayout::RowMajor => case.empty_out(&client),
+        MatrixLayout::ColMajor => {
+            expected = transpose(&expected, case.batch, case.m, case.n);
+            TensorHandle::new(
+                vec![case.batch, case.m, case.n],
+                vec![case.m * case.n, 1, case.m],
+                case.create_empty::<R>(&client, case.batch * case.m, case.n),
+            )
+        }
+    };
+
+    split_k::launch_ref::<R, F, StandardSelector<PlaneMma>>(
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &out.as_ref(),
+        splits,
+    )
+    .unwrap();
+
+    if let Err(e) = assert_equals_approx::<R, F>(
+        &client,
+        out.handle,
+        &expected,
+        EpsilonPolicy::Auto(MatmulAccuracy::scalar::<f32>(case.k)),
+        case.m,
+        case.n,
+    ) {
+        panic!("{}", e);
+    }
+}
+
+/// Check that a number of splits larger than k, or zero, is rejected.
+pub fn test_invalid_splits<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    let client = R::client(device);
+
+    let case = MatmulTestCase {
+        m: 16,
+        k: 8,
+        n: 16,
+        batch: 1,
+    };
+    let lhs = case.random_lhs::<R, F>(&client);
+    let rhs = case.random_rhs::<R, F>(&client);
+    let out: TensorHandle<R, F> = case.empty_out(&client);
+
+    for splits in [0, 9] {
+        match split_k::launch_ref::<R, F, StandardSelector<PlaneMma>>(
+            &client,
+            &lhs.as_ref(),
+            &rhs.as_ref(),
+            &out.as_ref(),
+            splits,
+        ) {
+            Err(MatmulLaunchError::InvalidProblem(MatmulInvalidProblem::InvalidSplitK {
+                k,
+                splits: s,
+            })) => assert_eq!((k, s), (8, splits)),
+            other => panic!("Expected an invalid split-k error, got {other:?}"),
+        }
+    }
+}
+
+/// Launch the same split-k problem several times through one [MatmulContext], which must
+/// allocate the buffer of the partial products only once.
+pub fn test_context_reuse<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    let client = R::client(device);
+    if !PlaneMma::is_available::<R>(&client) {
+        println!("Skipped - plane operations are not supported!");
+        return;
+    }
+
+    let case = MatmulTestCase {
+        m: 32,
+        k: 64,
+        n: 32,
+        batch: 2,
+    };
+    let lhs = case.random_lhs::<R, F>(&client);
+    let rhs = case.random_rhs::<R, F>(&client);
+    let expected = case.matmul_cpu::<R, F>(&lhs, &rhs, &client);
+
+    let mut context = MatmulContext::<R>::new();
+    for _ in 0..3 {
+        let out: TensorHandle<R, F> = case.empty_out(&client);
+        split_k::launch_ref_with_context::<R, F, StandardSelector<PlaneMma>>(
+            &client,
+            &mut context,
+            &lhs.as_ref(),
+            &rhs.as_ref(),
+            &out.as_ref(),
+            2,
+        )
+        .unwrap();
+
+        if let Err(e) = assert_equals_approx::<R, F>(
+            &client,
+            out.handle,
+            &expected,
+            EpsilonPolicy::Auto(MatmulAccuracy::scalar::<f32>(case.k)),
+            case.m,
+            case.n,
+        ) {
+            panic!("{}", e);
+        }
+    }
+
+    assert_eq!(context.num_allocations(), 1);
+    assert_eq!(context.num_buffers(), 1);
+
+    context.clear();
+    assert_eq!(context.num_buffers(), 0);
+}
@@ -23,6 +23,548 @@ macro_rules! testgen_matmul_accelerated {
                     $crate::testgen_matmul_accelerated!($float, half::f16);
                 })*
             }
+
+            #[test]
+            pub fn plane_dim_mismatch() {
+                $crate::matmul::tests::test_plane_dim_mismatch::<TestRuntime>();
+            }
+
+            #[test]
+            pub fn pipeline_availability() {
+                $crate::matmul::tests::test_pipeline_availability::<TestRuntime>();
+            }
+
+            #[test]
+            pub fn launch_checked_rejects_invalid_handles() {
+                $crate::matmul::tests::test_launch_checked_rejects_invalid_handles::<TestRuntime>();
+            }
+
+            #[test]
+            pub fn launch_report() {
+                $crate::matmul::tests::test_launch_report::<TestRuntime>();
+            }
+
+            #[test]
+            pub fn prefetching_matches_full_load() {
+                $crate::matmul::tests::test_prefetching_matches_full_load::<TestRuntime>();
+            }
+
+            #[test]
+            pub fn swizzled_dispatch_matches_unswizzled_width_2() {
+                $crate::matmul::tests::test_swizzled_dispatch_matches_unswizzled::<2, TestRuntime>();
+            }
+
+            #[test]
+            pub fn swizzled_dispatch_matches_unswizzled_width_3() {
+                // The last swizzle column is only partially filled.
+                $crate::matmul::tests::test_swizzled_dispatch_matches_unswizzled::<3, TestRuntime>();
+            }
+
+            #[test]
+            pub fn accumulator_precision() {
+                $crate::matmul::tests::test_accumulator_precision::<TestRuntime>();
+            }
+
+            #[test]
+            pub fn f16_overflow_reports_non_finite() {
+                $crate::matmul::tests::test_f16_overflow_reports_non_finite::<TestRuntime>();
+            }
+
+            #[test]
+            pub fn with_beta() {
+                $crate::matmul::tests::test_matmul_with_beta::<TestRuntime>(0.5);
+            }
+
+            #[test]
+            pub fn with_beta_one() {
+                $crate::matmul::tests::test_matmul_with_beta::<TestRuntime>(1.0);
+            }
+
+            #[test]
+            pub fn with_beta_zero() {
+                $crate::matmul::tests::test_matmul_with_beta::<TestRuntime>(0.0);
+            }
+
+            #[test]
+            pub fn broadcast_batch_rank() {
+                $crate::matmul::tests::test_matmul_broadcast_batch_rank::<TestRuntime>();
+            }
+
+            #[test]
+            pub fn with_bias() {
+                $crate::matmul::tests::test_matmul_with_bias::<TestRuntime>(
+                    $crate::matmul::components::MatrixLayout::RowMajor,
+                );
+            }
+
+            #[test]
+            pub fn with_bias_col_major_out() {
+                $crate::matmul::tests::test_matmul_with_bias::<TestRuntime>(
+                    $crate::matmul::components::MatrixLayout::ColMajor,
+                );
+            }
+
+            #[test]
+            pub fn epilogue_relu() {
+                $crate::matmul::tests::test_matmul_with_epilogue::<TestRuntime>(
+                    $crate::matmul::components::global::EpilogueOp::Relu,
+                );
+            }
+
+            #[test]
+            pub fn epilogue_gelu() {
+                $crate::matmul::tests::test_matmul_with_epilogue::<TestRuntime>(
+                    $crate::matmul::components::global::EpilogueOp::Gelu,
+                );
+            }
+
+            #[test]
+            pub fn epilogue_gelu_tanh() {
+                $crate::matmul::tests::test_matmul_with_epilogue::<TestRuntime>(
+                    $crate::matmul::components::global::EpilogueOp::GeluTanh,
+                );
+            }
+
+            #[test]
+            pub fn epilogue_sigmoid() {
+                $crate::matmul::tests::test_matmul_with_epilogue::<TestRuntime>(
+                    $crate::matmul::components::global::EpilogueOp::Sigmoid,
+                );
+            }
+
+            #[test]
+            pub fn autotune_cache_hit() {
+                $crate::matmul::tests::test_autotune_cache_hit::<TestRuntime>();
+            }
+
+            #[test]
+            pub fn strided_output() {
+                $crate::matmul::tests::test_matmul_strided_output::<TestRuntime>();
+            }
+
+            #[test]
+            #[ignore = "Runs timed benchmarks of every strategy"]
+            pub fn bench_matmul() {
+                $crate::matmul::tests::test_bench_matmul::<TestRuntime>();
+            }
+
+            #[test]
+            pub fn compile_only() {
+                $crate::matmul::tests::test_compile_only::<TestRuntime>();
+            }
+
+            #[test]
+            pub fn unload_order() {
+                $crate::matmul::tests::test_unload_order::<TestRuntime>();
+            }
+
+            #[test]
+            pub fn unload_masked() {
+                $crate::matmul::tests::test_unload_masked::<TestRuntime>();
+            }
+
+            #[test]
+            pub fn cube_count_exceeds_device_limits() {
+                $crate::matmul::tests::test_cube_count_exceeds_device_limits::<TestRuntime>();
+            }
+
+            #[test]
+            pub fn launch_ref_exceeds_device_limits() {
+                $crate::matmul::tests::test_launch_ref_exceeds_device_limits::<TestRuntime>();
+            }
+
+            #[test]
+            pub fn incompatible_line_size_rejected() {
+                $crate::matmul::tests::test_incompatible_line_size_rejected();
+            }
+
+            #[test]
+            pub fn aliased_strides_rejected() {
+                $crate::matmul::tests::test_aliased_strides_rejected();
+            }
+
+            #[test]
+            pub fn launch_from_raw_parts() {
+                $crate::matmul::tests::test_launch_from_raw_parts::<TestRuntime>();
+            }
+
+            #[test]
+            pub fn unsupported_tile_size_rejected() {
+                $crate::matmul::tests::test_unsupported_tile_size_rejected();
+            }
+
+            #[test]
+            pub fn zero_unroll_rejected() {
+                $crate::matmul::tests::test_zero_unroll_rejected();
+            }
+
+            #[test]
+            pub fn problem_transposed_round_trip() {
+                $crate::matmul::tests::test_problem_transposed_round_trip();
+            }
+
+            #[test]
+            pub fn problem_flops_and_bytes_moved() {
+                $crate::matmul::tests::test_problem_flops_and_bytes_moved();
+            }
+
+            mod mixed {
+                use super::*;
+                use $crate::matmul::components::{MatmulSize, MatrixLayout};
+                use $crate::matmul::kernels::matmul::standard::StandardAlgorithm;
+
+                #[test]
+                pub fn lhs_f16_rhs_bf16() {
+                    $crate::matmul::tests::test_algo_mixed::<
+                        StandardAlgorithm<TMM>,
+                        half::f16,
+                        half::bf16,
+                        f32,
+                        half::f16,
+                        TestRuntime,
+                    >(
+                        (MatrixLayout::RowMajor, MatrixLayout::ColMajor),
+                        MatmulSize { m: 16, n: 16, k: 16 },
+                        MatmulSize { m: 2, n: 2, k: 2 },
+                        MatmulSize { m: 64, n: 64, k: 64 },
+                    );
+                }
+            }
+
+            mod ranged {
+                use super::*;
+                use $crate::matmul::components::{MatmulSize, MatrixLayout};
+                use $crate::matmul::kernels::matmul::standard::StandardAlgorithm;
+                use $crate::matmul::tests::Distribution;
+
+                #[test]
+                pub fn f16_near_max() {
+                    // Sums of up to 32 products below 45 * 45 stay under the f16 max of 65504.
+                    $crate::matmul::tests::test_algo_ranged::<
+                        StandardAlgorithm<TMM>,
+                        (half::f16, half::f16),
+                        TestRuntime,
+                    >(
+                        (MatrixLayout::RowMajor, MatrixLayout::ColMajor),
+                        MatmulSize { m: 16, n: 16, k: 16 },
+                        MatmulSize { m: 2, n: 2, k: 2 },
+                        MatmulSize { m: 32, n: 32, k: 32 },
+                        (0.0, 45.0),
+                        Distribution::Uniform,
+                    );
+                }
+            }
+
+            mod integer {
+                use super::*;
+                use $crate::matmul::components::{MatmulSize, MatrixLayout};
+                use $crate::matmul::kernels::matmul::standard::StandardAlgorithm;
+
+                #[test]
+                pub fn row_major() {
+                    $crate::matmul::tests::test_algo_integer::<StandardAlgorithm<TMM>, TestRuntime>(
+                        (MatrixLayout::RowMajor, MatrixLayout::RowMajor),
+                        MatmulSize { m: 16, n: 16, k: 16 },
+                        MatmulSize { m: 1, n: 1, k: 1 },
+                        MatmulSize { m: 16, n: 16, k: 16 },
+                    );
+                }
+
+                #[test]
+                pub fn row_col_multi_stage() {
+                    $crate::matmul::tests::test_algo_integer::<StandardAlgorithm<TMM>, TestRuntime>(
+                        (MatrixLayout::RowMajor, MatrixLayout::ColMajor),
+                        MatmulSize { m: 16, n: 16, k: 16 },
+                        MatmulSize { m: 2, n: 2, k: 2 },
+                        MatmulSize { m: 64, n: 32, k: 96 },
+                    );
+                }
+            }
+
+            mod sliced {
+                use super::*;
+                use $crate::matmul::components::MatmulSize;
+                use $crate::matmul::kernels::matmul::standard::StandardAlgorithm;
+
+                #[test]
+                pub fn offset_blocks() {
+                    $crate::matmul::tests::test_algo_sliced::<
+                        StandardAlgorithm<TMM>,
+                        (f32, half::f16),
+                        TestRuntime,
+                    >(
+                        MatmulSize { m: 16, n: 16, k: 16 },
+                        MatmulSize { m: 2, n: 2, k: 2 },
+                        MatmulSize { m: 64, n: 32, k: 64 },
+                        MatmulSize { m: 8, n: 32, k: 16 },
+                    );
+                }
+            }
+
+            mod padded {
+                use super::*;
+                use $crate::matmul::components::{MatmulSize, MatrixLayout};
+                use $crate::matmul::kernels::matmul::standard::StandardAlgorithm;
+
+                #[test]
+                pub fn vectorized_rows() {
+                    $crate::matmul::tests::test_algo_padded::<
+                        StandardAlgorithm<TMM>,
+                        (f32, half::f16),
+                        TestRuntime,
+                    >(
+                        (MatrixLayout::RowMajor, MatrixLayout::ColMajor),
+                        MatmulSize { m: 16, n: 16, k: 16 },
+                        MatmulSize { m: 2, n: 2, k: 2 },
+                        MatmulSize { m: 64, n: 64, k: 64 },
+                        4,
+                    );
+                }
+
+                #[test]
+                pub fn unaligned_rows() {
+                    $crate::matmul::tests::test_algo_padded::<
+                        StandardAlgorithm<TMM>,
+                        (f32, half::f16),
+                        TestRuntime,
+                    >(
+                        (MatrixLayout::ColMajor, MatrixLayout::RowMajor),
+                        MatmulSize { m: 16, n: 16, k: 16 },
+                        MatmulSize { m: 2, n: 2, k: 2 },
+                        MatmulSize { m: 64, n: 64, k: 64 },
+                        1,
+                    );
+                }
+            }
+
+            mod strided {
+                use super::*;
+                use $crate::matmul::components::{MatmulSize, MatrixLayout};
+                use $crate::matmul::kernels::matmul::standard::StandardAlgorithm;
+
+                #[test]
+                pub fn every_other_column() {
+                    $crate::matmul::tests::test_algo::<
+                        StandardAlgorithm<TMM>,
+                        (f32, half::f16),
+                        TestRuntime,
+                    >(
+                        (
+                            MatrixLayout::Strided {
+                                row_stride: 128,
+                                col_stride: 2,
+                            },
+                            MatrixLayout::ColMajor,
+                        ),
+                        MatmulSize { m: 16, n: 16, k: 16 },
+                        MatmulSize { m: 2, n: 2, k: 2 },
+                        MatmulSize { m: 64, n: 64, k: 64 },
+                    );
+                }
+
+                #[test]
+                pub fn every_other_row() {
+                    $crate::matmul::tests::test_algo::<
+                        StandardAlgorithm<TMM>,
+                        (f32, half::f16),
+                        TestRuntime,
+                    >(
+                        (
+                            MatrixLayout::RowMajor,
+                            MatrixLayout::Strided {
+                                row_stride: 2,
+                                col_stride: 128,
+                            },
+                        ),
+                        MatmulSize { m: 16, n: 16, k: 16 },
+                        MatmulSize { m: 2, n: 2, k: 2 },
+                        MatmulSize { m: 64, n: 64, k: 64 },
+                    );
+                }
+            }
+
+            mod masked {
+                use super::*;
+                use $crate::matmul::components::batch::TransposedDispatch;
+                use $crate::matmul::components::global::full_load::MaskedCyclicLoading;
+                use $crate::matmul::components::{MatmulSize, MatrixLayout};
+                use $crate::matmul::kernels::matmul::standard::StandardAlgorithm;
+
+                #[test]
+                pub fn p17x33x65_row_col() {
+                    $crate::matmul::tests::test_algo::<
+                        StandardAlgorithm<TMM, TransposedDispatch, MaskedCyclicLoading>,
+                        (f32, half::f16),
+                        TestRuntime,
+                    >(
+                        (MatrixLayout::RowMajor, MatrixLayout::ColMajor),
+                        MatmulSize { m: 16, n: 16, k: 16 },
+                        MatmulSize { m: 2, n: 2, k: 2 },
+                        MatmulSize { m: 17, n: 33, k: 65 },
+                    );
+                }
+
+                #[test]
+                pub fn p17x33x65_col_row() {
+                    $crate::matmul::tests::test_algo::<
+                        StandardAlgorithm<TMM, TransposedDispatch, MaskedCyclicLoading>,
+                        (f32, half::f16),
+                        TestRuntime,
+                    >(
+                        (MatrixLayout::ColMajor, MatrixLayout::RowMajor),
+                        MatmulSize { m: 16, n: 16, k: 16 },
+                        MatmulSize { m: 2, n: 2, k: 2 },
+                        MatmulSize { m: 17, n: 33, k: 65 },
+                    );
+                }
+            }
+
+            mod flattened {
+                use super::*;
+                use $crate::matmul::components::batch::FlattenedDispatch;
+                use $crate::matmul::components::{MatmulSize, MatrixLayout};
+                use $crate::matmul::kernels::matmul::standard::StandardAlgorithm;
+
+                #[test]
+                pub fn batches_exceeding_grid_z() {
+                    // One cube per batch, more than the 65535 cubes allowed along z.
+                    $crate::matmul::tests::test_algo_batched::<
+                        StandardAlgorithm<TMM, FlattenedDispatch>,
+                        (f32, half::f16),
+                        TestRuntime,
+                    >(
+                        (MatrixLayout::RowMajor, MatrixLayout::RowMajor),
+                        MatmulSize { m: 16, n: 16, k: 16 },
+                        MatmulSize { m: 1, n: 1, k: 1 },
+                        MatmulSize { m: 16, n: 16, k: 16 },
+                        65536,
+                    );
+                }
+            }
+
+            mod transposed_load {
+                use super::*;
+                use $crate::matmul::components::{MatmulSize, MatrixLayout};
+                use $crate::matmul::kernels::matmul::standard::StandardTransposedLoadAlgorithm;
+
+                /// The column-major inputs are laid out column-major on the device, and
+                /// transposed into the stage by the loaders.
+                fn run_algo(layouts: (MatrixLayout, MatrixLayout)) {
+                    $crate::matmul::tests::test_algo::<
+                        StandardTransposedLoadAlgorithm<TMM>,
+                        (f32, half::f16),
+                        TestRuntime,
+                    >(
+                        layouts,
+                        MatmulSize { m: 16, n: 16, k: 16 },
+                        MatmulSize { m: 2, n: 2, k: 2 },
+                        MatmulSize { m: 64, n: 32, k: 96 },
+                    );
+                }
+
+                #[test]
+                pub fn col_row() {
+                    run_algo((MatrixLayout::ColMajor, MatrixLayout::RowMajor));
+                }
+
+                #[test]
+                pub fn row_col() {
+                    run_algo((MatrixLayout::RowMajor, MatrixLayout::ColMajor));
+                }
+
+                #[test]
+                pub fn col_col() {
+                    run_algo((MatrixLayout::ColMajor, MatrixLayout::ColMajor));
+                }
+            }
+
+            mod unrolled {
+                use super::*;
+                use $crate::matmul::components::{MatmulSelection, MatmulSize, MatrixLayout};
+                use $crate::matmul::kernels::matmul::standard::StandardUnrolledAlgorithm;
+                use $crate::matmul::kernels::matmul::Algorithm;
+
+                /// The stage has 4 tiles along k, so only a factor of 4 unrolls the loop fully.
+                fn run_algo<A: Algorithm<Selection = MatmulSelection>>() {
+                    $crate::matmul::tests::test_algo::<A, (f32, half::f16), TestRuntime>(
+                        (MatrixLayout::RowMajor, MatrixLayout::ColMajor),
+                        MatmulSize { m: 16, n: 16, k: 16 },
+                        MatmulSize { m: 2, n: 2, k: 4 },
+                        MatmulSize { m: 64, n: 64, k: 192 },
+                    );
+                }
+
+                #[test]
+                pub fn factor_1() {
+                    run_algo::<StandardUnrolledAlgorithm<TMM, 1>>();
+                }
+
+                #[test]
+                pub fn factor_2() {
+                    run_algo::<StandardUnrolledAlgorithm<TMM, 2>>();
+                }
+
+                #[test]
+                pub fn factor_3() {
+                    // Not a divisor of the number of tiles.
+                    run_algo::<StandardUnrolledAlgorithm<TMM, 3>>();
+                }
+
+                #[test]
+                pub fn factor_4() {
+                    run_algo::<StandardUnrolledAlgorithm<TMM, 4>>();
+                }
+            }
+
+            mod asymmetric {
+                use super::*;
+                use $crate::matmul::components::{MatmulSize, MatrixLayout};
+                use $crate::matmul::kernels::matmul::standard::StandardAlgorithm;
+
+                type A = StandardAlgorithm<TMM>;
+                const LAYOUTS: (MatrixLayout, MatrixLayout) =
+                    (MatrixLayout::RowMajor, MatrixLayout::ColMajor);
+
+                #[test]
+                pub fn stage_64x32x16_square_tile() {
+                    $crate::matmul::tests::test_algo::<A, (f32, half::f16), TestRuntime>(
+                        LAYOUTS,
+                        MatmulSize { m: 16, n: 16, k: 16 },
+                        MatmulSize { m: 4, n: 2, k: 1 },
+                        MatmulSize { m: 128, n: 64, k: 64 },
+                    );
+                }
+
+                #[test]
+                pub fn stage_64x32x16_tall_tile() {
+                    $crate::matmul::tests::test_algo::<A, (f32, half::f16), TestRuntime>(
+                        LAYOUTS,
+                        MatmulSize { m: 32, n: 8, k: 16 },
+                        MatmulSize { m: 2, n: 4, k: 1 },
+                        MatmulSize { m: 128, n: 64, k: 64 },
+                    );
+                }
+
+                #[test]
+                pub fn stage_32x64x16_wide_tile() {
+                    $crate::matmul::tests::test_algo::<A, (f32, half::f16), TestRuntime>(
+                        LAYOUTS,
+                        MatmulSize { m: 8, n: 32, k: 16 },
+                        MatmulSize { m: 4, n: 2, k: 1 },
+                        MatmulSize { m: 64, n: 128, k: 64 },
+                    );
+                }
+
+                #[test]
+                pub fn stage_64x32x32_two_tiles_along_k() {
+                    $crate::matmul::tests::test_algo::<A, (f32, half::f16), TestRuntime>(
+                        LAYOUTS,
+                        MatmulSize { m: 16, n: 16, k: 16 },
+                        MatmulSize { m: 4, n: 2, k: 2 },
+                        MatmulSize { m: 64, n: 96, k: 128 },
+                    );
+                }
+            }
         }
     };
 }
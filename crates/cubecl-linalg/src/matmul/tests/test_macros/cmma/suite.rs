@@ -1,21 +1,219 @@
 use std::fmt::Display;
 
+use crate::matmul::components::batch::{FlattenedDispatch, NaturalDispatch};
+use crate::matmul::components::global::args::TensorInputsLaunch;
+use crate::matmul::components::global::{EpilogueOp, UnloadOrder};
 use crate::matmul::components::stage::CommonStageInput;
+use crate::matmul::components::tile::accelerated::Accelerated;
+use crate::matmul::components::tile::plane::PlaneMma;
 use crate::matmul::components::tile::TileMatmulFamily;
-use crate::matmul::components::{MatmulProblem, MatrixLayout};
+use crate::matmul::components::{Ident, MatmulProblem, MatrixLayout, SingleMatmulSpec};
 use crate::matmul::components::{MatmulSelection, MatmulSize};
-use crate::matmul::kernels::matmul::Algorithm;
-use crate::matmul::tests::cmma_matmul::matmul_test_launcher::test_matmul_algorithm;
+use crate::matmul::kernels::matmul::specialized::SpecializedDoubleBufferedAlgorithm;
+use crate::matmul::kernels::matmul::standard::{StandardAlgorithm, StandardUnrolledAlgorithm};
+use crate::matmul::kernels::matmul::{self, Algorithm};
+use crate::matmul::kernels::{MatmulAvailabilityError, MatmulInvalidProblem, MatmulLaunchError};
+use crate::matmul::tests::cmma_matmul::matmul_test_launcher::{
+    device_plane_dim, make_config, random_data, stage_input, strides, test_matmul_algorithm,
+    test_matmul_algorithm_integer, test_matmul_algorithm_sliced, test_selection, unload_tile,
+    unload_tile_masked, TestData,
+};
 use crate::matmul::tests::test_utils::CastInto;
+use crate::matmul::tests::test_utils::Distribution;
+use crate::matmul::tests::test_utils::RoundingMode;
+use crate::matmul::tests::test_utils::{
+    assert_equals_approx, epilogue_cpu_reference, generate_random_data_ranged,
+    matmul_cpu_reference, shape, transpose, EpsilonPolicy, MatmulAccuracy,
+};
+use crate::matmul::MatmulReport;
+use crate::tensor::TensorHandle;
 
-use cubecl_core::prelude::Float;
+use cubecl_core::prelude::{ComputeClient, CubeCount, Float, TensorArg};
+use cubecl_core::tf32;
 use cubecl_core::{CubeElement, Runtime};
+use cubecl_runtime::server::Handle;
 
 pub fn test_algo<A: Algorithm<Selection = MatmulSelection>, P: TestPrecision, R: Runtime>(
     layouts: (MatrixLayout, MatrixLayout),
     tile: MatmulSize,
     stage: MatmulSize,
     problem: MatmulSize,
+) {
+    test_algo_mixed::<A, P::EG, P::EG, P::EG, P::ES, R>(layouts, tile, stage, problem);
+}
+
+/// Same as [test_algo], with lhs and rhs of different element types.
+pub fn test_algo_mixed<A, EL, ER, EG, ES, R>(
+    layouts: (MatrixLayout, MatrixLayout),
+    tile: MatmulSize,
+    stage: MatmulSize,
+    problem: MatmulSize,
+) where
+    A: Algorithm<Selection = MatmulSelection>,
+    EL: Float + CubeElement + Display + CastInto<ES>,
+    ER: Float + CubeElement + Display + CastInto<ES>,
+    EG: Float + CubeElement + Display,
+    ES: Float + CubeElement + Display + CastInto<EG>,
+    R: Runtime,
+{
+    test_algo_strided::<A, EL, ER, EG, ES, R>(
+        layouts,
+        MatrixLayout::RowMajor,
+        tile,
+        stage,
+        problem,
+        0,
+        2,
+        TestData::from_env(),
+    );
+}
+
+/// Same as [test_algo], with an output of `out_layout` instead of a row-major one.
+pub fn test_algo_out_layout<
+    A: Algorithm<Selection = MatmulSelection>,
+    P: TestPrecision,
+    R: Runtime,
+>(
+    layouts: (MatrixLayout, MatrixLayout),
+    out_layout: MatrixLayout,
+    tile: MatmulSize,
+    stage: MatmulSize,
+    problem: MatmulSize,
+) {
+    test_algo_strided::<A, P::EG, P::EG, P::EG, P::ES, R>(
+        layouts,
+        out_layout,
+        tile,
+        stage,
+        problem,
+        0,
+        2,
+        TestData::from_env(),
+    );
+}
+
+/// Same as [test_algo], with `num_batches` batches instead of 2.
+pub fn test_algo_batched<
+    A: Algorithm<Selection = MatmulSelection>,
+    P: TestPrecision,
+    R: Runtime,
+>(
+    layouts: (MatrixLayout, MatrixLayout),
+    tile: MatmulSize,
+    stage: MatmulSize,
+    problem: MatmulSize,
+    num_batches: usize,
+) {
+    test_algo_strided::<A, P::EG, P::EG, P::EG, P::ES, R>(
+        layouts,
+        MatrixLayout::RowMajor,
+        tile,
+        stage,
+        problem,
+        0,
+        num_batches,
+        TestData::from_env(),
+    );
+}
+
+/// Same as [test_algo], with every row of the inputs followed by `padding` unused elements,
+/// so the inputs are not contiguous.
+pub fn test_algo_padded<A: Algorithm<Selection = MatmulSelection>, P: TestPrecision, R: Runtime>(
+    layouts: (MatrixLayout, MatrixLayout),
+    tile: MatmulSize,
+    stage: MatmulSize,
+    problem: MatmulSize,
+    padding: usize,
+) {
+    test_algo_strided::<A, P::EG, P::EG, P::EG, P::ES, R>(
+        layouts,
+        MatrixLayout::RowMajor,
+        tile,
+        stage,
+        problem,
+        padding,
+        2,
+        TestData::from_env(),
+    );
+}
+
+/// Same as [test_algo], with inputs drawn from `dist` in `[min, max)` instead of `[-1, 1)`.
+pub fn test_algo_ranged<A: Algorithm<Selection = MatmulSelection>, P: TestPrecision, R: Runtime>(
+    layouts: (MatrixLayout, MatrixLayout),
+    tile: MatmulSize,
+    stage: MatmulSize,
+    problem: MatmulSize,
+    (min, max): (f32, f32),
+    dist: Distribution,
+) {
+    test_algo_strided::<A, P::EG, P::EG, P::EG, P::ES, R>(
+        layouts,
+        MatrixLayout::RowMajor,
+        tile,
+        stage,
+        problem,
+        0,
+        2,
+        TestData::Ranged {
+            min,
+            max,
+            dist,
+            rounding: RoundingMode::NearestEven,
+        },
+    );
+}
+
+/// Same as [test_algo], with `i8` inputs multiplied and accumulated in `i32`, whose output is
+/// compared exactly.
+pub fn test_algo_integer<A: Algorithm<Selection = MatmulSelection>, R: Runtime>(
+    layouts: (MatrixLayout, MatrixLayout),
+    tile: MatmulSize,
+    stage: MatmulSize,
+    problem: MatmulSize,
+) {
+    let client = R::client(&Default::default());
+    let plane_dim = match client
+        .properties()
+        .hardware_properties()
+        .defined_plane_size()
+    {
+        Some(val) => val,
+        None => {
+            println!("Can't run test without a fixed plane size.");
+            return;
+        }
+    };
+
+    let problem = MatmulProblem {
+        m: problem.m as usize,
+        n: problem.n as usize,
+        k: problem.k as usize,
+        batches: (vec![2], vec![2]),
+        lhs_layout: layouts.0,
+        rhs_layout: layouts.1,
+        ..Default::default()
+    };
+
+    let selection = MatmulSelection {
+        tile,
+        num_stagess: stage,
+        plane_dim,
+    };
+    let config_input = CommonStageInput {
+        tile: A::TileMatmul::input(selection.tile),
+        num_stages: selection.num_stagess,
+    };
+
+    test_matmul_algorithm_integer::<A, R>(client, problem, config_input, selection);
+}
+
+/// Same as [test_algo], on row-major blocks of `problem` size starting at `start` (m, n, k) in
+/// larger tensors, selected with [VirtualTensor::slice_2d](crate::tensor::VirtualTensor::slice_2d).
+pub fn test_algo_sliced<A: Algorithm<Selection = MatmulSelection>, P: TestPrecision, R: Runtime>(
+    tile: MatmulSize,
+    stage: MatmulSize,
+    problem: MatmulSize,
+    start: MatmulSize,
 ) {
     let client = R::client(&Default::default());
     let plane_dim = match client
@@ -35,11 +233,67 @@ pub fn test_algo<A: Algorithm<Selection = MatmulSelection>, P: TestPrecision, R:
         n: problem.n as usize,
         k: problem.k as usize,
         batches: (vec![2], vec![2]),
+        ..Default::default()
+    };
+
+    let selection = MatmulSelection {
+        tile,
+        num_stagess: stage,
+        plane_dim,
+    };
+    let config_input = CommonStageInput {
+        tile: A::TileMatmul::input(selection.tile),
+        num_stages: selection.num_stagess,
+    };
+
+    test_matmul_algorithm_sliced::<A, P::EG, P::ES, R>(
+        client,
+        problem,
+        (start.m as usize, start.n as usize, start.k as usize),
+        config_input,
+        selection,
+    );
+}
+
+fn test_algo_strided<A, EL, ER, EG, ES, R>(
+    layouts: (MatrixLayout, MatrixLayout),
+    out_layout: MatrixLayout,
+    tile: MatmulSize,
+    stage: MatmulSize,
+    problem: MatmulSize,
+    padding: usize,
+    num_batches: usize,
+    data: TestData,
+) where
+    A: Algorithm<Selection = MatmulSelection>,
+    EL: Float + CubeElement + Display + CastInto<ES>,
+    ER: Float + CubeElement + Display + CastInto<ES>,
+    EG: Float + CubeElement + Display,
+    ES: Float + CubeElement + Display + CastInto<EG>,
+    R: Runtime,
+{
+    let client = R::client(&Default::default());
+    let plane_dim = match client
+        .properties()
+        .hardware_properties()
+        .defined_plane_size()
+    {
+        Some(val) => val,
+        None => {
+            println!("Can't run test without a fixed plane size.");
+            return;
+        }
+    };
+
+    let problem = MatmulProblem {
+        m: problem.m as usize,
+        n: problem.n as usize,
+        k: problem.k as usize,
+        batches: (vec![num_batches], vec![num_batches]),
         lhs_layout: layouts.0,
         rhs_layout: layouts.1,
-        lhs_line_size: 1, // Will be changed
-        rhs_line_size: 1, // Will be changed
-        out_line_size: 1, // Will be changed
+        out_layout,
+        ..Default::default()
     };
 
     let selection = MatmulSelection {
@@ -52,7 +306,1382 @@ pub fn test_algo<A: Algorithm<Selection = MatmulSelection>, P: TestPrecision, R:
         num_stages: selection.num_stagess,
     };
 
-    test_matmul_algorithm::<A, P::EG, P::ES, R>(client, problem, config_input, selection);
+    test_matmul_algorithm::<A, EL, ER, EG, ES, R>(
+        client,
+        problem,
+        config_input,
+        selection,
+        padding,
+        data,
+    );
+}
+
+/// Check that a plane dimension different from the one of the device is rejected.
+pub fn test_plane_dim_mismatch<R: Runtime>() {
+    let client = R::client(&Default::default());
+    let Some(device) = device_plane_dim::<R>(&client) else {
+        return;
+    };
+    let selected = if device == 32 { 64 } else { 32 };
+
+    assert!(matmul::check_plane_dim::<R>(&client, device).is_ok());
+    match matmul::check_plane_dim::<R>(&client, selected) {
+        Err(MatmulAvailabilityError::PlaneDimMismatch {
+            selected: s,
+            device: d,
+        }) => assert_eq!((s, d), (selected, device)),
+        other => panic!("Expected a plane dimension mismatch, got {other:?}"),
+    }
+}
+
+/// Check that the matmuls copying asynchronously are reported unavailable on clients without
+/// [pipelines](cubecl_core::Feature::Pipeline), instead of failing to compile.
+pub fn test_pipeline_availability<R: Runtime>() {
+    type A = SpecializedDoubleBufferedAlgorithm<Accelerated>;
+
+    let client = R::client(&Default::default());
+    let Some(plane_dim) = device_plane_dim::<R>(&client) else {
+        return;
+    };
+
+    let selection = test_selection(plane_dim);
+    let problem = MatmulProblem {
+        m: 64,
+        n: 64,
+        k: 64,
+        batches: (vec![2], vec![2]),
+        ..Default::default()
+    };
+    let config = make_config::<A>(&selection, &problem).unwrap();
+
+    let availability = A::check_availability::<R, (f32, half::f16, f32)>(&client, &config);
+    match client
+        .properties()
+        .feature_enabled(cubecl_core::Feature::Pipeline)
+    {
+        true => assert!(
+            !matches!(
+                availability,
+                Err(MatmulAvailabilityError::PipelineUnavailable)
+            ),
+            "Pipelines are supported by the client"
+        ),
+        false => assert!(
+            matches!(
+                availability,
+                Err(MatmulAvailabilityError::PipelineUnavailable)
+            ),
+            "Expected pipelines to be unavailable, got {availability:?}"
+        ),
+    }
+}
+
+/// Check that the CUDA C++ of a matmul copying asynchronously can be compiled without
+/// launching it, and that its tiles are copied with `cuda::memcpy_async`.
+pub fn test_compile_only<R: Runtime>() {
+    type A = SpecializedDoubleBufferedAlgorithm<Accelerated>;
+
+    if R::name() != "cuda" {
+        println!("Can't run test without the CUDA C++ compiler.");
+        return;
+    }
+
+    let client = R::client(&Default::default());
+    let Some(plane_dim) = device_plane_dim::<R>(&client) else {
+        return;
+    };
+
+    let selection = test_selection(plane_dim);
+    let problem = MatmulProblem {
+        m: 64,
+        n: 64,
+        k: 64,
+        batches: (vec![2], vec![2]),
+        ..Default::default()
+    };
+
+    // The tensors are never read nor written, so they don't need to be initialized.
+    let [lhs, rhs, out] = [Ident::Lhs, Ident::Rhs, Ident::Out].map(|ident| {
+        let shape = shape(&problem, ident);
+        let strides = strides(&problem, ident);
+        let handle = client.empty(shape.iter().product::<usize>() * core::mem::size_of::<f32>());
+        (handle, shape, strides)
+    });
+    let (lhs_arg, rhs_arg, out_arg) = unsafe {
+        (
+            TensorArg::<R>::from_raw_parts::<f32>(&lhs.0, &lhs.2, &lhs.1, 1),
+            TensorArg::<R>::from_raw_parts::<f32>(&rhs.0, &rhs.2, &rhs.1, 1),
+            TensorArg::<R>::from_raw_parts::<f32>(&out.0, &out.2, &out.1, 1),
+        )
+    };
+
+    let source = match matmul::compile_only::<SingleMatmulSpec<f32, half::f16, f32>, R, A>(
+        &client,
+        TensorInputsLaunch::new(lhs_arg, rhs_arg),
+        out_arg,
+        problem,
+        stage_input::<A>(&selection),
+        selection,
+    ) {
+        Ok(source) => source,
+        Err(MatmulLaunchError::Unavailable(err)) => {
+            println!("Skipped - {err:?}");
+            return;
+        }
+        Err(err) => panic!("{err:?}"),
+    };
+
+    assert!(
+        source.contains("cuda::memcpy_async("),
+        "Expected asynchronous copies in the source:\n{source}"
+    );
+}
+
+/// Check that tiles written in row-major and column-major orders give the same output, for
+/// outputs written element by element and with lines.
+pub fn test_unload_order<R: Runtime>() {
+    let client = R::client(&Default::default());
+    let Some(plane_dim) = device_plane_dim::<R>(&client) else {
+        return;
+    };
+
+    let selection = MatmulSelection {
+        num_stagess: MatmulSize { m: 1, n: 1, k: 1 },
+        ..test_selection(plane_dim)
+    };
+    let tile: Vec<f32> = (0..16 * 16).map(|i| i as f32).collect();
+
+    for line_size in [1, 4] {
+        for order in [UnloadOrder::RowMajor, UnloadOrder::ColMajor] {
+            let out = unload_tile::<StandardAlgorithm<PlaneMma>, R>(
+                &client, selection, line_size, order, &tile,
+            );
+            assert_eq!(out, tile, "{order:?} order with line size {line_size}");
+        }
+    }
+}
+
+/// Check that a [masked write](crate::matmul::components::stage::StageWriter::write_masked)
+/// to an output smaller than the tile only writes the lines inside the output, even though the
+/// config doesn't check bounds.
+pub fn test_unload_masked<R: Runtime>() {
+    let client = R::client(&Default::default());
+    let Some(plane_dim) = device_plane_dim::<R>(&client) else {
+        return;
+    };
+
+    let selection = MatmulSelection {
+        num_stagess: MatmulSize { m: 1, n: 1, k: 1 },
+        ..test_selection(plane_dim)
+    };
+    let tile: Vec<f32> = (0..16 * 16).map(|i| i as f32).collect();
+
+    for (line_size, shape) in [(1, (13, 11)), (4, (13, 12)), (4, (5, 16)), (1, (16, 3))] {
+        let out = unload_tile_masked::<StandardAlgorithm<PlaneMma>, R>(
+            &client, selection, line_size, &tile, shape,
+        );
+
+        for (i, value) in out.iter().enumerate() {
+            let (row, col) = (i / 16, i % 16);
+            let expected = match row < shape.0 && col < shape.1 {
+                true => tile[i],
+                false => -1.0,
+            };
+            assert_eq!(
+                *value, expected,
+                "Output of shape {shape:?} with line size {line_size}, at row {row} col {col}"
+            );
+        }
+    }
+}
+
+/// Check that [MatmulProblem::transposed] describes the inputs in the same memory, and gives back
+/// the original problem when applied twice.
+pub fn test_problem_transposed_round_trip() {
+    let padded = MatrixLayout::Strided {
+        row_stride: 1,
+        col_stride: 40,
+    };
+    let problem = MatmulProblem {
+        m: 32,
+        n: 48,
+        k: 16,
+        batches: (vec![3, 1], vec![1, 2]),
+        rhs_layout: MatrixLayout::ColMajor,
+        lhs_line_size: 4,
+        rhs_line_size: 2,
+        ..Default::default()
+    };
+
+    for problem in [problem.clone(), problem.with_layout(Ident::Rhs, padded)] {
+        let transposed = problem.transposed();
+        assert_eq!(transposed.transposed(), problem);
+
+        for (ident, original) in [(Ident::Lhs, Ident::Rhs), (Ident::Rhs, Ident::Lhs)] {
+            let swap_last = |mut dims: Vec<usize>| {
+                let rank = dims.len();
+                dims.swap(rank - 2, rank - 1);
+                dims
+            };
+            assert_eq!(
+                shape(&transposed, ident),
+                swap_last(shape(&problem, original))
+            );
+            assert_eq!(
+                strides(&transposed, ident),
+                swap_last(strides(&problem, original))
+            );
+        }
+        assert_eq!(
+            shape(&transposed, Ident::Out),
+            vec![3, 2, problem.n, problem.m]
+        );
+    }
+}
+
+/// Check [MatmulProblem::flops] and [MatmulProblem::bytes_moved] on shapes computed by hand,
+/// including broadcasted batches and an output accumulated with beta.
+pub fn test_problem_flops_and_bytes_moved() {
+    let problem = MatmulProblem {
+        m: 64,
+        n: 32,
+        k: 16,
+        batches: (vec![1], vec![1]),
+        ..Default::default()
+    };
+    assert_eq!(problem.flops(), 2 * 64 * 32 * 16);
+    assert_eq!(problem.bytes_moved(4), 4 * (64 * 16 + 16 * 32 + 64 * 32));
+
+    // The lhs is read once for the 6 batches of the output.
+    let broadcasted = MatmulProblem {
+        batches: (vec![1, 3], vec![2, 3]),
+        ..problem.clone()
+    };
+    assert_eq!(broadcasted.flops(), 6 * 2 * 64 * 32 * 16);
+    assert_eq!(
+        broadcasted.bytes_moved(2),
+        2 * (3 * 64 * 16 + 6 * 16 * 32 + 6 * 64 * 32)
+    );
+
+    let accumulated = MatmulProblem {
+        beta: 0.5,
+        ..problem.clone()
+    };
+    assert_eq!(accumulated.flops(), problem.flops());
+    assert_eq!(
+        accumulated.bytes_moved(4),
+        problem.bytes_moved(4) + 4 * 64 * 32
+    );
+
+    let square = MatmulProblem {
+        m: 4096,
+        n: 4096,
+        k: 4096,
+        ..problem
+    };
+    assert_eq!(square.flops(), 137_438_953_472);
+}
+
+/// Check that a line size not dividing the dimension it is aligned with is reported as an
+/// [IncompatibleLineSize](MatmulLaunchError::IncompatibleLineSize) naming the tensor, when
+/// making the config.
+pub fn test_incompatible_line_size_rejected() {
+    type A = StandardAlgorithm<Accelerated>;
+
+    let selection = test_selection(32);
+    // Row-major lhs with rows of 17 elements, read with lines of 4.
+    let problem = MatmulProblem {
+        m: 64,
+        n: 64,
+        k: 17,
+        batches: (vec![2], vec![2]),
+        lhs_line_size: 4,
+        ..Default::default()
+    };
+
+    match make_config::<A>(&selection, &problem) {
+        Err(MatmulLaunchError::IncompatibleLineSize {
+            ident: Ident::Lhs,
+            dim: 17,
+            line_size: 4,
+        }) => {}
+        Err(err) => panic!("Expected an incompatible lhs line size, got {err:?}"),
+        Ok(_) => panic!("Expected the lhs line size to be rejected"),
+    }
+}
+
+/// Check that a problem so wide that its cubes along n don't fit on the y axis of the grid is
+/// rejected as [ExceedsDeviceLimits](MatmulLaunchError::ExceedsDeviceLimits), and that the
+/// [FlattenedDispatch] folds it into a grid that fits the device.
+///
+/// Only the cube count is computed, so no tensor of that size is allocated.
+pub fn test_cube_count_exceeds_device_limits<R: Runtime>() {
+    let selection = test_selection(32);
+    let client = R::client(&Default::default());
+    let max_cube_count = client.properties().hardware_properties().max_cube_count;
+    let n_stage = selection.tile.n * selection.num_stagess.n;
+    let (max_x, max_y, _) = max_cube_count;
+    // The cube count is computed in u32, so n must leave room for the ceiling division.
+    let n = max_y
+        .checked_add(1)
+        .and_then(|cubes| cubes.checked_mul(n_stage))
+        .filter(|n| n.checked_add(n_stage).is_some());
+    let Some(n) = n else {
+        println!("Can't run test when the grid can't be exceeded along y.");
+        return;
+    };
+    let problem = MatmulProblem {
+        m: 32,
+        n: n as usize,
+        k: 32,
+        batches: (vec![1], vec![1]),
+        ..Default::default()
+    };
+
+    type Natural = StandardAlgorithm<PlaneMma, NaturalDispatch>;
+    let cube_count = Natural::cube_count(&selection, &problem);
+    match matmul::check_cube_count::<R>(&client, &cube_count) {
+        Err(MatmulLaunchError::ExceedsDeviceLimits { cube_count, max }) => {
+            assert_eq!(cube_count, (1, max_y + 1, 1));
+            assert_eq!(max, max_cube_count);
+        }
+        other => panic!("Expected the cube count to exceed the device limits, got {other:?}"),
+    }
+
+    type Flattened = StandardAlgorithm<PlaneMma, FlattenedDispatch>;
+    let cube_count = Flattened::cube_count(&selection, &problem);
+    assert!(matmul::check_cube_count::<R>(&client, &cube_count).is_ok());
+    match cube_count {
+        CubeCount::Static(x, y, 1) => {
+            assert!(x <= FlattenedDispatch::FLATTENED_MAX_X.min(max_x));
+            assert!(x as u64 * y as u64 >= max_y as u64 + 1);
+        }
+        cube_count => panic!("Expected a static cube count folded in 2D, got {cube_count:?}"),
+    }
+}
+
+/// Check that [launch_ref](crate::matmul::launch_ref) rejects a problem with more batches than
+/// cubes allowed along z as [ExceedsDeviceLimits](MatmulLaunchError::ExceedsDeviceLimits),
+/// before anything is launched.
+///
+/// The matrices hold a single element, so the tensors stay small despite the number of batches.
+pub fn test_launch_ref_exceeds_device_limits<R: Runtime>() {
+    let client = R::client(&Default::default());
+    let max_cube_count = client.properties().hardware_properties().max_cube_count;
+    let Some(batches) = max_cube_count
+        .2
+        .checked_add(1)
+        .filter(|batches| *batches <= 1 << 24)
+    else {
+        println!("Can't run test when the grid can't be exceeded along z.");
+        return;
+    };
+    let shape = vec![batches as usize, 1, 1];
+
+    let lhs = TensorHandle::<R, f32>::zeros(&client, shape.clone());
+    let rhs = TensorHandle::<R, f32>::zeros(&client, shape.clone());
+    let out = TensorHandle::<R, f32>::empty(&client, shape);
+
+    match crate::matmul::launch_ref::<R, f32>(
+        &crate::matmul::Strategy::Standard,
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &out.as_ref(),
+    ) {
+        Err(MatmulLaunchError::ExceedsDeviceLimits { cube_count, max }) => {
+            assert_eq!(cube_count.2, batches);
+            assert_eq!(max, max_cube_count);
+        }
+        Err(MatmulLaunchError::Unavailable(_)) => {
+            println!("Can't run test without the standard matmul.");
+        }
+        other => panic!("Expected the cube count to exceed the device limits, got {other:?}"),
+    }
+}
+
+/// Check that strides mapping different elements of a tile to the same address are rejected
+/// when making the config.
+pub fn test_aliased_strides_rejected() {
+    type A = StandardAlgorithm<Accelerated>;
+
+    let selection = test_selection(32);
+    // Rows of 16 elements, but only 8 elements apart.
+    let problem = MatmulProblem {
+        m: 64,
+        n: 64,
+        k: 64,
+        batches: (vec![2], vec![2]),
+        lhs_layout: MatrixLayout::Strided {
+            row_stride: 8,
+            col_stride: 1,
+        },
+        ..Default::default()
+    };
+
+    match make_config::<A>(&selection, &problem) {
+        Err(MatmulLaunchError::InvalidProblem(MatmulInvalidProblem::AliasedStrides {
+            ident: Ident::Lhs,
+            rows: 16,
+            cols: 16,
+        })) => {}
+        Err(err) => panic!("Expected aliased lhs strides, got {err:?}"),
+        Ok(_) => panic!("Expected aliased lhs strides to be rejected"),
+    }
+}
+
+/// Check that a tile size cmma instructions don't have is rejected when making the config, while
+/// the asymmetric ones are accepted for a stage that isn't square either.
+pub fn test_unsupported_tile_size_rejected() {
+    type A = StandardAlgorithm<Accelerated>;
+
+    let problem = MatmulProblem {
+        m: 128,
+        n: 64,
+        k: 64,
+        batches: (vec![1], vec![1]),
+        rhs_layout: MatrixLayout::ColMajor,
+        ..Default::default()
+    };
+    let make_config = |tile: MatmulSize, num_stagess: MatmulSize| {
+        let selection = MatmulSelection {
+            tile,
+            num_stagess,
+            plane_dim: 32,
+        };
+        make_config::<A>(&selection, &problem)
+    };
+
+    for tile in Accelerated::TILE_SIZES {
+        let num_stagess = MatmulSize {
+            m: 64 / tile.m,
+            n: 32 / tile.n,
+            k: 1,
+        };
+        if let Err(err) = make_config(tile, num_stagess) {
+            panic!("Expected tile {tile:?} to be accepted, got {err:?}");
+        }
+    }
+
+    match make_config(
+        MatmulSize { m: 16, n: 8, k: 16 },
+        MatmulSize { m: 4, n: 4, k: 1 },
+    ) {
+        Err(MatmulLaunchError::InvalidConfig(err)) => {
+            assert!(err.to_string().contains("16x8x16"), "{err}")
+        }
+        Err(err) => panic!("Expected an invalid config, got {err:?}"),
+        Ok(_) => panic!("Expected the 16x8x16 tile to be rejected"),
+    }
+}
+
+/// Check that a stage unrolled by a factor of zero is rejected when making the config.
+pub fn test_zero_unroll_rejected() {
+    type A = StandardUnrolledAlgorithm<Accelerated, 0>;
+
+    let problem = MatmulProblem {
+        m: 64,
+        n: 64,
+        k: 64,
+        batches: (vec![2], vec![2]),
+        ..Default::default()
+    };
+
+    match make_config::<A>(&test_selection(32), &problem) {
+        Err(MatmulLaunchError::InvalidConfig(err)) => {
+            assert!(err.to_string().contains("unrolled"), "{err}")
+        }
+        Err(err) => panic!("Expected an invalid config, got {err:?}"),
+        Ok(_) => panic!("Expected an unroll factor of zero to be rejected"),
+    }
+}
+
+/// Check that the checked launch rejects handles that don't describe a valid matmul.
+pub fn test_launch_checked_rejects_invalid_handles<R: Runtime>() {
+    let client = R::client(&Default::default());
+    let tensor = |shape: Vec<usize>, num_elems: usize| {
+        let handle = client.empty(num_elems * core::mem::size_of::<f32>());
+        TensorHandle::<R, f32>::new_contiguous(shape, handle)
+    };
+    let launch =
+        |lhs: &TensorHandle<R, f32>, rhs: &TensorHandle<R, f32>, out: &TensorHandle<R, f32>| {
+            matmul::launch_ref_checked::<R, f32, matmul::StandardSelector<Accelerated>>(
+                &client,
+                &lhs.as_ref(),
+                &rhs.as_ref(),
+                &out.as_ref(),
+            )
+        };
+
+    let lhs = tensor(vec![2, 8, 16], 2 * 8 * 16);
+    let rhs = tensor(vec![2, 16, 8], 2 * 16 * 8);
+
+    let rhs_other_k = tensor(vec![2, 12, 8], 2 * 12 * 8);
+    let out = tensor(vec![2, 8, 8], 2 * 8 * 8);
+    assert!(matches!(
+        launch(&lhs, &rhs_other_k, &out),
+        Err(MatmulLaunchError::InvalidProblem(
+            MatmulInvalidProblem::MismatchedK { lhs: 16, rhs: 12 }
+        ))
+    ));
+
+    let out_other_n = tensor(vec![2, 8, 4], 2 * 8 * 4);
+    assert!(matches!(
+        launch(&lhs, &rhs, &out_other_n),
+        Err(MatmulLaunchError::InvalidProblem(
+            MatmulInvalidProblem::InvalidOutShape { .. }
+        ))
+    ));
+
+    let out_too_small = tensor(vec![2, 8, 8], 2 * 8 * 8 - 1);
+    assert!(matches!(
+        launch(&lhs, &rhs, &out_too_small),
+        Err(MatmulLaunchError::InvalidProblem(
+            MatmulInvalidProblem::OutOfBounds {
+                ident: Ident::Out,
+                ..
+            }
+        ))
+    ));
+}
+
+/// Check that [launch_from_raw_parts](matmul::launch_from_raw_parts) computes the matmul of
+/// tensors given by pointers to their handles, shapes and strides along with their line sizes,
+/// with a transposed rhs, and that line sizes not matching the shapes or strides are rejected.
+pub fn test_launch_from_raw_parts<R: Runtime>() {
+    let client = R::client(&Default::default());
+    let problem = MatmulProblem {
+        m: 64,
+        n: 32,
+        k: 48,
+        batches: (vec![2], vec![2]),
+        rhs_layout: MatrixLayout::ColMajor,
+        lhs_line_size: 4,
+        rhs_line_size: 4,
+        out_line_size: 4,
+        ..Default::default()
+    };
+
+    let input = |ident: Ident, seed: u64| {
+        let data = random_data(&problem, ident, seed, 1.0);
+        (client.create(f32::as_bytes(&data)), data)
+    };
+    let (lhs, lhs_data) = input(Ident::Lhs, 1234);
+    let (rhs, rhs_data) = input(Ident::Rhs, 5678);
+    let out_shape = shape(&problem, Ident::Out);
+    let out = client.empty(out_shape.iter().product::<usize>() * core::mem::size_of::<f32>());
+
+    let (lhs_shape, lhs_strides) = (shape(&problem, Ident::Lhs), strides(&problem, Ident::Lhs));
+    let (rhs_shape, rhs_strides) = (shape(&problem, Ident::Rhs), strides(&problem, Ident::Rhs));
+    let out_strides = strides(&problem, Ident::Out);
+    let raw =
+        |handle: &Handle, shape: &[usize], strides: &[usize], line_size: u8| matmul::RawTensor {
+            handle,
+            shape: shape.as_ptr(),
+            strides: strides.as_ptr(),
+            rank: shape.len(),
+            line_size,
+        };
+    let launch = |lhs_strides: &[usize], lhs_line_size: u8| unsafe {
+        matmul::launch_from_raw_parts::<R, f32, matmul::StandardSelector<Accelerated>>(
+            &client,
+            raw(&lhs, &lhs_shape, lhs_strides, lhs_line_size),
+            raw(&rhs, &rhs_shape, &rhs_strides, problem.rhs_line_size),
+            raw(&out, &out_shape, &out_strides, problem.out_line_size),
+        )
+    };
+
+    match launch(&lhs_strides, problem.lhs_line_size) {
+        Ok(_) => {}
+        Err(MatmulLaunchError::Unavailable(_)) => {
+            println!("Can't run test without the standard matmul.");
+            return;
+        }
+        Err(err) => panic!("{err:?}"),
+    }
+
+    let expected = matmul_cpu_reference::<f32, f32>(&lhs_data, &rhs_data, &problem);
+    // The f32 inputs are multiplied as tf32 on tensor cores.
+    if let Err(err) = assert_equals_approx::<R, f32>(
+        &client,
+        out.clone(),
+        &expected,
+        EpsilonPolicy::Auto(MatmulAccuracy::new::<tf32, f32, R>(
+            &client, None, true, problem.k,
+        )),
+        problem.m,
+        problem.n,
+    ) {
+        panic!("{err}");
+    }
+
+    // No runtime reads f32 with lines of 32 or 3 elements.
+    for line_size in [32, 3] {
+        assert!(matches!(
+            launch(&lhs_strides, line_size),
+            Err(MatmulLaunchError::InvalidProblem(
+                MatmulInvalidProblem::UnsupportedLineSize {
+                    ident: Ident::Lhs,
+                    ..
+                }
+            ))
+        ));
+    }
+
+    // Batches starting two elements before the end of the previous one aren't aligned on lines.
+    let unaligned_strides = [problem.m * problem.k - 2, problem.k, 1];
+    assert!(matches!(
+        launch(&unaligned_strides, problem.lhs_line_size),
+        Err(MatmulLaunchError::InvalidProblem(
+            MatmulInvalidProblem::UnalignedStrides { line_size: 4, .. }
+        ))
+    ));
+}
+
+/// Check that [launch_ref_with_report](crate::matmul::launch_ref_with_report) reports the
+/// selection and cube sizes the standard selector computes for the problem.
+pub fn test_launch_report<R: Runtime>() {
+    type A = StandardAlgorithm<Accelerated>;
+
+    let client = R::client(&Default::default());
+    let tensor = |shape: Vec<usize>| {
+        let handle = client.empty(shape.iter().product::<usize>() * core::mem::size_of::<f32>());
+        TensorHandle::<R, f32>::new_contiguous(shape, handle)
+    };
+
+    let lhs = tensor(vec![2, 64, 32]);
+    let rhs = tensor(vec![2, 32, 48]);
+    let out = tensor(vec![2, 64, 48]);
+
+    let report = match crate::matmul::launch_ref_with_report::<R, f32>(
+        &crate::matmul::Strategy::Standard,
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &out.as_ref(),
+    ) {
+        Ok(report) => report,
+        Err(MatmulLaunchError::Unavailable(_)) => {
+            println!("Can't run test without the standard matmul.");
+            return;
+        }
+        Err(err) => panic!("{err:?}"),
+    };
+
+    let problem = MatmulProblem {
+        m: 64,
+        n: 48,
+        k: 32,
+        batches: (vec![2], vec![2]),
+        ..Default::default()
+    };
+    let plane_dim = matmul::device_plane_dim::<R>(&client).unwrap();
+    // Accelerated tiles multiply f32 inputs as tf32.
+    let selection =
+        matmul::matmul_selection::<Accelerated, SingleMatmulSpec<f32, cubecl_core::tf32, f32>, R>(
+            &client, &problem, plane_dim,
+        );
+
+    assert!(matches!(report.strategy, crate::matmul::Strategy::Standard));
+    assert_eq!(report.selection, Some(selection));
+    assert_eq!(report.cube_dim, Some(A::cube_dim(&selection)));
+    match (report.cube_count, A::cube_count(&selection, &problem)) {
+        (Some(CubeCount::Static(x, y, z)), CubeCount::Static(x_, y_, z_)) => {
+            assert_eq!((x, y, z), (x_, y_, z_))
+        }
+        (actual, expected) => panic!("Expected cube count {expected:?}, got {actual:?}"),
+    }
+}
+
+/// Check that prefetching the stages through a pipeline gives exactly the output of the
+/// [FullLoadMatmul](crate::matmul::components::global::full_load::FullLoadMatmul) it is based on.
+///
+/// k spans many stages and isn't a multiple of the stage size nor of the pipeline depth, so the
+/// last stages in flight are partial or empty.
+pub fn test_prefetching_matches_full_load<R: Runtime>() {
+    let client = R::client(&Default::default());
+    if !client
+        .properties()
+        .feature_enabled(cubecl_core::Feature::Pipeline)
+    {
+        println!("Can't run test without pipelines.");
+        return;
+    }
+
+    let problem = MatmulProblem {
+        m: 64,
+        n: 64,
+        k: 1000,
+        batches: (vec![2], vec![2]),
+        ..Default::default()
+    };
+
+    let input = |ident: Ident, seed: u64| {
+        let data = random_data(&problem, ident, seed, 1.0);
+        TensorHandle::<R, f32>::from_data(&client, &data, shape(&problem, ident))
+    };
+    let lhs = input(Ident::Lhs, 1234);
+    let rhs = input(Ident::Rhs, 5678);
+
+    let launch = |strategy: crate::matmul::Strategy| {
+        let out_shape = shape(&problem, Ident::Out);
+        let out_size = out_shape.iter().product::<usize>() * core::mem::size_of::<f32>();
+        let out = TensorHandle::<R, f32>::new_contiguous(out_shape, client.empty(out_size));
+
+        match crate::matmul::launch_ref::<R, f32>(
+            &strategy,
+            &client,
+            &lhs.as_ref(),
+            &rhs.as_ref(),
+            &out.as_ref(),
+        ) {
+            Ok(()) => Some(out.to_vec(&client)),
+            Err(MatmulLaunchError::Unavailable(_)) => None,
+            Err(err) => panic!("{err:?}"),
+        }
+    };
+
+    let (full_load, prefetching) = match (
+        launch(crate::matmul::Strategy::Standard),
+        launch(crate::matmul::Strategy::StandardPrefetching { depth: 2 }),
+    ) {
+        (Some(full_load), Some(prefetching)) => (full_load, prefetching),
+        _ => {
+            println!("Can't run test without the standard matmul.");
+            return;
+        }
+    };
+
+    assert_eq!(prefetching, full_load, "Prefetching changed the output");
+}
+
+/// Check that assigning the stages of the output to the cubes with a
+/// [SwizzleTransposedDispatch](crate::matmul::components::batch::SwizzleTransposedDispatch) of
+/// width `W` gives exactly the output of the unswizzled standard matmul.
+///
+/// The problem spans several stages along m and n, so cubes are actually reordered.
+pub fn test_swizzled_dispatch_matches_unswizzled<const W: u32, R: Runtime>() {
+    let client = R::client(&Default::default());
+    let problem = MatmulProblem {
+        m: 512,
+        n: 640,
+        k: 64,
+        batches: (vec![2], vec![2]),
+        ..Default::default()
+    };
+
+    let input = |ident: Ident, seed: u64| {
+        let data = random_data(&problem, ident, seed, 1.0);
+        TensorHandle::<R, f32>::from_data(&client, &data, shape(&problem, ident))
+    };
+    let lhs = input(Ident::Lhs, 1234);
+    let rhs = input(Ident::Rhs, 5678);
+
+    let launch = |launch: &dyn Fn(&TensorHandle<R, f32>) -> Result<(), MatmulLaunchError>| {
+        let out_shape = shape(&problem, Ident::Out);
+        let out_size = out_shape.iter().product::<usize>() * core::mem::size_of::<f32>();
+        let out = TensorHandle::<R, f32>::new_contiguous(out_shape, client.empty(out_size));
+
+        match launch(&out) {
+            Ok(()) => Some(out.to_vec(&client)),
+            Err(MatmulLaunchError::Unavailable(_)) => None,
+            Err(err) => panic!("{err:?}"),
+        }
+    };
+
+    let (unswizzled, swizzled) = match (
+        launch(&|out| {
+            matmul::launch_ref::<R, f32, matmul::StandardSelector<Accelerated>>(
+                &client,
+                &lhs.as_ref(),
+                &rhs.as_ref(),
+                &out.as_ref(),
+            )
+        }),
+        launch(&|out| {
+            matmul::launch_ref::<R, f32, matmul::StandardSwizzledSelector<Accelerated, W>>(
+                &client,
+                &lhs.as_ref(),
+                &rhs.as_ref(),
+                &out.as_ref(),
+            )
+        }),
+    ) {
+        (Some(unswizzled), Some(swizzled)) => (unswizzled, swizzled),
+        _ => {
+            println!("Can't run test without the standard matmul.");
+            return;
+        }
+    };
+
+    assert_eq!(
+        swizzled, unswizzled,
+        "Swizzling the cubes changed the output"
+    );
+}
+
+/// Check that the same f32 matmul is less precise when accumulated in f16 than in f32, using
+/// [launch_ref_with_accumulator](crate::matmul::launch_ref_with_accumulator).
+///
+/// The inputs are positive, so the sums grow with k and the rounding of an f16 accumulator
+/// shows. Plane tiles are used, since accelerated tiles can't accumulate tf32 products in f16.
+pub fn test_accumulator_precision<R: Runtime>() {
+    let client = R::client(&Default::default());
+    let problem = MatmulProblem {
+        m: 32,
+        n: 32,
+        k: 256,
+        batches: (vec![1], vec![1]),
+        ..Default::default()
+    };
+
+    let input = |ident: Ident, seed: u64| {
+        let shape = shape(&problem, ident);
+        let data = generate_random_data_ranged::<f32>(
+            shape.iter().product(),
+            seed,
+            0.0,
+            1.0,
+            Distribution::Uniform,
+            RoundingMode::NearestEven,
+        );
+        let handle = TensorHandle::<R, f32>::from_data(&client, &data, shape);
+        (data, handle)
+    };
+    let (lhs_data, lhs) = input(Ident::Lhs, 1234);
+    let (rhs_data, rhs) = input(Ident::Rhs, 5678);
+    let expected = matmul_cpu_reference::<f32, f32>(&lhs_data, &rhs_data, &problem);
+
+    // Largest error relative to the expected values, which are all around k / 4.
+    let max_error =
+        |launch: &dyn Fn(&TensorHandle<R, f32>) -> Result<MatmulReport, MatmulLaunchError>| {
+            let out_shape = shape(&problem, Ident::Out);
+            let out_size = out_shape.iter().product::<usize>() * core::mem::size_of::<f32>();
+            let out = TensorHandle::<R, f32>::new_contiguous(out_shape, client.empty(out_size));
+
+            match launch(&out) {
+                Ok(_) => {}
+                Err(MatmulLaunchError::Unavailable(_)) => return None,
+                Err(err) => panic!("{err:?}"),
+            }
+
+            let error = out
+                .to_vec(&client)
+                .iter()
+                .zip(&expected)
+                .map(|(actual, expected)| ((actual - expected) / expected).abs())
+                .fold(0.0, f32::max);
+            Some(error)
+        };
+    let strategy = crate::matmul::Strategy::PlaneMma;
+
+    let f32_error = max_error(&|out| {
+        crate::matmul::launch_ref_with_accumulator::<R, f32, f32>(
+            &strategy,
+            &client,
+            &lhs.as_ref(),
+            &rhs.as_ref(),
+            &out.as_ref(),
+        )
+    });
+    let f16_error = max_error(&|out| {
+        crate::matmul::launch_ref_with_accumulator::<R, f32, half::f16>(
+            &strategy,
+            &client,
+            &lhs.as_ref(),
+            &rhs.as_ref(),
+            &out.as_ref(),
+        )
+    });
+
+    let (f32_error, f16_error) = match (f32_error, f16_error) {
+        (Some(f32_error), Some(f16_error)) => (f32_error, f16_error),
+        _ => {
+            println!("Can't run test without plane matmuls accumulating in f16 and f32.");
+            return;
+        }
+    };
+
+    assert!(f32_error < 1e-4, "f32 accumulator is off by {f32_error}");
+    // An f16 ulp is 2^-10 of the value, and the rounding errors add up over k.
+    assert!(f16_error < 5e-2, "f16 accumulator is off by {f16_error}");
+    assert!(
+        f16_error > f32_error,
+        "f16 accumulator ({f16_error}) should be less precise than f32 ({f32_error})"
+    );
+}
+
+/// Check that an f16 matmul overflowing its f16 accumulator is reported as producing non-finite
+/// values by [assert_equals_approx], rather than as values off by some error.
+///
+/// Every product is `±256 * 256`, just above the largest f16, and their signs alternate along k,
+/// so the exact output is zero while the accumulator becomes infinite or NaN.
+pub fn test_f16_overflow_reports_non_finite<R: Runtime>() {
+    type F = half::f16;
+
+    let client = R::client(&Default::default());
+    let (m, n, k) = (32, 32, 64);
+    let lhs_data: Vec<F> = (0..m * k)
+        .map(|i| F::from_f32(if i % 2 == 0 { 256.0 } else { -256.0 }))
+        .collect();
+    let rhs_data = vec![F::from_f32(256.0); k * n];
+    let lhs = TensorHandle::<R, F>::from_data(&client, &lhs_data, vec![m, k]);
+    let rhs = TensorHandle::<R, F>::from_data(&client, &rhs_data, vec![k, n]);
+    let out = TensorHandle::<R, F>::new_contiguous(
+        vec![m, n],
+        client.empty(m * n * core::mem::size_of::<F>()),
+    );
+
+    match crate::matmul::launch_ref_with_accumulator::<R, F, F>(
+        &crate::matmul::Strategy::PlaneMma,
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &out.as_ref(),
+    ) {
+        Ok(_) => {}
+        Err(MatmulLaunchError::Unavailable(_)) => {
+            println!("Can't run test without plane matmuls accumulating in f16.");
+            return;
+        }
+        Err(err) => panic!("{err:?}"),
+    }
+
+    let expected = vec![F::from_f32(0.0); m * n];
+    match assert_equals_approx::<R, F>(
+        &client,
+        out.handle,
+        &expected,
+        EpsilonPolicy::Auto(Default::default()),
+        m,
+        n,
+    ) {
+        Err(err) => {
+            assert!(err.contains("Actual values are not finite"), "{err}");
+            assert!(!err.contains("differ"), "{err}");
+        }
+        Ok(_) => panic!("Expected the overflowing accumulator to produce non-finite values"),
+    }
+}
+
+/// Random inputs of a problem launched with the standard matmul on plane mma, shared by the
+/// tests of the output.
+struct OutputFixture<R: Runtime> {
+    client: ComputeClient<R::Server, R::Channel>,
+    lhs_data: Vec<f32>,
+    rhs_data: Vec<f32>,
+    lhs: TensorHandle<R, f32>,
+    rhs: TensorHandle<R, f32>,
+}
+
+impl<R: Runtime> OutputFixture<R> {
+    /// A batched problem that isn't a multiple of the stage size, so the output is written with
+    /// bound checks.
+    fn problem() -> MatmulProblem {
+        MatmulProblem {
+            m: 40,
+            n: 24,
+            k: 32,
+            batches: (vec![2], vec![2]),
+            ..Default::default()
+        }
+    }
+
+    /// Uploads inputs in `-range..range` for the given problem, or returns `None` if plane
+    /// operations are not supported.
+    fn new(problem: &MatmulProblem, range: f32) -> Option<Self> {
+        let client = R::client(&Default::default());
+        if !PlaneMma::is_available::<R>(&client) {
+            println!("Skipped - plane operations are not supported!");
+            return None;
+        }
+
+        let lhs_data = random_data(problem, Ident::Lhs, 1234, range);
+        let rhs_data = random_data(problem, Ident::Rhs, 5678, range);
+        let lhs = TensorHandle::from_data(&client, &lhs_data, shape(problem, Ident::Lhs));
+        let rhs = TensorHandle::from_data(&client, &rhs_data, shape(problem, Ident::Rhs));
+
+        Some(Self {
+            client,
+            lhs_data,
+            rhs_data,
+            lhs,
+            rhs,
+        })
+    }
+}
+
+/// Check that [launch_ref_with_beta](matmul::launch_ref_with_beta) accumulates into the existing
+/// output with the standard matmul, against `lhs @ rhs + beta * out` computed on the CPU.
+///
+/// The problem isn't a multiple of the stage size, so the existing output is read with bound
+/// checks. With a beta of zero, the output is filled with NaNs, which would show up if it was read.
+pub fn test_matmul_with_beta<R: Runtime>(beta: f32) {
+    let problem = MatmulProblem {
+        beta,
+        ..OutputFixture::<R>::problem()
+    };
+    let Some(OutputFixture {
+        client,
+        lhs_data,
+        rhs_data,
+        lhs,
+        rhs,
+    }) = OutputFixture::<R>::new(&problem, 1.0)
+    else {
+        return;
+    };
+    let out_data = match beta == 0.0 {
+        true => vec![f32::NAN; shape(&problem, Ident::Out).iter().product()],
+        false => random_data(&problem, Ident::Out, 9012, 1.0),
+    };
+
+    let out = TensorHandle::<R, f32>::from_data(&client, &out_data, shape(&problem, Ident::Out));
+
+    matmul::launch_ref_with_beta::<R, f32, matmul::StandardSelector<PlaneMma>>(
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &out.as_ref(),
+        beta,
+    )
+    .unwrap();
+
+    let expected: Vec<f32> = matmul_cpu_reference::<f32, f32>(&lhs_data, &rhs_data, &problem)
+        .iter()
+        .zip(&out_data)
+        .map(|(product, existing)| match beta == 0.0 {
+            true => *product,
+            false => product + beta * existing,
+        })
+        .collect();
+
+    // NaNs are never out of the tolerance of the comparison, so they are checked first.
+    assert!(
+        out.to_vec(&client).iter().all(|value| !value.is_nan()),
+        "The existing output was read with beta = {beta}"
+    );
+
+    if let Err(e) = assert_equals_approx::<R, f32>(
+        &client,
+        out.handle,
+        &expected,
+        EpsilonPolicy::Auto(MatmulAccuracy::scalar::<f32>(problem.k)),
+        problem.m,
+        problem.n,
+    ) {
+        panic!("{}", e);
+    }
+}
+
+/// Check that an input with fewer batch dimensions than the other is broadcasted over the leading
+/// ones, both when it is the lhs and when it is the rhs.
+pub fn test_matmul_broadcast_batch_rank<R: Runtime>() {
+    for batches in [(vec![3, 2], vec![2]), (vec![1], vec![2, 3, 1])] {
+        let problem = MatmulProblem {
+            batches,
+            ..OutputFixture::<R>::problem()
+        };
+        let Some(OutputFixture {
+            client,
+            lhs_data,
+            rhs_data,
+            lhs,
+            rhs,
+        }) = OutputFixture::<R>::new(&problem, 1.0)
+        else {
+            return;
+        };
+
+        let out = TensorHandle::<R, f32>::empty(&client, shape(&problem, Ident::Out));
+
+        matmul::launch_ref_checked::<R, f32, matmul::StandardSelector<PlaneMma>>(
+            &client,
+            &lhs.as_ref(),
+            &rhs.as_ref(),
+            &out.as_ref(),
+        )
+        .unwrap();
+
+        let expected = matmul_cpu_reference::<f32, f32>(&lhs_data, &rhs_data, &problem);
+        if let Err(e) = assert_equals_approx::<R, f32>(
+            &client,
+            out.handle,
+            &expected,
+            EpsilonPolicy::Auto(MatmulAccuracy::scalar::<f32>(problem.k)),
+            problem.m,
+            problem.n,
+        ) {
+            panic!("Batches {:?}: {}", problem.batches, e);
+        }
+    }
+}
+
+/// Check that [launch_ref_with_bias](matmul::launch_ref_with_bias) adds the bias to every row of
+/// an output with the given layout, against `lhs @ rhs + bias` computed on the CPU.
+///
+/// The problem isn't a multiple of the stage size, so the bias is read with bound checks.
+pub fn test_matmul_with_bias<R: Runtime>(out_layout: MatrixLayout) {
+    let problem = MatmulProblem {
+        out_layout,
+        has_bias: true,
+        ..OutputFixture::<R>::problem()
+    };
+    let Some(OutputFixture {
+        client,
+        lhs_data,
+        rhs_data,
+        lhs,
+        rhs,
+    }) = OutputFixture::<R>::new(&problem, 1.0)
+    else {
+        return;
+    };
+    let bias_data = random_data(&problem, Ident::Bias, 9012, 1.0);
+
+    let bias = TensorHandle::<R, f32>::from_data(&client, &bias_data, shape(&problem, Ident::Bias));
+    let out = TensorHandle::<R, f32>::new(
+        shape(&problem, Ident::Out),
+        strides(&problem, Ident::Out),
+        client.empty(problem.num_batches() * problem.m * problem.n * core::mem::size_of::<f32>()),
+    );
+
+    matmul::launch_ref_with_bias::<R, f32, matmul::StandardSelector<PlaneMma>>(
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &bias.as_ref(),
+        &out.as_ref(),
+    )
+    .unwrap();
+
+    let expected: Vec<f32> = matmul_cpu_reference::<f32, f32>(&lhs_data, &rhs_data, &problem)
+        .iter()
+        .enumerate()
+        .map(|(i, product)| product + bias_data[i % problem.n])
+        .collect();
+    let (expected, rows, cols) = match out_layout {
+        MatrixLayout::ColMajor => (
+            transpose(&expected, problem.num_batches(), problem.m, problem.n),
+            problem.n,
+            problem.m,
+        ),
+        _ => (expected, problem.m, problem.n),
+    };
+
+    if let Err(e) = assert_equals_approx::<R, f32>(
+        &client,
+        out.handle,
+        &expected,
+        EpsilonPolicy::Auto(MatmulAccuracy::scalar::<f32>(problem.k)),
+        rows,
+        cols,
+    ) {
+        panic!("{}", e);
+    }
+}
+
+/// Check that the epilogue is applied to the output of the standard matmul, against the CPU
+/// reference.
+pub fn test_matmul_with_epilogue<R: Runtime>(epilogue: EpilogueOp) {
+    let problem = MatmulProblem {
+        epilogue,
+        ..OutputFixture::<R>::problem()
+    };
+    // Small inputs keep the products around the range where the activations are not linear.
+    let Some(OutputFixture {
+        client,
+        lhs_data,
+        rhs_data,
+        lhs,
+        rhs,
+    }) = OutputFixture::<R>::new(&problem, 0.5)
+    else {
+        return;
+    };
+
+    let out = TensorHandle::<R, f32>::empty(&client, shape(&problem, Ident::Out));
+
+    matmul::launch_ref_with_epilogue::<R, f32, matmul::StandardSelector<PlaneMma>>(
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &out.as_ref(),
+        epilogue,
+    )
+    .unwrap();
+
+    let expected: Vec<f32> = matmul_cpu_reference::<f32, f32>(&lhs_data, &rhs_data, &problem)
+        .into_iter()
+        .map(|product| epilogue_cpu_reference(product, epilogue))
+        .collect();
+
+    if let Err(e) = assert_equals_approx::<R, f32>(
+        &client,
+        out.handle,
+        &expected,
+        EpsilonPolicy::Auto(MatmulAccuracy::scalar::<f32>(problem.k)),
+        problem.m,
+        problem.n,
+    ) {
+        panic!("{}", e);
+    }
+}
+
+/// Check that [Strategy::Autotune](crate::matmul::Strategy::Autotune) only benchmarks the
+/// candidates the first time a problem is seen, and launches the cached one afterwards.
+pub fn test_autotune_cache_hit<R: Runtime>() {
+    let client = R::client(&Default::default());
+
+    // An unusual shape, so the problem isn't tuned by another test.
+    let problem = MatmulProblem {
+        m: 37,
+        n: 19,
+        k: 23,
+        batches: (vec![3], vec![3]),
+        ..Default::default()
+    };
+
+    let lhs_data = random_data(&problem, Ident::Lhs, 1234, 1.0);
+    let rhs_data = random_data(&problem, Ident::Rhs, 5678, 1.0);
+
+    let lhs = TensorHandle::<R, f32>::from_data(&client, &lhs_data, shape(&problem, Ident::Lhs));
+    let rhs = TensorHandle::<R, f32>::from_data(&client, &rhs_data, shape(&problem, Ident::Rhs));
+    let out = TensorHandle::<R, f32>::empty(&client, shape(&problem, Ident::Out));
+
+    let launch = || {
+        crate::matmul::launch_ref::<R, f32>(
+            &crate::matmul::Strategy::Autotune,
+            &client,
+            &lhs.as_ref(),
+            &rhs.as_ref(),
+            &out.as_ref(),
+        )
+        .unwrap()
+    };
+
+    // Start from an empty cache, so the first launch has to tune the problem.
+    crate::matmul::clear_autotune_cache();
+    let num_tunings = || crate::matmul::tune::num_tunings(&lhs, &rhs, &out);
+    let before = num_tunings();
+
+    launch();
+    assert_eq!(
+        num_tunings(),
+        before + 1,
+        "The first launch should tune once"
+    );
+    launch();
+    assert_eq!(
+        num_tunings(),
+        before + 1,
+        "The second launch should hit the cache"
+    );
+
+    let expected = matmul_cpu_reference::<f32, f32>(&lhs_data, &rhs_data, &problem);
+    if let Err(e) = assert_equals_approx::<R, f32>(
+        &client,
+        out.handle,
+        &expected,
+        EpsilonPolicy::Auto(MatmulAccuracy::scalar::<f32>(problem.k)),
+        problem.m,
+        problem.n,
+    ) {
+        panic!("{}", e);
+    }
+}
+
+/// Check that the standard matmul writes into an over-strided output, whose rows are padded and
+/// whose batches are separated by a padding row, like a slice of an interleaved buffer.
+///
+/// The padding is filled with a sentinel, which must be left untouched.
+pub fn test_matmul_strided_output<R: Runtime>() {
+    let problem = OutputFixture::<R>::problem();
+    let Some(OutputFixture {
+        client,
+        lhs_data,
+        rhs_data,
+        lhs,
+        rhs,
+    }) = OutputFixture::<R>::new(&problem, 1.0)
+    else {
+        return;
+    };
+    let sentinel = -7.0;
+    let row_stride = problem.n + 8;
+    let batch_stride = (problem.m + 1) * row_stride;
+    let num_batches = problem.num_batches();
+
+    let buffer = client.create(f32::as_bytes(&vec![sentinel; num_batches * batch_stride]));
+    let out = TensorHandle::<R, f32>::new(
+        shape(&problem, Ident::Out),
+        vec![batch_stride, row_stride, 1],
+        buffer,
+    );
+
+    matmul::launch_ref::<R, f32, matmul::StandardSelector<PlaneMma>>(
+        &client,
+        &lhs.as_ref(),
+        &rhs.as_ref(),
+        &out.as_ref(),
+    )
+    .unwrap();
+
+    let product = matmul_cpu_reference::<f32, f32>(&lhs_data, &rhs_data, &problem);
+    let mut expected = vec![sentinel; num_batches * batch_stride];
+    for (i, value) in product.iter().enumerate() {
+        let (batch, row, col) = (
+            i / (problem.m * problem.n),
+            i / problem.n % problem.m,
+            i % problem.n,
+        );
+        expected[batch * batch_stride + row * row_stride + col] = *value;
+    }
+
+    if let Err(e) = assert_equals_approx::<R, f32>(
+        &client,
+        out.handle,
+        &expected,
+        EpsilonPolicy::Auto(MatmulAccuracy::scalar::<f32>(problem.k)),
+        problem.m + 1,
+        row_stride,
+    ) {
+        panic!("{}", e);
+    }
+}
+
+/// Check that [bench_matmul](crate::matmul::tests::bench::bench_matmul) reports consistent
+/// timings for the strategies that can run, and always for the simple one.
+pub fn test_bench_matmul<R: Runtime>() {
+    use crate::matmul::tests::bench::{bench_matmul, MatmulBenchShape};
+    use cubecl_core::benchmark::TimingMethod;
+
+    let shape = MatmulBenchShape {
+        b: 2,
+        m: 64,
+        n: 64,
+        k: 64,
+    };
+    let results = bench_matmul::<R, f32>(
+        &Default::default(),
+        &[
+            crate::matmul::Strategy::Simple,
+            crate::matmul::Strategy::Standard,
+        ],
+        &[shape],
+        TimingMethod::Full,
+    );
+
+    assert_eq!(shape.flops(), 2 * 2 * 64 * 64 * 64);
+    assert!(results.iter().any(|result| result.strategy == "Simple"));
+    for result in results {
+        assert_eq!(result.shape, shape);
+        assert_eq!(result.timing_method, "full");
+        assert!(result.num_samples > 0);
+        assert!(result.min_secs <= result.median_secs && result.median_secs <= result.max_secs);
+        assert!(result.gflops > 0.0, "{result:?}");
+        assert!(result.bandwidth_gbps > 0.0, "{result:?}");
+    }
 }
 
 #[allow(missing_docs)]
@@ -88,93 +1717,278 @@ macro_rules! matmul_standard_tests {
                 $crate::matmul_standard_tests!(ColMajor, ColMajor);
             }
         }
+
+        mod col_major_out {
+            use super::*;
+
+            mod row_major {
+                use super::*;
+
+                mod row_major {
+                    use super::*;
+                    $crate::matmul_standard_tests!(RowMajor, RowMajor, ColMajor);
+                }
+
+                mod col_major {
+                    use super::*;
+                    $crate::matmul_standard_tests!(RowMajor, ColMajor, ColMajor);
+                }
+            }
+
+            mod col_major {
+                use super::*;
+
+                mod row_major {
+                    use super::*;
+                    $crate::matmul_standard_tests!(ColMajor, RowMajor, ColMajor);
+                }
+
+                mod col_major {
+                    use super::*;
+                    $crate::matmul_standard_tests!(ColMajor, ColMajor, ColMajor);
+                }
+            }
+        }
     };
 
     ($lhs_layout:ident, $rhs_layout:ident) => {
+        $crate::matmul_standard_tests!($lhs_layout, $rhs_layout, RowMajor);
+    };
+
+    ($lhs_layout:ident, $rhs_layout:ident, $out_layout:ident) => {
         mod t16x16x16 {
             use super::*;
-            $crate::matmul_standard_tests!($lhs_layout, $rhs_layout, MatmulSize { m: 16, n: 16, k: 16 });
+            $crate::matmul_standard_tests!(
+                $lhs_layout,
+                $rhs_layout,
+                $out_layout,
+                MatmulSize {
+                    m: 16,
+                    n: 16,
+                    k: 16
+                }
+            );
         }
 
         mod t32x8x16 {
             use super::*;
-            $crate::matmul_standard_tests!($lhs_layout, $rhs_layout, MatmulSize { m: 32, n: 8, k: 16 });
+            $crate::matmul_standard_tests!(
+                $lhs_layout,
+                $rhs_layout,
+                $out_layout,
+                MatmulSize { m: 32, n: 8, k: 16 }
+            );
         }
 
         mod t8x32x16 {
             use super::*;
-            $crate::matmul_standard_tests!($lhs_layout, $rhs_layout, MatmulSize { m: 8, n: 32, k: 16 });
+            $crate::matmul_standard_tests!(
+                $lhs_layout,
+                $rhs_layout,
+                $out_layout,
+                MatmulSize { m: 8, n: 32, k: 16 }
+            );
         }
 
         mod t16x16x8 {
             use super::*;
-            $crate::matmul_standard_tests!($lhs_layout, $rhs_layout, MatmulSize { m: 16, n: 16, k: 8 });
+            $crate::matmul_standard_tests!(
+                $lhs_layout,
+                $rhs_layout,
+                $out_layout,
+                MatmulSize { m: 16, n: 16, k: 8 }
+            );
         }
     };
 
-    ($lhs_layout:ident, $rhs_layout:ident, $tile:expr) => {
+    ($lhs_layout:ident, $rhs_layout:ident, $out_layout:ident, $tile:expr) => {
         mod s1x1x1 {
             use super::*;
-            $crate::matmul_standard_tests!($lhs_layout, $rhs_layout, $tile, MatmulSize { m: 1, n: 1, k: 1 });
+            $crate::matmul_standard_tests!(
+                $lhs_layout,
+                $rhs_layout,
+                $out_layout,
+                $tile,
+                MatmulSize { m: 1, n: 1, k: 1 }
+            );
         }
 
         mod s8x8x1 {
             use super::*;
-            $crate::matmul_standard_tests!($lhs_layout, $rhs_layout, $tile, MatmulSize { m: 8, n: 8, k: 1 });
+            $crate::matmul_standard_tests!(
+                $lhs_layout,
+                $rhs_layout,
+                $out_layout,
+                $tile,
+                MatmulSize { m: 8, n: 8, k: 1 }
+            );
         }
 
         mod s2x2x2 {
             use super::*;
-            $crate::matmul_standard_tests!($lhs_layout, $rhs_layout, $tile, MatmulSize { m: 2, n: 2, k: 2 });
+            $crate::matmul_standard_tests!(
+                $lhs_layout,
+                $rhs_layout,
+                $out_layout,
+                $tile,
+                MatmulSize { m: 2, n: 2, k: 2 }
+            );
         }
 
         mod s4x4x2 {
             use super::*;
-            $crate::matmul_standard_tests!($lhs_layout, $rhs_layout, $tile, MatmulSize { m: 4, n: 4, k: 2 });
+            $crate::matmul_standard_tests!(
+                $lhs_layout,
+                $rhs_layout,
+                $out_layout,
+                $tile,
+                MatmulSize { m: 4, n: 4, k: 2 }
+            );
         }
     };
 
-    ($lhs_layout:ident, $rhs_layout:ident, $tile:expr, $stage:expr) => {
+    ($lhs_layout:ident, $rhs_layout:ident, $out_layout:ident, $tile:expr, $stage:expr) => {
         mod p32x32x32 {
             use super::*;
-            $crate::matmul_standard_tests!($lhs_layout, $rhs_layout, $tile, $stage, MatmulSize { m: 32, n: 32, k: 32 });
+            $crate::matmul_standard_tests!(
+                $lhs_layout,
+                $rhs_layout,
+                $out_layout,
+                $tile,
+                $stage,
+                MatmulSize {
+                    m: 32,
+                    n: 32,
+                    k: 32
+                }
+            );
         }
 
         mod p64x32x32 {
             use super::*;
-            $crate::matmul_standard_tests!($lhs_layout, $rhs_layout, $tile, $stage, MatmulSize { m: 64, n: 32, k: 32 });
+            $crate::matmul_standard_tests!(
+                $lhs_layout,
+                $rhs_layout,
+                $out_layout,
+                $tile,
+                $stage,
+                MatmulSize {
+                    m: 64,
+                    n: 32,
+                    k: 32
+                }
+            );
         }
 
         mod p32x32x64 {
             use super::*;
-            $crate::matmul_standard_tests!($lhs_layout, $rhs_layout, $tile, $stage, MatmulSize { m: 32, n: 32, k: 64 });
+            $crate::matmul_standard_tests!(
+                $lhs_layout,
+                $rhs_layout,
+                $out_layout,
+                $tile,
+                $stage,
+                MatmulSize {
+                    m: 32,
+                    n: 32,
+                    k: 64
+                }
+            );
         }
 
         mod p100x100x100 {
             use super::*;
-            $crate::matmul_standard_tests!($lhs_layout, $rhs_layout, $tile, $stage, MatmulSize { m: 100, n: 100, k: 100 });
+            $crate::matmul_standard_tests!(
+                $lhs_layout,
+                $rhs_layout,
+                $out_layout,
+                $tile,
+                $stage,
+                MatmulSize {
+                    m: 100,
+                    n: 100,
+                    k: 100
+                }
+            );
         }
 
         mod p23x1x17 {
             use super::*;
-            $crate::matmul_standard_tests!($lhs_layout, $rhs_layout, $tile, $stage, MatmulSize { m: 23, n: 1, k: 17 });
+            $crate::matmul_standard_tests!(
+                $lhs_layout,
+                $rhs_layout,
+                $out_layout,
+                $tile,
+                $stage,
+                MatmulSize { m: 23, n: 1, k: 17 }
+            );
         }
 
         mod p256x256x256 {
             use super::*;
-            $crate::matmul_standard_tests!($lhs_layout, $rhs_layout, $tile, $stage, MatmulSize { m: 256, n: 256, k: 256 });
+            $crate::matmul_standard_tests!(
+                $lhs_layout,
+                $rhs_layout,
+                $out_layout,
+                $tile,
+                $stage,
+                MatmulSize {
+                    m: 256,
+                    n: 256,
+                    k: 256
+                }
+            );
         }
     };
 
-    ($lhs_layout:ident, $rhs_layout:ident, $tile:expr, $stage:expr, $problem:expr) => {
-        use $crate::matmul::kernels::matmul::standard::StandardAlgorithm;
-        use $crate::matmul::kernels::matmul::specialized::SpecializedAlgorithm;
+    ($lhs_layout:ident, $rhs_layout:ident, $out_layout:ident, $tile:expr, $stage:expr, $problem:expr) => {
         use $crate::matmul::kernels::matmul::pipelined::PipelinedAlgorithm;
+        use $crate::matmul::kernels::matmul::specialized::{
+            SpecializedAlgorithm, SpecializedDoubleBufferedAlgorithm,
+        };
+        use $crate::matmul::kernels::matmul::standard::{
+            StandardAlgorithm, StandardPrefetchingAlgorithm,
+        };
 
         #[test]
         pub fn standard() {
-            cubecl_linalg::matmul::tests::test_algo::<StandardAlgorithm<TMM>, (EG, ES), TestRuntime>(
+            cubecl_linalg::matmul::tests::test_algo_out_layout::<
+                StandardAlgorithm<TMM>,
+                (EG, ES),
+                TestRuntime,
+            >(
+                (MatrixLayout::$lhs_layout, MatrixLayout::$rhs_layout),
+                MatrixLayout::$out_layout,
+                $tile,
+                $stage,
+                $problem,
+            );
+        }
+
+        #[test]
+        pub fn standard_prefetching() {
+            cubecl_linalg::matmul::tests::test_algo_out_layout::<
+                StandardPrefetchingAlgorithm<TMM>,
+                (EG, ES),
+                TestRuntime,
+            >(
+                (MatrixLayout::$lhs_layout, MatrixLayout::$rhs_layout),
+                MatrixLayout::$out_layout,
+                $tile,
+                $stage,
+                $problem,
+            );
+        }
+
+        #[test]
+        pub fn standard_prefetching_depth_3() {
+            cubecl_linalg::matmul::tests::test_algo_out_layout::<
+                StandardPrefetchingAlgorithm<TMM, 3>,
+                (EG, ES),
+                TestRuntime,
+            >(
                 (MatrixLayout::$lhs_layout, MatrixLayout::$rhs_layout),
+                MatrixLayout::$out_layout,
                 $tile,
                 $stage,
                 $problem,
@@ -183,8 +1997,28 @@ macro_rules! matmul_standard_tests {
 
         #[test]
         pub fn specialized() {
-            cubecl_linalg::matmul::tests::test_algo::<SpecializedAlgorithm<TMM>, (EG, ES), TestRuntime>(
+            cubecl_linalg::matmul::tests::test_algo_out_layout::<
+                SpecializedAlgorithm<TMM>,
+                (EG, ES),
+                TestRuntime,
+            >(
+                (MatrixLayout::$lhs_layout, MatrixLayout::$rhs_layout),
+                MatrixLayout::$out_layout,
+                $tile,
+                $stage,
+                $problem,
+            );
+        }
+
+        #[test]
+        pub fn specialized_double_buffered() {
+            cubecl_linalg::matmul::tests::test_algo_out_layout::<
+                SpecializedDoubleBufferedAlgorithm<TMM>,
+                (EG, ES),
+                TestRuntime,
+            >(
                 (MatrixLayout::$lhs_layout, MatrixLayout::$rhs_layout),
+                MatrixLayout::$out_layout,
                 $tile,
                 $stage,
                 $problem,
@@ -193,8 +2027,13 @@ macro_rules! matmul_standard_tests {
 
         #[test]
         pub fn pipelined() {
-            cubecl_linalg::matmul::tests::test_algo::<PipelinedAlgorithm<TMM>, (EG, ES), TestRuntime>(
+            cubecl_linalg::matmul::tests::test_algo_out_layout::<
+                PipelinedAlgorithm<TMM>,
+                (EG, ES),
+                TestRuntime,
+            >(
                 (MatrixLayout::$lhs_layout, MatrixLayout::$rhs_layout),
+                MatrixLayout::$out_layout,
                 $tile,
                 $stage,
                 $problem,
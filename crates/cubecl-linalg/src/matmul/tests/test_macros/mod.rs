@@ -1,3 +1,5 @@
 pub mod cmma;
+mod reduce;
 mod simple;
+mod split_k;
 mod tiling2d;
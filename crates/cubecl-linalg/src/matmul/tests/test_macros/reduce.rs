@@ -0,0 +1,66 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_matmul_reduce {
+    () => {
+        mod reduce {
+            $crate::testgen_matmul_reduce!(f32);
+        }
+    };
+    ($float:ident) => {
+            use super::*;
+            use cubecl_core::flex32;
+
+            pub type FloatT = $float;
+
+            #[test]
+            pub fn test_vectorized() {
+                cubecl_linalg::matmul::tests::reduce::test_vectorized::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_unaligned() {
+                cubecl_linalg::matmul::tests::reduce::test_unaligned::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_single_partial() {
+                cubecl_linalg::matmul::tests::reduce::test_single_partial::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_permuted_partials() {
+                cubecl_linalg::matmul::tests::reduce::test_permuted_partials::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_unaligned_partial_stride() {
+                cubecl_linalg::matmul::tests::reduce::test_unaligned_partial_stride::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+    };
+    ([$($float:ident),*]) => {
+        mod matmul_reduce {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$float _ty>] {
+                    use super::*;
+
+                    $crate::testgen_matmul_reduce!($float);
+                })*
+            }
+        }
+    };
+}
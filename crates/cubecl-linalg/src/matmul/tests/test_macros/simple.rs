@@ -43,6 +43,77 @@ macro_rules! testgen_matmul_simple {
                     FloatT,
                 >(&Default::default())
             }
+
+            #[test]
+            pub fn test_with_4d_batches() {
+                cubecl_linalg::matmul::tests::simple::test_with_4d_batches::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_with_broadcasted_batches() {
+                cubecl_linalg::matmul::tests::simple::test_with_broadcasted_batches::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_scaled() {
+                cubecl_linalg::matmul::tests::simple::test_scaled::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_scaled_without_beta() {
+                cubecl_linalg::matmul::tests::simple::test_scaled_without_beta::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_vector_matrix() {
+                cubecl_linalg::matmul::tests::simple::test_vector_matrix::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_matrix_vector() {
+                cubecl_linalg::matmul::tests::simple::test_matrix_vector::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_vector_matrix_with_batches() {
+                cubecl_linalg::matmul::tests::simple::test_vector_matrix_with_batches::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_matrix_vector_with_batches() {
+                cubecl_linalg::matmul::tests::simple::test_matrix_vector_with_batches::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_scaled_vector_matrix() {
+                cubecl_linalg::matmul::tests::simple::test_scaled_vector_matrix::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
     };
     ([$($float:ident),*]) => {
         mod simple {
@@ -0,0 +1,82 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_matmul_split_k {
+    () => {
+        mod split_k {
+            $crate::testgen_matmul_split_k!(f32);
+        }
+    };
+    ($float:ident) => {
+            use super::*;
+            use cubecl_core::flex32;
+
+            pub type FloatT = $float;
+
+            #[test]
+            pub fn test_even_splits() {
+                cubecl_linalg::matmul::tests::split_k::test_split_k::<TestRuntime, FloatT>(
+                    &Default::default(),
+                    64,
+                    4,
+                    cubecl_linalg::matmul::components::MatrixLayout::RowMajor,
+                )
+            }
+
+            #[test]
+            pub fn test_uneven_splits() {
+                cubecl_linalg::matmul::tests::split_k::test_split_k::<TestRuntime, FloatT>(
+                    &Default::default(),
+                    70,
+                    4,
+                    cubecl_linalg::matmul::components::MatrixLayout::RowMajor,
+                )
+            }
+
+            #[test]
+            pub fn test_many_small_splits() {
+                cubecl_linalg::matmul::tests::split_k::test_split_k::<TestRuntime, FloatT>(
+                    &Default::default(),
+                    20,
+                    7,
+                    cubecl_linalg::matmul::components::MatrixLayout::RowMajor,
+                )
+            }
+
+            #[test]
+            pub fn test_col_major_out() {
+                cubecl_linalg::matmul::tests::split_k::test_split_k::<TestRuntime, FloatT>(
+                    &Default::default(),
+                    70,
+                    4,
+                    cubecl_linalg::matmul::components::MatrixLayout::ColMajor,
+                )
+            }
+
+            #[test]
+            pub fn test_invalid_splits() {
+                cubecl_linalg::matmul::tests::split_k::test_invalid_splits::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_context_reuse() {
+                cubecl_linalg::matmul::tests::split_k::test_context_reuse::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+    };
+    ([$($float:ident),*]) => {
+        mod matmul_split_k {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$float _ty>] {
+                    use super::*;
+
+                    $crate::testgen_matmul_split_k!($float);
+                })*
+            }
+        }
+    };
+}
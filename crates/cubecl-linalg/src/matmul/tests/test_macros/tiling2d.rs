@@ -45,6 +45,22 @@ macro_rules! testgen_matmul_tiling2d {
                     FloatT,
                 >(&Default::default())
             }
+
+            #[test]
+            pub fn test_double_buffered() {
+                cubecl_linalg::matmul::tests::tiling2d::test_double_buffered::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_config_fits_device() {
+                cubecl_linalg::matmul::tests::tiling2d::test_config_fits_device::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
     };
     ([$($float:ident),*]) => {
         mod matmul_tiling2d {
@@ -1,51 +1,375 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
 use std::fmt::Display;
+use std::sync::{Mutex, OnceLock};
 
 use cubecl_core::{
     client::ComputeClient,
     flex32,
+    ir::{Elem, FloatKind},
     prelude::{Float, Numeric},
     server::Handle,
-    CubeElement, Runtime,
+    CubeElement, Feature, Runtime,
 };
 
 use crate::{
     matmul::{
-        components::{Ident, MatmulProblem},
-        tests::cmma_matmul::matmul_test_launcher::strides,
+        components::{
+            global::EpilogueOp, tile::accelerated::Accelerated, Ident, MatmulProblem, MatmulSize,
+        },
+        tests::cmma_matmul::matmul_test_launcher::broadcast_batch_strides,
     },
     tensor::TensorHandle,
 };
 
-/// Compares the content of a handle to a given slice of f32.
+/// How [assert_equals_approx] decides whether an actual value is close enough to the expected one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EpsilonPolicy {
+    /// The absolute difference is at most the given epsilon.
+    Absolute(f32),
+    /// The absolute difference is at most the given fraction of the expected magnitude, so an
+    /// expected zero must be matched exactly.
+    Relative(f32),
+    /// The values are at most the given number of representable values of the element type
+    /// apart. This is the natural metric for `f32`, whose relative error depends on where the
+    /// value falls between two powers of two.
+    Ulps(u32),
+    /// The epsilon is derived from the [accuracy](MatmulAccuracy) of the matmul that computed
+    /// the values, and loosened to the precision of the element type when it is coarser than
+    /// `f32`. It allows an error relative to the expected magnitude above one, absolute below.
+    Auto(MatmulAccuracy),
+}
+
+/// What the epsilon of [EpsilonPolicy::Auto] is derived from: the precisions a matmul of depth
+/// `k` is computed in, and whether it runs on tensor cores.
+///
+/// Each of the `k` accumulations may round by the machine epsilon of the precision it is done in,
+/// so the epsilon grows with `k`. It is never tighter than `10e-5`, which is enough for the
+/// default accuracy of a single `f32` product.
+///
+/// - Without tensor cores, units accumulate in `EA` in the same order as the reference, so only
+///   the rounding of the sums adds up: the epsilon is `k` times the one of `EA`. This stays
+///   `10e-5` for an `f32` accumulator up to a `k` of about 800.
+/// - With tensor cores, the hardware may round intermediate results to the coarsest of the stage
+///   and accumulator types, and accumulates in its own order, so the rounding errors are
+///   uncorrelated and grow like the square root of `k`: the epsilon is twice `sqrt(k)` times the
+///   epsilon of the coarsest type. This is about `0.016` for an `f16` or `tf32` stage with a `k`
+///   of 64, and `0.13` for a `bf16` stage.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MatmulAccuracy {
+    stage_epsilon: f32,
+    accumulator_epsilon: f32,
+    tensor_cores: bool,
+    k: usize,
+}
+
+impl Default for MatmulAccuracy {
+    /// A single product accumulated in `f32`.
+    fn default() -> Self {
+        Self::scalar::<f32>(1)
+    }
+}
+
+impl MatmulAccuracy {
+    /// A matmul of depth `k` computed by units accumulating in `EA`.
+    pub fn scalar<EA: Float>(k: usize) -> Self {
+        let epsilon = EA::EPSILON.to_f32().unwrap();
+        Self {
+            stage_epsilon: epsilon,
+            accumulator_epsilon: epsilon,
+            tensor_cores: false,
+            k,
+        }
+    }
+
+    /// A matmul of depth `k` computed on tensor cores multiplying `ES` into `EA`.
+    pub fn tensor_cores<ES: Float, EA: Float>(k: usize) -> Self {
+        Self {
+            stage_epsilon: ES::EPSILON.to_f32().unwrap(),
+            accumulator_epsilon: EA::EPSILON.to_f32().unwrap(),
+            tensor_cores: true,
+            k,
+        }
+    }
+
+    /// A matmul of depth `k` multiplying `ES` into `EA`, which runs on tensor cores when the tile
+    /// matmul requires them and a cmma instruction exists on the client for these types with the
+    /// `tile` shape, or any of the [tile sizes](Accelerated::TILE_SIZES) when `None`.
+    pub fn new<ES: Float, EA: Float, R: Runtime>(
+        client: &ComputeClient<R::Server, R::Channel>,
+        tile: Option<MatmulSize>,
+        tensor_cores: bool,
+        k: usize,
+    ) -> Self {
+        let stage = ES::as_elem_native().expect("To be a native type");
+        let accumulator = EA::as_elem_native().expect("To be a native type");
+
+        let uses_cmma = tensor_cores
+            && tile
+                .map(|tile| vec![tile])
+                .unwrap_or_else(|| Accelerated::TILE_SIZES.to_vec())
+                .into_iter()
+                .any(|tile| {
+                    client.properties().feature_enabled(Feature::Cmma {
+                        a: stage,
+                        b: stage,
+                        c: accumulator,
+                        m: tile.m as u8,
+                        k: tile.k as u8,
+                        n: tile.n as u8,
+                    })
+                });
+
+        match uses_cmma {
+            true => Self::tensor_cores::<ES, EA>(k),
+            false => Self::scalar::<EA>(k),
+        }
+    }
+
+    /// The epsilon for an output of `F`.
+    fn epsilon<F: Float>(&self) -> f32 {
+        let epsilon = match self.tensor_cores {
+            true => {
+                let coarsest = f32::max(self.stage_epsilon, self.accumulator_epsilon);
+                2.0 * (self.k as f32).sqrt() * coarsest
+            }
+            false => self.k as f32 * self.accumulator_epsilon,
+        };
+
+        // Outputs coarser than f32 are rounded more than the accumulator, finer ones aren't
+        // compared more tightly.
+        let output = f32::max(F::EPSILON.to_f32().unwrap() / f32::EPSILON, 1.0);
+        f32::max(epsilon, 10e-5 * output)
+    }
+}
+
+/// Compares the content of a handle to a given slice of f32, holding batched matrices of shape
+/// `rows x cols`, with the given [policy](EpsilonPolicy).
+///
+/// On failure, the error reports the number of mismatches along with the largest absolute and
+/// relative errors. Their positions are decoded as `(batch, row, col)`, which points to the
+/// failing tile.
+///
+/// NaNs and infinities, in the actual or the expected values, fail the comparison with any policy
+/// and are reported on their own, with their count and first position, since they usually come
+/// from an overflow rather than from a lack of precision. An infinity equal to the expected one
+/// isn't reported, and matches with any policy like other exactly equal values.
 pub(crate) fn assert_equals_approx<R: Runtime, F: Float + CubeElement + Display>(
     client: &ComputeClient<R::Server, R::Channel>,
     output: Handle,
     expected: &[F],
-    epsilon: f32,
+    policy: EpsilonPolicy,
+    rows: usize,
+    cols: usize,
 ) -> Result<(), String> {
     let actual = client.read_one(output.binding());
-    let actual = F::from_bytes(&actual);
+    compare_approx(F::from_bytes(&actual), expected, policy, rows, cols)
+}
 
-    // normalize to type epsilon
-    let epsilon = (epsilon / f32::EPSILON * F::EPSILON.to_f32().unwrap()).max(epsilon);
+fn compare_approx<F: Float + Display>(
+    actual: &[F],
+    expected: &[F],
+    policy: EpsilonPolicy,
+    rows: usize,
+    cols: usize,
+) -> Result<(), String> {
+    let auto_epsilon = match policy {
+        EpsilonPolicy::Auto(accuracy) => accuracy.epsilon::<F>(),
+        _ => 0.0,
+    };
+
+    check_finite(actual, expected, rows, cols)?;
+
+    let mut num_mismatches = 0;
+    let mut worst_abs = (0, 0.0f32);
+    let mut worst_rel = (0, 0.0f32);
 
     for (i, (a, e)) in actual.iter().zip(expected.iter()).enumerate() {
-        // account for lower precision at higher values
-        let allowed_error = (epsilon * e.to_f32().unwrap()).max(epsilon);
-
-        if f32::abs(a.to_f32().unwrap() - e.to_f32().unwrap()) >= allowed_error {
-            return Err(format!(
-            "Values differ more than epsilon: index={} actual={}, expected={}, difference={}, epsilon={}",
-            i,
-            *a,
-            *e,
-            f32::abs(a.to_f32().unwrap() - e.to_f32().unwrap()),
-            epsilon
-            ));
+        // Equal values can't differ, even infinities whose difference would be NaN.
+        if a == e {
+            continue;
+        }
+
+        let (a64, e64) = (a.to_f64().unwrap(), e.to_f64().unwrap());
+        let (a, e) = (a.to_f32().unwrap(), e.to_f32().unwrap());
+        let difference = f32::abs(a - e);
+
+        let mismatches = match policy {
+            // account for lower precision at higher values
+            EpsilonPolicy::Auto(_) => difference >= (auto_epsilon * f32::abs(e)).max(auto_epsilon),
+            EpsilonPolicy::Absolute(epsilon) => difference.is_nan() || difference > epsilon,
+            EpsilonPolicy::Relative(epsilon) => {
+                difference.is_nan() || difference > epsilon * f32::abs(e)
+            }
+            EpsilonPolicy::Ulps(max_ulps) => {
+                a64.is_nan() || e64.is_nan() || ulps_between::<F>(a64, e64) > max_ulps as u64
+            }
+        };
+        if mismatches {
+            num_mismatches += 1;
         }
+
+        if difference > worst_abs.1 {
+            worst_abs = (i, difference);
+        }
+        let relative = difference / f32::abs(e);
+        if relative > worst_rel.1 {
+            worst_rel = (i, relative);
+        }
+    }
+
+    if num_mismatches == 0 {
+        return Ok(());
     }
 
-    Ok(())
+    let position = |index: usize| position(index, rows, cols);
+    let policy = match policy {
+        EpsilonPolicy::Auto(_) => format!("{policy:?} with epsilon={auto_epsilon}"),
+        _ => format!("{policy:?}"),
+    };
+
+    Err(format!(
+        "Values differ more than {policy} at {num_mismatches}/{} positions\n\
+         max absolute error={} at {}: actual={}, expected={}\n\
+         max relative error={} at {}: actual={}, expected={}",
+        expected.len(),
+        worst_abs.1,
+        position(worst_abs.0),
+        actual[worst_abs.0],
+        expected[worst_abs.0],
+        worst_rel.1,
+        position(worst_rel.0),
+        actual[worst_rel.0],
+        expected[worst_rel.0],
+    ))
+}
+
+/// Fails with the count and first position of the NaNs and infinities of the actual and the
+/// expected values, ignoring the infinities found in both at the same position.
+fn check_finite<F: Float + Display>(
+    actual: &[F],
+    expected: &[F],
+    rows: usize,
+    cols: usize,
+) -> Result<(), String> {
+    let report = |name: &str, values: &[F], others: &[F]| {
+        let (mut num_nans, mut num_infinities, mut first) = (0, 0, None);
+
+        for (i, (value, other)) in values.iter().zip(others).enumerate() {
+            let (value, other) = (value.to_f64().unwrap(), other.to_f64().unwrap());
+            if value.is_nan() {
+                num_nans += 1;
+            } else if value.is_infinite() && value != other {
+                num_infinities += 1;
+            } else {
+                continue;
+            }
+            first.get_or_insert(i);
+        }
+
+        first.map(|first| {
+            format!(
+                "{name} values are not finite: {num_nans} NaN and {num_infinities} infinite \
+                 among {}\n\
+                 first at {}: actual={}, expected={}",
+                values.len(),
+                position(first, rows, cols),
+                actual[first],
+                expected[first],
+            )
+        })
+    };
+
+    let errors: Vec<String> = [
+        report("Actual", actual, expected),
+        report("Expected", expected, actual),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    match errors.is_empty() {
+        true => Ok(()),
+        false => Err(errors.join("\n")),
+    }
+}
+
+/// Number of representable values of `F` between `a` and `b`, both holding a value of `F`.
+///
+/// `f64` values are ordered like their bits once the negative ones are flipped. Narrower types
+/// have another exponent range, so their values are numbered from the smallest subnormal instead,
+/// counting `2^(MANTISSA_DIGITS - 1)` values per power of two.
+fn ulps_between<F: Float>(a: f64, b: f64) -> u64 {
+    let ordinal = |value: f64| -> i64 {
+        if F::MANTISSA_DIGITS == f64::MANTISSA_DIGITS {
+            let bits = value.to_bits() as i64;
+            return match bits < 0 {
+                true => i64::MIN - bits,
+                false => bits,
+            };
+        }
+
+        let per_binade = (1i64 << (F::MANTISSA_DIGITS - 1)) as f64;
+        let min_normal = F::MIN_POSITIVE.to_f64().unwrap();
+        let abs = value.abs();
+        let index = if abs.is_infinite() {
+            (F::MAX_EXP - F::MIN_EXP + 2) as f64 * per_binade
+        } else if abs < min_normal {
+            abs / min_normal * per_binade
+        } else {
+            let exponent = ((abs.to_bits() >> 52) & 0x7ff) as i32 - 1023;
+            let fraction = abs / 2f64.powi(exponent) - 1.0;
+            ((exponent - F::MIN_EXP + 2) as f64 + fraction) * per_binade
+        };
+
+        match value.is_sign_negative() {
+            true => -(index as i64),
+            false => index as i64,
+        }
+    };
+
+    ordinal(a).abs_diff(ordinal(b))
+}
+
+/// Compares the content of a handle to a given slice of integers, holding batched matrices of
+/// shape `rows x cols`, which must match exactly.
+///
+/// On failure, the error reports the number of mismatches along with the first one, decoded like
+/// in [assert_equals_approx].
+pub(crate) fn assert_equals_exact<R: Runtime, I: Numeric + CubeElement + Display>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    output: Handle,
+    expected: &[I],
+    rows: usize,
+    cols: usize,
+) -> Result<(), String> {
+    let actual = client.read_one(output.binding());
+    let actual = I::from_bytes(&actual);
+
+    let mut mismatches = (0..expected.len()).filter(|&i| actual[i] != expected[i]);
+    let first = match mismatches.next() {
+        Some(first) => first,
+        None => return Ok(()),
+    };
+
+    Err(format!(
+        "Values differ at {}/{} positions\n\
+         first mismatch at {}: actual={}, expected={}",
+        mismatches.count() + 1,
+        expected.len(),
+        position(first, rows, cols),
+        actual[first],
+        expected[first],
+    ))
+}
+
+fn position(index: usize, rows: usize, cols: usize) -> String {
+    format!(
+        "index={index} (batch={}, row={}, col={})",
+        index / (rows * cols),
+        index / cols % rows,
+        index % cols
+    )
 }
 
 pub trait CastInto<E> {
@@ -118,30 +442,259 @@ impl CastInto<flex32> for f32 {
     }
 }
 
-/// Generates num_elements random floats for tests.
+impl CastInto<i32> for i8 {
+    fn cast_into(self) -> i32 {
+        self as i32
+    }
+}
+
+/// How an `f32` is rounded when converted to a float with fewer mantissa bits.
+///
+/// Devices don't all convert with the default rounding of `half`, so tests with tight epsilons
+/// may need to round their inputs like the device does to match it exactly.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// To the nearest value, ties going to the even mantissa.
+    #[default]
+    NearestEven,
+    /// To the nearest value of smaller magnitude.
+    TowardZero,
+    /// To the nearest value greater or equal, toward positive infinity.
+    Up,
+    /// To the nearest value lower or equal, toward negative infinity.
+    Down,
+}
+
+/// Conversion of an `f32` into a narrower float with a chosen [RoundingMode].
+pub trait FromF32Rounded: Sized {
+    fn from_f32_rounded(value: f32, mode: RoundingMode) -> Self;
+}
+
+impl FromF32Rounded for half::f16 {
+    fn from_f32_rounded(value: f32, mode: RoundingMode) -> Self {
+        let nearest = half::f16::from_f32(value).to_bits();
+        half::f16::from_bits(round_bits(value, nearest, mode, |bits| {
+            half::f16::from_bits(bits).to_f32()
+        }))
+    }
+}
+
+impl FromF32Rounded for half::bf16 {
+    fn from_f32_rounded(value: f32, mode: RoundingMode) -> Self {
+        let nearest = half::bf16::from_f32(value).to_bits();
+        half::bf16::from_bits(round_bits(value, nearest, mode, |bits| {
+            half::bf16::from_bits(bits).to_f32()
+        }))
+    }
+}
+
+/// Adjusts the bits of a 16-bit float, rounded to the nearest of `value`, to the neighbour
+/// `mode` asks for.
+///
+/// Both `f16` and `bf16` are sign-magnitude, so stepping the bits walks between consecutive
+/// values of the same sign, the largest finite value being next to infinity.
+fn round_bits(value: f32, nearest: u16, mode: RoundingMode, to_f32: fn(u16) -> f32) -> u16 {
+    let rounded = to_f32(nearest);
+    if rounded.is_nan() || rounded == value {
+        return nearest;
+    }
+
+    let negative = nearest & 0x8000 != 0;
+    let away_from_zero = nearest + 1;
+    let toward_zero = match nearest & 0x7fff {
+        0 => nearest,
+        _ => nearest - 1,
+    };
+
+    match mode {
+        RoundingMode::NearestEven => nearest,
+        RoundingMode::TowardZero if rounded.abs() > value.abs() => toward_zero,
+        RoundingMode::Up if rounded < value => match negative {
+            true => toward_zero,
+            false => away_from_zero,
+        },
+        RoundingMode::Down if rounded > value => match negative {
+            true => away_from_zero,
+            false => toward_zero,
+        },
+        _ => nearest,
+    }
+}
+
+/// Rounds `value` to a value representable by `F` with `mode`, returned as an `f32` holding it
+/// exactly, so that [Float::new] doesn't round it again.
+///
+/// Only `f16` and `bf16` are rounded, the other float types are returned as is, to be converted
+/// with their own rounding.
+fn round_for<F: Float>(value: f32, mode: RoundingMode) -> f32 {
+    match F::as_elem_native_unchecked() {
+        Elem::Float(FloatKind::F16) => half::f16::from_f32_rounded(value, mode).to_f32(),
+        Elem::Float(FloatKind::BF16) => half::bf16::from_f32_rounded(value, mode).to_f32(),
+        _ => value,
+    }
+}
+
+/// Generates num_elements random floats for tests, uniformly distributed in `[-1, 1)`.
 ///
 /// This is a naive CPU implementation with fixed seed,
 /// not designed to be used for other purposes than testing.
 pub(crate) fn generate_random_data<F: Float + CubeElement>(
+    num_elements: usize,
+    seed: u64,
+) -> Vec<F> {
+    generate_random_data_ranged(
+        num_elements,
+        seed,
+        -1.0,
+        1.0,
+        Distribution::Uniform,
+        RoundingMode::NearestEven,
+    )
+}
+
+/// How the values of [generate_random_data_ranged] are distributed over their range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Distribution {
+    /// Every value of the range is equally likely.
+    Uniform,
+    /// Centered on the middle of the range, with a standard deviation of a sixth of its width.
+    /// The few values falling outside of the range are clamped to it.
+    Normal,
+}
+
+/// Generates num_elements random floats for tests, in `[min, max)` with the given distribution.
+///
+/// Large ranges stress the overflow of low precision floats, while tiny ones stress the
+/// cancellation of small values. Values are computed in `f64`, then rounded to `F` with
+/// `rounding`, so the bounds may be rounded too.
+pub(crate) fn generate_random_data_ranged<F: Float + CubeElement>(
     num_elements: usize,
     mut seed: u64,
+    min: f32,
+    max: f32,
+    dist: Distribution,
+    rounding: RoundingMode,
 ) -> Vec<F> {
-    fn lcg(seed: &mut u64) -> f32 {
+    // Uniform in [0, 1).
+    fn lcg(seed: &mut u64) -> f64 {
         const A: u64 = 1664525;
         const C: u64 = 1013904223;
         const M: f64 = 2u64.pow(32) as f64;
 
         *seed = (A.wrapping_mul(*seed).wrapping_add(C)) % (1u64 << 32);
-        (*seed as f64 / M * 2.0 - 1.0) as f32
+        *seed as f64 / M
+    }
+
+    let (min, max) = (min as f64, max as f64);
+
+    (0..num_elements)
+        .map(|_| {
+            let value = match dist {
+                Distribution::Uniform => lcg(&mut seed) * (max - min) + min,
+                Distribution::Normal => {
+                    // Box-Muller transform, with u1 in (0, 1] to keep the log finite.
+                    let u1 = 1.0 - lcg(&mut seed);
+                    let u2 = lcg(&mut seed);
+                    let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+                    ((min + max) / 2.0 + z * (max - min) / 6.0).clamp(min, max)
+                }
+            };
+            F::new(round_for::<F>(value as f32, rounding))
+        })
+        .collect()
+}
+
+/// Generates num_elements random `i8` in `-8..8` for tests.
+///
+/// Values are kept small so that the exact `i32` sums of a matmul are easy to read, and far from
+/// overflowing. Like [generate_random_data], the seed is fixed.
+pub(crate) fn generate_random_int_data(num_elements: usize, mut seed: u64) -> Vec<i8> {
+    const A: u64 = 1664525;
+    const C: u64 = 1013904223;
+
+    (0..num_elements)
+        .map(|_| {
+            seed = (A.wrapping_mul(seed).wrapping_add(C)) % (1u64 << 32);
+            // The high bits of a LCG are the most random ones.
+            (seed >> 28) as i8 - 8
+        })
+        .collect()
+}
+
+/// Generates `num_batches` identity matrices of shape `rows x cols` for tests.
+///
+/// Non-square matrices get ones on their main diagonal only, so multiplying by them either
+/// truncates or zero-pads the other operand.
+pub(crate) fn generate_identity_data<F: Float + CubeElement>(
+    num_batches: usize,
+    rows: usize,
+    cols: usize,
+) -> Vec<F> {
+    (0..num_batches * rows * cols)
+        .map(|i| {
+            let (row, col) = (i / cols % rows, i % cols);
+            F::new(if row == col { 1.0 } else { 0.0 })
+        })
+        .collect()
+}
+
+/// Generates the ramp `0, 1, 2, ...` of num_elements floats for tests.
+///
+/// Large ramps are not exactly representable in low precision floats, so this is better suited
+/// to small problems.
+pub(crate) fn generate_sequential_data<F: Float + CubeElement>(num_elements: usize) -> Vec<F> {
+    (0..num_elements).map(|i| F::new(i as f32)).collect()
+}
+
+/// Returns the shape of the identified tensor, inferred by the problem definition
+pub(crate) fn shape(problem: &MatmulProblem, ident: Ident) -> Vec<usize> {
+    match ident {
+        Ident::Lhs => problem
+            .batches
+            .0
+            .iter()
+            .cloned()
+            .chain(vec![problem.m, problem.k])
+            .collect(),
+        Ident::Rhs => problem
+            .batches
+            .1
+            .iter()
+            .cloned()
+            .chain(vec![problem.k, problem.n])
+            .collect(),
+        Ident::Out => problem
+            .batch_dims()
+            .iter()
+            .cloned()
+            .chain(vec![problem.m, problem.n])
+            .collect(),
+        Ident::Bias => vec![problem.n],
     }
+}
 
-    (0..num_elements).map(|_| F::new(lcg(&mut seed))).collect()
+/// Transposes the last two dims of row-major matrices of `rows` by `cols`, for each of the
+/// `batches` matrices stored one after the other.
+pub(crate) fn transpose<E: Copy>(array: &[E], batches: usize, rows: usize, cols: usize) -> Vec<E> {
+    let mut result = vec![array[0]; array.len()];
+    for b in 0..batches {
+        for i in 0..rows {
+            for j in 0..cols {
+                result[(b * rows * cols) + j * rows + i] = array[(b * rows * cols) + i * cols + j];
+            }
+        }
+    }
+    result
 }
 
 /// Solves a matmul problem with EG inputs, multiplied as ES
 ///
+/// Batches are broadcasted like NumPy would: the batch dimensions are aligned on the last one,
+/// and dimensions of size one (or missing) are repeated over the other input.
+///
 /// This is a naive CPU implementation, very slow on large payloads,
-/// not designed to be used for other purposes than testing.
+/// not designed to be used for other purposes than testing. With the `rayon` feature, output rows
+/// are computed in parallel, see [matmul_cpu_reference_mixed].
 pub(crate) fn matmul_cpu_reference<EG, ES>(
     lhs: &[EG],
     rhs: &[EG],
@@ -150,49 +703,180 @@ pub(crate) fn matmul_cpu_reference<EG, ES>(
 where
     EG: Numeric + CubeElement + CastInto<ES>,
     ES: Numeric + CubeElement + CastInto<EG>,
+{
+    matmul_cpu_reference_mixed::<EG, EG, EG, ES>(lhs, rhs, problem)
+}
+
+/// Applies the epilogue operation to a value on the CPU, in `f64`.
+pub(crate) fn epilogue_cpu_reference(value: f32, epilogue: EpilogueOp) -> f32 {
+    let x = value as f64;
+    let y = match epilogue {
+        EpilogueOp::None => x,
+        EpilogueOp::Relu => x.max(0.0),
+        EpilogueOp::Gelu => 0.5 * x * (1.0 + erf(x * core::f64::consts::FRAC_1_SQRT_2)),
+        EpilogueOp::GeluTanh => {
+            let inner = (2.0 / core::f64::consts::PI).sqrt() * (x + 0.044715 * x * x * x);
+            0.5 * x * (1.0 + inner.tanh())
+        }
+        EpilogueOp::Sigmoid => 1.0 / (1.0 + (-x).exp()),
+    };
+    y as f32
+}
+
+/// The error function, which the standard library doesn't provide.
+///
+/// Uses the approximation 7.1.26 of Abramowitz and Stegun, whose maximum error of 1.5e-7 is
+/// below the precision of `f32`.
+fn erf(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.327_591_1 * x.abs());
+    let poly = t
+        * (0.254_829_592
+            + t * (-0.284_496_736
+                + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    let erf = 1.0 - poly * (-x * x).exp();
+
+    erf.copysign(x)
+}
+
+/// Same as [matmul_cpu_reference], but with lhs and rhs of different element types.
+///
+/// Both inputs are cast to `ES` before being multiplied, like the kernels do when loading them
+/// into shared memory.
+///
+/// Every output element accumulates its products in `EG`, in increasing order of k, starting
+/// from zero. With the `rayon` feature, the rows of all batches are split across threads, but
+/// each element is still computed by a single thread in that order, so the result is bitwise
+/// equal to the serial one.
+pub(crate) fn matmul_cpu_reference_mixed<EL, ER, EG, ES>(
+    lhs: &[EL],
+    rhs: &[ER],
+    problem: &MatmulProblem,
+) -> Vec<EG>
+where
+    EL: Numeric + CubeElement + CastInto<ES>,
+    ER: Numeric + CubeElement + CastInto<ES>,
+    EG: Numeric + CubeElement,
+    ES: Numeric + CubeElement + CastInto<EG>,
 {
     let m = problem.m;
     let n = problem.n;
     let k = problem.k;
     let num_batches = problem.num_batches();
 
-    let (b_lhs, b_rhs) = problem.batches.clone();
-    assert!(b_lhs.len() == b_rhs.len(), "Cpu reference only works with batches of equal length. Please pad the shortest one with ones at the beginning.");
-
-    let lhs_strides = strides(problem, Ident::Lhs);
-    let rhs_strides = strides(problem, Ident::Rhs);
-    let out_strides = strides(problem, Ident::Out);
+    let batch_dims = problem.batch_dims();
+    let lhs_strides = broadcast_batch_strides(problem, Ident::Lhs);
+    let rhs_strides = broadcast_batch_strides(problem, Ident::Rhs);
+    let out_strides = broadcast_batch_strides(problem, Ident::Out);
 
     let mut out = vec![EG::from_int(0); m * n * num_batches];
 
-    for nth_batch in 0..num_batches {
+    // Computes the nth row of the output, counting the rows of all batches.
+    let compute_row = |nth_row: usize, out_row: &mut [EG]| {
+        let (nth_batch, i) = (nth_row / m, nth_row % m);
         let batch_out = nth_batch * m * n;
         let mut batch_lhs = 0;
         let mut batch_rhs = 0;
-        for b in 0..b_lhs.len() {
-            let tmp = batch_out / out_strides[b];
-            batch_lhs += tmp % b_lhs[b] * lhs_strides[b];
-            batch_rhs += tmp % b_rhs[b] * rhs_strides[b];
+        for b in 0..batch_dims.len() {
+            let index = batch_out / out_strides[b] % batch_dims[b];
+            batch_lhs += index * lhs_strides[b];
+            batch_rhs += index * rhs_strides[b];
         }
 
-        for i in 0..m {
-            for j in 0..n {
-                for k_ in 0..k {
-                    let lhs_index = i * k + k_;
-                    let rhs_index = k_ * n + j;
-                    let out_index = i * n + j;
+        for (j, out) in out_row.iter_mut().enumerate() {
+            for k_ in 0..k {
+                let lhs_index = i * k + k_;
+                let rhs_index = k_ * n + j;
 
-                    let l: ES = lhs[batch_lhs + lhs_index].cast_into();
-                    let r: ES = rhs[batch_rhs + rhs_index].cast_into();
-                    let prod = l * r;
-                    let casted: EG = prod.cast_into();
+                let l: ES = lhs[batch_lhs + lhs_index].cast_into();
+                let r: ES = rhs[batch_rhs + rhs_index].cast_into();
+                let prod = l * r;
+                let casted: EG = prod.cast_into();
 
-                    out[batch_out + out_index] += casted;
-                }
+                *out += casted;
             }
         }
+    };
+
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+
+        out.par_chunks_mut(n)
+            .enumerate()
+            .for_each(|(nth_row, out_row)| compute_row(nth_row, out_row));
     }
 
+    #[cfg(not(feature = "rayon"))]
+    out.chunks_mut(n)
+        .enumerate()
+        .for_each(|(nth_row, out_row)| compute_row(nth_row, out_row));
+
+    out
+}
+
+/// Key of a CPU reference in the cache of [matmul_cpu_reference_cached].
+///
+/// The inputs are kept in full, so that two references are only shared when they are computed
+/// from exactly the same data.
+#[derive(PartialEq, Eq, Hash)]
+struct ReferenceKey {
+    types: [TypeId; 4],
+    m: usize,
+    n: usize,
+    k: usize,
+    batches: (Vec<usize>, Vec<usize>),
+    lhs: Vec<u8>,
+    rhs: Vec<u8>,
+}
+
+/// CPU references by key, each holding the `Vec` of its output type.
+type ReferenceCache = Mutex<HashMap<ReferenceKey, Box<dyn Any + Send>>>;
+
+/// Same as [matmul_cpu_reference_mixed], but the result is computed once per test run for the
+/// same inputs, problem and types, then reused.
+///
+/// Tests comparing many strategies over the same generated inputs then only pay for the CPU
+/// reference once. The result is the one [matmul_cpu_reference_mixed] returns.
+pub(crate) fn matmul_cpu_reference_cached<EL, ER, EG, ES>(
+    lhs: &[EL],
+    rhs: &[ER],
+    problem: &MatmulProblem,
+) -> Vec<EG>
+where
+    EL: Numeric + CubeElement + CastInto<ES>,
+    ER: Numeric + CubeElement + CastInto<ES>,
+    EG: Numeric + CubeElement,
+    ES: Numeric + CubeElement + CastInto<EG>,
+{
+    static CACHE: OnceLock<ReferenceCache> = OnceLock::new();
+
+    let key = ReferenceKey {
+        types: [
+            TypeId::of::<EL>(),
+            TypeId::of::<ER>(),
+            TypeId::of::<EG>(),
+            TypeId::of::<ES>(),
+        ],
+        m: problem.m,
+        n: problem.n,
+        k: problem.k,
+        batches: problem.batches.clone(),
+        lhs: EL::as_bytes(lhs).to_vec(),
+        rhs: ER::as_bytes(rhs).to_vec(),
+    };
+
+    let cache = CACHE.get_or_init(Default::default);
+    if let Some(cached) = cache.lock().unwrap().get(&key) {
+        return cached
+            .downcast_ref::<Vec<EG>>()
+            .expect("The output type is part of the key")
+            .clone();
+    }
+
+    // Computed without holding the lock, so tests running in parallel aren't serialized.
+    let out = matmul_cpu_reference_mixed::<EL, ER, EG, ES>(lhs, rhs, problem);
+    cache.lock().unwrap().insert(key, Box::new(out.clone()));
+
     out
 }
 
@@ -275,8 +959,7 @@ impl MatmulTestCase {
         shape: Vec<usize>,
     ) -> TensorHandle<R, F> {
         let data = generate_random_data::<F>(shape.iter().product(), 999);
-        let handle = client.create(bytemuck::cast_slice(&data));
-        TensorHandle::new_contiguous(shape, handle)
+        TensorHandle::from_data(client, &data, shape)
     }
 
     pub(crate) fn create_empty<R: Runtime>(
@@ -288,3 +971,308 @@ impl MatmulTestCase {
         client.empty(x * y * core::mem::size_of::<f32>())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem(lhs_batches: Vec<usize>, rhs_batches: Vec<usize>) -> MatmulProblem {
+        MatmulProblem {
+            m: 1,
+            n: 1,
+            k: 2,
+            batches: (lhs_batches, rhs_batches),
+            ..Default::default()
+        }
+    }
+
+    /// Expected output of `[1, 4] x [3, 4]` batches, with every matrix a dot product of size 2.
+    fn expected_broadcast(lhs: &[f32], rhs: &[f32]) -> Vec<f32> {
+        let mut expected = Vec::new();
+        for b0 in 0..3 {
+            for b1 in 0..4 {
+                let l = &lhs[b1 * 2..b1 * 2 + 2];
+                let r = &rhs[(b0 * 4 + b1) * 2..(b0 * 4 + b1) * 2 + 2];
+                expected.push(l[0] * r[0] + l[1] * r[1]);
+            }
+        }
+        expected
+    }
+
+    #[test]
+    fn cpu_reference_broadcasts_unit_batch() {
+        let lhs: Vec<f32> = (0..8).map(|i| i as f32).collect();
+        let rhs: Vec<f32> = (0..24).map(|i| i as f32).collect();
+
+        let out = matmul_cpu_reference::<f32, f32>(&lhs, &rhs, &problem(vec![1, 4], vec![3, 4]));
+
+        assert_eq!(out, expected_broadcast(&lhs, &rhs));
+    }
+
+    #[test]
+    fn cpu_reference_broadcasts_missing_batch() {
+        let lhs: Vec<f32> = (0..8).map(|i| i as f32).collect();
+        let rhs: Vec<f32> = (0..24).map(|i| i as f32).collect();
+
+        let out = matmul_cpu_reference::<f32, f32>(&lhs, &rhs, &problem(vec![4], vec![3, 4]));
+
+        assert_eq!(out, expected_broadcast(&lhs, &rhs));
+    }
+
+    #[test]
+    fn cached_cpu_reference_matches_and_depends_on_inputs() {
+        let problem = problem(vec![1, 4], vec![3, 4]);
+        let lhs: Vec<f32> = (0..8).map(|i| i as f32).collect();
+        let rhs: Vec<f32> = (0..24).map(|i| i as f32).collect();
+        let other_rhs: Vec<f32> = (0..24).map(|i| -(i as f32)).collect();
+
+        let reference = matmul_cpu_reference::<f32, f32>(&lhs, &rhs, &problem);
+        let cached =
+            |rhs: &[f32]| matmul_cpu_reference_cached::<f32, f32, f32, f32>(&lhs, rhs, &problem);
+
+        assert_eq!(cached(&rhs), reference);
+        assert_eq!(cached(&rhs), reference);
+        assert_eq!(
+            cached(&other_rhs),
+            matmul_cpu_reference::<f32, f32>(&lhs, &other_rhs, &problem)
+        );
+    }
+
+    #[test]
+    fn broadcast_batch_strides_are_zero_on_broadcasted_dims() {
+        let problem = problem(vec![1, 4], vec![3, 4]);
+
+        assert_eq!(problem.batch_dims(), vec![3, 4]);
+        assert_eq!(broadcast_batch_strides(&problem, Ident::Lhs), vec![0, 2]);
+        assert_eq!(broadcast_batch_strides(&problem, Ident::Rhs), vec![8, 2]);
+        assert_eq!(broadcast_batch_strides(&problem, Ident::Out), vec![4, 1]);
+    }
+
+    #[test]
+    fn compare_approx_reports_worst_position() {
+        let expected = [1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+        let mut actual = expected;
+        actual[6] = 7.5;
+        actual[1] = 2.1;
+
+        let err =
+            compare_approx(&actual, &expected, EpsilonPolicy::Relative(0.01), 2, 2).unwrap_err();
+
+        assert!(err.contains("at 2/8 positions"), "{err}");
+        assert!(
+            err.contains("max absolute error=0.5 at index=6 (batch=1, row=1, col=0)"),
+            "{err}"
+        );
+        assert!(compare_approx(&expected, &expected, EpsilonPolicy::Relative(0.01), 2, 2).is_ok());
+    }
+
+    #[test]
+    fn compare_approx_reports_non_finite_values() {
+        let expected = [1.0f32, 2.0, 3.0, 4.0, f32::INFINITY, 6.0, 7.0, 8.0];
+        let mut actual = expected;
+        actual[5] = f32::NAN;
+        actual[6] = f32::NEG_INFINITY;
+        actual[7] = f32::NAN;
+
+        // A tolerance this loose would accept any finite value.
+        let err =
+            compare_approx(&actual, &expected, EpsilonPolicy::Absolute(1e30), 2, 2).unwrap_err();
+
+        assert!(
+            err.contains("Actual values are not finite: 2 NaN and 1 infinite among 8"),
+            "{err}"
+        );
+        assert!(
+            err.contains("first at index=5 (batch=1, row=0, col=1): actual=NaN, expected=6"),
+            "{err}"
+        );
+        // The matching infinity isn't reported on either side.
+        assert!(!err.contains("Expected values"), "{err}");
+        assert!(!err.contains("differ"), "{err}");
+
+        let err = compare_approx(
+            &expected,
+            &[1.0f32; 8],
+            EpsilonPolicy::Auto(Default::default()),
+            2,
+            2,
+        )
+        .unwrap_err();
+        assert!(err.contains("Actual values are not finite: 0 NaN"), "{err}");
+        let err = compare_approx(
+            &[1.0f32; 8],
+            &expected,
+            EpsilonPolicy::Auto(Default::default()),
+            2,
+            2,
+        )
+        .unwrap_err();
+        assert!(
+            err.contains("Expected values are not finite: 0 NaN"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn auto_policy_rejects_nan() {
+        // The difference with a NaN is never larger than the epsilon.
+        let policy = EpsilonPolicy::Auto(Default::default());
+        assert!(!compare_one(f32::NAN, 1.0, policy));
+    }
+
+    fn compare_one<F: Float + Display>(actual: F, expected: F, policy: EpsilonPolicy) -> bool {
+        compare_approx(&[actual], &[expected], policy, 1, 1).is_ok()
+    }
+
+    #[test]
+    fn equal_infinities_match_with_any_policy() {
+        let policies = [
+            EpsilonPolicy::Auto(Default::default()),
+            EpsilonPolicy::Absolute(0.01),
+            EpsilonPolicy::Relative(0.01),
+            EpsilonPolicy::Ulps(0),
+        ];
+
+        for policy in policies {
+            assert!(compare_one(f32::INFINITY, f32::INFINITY, policy));
+            assert!(compare_one(f32::NEG_INFINITY, f32::NEG_INFINITY, policy));
+            assert!(!compare_one(f32::NEG_INFINITY, f32::INFINITY, policy));
+        }
+    }
+
+    #[test]
+    fn absolute_policy_ignores_magnitude() {
+        let policy = EpsilonPolicy::Absolute(0.01);
+
+        assert!(compare_one(1000.005f32, 1000.0, policy));
+        assert!(!compare_one(1000.02f32, 1000.0, policy));
+        assert!(compare_one(0.005f32, 0.0, policy));
+        assert!(!compare_one(f32::NAN, 0.0, policy));
+    }
+
+    #[test]
+    fn relative_policy_scales_with_expected() {
+        let policy = EpsilonPolicy::Relative(0.01);
+
+        assert!(compare_one(1005.0f32, 1000.0, policy));
+        assert!(!compare_one(1020.0f32, 1000.0, policy));
+        assert!(!compare_one(0.02f32, 1.0e-3, policy));
+        assert!(compare_one(0.0f32, 0.0, policy));
+        assert!(!compare_one(1.0e-6f32, 0.0, policy));
+    }
+
+    #[test]
+    fn ulps_policy_counts_representable_values() {
+        let policy = EpsilonPolicy::Ulps(2);
+        let next = |value: f32, steps: u32| f32::from_bits(value.to_bits() + steps);
+
+        assert!(compare_one(next(1.0, 2), 1.0, policy));
+        assert!(!compare_one(next(1.0, 3), 1.0, policy));
+        assert!(compare_one(next(1.0e20, 2), 1.0e20, policy));
+        // Zero is one value away from the smallest subnormals on both sides.
+        assert!(compare_one(-f32::from_bits(1), f32::from_bits(1), policy));
+        assert!(!compare_one(f32::NAN, f32::NAN, policy));
+
+        // Steps are counted in the element type, not in f64.
+        let one = half::f16::from_f32(1.0);
+        let two_steps = half::f16::from_bits(one.to_bits() + 2);
+        let three_steps = half::f16::from_bits(one.to_bits() + 3);
+        assert!(compare_one(two_steps, one, policy));
+        assert!(!compare_one(three_steps, one, policy));
+    }
+
+    #[test]
+    fn auto_policy_loosens_with_precision() {
+        let policy = EpsilonPolicy::Auto(Default::default());
+
+        assert!(compare_one(1.00005f32, 1.0, policy));
+        assert!(!compare_one(1.0002f32, 1.0, policy));
+        // Relative above one, including for negative values.
+        assert!(compare_one(-100.005f32, -100.0, policy));
+        assert!(!compare_one(-100.02f32, -100.0, policy));
+        // Loosened to the f16 epsilon.
+        let (actual, expected) = (half::f16::from_f32(2.0), half::f16::from_f32(1.0));
+        assert!(compare_one(half::f16::from_f32(1.25), expected, policy));
+        assert!(!compare_one(actual, expected, policy));
+    }
+
+    #[test]
+    fn auto_policy_loosens_with_depth() {
+        // 10 000 accumulations in f32 may add up to about 1e-3 of rounding.
+        let policy = EpsilonPolicy::Auto(MatmulAccuracy::scalar::<f32>(10_000));
+        assert!(compare_one(1.0005f32, 1.0, policy));
+        assert!(!compare_one(1.002f32, 1.0, policy));
+
+        // Up to a k of about 800, the floor of 10e-5 is looser.
+        let policy = EpsilonPolicy::Auto(MatmulAccuracy::scalar::<f32>(64));
+        assert!(compare_one(1.00005f32, 1.0, policy));
+        assert!(!compare_one(1.0002f32, 1.0, policy));
+
+        // Tensor cores multiplying f16 with a k of 64 round to about 0.016.
+        let policy = EpsilonPolicy::Auto(MatmulAccuracy::tensor_cores::<half::f16, f32>(64));
+        assert!(compare_one(1.01f32, 1.0, policy));
+        assert!(!compare_one(1.02f32, 1.0, policy));
+        // Which grows with the square root of k.
+        let policy = EpsilonPolicy::Auto(MatmulAccuracy::tensor_cores::<half::f16, f32>(256));
+        assert!(compare_one(1.02f32, 1.0, policy));
+        assert!(!compare_one(1.04f32, 1.0, policy));
+    }
+
+    #[test]
+    fn rounding_modes_round_to_different_neighbours() {
+        use half::f16;
+
+        // Between 1 and the next f16, 1 + 2^-10, closer to the latter.
+        let value = 1.0 + 2f32.powi(-11) + 2f32.powi(-13);
+        let (low, high) = (1.0, 1.0 + 2f32.powi(-10));
+        let round = |value, mode| f16::from_f32_rounded(value, mode).to_f32();
+
+        assert_eq!(round(value, RoundingMode::NearestEven), high);
+        assert_eq!(round(value, RoundingMode::TowardZero), low);
+        assert_eq!(round(value, RoundingMode::Up), high);
+        assert_eq!(round(value, RoundingMode::Down), low);
+
+        assert_eq!(round(-value, RoundingMode::NearestEven), -high);
+        assert_eq!(round(-value, RoundingMode::TowardZero), -low);
+        assert_eq!(round(-value, RoundingMode::Up), -low);
+        assert_eq!(round(-value, RoundingMode::Down), -high);
+
+        // Exact values are never rounded.
+        assert_eq!(round(high, RoundingMode::TowardZero), high);
+        assert_eq!(round(high, RoundingMode::Up), high);
+    }
+
+    #[test]
+    fn rounding_modes_saturate_or_overflow() {
+        use half::{bf16, f16};
+
+        let value = 70000.0;
+
+        assert!(f16::from_f32_rounded(value, RoundingMode::NearestEven).is_infinite());
+        assert!(f16::from_f32_rounded(value, RoundingMode::Up).is_infinite());
+        assert_eq!(
+            f16::from_f32_rounded(value, RoundingMode::TowardZero),
+            f16::MAX
+        );
+        assert_eq!(f16::from_f32_rounded(-value, RoundingMode::Up), f16::MIN);
+
+        // 257 lies between the bf16 values 256 and 258, and ties to the even 256.
+        assert_eq!(
+            bf16::from_f32_rounded(257.0, RoundingMode::NearestEven).to_f32(),
+            256.0
+        );
+        assert_eq!(
+            bf16::from_f32_rounded(257.0, RoundingMode::Up).to_f32(),
+            258.0
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Incompatible batch dimensions")]
+    fn cpu_reference_rejects_incompatible_batches() {
+        let lhs = vec![0.0f32; 16];
+        let rhs = vec![0.0f32; 24];
+
+        matmul_cpu_reference::<f32, f32>(&lhs, &rhs, &problem(vec![2, 4], vec![3, 4]));
+    }
+}
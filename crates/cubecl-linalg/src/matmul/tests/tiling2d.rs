@@ -2,9 +2,11 @@ use std::fmt::Display;
 
 use cubecl_core::{prelude::Float, CubeElement, Runtime};
 
-use crate::matmul::kernels::tiling2d;
+use crate::matmul::components::MatmulProblem;
+use crate::matmul::kernels::tiling2d::{self, Tiling2dConfig};
+use crate::tensor::TensorHandle;
 
-use super::test_utils::{assert_equals_approx, MatmulTestCase};
+use super::test_utils::{assert_equals_approx, EpsilonPolicy, MatmulAccuracy, MatmulTestCase};
 
 pub fn test_one_cube<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
     let case = MatmulTestCase {
@@ -50,6 +52,80 @@ pub fn test_with_batches<R: Runtime, F: Float + CubeElement + Display>(device: &
     test_tiling2d::<R, F>(case, device);
 }
 
+/// Check that double buffering gives exactly the same output as a single buffer, with an odd
+/// number of blocks along k and bound checks on every dimension.
+pub fn test_double_buffered<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    let case = MatmulTestCase {
+        m: 60,
+        k: 200,
+        n: 60,
+        batch: 2,
+    };
+    let client = R::client(device);
+    let lhs = case.random_lhs::<R, F>(&client);
+    let rhs = case.random_rhs::<R, F>(&client);
+
+    let expected = case.matmul_cpu::<R, F>(&lhs, &rhs, &client);
+
+    let launch = |double_buffered: bool| {
+        let config = Tiling2dConfig {
+            double_buffered,
+            ..Default::default()
+        };
+        tiling2d::launch::<R, F>(
+            &client,
+            lhs.clone(),
+            rhs.clone(),
+            case.empty_out(&client),
+            config,
+        )
+    };
+    let single = launch(false);
+    let double = launch(true);
+
+    let read = |out: &TensorHandle<R, F>| client.read_one(out.handle.clone().binding());
+    assert_eq!(
+        F::from_bytes(&read(&double)),
+        F::from_bytes(&read(&single)),
+        "Double buffering changed the output"
+    );
+
+    if let Err(e) = assert_equals_approx::<R, F>(
+        &client,
+        double.handle,
+        &expected,
+        EpsilonPolicy::Auto(MatmulAccuracy::scalar::<F>(case.k)),
+        case.m,
+        case.n,
+    ) {
+        panic!("{}", e);
+    }
+}
+
+/// The config picked for a large problem fits the shared memory reported by the device.
+pub fn test_config_fits_device<R: Runtime, F: Float + CubeElement + Display>(device: &R::Device) {
+    let client = R::client(device);
+    let problem = MatmulProblem {
+        m: 4096,
+        n: 4096,
+        k: 4096,
+        batches: (vec![1], vec![1]),
+        ..Default::default()
+    };
+    let max_shared_memory_size = client
+        .properties()
+        .hardware_properties()
+        .max_shared_memory_size;
+    let elem_size = F::size().unwrap();
+
+    let config = Tiling2dConfig::from_problem::<R, F>(&client, &problem);
+
+    assert!(
+        config.shared_memory_size(elem_size) <= max_shared_memory_size,
+        "{config:?} exceeds the {max_shared_memory_size} bytes of shared memory of the device"
+    );
+}
+
 fn test_tiling2d<R: Runtime, F: Float + CubeElement + Display>(
     case: MatmulTestCase,
     device: &R::Device,
@@ -68,7 +144,14 @@ fn test_tiling2d<R: Runtime, F: Float + CubeElement + Display>(
         Default::default(),
     );
 
-    if let Err(e) = assert_equals_approx::<R, F>(&client, out.handle, &expected, 0.01) {
+    if let Err(e) = assert_equals_approx::<R, F>(
+        &client,
+        out.handle,
+        &expected,
+        EpsilonPolicy::Auto(MatmulAccuracy::scalar::<F>(case.k)),
+        case.m,
+        case.n,
+    ) {
         panic!("{}", e);
     }
 }
@@ -0,0 +1,195 @@
+use core::fmt::Display;
+
+use cubecl_core::{
+    client::ComputeClient,
+    prelude::{Numeric, TensorHandleRef},
+    Runtime,
+};
+use cubecl_runtime::tune::{AutotuneKey, LocalTuner, TunableSet};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "export_tests")]
+use std::sync::Mutex;
+
+use crate::tensor::{matrix_layout, MatrixLayout, TensorHandle};
+
+use super::{kernels::MatmulLaunchError, launch_ref, Strategy};
+
+static TUNER: LocalTuner<MatmulAutotuneKey, String> = LocalTuner::new(module_path!());
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/// Key under which the fastest matmul strategy is cached by [Strategy::Autotune].
+pub struct MatmulAutotuneKey {
+    m: usize,
+    n: usize,
+    k: usize,
+    batches: usize,
+    dtype: String,
+    lhs_transposed: bool,
+    rhs_transposed: bool,
+}
+
+impl AutotuneKey for MatmulAutotuneKey {}
+
+impl Display for MatmulAutotuneKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Matmul - m: {}, n: {}, k: {}, batches: {}, dtype: {}, lhs_transposed: {}, rhs_transposed: {}",
+            self.m,
+            self.n,
+            self.k,
+            self.batches,
+            self.dtype,
+            self.lhs_transposed,
+            self.rhs_transposed
+        )
+    }
+}
+
+impl MatmulAutotuneKey {
+    fn generate<R: Runtime, EG: Numeric>(
+        lhs: &TensorHandle<R, EG>,
+        rhs: &TensorHandle<R, EG>,
+        out: &TensorHandle<R, EG>,
+    ) -> Self {
+        let rank = out.shape.len();
+        let is_transposed = |strides: &[usize]| {
+            matches!(
+                matrix_layout(strides),
+                MatrixLayout::MildlyPermuted {
+                    transposed: true,
+                    ..
+                }
+            )
+        };
+
+        Self {
+            m: lhs.shape[rank - 2],
+            n: rhs.shape[rank - 1],
+            k: lhs.shape[rank - 1],
+            batches: out.shape[..rank - 2].iter().product(),
+            dtype: core::any::type_name::<EG>().to_string(),
+            lhs_transposed: is_transposed(&lhs.strides),
+            rhs_transposed: is_transposed(&rhs.strides),
+        }
+    }
+}
+
+/// Clear the cache of the fastest strategies found by [Strategy::Autotune].
+///
+/// The next launch of every problem will be benchmarked again.
+pub fn clear_autotune_cache() {
+    TUNER.clear();
+}
+
+/// Problems whose candidates were benchmarked, once per tuning, to check that cached problems
+/// aren't tuned again.
+#[cfg(feature = "export_tests")]
+static TUNINGS: Mutex<Vec<MatmulAutotuneKey>> = Mutex::new(Vec::new());
+
+/// Number of times the candidates were benchmarked on the problem of the given tensors.
+#[cfg(feature = "export_tests")]
+pub(crate) fn num_tunings<R: Runtime, EG: Numeric>(
+    lhs: &TensorHandle<R, EG>,
+    rhs: &TensorHandle<R, EG>,
+    out: &TensorHandle<R, EG>,
+) -> usize {
+    let key = MatmulAutotuneKey::generate::<R, EG>(lhs, rhs, out);
+    let tunings = TUNINGS.lock().unwrap();
+
+    tunings.iter().filter(|tuned| **tuned == key).count()
+}
+
+/// Benchmark the candidate strategies on the problem the first time it is seen,
+/// then launch the fastest one.
+///
+/// The candidates are benchmarked on a scratch output, so the output is only written by the
+/// launch of the fastest one. A candidate that fails to launch while benchmarking is never
+/// picked. The fastest candidate of a problem may still fail on other handles with the same key,
+/// e.g. with strides it can't read with the same line sizes, in which case the first candidate
+/// is launched instead, and its error is returned if it fails too.
+pub(super) fn launch_autotune<R: Runtime, EG: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<R>,
+    rhs: &TensorHandleRef<R>,
+    out: &TensorHandleRef<R>,
+) -> Result<(), MatmulLaunchError> {
+    let to_owned = |tensor: &TensorHandleRef<R>| {
+        TensorHandle::<R, EG>::new(
+            tensor.shape.to_vec(),
+            tensor.strides.to_vec(),
+            tensor.handle.clone(),
+        )
+    };
+
+    // Tiling2D comes first since it is always available, autotune falls back on the first
+    // candidate while results are pending.
+    let candidates = [
+        Strategy::Tiling2D(Default::default()),
+        Strategy::Standard,
+        Strategy::Pipelined,
+        Strategy::Specialized,
+        Strategy::SpecializedDoubleBuffered,
+    ];
+
+    let scratch_client = client.clone();
+    let mut tunables = TunableSet::new(
+        |lhs: &TensorHandle<R, EG>,
+         rhs: &TensorHandle<R, EG>,
+         out: &TensorHandle<R, EG>,
+         _benchmark: &bool| MatmulAutotuneKey::generate::<R, EG>(lhs, rhs, out),
+        move |_key: &MatmulAutotuneKey,
+              lhs: &TensorHandle<R, EG>,
+              rhs: &TensorHandle<R, EG>,
+              out: &TensorHandle<R, EG>,
+              _benchmark: &bool| {
+            #[cfg(feature = "export_tests")]
+            TUNINGS.lock().unwrap().push(_key.clone());
+
+            let scratch = TensorHandle::<R, EG>::new(
+                out.shape.clone(),
+                out.strides.clone(),
+                scratch_client.empty(out.handle.size() as usize),
+            );
+            (lhs.clone(), rhs.clone(), scratch, true)
+        },
+    );
+
+    for strategy in candidates.iter().cloned() {
+        let client = client.clone();
+        tunables = tunables.with_tunable(
+            move |lhs: TensorHandle<R, EG>,
+                  rhs: TensorHandle<R, EG>,
+                  out: TensorHandle<R, EG>,
+                  benchmark: bool| {
+                let result = launch_ref::<R, EG>(
+                    &strategy,
+                    &client,
+                    &lhs.as_ref(),
+                    &rhs.as_ref(),
+                    &out.as_ref(),
+                )
+                .map_err(|err| format!("{err:?}"));
+
+                // Failing while benchmarking rules the candidate out, while the failure of the
+                // selected candidate is handled below.
+                match benchmark {
+                    true => result.map(Ok),
+                    false => Ok(result),
+                }
+            },
+        );
+    }
+
+    let result: Result<(), String> = TUNER.execute(
+        &R::name().to_string(),
+        client,
+        &tunables,
+        (to_owned(lhs), to_owned(rhs), to_owned(out), false),
+    );
+
+    match result {
+        Ok(()) => Ok(()),
+        Err(_) => launch_ref::<R, EG>(&candidates[0], client, lhs, rhs, out),
+    }
+}
@@ -138,7 +138,23 @@ where
         Self::new(shape, strides, handle)
     }
 
+    /// Create a contiguous tensor of the given shape filled with zeros.
+    ///
+    /// The buffer is filled by a kernel on the device, so nothing is uploaded.
     pub fn zeros(client: &ComputeClient<R::Server, R::Channel>, shape: Vec<usize>) -> Self {
+        Self::filled(client, shape, E::from_int(0))
+    }
+
+    /// Create a contiguous tensor of the given shape filled with ones.
+    ///
+    /// The buffer is filled by a kernel on the device, so nothing is uploaded.
+    pub fn ones(client: &ComputeClient<R::Server, R::Channel>, shape: Vec<usize>) -> Self {
+        Self::filled(client, shape, E::from_int(1))
+    }
+
+    /// Create a contiguous tensor of the given shape filled with `value`, which is given to the
+    /// kernel at runtime so that every value shares the same kernel.
+    fn filled(client: &ComputeClient<R::Server, R::Channel>, shape: Vec<usize>, value: E) -> Self {
         let num_elements: usize = shape.iter().product();
         let rank = shape.len();
         let output = Self::empty(client, shape);
@@ -155,11 +171,12 @@ where
             calculate_cube_count_elemwise(num_elements / vectorization_factor as usize, cube_dim);
 
         unsafe {
-            init::zeros_array::launch_unchecked::<E, R>(
+            init::fill_array::launch_unchecked::<E, R>(
                 client,
                 cube_count,
                 cube_dim,
                 ArrayArg::from_raw_parts::<E>(&output.handle, num_elements, vectorization_factor),
+                ScalarArg::new(value),
             )
         };
 
@@ -167,14 +184,57 @@ where
     }
 }
 
+impl<R, E> TensorHandle<R, E>
+where
+    R: Runtime,
+    E: CubePrimitive + CubeElement,
+{
+    /// Create a contiguous tensor of the given shape by uploading `data`, in row-major order.
+    ///
+    /// # Panics
+    ///
+    /// If the number of elements of `data` doesn't match the shape.
+    pub fn from_data(
+        client: &ComputeClient<R::Server, R::Channel>,
+        data: &[E],
+        shape: Vec<usize>,
+    ) -> Self {
+        let num_elements: usize = shape.iter().product();
+        assert_eq!(
+            data.len(),
+            num_elements,
+            "{} elements can't fill a tensor of shape {shape:?}",
+            data.len(),
+        );
+
+        Self::new_contiguous(shape, client.create(E::as_bytes(data)))
+    }
+
+    /// Download the elements of the tensor, in row-major order.
+    ///
+    /// # Panics
+    ///
+    /// If the tensor isn't contiguous, since its buffer then isn't in row-major order.
+    pub fn to_vec(&self, client: &ComputeClient<R::Server, R::Channel>) -> Vec<E> {
+        assert_eq!(
+            self.strides,
+            Self::contiguous_strides(&self.shape),
+            "Only contiguous tensors can be downloaded"
+        );
+
+        let bytes = client.read_one(self.handle.clone().binding());
+        E::from_bytes(&bytes).to_vec()
+    }
+}
+
 pub(crate) mod init {
     use cubecl::prelude::*;
     use cubecl_core as cubecl;
 
     #[cube(launch_unchecked)]
-    pub fn zeros_array<C: Numeric>(output: &mut Array<C>) {
+    pub fn fill_array<C: Numeric>(output: &mut Array<Line<C>>, value: C) {
         if ABSOLUTE_POS < output.len() {
-            output[ABSOLUTE_POS] = C::from_int(0);
+            output[ABSOLUTE_POS] = Line::empty(output.line_size()).fill(value);
         }
     }
 }
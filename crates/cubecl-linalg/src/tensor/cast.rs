@@ -0,0 +1,96 @@
+use cubecl::frontend::TensorHandleRef;
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise, tensor_line_size_parallel};
+
+use super::TensorHandle;
+
+#[cube(launch)]
+fn cast_kernel<From: Numeric, To: Numeric>(
+    input: &Tensor<Line<From>>,
+    output: &mut Tensor<Line<To>>,
+    #[comptime] rank: u32,
+) {
+    let line_size = output.line_size();
+
+    // The position of the unit counts lines in row-major order, independently of the strides of
+    // both tensors, so it is split into coordinates starting from the last dim.
+    let mut remainder = ABSOLUTE_POS * line_size;
+    let mut offset_input = 0;
+    let mut offset_output = 0;
+    let mut dim = rank;
+
+    #[unroll]
+    for _ in 0..rank {
+        dim -= 1;
+        let shape = output.shape(dim);
+        let coordinate = remainder % shape;
+        remainder /= shape;
+
+        offset_input += coordinate * input.stride(dim);
+        offset_output += coordinate * output.stride(dim);
+    }
+
+    // Units past the last line have coordinates wrapping around the first dim.
+    if remainder == 0 {
+        output[offset_output / line_size] = Line::<To>::cast_from(input[offset_input / line_size]);
+    }
+}
+
+/// Launch the cast kernel, converting the elements of `input` into the element type of `output`.
+/// See [launch_ref].
+pub fn launch<R: Runtime, From: Numeric, To: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandle<R, From>,
+    output: &TensorHandle<R, To>,
+) {
+    launch_ref::<R, From, To>(client, &input.as_ref(), &output.as_ref());
+}
+
+/// Launch the cast kernel by ref, converting the elements of `input` into the element type of
+/// `output`, with the same conversions as [Cast::cast_from].
+///
+/// Ensure output has the shape of input. Any strides are supported, and the elements are read
+/// and written in lines when the last dim of both tensors is contiguous.
+pub fn launch_ref<R: Runtime, From: Numeric, To: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<R>,
+    output: &TensorHandleRef<R>,
+) {
+    assert_eq!(
+        input.shape, output.shape,
+        "input and output should have the same shape"
+    );
+
+    let rank = input.shape.len();
+    let num_elems: usize = input.shape.iter().product();
+    if num_elems == 0 {
+        return;
+    }
+
+    // The supported line sizes are powers of two, so the smallest one works for both tensors. A
+    // tensor of rank 0 holds a single element, which isn't split into lines.
+    let line_size = |tensor: &TensorHandleRef<R>| {
+        tensor_line_size_parallel(
+            R::supported_line_sizes().iter().cloned(),
+            tensor.shape,
+            tensor.strides,
+            rank - 1,
+        )
+    };
+    let line_size = match rank {
+        0 => 1,
+        _ => u8::min(line_size(input), line_size(output)),
+    };
+
+    let cube_dim = CubeDim::default();
+    let cube_count = calculate_cube_count_elemwise(num_elems / line_size as usize, cube_dim);
+
+    cast_kernel::launch::<From, To, R>(
+        client,
+        cube_count,
+        cube_dim,
+        input.as_tensor_arg(line_size),
+        output.as_tensor_arg(line_size),
+        rank as u32,
+    );
+}
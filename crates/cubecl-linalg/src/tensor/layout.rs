@@ -1,3 +1,5 @@
+use cubecl_core::{prelude::CubePrimitive, tensor_line_size_parallel, Runtime};
+
 #[derive(PartialEq, Eq, Debug)]
 /// Layout for matrix tensors, i.e. tensors whose interpretation
 /// is a bunch of batched matrices of 2 dimensions
@@ -62,6 +64,62 @@ pub fn matrix_layout(strides: &[usize]) -> MatrixLayout {
     }
 }
 
+/// Find the maximum line size usable along the given axis of a tensor with arbitrary strides,
+/// from the supported line sizes, or return 1 if vectorization is impossible.
+///
+/// Tightly packed tensors get the same line size as with
+/// [tensor_line_size_parallel](cubecl_core::tensor_line_size_parallel). Tensors whose other
+/// dims are padded, like a view of a sub-matrix of a larger tensor, can still be vectorized
+/// as long as every stride is a multiple of the line size.
+pub fn tensor_line_size_strided(
+    supported_line_sizes: impl Iterator<Item = u8>,
+    shape: &[usize],
+    strides: &[usize],
+    axis: usize,
+) -> u8 {
+    if strides.get(axis) != Some(&1) {
+        return 1;
+    }
+
+    let axis_shape = match shape.get(axis) {
+        Some(val) => *val,
+        None => return 1,
+    };
+
+    supported_line_sizes
+        .filter(|line_size| {
+            let line_size = *line_size as usize;
+            axis_shape % line_size == 0
+                && strides
+                    .iter()
+                    .enumerate()
+                    .all(|(i, stride)| i == axis || stride % line_size == 0)
+        })
+        .max()
+        .unwrap_or(1)
+}
+
+/// Find the largest line size supported by the runtime for elements of type `E` that can be used
+/// to read or write a tensor along its last dim, or return 1 if it can't be vectorized.
+///
+/// A line size above 1 is only returned when:
+///  - the last dim has a stride of 1 and is divisible by the line size.
+///  - the last dim is contiguous with the next one, i.e. the smallest stride above 1 equals the
+///    size of the last dim. Tensors with padded rows are therefore read element by element,
+///    see [tensor_line_size_strided] for kernels that handle arbitrary strides.
+///
+/// This is [tensor_line_size_parallel] over the line sizes of [Runtime::line_size_elem].
+pub fn best_line_size<R: Runtime, E: CubePrimitive>(shape: &[usize], strides: &[usize]) -> u8 {
+    let elem = E::as_elem_native().expect("To be a native type");
+
+    tensor_line_size_parallel(
+        R::line_size_elem(&elem),
+        shape,
+        strides,
+        shape.len().saturating_sub(1),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -120,6 +178,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn line_size_strided_matches_packed_tensors() {
+        let line_size =
+            tensor_line_size_strided([1, 2, 4].into_iter(), &[2, 8, 16], &[128, 16, 1], 2);
+        assert_eq!(line_size, 4);
+    }
+
+    #[test]
+    fn line_size_strided_vectorizes_padded_rows() {
+        // Rows of 16 elements spaced by 20, e.g. a view of the first 16 columns of 20.
+        let line_size =
+            tensor_line_size_strided([1, 2, 4].into_iter(), &[2, 8, 16], &[160, 20, 1], 2);
+        assert_eq!(line_size, 4);
+    }
+
+    #[test]
+    fn line_size_strided_is_limited_by_padding() {
+        let line_size = tensor_line_size_strided([1, 2, 4].into_iter(), &[8, 16], &[18, 1], 1);
+        assert_eq!(line_size, 2);
+    }
+
+    #[test]
+    fn line_size_strided_needs_unit_stride() {
+        let line_size = tensor_line_size_strided([1, 2, 4].into_iter(), &[16, 8], &[1, 16], 1);
+        assert_eq!(line_size, 1);
+    }
+
     #[test]
     fn layout_has_batch_swapped_with_row() {
         let strides = &[8, 2, 4, 1];
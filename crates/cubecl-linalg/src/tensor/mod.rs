@@ -1,7 +1,9 @@
 mod base;
+pub mod cast;
 mod contiguous;
 pub mod identity;
 mod layout;
+pub mod transpose;
 mod r#virtual;
 
 pub use base::*;
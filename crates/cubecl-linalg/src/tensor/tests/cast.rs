@@ -0,0 +1,40 @@
+use cubecl_core::prelude::Runtime;
+use half::bf16;
+
+use crate::tensor::{self, TensorHandle};
+
+/// Convert f32 values to bf16 and back on the device, which should only lose the precision
+/// bf16 doesn't have.
+///
+/// With `transposed`, the bf16 tensor has its last two dims transposed in memory, so that the
+/// strides of both conversions differ.
+pub fn test_round_trip_bf16<R: Runtime>(device: &R::Device, shape: &[usize], transposed: bool) {
+    let client = R::client(device);
+    let rank = shape.len();
+    let num_elements: usize = shape.iter().product();
+
+    let data: Vec<f32> = (0..num_elements)
+        .map(|i| (i as f32 * 0.37).sin() * 100.0)
+        .collect();
+    let input = TensorHandle::<R, f32>::from_data(&client, &data, shape.to_vec());
+
+    let mut half = TensorHandle::<R, bf16>::empty(&client, shape.to_vec());
+    if transposed {
+        let rows = shape[rank - 2];
+        half.strides[rank - 2] = 1;
+        half.strides[rank - 1] = rows;
+    }
+    tensor::cast::launch(&client, &input, &half);
+
+    let output = TensorHandle::<R, f32>::empty(&client, shape.to_vec());
+    tensor::cast::launch(&client, &half, &output);
+
+    // bf16 keeps 8 bits of mantissa, so rounding to nearest is off by at most half an ulp.
+    let tolerance = 2f32.powi(-8);
+    for (i, (expected, actual)) in data.iter().zip(output.to_vec(&client)).enumerate() {
+        assert!(
+            (expected - actual).abs() <= expected.abs() * tolerance,
+            "Round trip of element {i} gave {actual} instead of {expected}"
+        );
+    }
+}
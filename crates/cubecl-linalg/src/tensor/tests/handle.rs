@@ -0,0 +1,59 @@
+use std::fmt::Display;
+
+use cubecl_core::{
+    prelude::{Numeric, Runtime},
+    CubeElement,
+};
+
+use crate::tensor::TensorHandle;
+
+pub fn test_round_trip<R: Runtime, C: Numeric + CubeElement + Display>(
+    device: &R::Device,
+    shape: &[usize],
+) {
+    let client = R::client(device);
+    let num_elements: usize = shape.iter().product();
+
+    // Kept small so that every value is exact in all numeric types.
+    let data: Vec<C> = (0..num_elements)
+        .map(|i| C::from_int((i % 128) as i64))
+        .collect();
+
+    let tensor = TensorHandle::<R, C>::from_data(&client, &data, shape.to_vec());
+    let mut expected_strides = vec![1; shape.len()];
+    for i in (0..shape.len().saturating_sub(1)).rev() {
+        expected_strides[i] = expected_strides[i + 1] * shape[i + 1];
+    }
+
+    assert_eq!(tensor.shape, shape);
+    assert_eq!(tensor.strides, expected_strides);
+    assert_eq!(
+        tensor.to_vec(&client),
+        data,
+        "downloaded data is not equal."
+    );
+}
+
+pub fn test_zeros_and_ones<R: Runtime, C: Numeric + CubeElement + Display>(
+    device: &R::Device,
+    shape: &[usize],
+) {
+    let client = R::client(device);
+    let num_elements: usize = shape.iter().product();
+
+    // Overwrite the memory a previous tensor may have left uninitialized.
+    let garbage: Vec<C> = (0..num_elements).map(|_| C::from_int(7)).collect();
+    drop(TensorHandle::<R, C>::from_data(
+        &client,
+        &garbage,
+        shape.to_vec(),
+    ));
+
+    let zeros = TensorHandle::<R, C>::zeros(&client, shape.to_vec());
+    let ones = TensorHandle::<R, C>::ones(&client, shape.to_vec());
+
+    assert_eq!(zeros.shape, shape);
+    assert_eq!(ones.shape, shape);
+    assert_eq!(zeros.to_vec(&client), vec![C::from_int(0); num_elements]);
+    assert_eq!(ones.to_vec(&client), vec![C::from_int(1); num_elements]);
+}
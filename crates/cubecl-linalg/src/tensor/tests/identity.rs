@@ -19,8 +19,9 @@ pub fn test_identity<R: Runtime, C: Numeric + CubeElement + Display>(
     let identity = TensorHandle::<R, C>::empty(&client, [dim, dim].to_vec());
     tensor::identity::launch(&client, &identity);
 
-    let actual = client.read_one(identity.handle.clone().binding());
-    let actual = C::from_bytes(&actual);
-
-    assert_eq!(&expected[..], actual, "identity matrices are not equal.");
+    assert_eq!(
+        expected,
+        identity.to_vec(&client),
+        "identity matrices are not equal."
+    );
 }
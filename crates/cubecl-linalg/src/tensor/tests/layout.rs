@@ -0,0 +1,39 @@
+use cubecl_core::{prelude::CubePrimitive, Runtime};
+
+use crate::tensor::best_line_size;
+
+/// Check the line size picked for tensors of different shapes and strides, against the largest
+/// line size supported by the runtime that divides the last dim.
+pub fn test_best_line_size<R: Runtime, E: CubePrimitive>() {
+    let elem = E::as_elem_native().expect("To be a native type");
+    let largest_dividing = |dim: usize| {
+        R::line_size_elem(&elem)
+            .filter(|line_size| dim % *line_size as usize == 0)
+            .max()
+            .unwrap_or(1)
+    };
+
+    let cases: [(&[usize], &[usize], u8); 8] = [
+        // Contiguous tensors are vectorized as much as the last dim allows.
+        (&[4, 64], &[64, 1], largest_dividing(64)),
+        (&[2, 3, 6], &[18, 6, 1], largest_dividing(6)),
+        (&[256], &[1], largest_dividing(256)),
+        // An odd last dim can only be read element by element.
+        (&[3, 5], &[5, 1], 1),
+        // The last dim isn't the contiguous one.
+        (&[8, 16], &[1, 8], 1),
+        // Padded rows aren't contiguous with the next dim.
+        (&[8, 16], &[20, 1], 1),
+        // Broadcasted along the last dim.
+        (&[4, 8], &[1, 0], 1),
+        (&[], &[], 1),
+    ];
+
+    for (shape, strides, expected) in cases {
+        assert_eq!(
+            best_line_size::<R, E>(shape, strides),
+            expected,
+            "shape {shape:?} with strides {strides:?}"
+        );
+    }
+}
@@ -1,4 +1,8 @@
+pub mod cast;
+pub mod handle;
 pub mod identity;
+pub mod layout;
+pub mod transpose;
 
 mod test_macros;
 mod test_utils;
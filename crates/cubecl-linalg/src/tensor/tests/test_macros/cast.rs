@@ -0,0 +1,55 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_tensor_cast {
+    () => {
+        mod cast {
+            use super::*;
+
+            #[test]
+            pub fn test_round_trip_bf16_rank_0() {
+                cubecl_linalg::tensor::tests::cast::test_round_trip_bf16::<TestRuntime>(
+                    &Default::default(),
+                    &[],
+                    false,
+                );
+            }
+
+            #[test]
+            pub fn test_round_trip_bf16_rank_1() {
+                cubecl_linalg::tensor::tests::cast::test_round_trip_bf16::<TestRuntime>(
+                    &Default::default(),
+                    &[1000],
+                    false,
+                );
+            }
+
+            #[test]
+            pub fn test_round_trip_bf16_non_power_of_two() {
+                cubecl_linalg::tensor::tests::cast::test_round_trip_bf16::<TestRuntime>(
+                    &Default::default(),
+                    &[33, 70],
+                    false,
+                );
+            }
+
+            #[test]
+            pub fn test_round_trip_bf16_transposed() {
+                cubecl_linalg::tensor::tests::cast::test_round_trip_bf16::<TestRuntime>(
+                    &Default::default(),
+                    &[16, 64],
+                    true,
+                );
+            }
+
+            #[test]
+            pub fn test_round_trip_bf16_batched_transposed() {
+                cubecl_linalg::tensor::tests::cast::test_round_trip_bf16::<TestRuntime>(
+                    &Default::default(),
+                    &[2, 3, 5, 7],
+                    true,
+                );
+            }
+        }
+    };
+}
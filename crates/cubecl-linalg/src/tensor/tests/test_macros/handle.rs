@@ -0,0 +1,59 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_tensor_handle {
+    () => {
+        mod handle {
+            $crate::testgen_tensor_handle!(f32);
+        }
+    };
+    ($numeric:ident) => {
+            use super::*;
+            use cubecl_linalg::tensor::tests;
+            use cubecl_core::flex32;
+
+            pub type NumericT = $numeric;
+
+            #[test]
+            pub fn test_round_trip_rank_1() {
+                cubecl_linalg::tensor::tests::handle::test_round_trip::<TestRuntime, NumericT>(&Default::default(), &[100]);
+            }
+
+            #[test]
+            pub fn test_round_trip_rank_2() {
+                cubecl_linalg::tensor::tests::handle::test_round_trip::<TestRuntime, NumericT>(&Default::default(), &[16, 33]);
+            }
+
+            #[test]
+            pub fn test_round_trip_rank_3() {
+                cubecl_linalg::tensor::tests::handle::test_round_trip::<TestRuntime, NumericT>(&Default::default(), &[3, 8, 5]);
+            }
+
+            #[test]
+            pub fn test_round_trip_rank_4() {
+                cubecl_linalg::tensor::tests::handle::test_round_trip::<TestRuntime, NumericT>(&Default::default(), &[2, 3, 5, 7]);
+            }
+
+            #[test]
+            pub fn test_zeros_and_ones_rank_1() {
+                cubecl_linalg::tensor::tests::handle::test_zeros_and_ones::<TestRuntime, NumericT>(&Default::default(), &[100]);
+            }
+
+            #[test]
+            pub fn test_zeros_and_ones_rank_3() {
+                cubecl_linalg::tensor::tests::handle::test_zeros_and_ones::<TestRuntime, NumericT>(&Default::default(), &[3, 8, 5]);
+            }
+    };
+    ([$($numeric:ident),*]) => {
+        mod handle {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$numeric _ty>] {
+                    use super::*;
+
+                    $crate::testgen_tensor_handle!($numeric);
+                })*
+            }
+        }
+    };
+}
@@ -0,0 +1,33 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_tensor_layout {
+    () => {
+        mod layout {
+            $crate::testgen_tensor_layout!(f32);
+        }
+    };
+    ($numeric:ident) => {
+            use super::*;
+            use cubecl_core::flex32;
+
+            pub type NumericT = $numeric;
+
+            #[test]
+            pub fn test_best_line_size() {
+                cubecl_linalg::tensor::tests::layout::test_best_line_size::<TestRuntime, NumericT>();
+            }
+    };
+    ([$($numeric:ident),*]) => {
+        mod layout {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$numeric _ty>] {
+                    use super::*;
+
+                    $crate::testgen_tensor_layout!($numeric);
+                })*
+            }
+        }
+    };
+}
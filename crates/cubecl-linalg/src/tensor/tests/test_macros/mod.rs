@@ -1 +1,5 @@
+mod cast;
+mod handle;
 mod identity;
+mod layout;
+mod transpose;
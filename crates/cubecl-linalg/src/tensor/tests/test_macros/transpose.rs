@@ -0,0 +1,59 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_tensor_transpose {
+    () => {
+        mod transpose {
+            $crate::testgen_tensor_transpose!(f32);
+        }
+    };
+    ($numeric:ident) => {
+            use super::*;
+            use cubecl_linalg::tensor::tests;
+            use cubecl_core::flex32;
+
+            pub type NumericT = $numeric;
+
+            #[test]
+            pub fn test_square() {
+                cubecl_linalg::tensor::tests::transpose::test_transpose::<TestRuntime, NumericT>(&Default::default(), &[32, 32]);
+            }
+
+            #[test]
+            pub fn test_non_square() {
+                cubecl_linalg::tensor::tests::transpose::test_transpose::<TestRuntime, NumericT>(&Default::default(), &[16, 64]);
+            }
+
+            #[test]
+            pub fn test_non_power_of_two() {
+                cubecl_linalg::tensor::tests::transpose::test_transpose::<TestRuntime, NumericT>(&Default::default(), &[33, 70]);
+            }
+
+            #[test]
+            pub fn test_single_row() {
+                cubecl_linalg::tensor::tests::transpose::test_transpose::<TestRuntime, NumericT>(&Default::default(), &[1, 100]);
+            }
+
+            #[test]
+            pub fn test_batched() {
+                cubecl_linalg::tensor::tests::transpose::test_transpose::<TestRuntime, NumericT>(&Default::default(), &[2, 3, 5, 7]);
+            }
+
+            #[test]
+            pub fn test_more_batches_than_cubes() {
+                cubecl_linalg::tensor::tests::transpose::test_transpose_more_batches_than_cubes::<TestRuntime, NumericT>(&Default::default());
+            }
+    };
+    ([$($numeric:ident),*]) => {
+        mod transpose {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$numeric _ty>] {
+                    use super::*;
+
+                    $crate::testgen_tensor_transpose!($numeric);
+                })*
+            }
+        }
+    };
+}
@@ -0,0 +1,57 @@
+use std::fmt::Display;
+
+use cubecl_core::{
+    prelude::{Numeric, Runtime},
+    CubeElement,
+};
+
+use crate::matmul::tests::test_utils::transpose;
+use crate::tensor::{self, TensorHandle};
+
+pub fn test_transpose<R: Runtime, C: Numeric + CubeElement + Display>(
+    device: &R::Device,
+    shape: &[usize],
+) {
+    let client = R::client(device);
+
+    let rank = shape.len();
+    let (rows, cols) = (shape[rank - 2], shape[rank - 1]);
+    let num_batches = shape[..rank - 2].iter().product();
+    let num_elements: usize = shape.iter().product();
+
+    // Kept small so that every value is exact in all numeric types.
+    let data: Vec<C> = (0..num_elements)
+        .map(|i| C::from_int((i % 128) as i64))
+        .collect();
+    let expected = transpose(&data, num_batches, rows, cols);
+
+    let mut out_shape = shape.to_vec();
+    out_shape.swap(rank - 2, rank - 1);
+
+    let input = TensorHandle::<R, C>::from_data(&client, &data, shape.to_vec());
+    let output = TensorHandle::<R, C>::empty(&client, out_shape);
+    tensor::transpose::launch(&client, &input, &output);
+
+    assert_eq!(
+        expected,
+        output.to_vec(&client),
+        "transposed matrices are not equal."
+    );
+}
+
+/// More matrices than cubes can be launched along z, so cubes loop over the batches.
+pub fn test_transpose_more_batches_than_cubes<R: Runtime, C: Numeric + CubeElement + Display>(
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let max_cubes_z = client.properties().hardware_properties().max_cube_count.2;
+    let Some(num_batches) = max_cubes_z
+        .checked_add(1)
+        .filter(|num_batches| *num_batches <= 1 << 20)
+    else {
+        println!("Can't run test when the grid can't be exceeded along z.");
+        return;
+    };
+
+    test_transpose::<R, C>(device, &[num_batches as usize, 2, 3]);
+}
@@ -0,0 +1,138 @@
+use cubecl::frontend::TensorHandleRef;
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+use super::TensorHandle;
+
+/// Number of rows and columns of the tile staged in shared memory by each cube.
+const TILE_SIZE: u32 = 16;
+
+#[cube(launch_unchecked)]
+fn transpose_kernel<E: Numeric>(
+    input: &Tensor<E>,
+    output: &mut Tensor<E>,
+    #[comptime] tile_size: u32,
+) {
+    let rank = output.rank();
+    let rows = input.shape(rank - 2);
+    let cols = input.shape(rank - 1);
+
+    let mut num_batches = 1;
+    for axis in 0..rank - 2 {
+        num_batches *= output.shape(axis);
+    }
+
+    // The tile rows are padded by one element to avoid bank conflicts when reading columns.
+    let mut tile = SharedMemory::<E>::new(tile_size * (tile_size + 1));
+
+    let row = CUBE_POS_Y * tile_size + UNIT_POS_Y;
+    let col = CUBE_POS_X * tile_size + UNIT_POS_X;
+
+    // Units are swapped when writing, so that consecutive units write consecutive elements.
+    let out_row = CUBE_POS_X * tile_size + UNIT_POS_Y;
+    let out_col = CUBE_POS_Y * tile_size + UNIT_POS_X;
+
+    // Each cube along z handles every CUBE_COUNT_Z-th matrix, since there can be more matrices
+    // than cubes along z. The index of a matrix is decomposed over the leading dims.
+    let mut batch_index = CUBE_POS_Z;
+
+    while batch_index < num_batches {
+        let mut batch = batch_index;
+        let mut input_offset = 0;
+        let mut output_offset = 0;
+
+        for i in 0..rank - 2 {
+            let axis = rank - 3 - i;
+            let coordinate = batch % output.shape(axis);
+            batch /= output.shape(axis);
+            input_offset += coordinate * input.stride(axis);
+            output_offset += coordinate * output.stride(axis);
+        }
+
+        if row < rows && col < cols {
+            tile[UNIT_POS_Y * (tile_size + 1) + UNIT_POS_X] =
+                input[input_offset + row * input.stride(rank - 2) + col * input.stride(rank - 1)];
+        }
+
+        sync_units();
+
+        if out_row < cols && out_col < rows {
+            output[output_offset
+                + out_row * output.stride(rank - 2)
+                + out_col * output.stride(rank - 1)] =
+                tile[UNIT_POS_X * (tile_size + 1) + UNIT_POS_Y];
+        }
+
+        // The tile is only refilled once every unit has read it.
+        sync_units();
+
+        batch_index += CUBE_COUNT_Z;
+    }
+}
+
+/// Launch the transpose kernel, swapping the last two dims of `input` into `output`.
+/// See [launch_ref].
+pub fn launch<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandle<R, E>,
+    output: &TensorHandle<R, E>,
+) {
+    launch_ref::<R, E>(client, &input.as_ref(), &output.as_ref());
+}
+
+/// Launch the transpose kernel by ref, swapping the last two dims of `input` into `output`.
+///
+/// Ensure output has the shape of input with its last two dims swapped. Leading dims are
+/// treated as batches, and cubes loop over them when there are more batches than the device
+/// can launch cubes along z. Any strides are supported, but a contiguous output is written
+/// coalesced since each cube first stages a tile of the input in shared memory.
+pub fn launch_ref<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<R>,
+    output: &TensorHandleRef<R>,
+) {
+    let rank = input.shape.len();
+    assert!(rank >= 2, "input should have at least two dims");
+    assert_eq!(
+        rank,
+        output.shape.len(),
+        "input and output should have the same rank"
+    );
+    assert_eq!(
+        input.shape[..rank - 2],
+        output.shape[..rank - 2],
+        "input and output should have the same batches"
+    );
+    assert!(
+        input.shape[rank - 2] == output.shape[rank - 1]
+            && input.shape[rank - 1] == output.shape[rank - 2],
+        "output should have the last two dims of input swapped"
+    );
+
+    let rows = input.shape[rank - 2] as u32;
+    let cols = input.shape[rank - 1] as u32;
+    let num_batches: usize = input.shape[..rank - 2].iter().product();
+
+    if rows == 0 || cols == 0 || num_batches == 0 {
+        return;
+    }
+
+    let max_cubes_z = client.properties().hardware_properties().max_cube_count.2;
+    let cube_dim = CubeDim::new(TILE_SIZE, TILE_SIZE, 1);
+    let cube_count = CubeCount::Static(
+        cols.div_ceil(TILE_SIZE),
+        rows.div_ceil(TILE_SIZE),
+        (num_batches as u32).min(max_cubes_z),
+    );
+
+    unsafe {
+        transpose_kernel::launch_unchecked::<E, R>(
+            client,
+            cube_count,
+            cube_dim,
+            input.as_tensor_arg(1),
+            output.as_tensor_arg(1),
+            TILE_SIZE,
+        );
+    }
+}
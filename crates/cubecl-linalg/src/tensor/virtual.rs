@@ -33,6 +33,15 @@ impl<E: Numeric, IO: Clone> VirtualTensor<E, IO> {
     pub fn read(&self, index: u32) -> Line<E> {
         unexpanded!();
     }
+    /// Read the tensor at the given 64-bit index, to address tensors of more than `u32::MAX`
+    /// lines.
+    pub fn read_wide(&self, index: u64) -> Line<E> {
+        unexpanded!();
+    }
+    /// Get a slice of the tensor between the given indices, e.g. to copy it asynchronously.
+    pub fn read_window(&self, start: u32, end: u32) -> Slice<Line<E>> {
+        unexpanded!();
+    }
     /// Get the shape of the tensor at the given axis.
     pub fn shape(&self, axis: u32) -> u32 {
         unexpanded!();
@@ -45,6 +54,22 @@ impl<E: Numeric, IO: Clone> VirtualTensor<E, IO> {
     pub fn rank(&self) -> u32 {
         unexpanded!();
     }
+    /// Get a view of the rows `row_start..row_end` and the columns `col_start..col_end` of the
+    /// tensor, which are its last two axes, in every batch.
+    ///
+    /// The view keeps the strides of the tensor and only shifts where it starts, so a matmul on
+    /// the view works on that block of a larger tensor. The tensor must be read with lines of
+    /// `line_size` elements, and the block must start on a line.
+    pub fn slice_2d(
+        &self,
+        row_start: u32,
+        row_end: u32,
+        col_start: u32,
+        col_end: u32,
+        #[comptime] line_size: u32,
+    ) -> VirtualTensor<E, IO> {
+        unexpanded!();
+    }
     pub fn __expand_read(
         scope: &mut Scope,
         this: <Self as CubeType>::ExpandType,
@@ -52,6 +77,21 @@ impl<E: Numeric, IO: Clone> VirtualTensor<E, IO> {
     ) -> <Line<E> as CubeType>::ExpandType {
         this.__expand_read_method(scope, index)
     }
+    pub fn __expand_read_wide(
+        scope: &mut Scope,
+        this: <Self as CubeType>::ExpandType,
+        index: <u64 as CubeType>::ExpandType,
+    ) -> <Line<E> as CubeType>::ExpandType {
+        this.__expand_read_wide_method(scope, index)
+    }
+    pub fn __expand_read_window(
+        scope: &mut Scope,
+        this: <Self as CubeType>::ExpandType,
+        start: <u32 as CubeType>::ExpandType,
+        end: <u32 as CubeType>::ExpandType,
+    ) -> <Slice<Line<E>> as CubeType>::ExpandType {
+        this.__expand_read_window_method(scope, start, end)
+    }
     pub fn __expand_shape(
         scope: &mut Scope,
         this: <Self as CubeType>::ExpandType,
@@ -72,6 +112,20 @@ impl<E: Numeric, IO: Clone> VirtualTensor<E, IO> {
     ) -> <u32 as CubeType>::ExpandType {
         this.__expand_rank_method(scope)
     }
+    pub fn __expand_slice_2d(
+        scope: &mut Scope,
+        this: <Self as CubeType>::ExpandType,
+        row_start: <u32 as CubeType>::ExpandType,
+        row_end: <u32 as CubeType>::ExpandType,
+        col_start: <u32 as CubeType>::ExpandType,
+        col_end: <u32 as CubeType>::ExpandType,
+        line_size: u32,
+    ) -> <Self as CubeType>::ExpandType
+    where
+        IO: 'static,
+    {
+        this.__expand_slice_2d_method(scope, row_start, row_end, col_start, col_end, line_size)
+    }
 }
 
 #[allow(unused, clippy::all)]
@@ -87,6 +141,25 @@ impl<E: Numeric, IO: Clone> VirtualTensorExpand<E, IO> {
             .__expand_read_method(scope, _arg_0.into())
     }
 
+    pub fn __expand_read_wide_method(
+        self,
+        scope: &mut Scope,
+        index: <u64 as CubeType>::ExpandType,
+    ) -> <Line<E> as CubeType>::ExpandType {
+        self.state.clone().__expand_read_wide_method(scope, index)
+    }
+
+    pub fn __expand_read_window_method(
+        self,
+        scope: &mut Scope,
+        start: <u32 as CubeType>::ExpandType,
+        end: <u32 as CubeType>::ExpandType,
+    ) -> <Slice<Line<E>> as CubeType>::ExpandType {
+        self.state
+            .clone()
+            .__expand_read_window_method(scope, start, end)
+    }
+
     pub fn __expand_shape_method(
         self,
         scope: &mut Scope,
@@ -113,6 +186,39 @@ impl<E: Numeric, IO: Clone> VirtualTensorExpand<E, IO> {
         self.state.clone().__expand_rank_method(scope)
     }
 
+    pub fn __expand_slice_2d_method(
+        self,
+        scope: &mut Scope,
+        row_start: <u32 as CubeType>::ExpandType,
+        row_end: <u32 as CubeType>::ExpandType,
+        col_start: <u32 as CubeType>::ExpandType,
+        col_end: <u32 as CubeType>::ExpandType,
+        line_size: u32,
+    ) -> Self
+    where
+        IO: 'static,
+    {
+        let offset = slice_2d_offset::expand::<E, IO>(
+            scope,
+            self.clone(),
+            row_start.clone(),
+            col_start.clone(),
+            line_size,
+        );
+        let rows = extent::expand(scope, row_start, row_end);
+        let cols = extent::expand(scope, col_start, col_end);
+
+        VirtualTensorExpand {
+            state: Arc::new(Slice2dExpand {
+                tensor: self,
+                offset,
+                rows,
+                cols,
+            }),
+            _p: PhantomData,
+        }
+    }
+
     pub fn __expand_read(
         scope: &mut Scope,
         this: Self,
@@ -121,6 +227,23 @@ impl<E: Numeric, IO: Clone> VirtualTensorExpand<E, IO> {
         VirtualTensor::<E, IO>::__expand_read(scope, this, index)
     }
 
+    pub fn __expand_read_wide(
+        scope: &mut Scope,
+        this: Self,
+        index: <u64 as CubeType>::ExpandType,
+    ) -> <Line<E> as CubeType>::ExpandType {
+        VirtualTensor::<E, IO>::__expand_read_wide(scope, this, index)
+    }
+
+    pub fn __expand_read_window(
+        scope: &mut Scope,
+        this: Self,
+        start: <u32 as CubeType>::ExpandType,
+        end: <u32 as CubeType>::ExpandType,
+    ) -> <Slice<Line<E>> as CubeType>::ExpandType {
+        VirtualTensor::<E, IO>::__expand_read_window(scope, this, start, end)
+    }
+
     pub fn __expand_shape(
         scope: &mut Scope,
         this: Self,
@@ -237,6 +360,14 @@ pub trait VirtualTensorOperations<E: Numeric> {
     fn read(&self, _index: u32) -> Line<E> {
         unexpanded!()
     }
+    /// Read the tensor at the given 64-bit index.
+    fn read_wide(&self, _index: u64) -> Line<E> {
+        unexpanded!()
+    }
+    /// Get a slice of the tensor between the given indices.
+    fn read_window(&self, _start: u32, _end: u32) -> Slice<Line<E>> {
+        unexpanded!()
+    }
     /// Write the tensor at the given index.
     fn write(&self, _index: u32, _value: Line<E>) {
         unexpanded!()
@@ -265,6 +396,17 @@ pub trait VirtualTensorOperationsExpand<E: Numeric> {
         scope: &mut Scope,
         index: ExpandElementTyped<u32>,
     ) -> ExpandElementTyped<Line<E>>;
+    fn __expand_read_wide_method(
+        &self,
+        scope: &mut Scope,
+        index: ExpandElementTyped<u64>,
+    ) -> ExpandElementTyped<Line<E>>;
+    fn __expand_read_window_method(
+        &self,
+        scope: &mut Scope,
+        start: ExpandElementTyped<u32>,
+        end: ExpandElementTyped<u32>,
+    ) -> ExpandElementTyped<Slice<Line<E>>>;
     fn __expand_write_method(
         &self,
         scope: &mut Scope,
@@ -319,6 +461,24 @@ mod __tensor {
             self.clone().__expand_index_unchecked_method(scope, index)
         }
 
+        fn __expand_read_wide_method(
+            &self,
+            scope: &mut Scope,
+            index: ExpandElementTyped<u64>,
+        ) -> ExpandElementTyped<Line<E>> {
+            self.clone()
+                .__expand_index_unchecked_wide_method(scope, index)
+        }
+
+        fn __expand_read_window_method(
+            &self,
+            scope: &mut Scope,
+            start: ExpandElementTyped<u32>,
+            end: ExpandElementTyped<u32>,
+        ) -> ExpandElementTyped<Slice<Line<E>>> {
+            self.__expand_slice_method(scope, start, end)
+        }
+
         fn __expand_write_method(
             &self,
             scope: &mut Scope,
@@ -350,3 +510,123 @@ mod __tensor {
         }
     }
 }
+
+/// View of a block of the last two axes of a [virtual tensor](VirtualTensor), see
+/// [VirtualTensor::slice_2d].
+struct Slice2dExpand<E: Numeric, IO> {
+    tensor: VirtualTensorExpand<E, IO>,
+    /// Offset of the block in lines.
+    offset: ExpandElementTyped<u32>,
+    rows: ExpandElementTyped<u32>,
+    cols: ExpandElementTyped<u32>,
+}
+
+#[cube]
+fn slice_2d_offset<E: Numeric, IO: Clone>(
+    tensor: &VirtualTensor<E, IO>,
+    row_start: u32,
+    col_start: u32,
+    #[comptime] line_size: u32,
+) -> u32 {
+    let rank = tensor.rank();
+    (row_start * tensor.stride(rank - 2) + col_start * tensor.stride(rank - 1)) / line_size
+}
+
+#[cube]
+fn slice_2d_shape<E: Numeric, IO: Clone>(
+    tensor: &VirtualTensor<E, IO>,
+    axis: u32,
+    rows: u32,
+    cols: u32,
+) -> u32 {
+    let rank = tensor.rank();
+    select(
+        axis + 2 == rank,
+        rows,
+        select(axis + 1 == rank, cols, tensor.shape(axis)),
+    )
+}
+
+#[cube]
+fn extent(start: u32, end: u32) -> u32 {
+    end - start
+}
+
+#[cube]
+fn shift(index: u32, offset: u32) -> u32 {
+    index + offset
+}
+
+#[cube]
+fn shift_wide(index: u64, offset: u32) -> u64 {
+    index + u64::cast_from(offset)
+}
+
+impl<E: Numeric, IO: Clone> VirtualTensorOperationsExpand<E> for Slice2dExpand<E, IO> {
+    fn __expand_read_method(
+        &self,
+        scope: &mut Scope,
+        index: ExpandElementTyped<u32>,
+    ) -> ExpandElementTyped<Line<E>> {
+        let index = shift::expand(scope, index, self.offset.clone());
+        self.tensor.state.__expand_read_method(scope, index)
+    }
+
+    fn __expand_read_wide_method(
+        &self,
+        scope: &mut Scope,
+        index: ExpandElementTyped<u64>,
+    ) -> ExpandElementTyped<Line<E>> {
+        let index = shift_wide::expand(scope, index, self.offset.clone());
+        self.tensor.state.__expand_read_wide_method(scope, index)
+    }
+
+    fn __expand_read_window_method(
+        &self,
+        scope: &mut Scope,
+        start: ExpandElementTyped<u32>,
+        end: ExpandElementTyped<u32>,
+    ) -> ExpandElementTyped<Slice<Line<E>>> {
+        let start = shift::expand(scope, start, self.offset.clone());
+        let end = shift::expand(scope, end, self.offset.clone());
+        self.tensor
+            .state
+            .__expand_read_window_method(scope, start, end)
+    }
+
+    fn __expand_write_method(
+        &self,
+        scope: &mut Scope,
+        index: ExpandElementTyped<u32>,
+        value: ExpandElementTyped<Line<E>>,
+    ) {
+        let index = shift::expand(scope, index, self.offset.clone());
+        self.tensor.state.__expand_write_method(scope, index, value)
+    }
+
+    fn __expand_shape_method(
+        &self,
+        scope: &mut Scope,
+        axis: ExpandElementTyped<u32>,
+    ) -> ExpandElementTyped<u32> {
+        slice_2d_shape::expand::<E, IO>(
+            scope,
+            self.tensor.clone(),
+            axis,
+            self.rows.clone(),
+            self.cols.clone(),
+        )
+    }
+
+    fn __expand_stride_method(
+        &self,
+        scope: &mut Scope,
+        axis: ExpandElementTyped<u32>,
+    ) -> ExpandElementTyped<u32> {
+        self.tensor.state.__expand_stride_method(scope, axis)
+    }
+
+    fn __expand_rank_method(&self, scope: &mut Scope) -> ExpandElementTyped<u32> {
+        self.tensor.state.__expand_rank_method(scope)
+    }
+}
@@ -202,6 +202,7 @@ impl Launch {
 
     fn define_body(&self) -> TokenStream {
         let kernel_builder = prelude_type("KernelBuilder");
+        let fast_math = prelude_type("FastMath");
         let io_map = self.io_mappings();
         let register_type = self.analysis.register_elems();
         let runtime_args = self.runtime_params().map(|it| &it.name);
@@ -210,6 +211,11 @@ impl Launch {
 
         quote! {
             let mut builder = #kernel_builder::default();
+            builder.context.allow_reciprocal = self
+                .settings
+                .options
+                .fp_math_mode
+                .contains(#fast_math::AllowReciprocal);
             #register_type
             #io_map
             expand #generics(&mut builder.context, #(#runtime_args.clone(),)* #(self.#comptime_args.clone()),*);
@@ -134,6 +134,9 @@ impl Launch {
             use #core_path::frontend::ArgSettings as _;
 
             #settings
+            let __settings = __settings.max_shared_memory_size(
+                __client.properties().hardware_properties().max_shared_memory_size,
+            );
             #compilation_args
 
             let __kernel = #kernel_name #kernel_generics::new(__settings, #args #(#comptime_args),*);
@@ -71,6 +71,7 @@ impl Optimizer {
             | Arithmetic::Mul(binary_operator)
             | Arithmetic::Div(binary_operator)
             | Arithmetic::Powf(binary_operator)
+            | Arithmetic::Atan2(binary_operator)
             | Arithmetic::Modulo(binary_operator)
             | Arithmetic::Max(binary_operator)
             | Arithmetic::Min(binary_operator)
@@ -78,15 +79,28 @@ impl Optimizer {
             | Arithmetic::Dot(binary_operator) => self.visit_binop(binary_operator, visit_read),
 
             Arithmetic::Abs(unary_operator)
+            | Arithmetic::Signum(unary_operator)
             | Arithmetic::Exp(unary_operator)
             | Arithmetic::Log(unary_operator)
             | Arithmetic::Log1p(unary_operator)
             | Arithmetic::Cos(unary_operator)
             | Arithmetic::Sin(unary_operator)
             | Arithmetic::Tanh(unary_operator)
+            | Arithmetic::Log2(unary_operator)
+            | Arithmetic::Exp2(unary_operator)
+            | Arithmetic::Sinh(unary_operator)
+            | Arithmetic::Cosh(unary_operator)
+            | Arithmetic::Asinh(unary_operator)
+            | Arithmetic::Acosh(unary_operator)
+            | Arithmetic::Atanh(unary_operator)
+            | Arithmetic::Tan(unary_operator)
+            | Arithmetic::Asin(unary_operator)
+            | Arithmetic::Acos(unary_operator)
+            | Arithmetic::Atan(unary_operator)
             | Arithmetic::Sqrt(unary_operator)
             | Arithmetic::Round(unary_operator)
             | Arithmetic::Floor(unary_operator)
+            | Arithmetic::Trunc(unary_operator)
             | Arithmetic::Ceil(unary_operator)
             | Arithmetic::Erf(unary_operator)
             | Arithmetic::Recip(unary_operator)
@@ -139,6 +153,7 @@ impl Optimizer {
             | Bitwise::BitwiseNot(unary_operator)
             | Bitwise::ReverseBits(unary_operator)
             | Bitwise::LeadingZeros(unary_operator)
+            | Bitwise::TrailingZeros(unary_operator)
             | Bitwise::FindFirstSet(unary_operator) => self.visit_unop(unary_operator, visit_read),
         }
     }
@@ -1,6 +1,6 @@
 use cubecl_ir::{
-    Arithmetic, Bitwise, Comparison, ConstantScalarValue, Instruction, Metadata, Operation,
-    Operator, UIntKind, Variable, VariableKind,
+    Arithmetic, Bitwise, Comparison, ConstantScalarValue, Instruction, IntKind, Metadata,
+    Operation, Operator, UIntKind, Variable, VariableKind,
 };
 
 use crate::{
@@ -312,6 +312,7 @@ fn try_const_eval_arithmetic(op: &mut Arithmetic) -> Option<ConstantScalarValue>
         Arithmetic::Mul(op) => const_eval!(*op.lhs, op.rhs),
         Arithmetic::Div(op) => const_eval!(/ op.lhs, op.rhs),
         Arithmetic::Powf(op) => const_eval_float!(op.lhs, op.rhs; num::Float::powf),
+        Arithmetic::Atan2(op) => const_eval_float!(op.lhs, op.rhs; num::Float::atan2),
         Arithmetic::Modulo(op) => const_eval!(% op.lhs, op.rhs),
         Arithmetic::Remainder(op) => const_eval!(% op.lhs, op.rhs),
         Arithmetic::Max(op) => {
@@ -355,8 +356,19 @@ fn try_const_eval_arithmetic(op: &mut Arithmetic) -> Option<ConstantScalarValue>
         Arithmetic::Abs(op) => {
             use ConstantScalarValue::*;
             op.input.as_const().map(|input| match input {
-                Int(input, kind) => ConstantScalarValue::Int(input.abs(), kind),
+                Int(input, kind) => {
+                    ConstantScalarValue::Int(wrap_int(input.wrapping_abs(), kind), kind)
+                }
                 Float(input, kind) => ConstantScalarValue::Float(input.abs(), kind),
+                UInt(input, kind) => ConstantScalarValue::UInt(input, kind),
+                _ => unreachable!(),
+            })
+        }
+        Arithmetic::Signum(op) => {
+            use ConstantScalarValue::*;
+            op.input.as_const().map(|input| match input {
+                Int(input, kind) => ConstantScalarValue::Int(input.signum(), kind),
+                UInt(input, kind) => ConstantScalarValue::UInt(input.min(1), kind),
                 _ => unreachable!(),
             })
         }
@@ -366,9 +378,21 @@ fn try_const_eval_arithmetic(op: &mut Arithmetic) -> Option<ConstantScalarValue>
         Arithmetic::Cos(op) => const_eval_float!(op.input; num::Float::cos),
         Arithmetic::Sin(op) => const_eval_float!(op.input; num::Float::sin),
         Arithmetic::Tanh(op) => const_eval_float!(op.input; num::Float::tanh),
+        Arithmetic::Log2(op) => const_eval_float!(op.input; num::Float::log2),
+        Arithmetic::Exp2(op) => const_eval_float!(op.input; num::Float::exp2),
+        Arithmetic::Sinh(op) => const_eval_float!(op.input; num::Float::sinh),
+        Arithmetic::Cosh(op) => const_eval_float!(op.input; num::Float::cosh),
+        Arithmetic::Asinh(op) => const_eval_float!(op.input; num::Float::asinh),
+        Arithmetic::Acosh(op) => const_eval_float!(op.input; num::Float::acosh),
+        Arithmetic::Atanh(op) => const_eval_float!(op.input; num::Float::atanh),
+        Arithmetic::Tan(op) => const_eval_float!(op.input; num::Float::tan),
+        Arithmetic::Asin(op) => const_eval_float!(op.input; num::Float::asin),
+        Arithmetic::Acos(op) => const_eval_float!(op.input; num::Float::acos),
+        Arithmetic::Atan(op) => const_eval_float!(op.input; num::Float::atan),
         Arithmetic::Sqrt(op) => const_eval_float!(op.input; num::Float::sqrt),
-        Arithmetic::Round(op) => const_eval_float!(op.input; num::Float::round),
+        Arithmetic::Round(op) => const_eval_float!(op.input; f64::round_ties_even),
         Arithmetic::Floor(op) => const_eval_float!(op.input; num::Float::floor),
+        Arithmetic::Trunc(op) => const_eval_float!(op.input; num::Float::trunc),
         Arithmetic::Ceil(op) => const_eval_float!(op.input; num::Float::ceil),
         Arithmetic::Recip(op) => const_eval_float!(op.input; num::Float::recip),
         Arithmetic::Neg(op) => {
@@ -450,14 +474,7 @@ fn try_const_eval_bitwise(op: &mut Bitwise) -> Option<ConstantScalarValue> {
                 _ => unreachable!(),
             })
         }
-        Bitwise::CountOnes(op) => {
-            use ConstantScalarValue::*;
-            op.input.as_const().map(|input| match input {
-                Int(input, kind) => ConstantScalarValue::Int(input.count_ones() as i64, kind),
-                UInt(input, kind) => ConstantScalarValue::UInt(input.count_ones() as u64, kind),
-                _ => unreachable!(),
-            })
-        }
+        Bitwise::CountOnes(op) => const_eval_bit_count(op.input, |bits, _| bits.count_ones()),
         Bitwise::ReverseBits(op) => {
             use ConstantScalarValue::*;
             op.input.as_const().map(|input| match input {
@@ -466,13 +483,52 @@ fn try_const_eval_bitwise(op: &mut Bitwise) -> Option<ConstantScalarValue> {
                 _ => unreachable!(),
             })
         }
-        Bitwise::LeadingZeros(_) | Bitwise::FindFirstSet(_) => {
-            // Depends too much on type width and Rust semantics, leave this one out of const eval
-            None
+        Bitwise::LeadingZeros(op) => {
+            const_eval_bit_count(op.input, |bits, width| bits.leading_zeros() - (64 - width))
+        }
+        Bitwise::TrailingZeros(op) => {
+            const_eval_bit_count(op.input, |bits, width| bits.trailing_zeros().min(width))
         }
+        Bitwise::FindFirstSet(op) => const_eval_bit_count(op.input, |bits, _| match bits {
+            0 => 0,
+            bits => bits.trailing_zeros() + 1,
+        }),
+    }
+}
+
+/// Wraps a signed value around to the range of its kind, as two's complement hardware does.
+fn wrap_int(value: i64, kind: IntKind) -> i64 {
+    match kind {
+        IntKind::I8 => value as i8 as i64,
+        IntKind::I16 => value as i16 as i64,
+        IntKind::I32 => value as i32 as i64,
+        IntKind::I64 => value,
     }
 }
 
+/// Evaluates a bit counting operation on the two's complement representation of the input,
+/// truncated to its own width. The result is always a `u32`.
+fn const_eval_bit_count(
+    input: Variable,
+    count: impl Fn(u64, u32) -> u32,
+) -> Option<ConstantScalarValue> {
+    use ConstantScalarValue::*;
+
+    let input = input.as_const()?;
+    let width = input.elem().size() as u32 * 8;
+    let bits = match input {
+        Int(input, _) => input as u64,
+        UInt(input, _) => input,
+        _ => unreachable!(),
+    };
+    let bits = match width {
+        64 => bits,
+        width => bits & ((1 << width) - 1),
+    };
+
+    Some(UInt(count(bits, width) as u64, UIntKind::U32))
+}
+
 fn try_const_eval_operator(op: &mut Operator) -> Option<ConstantScalarValue> {
     match op {
         Operator::And(op) => const_eval_bool!(&&op.lhs, op.rhs),
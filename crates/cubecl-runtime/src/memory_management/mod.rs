@@ -103,6 +103,14 @@ pub struct HardwareProperties {
     pub plane_size_max: u32,
     /// minimum number of bindings for a kernel that can be used at once.
     pub max_bindings: u32,
+    /// The maximum amount of shared memory a single cube can allocate, in bytes.
+    pub max_shared_memory_size: usize,
+    /// The maximum number of cubes that can be launched along each axis.
+    pub max_cube_count: (u32, u32, u32),
+    /// The maximum number of units of a cube along each axis.
+    pub max_cube_dim: (u32, u32, u32),
+    /// The maximum number of units of a cube, across all axes.
+    pub max_units_per_cube: u32,
 }
 
 impl HardwareProperties {
@@ -39,6 +39,10 @@ pub fn init_client() -> ComputeClient<DummyServer, MutexComputeChannel<DummyServ
         plane_size_min: 32,
         plane_size_max: 32,
         max_bindings: 32,
+        max_shared_memory_size: 32768,
+        max_cube_count: (u16::MAX as u32, u16::MAX as u32, u16::MAX as u32),
+        max_cube_dim: (1024, 1024, 64),
+        max_units_per_cube: 1024,
     };
     let memory_management = MemoryManagement::from_configuration(
         storage,
@@ -60,6 +60,17 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
                     b.i_sub(ty, Some(out), width, msb).unwrap();
                 });
             }
+            Bitwise::TrailingZeros(op) => {
+                let width = op.input.item.elem.size() as u32 * 8;
+                self.compile_unary_op_cast(op, out, uniform, |b, out_ty, ty, input, out| {
+                    let width = out_ty.const_u32(b, width);
+                    let lsb = b.id();
+                    T::find_lsb(b, ty, input, lsb);
+                    b.mark_uniformity(lsb, uniform);
+                    // `FindILsb` returns -1 for zero, which saturates to the width as unsigned
+                    T::u_min(b, ty, lsb, width, out);
+                });
+            }
             Bitwise::FindFirstSet(op) => {
                 self.compile_unary_op_cast(op, out, uniform, |b, out_ty, ty, input, out| {
                     let one = out_ty.const_u32(b, 1);
@@ -124,6 +135,22 @@ pub(crate) fn u64_leading_zeros<I: Int>(x: Line<I>, out: &mut Line<u32>) {
     );
 }
 
+#[cube]
+pub(crate) fn u64_trailing_zeros<I: Int>(x: Line<I>, out: &mut Line<u32>) {
+    let shift = Line::new(I::new(32));
+
+    let low = Line::<u32>::cast_from(x);
+    let high = Line::<u32>::cast_from(x >> shift);
+    let low_zeros = Line::trailing_zeros(low);
+    let high_zeros = Line::trailing_zeros(high);
+
+    *out = select_many(
+        low_zeros.equal(Line::new(32)),
+        low_zeros + high_zeros,
+        low_zeros,
+    );
+}
+
 /// There are three possible outcomes:
 /// * low has any set -> return low
 /// * low is empty, high has any set -> return high + 32
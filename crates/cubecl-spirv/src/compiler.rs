@@ -10,7 +10,7 @@ use std::{
     rc::Rc,
 };
 
-use cubecl_core::{compute::KernelDefinition, Compiler};
+use cubecl_core::{compute::KernelDefinition, CompilationError, Compiler};
 use rspirv::{
     dr::{Builder, InsertPoint, Instruction, Module, Operand},
     spirv::{self, BuiltIn, Capability, Decoration, FPFastMathMode, Op, StorageClass, Word},
@@ -163,6 +163,23 @@ impl<T: SpirvTarget> Compiler for SpirvCompiler<T> {
         }
     }
 
+    fn try_compile(
+        value: KernelDefinition,
+        compilation_options: &Self::CompilationOptions,
+        mode: ExecutionMode,
+    ) -> Result<Self::Representation, CompilationError> {
+        if let Some(elem) = value.find_elem(|elem| {
+            matches!(
+                elem,
+                core::Elem::Float(core::FloatKind::E4M3 | core::FloatKind::E5M2)
+                    | core::Elem::AtomicFloat(core::FloatKind::E4M3 | core::FloatKind::E5M2)
+            )
+        }) {
+            return Err(CompilationError::UnsupportedElem(elem));
+        }
+        Ok(Self::compile(value, compilation_options, mode))
+    }
+
     fn elem_size(elem: core::Elem) -> usize {
         elem.size()
     }
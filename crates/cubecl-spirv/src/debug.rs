@@ -330,8 +330,8 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
                     operands.extend(args);
                     self.ext_inst(void, None, ext, 1, operands).unwrap();
                 }
-                core::NonSemantic::Comment { .. } => {
-                    // Comments not supported for SPIR-V
+                core::NonSemantic::Comment { .. } | core::NonSemantic::DebugName { .. } => {
+                    // Comments not supported for SPIR-V, and variables are named by the lookups
                 }
             };
         }
@@ -18,11 +18,25 @@ pub trait TargetExtensions<T: SpirvTarget> {
     fn round(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
     fn f_abs(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
     fn s_abs(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
+    fn s_sign(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
     fn floor(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
+    fn trunc(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
     fn ceil(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
     fn sin(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
     fn cos(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
     fn tanh(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
+    fn log2(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
+    fn exp2(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
+    fn sinh(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
+    fn cosh(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
+    fn asinh(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
+    fn acosh(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
+    fn atanh(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
+    fn tan(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
+    fn asin(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
+    fn acos(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
+    fn atan(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
+    fn atan2(b: &mut SpirvCompiler<T>, ty: Word, lhs: Word, rhs: Word, out: Word);
     fn pow(b: &mut SpirvCompiler<T>, ty: Word, lhs: Word, rhs: Word, out: Word);
     fn exp(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
     fn log(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
@@ -64,7 +78,8 @@ pub mod glcompute {
 
     impl<T: SpirvTarget> TargetExtensions<T> for GLCompute {
         fn round(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word) {
-            ext_op(b, ty, out, GLSLstd450Round, [input]);
+            // `Round` breaks ties in an implementation defined direction.
+            ext_op(b, ty, out, GLSLstd450RoundEven, [input]);
         }
 
         fn f_abs(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word) {
@@ -75,10 +90,18 @@ pub mod glcompute {
             ext_op(b, ty, out, GLSLstd450SAbs, [input]);
         }
 
+        fn s_sign(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word) {
+            ext_op(b, ty, out, GLSLstd450SSign, [input]);
+        }
+
         fn floor(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word) {
             ext_op(b, ty, out, GLSLstd450Floor, [input]);
         }
 
+        fn trunc(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word) {
+            ext_op(b, ty, out, GLSLstd450Trunc, [input]);
+        }
+
         fn ceil(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word) {
             ext_op(b, ty, out, GLSLstd450Ceil, [input]);
         }
@@ -95,6 +118,54 @@ pub mod glcompute {
             ext_op(b, ty, out, GLSLstd450Tanh, [input]);
         }
 
+        fn log2(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word) {
+            ext_op(b, ty, out, GLSLstd450Log2, [input]);
+        }
+
+        fn exp2(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word) {
+            ext_op(b, ty, out, GLSLstd450Exp2, [input]);
+        }
+
+        fn sinh(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word) {
+            ext_op(b, ty, out, GLSLstd450Sinh, [input]);
+        }
+
+        fn cosh(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word) {
+            ext_op(b, ty, out, GLSLstd450Cosh, [input]);
+        }
+
+        fn asinh(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word) {
+            ext_op(b, ty, out, GLSLstd450Asinh, [input]);
+        }
+
+        fn acosh(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word) {
+            ext_op(b, ty, out, GLSLstd450Acosh, [input]);
+        }
+
+        fn atanh(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word) {
+            ext_op(b, ty, out, GLSLstd450Atanh, [input]);
+        }
+
+        fn tan(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word) {
+            ext_op(b, ty, out, GLSLstd450Tan, [input]);
+        }
+
+        fn asin(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word) {
+            ext_op(b, ty, out, GLSLstd450Asin, [input]);
+        }
+
+        fn acos(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word) {
+            ext_op(b, ty, out, GLSLstd450Acos, [input]);
+        }
+
+        fn atan(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word) {
+            ext_op(b, ty, out, GLSLstd450Atan, [input]);
+        }
+
+        fn atan2(b: &mut SpirvCompiler<T>, ty: Word, lhs: Word, rhs: Word, out: Word) {
+            ext_op(b, ty, out, GLSLstd450Atan2, [lhs, rhs]);
+        }
+
         fn pow(b: &mut SpirvCompiler<T>, ty: Word, lhs: Word, rhs: Word, out: Word) {
             ext_op(b, ty, out, GLSLstd450Pow, [lhs, rhs]);
         }
@@ -312,6 +312,9 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
                 Elem::Float(16)
             }
             core::Elem::Float(FloatKind::TF32) => panic!("TF32 not supported in SPIR-V"),
+            core::Elem::Float(FloatKind::E4M3 | FloatKind::E5M2) => {
+                panic!("FP8 not supported in SPIR-V")
+            }
             core::Elem::Float(FloatKind::Flex32) => Elem::Relaxed,
             core::Elem::Float(FloatKind::F32) => Elem::Float(32),
             core::Elem::Float(FloatKind::F64) => {
@@ -334,6 +337,9 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
             core::Elem::AtomicFloat(core::FloatKind::TF32) => {
                 panic!("TF32 not supported in SPIR-V")
             }
+            core::Elem::AtomicFloat(FloatKind::E4M3 | FloatKind::E5M2) => {
+                panic!("FP8 not supported in SPIR-V")
+            }
             core::Elem::Int(IntKind::I8) => {
                 self.capabilities.insert(Capability::Int8);
                 Elem::Int(8, true)
@@ -7,7 +7,9 @@ use cubecl_core::{
 };
 use cubecl_opt::{IrTransformer, TransformAction};
 
-use crate::bitwise::{small_int_reverse, u64_count_bits, u64_ffs, u64_leading_zeros, u64_reverse};
+use crate::bitwise::{
+    small_int_reverse, u64_count_bits, u64_ffs, u64_leading_zeros, u64_reverse, u64_trailing_zeros,
+};
 
 /// Expand erf
 #[derive(Debug)]
@@ -78,6 +80,16 @@ impl IrTransformer for BitwiseTransform {
                 );
                 TransformAction::Replace(into_instructions(scope))
             }
+            Bitwise::TrailingZeros(op) if is_u64(op.input) => {
+                let mut scope = scope.child();
+                scope.register_elem::<IntExpand<0>>(op.input.elem());
+                u64_trailing_zeros::expand::<IntExpand<0>>(
+                    &mut scope,
+                    ExpandElement::Plain(op.input).into(),
+                    ExpandElement::Plain(inst.out()).into(),
+                );
+                TransformAction::Replace(into_instructions(scope))
+            }
             Bitwise::FindFirstSet(op) if is_u64(op.input) => {
                 let mut scope = scope.child();
                 scope.register_elem::<IntExpand<0>>(op.input.elem());
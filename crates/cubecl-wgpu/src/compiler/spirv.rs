@@ -395,6 +395,17 @@ fn register_cmma(
         })
         .collect::<Vec<_>>();
     log::debug!("Supported CMMA sizes: {sizes:#?}");
+    if sizes.iter().any(|size| {
+        matches!(
+            size,
+            Feature::Cmma {
+                a: Elem::Int(_) | Elem::UInt(_),
+                ..
+            }
+        )
+    }) {
+        props.register_feature(Feature::CmmaInt);
+    }
     for size in sizes {
         props.register_feature(size);
     }
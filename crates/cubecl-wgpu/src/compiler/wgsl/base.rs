@@ -293,7 +293,11 @@ impl Display for Variable {
                     _ => unimplemented!("{:?} not supported in WGSL", kind),
                 },
                 ConstantScalarValue::Float(val, kind) => match kind {
-                    FloatKind::F16 | FloatKind::BF16 | FloatKind::TF32 => {
+                    FloatKind::E4M3
+                    | FloatKind::E5M2
+                    | FloatKind::F16
+                    | FloatKind::BF16
+                    | FloatKind::TF32 => {
                         todo!("Unsupported")
                     }
                     FloatKind::F32 | FloatKind::Flex32 | FloatKind::F64 => {
@@ -14,7 +14,7 @@ use cubecl_core::{
     ir::{self as cube, Scope, UIntKind},
     prelude::{expand_checked_index_assign, expand_erf, CompiledKernel},
     server::ComputeServer,
-    Feature, Metadata,
+    CompilationError, Feature, Metadata,
 };
 use cubecl_runtime::DeviceProperties;
 use wgpu::{
@@ -75,6 +75,23 @@ impl cubecl_core::Compiler for WgslCompiler {
         compiler.compile_shader(shader, mode)
     }
 
+    fn try_compile(
+        shader: compute::KernelDefinition,
+        compilation_options: &Self::CompilationOptions,
+        mode: ExecutionMode,
+    ) -> Result<Self::Representation, CompilationError> {
+        if let Some(elem) = shader.find_elem(|elem| {
+            matches!(
+                elem,
+                cube::Elem::Float(cube::FloatKind::E4M3 | cube::FloatKind::E5M2)
+                    | cube::Elem::AtomicFloat(cube::FloatKind::E4M3 | cube::FloatKind::E5M2)
+            )
+        }) {
+            return Err(CompilationError::UnsupportedElem(elem));
+        }
+        Ok(Self::compile(shader, compilation_options, mode))
+    }
+
     fn elem_size(elem: cube::Elem) -> usize {
         Self::compile_elem(elem).size()
     }
@@ -322,6 +339,8 @@ impl WgslCompiler {
                 cube::FloatKind::F16 => panic!("f16 is not yet supported"),
                 cube::FloatKind::BF16 => panic!("bf16 is not a valid WgpuElement"),
                 cube::FloatKind::TF32 => panic!("tf32 is not a valid WgpuElement"),
+                cube::FloatKind::E4M3 => panic!("e4m3 is not a valid WgpuElement"),
+                cube::FloatKind::E5M2 => panic!("e5m2 is not a valid WgpuElement"),
                 cube::FloatKind::Flex32 => wgsl::Elem::F32,
                 cube::FloatKind::F32 => wgsl::Elem::F32,
                 cube::FloatKind::F64 => panic!("f64 is not a valid WgpuElement"),
@@ -832,6 +851,10 @@ impl WgslCompiler {
                 input: self.compile_variable(op.input),
                 out: self.compile_variable(out),
             }),
+            cube::Arithmetic::Signum(op) => instructions.push(wgsl::Instruction::Signum {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(out),
+            }),
             cube::Arithmetic::Exp(op) => instructions.push(wgsl::Instruction::Exp {
                 input: self.compile_variable(op.input),
                 out: self.compile_variable(out),
@@ -856,6 +879,55 @@ impl WgslCompiler {
                 input: self.compile_variable(op.input),
                 out: self.compile_variable(out),
             }),
+            cube::Arithmetic::Log2(op) => instructions.push(wgsl::Instruction::Log2 {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(out),
+            }),
+            cube::Arithmetic::Exp2(op) => instructions.push(wgsl::Instruction::Exp2 {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(out),
+            }),
+            cube::Arithmetic::Sinh(op) => instructions.push(wgsl::Instruction::Sinh {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(out),
+            }),
+            cube::Arithmetic::Cosh(op) => instructions.push(wgsl::Instruction::Cosh {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(out),
+            }),
+            cube::Arithmetic::Asinh(op) => instructions.push(wgsl::Instruction::Asinh {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(out),
+            }),
+            cube::Arithmetic::Acosh(op) => instructions.push(wgsl::Instruction::Acosh {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(out),
+            }),
+            cube::Arithmetic::Atanh(op) => instructions.push(wgsl::Instruction::Atanh {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(out),
+            }),
+            cube::Arithmetic::Tan(op) => instructions.push(wgsl::Instruction::Tan {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(out),
+            }),
+            cube::Arithmetic::Asin(op) => instructions.push(wgsl::Instruction::Asin {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(out),
+            }),
+            cube::Arithmetic::Acos(op) => instructions.push(wgsl::Instruction::Acos {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(out),
+            }),
+            cube::Arithmetic::Atan(op) => instructions.push(wgsl::Instruction::Atan {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(out),
+            }),
+            cube::Arithmetic::Atan2(op) => instructions.push(wgsl::Instruction::Atan2 {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(out),
+            }),
             cube::Arithmetic::Powf(op) => instructions.push(wgsl::Instruction::Powf {
                 lhs: self.compile_variable(op.lhs),
                 rhs: self.compile_variable(op.rhs),
@@ -873,6 +945,10 @@ impl WgslCompiler {
                 input: self.compile_variable(op.input),
                 out: self.compile_variable(out),
             }),
+            cube::Arithmetic::Trunc(op) => instructions.push(wgsl::Instruction::Trunc {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(out),
+            }),
             cube::Arithmetic::Ceil(op) => instructions.push(wgsl::Instruction::Ceil {
                 input: self.compile_variable(op.input),
                 out: self.compile_variable(out),
@@ -1009,6 +1085,12 @@ impl WgslCompiler {
                 input: self.compile_variable(op.input),
                 out: self.compile_variable(out),
             }),
+            cube::Bitwise::TrailingZeros(op) => {
+                instructions.push(wgsl::Instruction::TrailingZeros {
+                    input: self.compile_variable(op.input),
+                    out: self.compile_variable(out),
+                })
+            }
             cube::Bitwise::FindFirstSet(op) => instructions.push(wgsl::Instruction::FindFirstSet {
                 input: self.compile_variable(op.input),
                 out: self.compile_variable(out),
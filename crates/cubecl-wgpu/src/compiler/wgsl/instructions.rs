@@ -104,6 +104,10 @@ pub enum Instruction {
         input: Variable,
         out: Variable,
     },
+    Signum {
+        input: Variable,
+        out: Variable,
+    },
     Exp {
         input: Variable,
         out: Variable,
@@ -128,11 +132,60 @@ pub enum Instruction {
         input: Variable,
         out: Variable,
     },
+    Log2 {
+        input: Variable,
+        out: Variable,
+    },
+    Exp2 {
+        input: Variable,
+        out: Variable,
+    },
+    Sinh {
+        input: Variable,
+        out: Variable,
+    },
+    Cosh {
+        input: Variable,
+        out: Variable,
+    },
+    Asinh {
+        input: Variable,
+        out: Variable,
+    },
+    Acosh {
+        input: Variable,
+        out: Variable,
+    },
+    Atanh {
+        input: Variable,
+        out: Variable,
+    },
+    Tan {
+        input: Variable,
+        out: Variable,
+    },
+    Asin {
+        input: Variable,
+        out: Variable,
+    },
+    Acos {
+        input: Variable,
+        out: Variable,
+    },
+    Atan {
+        input: Variable,
+        out: Variable,
+    },
     Powf {
         lhs: Variable,
         rhs: Variable,
         out: Variable,
     },
+    Atan2 {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
     Sqrt {
         input: Variable,
         out: Variable,
@@ -256,6 +309,10 @@ pub enum Instruction {
         input: Variable,
         out: Variable,
     },
+    TrailingZeros {
+        input: Variable,
+        out: Variable,
+    },
     FindFirstSet {
         input: Variable,
         out: Variable,
@@ -268,6 +325,10 @@ pub enum Instruction {
         input: Variable,
         out: Variable,
     },
+    Trunc {
+        input: Variable,
+        out: Variable,
+    },
     Ceil {
         input: Variable,
         out: Variable,
@@ -602,6 +663,15 @@ impl Display for Instruction {
                 let out = out.fmt_left();
                 writeln!(f, "{out} = abs({input});")
             }
+            Instruction::Signum { input, out } => {
+                let item = out.item();
+                let out = out.fmt_left();
+                match item.elem() {
+                    // `sign` isn't defined on unsigned integers
+                    Elem::U32 => writeln!(f, "{out} = min({input}, {item}(1u));"),
+                    _ => writeln!(f, "{out} = sign({input});"),
+                }
+            }
             Instruction::Exp { input, out } => {
                 let out = out.fmt_left();
                 writeln!(f, "{out} = exp({input});")
@@ -621,6 +691,10 @@ impl Display for Instruction {
                 let out = out.fmt_left();
                 writeln!(f, "{out} = clamp({input}, {min}, {max});")
             }
+            Instruction::Atan2 { lhs, rhs, out } => {
+                let out = out.fmt_left();
+                writeln!(f, "{out} = atan2({lhs}, {rhs});")
+            }
             Instruction::Powf { lhs, rhs, out } => {
                 if rhs.is_always_scalar() || rhs.item().vectorization_factor() == 1 {
                     let out = out.fmt_left();
@@ -655,6 +729,50 @@ impl Display for Instruction {
 
                 result
             }
+            Instruction::Log2 { input, out } => {
+                let out = out.fmt_left();
+                writeln!(f, "{out} = log2({input});")
+            }
+            Instruction::Exp2 { input, out } => {
+                let out = out.fmt_left();
+                writeln!(f, "{out} = exp2({input});")
+            }
+            Instruction::Sinh { input, out } => {
+                let out = out.fmt_left();
+                writeln!(f, "{out} = sinh({input});")
+            }
+            Instruction::Cosh { input, out } => {
+                let out = out.fmt_left();
+                writeln!(f, "{out} = cosh({input});")
+            }
+            Instruction::Asinh { input, out } => {
+                let out = out.fmt_left();
+                writeln!(f, "{out} = asinh({input});")
+            }
+            Instruction::Acosh { input, out } => {
+                let out = out.fmt_left();
+                writeln!(f, "{out} = acosh({input});")
+            }
+            Instruction::Atanh { input, out } => {
+                let out = out.fmt_left();
+                writeln!(f, "{out} = atanh({input});")
+            }
+            Instruction::Tan { input, out } => {
+                let out = out.fmt_left();
+                writeln!(f, "{out} = tan({input});")
+            }
+            Instruction::Asin { input, out } => {
+                let out = out.fmt_left();
+                writeln!(f, "{out} = asin({input});")
+            }
+            Instruction::Acos { input, out } => {
+                let out = out.fmt_left();
+                writeln!(f, "{out} = acos({input});")
+            }
+            Instruction::Atan { input, out } => {
+                let out = out.fmt_left();
+                writeln!(f, "{out} = atan({input});")
+            }
             Instruction::Recip { input, out } => {
                 let out = out.fmt_left();
                 write!(f, "{out} = 1.0 / {input};")
@@ -865,6 +983,18 @@ for (var {i}: {i_ty} = {start}; {i} {cmp} {end}; {increment}) {{
                 let out = out.fmt_left();
                 writeln!(f, "{out} = countLeadingZeros({input});")
             }
+            Instruction::TrailingZeros { input, out } => {
+                let u32_ty = match input.item() {
+                    Item::Vec4(_) => Item::Vec4(Elem::U32),
+                    Item::Vec3(_) => Item::Vec3(Elem::U32),
+                    Item::Vec2(_) => Item::Vec2(Elem::U32),
+                    Item::Scalar(_) => Item::Scalar(Elem::U32),
+                };
+
+                let input = input.fmt_cast_to(u32_ty);
+                let out = out.fmt_left();
+                writeln!(f, "{out} = countTrailingZeros({input});")
+            }
             Instruction::FindFirstSet { input, out } => {
                 let u32_ty = match input.item() {
                     Item::Vec4(_) => Item::Vec4(Elem::U32),
@@ -885,6 +1015,10 @@ for (var {i}: {i_ty} = {start}; {i} {cmp} {end}; {increment}) {{
                 let out = out.fmt_left();
                 writeln!(f, "{out} = floor({input});")
             }
+            Instruction::Trunc { input, out } => {
+                let out = out.fmt_left();
+                writeln!(f, "{out} = trunc({input});")
+            }
             Instruction::Ceil { input, out } => {
                 let out = out.fmt_left();
                 writeln!(f, "{out} = ceil({input});")
@@ -31,7 +31,12 @@ mod tests {
     cubecl_linalg::testgen_matmul_accelerated!([f32]);
     cubecl_linalg::testgen_matmul_tiling2d!([flex32, f32]);
     cubecl_linalg::testgen_matmul_simple!([flex32, f32]);
+    cubecl_linalg::testgen_matmul_reduce!([flex32, f32]);
+    cubecl_linalg::testgen_matmul_split_k!([f32]);
     cubecl_linalg::testgen_tensor_identity!([flex32, f32, u32]);
+    cubecl_linalg::testgen_tensor_transpose!([flex32, f32, u32]);
+    cubecl_linalg::testgen_tensor_handle!([flex32, f32, u32]);
+    cubecl_linalg::testgen_tensor_layout!([flex32, f32, u32]);
     cubecl_reduce::testgen_reduce!();
     cubecl_reduce::testgen_shared_sum!([f32]);
 }
@@ -47,6 +52,8 @@ mod tests_spirv {
     cubecl_linalg::testgen_matmul_plane!([f16, f32]);
     cubecl_linalg::testgen_matmul_tiling2d!([f16, f32, f64]);
     cubecl_linalg::testgen_matmul_simple!([f32]);
+    cubecl_linalg::testgen_matmul_reduce!([f32]);
+    cubecl_linalg::testgen_matmul_split_k!([f32]);
     cubecl_linalg::testgen_matmul_accelerated!([f16]);
     cubecl_reduce::testgen_reduce!();
     cubecl_reduce::testgen_shared_sum!([f32]);
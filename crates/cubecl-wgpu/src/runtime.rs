@@ -178,6 +178,18 @@ pub(crate) fn create_client_on_setup<C: WgpuCompiler>(
         plane_size_min: adapter_limits.min_subgroup_size,
         plane_size_max: adapter_limits.max_subgroup_size,
         max_bindings: limits.max_storage_buffers_per_shader_stage,
+        max_shared_memory_size: limits.max_compute_workgroup_storage_size as usize,
+        max_cube_count: (
+            limits.max_compute_workgroups_per_dimension,
+            limits.max_compute_workgroups_per_dimension,
+            limits.max_compute_workgroups_per_dimension,
+        ),
+        max_cube_dim: (
+            limits.max_compute_workgroup_size_x,
+            limits.max_compute_workgroup_size_y,
+            limits.max_compute_workgroup_size_z,
+        ),
+        max_units_per_cube: limits.max_compute_invocations_per_workgroup,
     };
 
     let mut compilation_options = Default::default();
@@ -0,0 +1,86 @@
+use cubecl::{frontend, prelude::*};
+use std::marker::PhantomData;
+
+#[cfg(feature = "cuda")]
+use half::f16;
+
+use cubecl::benchmark::{Benchmark, TimingMethod};
+use cubecl::future;
+use cubecl_linalg::tensor::TensorHandle;
+
+/// How the tensor is filled with zeros.
+#[derive(Clone, Copy, Debug)]
+enum FillKind {
+    /// With [TensorHandle::zeros], which fills the buffer with a kernel on the device.
+    Device,
+    /// By uploading zeros from the host.
+    Upload,
+}
+
+impl<R: Runtime, E: Numeric + CubeElement> Benchmark for FillBench<R, E> {
+    type Args = Vec<E>;
+
+    fn prepare(&self) -> Self::Args {
+        match self.kind {
+            FillKind::Device => Vec::new(),
+            FillKind::Upload => vec![E::from_int(0); self.shape.iter().product()],
+        }
+    }
+
+    fn execute(&self, host: Self::Args) {
+        let tensor = match self.kind {
+            FillKind::Device => TensorHandle::<R, E>::zeros(&self.client, self.shape.clone()),
+            FillKind::Upload => TensorHandle::from_data(&self.client, &host, self.shape.clone()),
+        };
+        // Wait before the buffer is freed, so that the next fill can't reuse it right away.
+        self.sync();
+        drop(tensor);
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "fill-{}-{}-{:?}",
+            R::name(),
+            E::as_elem_native_unchecked(),
+            self.kind
+        )
+        .to_lowercase()
+    }
+
+    fn sync(&self) {
+        future::block_on(self.client.sync())
+    }
+}
+
+#[allow(dead_code)]
+struct FillBench<R: Runtime, E> {
+    shape: Vec<usize>,
+    kind: FillKind,
+    client: ComputeClient<R::Server, R::Channel>,
+    _e: PhantomData<E>,
+}
+
+#[allow(dead_code)]
+fn run<R: Runtime, E: frontend::Numeric + CubeElement>(device: R::Device) {
+    let client = R::client(&device);
+
+    for kind in [FillKind::Device, FillKind::Upload] {
+        let bench = FillBench::<R, E> {
+            shape: vec![64, 1024, 1024],
+            kind,
+            client: client.clone(),
+            _e: PhantomData,
+        };
+        println!("{}", bench.name());
+        println!("{}", bench.run(TimingMethod::Full));
+    }
+}
+
+fn main() {
+    #[cfg(feature = "cuda")]
+    run::<cubecl::cuda::CudaRuntime, f16>(Default::default());
+    #[cfg(feature = "cuda")]
+    run::<cubecl::cuda::CudaRuntime, f32>(Default::default());
+    #[cfg(feature = "wgpu")]
+    run::<cubecl::wgpu::WgpuRuntime, f32>(Default::default());
+}
@@ -84,6 +84,87 @@ fn run<R: Runtime, E: Float>(device: R::Device, strategy: matmul::Strategy) {
     }
 }
 
+/// Compares the single and double buffered Tiling2D kernels on a problem with a large k, where
+/// the loads of the blocks along k dominate.
+#[allow(dead_code)]
+fn run_tiling2d_large_k<R: Runtime, E: Float>(device: R::Device) {
+    let client = R::client(&device);
+
+    for double_buffered in [false, true] {
+        let config = matmul::kernels::tiling2d::Tiling2dConfig {
+            double_buffered,
+            ..Default::default()
+        };
+        let bench = MatmulBench::<R, E> {
+            b: 1,
+            m: 1024,
+            k: 16384,
+            n: 1024,
+            client: client.clone(),
+            device: device.clone(),
+            strategy: matmul::Strategy::Tiling2D(config),
+            _e: PhantomData,
+        };
+        println!("b: 1 m: 1024 n: 1024 k: 16384");
+        println!("{}", bench.name());
+        println!("{}", bench.run(TimingMethod::Full));
+    }
+}
+
+/// Compares the standard matmul with and without prefetching the next stages along k, on a
+/// problem with a large k where the loads of the stages dominate.
+#[allow(dead_code)]
+fn run_prefetching_large_k<R: Runtime, E: Float>(device: R::Device) {
+    let client = R::client(&device);
+
+    for strategy in [
+        matmul::Strategy::Standard,
+        matmul::Strategy::StandardPrefetching { depth: 2 },
+        matmul::Strategy::StandardPrefetching { depth: 3 },
+    ] {
+        let bench = MatmulBench::<R, E> {
+            b: 1,
+            m: 1024,
+            k: 16384,
+            n: 1024,
+            client: client.clone(),
+            device: device.clone(),
+            strategy,
+            _e: PhantomData,
+        };
+        println!("b: 1 m: 1024 n: 1024 k: 16384");
+        println!("{}", bench.name());
+        println!("{}", bench.run(TimingMethod::Full));
+    }
+}
+
+/// Compares the standard matmul with and without swizzling the cubes over the stages of the
+/// output, on a problem with a large n where the cubes of a row of stages don't share their rhs
+/// columns in the L2 cache.
+#[allow(dead_code)]
+fn run_swizzled_large_n<R: Runtime, E: Float>(device: R::Device) {
+    let client = R::client(&device);
+
+    for strategy in [
+        matmul::Strategy::Standard,
+        matmul::Strategy::StandardSwizzled,
+    ] {
+        let bench = MatmulBench::<R, E> {
+            b: 1,
+            m: 4096,
+            k: 1024,
+            n: 16384,
+            client: client.clone(),
+            device: device.clone(),
+            strategy,
+            _e: PhantomData,
+        };
+        println!("b: 1 m: 4096 n: 16384 k: 1024");
+        println!("{}", bench.name());
+        println!("{}", bench.run(TimingMethod::Full));
+    }
+}
+
 fn main() {
     #[cfg(feature = "wgpu")]
     {
@@ -91,6 +172,7 @@ fn main() {
             Default::default(),
             matmul::Strategy::Tiling2D(Default::default()),
         );
+        run_tiling2d_large_k::<cubecl::wgpu::WgpuRuntime, f32>(Default::default());
         run::<cubecl::wgpu::WgpuRuntime, f32>(Default::default(), matmul::Strategy::PlaneMma);
     }
 
@@ -101,12 +183,26 @@ fn main() {
 
         run::<R, f16>(Default::default(), matmul::Strategy::Standard);
         run::<R, f16>(Default::default(), matmul::Strategy::Specialized);
+        run::<R, f16>(
+            Default::default(),
+            matmul::Strategy::SpecializedDoubleBuffered,
+        );
         run::<R, f16>(Default::default(), matmul::Strategy::Pipelined);
+        run_prefetching_large_k::<R, f16>(Default::default());
+        run_swizzled_large_n::<R, f16>(Default::default());
         run::<R, flex32>(Default::default(), matmul::Strategy::Standard);
         run::<R, flex32>(Default::default(), matmul::Strategy::Specialized);
+        run::<R, flex32>(
+            Default::default(),
+            matmul::Strategy::SpecializedDoubleBuffered,
+        );
         run::<R, flex32>(Default::default(), matmul::Strategy::Pipelined);
         run::<R, f32>(Default::default(), matmul::Strategy::Standard);
         run::<R, f32>(Default::default(), matmul::Strategy::Specialized);
+        run::<R, f32>(
+            Default::default(),
+            matmul::Strategy::SpecializedDoubleBuffered,
+        );
         run::<R, f32>(Default::default(), matmul::Strategy::Pipelined);
     }
 
@@ -145,12 +241,26 @@ fn main() {
 
         run::<cubecl::cuda::CudaRuntime, f16>(Default::default(), matmul::Strategy::Standard);
         run::<cubecl::cuda::CudaRuntime, f16>(Default::default(), matmul::Strategy::Specialized);
+        run::<cubecl::cuda::CudaRuntime, f16>(
+            Default::default(),
+            matmul::Strategy::SpecializedDoubleBuffered,
+        );
         run::<cubecl::cuda::CudaRuntime, f16>(Default::default(), matmul::Strategy::Pipelined);
+        run_prefetching_large_k::<cubecl::cuda::CudaRuntime, f16>(Default::default());
+        run_swizzled_large_n::<cubecl::cuda::CudaRuntime, f16>(Default::default());
         run::<cubecl::cuda::CudaRuntime, flex32>(Default::default(), matmul::Strategy::Standard);
         run::<cubecl::cuda::CudaRuntime, flex32>(Default::default(), matmul::Strategy::Specialized);
+        run::<cubecl::cuda::CudaRuntime, flex32>(
+            Default::default(),
+            matmul::Strategy::SpecializedDoubleBuffered,
+        );
         run::<cubecl::cuda::CudaRuntime, flex32>(Default::default(), matmul::Strategy::Pipelined);
         run::<cubecl::cuda::CudaRuntime, f32>(Default::default(), matmul::Strategy::Standard);
         run::<cubecl::cuda::CudaRuntime, f32>(Default::default(), matmul::Strategy::Specialized);
+        run::<cubecl::cuda::CudaRuntime, f32>(
+            Default::default(),
+            matmul::Strategy::SpecializedDoubleBuffered,
+        );
         run::<cubecl::cuda::CudaRuntime, f32>(Default::default(), matmul::Strategy::Pipelined);
     }
 }
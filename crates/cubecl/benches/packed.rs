@@ -0,0 +1,101 @@
+use cubecl::{calculate_cube_count_elemwise, frontend, prelude::*};
+use cubecl_runtime::TimestampsResult;
+use std::marker::PhantomData;
+
+#[cfg(feature = "cuda")]
+use half::{bf16, f16};
+
+use cubecl::benchmark::{Benchmark, TimingMethod};
+use cubecl::future;
+use cubecl_linalg::tensor::TensorHandle;
+
+/// A plain copy, so the kernel is bound by the memory bandwidth.
+#[cube(launch)]
+fn copy<F: Float>(input: &Tensor<Line<F>>, output: &mut Tensor<Line<F>>) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = input[ABSOLUTE_POS];
+    }
+}
+
+impl<R: Runtime, E: Float> Benchmark for PackedBench<R, E> {
+    type Args = (TensorHandle<R, E>, TensorHandle<R, E>);
+
+    fn prepare(&self) -> Self::Args {
+        let client = R::client(&self.device);
+        let input = TensorHandle::zeros(&client, self.shape.clone());
+        let output = TensorHandle::zeros(&client, self.shape.clone());
+
+        (input, output)
+    }
+
+    fn execute(&self, (input, output): Self::Args) {
+        let num_elems: usize = output.shape.iter().product();
+
+        let cube_dim = CubeDim::new(16, 16, 1);
+        let cube_count =
+            calculate_cube_count_elemwise(num_elems / self.line_size as usize, cube_dim);
+
+        copy::launch::<E, R>(
+            &self.client,
+            cube_count,
+            cube_dim,
+            input.as_arg(self.line_size),
+            output.as_arg(self.line_size),
+        )
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "packed-copy-{}-{}-{:?}",
+            R::name(),
+            E::as_elem_native_unchecked(),
+            self.line_size
+        )
+        .to_lowercase()
+    }
+
+    fn sync(&self) {
+        future::block_on(self.client.sync())
+    }
+
+    fn sync_elapsed(&self) -> TimestampsResult {
+        future::block_on(self.client.sync_elapsed())
+    }
+}
+
+#[allow(dead_code)]
+struct PackedBench<R: Runtime, E> {
+    shape: Vec<usize>,
+    line_size: u8,
+    device: R::Device,
+    client: ComputeClient<R::Server, R::Channel>,
+    _e: PhantomData<E>,
+}
+
+/// Compares loading half precision elements one by one with loading them in packed pairs.
+#[allow(dead_code)]
+fn run<R: Runtime, E: frontend::Float>(device: R::Device, line_size: u8) {
+    let client = R::client(&device);
+    client.enable_timestamps();
+
+    let bench = PackedBench::<R, E> {
+        shape: vec![32, 512, 2048],
+        line_size,
+        client,
+        device,
+        _e: PhantomData,
+    };
+    println!("{}", bench.name());
+    println!("{}", bench.run(TimingMethod::DeviceOnly));
+}
+
+fn main() {
+    #[cfg(feature = "cuda")]
+    run::<cubecl::cuda::CudaRuntime, f16>(Default::default(), 1);
+    #[cfg(feature = "cuda")]
+    run::<cubecl::cuda::CudaRuntime, f16>(Default::default(), 2);
+    #[cfg(feature = "cuda")]
+    run::<cubecl::cuda::CudaRuntime, bf16>(Default::default(), 1);
+    #[cfg(feature = "cuda")]
+    run::<cubecl::cuda::CudaRuntime, bf16>(Default::default(), 2);
+}
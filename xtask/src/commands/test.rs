@@ -23,5 +23,21 @@ pub(crate) fn handle_command(mut args: CubeCLTestCmdArgs) -> anyhow::Result<()>
         None,
         "std with exclusive_memory_only",
     )?;
+    // cubecl-wgpu with overflow_checks
+    helpers::custom_crates_tests(
+        vec!["cubecl-wgpu"],
+        vec!["--features", "overflow_checks", "--lib"],
+        None,
+        None,
+        "std with overflow_checks",
+    )?;
+    // cubecl-cpp with pipeline_profiling
+    helpers::custom_crates_tests(
+        vec!["cubecl-cpp"],
+        vec!["--features", "cuda,pipeline_profiling", "--lib"],
+        None,
+        None,
+        "std with pipeline_profiling",
+    )?;
     Ok(())
 }